@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Result, bail};
+use caldir_core::rpc::AccountStatus;
+use caldir_core::{Caldir, Calendar, ProviderSlug};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+
+use crate::render::diff::Render;
+use crate::utils::tui;
+
+/// A provider account and the calendars synced through it.
+pub(crate) struct Account {
+    pub provider: ProviderSlug,
+    /// `None` for providers without accounts (e.g. webcal)
+    pub identifier: Option<String>,
+    pub calendars: Vec<Calendar>,
+}
+
+/// Connected calendars grouped by provider and account.
+pub(crate) fn connected_accounts(caldir: &Caldir) -> Vec<Account> {
+    let mut accounts: BTreeMap<(String, Option<String>), Vec<Calendar>> = BTreeMap::new();
+
+    for calendar in caldir.calendars().into_iter().filter_map(Result::ok) {
+        let Some(remote) = calendar.remote_config() else {
+            continue;
+        };
+        let key = (
+            remote.provider_slug().to_string(),
+            remote.account_identifier().map(String::from),
+        );
+        accounts.entry(key).or_default().push(calendar);
+    }
+
+    accounts
+        .into_iter()
+        .map(|((provider, identifier), calendars)| Account {
+            provider: ProviderSlug::from(provider),
+            identifier,
+            calendars,
+        })
+        .collect()
+}
+
+/// The connected account of `provider` named `account`, which may be left
+/// out when there's only one.
+pub(crate) fn pick_account(
+    accounts: Vec<Account>,
+    provider: &ProviderSlug,
+    account: Option<String>,
+) -> Result<Account> {
+    let mut candidates: Vec<Account> = accounts
+        .into_iter()
+        .filter(|a| &a.provider == provider)
+        .filter(|a| account.is_none() || a.identifier == account)
+        .collect();
+
+    match (candidates.len(), &account) {
+        (1, _) => Ok(candidates.remove(0)),
+        (0, Some(account)) => bail!("No calendars are connected to {provider} as {account}"),
+        (0, None) => bail!("No calendars are connected to {provider}"),
+        _ => {
+            let names: Vec<String> = candidates
+                .iter()
+                .filter_map(|a| a.identifier.clone())
+                .map(|id| format!("  {id}"))
+                .collect();
+            bail!(
+                "Several {provider} accounts are connected. Pass one of:\n\n{}",
+                names.join("\n")
+            )
+        }
+    }
+}
+
+/// Lists connected accounts per provider with whether they're still signed in.
+pub async fn run(caldir: &Caldir) -> Result<()> {
+    let accounts = connected_accounts(caldir);
+
+    if accounts.is_empty() {
+        println!("No accounts connected. Run `caldir connect <provider>` to add one.");
+        return Ok(());
+    }
+
+    for (i, account) in accounts.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let name = account.identifier.as_deref().unwrap_or("(no account)");
+        println!("{} {}", account.provider.bold(), name);
+
+        if let Some(identifier) = &account.identifier {
+            let status = match caldir.provider(&account.provider) {
+                Ok(provider) => {
+                    let spinner = tui::create_spinner(name.to_string());
+                    let result = provider.provider_account(identifier.clone()).check().await;
+                    spinner.finish_and_clear();
+
+                    match result {
+                        Ok(status) => status_line(&status, Utc::now()),
+                        // Providers built before account checks
+                        Err(_) => "status unknown".dimmed().to_string(),
+                    }
+                }
+                Err(e) => e.to_string().red().to_string(),
+            };
+            println!("   {status}");
+        }
+
+        for calendar in &account.calendars {
+            println!("   {}", calendar.render(caldir));
+        }
+    }
+
+    Ok(())
+}
+
+fn status_line(status: &AccountStatus, now: DateTime<Utc>) -> String {
+    if !status.signed_in {
+        return "✗ Signed out, run `caldir connect` to sign in again"
+            .red()
+            .to_string();
+    }
+
+    match status.expires_at {
+        Some(_) if status.refreshable => format!(
+            "{} {}",
+            "✓ Signed in".green(),
+            "(renewed automatically)".dimmed()
+        ),
+        Some(expires_at) if expires_at <= now => {
+            "✗ Sign-in expired, run `caldir connect` to sign in again"
+                .red()
+                .to_string()
+        }
+        Some(expires_at) => format!(
+            "{} {}",
+            "✓ Signed in".green(),
+            format!("(expires {})", expires_at.format("%Y-%m-%d %H:%M UTC")).dimmed()
+        ),
+        None => "✓ Signed in".green().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn status_line_flags_expired_sessions_that_cant_renew() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+        let expired = AccountStatus {
+            signed_in: true,
+            expires_at: Some(now - chrono::Duration::hours(1)),
+            refreshable: false,
+        };
+
+        assert!(status_line(&expired, now).contains("expired"));
+        assert!(
+            status_line(
+                &AccountStatus {
+                    refreshable: true,
+                    ..expired
+                },
+                now
+            )
+            .contains("renewed automatically")
+        );
+        assert!(status_line(&AccountStatus::default(), now).contains("Signed out"));
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use caldir_core::{Caldir, Calendar, Connection, ProviderSlug};
+use owo_colors::OwoColorize;
+
+use crate::commands::{accounts, connect};
+
+use crate::render::diff::Render;
+use crate::render::time::{Locale, format_timestamp};
+use crate::utils::{PathExt, require_calendars, resolve_sync_range};
+
+/// Lists every calendar with its provider, account, event count and sync
+/// health. Reads only local files and sync state, so it works offline.
+pub fn run(caldir: &Caldir) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let range = resolve_sync_range(None, None)?;
+    let locale = Locale::from_config(caldir.config());
+
+    let connections: HashMap<String, Connection> = caldir
+        .connections()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|c| Some((c.local().slug()?.to_string(), c)))
+        .collect();
+
+    let calendars = caldir.calendars();
+    let total = calendars.len();
+
+    for (i, calendar) in calendars.into_iter().enumerate() {
+        match calendar {
+            Ok(calendar) => {
+                let connection = calendar.slug().and_then(|slug| connections.get(slug));
+                let pending = connection.map(|c| {
+                    c.offline_diff(&range)
+                        .map(|diff| diff.outgoing().len())
+                        .map_err(anyhow::Error::from)
+                });
+                print_calendar(caldir, &calendar, pending, locale)?;
+            }
+            Err(e) => println!("{}", e.to_string().red()),
+        }
+
+        if i < total - 1 {
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists the remote calendars of an already connected account again, to
+/// pick ones that weren't added when connecting (e.g. shared calendars).
+pub async fn add(caldir: &mut Caldir, provider: String, account: Option<String>) -> Result<()> {
+    let provider_slug = ProviderSlug::from(provider);
+    let account = accounts::pick_account(
+        accounts::connected_accounts(caldir),
+        &provider_slug,
+        account,
+    )?;
+    let identifier = account.identifier.with_context(|| {
+        format!("{provider_slug} calendars aren't tied to an account; add more with `caldir connect {provider_slug}`")
+    })?;
+
+    println!("Fetching calendars for {identifier}...");
+    let calendar_configs = caldir
+        .provider(&provider_slug)?
+        .provider_account(identifier)
+        .list_calendars()
+        .await?;
+
+    connect::import_calendars(caldir, &provider_slug, calendar_configs, false).await
+}
+
+pub fn rename(caldir: &mut Caldir, slug: String, new_slug: String) -> Result<()> {
+    let calendar = caldir.rename_calendar(&slug, &new_slug)?;
+
+    println!("{}", format!("  Renamed {slug} to {new_slug}").green());
+    println!("{}", format!("  {}", calendar.path().tilde()).dimmed());
+
+    Ok(())
+}
+
+/// Stops syncing and listing the calendar, keeping its files.
+pub fn archive(caldir: &Caldir, slug: String, archived: bool) -> Result<()> {
+    let mut calendar = caldir.calendar(&slug)?;
+    calendar.set_archived(archived)?;
+
+    if archived {
+        println!("{}", format!("  Archived {slug}").green());
+        println!(
+            "{}",
+            format!("  It's left out of syncing and event listings. Undo with `caldir calendars unarchive {slug}`.")
+                .dimmed()
+        );
+    } else {
+        println!("{}", format!("  Unarchived {slug}").green());
+    }
+
+    Ok(())
+}
+
+fn print_calendar(
+    caldir: &Caldir,
+    calendar: &Calendar,
+    pending: Option<Result<usize>>,
+    locale: Locale,
+) -> Result<()> {
+    let header = if calendar.is_archived() {
+        format!("{} {}", calendar.render(caldir), "(archived)".dimmed())
+    } else if calendar.is_read_only() {
+        format!("{} {}", calendar.render(caldir), "(read-only)".dimmed())
+    } else {
+        calendar.render(caldir)
+    };
+    println!("{header}");
+
+    let source = match calendar.remote_config() {
+        Some(remote) => match remote.account_identifier() {
+            Some(account) => format!("{} · {account}", remote.provider_slug()),
+            None => remote.provider_slug().to_string(),
+        },
+        None => "local".to_string(),
+    };
+    println!("   {source}");
+
+    let events = calendar.events()?.len();
+    let mut details = vec![format!(
+        "{events} {}",
+        if events == 1 { "event" } else { "events" }
+    )];
+
+    if calendar.has_remote() && !calendar.is_archived() {
+        details.push(match calendar.last_synced() {
+            Some(at) => format!("synced {}", format_timestamp(at, locale)),
+            None => "never synced".yellow().to_string(),
+        });
+
+        details.push(match pending {
+            Some(Ok(changes)) => pending_label(changes),
+            Some(Err(e)) => e.to_string().red().to_string(),
+            // The provider isn't installed
+            None => "provider not found".red().to_string(),
+        });
+    }
+
+    println!("   {}", details.join(" · "));
+
+    Ok(())
+}
+
+fn pending_label(changes: usize) -> String {
+    match changes {
+        0 => "nothing to push".green().to_string(),
+        1 => "1 change to push".yellow().to_string(),
+        n => format!("{n} changes to push").yellow().to_string(),
+    }
+}
@@ -1,16 +1,69 @@
-use anyhow::Result;
-use caldir_core::{Caldir, CaldirConfig};
+use anyhow::{Context, Result};
+use caldir_core::{Caldir, CaldirConfig, CalendarConfig};
 use std::{io::Write, path::Path};
 
+/// Keys of the form `calendars.<slug>.<key>` address a calendar's own config.
+const CALENDAR_KEY_PREFIX: &str = "calendars.";
+
 pub fn run(caldir: &Caldir) -> Result<()> {
     let mut out = std::io::stdout().lock();
 
     let config = caldir.config();
-    let config_path = CaldirConfig::default_system_config_path()?;
+    let config_path = match caldir.config_path() {
+        Some(path) => path.to_path_buf(),
+        None => CaldirConfig::default_system_config_path()?,
+    };
 
     render(&mut out, &config_path, config)
 }
 
+pub fn get(caldir: &Caldir, key: String) -> Result<()> {
+    let value = match split_calendar_key(&key) {
+        Some((slug, calendar_key)) => {
+            let calendar = caldir.calendar(slug)?;
+            let config = calendar.config().cloned().unwrap_or_default();
+            config.get(calendar_key)?
+        }
+        None => caldir.config().get(&key)?,
+    };
+
+    let value = value.with_context(|| format!("{key} is not set"))?;
+
+    println!("{}", format_value(&value));
+
+    Ok(())
+}
+
+pub fn set(caldir: &mut Caldir, key: String, value: String) -> Result<()> {
+    match split_calendar_key(&key) {
+        Some((slug, calendar_key)) => {
+            let calendar = caldir.calendar(slug)?;
+            let mut config = calendar.config().cloned().unwrap_or_default();
+            config.set(calendar_key, &value)?;
+            CalendarConfig::write(&config, &calendar.config_path())?;
+        }
+        None => {
+            let mut config = caldir.config().clone();
+            config.set(&key, &value)?;
+            caldir.save_config(config)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn split_calendar_key(key: &str) -> Option<(&str, &str)> {
+    key.strip_prefix(CALENDAR_KEY_PREFIX)?.split_once('.')
+}
+
+/// Strings print bare so `caldir config get` composes in shell scripts.
+fn format_value(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
 fn render(out: &mut impl Write, config_path: &Path, config: &CaldirConfig) -> Result<()> {
     writeln!(out, "Path: {}", config_path.display())?;
     writeln!(out)?;
@@ -48,10 +101,33 @@ mod tests {
 
             calendar_dir = "/tmp/calendars"
             time_format = "24h"
+            week_start = "monday"
+            date_order = "mdy"
             default_calendar = "my_calendar"
             default_reminders = ["30m", "2h"]
+            token_storage = "file"
+            max_attempts = 4
+            pull_delete_threshold = 10
         "#};
 
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn split_calendar_key_separates_slug_from_key() {
+        assert_eq!(
+            split_calendar_key("calendars.work.remote.google_calendar_id"),
+            Some(("work", "remote.google_calendar_id"))
+        );
+        assert_eq!(split_calendar_key("default_calendar"), None);
+    }
+
+    #[test]
+    fn format_value_prints_strings_bare() {
+        assert_eq!(
+            format_value(&toml::Value::String("work".to_string())),
+            "work"
+        );
+        assert_eq!(format_value(&toml::Value::Boolean(true)), "true");
+    }
 }
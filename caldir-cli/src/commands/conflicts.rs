@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use caldir_core::{Caldir, QueryMatch};
+use chrono::Utc;
+use owo_colors::OwoColorize;
+
+use crate::commands::events::resolve_range;
+use crate::render::event::format_event_line;
+use crate::render::time::{Locale, date_in, format_date_label_in};
+use crate::utils::{display_timezone, require_calendars};
+
+/// Lists busy events that overlap, across all calendars.
+pub fn run(caldir: &Caldir, from: Option<String>, to: Option<String>) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let tz = display_timezone(caldir, None)?;
+    let (from, to) = resolve_range(
+        Utc::now().with_timezone(&tz),
+        from.as_deref(),
+        to.as_deref(),
+    )?;
+
+    let conflicts = caldir.conflicts(from, to)?;
+
+    if conflicts.is_empty() {
+        println!("{}", "No conflicts found".dimmed());
+        return Ok(());
+    }
+
+    let colors: HashMap<String, String> = caldir
+        .calendars()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|cal| Some((cal.slug()?.to_string(), cal.color()?.to_string())))
+        .collect();
+
+    let locale = Locale::from_config(caldir.config());
+    let line = |m: &QueryMatch| {
+        let color = colors.get(&m.calendar).map(String::as_str);
+        format_event_line(&m.event, &m.calendar, color, "", caldir, tz)
+    };
+
+    let mut current_date = None;
+
+    // One block per overlapping pair
+    for conflict in &conflicts {
+        if current_date.is_some() {
+            println!();
+        }
+
+        let date = date_in(&conflict.first.event.start, locale, &tz);
+        if current_date != Some(date) {
+            println!("{}", format_date_label_in(date, locale, &tz).bold());
+            current_date = Some(date);
+        }
+
+        println!("{}", line(&conflict.first));
+        println!("{}", line(&conflict.second));
+    }
+
+    Ok(())
+}
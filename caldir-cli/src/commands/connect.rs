@@ -21,12 +21,17 @@ fn calendar_base_slug(name: Option<&str>, provider_slug: &ProviderSlug) -> Strin
     Calendar::base_slug_for(name.or(Some(provider_slug.as_str())))
 }
 
-pub async fn run(caldir: &mut Caldir, provider: Option<String>, hosted: bool) -> Result<()> {
+pub async fn run(
+    caldir: &mut Caldir,
+    provider: Option<String>,
+    hosted: bool,
+    paste_redirect: bool,
+) -> Result<()> {
     let provider_slug = provider.context(missing_provider_message(caldir))?;
 
     let provider_slug = ProviderSlug::from(provider_slug);
 
-    run_parsed(caldir, provider_slug, hosted).await
+    run_parsed(caldir, provider_slug, hosted, paste_redirect).await
 }
 
 fn missing_provider_message(caldir: &Caldir) -> String {
@@ -53,7 +58,12 @@ fn missing_provider_message(caldir: &Caldir) -> String {
     )
 }
 
-async fn run_parsed(caldir: &mut Caldir, provider_slug: ProviderSlug, hosted: bool) -> Result<()> {
+async fn run_parsed(
+    caldir: &mut Caldir,
+    provider_slug: ProviderSlug,
+    hosted: bool,
+    paste_redirect: bool,
+) -> Result<()> {
     let provider = caldir.provider(&provider_slug)?;
 
     // Bind to port 0 so the OS picks a free port
@@ -84,7 +94,8 @@ async fn run_parsed(caldir: &mut Caldir, provider_slug: ProviderSlug, hosted: bo
                 step,
                 data: step_data,
             } => {
-                data = run_connect_step(step, step_data, &listener, &redirect_uri).await?;
+                data = run_connect_step(step, step_data, &listener, &redirect_uri, paste_redirect)
+                    .await?;
             }
         }
     };
@@ -106,6 +117,17 @@ async fn run_parsed(caldir: &mut Caldir, provider_slug: ProviderSlug, hosted: bo
         provider.provider_account(id).list_calendars().await?
     };
 
+    import_calendars(caldir, &provider_slug, calendar_configs, true).await
+}
+
+/// Asks which of an account's `calendar_configs` to sync, skipping those
+/// already connected, then creates and pulls the picked ones.
+pub(crate) async fn import_calendars(
+    caldir: &mut Caldir,
+    provider_slug: &ProviderSlug,
+    calendar_configs: Vec<CalendarConfig>,
+    select_all: bool,
+) -> Result<()> {
     if calendar_configs.is_empty() {
         println!("No calendars found.");
         return Ok(());
@@ -164,8 +186,7 @@ async fn run_parsed(caldir: &mut Caldir, provider_slug: ProviderSlug, hosted: bo
         })
         .collect();
 
-    // Show multi-select (all selected by default)
-    let defaults: Vec<bool> = vec![true; items.len()];
+    let defaults: Vec<bool> = vec![select_all; items.len()];
     let selections = MultiSelect::new()
         .with_prompt("Select calendars to import (space to toggle, enter to confirm)")
         .items(&items)
@@ -184,7 +205,7 @@ async fn run_parsed(caldir: &mut Caldir, provider_slug: ProviderSlug, hosted: bo
 
     for &idx in &selections {
         let config = &calendar_configs[idx];
-        let desired_slug = calendar_base_slug(config.name(), &provider_slug);
+        let desired_slug = calendar_base_slug(config.name(), provider_slug);
         let calendar = caldir.create_calendar(&desired_slug, Some(config.clone()))?;
 
         if let Some(slug) = calendar.slug() {
@@ -216,7 +237,7 @@ async fn run_parsed(caldir: &mut Caldir, provider_slug: ProviderSlug, hosted: bo
 
     if !created_slugs.is_empty() {
         println!("Pulling events...\n");
-        super::pull::run(caldir, created_slugs, None, None, false).await?;
+        super::pull::run(caldir, created_slugs, None, None, false, false, false, None).await?;
     }
 
     Ok(())
@@ -229,6 +250,7 @@ async fn run_connect_step(
     step_data: serde_json::Value,
     listener: &TcpListener,
     redirect_uri: &str,
+    paste_redirect: bool,
 ) -> Result<serde_json::Map<String, serde_json::Value>> {
     match step {
         ConnectStepKind::NeedsSetup => {
@@ -256,14 +278,7 @@ async fn run_connect_step(
             let oauth: OAuthData = serde_json::from_value(step_data)
                 .context("Failed to parse OAuth data from provider")?;
 
-            println!("Open this URL in your browser to authenticate:\n");
-            println!("{}\n", oauth.authorization_url);
-
-            if open::that(&oauth.authorization_url).is_err() {
-                println!("(Could not open browser automatically, please copy the URL above)");
-            }
-
-            let params = wait_for_callback(listener).await?;
+            let params = authenticate(&oauth.authorization_url, listener, paste_redirect).await?;
 
             let code = params
                 .get("code")
@@ -288,14 +303,7 @@ async fn run_connect_step(
             let hosted_data: HostedOAuthData = serde_json::from_value(step_data)
                 .context("Failed to parse hosted OAuth data from provider")?;
 
-            println!("Open this URL in your browser to authenticate:\n");
-            println!("{}\n", hosted_data.url);
-
-            if open::that(&hosted_data.url).is_err() {
-                println!("(Could not open browser automatically, please copy the URL above)");
-            }
-
-            let params = wait_for_callback(listener).await?;
+            let params = authenticate(&hosted_data.url, listener, paste_redirect).await?;
 
             let access_token = params
                 .get("access_token")
@@ -337,6 +345,41 @@ async fn run_connect_step(
     }
 }
 
+/// Send the user to `url` to sign in and return the query parameters of the
+/// OAuth callback. With `paste_redirect`, the sign-in happens in a browser on another
+/// machine, whose redirect to localhost fails; the user pastes that URL instead.
+async fn authenticate(
+    url: &str,
+    listener: &TcpListener,
+    paste_redirect: bool,
+) -> Result<HashMap<String, String>> {
+    if paste_redirect {
+        println!("Open this URL in a browser on any device to authenticate:\n");
+        println!("{}\n", url);
+        println!("Once you've approved access, the browser is sent to a localhost page that");
+        println!("won't load. Copy that page's URL from the address bar and paste it here.\n");
+
+        return pasted_callback_params(&prompt_text("URL")?);
+    }
+
+    println!("Open this URL in your browser to authenticate:\n");
+    println!("{}\n", url);
+
+    if open::that(url).is_err() {
+        println!("(Could not open browser automatically, please copy the URL above)");
+    }
+
+    wait_for_callback(listener).await
+}
+
+/// The query parameters of a callback URL pasted from the browser.
+fn pasted_callback_params(pasted: &str) -> Result<HashMap<String, String>> {
+    let url = url::Url::parse(pasted.trim())
+        .context("Expected the whole URL from the address bar, starting with http://localhost")?;
+
+    Ok(url.query_pairs().into_owned().collect())
+}
+
 /// Wait for an HTTP callback on a pre-bound listener and return all query parameters.
 async fn wait_for_callback(listener: &TcpListener) -> Result<HashMap<String, String>> {
     let (stream, _) = listener
@@ -418,4 +461,16 @@ mod tests {
             "personal-calendar"
         );
     }
+
+    #[test]
+    fn reads_the_code_from_a_pasted_callback_url() {
+        let params = pasted_callback_params(
+            "  http://localhost:49152/callback?state=abc&code=4%2F0Ad&scope=calendar\n",
+        )
+        .unwrap();
+
+        assert_eq!(params["code"], "4/0Ad");
+        assert_eq!(params["state"], "abc");
+        assert!(pasted_callback_params("4/0Ad").is_err());
+    }
 }
@@ -0,0 +1,157 @@
+use std::io::IsTerminal;
+
+use anyhow::{Result, bail};
+use caldir_core::{Caldir, Calendar, ProviderSlug};
+use dialoguer::Confirm;
+use owo_colors::OwoColorize;
+
+use crate::commands::accounts;
+use crate::render::diff::Render;
+use crate::utils::PathExt;
+
+/// Signs out of a provider account and stops syncing its calendars. With
+/// `remove`, the calendars' directories are deleted too.
+pub async fn run(
+    caldir: &Caldir,
+    provider: String,
+    account: Option<String>,
+    remove: bool,
+    force: bool,
+) -> Result<()> {
+    let provider = ProviderSlug::from(provider);
+    let account = accounts::pick_account(accounts::connected_accounts(caldir), &provider, account)?;
+
+    if remove && !force {
+        println!("This deletes these calendars and their events:");
+        for calendar in &account.calendars {
+            println!("   {} {}", calendar.render(caldir), calendar.path().tilde());
+        }
+
+        if !std::io::stdin().is_terminal() {
+            bail!("Not removing. If you're sure, re-run with --force.");
+        }
+
+        let confirmed = Confirm::new()
+            .with_prompt("Remove them?")
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            return Ok(());
+        }
+    }
+
+    if let Some(identifier) = &account.identifier {
+        // Still unlink the calendars when the provider can't be reached
+        let signed_out = match caldir.provider(&provider) {
+            Ok(provider) => provider
+                .provider_account(identifier.clone())
+                .disconnect()
+                .await
+                .map_err(anyhow::Error::from),
+            Err(e) => Err(e.into()),
+        };
+
+        match signed_out {
+            Ok(()) => println!("{}", format!("  Signed out of {identifier}").green()),
+            Err(e) => println!(
+                "{}",
+                format!("  Couldn't sign out of {identifier}: {e}").yellow()
+            ),
+        }
+    }
+
+    let count = account.calendars.len();
+    unlink(account.calendars, remove)?;
+
+    let verb = if remove { "Removed" } else { "Disconnected" };
+    println!(
+        "{}",
+        format!(
+            "  {verb} {count} {}",
+            if count == 1 { "calendar" } else { "calendars" }
+        )
+        .green()
+    );
+    if !remove {
+        println!(
+            "{}",
+            "  Their events stay as local calendars. Delete the directories to remove them."
+                .dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+fn unlink(calendars: Vec<Calendar>, remove: bool) -> Result<()> {
+    for mut calendar in calendars {
+        if remove {
+            std::fs::remove_dir_all(calendar.path())?;
+        } else {
+            calendar.disconnect()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_caldir;
+    use caldir_core::{CalendarConfig, RemoteConfig, RemoteConfigParams};
+
+    fn connect(caldir: &Caldir, slug: &str, account: &str) {
+        let mut params = RemoteConfigParams::new();
+        params.insert("google_account".to_string(), account.into());
+        let remote = RemoteConfig::new(ProviderSlug::from("google"), params);
+        let config = CalendarConfig::new(Some(slug.to_string()), None, None, Some(remote));
+        caldir.create_calendar(slug, Some(config)).unwrap();
+    }
+
+    #[test]
+    fn picks_the_only_account_or_asks_which() {
+        let (_tmp, caldir) = test_caldir();
+        let google = ProviderSlug::from("google");
+        connect(&caldir, "work", "me@work.com");
+
+        let account =
+            accounts::pick_account(accounts::connected_accounts(&caldir), &google, None).unwrap();
+        assert_eq!(account.identifier.as_deref(), Some("me@work.com"));
+
+        connect(&caldir, "home", "me@home.com");
+        let err = accounts::pick_account(accounts::connected_accounts(&caldir), &google, None)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("me@home.com"));
+        assert!(
+            accounts::pick_account(
+                accounts::connected_accounts(&caldir),
+                &ProviderSlug::from("outlook"),
+                None
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn unlinking_keeps_the_events_unless_removing() {
+        let (_tmp, caldir) = test_caldir();
+        connect(&caldir, "work", "me@work.com");
+        connect(&caldir, "home", "me@home.com");
+
+        let google = ProviderSlug::from("google");
+        let work = accounts::pick_account(
+            accounts::connected_accounts(&caldir),
+            &google,
+            Some("me@work.com".to_string()),
+        )
+        .unwrap();
+        unlink(work.calendars, false).unwrap();
+        assert!(!caldir.calendar("work").unwrap().has_remote());
+
+        let home =
+            accounts::pick_account(accounts::connected_accounts(&caldir), &google, None).unwrap();
+        unlink(home.calendars, true).unwrap();
+        assert!(caldir.calendar("home").is_err());
+    }
+}
@@ -1,25 +1,59 @@
 mod warning;
 
 use crate::render::diff::Render;
-use crate::utils::require_calendars;
-use anyhow::Result;
-use caldir_core::{Caldir, Calendar};
+use crate::utils::{error_hint, require_calendars, tui};
+use anyhow::{Result, bail};
+use caldir_core::{Caldir, Calendar, CalendarEvent, DateRange, DuplicateRepair, ProviderSlug};
 use owo_colors::OwoColorize;
+use std::collections::HashSet;
 use std::io::{self, Write};
+use std::path::Path;
 use warning::{DoctorWarning, event_warnings};
 
-/// Checks local caldir for bad calendar data:
-pub fn run(caldir: &Caldir) -> Result<()> {
+/// Checks the caldir setup, its providers and sign-ins, and each calendar
+/// for bad data. `offline` skips the checks that start providers, and
+/// `fix_duplicates` repairs files holding the same event before checking.
+pub async fn run(caldir: &Caldir, offline: bool, fix_duplicates: Option<String>) -> Result<()> {
     require_calendars(caldir)?;
 
-    let reports = calendar_reports(caldir);
+    if let Some(how) = fix_duplicates {
+        repair_duplicates(caldir, parse_repair(&how)?)?;
+    }
+
+    let mut setup = setup_warnings(caldir);
+    let mut reports = calendar_reports(caldir, &mut setup);
+
+    if !offline {
+        let spinner = tui::create_spinner("Checking providers".to_string());
+        let working = provider_warnings(caldir, &mut setup).await;
+        sign_in_warnings(caldir, &working, &mut reports).await;
+        spinner.finish_and_clear();
+    }
+
     let mut out = io::stdout().lock();
 
-    render(&mut out, caldir, &reports)
+    render(&mut out, caldir, &setup, &reports)
 }
 
-fn render(out: &mut impl Write, caldir: &Caldir, reports: &[CalendarReport]) -> Result<()> {
-    let warning_count: usize = reports.iter().map(|report| report.warnings.len()).sum();
+fn render(
+    out: &mut impl Write,
+    caldir: &Caldir,
+    setup: &[DoctorWarning],
+    reports: &[CalendarReport],
+) -> Result<()> {
+    let warning_count = setup.len()
+        + reports
+            .iter()
+            .map(|report| report.warnings.len())
+            .sum::<usize>();
+
+    if !setup.is_empty() {
+        writeln!(out, "⚙ {}", "caldir".bold())?;
+        for warning in setup {
+            warning.render(out)?;
+        }
+        writeln!(out)?;
+    }
 
     for report in reports.iter().filter(|report| !report.warnings.is_empty()) {
         writeln!(out, "{}", report.calendar.render(caldir))?;
@@ -42,29 +76,181 @@ struct CalendarReport {
     warnings: Vec<DoctorWarning>,
 }
 
-fn calendar_reports(caldir: &Caldir) -> Vec<CalendarReport> {
-    caldir
+fn setup_warnings(caldir: &Caldir) -> Vec<DoctorWarning> {
+    let mut warnings = Vec::new();
+
+    if !is_writable(&caldir.data_dir()) {
+        warnings.push(DoctorWarning::NotWritable(caldir.data_dir()));
+    }
+
+    if let Some(slug) = caldir.config().default_calendar_slug()
+        && caldir.calendar(slug).is_err()
+    {
+        warnings.push(DoctorWarning::MissingDefaultCalendar(slug.to_string()));
+    }
+
+    warnings
+}
+
+/// `merge`, `keep-newest` or `new-uids`
+fn parse_repair(input: &str) -> Result<DuplicateRepair> {
+    match input {
+        "merge" => Ok(DuplicateRepair::Merge),
+        "keep-newest" => Ok(DuplicateRepair::KeepNewest),
+        "new-uids" => Ok(DuplicateRepair::NewUids),
+        other => bail!("Unknown --fix-duplicates: {other} (use merge, keep-newest or new-uids)"),
+    }
+}
+
+fn repair_duplicates(caldir: &Caldir, how: DuplicateRepair) -> Result<()> {
+    for calendar in caldir.calendars().into_iter().filter_map(Result::ok) {
+        let repaired = calendar.repair_duplicates(how)?;
+        if repaired > 0 {
+            println!(
+                "{} Repaired {repaired} duplicated event(s) in {}",
+                "✓".green(),
+                calendar.render(caldir)
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Reports for the calendars that load. The others are added to `setup`.
+fn calendar_reports(caldir: &Caldir, setup: &mut Vec<DoctorWarning>) -> Vec<CalendarReport> {
+    let mut reports = Vec::new();
+
+    for calendar in caldir.calendars() {
+        match calendar {
+            Ok(calendar) => reports.push(calendar_report(calendar)),
+            Err(err) => setup.push(DoctorWarning::InvalidCalendar(err.to_string())),
+        }
+    }
+
+    reports
+}
+
+fn calendar_report(calendar: Calendar) -> CalendarReport {
+    let mut warnings = Vec::new();
+
+    if !is_writable(calendar.path()) {
+        warnings.push(DoctorWarning::NotWritable(calendar.path().to_path_buf()));
+    }
+
+    match load_events(&calendar, &mut warnings) {
+        Ok(events) => warnings.extend(event_warnings(&events)),
+        Err(err) => warnings.push(DoctorWarning::UnreadableEvents(err.to_string())),
+    }
+
+    CalendarReport { calendar, warnings }
+}
+
+/// Loads the calendar's event files one by one, so a malformed file doesn't
+/// hide the rest.
+fn load_events(
+    calendar: &Calendar,
+    warnings: &mut Vec<DoctorWarning>,
+) -> Result<Vec<CalendarEvent>> {
+    let mut events = Vec::new();
+
+    for path in calendar.event_files()? {
+        match CalendarEvent::load_all(path.clone()) {
+            Ok(in_file) => events.extend(in_file),
+            Err(err) => warnings.push(DoctorWarning::MalformedFile {
+                path,
+                error: err.to_string(),
+            }),
+        }
+    }
+
+    Ok(events)
+}
+
+fn is_writable(dir: &Path) -> bool {
+    tempfile::tempfile_in(dir).is_ok()
+}
+
+/// Checks that the providers used by calendars are installed and respond.
+/// Returns the ones that do.
+async fn provider_warnings(caldir: &Caldir, setup: &mut Vec<DoctorWarning>) -> Vec<ProviderSlug> {
+    let mut slugs: Vec<ProviderSlug> = caldir
         .calendars()
         .into_iter()
         .filter_map(Result::ok)
-        .map(calendar_report)
-        .collect()
+        .filter_map(|calendar| Some(calendar.remote_config()?.provider_slug().clone()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    slugs.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+    let mut working = Vec::new();
+
+    for slug in slugs {
+        let Ok(provider) = caldir.provider(&slug) else {
+            setup.push(DoctorWarning::ProviderNotFound(slug.to_string()));
+            continue;
+        };
+
+        match provider.handshake().await {
+            Ok(_) => working.push(slug),
+            Err(err) => setup.push(DoctorWarning::ProviderNotResponding {
+                provider: slug.to_string(),
+                error: err.to_string(),
+            }),
+        }
+    }
+
+    working
 }
 
-fn calendar_report(calendar: Calendar) -> CalendarReport {
-    let warnings = match calendar.events() {
-        Ok(events) => event_warnings(&events),
-        Err(err) => vec![DoctorWarning::UnreadableEvents(err.to_string())],
+/// Signs in to each calendar whose provider works, by listing a one-minute
+/// window, to catch expired or revoked tokens.
+async fn sign_in_warnings(
+    caldir: &Caldir,
+    working: &[ProviderSlug],
+    reports: &mut [CalendarReport],
+) {
+    let now = chrono::Utc::now();
+    let range = DateRange {
+        from: Some(now),
+        to: Some(now + chrono::Duration::minutes(1)),
     };
 
-    CalendarReport { calendar, warnings }
+    for connection in caldir.connections().into_iter().filter_map(Result::ok) {
+        let uses_working_provider = connection
+            .local()
+            .remote_config()
+            .is_some_and(|config| working.contains(config.provider_slug()));
+        if !uses_working_provider {
+            continue;
+        }
+
+        let Err(err) = connection.remote().list_events(&range).await else {
+            continue;
+        };
+
+        let warning = DoctorWarning::SignInFailed {
+            hint: error_hint(&connection, err.code()),
+            error: err.to_string(),
+        };
+
+        if let Some(report) = reports
+            .iter_mut()
+            .find(|report| report.calendar.path() == connection.local().path())
+        {
+            report.warnings.push(warning);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::calendar_report;
     use super::warning::DoctorWarning;
-    use caldir_core::Calendar;
+    use caldir_core::{Calendar, Event, EventTime};
+    use chrono::NaiveDate;
 
     fn test_calendar() -> (tempfile::TempDir, Calendar) {
         let tmp = tempfile::tempdir().unwrap();
@@ -86,7 +272,35 @@ mod tests {
         assert_eq!(report.warnings.len(), 1);
         assert!(matches!(
             report.warnings[0],
-            DoctorWarning::UnreadableEvents(_)
+            DoctorWarning::MalformedFile { .. }
         ));
     }
+
+    #[test]
+    fn checks_the_rest_of_the_calendar_past_a_malformed_file() {
+        let (_tmp, calendar) = test_calendar();
+        std::fs::write(calendar.path().join("bad.ics"), "not an event").unwrap();
+
+        let event = Event::new(
+            "Standup",
+            EventTime::DateTimeFloating(
+                NaiveDate::from_ymd_opt(2026, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap(),
+            ),
+        );
+        calendar.create_event(event.clone()).unwrap();
+        calendar.create_event(event).unwrap();
+
+        let report = calendar_report(calendar);
+
+        assert_eq!(report.warnings.len(), 2);
+        assert!(
+            report
+                .warnings
+                .iter()
+                .any(|warning| matches!(warning, DoctorWarning::DuplicateFiles(_)))
+        );
+    }
 }
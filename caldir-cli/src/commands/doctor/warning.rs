@@ -1,16 +1,26 @@
 mod duplicate_file;
+mod orphaned_override;
 
 use anyhow::Result;
 use caldir_core::CalendarEvent;
 use duplicate_file::duplicate_file_warnings;
+use orphaned_override::orphaned_override_warnings;
 use owo_colors::OwoColorize;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub(crate) enum DoctorWarning {
     DuplicateFiles(Vec<PathBuf>),
     UnreadableEvents(String),
+    MalformedFile { path: PathBuf, error: String },
+    OrphanedOverride(PathBuf),
+    NotWritable(PathBuf),
+    InvalidCalendar(String),
+    MissingDefaultCalendar(String),
+    ProviderNotFound(String),
+    ProviderNotResponding { provider: String, error: String },
+    SignInFailed { error: String, hint: Option<String> },
 }
 
 impl DoctorWarning {
@@ -19,26 +29,102 @@ impl DoctorWarning {
             DoctorWarning::DuplicateFiles(paths) => {
                 writeln!(
                     out,
-                    "   {} same event saved as multiple files; delete all but one:",
+                    "   {} same event saved as multiple files:",
                     "⚠".yellow()
                 )?;
                 for path in paths {
-                    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                    writeln!(out, "       {}", name.dimmed())?;
+                    writeln!(out, "       {}", file_name(path).dimmed())?;
                 }
+                render_fix(
+                    out,
+                    "Sync skips the calendar until fixed. Delete all but one, or run \
+                     `caldir doctor --fix-duplicates merge` (or keep-newest, new-uids).",
+                )?;
             }
             DoctorWarning::UnreadableEvents(error) => {
                 writeln!(out, "   {}", error.red())?;
             }
+            DoctorWarning::MalformedFile { path, error } => {
+                writeln!(
+                    out,
+                    "   {} {} doesn't parse: {error}",
+                    "⚠".yellow(),
+                    file_name(path)
+                )?;
+                render_fix(out, "Fix or delete the file.")?;
+            }
+            DoctorWarning::OrphanedOverride(path) => {
+                writeln!(
+                    out,
+                    "   {} {} changes one occurrence of a recurring event that isn't here",
+                    "⚠".yellow(),
+                    file_name(path)
+                )?;
+                render_fix(
+                    out,
+                    "Run `caldir pull` to fetch the event, or delete the file.",
+                )?;
+            }
+            DoctorWarning::NotWritable(path) => {
+                writeln!(out, "   {} can't write to {}", "✗".red(), path.display())?;
+                render_fix(out, "Check the directory's owner and permissions.")?;
+            }
+            DoctorWarning::InvalidCalendar(error) => {
+                writeln!(out, "   {} {}", "✗".red(), error.red())?;
+                render_fix(out, "Fix the calendar's .caldir/config.toml, or remove it.")?;
+            }
+            DoctorWarning::MissingDefaultCalendar(slug) => {
+                writeln!(
+                    out,
+                    "   {} default_calendar is \"{slug}\", which doesn't exist",
+                    "⚠".yellow()
+                )?;
+                render_fix(out, "Run `caldir config set default_calendar <slug>`.")?;
+            }
+            DoctorWarning::ProviderNotFound(provider) => {
+                writeln!(
+                    out,
+                    "   {} caldir-provider-{provider} isn't on your PATH",
+                    "✗".red()
+                )?;
+                render_fix(out, &format!("Run `caldir providers install {provider}`."))?;
+            }
+            DoctorWarning::ProviderNotResponding { provider, error } => {
+                writeln!(
+                    out,
+                    "   {} caldir-provider-{provider} doesn't respond: {}",
+                    "✗".red(),
+                    error.red()
+                )?;
+                render_fix(
+                    out,
+                    &format!("Reinstall it with `caldir providers install {provider}`."),
+                )?;
+            }
+            DoctorWarning::SignInFailed { error, hint } => {
+                writeln!(out, "   {} can't sign in: {}", "✗".red(), error.red())?;
+                if let Some(hint) = hint {
+                    render_fix(out, hint)?;
+                }
+            }
         }
 
         Ok(())
     }
 }
 
+fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or("?")
+}
+
+fn render_fix(out: &mut impl Write, fix: &str) -> Result<()> {
+    writeln!(out, "     {}", fix.dimmed())?;
+    Ok(())
+}
+
 type EventCheck = fn(&[CalendarEvent]) -> Vec<DoctorWarning>;
 
-const EVENT_CHECKS: &[EventCheck] = &[duplicate_file_warnings];
+const EVENT_CHECKS: &[EventCheck] = &[duplicate_file_warnings, orphaned_override_warnings];
 
 pub(crate) fn event_warnings(events: &[CalendarEvent]) -> Vec<DoctorWarning> {
     EVENT_CHECKS
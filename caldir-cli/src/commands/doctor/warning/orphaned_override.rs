@@ -0,0 +1,84 @@
+use crate::commands::doctor::warning::DoctorWarning;
+use caldir_core::CalendarEvent;
+use std::collections::HashSet;
+
+pub(crate) fn orphaned_override_warnings(events: &[CalendarEvent]) -> Vec<DoctorWarning> {
+    let masters: HashSet<&str> = events
+        .iter()
+        .map(CalendarEvent::event)
+        .filter(|event| event.recurrence.is_some() && event.recurrence_id.is_none())
+        .map(|event| event.uid.as_str())
+        .collect();
+
+    let mut orphans: Vec<_> = events
+        .iter()
+        .filter(|ce| {
+            let event = ce.event();
+            event.recurrence_id.is_some() && !masters.contains(event.uid.as_str())
+        })
+        .map(|ce| ce.path().to_path_buf())
+        .collect();
+
+    orphans.sort();
+    orphans
+        .into_iter()
+        .map(DoctorWarning::OrphanedOverride)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::orphaned_override_warnings;
+    use caldir_core::{Calendar, Event, EventTime, Recurrence, RecurrenceId};
+    use chrono::NaiveDate;
+
+    fn test_calendar() -> (tempfile::TempDir, Calendar) {
+        let tmp = tempfile::tempdir().unwrap();
+        let calendar = Calendar::create(&tmp.path().join("work"), None).unwrap();
+        (tmp, calendar)
+    }
+
+    fn start() -> EventTime {
+        EventTime::DateTimeFloating(
+            NaiveDate::from_ymd_opt(2026, 1, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap(),
+        )
+    }
+
+    fn override_of(master: &Event) -> Event {
+        let mut event = master.clone();
+        event.recurrence = None;
+        event.recurrence_id = Some(RecurrenceId::from_event_time(start()));
+        event
+    }
+
+    fn master() -> Event {
+        let mut event = Event::new("Standup", start());
+        event.recurrence = Some(Recurrence::new("FREQ=WEEKLY"));
+        event
+    }
+
+    #[test]
+    fn warns_about_overrides_without_master() {
+        let (_tmp, calendar) = test_calendar();
+        calendar.create_event(override_of(&master())).unwrap();
+
+        let warnings = orphaned_override_warnings(&calendar.events().unwrap());
+
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn ignores_overrides_with_master() {
+        let (_tmp, calendar) = test_calendar();
+        let master = master();
+        calendar.create_event(master.clone()).unwrap();
+        calendar.create_event(override_of(&master)).unwrap();
+
+        let warnings = orphaned_override_warnings(&calendar.events().unwrap());
+
+        assert!(warnings.is_empty());
+    }
+}
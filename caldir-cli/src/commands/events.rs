@@ -4,20 +4,28 @@ use caldir_core::DateBounds;
 use chrono::{DateTime, Duration, TimeZone, Utc};
 
 use crate::render::events_in_range::render_events_in_range;
+use crate::render::template::Template;
 use crate::utils::parse_date;
-use crate::utils::{require_calendars, resolve_calendars};
+use crate::utils::{display_timezone, require_calendars, resolve_calendars};
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     caldir: &Caldir,
     calendar: Option<String>,
     from: Option<String>,
     to: Option<String>,
+    tags: Vec<String>,
+    hide_declined: bool,
+    tz: Option<String>,
+    format: Option<String>,
 ) -> Result<()> {
     require_calendars(caldir)?;
 
+    let template = format.as_deref().map(Template::parse).transpose()?;
+
     let calendars = resolve_calendars(caldir, calendar.as_deref())?;
 
-    let tz: chrono_tz::Tz = iana_time_zone::get_timezone()?.parse()?;
+    let tz = display_timezone(caldir, tz.as_deref())?;
 
     let (from, to) = resolve_range(
         Utc::now().with_timezone(&tz),
@@ -25,10 +33,19 @@ pub fn run(
         to.as_deref(),
     )?;
 
-    render_events_in_range(caldir, calendars, from, to)
+    render_events_in_range(
+        caldir,
+        calendars,
+        from,
+        to,
+        &tags,
+        hide_declined,
+        tz,
+        template.as_ref(),
+    )
 }
 
-fn resolve_range<Tz: TimeZone>(
+pub(crate) fn resolve_range<Tz: TimeZone>(
     now: DateTime<Tz>,
     from: Option<&str>,
     to: Option<&str>,
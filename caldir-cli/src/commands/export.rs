@@ -0,0 +1,200 @@
+use anyhow::{Result, bail};
+use caldir_core::{Caldir, Event, EventQuery, EventTime, FloatingTimezone};
+use chrono::Utc;
+
+use crate::commands::events::resolve_range;
+use crate::utils::{display_timezone, require_calendars, resolve_calendars};
+
+const COLUMNS: &[&str] = &[
+    "start",
+    "end",
+    "duration",
+    "summary",
+    "calendar",
+    "location",
+    "attendees",
+];
+
+/// Prints the events in the range as CSV or TSV, one row per occurrence,
+/// for spreadsheets.
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    caldir: &Caldir,
+    format: String,
+    columns: Option<String>,
+    calendar: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    tags: Vec<String>,
+    redact: bool,
+) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let delimiter = match format.as_str() {
+        "csv" => ',',
+        "tsv" => '\t',
+        other => bail!("Unknown --format: {other} (use csv or tsv)"),
+    };
+    let columns = parse_columns(columns.as_deref())?;
+
+    let calendars = resolve_calendars(caldir, calendar.as_deref())?;
+    let tz = display_timezone(caldir, None)?;
+    let floating = caldir.config().floating_timezone();
+    let (from, to) = resolve_range(
+        Utc::now().with_timezone(&tz),
+        from.as_deref(),
+        to.as_deref(),
+    )?;
+
+    let query = EventQuery::between(from, to).tags(&tags);
+    let mut rows: Vec<(Event, String)> = Vec::new();
+
+    for cal in &calendars {
+        let slug = cal.slug().unwrap_or_default().to_string();
+        rows.extend(
+            cal.query(&query)?
+                .into_iter()
+                .map(|event| if redact { event.redacted() } else { event })
+                .map(|event| (event, slug.clone())),
+        );
+    }
+
+    rows.sort_by_key(|(event, _)| event.start.to_utc());
+
+    println!("{}", format_row(&columns, delimiter));
+    for (event, slug) in &rows {
+        let values: Vec<String> = columns
+            .iter()
+            .map(|column| column_value(column, event, slug, &tz, floating))
+            .collect();
+        println!("{}", format_row(&values, delimiter));
+    }
+
+    Ok(())
+}
+
+/// `start,summary` → the columns to print, in that order. All of them by
+/// default.
+fn parse_columns(input: Option<&str>) -> Result<Vec<String>> {
+    let Some(input) = input else {
+        return Ok(COLUMNS.iter().map(|c| c.to_string()).collect());
+    };
+
+    let columns: Vec<String> = input
+        .split(',')
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+
+    if let Some(unknown) = columns.iter().find(|c| !COLUMNS.contains(&c.as_str())) {
+        bail!(
+            "Unknown column: {unknown} (available: {})",
+            COLUMNS.join(", ")
+        );
+    }
+
+    if columns.is_empty() {
+        bail!("--columns is empty");
+    }
+
+    Ok(columns)
+}
+
+fn column_value(
+    column: &str,
+    event: &Event,
+    calendar: &str,
+    tz: &chrono_tz::Tz,
+    floating: FloatingTimezone,
+) -> String {
+    match column {
+        "start" => format_time(&event.start, tz, floating),
+        "end" => event
+            .end
+            .as_ref()
+            .map(|end| format_time(end, tz, floating))
+            .unwrap_or_default(),
+        "duration" => event
+            .end
+            .as_ref()
+            .map(|end| {
+                (end.to_utc() - event.start.to_utc())
+                    .num_minutes()
+                    .to_string()
+            })
+            .unwrap_or_else(|| "0".to_string()),
+        "summary" => event.summary.clone().unwrap_or_default(),
+        "calendar" => calendar.to_string(),
+        "location" => event.location.clone().unwrap_or_default(),
+        "attendees" => event
+            .attendees
+            .iter()
+            .map(|attendee| attendee.email.as_str())
+            .collect::<Vec<_>>()
+            .join("; "),
+        _ => String::new(),
+    }
+}
+
+/// `2026-03-02 15:00` in `tz`, or just the date for all-day events, which
+/// spreadsheets read as dates.
+fn format_time(time: &EventTime, tz: &chrono_tz::Tz, floating: FloatingTimezone) -> String {
+    match time {
+        EventTime::Date(date) => date.format("%Y-%m-%d").to_string(),
+        _ => time
+            .to_local_tz_with(tz, floating)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+    }
+}
+
+/// CSV fields are quoted when they need to be (RFC 4180). TSV has no
+/// quoting, so tabs and line breaks inside fields become spaces.
+fn format_row(values: &[String], delimiter: char) -> String {
+    values
+        .iter()
+        .map(|value| {
+            if delimiter == '\t' {
+                value.replace(['\t', '\r', '\n'], " ")
+            } else if value.contains([delimiter, '"', '\r', '\n']) {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&delimiter.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn csv_quotes_fields_that_need_it() {
+        let row = format_row(&strings(&["plain", "a, b", "say \"hi\""]), ',');
+
+        assert_eq!(row, "plain,\"a, b\",\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn tsv_flattens_tabs_and_newlines() {
+        let row = format_row(&strings(&["a\tb", "line\nbreak"]), '\t');
+
+        assert_eq!(row, "a b\tline break");
+    }
+
+    #[test]
+    fn columns_default_to_all_and_reject_unknown_ones() {
+        assert_eq!(parse_columns(None).unwrap().len(), COLUMNS.len());
+        assert_eq!(
+            parse_columns(Some("Summary, start")).unwrap(),
+            ["summary", "start"]
+        );
+        assert!(parse_columns(Some("start,billable")).is_err());
+    }
+}
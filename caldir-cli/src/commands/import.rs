@@ -0,0 +1,126 @@
+use anyhow::{Context, Result, bail};
+use caldir_core::{Caldir, Calendar, Event, ImipOutcome, ImipPayload};
+use mail_parser::{MessageParser, MimeHeaders};
+use owo_colors::OwoColorize;
+
+use crate::render::diff::Render;
+use crate::utils::{require_calendars, resolve_calendars};
+
+/// Applies an emailed invitation, reply or cancellation (`.eml` or `.ics`)
+/// to the calendar that has the event, or that it's for.
+pub fn invite(caldir: &Caldir, file: String, calendar: Option<String>) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let contents = std::fs::read(&file).with_context(|| format!("Couldn't read {file}"))?;
+    let ics =
+        calendar_part(&contents).with_context(|| format!("No calendar attachment in {file}"))?;
+    let payload = ImipPayload::parse(&ics)?;
+
+    let calendar = match calendar {
+        Some(slug) => resolve_calendars(caldir, Some(&slug))?.remove(0),
+        None => target_calendar(caldir, &payload)?,
+    };
+
+    println!("{}", calendar.render(caldir));
+
+    for outcome in calendar.apply_imip(&payload)? {
+        println!("   {}", render_outcome(&outcome));
+    }
+
+    if calendar.has_remote() {
+        println!();
+        println!("{}", "Remember to run: caldir push".dimmed());
+    }
+
+    Ok(())
+}
+
+/// The `text/calendar` part of an email, or the file itself if it's ICS.
+fn calendar_part(contents: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(contents);
+    if text.trim_start().starts_with("BEGIN:VCALENDAR") {
+        return Some(text.into_owned());
+    }
+
+    let message = MessageParser::default().parse(contents)?;
+    message.parts.iter().find_map(|part| {
+        let content_type = part.content_type()?;
+        let is_calendar = matches!(
+            (content_type.ctype(), content_type.subtype()),
+            ("text", Some("calendar")) | ("application", Some("ics"))
+        );
+        is_calendar.then(|| String::from_utf8_lossy(part.contents()).into_owned())
+    })
+}
+
+/// The calendar that already has one of the events, else the one whose
+/// account is invited, else the default calendar.
+fn target_calendar(caldir: &Caldir, payload: &ImipPayload) -> Result<Calendar> {
+    let calendars = resolve_calendars(caldir, None)?;
+
+    for calendar in &calendars {
+        for event in &payload.events {
+            if calendar
+                .event_by_instance_id(&event.event_instance_id())?
+                .is_some()
+                || calendar.master_event_for(event.uid.as_str())?.is_some()
+            {
+                return Ok(Calendar::load(calendar.path())?);
+            }
+        }
+    }
+
+    let invited = calendars.into_iter().find(|calendar| {
+        calendar.remote_email().is_some_and(|email| {
+            payload
+                .events
+                .iter()
+                .any(|event| event.find_attendee(email).is_some())
+        })
+    });
+
+    match invited {
+        Some(calendar) => Ok(calendar),
+        None => match caldir.default_calendar() {
+            Ok(calendar) => Ok(calendar),
+            Err(_) => bail!("Couldn't tell which calendar this is for. Pass --calendar."),
+        },
+    }
+}
+
+fn render_outcome(outcome: &ImipOutcome) -> String {
+    let summary = |event: &Event| {
+        event
+            .summary
+            .clone()
+            .unwrap_or_else(|| "(Untitled)".to_string())
+    };
+
+    match outcome {
+        ImipOutcome::Created(event) => format!("{} {}", "+".green(), summary(event)),
+        ImipOutcome::Updated(event) => format!("{} {}", "~".yellow(), summary(event)),
+        ImipOutcome::Cancelled(event) => {
+            format!(
+                "{} {} {}",
+                "-".red(),
+                summary(event),
+                "(cancelled)".dimmed()
+            )
+        }
+        ImipOutcome::Replied {
+            event,
+            email,
+            status,
+        } => format!("{} {}: {email} {status}", "~".yellow(), summary(event)),
+        ImipOutcome::Outdated(event) => format!(
+            "{} {}",
+            summary(event),
+            "(skipped: calendar has a newer version)".dimmed()
+        ),
+        ImipOutcome::Unknown(event) => format!(
+            "{} {}",
+            summary(event),
+            "(skipped: event not in calendar)".dimmed()
+        ),
+    }
+}
@@ -1,17 +1,17 @@
 use anyhow::Result;
-use caldir_core::{Caldir, DateBounds, ParticipationStatus};
+use caldir_core::{Caldir, DateBounds, EventQuery, ParticipationStatus};
 use chrono::{Duration, Utc};
 use owo_colors::OwoColorize;
 
-use crate::render::event::{format_event_line, is_visible, render_participation_status};
-use crate::render::time::format_date_only;
-use crate::utils::{require_calendars, resolve_calendars};
+use crate::render::event::{format_event_line, render_participation_status};
+use crate::render::time::{Locale, date_in, format_date_label_in};
+use crate::utils::{display_timezone, require_calendars, resolve_calendars};
 
 pub fn run(caldir: &Caldir, calendar: Option<String>, all: bool) -> Result<()> {
     require_calendars(caldir)?;
     let calendars = resolve_calendars(caldir, calendar.as_deref())?;
 
-    let tz: chrono_tz::Tz = iana_time_zone::get_timezone()?.parse()?;
+    let tz = display_timezone(caldir, None)?;
     let today = Utc::now().with_timezone(&tz).date_naive();
 
     let from = today
@@ -28,20 +28,17 @@ pub fn run(caldir: &Caldir, calendar: Option<String>, all: bool) -> Result<()> {
         .unwrap()
         .with_timezone(&Utc);
 
-    let mut invites: Vec<(String, caldir_core::Event, String)> = Vec::new();
+    let mut invites: Vec<(String, Option<&str>, caldir_core::Event, String)> = Vec::new();
 
     for cal in &calendars {
         let Some(email) = cal.remote_email() else {
             continue;
         };
 
-        let events = cal.expanded_events_in_range(from, to)?;
+        let events = cal.query(&EventQuery::between(from, to).attendee(email))?;
         let cal_slug = cal.slug().unwrap_or("(Unknown calendar)").to_string();
 
         for event in events {
-            if !is_visible(&event) {
-                continue;
-            }
             let is_invite = event.is_invite_for(email);
             let matches = if all {
                 is_invite
@@ -49,22 +46,23 @@ pub fn run(caldir: &Caldir, calendar: Option<String>, all: bool) -> Result<()> {
                 is_invite && event.attendee_status(email) == Some(ParticipationStatus::NeedsAction)
             };
             if matches {
-                invites.push((cal_slug.clone(), event, email.to_string()));
+                invites.push((cal_slug.clone(), cal.color(), event, email.to_string()));
             }
         }
     }
 
-    invites.sort_by_key(|(_, event, _)| event.start.to_utc());
+    invites.sort_by_key(|(_, _, event, _)| event.start.to_utc());
 
     if invites.is_empty() {
         println!("{}", "No pending invites.".dimmed());
         return Ok(());
     }
 
+    let locale = Locale::from_config(caldir.config());
     let mut current_date: Option<String> = None;
 
-    for (cal_slug, event, email) in &invites {
-        let date_label = format_date_only(&event.start);
+    for (cal_slug, cal_color, event, email) in &invites {
+        let date_label = format_date_label_in(date_in(&event.start, locale, &tz), locale, &tz);
         if current_date.as_ref() != Some(&date_label) {
             if current_date.is_some() {
                 println!();
@@ -79,7 +77,7 @@ pub fn run(caldir: &Caldir, calendar: Option<String>, all: bool) -> Result<()> {
             .unwrap_or_default();
         println!(
             "{}",
-            format_event_line(event, cal_slug, &status_suffix, caldir)
+            format_event_line(event, cal_slug, *cal_color, &status_suffix, caldir, tz)
         );
 
         if let Some(organizer) = event.organizer.as_ref().filter(|o| !o.email.is_empty()) {
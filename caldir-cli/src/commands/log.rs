@@ -0,0 +1,94 @@
+use anyhow::Result;
+use caldir_core::{Caldir, SyncChangeKind, SyncDirection, SyncLogEntry};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+
+use crate::render::time::{Locale, format_timestamp};
+use crate::utils::{parse_date, require_calendars, resolve_calendars};
+
+/// Prints the sync log of each calendar (or just `calendar`), oldest first.
+pub fn run(caldir: &Caldir, calendar: Option<String>, since: Option<String>) -> Result<()> {
+    require_calendars(caldir)?;
+    let calendars = resolve_calendars(caldir, calendar.as_deref())?;
+    let since = since.as_deref().map(parse_since).transpose()?;
+
+    let mut entries: Vec<(String, SyncLogEntry)> = Vec::new();
+
+    for cal in &calendars {
+        let slug = cal.slug().unwrap_or_default().to_string();
+        entries.extend(
+            cal.sync_log()?
+                .into_iter()
+                .filter(|entry| since.is_none_or(|since| entry.at >= since))
+                .map(|entry| (slug.clone(), entry)),
+        );
+    }
+
+    entries.sort_by_key(|(_, entry)| entry.at);
+
+    if entries.is_empty() {
+        println!("{}", "Nothing synced yet.".dimmed());
+        return Ok(());
+    }
+
+    let locale = Locale::from_config(caldir.config());
+
+    for (slug, entry) in &entries {
+        println!("{}", render_entry(slug, entry, locale));
+    }
+
+    Ok(())
+}
+
+/// `YYYY-MM-DD`, or a duration back from now (e.g. "2h", "7d").
+fn parse_since(input: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = parse_date(input) {
+        return date
+            .and_hms_opt(0, 0, 0)
+            .and_then(|time| time.and_local_timezone(chrono::Local).earliest())
+            .map(|time| time.with_timezone(&Utc))
+            .ok_or_else(|| anyhow::anyhow!("Invalid date: {input}"));
+    }
+
+    let duration = humantime::parse_duration(input).map_err(|_| {
+        anyhow::anyhow!("Invalid --since: {input} (use YYYY-MM-DD or a duration like 7d)")
+    })?;
+
+    Ok(Utc::now() - chrono::Duration::from_std(duration)?)
+}
+
+fn render_entry(slug: &str, entry: &SyncLogEntry, locale: Locale) -> String {
+    let at = format_timestamp(entry.at, locale);
+
+    let direction = match entry.direction {
+        SyncDirection::Pull => "pull",
+        SyncDirection::Push => "push",
+    };
+
+    let kind = match entry.kind {
+        SyncChangeKind::Create => "+".green().to_string(),
+        SyncChangeKind::Update => "~".yellow().to_string(),
+        SyncChangeKind::Delete => "-".red().to_string(),
+    };
+
+    let summary = entry.summary.as_deref().unwrap_or("(Untitled)");
+    let mut line = format!(
+        "{} {} {kind} {summary} {}",
+        at.dimmed(),
+        direction,
+        format!("[{slug}]").dimmed()
+    );
+
+    if !entry.changed.is_empty() {
+        line.push_str(&format!(
+            " {}",
+            format!("({})", entry.changed.join(", ")).dimmed()
+        ));
+    }
+
+    if let Some(error) = &entry.error {
+        line.push_str(&format!("\n    {}", format!("failed: {error}").red()));
+    }
+
+    line
+}
@@ -0,0 +1,631 @@
+//! `caldir mcp`: a Model Context Protocol server on stdin/stdout, so AI
+//! assistants can read the local calendars, find free time and draft events.
+//!
+//! Write tools only describe what they would do unless called with
+//! `"confirm": true`, and nothing is pushed: changes stay in the local files
+//! until `caldir push`.
+
+use std::io::{BufRead, Write};
+
+use anyhow::{Context, Result};
+use caldir_core::{Attendee, Caldir, Calendar, Event, EventQuery, EventTime};
+use chrono::{Duration, Local, Utc};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::commands::events::resolve_range;
+use crate::commands::new::{
+    apply_duration, check_end_matches_start, default_end, default_reminders, parse_datetime,
+    resolve_attendee, resolve_calendar, resolve_tzid,
+};
+use crate::commands::schedule::{free_slots, parse_hours, parse_within, working_windows};
+use crate::render::event::event_json;
+use crate::render::time::Locale;
+use crate::utils::{PathExt, display_timezone, resolve_calendars, resolve_event_uid};
+
+const PROTOCOL_VERSION: &str = "2025-06-18";
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Tools that change files, hidden with `--read-only`.
+const WRITE_TOOLS: &[&str] = &["create_event", "update_event"];
+
+pub fn run(caldir: &Caldir, read_only: bool) -> Result<()> {
+    let server = Server { caldir, read_only };
+    let mut stdout = std::io::stdout().lock();
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line.context("Failed to read from stdin")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = server.handle(&line) {
+            writeln!(stdout, "{response}")?;
+            stdout.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+struct Server<'a> {
+    caldir: &'a Caldir,
+    read_only: bool,
+}
+
+impl Server<'_> {
+    /// The response to one JSON-RPC message; notifications get none.
+    fn handle(&self, line: &str) -> Option<Value> {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            return Some(error_response(Value::Null, PARSE_ERROR, "Invalid JSON"));
+        };
+
+        // Without an id it's a notification (e.g. `notifications/initialized`)
+        let id = message.get("id")?.clone();
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = message.get("params").cloned().unwrap_or(json!({}));
+
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "caldir", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": self.tools() })),
+            "tools/call" => self.call_tool(&params),
+            _ => Err((METHOD_NOT_FOUND, format!("Unknown method: {method}"))),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    fn tools(&self) -> Vec<Value> {
+        tool_definitions()
+            .into_iter()
+            .filter(|tool| {
+                !self.read_only || !WRITE_TOOLS.contains(&tool["name"].as_str().unwrap_or_default())
+            })
+            .collect()
+    }
+
+    fn call_tool(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params["name"].as_str().unwrap_or_default();
+        if !self.tools().iter().any(|tool| tool["name"] == name) {
+            return Err((INVALID_PARAMS, format!("Unknown tool: {name}")));
+        }
+
+        let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+
+        let output = match name {
+            "list_calendars" => self.list_calendars(),
+            "list_events" => args(arguments).and_then(|args| self.list_events(args)),
+            "find_free_time" => args(arguments).and_then(|args| self.find_free_time(args)),
+            "create_event" => args(arguments).and_then(|args| self.create_event(args)),
+            "update_event" => args(arguments).and_then(|args| self.update_event(args)),
+            _ => unreachable!("listed tools are handled"),
+        };
+
+        let (text, is_error) = match output {
+            Ok(text) => (text, false),
+            Err(e) => (format!("{e:#}"), true),
+        };
+
+        Ok(json!({
+            "content": [{ "type": "text", "text": text }],
+            "isError": is_error,
+        }))
+    }
+
+    fn list_calendars(&self) -> Result<String> {
+        let default_slug = self.caldir.config().default_calendar_slug();
+
+        let calendars: Vec<Value> = self
+            .caldir
+            .calendars()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|cal| !cal.is_archived())
+            .map(|cal| {
+                json!({
+                    "slug": cal.slug(),
+                    "name": cal.name(),
+                    "read_only": cal.is_read_only(),
+                    "default": cal.slug().is_some() && cal.slug() == default_slug,
+                })
+            })
+            .collect();
+
+        Ok(serde_json::to_string_pretty(&calendars)?)
+    }
+
+    fn list_events(&self, args: ListEventsArgs) -> Result<String> {
+        let calendars = resolve_calendars(self.caldir, args.calendar.as_deref())?;
+        let tz = display_timezone(self.caldir, None)?;
+        let (from, to) = resolve_range(
+            Utc::now().with_timezone(&tz),
+            args.from.as_deref(),
+            args.to.as_deref(),
+        )?;
+
+        let mut query = EventQuery::between(from, to);
+        if let Some(text) = args.query {
+            query = query.text(text);
+        }
+
+        let mut events = Vec::new();
+        for cal in &calendars {
+            for event in cal.query(&query)? {
+                events.push(event_json(&event, cal.slug().unwrap_or_default(), tz));
+            }
+        }
+
+        Ok(serde_json::to_string_pretty(&events)?)
+    }
+
+    /// Free slots in working hours, from the local calendars only.
+    fn find_free_time(&self, args: FindFreeTimeArgs) -> Result<String> {
+        let duration = humantime::parse_duration(&args.duration)
+            .map_err(|_| anyhow::anyhow!("Invalid duration: {} (e.g. 30m, 1h)", args.duration))?;
+        let duration = Duration::from_std(duration)?;
+
+        let locale = Locale::from_config(self.caldir.config());
+        let (first_day, last_day) =
+            parse_within(&args.within, Local::now().date_naive(), locale.week_start)?;
+        let (start_hour, end_hour) = parse_hours(&args.hours)?;
+        let windows = working_windows(
+            first_day,
+            last_day,
+            start_hour,
+            end_hour,
+            &Local,
+            Utc::now(),
+        );
+
+        let (Some(from), Some(to)) = (
+            windows.first().map(|w| w.start),
+            windows.last().map(|w| w.end),
+        ) else {
+            return Ok("[]".to_string());
+        };
+
+        let mut busy = Vec::new();
+        for cal in self.caldir.calendars().into_iter().filter_map(Result::ok) {
+            if !cal.is_archived() {
+                busy.extend(cal.busy_periods(from, to)?);
+            }
+        }
+
+        let tz = display_timezone(self.caldir, None)?;
+        let slots: Vec<Value> = free_slots(&busy, &windows, duration)
+            .iter()
+            .take(args.limit)
+            .map(|slot| {
+                json!({
+                    "start": slot.start.with_timezone(&tz).to_rfc3339(),
+                    "end": slot.end.with_timezone(&tz).to_rfc3339(),
+                })
+            })
+            .collect();
+
+        Ok(serde_json::to_string_pretty(&slots)?)
+    }
+
+    fn create_event(&self, args: CreateEventArgs) -> Result<String> {
+        let tzid = resolve_tzid(None)?;
+        let start = parse_datetime(&args.start, &tzid)?;
+        let end = match (&args.end, &args.duration) {
+            (Some(end), _) => {
+                let end = parse_datetime(end, &tzid)?;
+                check_end_matches_start(&start, &end)?;
+                end
+            }
+            (None, Some(duration)) => apply_duration(&start, duration)?,
+            (None, None) => default_end(&start),
+        };
+
+        let attendees: Vec<Attendee> = args
+            .attendees
+            .iter()
+            .map(|a| resolve_attendee(self.caldir, a))
+            .collect::<Result<_>>()?;
+
+        let calendars: Vec<Calendar> = self
+            .caldir
+            .calendars()
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect();
+        let calendar = resolve_calendar(self.caldir, args.calendar, &calendars, false)?;
+        check_writable(calendar)?;
+
+        let (reminders, reminders_default) = default_reminders(self.caldir, calendar);
+
+        let mut event = Event::new(args.title, start);
+        event.end = Some(end);
+        event.location = args.location;
+        event.description = args.description;
+        event.reminders = reminders;
+        event.reminders_default = reminders_default;
+        event.attendees = attendees;
+
+        let slug = calendar.slug().unwrap_or_default();
+        let preview = serde_json::to_string_pretty(&event_json(
+            &event,
+            slug,
+            display_timezone(self.caldir, None)?,
+        ))?;
+
+        if !args.confirm {
+            return Ok(format!(
+                "Not created yet. Call create_event again with \"confirm\": true to create:\n{preview}"
+            ));
+        }
+
+        let calendar_event = calendar.create_event(event)?;
+
+        Ok(format!(
+            "Created {}. It's sent to the provider on the next `caldir push`.\n{preview}",
+            calendar_event.path().tilde()
+        ))
+    }
+
+    fn update_event(&self, args: UpdateEventArgs) -> Result<String> {
+        let calendars = resolve_calendars(self.caldir, args.calendar.as_deref())?;
+
+        let (calendar, mut cal_event) = calendars
+            .iter()
+            .find_map(|cal| {
+                let uid = resolve_event_uid(cal, &args.event);
+                let cal_event = cal.events().ok()?.into_iter().find(|ce| {
+                    ce.event().uid.as_str() == uid && ce.event().recurrence_id.is_none()
+                })?;
+                Some((cal, cal_event))
+            })
+            .ok_or_else(|| anyhow::anyhow!("No event matches \"{}\"", args.event))?;
+        check_writable(calendar)?;
+
+        let tzid = resolve_tzid(None)?;
+        let mut event = cal_event.event().clone();
+
+        if let Some(title) = args.title {
+            event.summary = Some(title);
+        }
+        if let Some(start) = &args.start {
+            let start = parse_datetime(start, &tzid)?;
+            event.end = Some(end_keeping_length(&event, &start));
+            event.start = start;
+        }
+        if let Some(end) = &args.end {
+            let end = parse_datetime(end, &tzid)?;
+            check_end_matches_start(&event.start, &end)?;
+            event.end = Some(end);
+        }
+        if let Some(location) = args.location {
+            event.location = Some(location).filter(|l| !l.is_empty());
+        }
+        if let Some(description) = args.description {
+            event.description = Some(description).filter(|d| !d.is_empty());
+        }
+        event.last_modified = Some(Utc::now());
+
+        let preview = serde_json::to_string_pretty(&event_json(
+            &event,
+            calendar.slug().unwrap_or_default(),
+            display_timezone(self.caldir, None)?,
+        ))?;
+
+        if !args.confirm {
+            return Ok(format!(
+                "Not changed yet. Call update_event again with \"confirm\": true to save:\n{preview}"
+            ));
+        }
+
+        cal_event.update(event)?;
+
+        Ok(format!(
+            "Updated {}. It's sent to the provider on the next `caldir push`.\n{preview}",
+            cal_event.path().tilde()
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct ListEventsArgs {
+    from: Option<String>,
+    to: Option<String>,
+    calendar: Option<String>,
+    query: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct FindFreeTimeArgs {
+    #[serde(default = "default_duration")]
+    duration: String,
+    #[serde(default = "default_within")]
+    within: String,
+    #[serde(default = "default_hours")]
+    hours: String,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+fn default_duration() -> String {
+    "30m".to_string()
+}
+
+fn default_within() -> String {
+    "this week".to_string()
+}
+
+fn default_hours() -> String {
+    "9-17".to_string()
+}
+
+fn default_limit() -> usize {
+    10
+}
+
+#[derive(Deserialize)]
+struct CreateEventArgs {
+    title: String,
+    start: String,
+    end: Option<String>,
+    duration: Option<String>,
+    location: Option<String>,
+    description: Option<String>,
+    calendar: Option<String>,
+    #[serde(default)]
+    attendees: Vec<String>,
+    #[serde(default)]
+    confirm: bool,
+}
+
+#[derive(Deserialize)]
+struct UpdateEventArgs {
+    event: String,
+    calendar: Option<String>,
+    title: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    location: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    confirm: bool,
+}
+
+fn args<T: serde::de::DeserializeOwned>(arguments: Value) -> Result<T> {
+    serde_json::from_value(arguments).context("Invalid arguments")
+}
+
+fn check_writable(calendar: &Calendar) -> Result<()> {
+    if calendar.is_read_only() {
+        anyhow::bail!(
+            "Calendar '{}' is read-only",
+            calendar.slug().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// The end of `event` moved to `start`, keeping its length. Changing between
+/// all-day and timed gets the default length.
+fn end_keeping_length(event: &Event, start: &EventTime) -> EventTime {
+    let Some(end) = event
+        .end
+        .as_ref()
+        .filter(|end| end.is_date() == start.is_date() && event.start.is_date() == start.is_date())
+    else {
+        return default_end(start);
+    };
+
+    let length = end.to_utc() - event.start.to_utc();
+
+    match start {
+        EventTime::Date(d) => EventTime::Date(*d + Duration::days(length.num_days())),
+        EventTime::DateTimeFloating(dt) => EventTime::DateTimeFloating(*dt + length),
+        EventTime::DateTimeUtc(dt) => EventTime::DateTimeUtc(*dt + length),
+        EventTime::DateTimeZoned { datetime, tzid } => EventTime::DateTimeZoned {
+            datetime: *datetime + length,
+            tzid: tzid.clone(),
+        },
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn tool_definitions() -> Vec<Value> {
+    let read_only = json!({ "readOnlyHint": true });
+    let writes = json!({ "readOnlyHint": false, "destructiveHint": false });
+
+    vec![
+        json!({
+            "name": "list_calendars",
+            "description": "List the local calendars, with which one is the default and which are read-only.",
+            "inputSchema": { "type": "object", "properties": {} },
+            "annotations": read_only,
+        }),
+        json!({
+            "name": "list_events",
+            "description": "List events between two dates (default: today and the next two days), with recurring events expanded.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "from": { "type": "string", "description": "First day, YYYY-MM-DD" },
+                    "to": { "type": "string", "description": "Last day, YYYY-MM-DD" },
+                    "calendar": { "type": "string", "description": "Only this calendar (by slug)" },
+                    "query": { "type": "string", "description": "Text to look for in the title, description or location" },
+                },
+            },
+            "annotations": read_only,
+        }),
+        json!({
+            "name": "find_free_time",
+            "description": "Find free slots in working hours, based on the local calendars.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "duration": { "type": "string", "description": "Length of the slot, e.g. 30m or 1h (default 30m)" },
+                    "within": { "type": "string", "description": "today, tomorrow, this week, next week, 5d or FROM..TO dates (default this week)" },
+                    "hours": { "type": "string", "description": "Working hours, local time (default 9-17)" },
+                    "limit": { "type": "integer", "description": "Maximum number of slots (default 10)" },
+                },
+            },
+            "annotations": read_only,
+        }),
+        json!({
+            "name": "create_event",
+            "description": "Draft an event. Without confirm it only shows the event; with confirm it's saved locally, and sent to the provider on the next `caldir push`.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "start": { "type": "string", "description": "e.g. 2026-06-09T10:00, 2026-06-09 (all-day) or \"tomorrow 3pm\"" },
+                    "end": { "type": "string" },
+                    "duration": { "type": "string", "description": "e.g. 30m, 2h or 3 days (instead of end; default 1 hour or 1 day)" },
+                    "location": { "type": "string" },
+                    "description": { "type": "string" },
+                    "calendar": { "type": "string", "description": "Calendar slug (default: the default calendar)" },
+                    "attendees": { "type": "array", "items": { "type": "string" }, "description": "Emails, or names of people from past events" },
+                    "confirm": { "type": "boolean", "description": "Save the event. Only set after the user has approved it." },
+                },
+                "required": ["title", "start"],
+            },
+            "annotations": writes,
+        }),
+        json!({
+            "name": "update_event",
+            "description": "Change an event's title, time, location or description. Without confirm it only shows the result; with confirm it's saved locally, and sent to the provider on the next `caldir push`.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "event": { "type": "string", "description": "The event's uid (from list_events) or file name" },
+                    "calendar": { "type": "string", "description": "Only look in this calendar (by slug)" },
+                    "title": { "type": "string" },
+                    "start": { "type": "string", "description": "New start; the event keeps its length unless end is given" },
+                    "end": { "type": "string" },
+                    "location": { "type": "string", "description": "Empty to remove it" },
+                    "description": { "type": "string", "description": "Empty to remove it" },
+                    "confirm": { "type": "boolean", "description": "Save the change. Only set after the user has approved it." },
+                },
+                "required": ["event"],
+            },
+            "annotations": writes,
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_caldir;
+
+    fn call(server: &Server, name: &str, arguments: Value) -> Value {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": name, "arguments": arguments },
+        });
+        server.handle(&request.to_string()).unwrap()
+    }
+
+    fn tool_names(server: &Server) -> Vec<String> {
+        let response = server
+            .handle(r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#)
+            .unwrap();
+        response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn initializes_and_ignores_notifications() {
+        let (_tmp, caldir) = test_caldir();
+        let server = Server {
+            caldir: &caldir,
+            read_only: false,
+        };
+
+        let response = server
+            .handle(r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{}}"#)
+            .unwrap();
+        assert_eq!(response["result"]["serverInfo"]["name"], "caldir");
+        assert!(
+            server
+                .handle(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+                .is_none()
+        );
+        assert_eq!(
+            server.handle("not json").unwrap()["error"]["code"],
+            PARSE_ERROR
+        );
+    }
+
+    #[test]
+    fn read_only_hides_the_write_tools() {
+        let (_tmp, caldir) = test_caldir();
+        let server = Server {
+            caldir: &caldir,
+            read_only: true,
+        };
+
+        assert_eq!(
+            tool_names(&server),
+            ["list_calendars", "list_events", "find_free_time"]
+        );
+        assert_eq!(
+            call(&server, "create_event", json!({}))["error"]["code"],
+            INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn creates_events_only_when_confirmed() {
+        let (_tmp, caldir) = test_caldir();
+        let server = Server {
+            caldir: &caldir,
+            read_only: false,
+        };
+        let arguments = json!({ "title": "Lunch", "start": "2026-06-09T12:00" });
+        let calendar = caldir.calendar("personal").unwrap();
+
+        let preview = call(&server, "create_event", arguments.clone());
+        assert_eq!(preview["result"]["isError"], false);
+        assert!(calendar.events().unwrap().is_empty());
+
+        let mut confirmed = arguments;
+        confirmed["confirm"] = json!(true);
+        let created = call(&server, "create_event", confirmed);
+        assert_eq!(created["result"]["isError"], false);
+
+        let events = calendar.events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event().summary.as_deref(), Some("Lunch"));
+    }
+
+    #[test]
+    fn moving_an_event_keeps_its_length() {
+        let start =
+            |hour| parse_datetime(&format!("2026-06-09T{hour:02}:00"), "Europe/Stockholm").unwrap();
+        let mut event = Event::new("Lunch", start(12));
+        event.end = Some(start(13));
+
+        assert_eq!(end_keeping_length(&event, &start(15)), start(16));
+    }
+}
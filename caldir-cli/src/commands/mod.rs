@@ -1,15 +1,37 @@
+pub mod accounts;
+pub mod calendars;
 pub mod config;
+pub mod conflicts;
 pub mod connect;
 pub mod discard;
+pub mod disconnect;
 pub mod doctor;
 pub mod events;
+pub mod export;
+pub mod import;
 pub mod invites;
+pub mod log;
+pub mod mcp;
 pub mod new;
+pub mod providers;
 pub mod pull;
 pub mod push;
+pub mod quick;
+pub mod reschedule;
+pub mod restore;
 pub mod rsvp;
+pub mod schedule;
+pub mod service;
+pub mod shift;
+pub mod skip;
+pub mod snapshot;
+pub mod stats;
 pub mod status;
+pub mod statusbar;
 pub mod sync;
 pub mod today;
+pub mod trash;
+pub mod upcoming;
 pub mod update;
+pub mod validate;
 pub mod week;
@@ -1,25 +1,70 @@
+mod batch;
+
 use anyhow::{Context, Result};
-use caldir_core::{Caldir, Calendar, Event, EventTime, Reminder};
+use caldir_core::{Attendee, Caldir, Calendar, Contact, Event, EventTime, Reminder, XProperty};
 use chrono::Duration;
-use dialoguer::{Input, Select};
+use dialoguer::{Completion, Editor, Input, Select};
 use owo_colors::OwoColorize;
 
 use crate::utils::{PathExt, require_calendars};
 
-#[allow(clippy::too_many_arguments)]
-pub fn run(
-    caldir: &Caldir,
-    title: Option<String>,
-    start: Option<String>,
-    end: Option<String>,
-    duration: Option<String>,
-    location: Option<String>,
-    calendar_slug: Option<String>,
-    reminder_args: Vec<String>,
-    no_reminders: bool,
-) -> Result<()> {
+pub use batch::run as from_file;
+
+/// Holds the event's Meet URL in calendars connected to Google.
+const GOOGLE_CONFERENCE_PROPERTY: &str = "X-GOOGLE-CONFERENCE";
+
+/// What `caldir new` was given. Anything left unset is asked for
+/// interactively when the title or start is missing.
+#[derive(Default)]
+pub struct Options {
+    pub title: Option<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub duration: Option<String>,
+    pub location: Option<String>,
+    pub calendar_slug: Option<String>,
+    pub reminders: Vec<String>,
+    pub no_reminders: bool,
+    pub tags: Vec<String>,
+    pub attendees: Vec<String>,
+    pub edit_description: bool,
+    pub meet: bool,
+    pub tz: Option<String>,
+}
+
+pub fn run(caldir: &Caldir, options: Options) -> Result<()> {
+    let Options {
+        title,
+        start,
+        end,
+        duration,
+        location,
+        calendar_slug,
+        reminders: reminder_args,
+        no_reminders,
+        tags,
+        attendees: attendee_args,
+        edit_description,
+        meet,
+        tz,
+    } = options;
+
     require_calendars(caldir)?;
 
+    // Validate flags before any interactive prompts
+    let reminder_flags: Vec<Reminder> = reminder_args
+        .iter()
+        .map(|r| parse_reminder(r))
+        .collect::<Result<_>>()?;
+
+    let attendees: Vec<Attendee> = attendee_args
+        .iter()
+        .map(|a| resolve_attendee(caldir, a))
+        .collect::<Result<_>>()?;
+
+    let tzid = resolve_tzid(tz)?;
+    let parse = |input: &str| parse_datetime(input, &tzid);
+
     let interactive = title.is_none() || start.is_none();
 
     // --- Title ---
@@ -32,9 +77,9 @@ pub fn run(
 
     // --- Start ---
     let start_time = if let Some(s) = start {
-        parse_datetime(&s)?
+        parse(&s)?
     } else {
-        prompt_with_retry("  When?", parse_datetime)?
+        prompt_with_retry("  When?", parse)?
     };
 
     // --- Duration / End ---
@@ -42,7 +87,7 @@ pub fn run(
     let default_hint = if is_allday { "1 day" } else { "1 hour" };
 
     let end_time = if let Some(end_input) = end {
-        let parsed = parse_datetime(&end_input)?;
+        let parsed = parse(&end_input)?;
         check_end_matches_start(&start_time, &parsed)?;
         parsed
     } else if let Some(dur_input) = duration {
@@ -67,16 +112,18 @@ pub fn run(
         None
     };
 
-    // --- Reminders ---
-    let reminders: Vec<Reminder> = if no_reminders {
-        vec![]
-    } else if !reminder_args.is_empty() {
-        reminder_args
-            .iter()
-            .map(|r| parse_reminder(r))
-            .collect::<Result<_>>()?
+    // --- Attendees ---
+    let attendees = if attendees.is_empty() && interactive {
+        prompt_attendees(caldir)?
+    } else {
+        attendees
+    };
+
+    // --- Description ---
+    let description = if edit_description {
+        prompt_description(&title)?
     } else {
-        caldir.config().default_reminders().unwrap_or(vec![])
+        None
     };
 
     // --- Calendar ---
@@ -88,14 +135,42 @@ pub fn run(
 
     let calendar = resolve_calendar(caldir, calendar_slug, &calendars, interactive)?;
 
+    if meet && !is_google(calendar) {
+        anyhow::bail!(
+            "--meet needs a Google calendar, and {} isn't one",
+            calendar.slug().unwrap_or("this calendar")
+        );
+    }
+
+    // --- Reminders ---
+    // Flags win, then the calendar's default_reminders, then the global one.
+    // Without any of them, the event uses the provider's default reminders.
+    let (reminders, reminders_default): (Vec<Reminder>, bool) = if no_reminders {
+        (vec![], false)
+    } else if !reminder_flags.is_empty() {
+        (reminder_flags, false)
+    } else {
+        default_reminders(caldir, calendar)
+    };
+
     let mut event = Event::new(title, start_time);
     event.end = Some(end_time);
     if let Some(loc) = location {
         event.location = Some(loc);
     }
+    event.description = description;
     if !reminders.is_empty() {
         event.reminders = reminders;
     }
+    event.reminders_default = reminders_default;
+    event.categories = tags;
+    event.attendees = attendees;
+    if meet {
+        // Empty asks Google to create the conference when the event is pushed
+        event
+            .x_properties
+            .push(XProperty::new(GOOGLE_CONFERENCE_PROPERTY, ""));
+    }
 
     let calendar_event = calendar.create_event(event)?;
 
@@ -108,6 +183,20 @@ pub fn run(
         format!("  Created: {}", calendar_event.path().tilde()).green()
     );
 
+    if meet {
+        println!(
+            "{}",
+            "  Run `caldir push` to create the Google Meet link.".dimmed()
+        );
+    }
+
+    if !calendar_event.event().attendees.is_empty() {
+        println!(
+            "{}",
+            "  Run `caldir push --notify` to send the invitations.".dimmed()
+        );
+    }
+
     Ok(())
 }
 
@@ -127,6 +216,40 @@ where
     }
 }
 
+/// Open $VISUAL/$EDITOR on a commented template and return the saved text.
+/// Aborting the editor or saving an empty buffer leaves no description.
+fn prompt_description(title: &str) -> Result<Option<String>> {
+    let template = format!(
+        "\n# Description for \"{}\".\n\
+         # Lines starting with '#' are ignored; an empty description is skipped.\n",
+        title
+    );
+
+    let edited = Editor::new()
+        .extension(".txt")
+        .edit(&template)
+        .context("Failed to open $EDITOR")?;
+
+    Ok(edited.and_then(|text| strip_comment_lines(&text)))
+}
+
+/// Drop `#` comment lines and surrounding blank lines, like `git commit`.
+fn strip_comment_lines(text: &str) -> Option<String> {
+    let kept: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(str::trim_end)
+        .collect();
+
+    let description = kept.join("\n").trim().to_string();
+
+    if description.is_empty() {
+        None
+    } else {
+        Some(description)
+    }
+}
+
 /// Prompt for duration with retry on parse errors. Only accepts duration
 /// strings like "30m", "1h", "2 days" — never a date or end time.
 fn prompt_duration(start: &EventTime, default_hint: &str) -> Result<EventTime> {
@@ -200,14 +323,33 @@ fn expand_abbreviations(input: &str) -> String {
     result
 }
 
+/// Resolve the TZID for timed events: `--tz` if given, else the system timezone.
+pub(crate) fn resolve_tzid(tz: Option<String>) -> Result<String> {
+    match tz {
+        Some(tz) => {
+            let parsed: chrono_tz::Tz = tz.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "Unknown timezone: \"{}\" (expected an IANA name like Europe/Stockholm)",
+                    tz
+                )
+            })?;
+            Ok(parsed.name().to_string())
+        }
+        None => Ok(iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string())),
+    }
+}
+
 /// Parse a date/time string into an EventTime.
 /// Tries ISO 8601 formats first (e.g. "2026-06-09T10:00"), then falls back
 /// to fuzzydate natural language parsing (e.g. "tomorrow 6pm").
-fn parse_datetime(input: &str) -> Result<EventTime> {
+/// Timed results are zoned to `tzid`.
+pub(crate) fn parse_datetime(input: &str, tzid: &str) -> Result<EventTime> {
     // Try ISO 8601 datetime first (YYYY-MM-DDTHH:MM)
     if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M") {
-        let tzid = iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string());
-        return Ok(EventTime::DateTimeZoned { datetime: dt, tzid });
+        return Ok(EventTime::DateTimeZoned {
+            datetime: dt,
+            tzid: tzid.to_string(),
+        });
     }
 
     // Try ISO 8601 date-only (YYYY-MM-DD)
@@ -221,8 +363,10 @@ fn parse_datetime(input: &str) -> Result<EventTime> {
         .map_err(|_| anyhow::anyhow!("Could not parse date/time: \"{}\"", input))?;
 
     if has_time_component(input) {
-        let tzid = iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string());
-        Ok(EventTime::DateTimeZoned { datetime: dt, tzid })
+        Ok(EventTime::DateTimeZoned {
+            datetime: dt,
+            tzid: tzid.to_string(),
+        })
     } else {
         Ok(EventTime::Date(dt.date()))
     }
@@ -282,7 +426,7 @@ fn has_time_component(input: &str) -> bool {
 /// Verify that an explicit `--end` value matches the kind of `--start`
 /// (both timed or both all-day). Mismatches like a timed start with a
 /// date-only end are rejected by Google Calendar with a 400.
-fn check_end_matches_start(start: &EventTime, end: &EventTime) -> Result<()> {
+pub(crate) fn check_end_matches_start(start: &EventTime, end: &EventTime) -> Result<()> {
     match (start.is_date(), end.is_date()) {
         (true, false) => {
             anyhow::bail!("--start is all-day but --end is timed — both must be the same kind")
@@ -295,7 +439,7 @@ fn check_end_matches_start(start: &EventTime, end: &EventTime) -> Result<()> {
 }
 
 /// Apply a duration string to a start time.
-fn apply_duration(start: &EventTime, dur_input: &str) -> Result<EventTime> {
+pub(crate) fn apply_duration(start: &EventTime, dur_input: &str) -> Result<EventTime> {
     try_apply_duration(start, dur_input)
         .with_context(|| format!("Could not parse duration: \"{}\"", dur_input))
 }
@@ -316,7 +460,7 @@ fn try_apply_duration(start: &EventTime, dur_input: &str) -> Result<EventTime> {
 }
 
 /// Default end time: +1 hour for timed events, +1 day for all-day events.
-fn default_end(start: &EventTime) -> EventTime {
+pub(crate) fn default_end(start: &EventTime) -> EventTime {
     match start {
         EventTime::Date(d) => EventTime::Date(*d + Duration::days(1)),
         EventTime::DateTimeFloating(dt) => EventTime::DateTimeFloating(*dt + Duration::hours(1)),
@@ -328,13 +472,112 @@ fn default_end(start: &EventTime) -> EventTime {
     }
 }
 
+/// The calendar's default_reminders, else the global one. Without either,
+/// `(vec![], true)`: the event uses the provider's default reminders.
+pub(crate) fn default_reminders(caldir: &Caldir, calendar: &Calendar) -> (Vec<Reminder>, bool) {
+    match calendar
+        .default_reminders()
+        .or_else(|| caldir.config().default_reminders())
+    {
+        Some(reminders) => (reminders, false),
+        None => (vec![], true),
+    }
+}
+
 /// Parse a reminder string like "10m", "1h", "2 days" into a Reminder.
 fn parse_reminder(input: &str) -> Result<Reminder> {
     Reminder::from_human(input).map_err(|e| anyhow::anyhow!("{}", e))
 }
 
+/// Parse an attendee email, accepting an optional `mailto:` prefix.
+fn parse_attendee(input: &str) -> Result<Attendee> {
+    let email = input.trim();
+    let email = email.strip_prefix("mailto:").unwrap_or(email);
+
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => {
+            Ok(Attendee::new(email))
+        }
+        _ => anyhow::bail!("Invalid attendee email: \"{}\"", input),
+    }
+}
+
+/// An email address, or the name or start of the email of someone from
+/// past events (the most frequent match).
+pub(crate) fn resolve_attendee(caldir: &Caldir, input: &str) -> Result<Attendee> {
+    if input.contains('@') {
+        let mut attendee = parse_attendee(input)?;
+        attendee.name = caldir
+            .contact_names()
+            .remove(&attendee.email.to_lowercase());
+        return Ok(attendee);
+    }
+
+    let contact =
+        caldir.contacts(input)?.into_iter().next().ok_or_else(|| {
+            anyhow::anyhow!("No contact matches \"{input}\" (use an email address)")
+        })?;
+
+    Ok(Attendee {
+        name: contact.name,
+        ..Attendee::new(contact.email)
+    })
+}
+
+/// Prompt for comma-separated attendees, completing names and emails from
+/// past events with Tab.
+fn prompt_attendees(caldir: &Caldir) -> Result<Vec<Attendee>> {
+    let completion = ContactCompletion(caldir.contacts("").unwrap_or_default());
+
+    loop {
+        let input: String = Input::new()
+            .with_prompt("  Who? (skip)")
+            .default(String::new())
+            .show_default(false)
+            .completion_with(&completion)
+            .interact_text()?;
+
+        let attendees = input
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(|a| resolve_attendee(caldir, a))
+            .collect::<Result<Vec<_>>>();
+
+        match attendees {
+            Ok(attendees) => return Ok(attendees),
+            Err(e) => eprintln!("  {}", e.to_string().red()),
+        }
+    }
+}
+
+/// Completes the last of the comma-separated attendees to the email of the
+/// best matching contact.
+struct ContactCompletion(Vec<Contact>);
+
+impl Completion for ContactCompletion {
+    fn get(&self, input: &str) -> Option<String> {
+        let (done, last) = match input.rsplit_once(',') {
+            Some((done, last)) => (format!("{done}, "), last.trim()),
+            None => (String::new(), input.trim()),
+        };
+        if last.is_empty() {
+            return None;
+        }
+
+        let contact = self.0.iter().find(|contact| contact.matches(last))?;
+        Some(format!("{done}{}", contact.email))
+    }
+}
+
+fn is_google(calendar: &Calendar) -> bool {
+    calendar
+        .remote_config()
+        .is_some_and(|config| config.provider_slug().as_str() == "google")
+}
+
 /// Resolve which calendar to use.
-fn resolve_calendar<'a>(
+pub(crate) fn resolve_calendar<'a>(
     caldir: &Caldir,
     slug: Option<String>,
     calendars: &'a [Calendar],
@@ -391,6 +634,7 @@ fn resolve_calendar<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::test_caldir;
     use chrono::{Datelike, NaiveDate, Timelike};
 
     // --- has_time_component ---
@@ -463,27 +707,29 @@ mod tests {
 
     // --- parse_datetime ---
 
+    const TZID: &str = "Europe/Stockholm";
+
     #[test]
     fn parse_datetime_timed_returns_zoned() {
-        let result = parse_datetime("tomorrow 3pm").unwrap();
+        let result = parse_datetime("tomorrow 3pm", TZID).unwrap();
         assert!(matches!(result, EventTime::DateTimeZoned { .. }));
     }
 
     #[test]
     fn parse_datetime_date_only_returns_date() {
-        let result = parse_datetime("tomorrow").unwrap();
+        let result = parse_datetime("tomorrow", TZID).unwrap();
         assert!(matches!(result, EventTime::Date(_)));
     }
 
     #[test]
     fn parse_datetime_abbreviation_works() {
-        let result = parse_datetime("sat 3pm").unwrap();
+        let result = parse_datetime("sat 3pm", TZID).unwrap();
         assert!(matches!(result, EventTime::DateTimeZoned { .. }));
     }
 
     #[test]
     fn parse_datetime_absolute_date() {
-        let result = parse_datetime("march 20").unwrap();
+        let result = parse_datetime("march 20", TZID).unwrap();
         assert!(matches!(result, EventTime::Date(_)));
         if let EventTime::Date(d) = result {
             assert_eq!(d.month(), 3);
@@ -493,9 +739,10 @@ mod tests {
 
     #[test]
     fn parse_datetime_iso_datetime() {
-        let result = parse_datetime("2026-06-09T10:00").unwrap();
+        let result = parse_datetime("2026-06-09T10:00", TZID).unwrap();
         assert!(matches!(result, EventTime::DateTimeZoned { .. }));
-        if let EventTime::DateTimeZoned { datetime, .. } = result {
+        if let EventTime::DateTimeZoned { datetime, tzid } = result {
+            assert_eq!(tzid, TZID);
             assert_eq!(
                 datetime.date(),
                 NaiveDate::from_ymd_opt(2026, 6, 9).unwrap()
@@ -507,7 +754,7 @@ mod tests {
 
     #[test]
     fn parse_datetime_iso_date_only() {
-        let result = parse_datetime("2026-06-09").unwrap();
+        let result = parse_datetime("2026-06-09", TZID).unwrap();
         assert!(matches!(result, EventTime::Date(_)));
         if let EventTime::Date(d) = result {
             assert_eq!(d, NaiveDate::from_ymd_opt(2026, 6, 9).unwrap());
@@ -516,7 +763,85 @@ mod tests {
 
     #[test]
     fn parse_datetime_invalid_input() {
-        assert!(parse_datetime("not a date at all xyz").is_err());
+        assert!(parse_datetime("not a date at all xyz", TZID).is_err());
+    }
+
+    // --- resolve_tzid ---
+
+    #[test]
+    fn resolve_tzid_uses_override() {
+        assert_eq!(
+            resolve_tzid(Some("Europe/Stockholm".to_string())).unwrap(),
+            "Europe/Stockholm"
+        );
+    }
+
+    #[test]
+    fn resolve_tzid_rejects_unknown_timezone() {
+        assert!(resolve_tzid(Some("Mars/Olympus_Mons".to_string())).is_err());
+    }
+
+    // --- strip_comment_lines ---
+
+    #[test]
+    fn strip_comment_lines_keeps_text_and_drops_comments() {
+        let text = "\nFirst line\n\n  indented, kept  \n# comment\n\n";
+
+        assert_eq!(
+            strip_comment_lines(text).as_deref(),
+            Some("First line\n\n  indented, kept")
+        );
+    }
+
+    #[test]
+    fn strip_comment_lines_returns_none_for_template_only() {
+        let text = "\n# Description for \"Standup\".\n# Lines starting with '#' are ignored.\n";
+
+        assert_eq!(strip_comment_lines(text), None);
+    }
+
+    // --- parse_attendee ---
+
+    #[test]
+    fn parse_attendee_accepts_plain_and_mailto_emails() {
+        assert_eq!(
+            parse_attendee("alice@example.com").unwrap().email,
+            "alice@example.com"
+        );
+        assert_eq!(
+            parse_attendee("mailto:bob@example.com").unwrap().email,
+            "bob@example.com"
+        );
+    }
+
+    #[test]
+    fn parse_attendee_rejects_invalid_emails() {
+        assert!(parse_attendee("alice").is_err());
+        assert!(parse_attendee("@example.com").is_err());
+        assert!(parse_attendee("alice@localhost").is_err());
+    }
+
+    #[test]
+    fn completes_the_last_attendee_from_contacts() {
+        let contact = |email: &str, name: &str| Contact {
+            email: email.to_string(),
+            name: Some(name.to_string()),
+            events: 1,
+            last_seen: chrono::Utc::now(),
+            looked_up: false,
+        };
+        let completion = ContactCompletion(vec![
+            contact("alice@example.com", "Alice Smith"),
+            contact("bob@example.com", "Bob Jones"),
+        ]);
+
+        assert_eq!(completion.get("ali").as_deref(), Some("alice@example.com"));
+        assert_eq!(
+            completion.get("alice@example.com, jon").as_deref(),
+            Some("alice@example.com, bob@example.com")
+        );
+        assert_eq!(completion.get("alice@example.com, "), None);
+        assert_eq!(completion.get("carol"), None);
     }
 
     // --- default_end ---
@@ -652,4 +977,42 @@ mod tests {
         );
         assert!(check_end_matches_start(&start, &end).is_err());
     }
+
+    // --- reminders ---
+
+    fn create(caldir: &Caldir, no_reminders: bool) -> Event {
+        run(
+            caldir,
+            Options {
+                title: Some("Dentist".to_string()),
+                start: Some("2030-01-07".to_string()),
+                no_reminders,
+                tz: Some(TZID.to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let calendar = caldir.calendar("personal").unwrap();
+        calendar.events().unwrap().remove(0).event().clone()
+    }
+
+    #[test]
+    fn events_without_reminders_use_the_providers_defaults() {
+        let (_tmp, caldir) = test_caldir();
+
+        let event = create(&caldir, false);
+
+        assert!(event.reminders.is_empty());
+        assert!(event.reminders_default);
+    }
+
+    #[test]
+    fn no_reminders_turns_off_the_providers_defaults() {
+        let (_tmp, caldir) = test_caldir();
+
+        let event = create(&caldir, true);
+
+        assert!(!event.reminders_default);
+    }
 }
@@ -0,0 +1,404 @@
+//! `caldir new --from-file lectures.csv`
+//!
+//! Creates one event per row of a JSON or CSV/TSV file. Every row is checked
+//! before anything is written, so a file with mistakes creates nothing and
+//! can simply be fixed and run again.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use caldir_core::{Attendee, Caldir, Calendar, Event, EventTime, Reminder};
+use owo_colors::OwoColorize;
+use serde::Deserialize;
+
+use super::{
+    apply_duration, check_end_matches_start, default_end, default_reminders, parse_datetime,
+    parse_reminder, resolve_attendee, resolve_calendar, resolve_tzid,
+};
+use crate::utils::require_calendars;
+
+/// Columns (CSV) or keys (JSON) a row can have. `summary` is accepted for
+/// `title`, so files from `caldir export` can be read back.
+const COLUMNS: &[&str] = &[
+    "title",
+    "start",
+    "end",
+    "duration",
+    "location",
+    "description",
+    "calendar",
+    "tags",
+    "attendees",
+    "reminders",
+];
+
+/// Separates several tags, attendees or reminders in one CSV cell
+const LIST_SEPARATOR: char = ';';
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Row {
+    #[serde(alias = "summary")]
+    title: String,
+    start: String,
+    end: Option<String>,
+    /// e.g. "90m", or plain minutes like `caldir export` writes
+    duration: Option<String>,
+    location: Option<String>,
+    description: Option<String>,
+    calendar: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    attendees: Vec<String>,
+    #[serde(default)]
+    reminders: Vec<String>,
+}
+
+pub fn run(
+    caldir: &Caldir,
+    file: String,
+    calendar_slug: Option<String>,
+    tz: Option<String>,
+) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let contents =
+        std::fs::read_to_string(&file).with_context(|| format!("Couldn't read {file}"))?;
+    let rows = match Path::new(&file).extension().and_then(|e| e.to_str()) {
+        Some("json") => rows_from_json(&contents)?,
+        Some("csv") => rows_from_delimited(&contents, ',')?,
+        Some("tsv") => rows_from_delimited(&contents, '\t')?,
+        _ => bail!("Unknown file type: {file} (use .json, .csv or .tsv)"),
+    };
+
+    if rows.is_empty() {
+        bail!("No events in {file}");
+    }
+
+    let tzid = resolve_tzid(tz)?;
+    let calendars: Vec<Calendar> = caldir
+        .calendars()
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect();
+
+    let mut events = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, row) in rows.into_iter().enumerate() {
+        let event = row.and_then(|row| {
+            let slug = row.calendar.clone().or_else(|| calendar_slug.clone());
+            let calendar = resolve_calendar(caldir, slug, &calendars, false)?;
+            Ok((calendar, build_event(caldir, row, calendar, &tzid)?))
+        });
+
+        match event {
+            Ok(event) => events.push(event),
+            Err(e) => errors.push(format!("  Row {}: {e}", i + 1)),
+        }
+    }
+
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{}", error.red());
+        }
+        bail!(
+            "{} of {} rows have errors, no events were created",
+            errors.len(),
+            errors.len() + events.len()
+        );
+    }
+
+    let invites = events.iter().any(|(_, event)| !event.attendees.is_empty());
+    let mut created: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for (calendar, event) in events {
+        calendar.create_event(event)?;
+        *created
+            .entry(calendar.slug().unwrap_or_default())
+            .or_default() += 1;
+    }
+
+    for (slug, count) in created {
+        let noun = if count == 1 { "event" } else { "events" };
+        println!("{}", format!("  Created {count} {noun} in {slug}").green());
+    }
+
+    if invites {
+        println!(
+            "{}",
+            "  Run `caldir push --notify` to send the invitations.".dimmed()
+        );
+    } else {
+        println!("{}", "  Run `caldir push` to sync them.".dimmed());
+    }
+
+    Ok(())
+}
+
+/// The event a row describes, with the same defaults as `caldir new`.
+fn build_event(caldir: &Caldir, row: Row, calendar: &Calendar, tzid: &str) -> Result<Event> {
+    if row.title.trim().is_empty() {
+        bail!("title is empty");
+    }
+
+    let start = parse_time(&row.start, tzid)?;
+    let end = match (&row.end, &row.duration) {
+        (Some(end), _) => {
+            let end = parse_time(end, tzid)?;
+            check_end_matches_start(&start, &end)?;
+            end
+        }
+        (None, Some(minutes)) if minutes.chars().all(|c| c.is_ascii_digit()) => {
+            apply_duration(&start, &format!("{minutes}m"))?
+        }
+        (None, Some(duration)) => apply_duration(&start, duration)?,
+        (None, None) => default_end(&start),
+    };
+
+    let (reminders, reminders_default): (Vec<Reminder>, bool) = if row.reminders.is_empty() {
+        default_reminders(caldir, calendar)
+    } else {
+        let reminders = row
+            .reminders
+            .iter()
+            .map(|r| parse_reminder(r))
+            .collect::<Result<_>>()?;
+        (reminders, false)
+    };
+
+    let attendees: Vec<Attendee> = row
+        .attendees
+        .iter()
+        .map(|a| resolve_attendee(caldir, a))
+        .collect::<Result<_>>()?;
+
+    let mut event = Event::new(row.title, start);
+    event.end = Some(end);
+    event.location = row.location;
+    event.description = row.description;
+    event.categories = row.tags;
+    event.attendees = attendees;
+    event.reminders = reminders;
+    event.reminders_default = reminders_default;
+
+    Ok(event)
+}
+
+/// Like `caldir new --start`, also taking `2026-03-02 15:00` as
+/// `caldir export` writes it.
+fn parse_time(input: &str, tzid: &str) -> Result<EventTime> {
+    match chrono::NaiveDateTime::parse_from_str(input.trim(), "%Y-%m-%d %H:%M") {
+        Ok(datetime) => Ok(EventTime::DateTimeZoned {
+            datetime,
+            tzid: tzid.to_string(),
+        }),
+        Err(_) => parse_datetime(input.trim(), tzid),
+    }
+}
+
+/// An array of row objects. Each row is read on its own, so one with a
+/// wrong key is reported without hiding the others.
+fn rows_from_json(contents: &str) -> Result<Vec<Result<Row>>> {
+    let values: Vec<serde_json::Value> =
+        serde_json::from_str(contents).context("Expected a JSON array of events")?;
+
+    Ok(values
+        .into_iter()
+        .map(|value| serde_json::from_value(value).map_err(anyhow::Error::from))
+        .collect())
+}
+
+/// A header line naming the columns, then one event per line.
+fn rows_from_delimited(contents: &str, delimiter: char) -> Result<Vec<Result<Row>>> {
+    let mut records = parse_delimited(contents, delimiter)?.into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+
+    let header: Vec<String> = header
+        .iter()
+        .map(|column| match column.trim().to_lowercase().as_str() {
+            "summary" => "title".to_string(),
+            column => column.to_string(),
+        })
+        .collect();
+
+    if let Some(unknown) = header.iter().find(|c| !COLUMNS.contains(&c.as_str())) {
+        bail!(
+            "Unknown column: {unknown} (available: {})",
+            COLUMNS.join(", ")
+        );
+    }
+
+    Ok(records
+        .map(|record| row_from_record(&header, record))
+        .collect())
+}
+
+fn row_from_record(header: &[String], record: Vec<String>) -> Result<Row> {
+    let mut row = Row::default();
+    let list = |value: &str| -> Vec<String> {
+        value
+            .split(LIST_SEPARATOR)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+
+    for (column, value) in header.iter().zip(record) {
+        let text = Some(value.clone()).filter(|v| !v.trim().is_empty());
+        match column.as_str() {
+            "title" => row.title = value,
+            "start" => row.start = value,
+            "end" => row.end = text,
+            "duration" => row.duration = text,
+            "location" => row.location = text,
+            "description" => row.description = text,
+            "calendar" => row.calendar = text,
+            "tags" => row.tags = list(&value),
+            "attendees" => row.attendees = list(&value),
+            "reminders" => row.reminders = list(&value),
+            _ => {}
+        }
+    }
+
+    if row.start.trim().is_empty() {
+        bail!("start is empty");
+    }
+
+    Ok(row)
+}
+
+/// Records of a CSV (RFC 4180: quoted fields may hold delimiters, `""` and
+/// line breaks) or TSV file. Blank lines are skipped.
+fn parse_delimited(contents: &str, delimiter: char) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() && delimiter != '\t' => quoted = true,
+            c if quoted => field.push(c),
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                let done = std::mem::take(&mut record);
+                if done.iter().any(|f| !f.is_empty()) {
+                    records.push(done);
+                }
+            }
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        bail!("A quoted field is never closed");
+    }
+
+    record.push(field);
+    if record.iter().any(|f| !f.is_empty()) {
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_caldir;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_delimited_handles_quotes_and_blank_lines() {
+        let csv = "title,location\r\n\"Lecture, week 1\",\"Room \"\"B\"\"\"\n\n\"Two\nlines\",\n";
+
+        assert_eq!(
+            parse_delimited(csv, ',').unwrap(),
+            vec![
+                strings(&["title", "location"]),
+                strings(&["Lecture, week 1", "Room \"B\""]),
+                strings(&["Two\nlines", ""]),
+            ]
+        );
+        assert!(parse_delimited("\"open", ',').is_err());
+    }
+
+    #[test]
+    fn csv_rows_map_columns_and_lists() {
+        let csv = "Summary,start,duration,tags\nLecture,2026-09-01 10:00,90,uni; cs101\n,,,\n";
+        let rows = rows_from_delimited(csv, ',').unwrap();
+
+        assert_eq!(
+            *rows[0].as_ref().unwrap(),
+            Row {
+                title: "Lecture".to_string(),
+                start: "2026-09-01 10:00".to_string(),
+                duration: Some("90".to_string()),
+                tags: strings(&["uni", "cs101"]),
+                ..Row::default()
+            }
+        );
+        assert_eq!(rows.len(), 1);
+        assert!(rows_from_delimited("title,room\n", ',').is_err());
+    }
+
+    #[test]
+    fn json_rows_report_unknown_keys_per_row() {
+        let json =
+            r#"[{"title": "Lecture", "start": "2026-09-01"}, {"title": "Lab", "room": "B"}]"#;
+        let rows = rows_from_json(json).unwrap();
+
+        assert!(rows[0].is_ok());
+        assert!(rows[1].as_ref().unwrap_err().to_string().contains("room"));
+    }
+
+    #[test]
+    fn creates_nothing_when_a_row_is_invalid() {
+        let (tmp, caldir) = test_caldir();
+        let file = tmp.path().join("events.csv");
+        std::fs::write(
+            &file,
+            "title,start,end\nLecture,2026-09-01T10:00,2026-09-01T11:30\nLab,someday,\n",
+        )
+        .unwrap();
+        let file = file.to_string_lossy().to_string();
+
+        let err = run(&caldir, file.clone(), None, Some("UTC".to_string())).unwrap_err();
+        assert!(err.to_string().contains("1 of 2 rows"));
+        assert!(
+            caldir
+                .calendar("personal")
+                .unwrap()
+                .events()
+                .unwrap()
+                .is_empty()
+        );
+
+        std::fs::write(
+            &file,
+            "title,start\nLecture,2026-09-01T10:00\nLab,2026-09-02\n",
+        )
+        .unwrap();
+        run(&caldir, file, None, Some("UTC".to_string())).unwrap();
+
+        let events = caldir.calendar("personal").unwrap().events().unwrap();
+        assert_eq!(events.len(), 2);
+    }
+}
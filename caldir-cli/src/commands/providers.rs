@@ -0,0 +1,406 @@
+use anyhow::{Result, bail};
+use caldir_core::rpc::{AuthStyle, Capabilities, PROTOCOL_VERSION};
+use caldir_core::{Caldir, DateRange, Provider, ProviderSlug};
+use owo_colors::OwoColorize;
+use std::path::Path;
+
+use crate::commands::update::{self, GitHubRelease};
+use crate::render::diff::Render;
+use crate::utils::{self, tui};
+
+/// Providers that ship in caldir's own release tarball.
+const BUNDLED: &[&str] = &["apple", "caldav", "google", "icloud", "outlook", "webcal"];
+
+/// Third-party providers installable by name, and the GitHub repo they're released from.
+const REGISTRY: &[(&str, &str)] = &[
+    ("atproto", "t4t5/caldir-provider-atproto"),
+    ("proton", "t4t5/caldir-provider-proton"),
+    ("tuta", "t4t5/caldir-provider-tuta"),
+];
+
+/// Where a provider's releases come from.
+#[derive(Debug, PartialEq)]
+enum Source {
+    /// caldir's own release tarball
+    Bundled,
+    /// `caldir-provider-{name}-{target}.tar.gz` in this repo's latest release
+    GitHub(String),
+}
+
+fn source_for(name: &str) -> Option<Source> {
+    if BUNDLED.contains(&name) {
+        return Some(Source::Bundled);
+    }
+
+    REGISTRY
+        .iter()
+        .find(|(slug, _)| *slug == name)
+        .map(|(_, repo)| Source::GitHub(repo.to_string()))
+}
+
+/// Lists installed providers with their version and capabilities.
+pub async fn list(caldir: &Caldir) -> Result<()> {
+    let providers = installed(caldir, None)?;
+
+    if providers.is_empty() {
+        println!("No providers found in PATH.");
+        return Ok(());
+    }
+
+    for provider in providers {
+        let spinner = tui::create_spinner(provider.slug().to_string());
+        let result = provider.handshake().await;
+        spinner.finish_and_clear();
+
+        match result {
+            Ok(description) => {
+                let version = description
+                    .version
+                    .as_deref()
+                    .map(|v| format!("v{v}"))
+                    .unwrap_or_else(|| "unknown version".to_string());
+
+                println!(
+                    "{} {} {}",
+                    provider.slug().bold(),
+                    version.dimmed(),
+                    format!("({})", auth_label(description.auth)).dimmed()
+                );
+                println!(
+                    "   {}",
+                    capability_names(&description.capabilities).join(", ")
+                );
+            }
+            Err(e) => {
+                println!("{}", provider.slug().bold());
+                println!("   {}", e.to_string().red());
+            }
+        }
+
+        if let Some(path) = provider.binary_path() {
+            println!("   {}", path.display().dimmed());
+        }
+    }
+
+    Ok(())
+}
+
+/// Starts each provider, checks it answers, and checks that every calendar
+/// connected through it can still sign in (by listing a one-minute window).
+pub async fn check(caldir: &Caldir, name: Option<String>) -> Result<()> {
+    let providers = installed(caldir, name.as_deref())?;
+    let mut failures = 0;
+
+    for provider in providers {
+        println!("{}", provider.slug().bold());
+
+        let description = match provider.handshake().await {
+            Ok(description) => description,
+            Err(e) => {
+                println!("   {} {}", "✗".red(), e.to_string().red());
+                failures += 1;
+                continue;
+            }
+        };
+
+        if description.protocol_version == 0 {
+            println!("   {} Responds (no `describe` support)", "✓".green());
+        } else {
+            println!(
+                "   {} Responds (protocol v{})",
+                "✓".green(),
+                description.protocol_version
+            );
+        }
+
+        if description.protocol_version > PROTOCOL_VERSION {
+            println!(
+                "   {}",
+                "Speaks a newer protocol than this caldir. Run `caldir update`.".yellow()
+            );
+        }
+
+        failures += check_connections(caldir, provider.slug()).await;
+    }
+
+    if failures > 0 {
+        bail!("{failures} check(s) failed");
+    }
+
+    Ok(())
+}
+
+/// Signs in to each calendar using `slug`. Returns the number that failed.
+async fn check_connections(caldir: &Caldir, slug: &ProviderSlug) -> usize {
+    let now = chrono::Utc::now();
+    let range = DateRange {
+        from: Some(now),
+        to: Some(now + chrono::Duration::minutes(1)),
+    };
+
+    let connections: Vec<_> = caldir
+        .connections()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|connection| {
+            connection
+                .local()
+                .remote_config()
+                .is_some_and(|config| config.provider_slug() == slug)
+        })
+        .collect();
+
+    if connections.is_empty() {
+        println!("   {}", "No calendars connected".dimmed());
+        return 0;
+    }
+
+    let mut failures = 0;
+
+    for connection in connections {
+        let name = connection.local().render(caldir);
+
+        match connection.remote().list_events(&range).await {
+            Ok(_) => println!("   {} {name}", "✓".green()),
+            Err(e) => {
+                println!("   {} {name}: {}", "✗".red(), e.to_string().red());
+                if let Some(hint) = utils::error_hint(&connection, e.code()) {
+                    println!("     {}", hint.dimmed());
+                }
+                failures += 1;
+            }
+        }
+    }
+
+    failures
+}
+
+/// Downloads a provider from its latest release into caldir's install directory.
+pub async fn install(name: String, repo: Option<String>) -> Result<()> {
+    let source = match repo {
+        Some(repo) => Source::GitHub(repo),
+        None => source_for(&name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown provider: {name}. Pass --repo <owner/repo> to install it from GitHub releases."
+            )
+        })?,
+    };
+
+    let dst = update::get_install_dir()?.join(binary_name(&name));
+    let version = download(&name, &source, &dst).await?;
+
+    println!(
+        "{}",
+        format!("Installed {} v{version}", binary_name(&name)).green()
+    );
+
+    Ok(())
+}
+
+/// Updates third-party providers in place. Bundled providers are updated
+/// with caldir itself.
+pub async fn update(caldir: &Caldir, name: Option<String>) -> Result<()> {
+    for provider in installed(caldir, name.as_deref())? {
+        let slug = provider.slug().as_str();
+
+        let repo = match source_for(slug) {
+            Some(Source::GitHub(repo)) => repo,
+            Some(Source::Bundled) => {
+                println!(
+                    "{} {}",
+                    slug.bold(),
+                    "ships with caldir, run `caldir update`".dimmed()
+                );
+                continue;
+            }
+            None => {
+                println!(
+                    "{} {}",
+                    slug.bold(),
+                    format!(
+                        "not in the registry, run `caldir providers install {slug} --repo <owner/repo>`"
+                    )
+                    .dimmed()
+                );
+                continue;
+            }
+        };
+
+        let Some(dst) = provider.binary_path() else {
+            continue;
+        };
+
+        let latest = update::fetch_latest_release(&repo).await?;
+        let current = provider
+            .handshake()
+            .await
+            .ok()
+            .and_then(|d| d.version.clone());
+
+        if current.as_deref() == Some(latest.version()) {
+            println!(
+                "{} {}",
+                slug.bold(),
+                format!("already up to date (v{})", latest.version()).dimmed()
+            );
+            continue;
+        }
+
+        let version = install_from(&latest, slug, &repo, dst).await?;
+        println!("{} {}", slug.bold(), format!("v{version}").green());
+    }
+
+    Ok(())
+}
+
+/// Installs `name` from `source` at `dst`, returning the installed version.
+async fn download(name: &str, source: &Source, dst: &Path) -> Result<String> {
+    match source {
+        Source::Bundled => {
+            let latest = update::fetch_latest_release(update::REPO).await?;
+            let target = update::detect_target()?;
+            let tarball_name = format!("caldir-{target}.tar.gz");
+
+            let url = latest
+                .asset_url(&tarball_name)
+                .ok_or_else(|| anyhow::anyhow!("No release found for platform: {target}"))?;
+            let tmp_dir = update::download_and_unpack(url).await?;
+
+            let src = tmp_dir.path().join(binary_name(name));
+            if !src.exists() {
+                bail!("{} isn't available for {target}", binary_name(name));
+            }
+            update::install_binary(&src, dst)?;
+
+            Ok(latest.version().to_string())
+        }
+        Source::GitHub(repo) => {
+            let latest = update::fetch_latest_release(repo).await?;
+            install_from(&latest, name, repo, dst).await
+        }
+    }
+}
+
+async fn install_from(
+    release: &GitHubRelease,
+    name: &str,
+    repo: &str,
+    dst: &Path,
+) -> Result<String> {
+    let target = update::detect_target()?;
+    let tarball_name = format!("{}-{target}.tar.gz", binary_name(name));
+
+    let url = release
+        .asset_url(&tarball_name)
+        .ok_or_else(|| anyhow::anyhow!("{repo} has no release for platform: {target}"))?;
+    let tmp_dir = update::download_and_unpack(url).await?;
+
+    let src = tmp_dir.path().join(binary_name(name));
+    if !src.exists() {
+        bail!("{tarball_name} doesn't contain {}", binary_name(name));
+    }
+    update::install_binary(&src, dst)?;
+
+    Ok(release.version().to_string())
+}
+
+fn binary_name(name: &str) -> String {
+    format!("caldir-provider-{name}")
+}
+
+/// Discovered providers sorted by slug, or just `name` if given.
+fn installed<'a>(caldir: &'a Caldir, name: Option<&str>) -> Result<Vec<&'a Provider>> {
+    if let Some(name) = name {
+        return Ok(vec![caldir.provider(&ProviderSlug::from(name))?]);
+    }
+
+    let mut slugs = caldir.providers().slugs();
+    slugs.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+    slugs
+        .into_iter()
+        .map(|slug| caldir.provider(slug).map_err(anyhow::Error::from))
+        .collect()
+}
+
+fn auth_label(auth: AuthStyle) -> &'static str {
+    match auth {
+        AuthStyle::OAuth => "OAuth",
+        AuthStyle::Credentials => "username + password",
+        AuthStyle::None => "no auth",
+        AuthStyle::Local => "local",
+        AuthStyle::Unknown => "unknown auth",
+    }
+}
+
+fn capability_names(capabilities: &Capabilities) -> Vec<&'static str> {
+    let Capabilities {
+        create,
+        update,
+        delete,
+        incremental_sync,
+        attendees,
+        attachments,
+        urls,
+        reminders,
+        default_reminders,
+        free_busy,
+        contacts,
+        invitations,
+        floating_times,
+        time_zones,
+        cancel_occurrences,
+    } = *capabilities;
+
+    [
+        (create, "create"),
+        (update, "update"),
+        (delete, "delete"),
+        (incremental_sync, "incremental sync"),
+        (attendees, "attendees"),
+        (attachments, "attachments"),
+        (urls, "URLs"),
+        (reminders, "reminders"),
+        (default_reminders, "default reminders"),
+        (free_busy, "free/busy"),
+        (contacts, "contacts"),
+        (invitations, "invitations"),
+        (floating_times, "floating times"),
+        (time_zones, "time zones"),
+        (cancel_occurrences, "cancel occurrences"),
+    ]
+    .into_iter()
+    .filter_map(|(supported, name)| supported.then_some(name))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_for_knows_bundled_and_registry_providers() {
+        assert_eq!(source_for("google"), Some(Source::Bundled));
+        assert_eq!(
+            source_for("proton"),
+            Some(Source::GitHub("t4t5/caldir-provider-proton".to_string()))
+        );
+        assert_eq!(source_for("hooli"), None);
+    }
+
+    #[test]
+    fn capability_names_lists_supported_capabilities() {
+        assert_eq!(
+            capability_names(&Capabilities::read_only()),
+            [
+                "attendees",
+                "attachments",
+                "URLs",
+                "reminders",
+                "invitations",
+                "floating times",
+                "time zones"
+            ]
+        );
+    }
+}
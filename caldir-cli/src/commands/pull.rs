@@ -3,24 +3,44 @@ use caldir_core::{Caldir, CalendarDiff, Connection, DateRange};
 use owo_colors::OwoColorize;
 
 use crate::render::diff::{CalendarDiffRender, Render};
-use crate::utils::{connections, count_changes, resolve_sync_range, tui};
+use crate::render::plan::render_plan;
+use crate::utils::{
+    confirm_pull_deletes, connections, count_changes, error_hint, resolve_event_uid,
+    resolve_sync_range, tui,
+};
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     caldir: &Caldir,
     calendar_slugs: Vec<String>,
     from: Option<String>,
     to: Option<String>,
     verbose: bool,
+    force: bool,
+    dry_run: bool,
+    event: Option<String>,
 ) -> Result<()> {
     let connections = connections(caldir, &calendar_slugs);
     let range = resolve_sync_range(from, to)?;
     let mut applied: Vec<CalendarDiff> = Vec::new();
+    let mut pulled: Vec<Connection> = Vec::new();
     let total = connections.len();
 
     for (i, connection) in connections.into_iter().enumerate() {
         match connection {
             Ok(mut connection) => {
-                pull_connection(caldir, &mut connection, &range, verbose, &mut applied).await;
+                pull_connection(
+                    caldir,
+                    &mut connection,
+                    &range,
+                    verbose,
+                    force,
+                    dry_run,
+                    event.as_deref(),
+                    &mut applied,
+                )
+                .await;
+                pulled.push(connection);
             }
             Err(e) => println!("   {}", e.to_string().red()),
         }
@@ -30,6 +50,17 @@ pub async fn run(
         }
     }
 
+    if !dry_run {
+        if let Err(e) = caldir.refresh_views() {
+            println!("{}", e.to_string().yellow());
+        }
+        if let Err(e) = caldir.update_contacts(applied.iter().flat_map(|d| d.incoming())) {
+            println!("{}", e.to_string().yellow());
+        } else if let Err(e) = caldir.look_up_contact_names(&pulled).await {
+            println!("{}", e.to_string().yellow());
+        }
+    }
+
     let (created, updated, deleted) = count_changes(applied.iter().flat_map(|d| d.incoming()));
 
     if created > 0 || updated > 0 || deleted > 0 {
@@ -42,30 +73,55 @@ pub async fn run(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn pull_connection(
     caldir: &Caldir,
     connection: &mut Connection,
     range: &DateRange,
     verbose: bool,
+    force: bool,
+    dry_run: bool,
+    event: Option<&str>,
     applied: &mut Vec<CalendarDiff>,
 ) {
     let header = connection.local().render(caldir);
     let spinner = tui::create_spinner(header.clone());
-    let result = connection.diff(range).await;
+    let result = connection
+        .diff_with_progress(range, &tui::fetch_progress(&spinner, &header))
+        .await;
     spinner.finish_and_clear();
 
     println!("{}", header);
 
-    let diff = match result {
+    let mut diff = match result {
         Ok(diff) => diff,
         Err(e) => {
             println!("   {}", e.to_string().red());
+            if let Some(hint) = error_hint(connection, e.code()) {
+                println!("   {}", hint.dimmed());
+            }
             return;
         }
     };
 
+    if let Some(event) = event {
+        diff.retain_uid(&resolve_event_uid(connection.local(), event));
+    }
+
     println!("{}", diff.render_pull(verbose, caldir));
 
+    if dry_run {
+        match connection.plan_incoming_diff(&diff) {
+            Ok(steps) => println!("{}", render_plan(&steps)),
+            Err(e) => println!("   {}", e.to_string().red()),
+        }
+        return;
+    }
+
+    if !confirm_pull_deletes(&diff, caldir, force) {
+        return;
+    }
+
     match connection.apply_incoming_diff(&diff) {
         Ok(()) => applied.push(diff),
         Err(e) => println!("   {}", e.to_string().red()),
@@ -3,8 +3,13 @@ use caldir_core::{Caldir, CalendarDiff, Connection, DateRange};
 use owo_colors::OwoColorize;
 
 use crate::render::diff::{CalendarDiffRender, Render};
-use crate::utils::{allow_mass_delete, connections, count_changes, resolve_sync_range, tui};
+use crate::render::plan::render_plan;
+use crate::utils::{
+    allow_mass_delete, connections, count_changes, error_hint, notify_for, parse_notify,
+    resolve_event_uid, resolve_sync_range, send_invitations, tui,
+};
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     caldir: &Caldir,
     calendar: Option<String>,
@@ -12,7 +17,11 @@ pub async fn run(
     to: Option<String>,
     verbose: bool,
     force: bool,
+    notify: Option<String>,
+    dry_run: bool,
+    event: Option<String>,
 ) -> Result<()> {
+    let notify = parse_notify(notify.as_deref())?;
     let calendar_slugs: Vec<String> = calendar.into_iter().collect();
     let connections = connections(caldir, &calendar_slugs);
     let range = resolve_sync_range(from, to)?;
@@ -22,12 +31,15 @@ pub async fn run(
     for (i, connection) in connections.into_iter().enumerate() {
         match connection {
             Ok(mut connection) => {
+                connection.set_notify_attendees(notify_for(&connection, notify));
                 push_connection(
                     caldir,
                     &mut connection,
                     &range,
                     verbose,
                     force,
+                    dry_run,
+                    event.as_deref(),
                     &mut applied,
                 )
                 .await;
@@ -52,37 +64,76 @@ pub async fn run(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn push_connection(
     caldir: &Caldir,
     connection: &mut Connection,
     range: &DateRange,
     verbose: bool,
     force: bool,
+    dry_run: bool,
+    event: Option<&str>,
     applied: &mut Vec<CalendarDiff>,
 ) {
     let header = connection.local().render(caldir);
-    let spinner = tui::create_spinner(header.clone());
-    let result = connection.diff(range).await;
-    spinner.finish_and_clear();
+
+    let result = match event {
+        // Compared with its last synced version, so nothing needs fetching
+        Some(event) => {
+            let uid = resolve_event_uid(connection.local(), event);
+            connection
+                .offline_diff(&DateRange::default())
+                .map(|mut diff| {
+                    diff.retain_uid(&uid);
+                    diff
+                })
+        }
+        None => {
+            let spinner = tui::create_spinner(header.clone());
+            let result = connection
+                .diff_with_progress(range, &tui::fetch_progress(&spinner, &header))
+                .await;
+            spinner.finish_and_clear();
+            result
+        }
+    };
 
     println!("{}", header);
 
-    let diff = match result {
+    let mut diff = match result {
         Ok(diff) => diff,
         Err(e) => {
             println!("   {}", e.to_string().red());
+            if let Some(hint) = error_hint(connection, e.code()) {
+                println!("   {}", hint.dimmed());
+            }
             return;
         }
     };
 
+    if force {
+        diff.overwrite_conflicts();
+    }
+
     println!("{}", diff.render_push(verbose, caldir));
 
     if !allow_mass_delete(&diff, force) {
         return;
     }
 
+    if dry_run {
+        match connection.plan_outgoing_diff(&diff) {
+            Ok(steps) => println!("{}", render_plan(&steps)),
+            Err(e) => println!("   {}", e.to_string().red()),
+        }
+        return;
+    }
+
     match connection.apply_outgoing_diff(&diff).await {
-        Ok(()) => applied.push(diff),
+        Ok(()) => {
+            send_invitations(caldir, connection, &diff).await;
+            applied.push(diff);
+        }
         Err(e) => println!("   {}", e.to_string().red()),
     }
 }
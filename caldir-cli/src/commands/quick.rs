@@ -0,0 +1,261 @@
+//! `caldir quick "Lunch with Anna tomorrow 3pm at Blå Porten for 90m"`
+//!
+//! Splits one free-form string into title, start, duration and location,
+//! then hands the pieces to `caldir new`.
+
+use anyhow::Result;
+use caldir_core::Caldir;
+use chrono::{Month, Weekday};
+use owo_colors::OwoColorize;
+
+use crate::commands::new::{self, parse_datetime};
+
+/// Any timezone works here: parsing only decides *which words* form the
+/// date/time, `caldir new` resolves the real TZID afterwards.
+const DETECTION_TZID: &str = "UTC";
+
+const RELATIVE_WORDS: &[&str] = &[
+    "today", "tomorrow", "next", "this", "noon", "midnight", "in",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct QuickEvent {
+    pub title: String,
+    pub start: String,
+    pub duration: Option<String>,
+    pub location: Option<String>,
+}
+
+pub fn run(
+    caldir: &Caldir,
+    text: String,
+    calendar_slug: Option<String>,
+    tz: Option<String>,
+) -> Result<()> {
+    let quick = parse_quick(&text)?;
+
+    let mut summary = format!("  {} · {}", quick.title, quick.start);
+    if let Some(duration) = &quick.duration {
+        summary.push_str(&format!(" · {}", duration));
+    }
+    if let Some(location) = &quick.location {
+        summary.push_str(&format!(" · @ {}", location));
+    }
+    println!("{}", summary.dimmed());
+
+    new::run(
+        caldir,
+        new::Options {
+            title: Some(quick.title),
+            start: Some(quick.start),
+            duration: quick.duration,
+            // Some("") keeps `new` from prompting for a location
+            location: Some(quick.location.unwrap_or_default()),
+            calendar_slug,
+            tz,
+            ..Default::default()
+        },
+    )
+}
+
+/// Extract title, start, duration (`for 90m`) and location (`at Café`)
+/// from a single string.
+pub fn parse_quick(text: &str) -> Result<QuickEvent> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    let (start_idx, end_idx) = find_datetime_span(&words)
+        .ok_or_else(|| anyhow::anyhow!("Could not find a date or time in \"{}\"", text))?;
+
+    let start = words[start_idx..end_idx].join(" ");
+
+    // Clauses may appear before or after the date/time:
+    // "Lunch at Café tomorrow 12:30" and "Lunch tomorrow 12:30 at Café"
+    let mut title_words = Vec::new();
+    let mut duration = None;
+    let mut location = None;
+
+    for region in [&words[..start_idx], &words[end_idx..]] {
+        let clauses = extract_clauses(region);
+        title_words.extend(clauses.rest);
+        duration = duration.or(clauses.duration);
+        location = location.or(clauses.location);
+    }
+
+    let title = title_words.join(" ");
+    if title.is_empty() {
+        anyhow::bail!("Could not find a title in \"{}\"", text);
+    }
+
+    Ok(QuickEvent {
+        title,
+        start,
+        duration,
+        location,
+    })
+}
+
+/// The longest run of words that parses as a date/time (earliest wins ties).
+/// Runs must start on a date-ish word so titles like "Room 101" aren't eaten.
+fn find_datetime_span(words: &[&str]) -> Option<(usize, usize)> {
+    let mut best: Option<(usize, usize)> = None;
+
+    for i in 0..words.len() {
+        if !starts_datetime(words, i) {
+            continue;
+        }
+        for j in (i + 1..=words.len()).rev() {
+            let longer = best.is_none_or(|(bi, bj)| j - i > bj - bi);
+            if !longer {
+                break;
+            }
+            if is_keyword(words[j - 1]) {
+                continue;
+            }
+            if parse_datetime(&words[i..j].join(" "), DETECTION_TZID).is_ok() {
+                best = Some((i, j));
+                break;
+            }
+        }
+    }
+
+    best
+}
+
+fn starts_datetime(words: &[&str], i: usize) -> bool {
+    let word = words[i].to_lowercase();
+
+    // "at 3pm" is a time, "at Café" is a location
+    if word == "at" {
+        return words
+            .get(i + 1)
+            .is_some_and(|next| next.starts_with(|c: char| c.is_ascii_digit()));
+    }
+
+    word.starts_with(|c: char| c.is_ascii_digit())
+        || word.parse::<Weekday>().is_ok()
+        || word.parse::<Month>().is_ok()
+        || RELATIVE_WORDS.contains(&word.as_str())
+}
+
+fn is_keyword(word: &str) -> bool {
+    matches!(word.to_lowercase().as_str(), "at" | "@" | "for")
+}
+
+struct Clauses<'a> {
+    rest: Vec<&'a str>,
+    duration: Option<String>,
+    location: Option<String>,
+}
+
+/// Pull `for <duration>` and `at <location>` out of a run of words.
+/// A `for` that isn't followed by a duration ("Shopping for groceries")
+/// stays part of the title.
+fn extract_clauses<'a>(words: &[&'a str]) -> Clauses<'a> {
+    let mut clauses = Clauses {
+        rest: Vec::new(),
+        duration: None,
+        location: None,
+    };
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i].to_lowercase();
+        let clause_end = words[i + 1..]
+            .iter()
+            .position(|w| is_keyword(w))
+            .map_or(words.len(), |p| i + 1 + p);
+        let body = words[i + 1..clause_end].join(" ");
+
+        if word == "for" && clauses.duration.is_none() && humantime::parse_duration(&body).is_ok() {
+            clauses.duration = Some(body);
+            i = clause_end;
+        } else if (word == "at" || word == "@") && clauses.location.is_none() && !body.is_empty() {
+            clauses.location = Some(body);
+            i = clause_end;
+        } else {
+            clauses.rest.push(words[i]);
+            i += 1;
+        }
+    }
+
+    clauses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_title_time_location_and_duration() {
+        let quick = parse_quick("Lunch with Anna tomorrow 3pm at Blå Porten for 90m").unwrap();
+
+        assert_eq!(
+            quick,
+            QuickEvent {
+                title: "Lunch with Anna".to_string(),
+                start: "tomorrow 3pm".to_string(),
+                duration: Some("90m".to_string()),
+                location: Some("Blå Porten".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn clock_time_after_a_relative_day() {
+        for text in [
+            "Lunch at Café tomorrow 12:30",
+            "Lunch tomorrow 12:30 at Café",
+        ] {
+            let quick = parse_quick(text).unwrap();
+
+            assert_eq!(quick.title, "Lunch");
+            assert_eq!(quick.start, "tomorrow 12:30");
+            assert_eq!(quick.location.as_deref(), Some("Café"));
+        }
+    }
+
+    #[test]
+    fn location_before_time() {
+        let quick = parse_quick("Drinks at Oliver Twist sat 3pm").unwrap();
+
+        assert_eq!(quick.title, "Drinks");
+        assert_eq!(quick.start, "sat 3pm");
+        assert_eq!(quick.location.as_deref(), Some("Oliver Twist"));
+        assert_eq!(quick.duration, None);
+    }
+
+    #[test]
+    fn for_without_duration_stays_in_title() {
+        let quick = parse_quick("Shopping for groceries march 20").unwrap();
+
+        assert_eq!(quick.title, "Shopping for groceries");
+        assert_eq!(quick.start, "march 20");
+        assert_eq!(quick.duration, None);
+    }
+
+    #[test]
+    fn iso_datetime_is_recognised() {
+        let quick = parse_quick("Dentist 2026-06-09T10:00 for 45m").unwrap();
+
+        assert_eq!(quick.title, "Dentist");
+        assert_eq!(quick.start, "2026-06-09T10:00");
+        assert_eq!(quick.duration.as_deref(), Some("45m"));
+    }
+
+    #[test]
+    fn words_after_the_time_join_the_title() {
+        let quick = parse_quick("Dinner 2026-06-09T19:00 with Bob").unwrap();
+
+        assert_eq!(quick.title, "Dinner with Bob");
+    }
+
+    #[test]
+    fn errors_without_a_time() {
+        assert!(parse_quick("Lunch with Anna").is_err());
+    }
+
+    #[test]
+    fn errors_without_a_title() {
+        assert!(parse_quick("tomorrow 3pm").is_err());
+    }
+}
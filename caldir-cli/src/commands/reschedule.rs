@@ -0,0 +1,243 @@
+//! `caldir postpone standup 30m` and `caldir reschedule standup "thu 2pm"`
+
+use anyhow::{Context, Result, bail};
+use caldir_core::{Caldir, Calendar, Event, EventQuery, EventTime};
+use chrono::{Duration, NaiveDateTime, NaiveTime, Utc};
+use owo_colors::OwoColorize;
+
+use crate::commands::new::{parse_datetime, resolve_tzid};
+use crate::render::time::{Locale, format_datetime};
+use crate::utils::{require_calendars, resolve_calendars, resolve_event_uid};
+
+/// How far ahead to look for the event
+const LOOKAHEAD_DAYS: i64 = 365;
+
+/// Moves the next occurrence of `event` later by `by` (e.g. "30m", "1 day").
+pub fn postpone(
+    caldir: &Caldir,
+    event: String,
+    by: String,
+    calendar: Option<String>,
+) -> Result<()> {
+    let by = humantime::parse_duration(&by)
+        .map_err(|e| anyhow::anyhow!("{e}"))
+        .with_context(|| format!("Could not parse duration: \"{by}\""))?;
+    let by = Duration::from_std(by).context("Duration too large")?;
+
+    move_event(caldir, &event, calendar, |_| Ok(by))
+}
+
+/// Moves the next occurrence of `event` to `to` (e.g. "thu 2pm", or "fri"
+/// to keep its time of day), keeping its length.
+pub fn reschedule(
+    caldir: &Caldir,
+    event: String,
+    to: String,
+    calendar: Option<String>,
+) -> Result<()> {
+    move_event(caldir, &event, calendar, |start| offset_to(start, &to))
+}
+
+fn move_event(
+    caldir: &Caldir,
+    arg: &str,
+    calendar: Option<String>,
+    offset: impl FnOnce(&EventTime) -> Result<Duration>,
+) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let calendars = resolve_calendars(caldir, calendar.as_deref())?;
+    let (calendar, event) = next_occurrence(&calendars, arg)?;
+
+    if calendar.is_read_only() {
+        bail!(
+            "{} is read-only",
+            calendar.slug().unwrap_or("This calendar")
+        );
+    }
+
+    let label = event.summary.as_deref().unwrap_or("(Untitled)");
+    let by = offset(&event.start)?;
+
+    if by.is_zero() {
+        println!("{}", format!("  {label} is already at that time").dimmed());
+        return Ok(());
+    }
+    if event.start.is_date() && by.num_seconds() % 86_400 != 0 {
+        bail!("{label} is an all-day event, so it only moves by whole days");
+    }
+
+    let mut moved = event.clone();
+    moved.shift(by);
+
+    let id = event.event_instance_id();
+    if event.recurrence_id.is_some() {
+        // Only this occurrence, not the whole series
+        calendar.update_recurring_instance(&id, |e| e.shift(by))?;
+    } else {
+        let mut ce = calendar
+            .event_by_instance_id(&id)?
+            .with_context(|| format!("{label} is no longer in the calendar"))?;
+        let mut saved = moved.clone();
+        saved.sequence += 1;
+        saved.last_modified = Some(Utc::now());
+        ce.update(saved)?;
+    }
+
+    let locale = Locale::from_config(caldir.config());
+    println!(
+        "  {} {} {} → {}",
+        "~".yellow(),
+        label.yellow(),
+        format_datetime(&event.start, locale).dimmed(),
+        format_datetime(&moved.start, locale)
+    );
+    println!("{}", "  Run `caldir push` to sync it.".dimmed());
+
+    Ok(())
+}
+
+/// The soonest upcoming event (or occurrence of a series) that `arg` names,
+/// by file name, UID or title.
+fn next_occurrence<'a>(calendars: &'a [Calendar], arg: &str) -> Result<(&'a Calendar, Event)> {
+    let now = Utc::now();
+    let query = EventQuery::between(now, now + Duration::days(LOOKAHEAD_DAYS));
+
+    let mut next: Option<(&Calendar, Event)> = None;
+
+    for cal in calendars {
+        let uid = resolve_event_uid(cal, arg);
+        for event in cal.query(&query)? {
+            let named = event.uid.as_str() == uid
+                || event
+                    .summary
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(arg));
+            let sooner = next
+                .as_ref()
+                .is_none_or(|(_, e)| event.start.to_utc() < e.start.to_utc());
+
+            if named && sooner {
+                next = Some((cal, event));
+            }
+        }
+    }
+
+    next.with_context(|| format!("No upcoming event matches \"{arg}\""))
+}
+
+/// How far `start` has to move to land on `to`, on the event's own clock.
+/// A date alone keeps the time of day.
+fn offset_to(start: &EventTime, to: &str) -> Result<Duration> {
+    let tzid = match start {
+        EventTime::DateTimeZoned { tzid, .. } => tzid.clone(),
+        _ => resolve_tzid(None)?,
+    };
+
+    let target = match (start, parse_datetime(to, &tzid)?) {
+        (EventTime::Date(_), EventTime::Date(date)) => date.and_time(NaiveTime::MIN),
+        (EventTime::Date(_), _) => {
+            bail!("This is an all-day event, so reschedule it to a date (e.g. \"fri\")")
+        }
+        (_, EventTime::Date(date)) => date.and_time(wall_clock(start).time()),
+        (EventTime::DateTimeUtc(_), target) => target.to_utc().naive_utc(),
+        (_, EventTime::DateTimeZoned { datetime, .. }) => datetime,
+        (_, target) => wall_clock(&target),
+    };
+
+    Ok(target - wall_clock(start))
+}
+
+fn wall_clock(time: &EventTime) -> NaiveDateTime {
+    match time {
+        EventTime::Date(date) => date.and_time(NaiveTime::MIN),
+        EventTime::DateTimeUtc(datetime) => datetime.naive_utc(),
+        EventTime::DateTimeFloating(datetime) | EventTime::DateTimeZoned { datetime, .. } => {
+            *datetime
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_caldir;
+    use caldir_core::Recurrence;
+    use chrono::{Local, NaiveDate};
+
+    fn in_two_days_at(hour: u32, minute: u32) -> NaiveDateTime {
+        (Local::now().date_naive() + Duration::days(2))
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn floating_event(summary: &str) -> Event {
+        let start = in_two_days_at(9, 0);
+        let mut event = Event::new(summary, EventTime::DateTimeFloating(start));
+        event.end = Some(EventTime::DateTimeFloating(start + Duration::hours(1)));
+        event
+    }
+
+    #[test]
+    fn postpone_keeps_the_length_and_bumps_sequence() {
+        let (_tmp, caldir) = test_caldir();
+        let calendar = caldir.calendar("personal").unwrap();
+        calendar.create_event(floating_event("Dentist")).unwrap();
+
+        postpone(&caldir, "dentist".to_string(), "30m".to_string(), None).unwrap();
+
+        let event = calendar.events().unwrap().remove(0).event().clone();
+        assert_eq!(
+            event.start,
+            EventTime::DateTimeFloating(in_two_days_at(9, 30))
+        );
+        assert_eq!(
+            event.end,
+            Some(EventTime::DateTimeFloating(in_two_days_at(10, 30)))
+        );
+        assert_eq!(event.sequence, 1);
+    }
+
+    #[test]
+    fn reschedule_to_a_date_moves_one_occurrence_at_the_same_time() {
+        let (_tmp, caldir) = test_caldir();
+        let calendar = caldir.calendar("personal").unwrap();
+        let mut standup = floating_event("Standup");
+        standup.recurrence = Some(Recurrence::new("FREQ=WEEKLY"));
+        calendar.create_event(standup).unwrap();
+
+        let day_after = (in_two_days_at(9, 0) + Duration::days(1)).date();
+        reschedule(
+            &caldir,
+            "Standup".to_string(),
+            day_after.format("%Y-%m-%d").to_string(),
+            None,
+        )
+        .unwrap();
+
+        let events: Vec<Event> = calendar
+            .events()
+            .unwrap()
+            .into_iter()
+            .map(|ce| ce.event().clone())
+            .collect();
+        let moved = events.iter().find(|e| e.recurrence_id.is_some()).unwrap();
+        assert_eq!(
+            moved.start,
+            EventTime::DateTimeFloating(day_after.and_hms_opt(9, 0, 0).unwrap())
+        );
+        let master = events.iter().find(|e| e.recurrence.is_some()).unwrap();
+        assert_eq!(
+            master.start,
+            EventTime::DateTimeFloating(in_two_days_at(9, 0))
+        );
+    }
+
+    #[test]
+    fn all_day_events_only_move_to_dates() {
+        let start = EventTime::Date(NaiveDate::from_ymd_opt(2030, 1, 7).unwrap());
+
+        assert_eq!(offset_to(&start, "2030-01-09").unwrap(), Duration::days(2));
+        assert!(offset_to(&start, "2030-01-09T10:00").is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use caldir_core::Caldir;
+use owo_colors::OwoColorize;
+
+use crate::render::diff::Render;
+
+pub fn run(caldir: &Caldir, id: String) -> Result<()> {
+    // So the restore itself can be undone
+    let backup = caldir.snapshot()?;
+
+    let calendars = caldir.restore(&id)?;
+
+    println!("Restored snapshot {}", id.bold());
+    for calendar in &calendars {
+        println!("   {}", calendar.render(caldir));
+    }
+
+    println!();
+    println!(
+        "{}",
+        "Run \"caldir push\" to bring the remote calendars in line.".dimmed()
+    );
+    println!(
+        "{}",
+        format!("The previous state was saved as snapshot {}.", backup.id()).dimmed()
+    );
+
+    Ok(())
+}
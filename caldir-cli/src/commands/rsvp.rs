@@ -2,13 +2,13 @@ use std::io::{self, Write};
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use caldir_core::{Caldir, CalendarEvent, DateBounds, Event, ParticipationStatus};
+use caldir_core::{Caldir, CalendarEvent, DateBounds, Event, Layout, ParticipationStatus};
 use chrono::{Duration, Utc};
 use owo_colors::OwoColorize;
 
 use crate::render::event::format_event_line;
-use crate::render::time::format_date_only;
-use crate::utils::require_calendars;
+use crate::render::time::{Locale, date_in, format_date_label_in};
+use crate::utils::{display_timezone, require_calendars};
 
 pub fn run(caldir: &Caldir, path: Option<String>, response: Option<String>) -> Result<()> {
     require_calendars(caldir)?;
@@ -29,9 +29,9 @@ fn run_direct(caldir: &Caldir, path_str: &str, response_str: &str) -> Result<()>
         anyhow::bail!("File not found: {}", path.display());
     }
 
-    let cal_slug = path
-        .parent()
-        .and_then(|p| p.file_name())
+    let (_, calendar_dir) = Layout::of_path(&path);
+    let cal_slug = calendar_dir
+        .file_name()
         .and_then(|n| n.to_str())
         .context("Cannot determine calendar from path")?;
 
@@ -63,7 +63,7 @@ fn run_direct(caldir: &Caldir, path_str: &str, response_str: &str) -> Result<()>
 }
 
 fn run_interactive(caldir: &Caldir) -> Result<()> {
-    let tz: chrono_tz::Tz = iana_time_zone::get_timezone()?.parse()?;
+    let tz = display_timezone(caldir, None)?;
     let today = Utc::now().with_timezone(&tz).date_naive();
     let from = today
         .start_of_date()
@@ -78,8 +78,8 @@ fn run_interactive(caldir: &Caldir) -> Result<()> {
         .unwrap()
         .with_timezone(&Utc);
 
-    // (cal_slug, email, CalendarEvent) — own the CalendarEvent so we can mutate.
-    let mut invites: Vec<(String, String, CalendarEvent)> = Vec::new();
+    // (cal_slug, cal_color, email, CalendarEvent) — own the CalendarEvent so we can mutate.
+    let mut invites: Vec<(String, Option<String>, String, CalendarEvent)> = Vec::new();
 
     for cal in caldir.calendars().into_iter().filter_map(Result::ok) {
         let Some(email) = cal.remote_email() else {
@@ -87,6 +87,7 @@ fn run_interactive(caldir: &Caldir) -> Result<()> {
         };
         let email = email.to_string();
         let cal_slug = cal.slug().unwrap_or("(Unknown calendar)").to_string();
+        let cal_color = cal.color().map(str::to_string);
 
         for ce in cal.events()? {
             let event = ce.event();
@@ -94,12 +95,12 @@ fn run_interactive(caldir: &Caldir) -> Result<()> {
             let is_pending = event.is_invite_for(&email)
                 && event.attendee_status(&email) == Some(ParticipationStatus::NeedsAction);
             if in_range && is_pending {
-                invites.push((cal_slug.clone(), email.clone(), ce));
+                invites.push((cal_slug.clone(), cal_color.clone(), email.clone(), ce));
             }
         }
     }
 
-    invites.sort_by_key(|(_, _, ce)| ce.event().start.to_utc());
+    invites.sort_by_key(|(_, _, _, ce)| ce.event().start.to_utc());
 
     if invites.is_empty() {
         println!("{}", "No pending invites.".dimmed());
@@ -116,12 +117,13 @@ fn run_interactive(caldir: &Caldir) -> Result<()> {
         }
     );
 
+    let locale = Locale::from_config(caldir.config());
     let mut responded = 0;
     let mut current_date: Option<String> = None;
 
-    for (cal_slug, email, mut ce) in invites {
+    for (cal_slug, cal_color, email, mut ce) in invites {
         let event = ce.event().clone();
-        let date_label = format_date_only(&event.start);
+        let date_label = format_date_label_in(date_in(&event.start, locale, &tz), locale, &tz);
         if current_date.as_ref() != Some(&date_label) {
             if current_date.is_some() {
                 println!();
@@ -136,7 +138,10 @@ fn run_interactive(caldir: &Caldir) -> Result<()> {
             .map(|o| o.name.as_deref().unwrap_or(&o.email).to_string())
             .unwrap_or_else(|| "(unknown)".to_string());
 
-        println!("{}", format_event_line(&event, &cal_slug, "", caldir));
+        println!(
+            "{}",
+            format_event_line(&event, &cal_slug, cal_color.as_deref(), "", caldir, tz)
+        );
         println!("       {} {}", "from:".dimmed(), organizer.dimmed());
         print!("  [a]ccept  [d]ecline  [m]aybe  [s]kip: ");
         io::stdout().flush()?;
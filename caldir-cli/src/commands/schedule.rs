@@ -0,0 +1,320 @@
+use anyhow::{Result, bail};
+use caldir_core::rpc::TimePeriod;
+use caldir_core::{Caldir, Connection, EventTime, WeekStart};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
+use owo_colors::OwoColorize;
+
+use crate::render::time::{Locale, format_date_label, format_time_only};
+use crate::utils::{connections, error_hint, parse_date, require_calendars, tui};
+
+/// Candidate slots start on these boundaries.
+const SLOT_STEP_MINUTES: i64 = 30;
+
+/// Proposes times in working hours when I (and everyone in `with`) am free.
+/// My busy times come from the local calendars; other people's from the
+/// free/busy lookup of a provider that has one.
+pub async fn run(
+    caldir: &Caldir,
+    with: Vec<String>,
+    duration: String,
+    within: String,
+    hours: String,
+    calendar: Option<String>,
+    limit: usize,
+) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let duration = humantime::parse_duration(&duration)
+        .map_err(|_| anyhow::anyhow!("Invalid --duration: {duration} (e.g. 30m, 1h)"))?;
+    let duration = Duration::from_std(duration)?;
+
+    let locale = Locale::from_config(caldir.config());
+    let (first_day, last_day) =
+        parse_within(&within, Local::now().date_naive(), locale.week_start)?;
+    let (start_hour, end_hour) = parse_hours(&hours)?;
+    let windows = working_windows(
+        first_day,
+        last_day,
+        start_hour,
+        end_hour,
+        &Local,
+        Utc::now(),
+    );
+
+    let (Some(from), Some(to)) = (
+        windows.first().map(|w| w.start),
+        windows.last().map(|w| w.end),
+    ) else {
+        println!("{}", "No working hours left in that range.".dimmed());
+        return Ok(());
+    };
+
+    let mut busy = Vec::new();
+    for cal in caldir.calendars().into_iter().filter_map(Result::ok) {
+        if !cal.is_archived() {
+            busy.extend(cal.busy_periods(from, to)?);
+        }
+    }
+
+    if !with.is_empty() {
+        let connection = free_busy_connection(caldir, calendar.as_deref()).await?;
+
+        let spinner = tui::create_spinner(format!("Checking {}", with.join(", ")));
+        let result = connection.remote().free_busy(from, to, with).await;
+        spinner.finish_and_clear();
+
+        let schedules = match result {
+            Ok(schedules) => schedules,
+            Err(e) => {
+                if let Some(hint) = error_hint(&connection, e.code()) {
+                    println!("{}", hint.dimmed());
+                }
+                return Err(e.into());
+            }
+        };
+
+        for schedule in schedules {
+            if let Some(error) = &schedule.error {
+                println!(
+                    "{}",
+                    format!("Couldn't see {}'s calendar ({error})", schedule.email).yellow()
+                );
+            }
+            busy.extend(schedule.busy);
+        }
+    }
+
+    let slots = free_slots(&busy, &windows, duration);
+
+    if slots.is_empty() {
+        println!("{}", "No free slots found.".dimmed());
+        return Ok(());
+    }
+
+    let mut current_date = None;
+
+    for slot in slots.iter().take(limit) {
+        let date = slot.start.with_timezone(&Local).date_naive();
+        if current_date != Some(date) {
+            if current_date.is_some() {
+                println!();
+            }
+            println!("{}", format_date_label(date, locale).bold());
+            current_date = Some(date);
+        }
+
+        let start = format_time_only(&EventTime::DateTimeUtc(slot.start), locale);
+        let end = format_time_only(&EventTime::DateTimeUtc(slot.end), locale);
+        println!("  {} – {}", start, end.trim_start());
+    }
+
+    Ok(())
+}
+
+/// The calendar's connection (or the first one, starting with the default
+/// calendar) whose provider can look up other people's free/busy.
+async fn free_busy_connection(caldir: &Caldir, calendar: Option<&str>) -> Result<Connection> {
+    let slugs: Vec<String> = calendar.map(str::to_string).into_iter().collect();
+    let default_slug = caldir.config().default_calendar_slug();
+
+    let mut candidates: Vec<Connection> = connections(caldir, &slugs)
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect();
+    candidates.sort_by_key(|c| c.local().slug() != default_slug);
+
+    for connection in candidates {
+        if connection.remote().capabilities().await.free_busy {
+            return Ok(connection);
+        }
+    }
+
+    match calendar {
+        Some(slug) => bail!("The provider of '{slug}' can't look up other people's free/busy"),
+        None => bail!(
+            "None of your calendars' providers can look up other people's free/busy (Google can)"
+        ),
+    }
+}
+
+/// `today`, `tomorrow`, `this week`, `next week`, a number of days from
+/// today (e.g. `3d`), a date, or `FROM..TO` dates. Inclusive.
+pub(crate) fn parse_within(
+    input: &str,
+    today: NaiveDate,
+    week_start: WeekStart,
+) -> Result<(NaiveDate, NaiveDate)> {
+    let first = today - Duration::days(week_start.days_into_week(today.weekday()).into());
+
+    let range = match input.trim().to_lowercase().as_str() {
+        "today" => (today, today),
+        "tomorrow" => (today + Duration::days(1), today + Duration::days(1)),
+        "this week" => (today, first + Duration::days(6)),
+        "next week" => (first + Duration::days(7), first + Duration::days(13)),
+        other => {
+            if let Some((from, to)) = other.split_once("..") {
+                (parse_date(from)?, parse_date(to)?)
+            } else if let Ok(date) = parse_date(other) {
+                (date, date)
+            } else {
+                let span = humantime::parse_duration(other).map_err(|_| {
+                    anyhow::anyhow!(
+                        "Invalid --within: {input} (use e.g. \"next week\", 5d or 2026-03-02..2026-03-06)"
+                    )
+                })?;
+                let days = Duration::from_std(span)?.num_days().max(1);
+                (today, today + Duration::days(days - 1))
+            }
+        }
+    };
+
+    if range.1 < range.0 {
+        bail!("--within ends before it starts");
+    }
+
+    Ok(range)
+}
+
+/// `9-17` → (9, 17)
+pub(crate) fn parse_hours(input: &str) -> Result<(u32, u32)> {
+    let parsed = input
+        .split_once('-')
+        .and_then(|(start, end)| Some((start.trim().parse().ok()?, end.trim().parse().ok()?)));
+
+    match parsed {
+        Some((start, end)) if start < end && end <= 24 => Ok((start, end)),
+        _ => bail!("Invalid --hours: {input} (e.g. 9-17)"),
+    }
+}
+
+/// Working hours on the weekdays from `first_day` to `last_day`, from `now`
+/// on.
+pub(crate) fn working_windows<Tz: TimeZone>(
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+    start_hour: u32,
+    end_hour: u32,
+    tz: &Tz,
+    now: DateTime<Utc>,
+) -> Vec<TimePeriod> {
+    first_day
+        .iter_days()
+        .take_while(|day| *day <= last_day)
+        .filter(|day| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+        .filter_map(|day| {
+            let at = |hour: u32| {
+                let time = day.and_hms_opt(hour, 0, 0).or_else(|| {
+                    // 24 means midnight at the end of the day
+                    (day + Duration::days(1)).and_hms_opt(0, 0, 0)
+                })?;
+                Some(tz.from_local_datetime(&time).earliest()?.to_utc())
+            };
+            let start = at(start_hour)?.max(now);
+            let end = at(end_hour)?;
+            (start < end).then_some(TimePeriod { start, end })
+        })
+        .collect()
+}
+
+/// Slots of `duration` inside `windows` that overlap nothing in `busy`,
+/// starting on [`SLOT_STEP_MINUTES`] boundaries.
+pub(crate) fn free_slots(
+    busy: &[TimePeriod],
+    windows: &[TimePeriod],
+    duration: Duration,
+) -> Vec<TimePeriod> {
+    let step = Duration::minutes(SLOT_STEP_MINUTES);
+    let mut slots = Vec::new();
+
+    for window in windows {
+        let mut start = round_up(window.start, step);
+
+        while start + duration <= window.end {
+            let end = start + duration;
+            if !busy.iter().any(|period| period.overlaps(start, end)) {
+                slots.push(TimePeriod { start, end });
+            }
+            start += step;
+        }
+    }
+
+    slots
+}
+
+fn round_up(time: DateTime<Utc>, step: Duration) -> DateTime<Utc> {
+    let step_secs = step.num_seconds();
+    let secs = time.timestamp();
+    let rounded = (secs + step_secs - 1).div_euclid(step_secs) * step_secs;
+    DateTime::from_timestamp(rounded, 0).unwrap_or(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        date(2026, 3, 2)
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn within_understands_weeks_and_day_counts() {
+        // A Wednesday
+        let today = date(2026, 3, 4);
+
+        assert_eq!(
+            parse_within("next week", today, WeekStart::Monday).unwrap(),
+            (date(2026, 3, 9), date(2026, 3, 15))
+        );
+        assert_eq!(
+            parse_within("this week", today, WeekStart::Monday).unwrap(),
+            (today, date(2026, 3, 8))
+        );
+        assert_eq!(
+            parse_within("3d", today, WeekStart::Monday).unwrap(),
+            (today, date(2026, 3, 6))
+        );
+        assert!(parse_within("someday", today, WeekStart::Monday).is_err());
+        assert_eq!(
+            parse_within("next week", today, WeekStart::Sunday).unwrap(),
+            (date(2026, 3, 8), date(2026, 3, 14))
+        );
+    }
+
+    #[test]
+    fn working_windows_skip_weekends_and_the_past() {
+        // Friday to Monday, at 10:30 on Friday
+        let now = date(2026, 3, 6).and_hms_opt(10, 30, 0).unwrap().and_utc();
+        let windows = working_windows(date(2026, 3, 6), date(2026, 3, 9), 9, 17, &Utc, now);
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].start.format("%a %H:%M").to_string(), "Fri 10:30");
+        assert_eq!(windows[1].start.format("%a %H:%M").to_string(), "Mon 09:00");
+    }
+
+    #[test]
+    fn free_slots_avoid_busy_periods() {
+        let windows = [TimePeriod {
+            start: at(9, 0),
+            end: at(12, 0),
+        }];
+        let busy = [TimePeriod {
+            start: at(9, 15),
+            end: at(10, 45),
+        }];
+
+        let slots = free_slots(&busy, &windows, Duration::minutes(45));
+
+        let starts: Vec<String> = slots
+            .iter()
+            .map(|slot| slot.start.format("%H:%M").to_string())
+            .collect();
+        assert_eq!(starts, ["11:00"]);
+    }
+}
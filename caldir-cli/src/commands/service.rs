@@ -0,0 +1,361 @@
+//! `caldir service`: run `caldir sync` in the background on a timer, as a
+//! systemd user timer on Linux or a launchd agent on macOS.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use owo_colors::OwoColorize;
+
+use crate::utils::PathExt;
+
+const SYSTEMD_UNIT: &str = "caldir-sync";
+const LAUNCHD_LABEL: &str = "org.caldir.sync";
+
+/// Passed on to the background sync, so it finds the same providers and
+/// caldir home as the shell that installed it.
+const PASSED_ENV: &[&str] = &["PATH", "CALDIR_HOME"];
+
+enum Platform {
+    Systemd,
+    Launchd,
+}
+
+impl Platform {
+    fn current() -> Result<Self> {
+        if cfg!(target_os = "macos") {
+            Ok(Self::Launchd)
+        } else if cfg!(target_os = "linux") {
+            Ok(Self::Systemd)
+        } else {
+            bail!("`caldir service` supports Linux (systemd) and macOS (launchd)")
+        }
+    }
+}
+
+/// Sync every `every`, starting now. Installing again replaces the service.
+pub fn install(every: &str, caldir_home: Option<&Path>, profile: Option<&str>) -> Result<()> {
+    let interval = humantime::parse_duration(every)
+        .map_err(|_| anyhow::anyhow!("Invalid --every: {every} (e.g. 10m, 1h)"))?;
+    if interval < Duration::from_secs(60) {
+        bail!("--every must be at least a minute");
+    }
+
+    let exe = std::env::current_exe().context("Couldn't find the caldir executable")?;
+    let mut command = vec![exe.to_string_lossy().into_owned()];
+    if let Some(home) = caldir_home {
+        let home = std::path::absolute(home)?;
+        command.extend(["--caldir".to_string(), home.to_string_lossy().into_owned()]);
+    }
+    if let Some(profile) = profile {
+        command.extend(["--profile".to_string(), profile.to_string()]);
+    }
+    command.push("sync".to_string());
+
+    let env: Vec<(&str, String)> = PASSED_ENV
+        .iter()
+        .filter_map(|name| Some((*name, std::env::var(name).ok()?)))
+        .collect();
+
+    let installed = match Platform::current()? {
+        Platform::Systemd => {
+            let dir = systemd_dir()?;
+            std::fs::create_dir_all(&dir)?;
+
+            let service = dir.join(format!("{SYSTEMD_UNIT}.service"));
+            std::fs::write(&service, systemd_service(&command, &env))?;
+            std::fs::write(
+                dir.join(format!("{SYSTEMD_UNIT}.timer")),
+                systemd_timer(interval),
+            )?;
+
+            systemctl(&["daemon-reload"])?;
+            systemctl(&["enable", "--now", &format!("{SYSTEMD_UNIT}.timer")])?;
+            service
+        }
+        Platform::Launchd => {
+            let plist = launchd_plist_path()?;
+            std::fs::create_dir_all(plist.parent().unwrap_or(Path::new(".")))?;
+
+            // Replacing a loaded agent needs it unloaded first
+            if plist.exists() {
+                let _ = launchctl(&["unload", &plist.to_string_lossy()]);
+            }
+
+            std::fs::write(
+                &plist,
+                launchd_plist(&command, &env, interval, &log_path()?),
+            )?;
+            launchctl(&["load", "-w", &plist.to_string_lossy()])?;
+            plist
+        }
+    };
+
+    println!(
+        "{}",
+        format!(
+            "  Syncing every {} in the background",
+            humantime::format_duration(interval)
+        )
+        .green()
+    );
+    println!("{}", format!("  Installed {}", installed.tilde()).dimmed());
+
+    Ok(())
+}
+
+pub fn status() -> Result<()> {
+    match Platform::current()? {
+        Platform::Systemd => {
+            if !systemd_dir()?
+                .join(format!("{SYSTEMD_UNIT}.timer"))
+                .exists()
+            {
+                return not_installed();
+            }
+            // Shows when it last ran and runs next; fails if it isn't loaded
+            let _ = Command::new("systemctl")
+                .args(["--user", "list-timers", "--all", "--no-pager"])
+                .arg(format!("{SYSTEMD_UNIT}.timer"))
+                .status();
+            println!();
+            println!(
+                "{}",
+                format!("  Logs: journalctl --user -u {SYSTEMD_UNIT}").dimmed()
+            );
+        }
+        Platform::Launchd => {
+            if !launchd_plist_path()?.exists() {
+                return not_installed();
+            }
+            // Shows the last exit status; fails if it isn't loaded
+            let _ = Command::new("launchctl")
+                .args(["list", LAUNCHD_LABEL])
+                .status();
+            println!();
+            println!("{}", format!("  Logs: {}", log_path()?.tilde()).dimmed());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn uninstall() -> Result<()> {
+    match Platform::current()? {
+        Platform::Systemd => {
+            let dir = systemd_dir()?;
+            let timer = dir.join(format!("{SYSTEMD_UNIT}.timer"));
+            if !timer.exists() {
+                return not_installed();
+            }
+
+            systemctl(&["disable", "--now", &format!("{SYSTEMD_UNIT}.timer")])?;
+            std::fs::remove_file(&timer)?;
+            let service = dir.join(format!("{SYSTEMD_UNIT}.service"));
+            if service.exists() {
+                std::fs::remove_file(service)?;
+            }
+            systemctl(&["daemon-reload"])?;
+        }
+        Platform::Launchd => {
+            let plist = launchd_plist_path()?;
+            if !plist.exists() {
+                return not_installed();
+            }
+
+            let _ = launchctl(&["unload", "-w", &plist.to_string_lossy()]);
+            std::fs::remove_file(&plist)?;
+        }
+    }
+
+    println!("{}", "  Stopped syncing in the background".green());
+
+    Ok(())
+}
+
+fn not_installed() -> Result<()> {
+    println!(
+        "{}",
+        "Not installed. Run `caldir service install` to sync in the background.".dimmed()
+    );
+    Ok(())
+}
+
+fn home() -> Result<PathBuf> {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .context("$HOME isn't set")
+}
+
+fn systemd_dir() -> Result<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => home()?.join(".config"),
+    };
+    Ok(config.join("systemd").join("user"))
+}
+
+fn launchd_plist_path() -> Result<PathBuf> {
+    Ok(home()?
+        .join("Library/LaunchAgents")
+        .join(format!("{LAUNCHD_LABEL}.plist")))
+}
+
+fn log_path() -> Result<PathBuf> {
+    Ok(home()?.join("Library/Logs/caldir-sync.log"))
+}
+
+fn systemctl(args: &[&str]) -> Result<()> {
+    run("systemctl", &[&["--user"][..], args].concat())
+}
+
+fn launchctl(args: &[&str]) -> Result<()> {
+    run("launchctl", args)
+}
+
+fn run(program: &str, args: &[&str]) -> Result<()> {
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .with_context(|| format!("Couldn't run {program}"))?;
+
+    if !status.success() {
+        bail!("`{program} {}` failed ({status})", args.join(" "));
+    }
+    Ok(())
+}
+
+fn systemd_service(command: &[String], env: &[(&str, String)]) -> String {
+    // `%` starts a specifier in unit files
+    let quote = |s: &str| {
+        let escaped = s
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('%', "%%");
+        format!("\"{escaped}\"")
+    };
+
+    let exec_start: Vec<String> = command.iter().map(|arg| quote(arg)).collect();
+    let environment: String = env
+        .iter()
+        .map(|(name, value)| format!("Environment={}\n", quote(&format!("{name}={value}"))))
+        .collect();
+
+    format!(
+        "[Unit]\n\
+         Description=Sync caldir calendars\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         {environment}\
+         ExecStart={}\n",
+        exec_start.join(" ")
+    )
+}
+
+fn systemd_timer(interval: Duration) -> String {
+    format!(
+        "[Unit]\n\
+         Description=Sync caldir calendars every {}\n\
+         \n\
+         [Timer]\n\
+         OnActiveSec=0\n\
+         OnUnitActiveSec={}s\n\
+         \n\
+         [Install]\n\
+         WantedBy=timers.target\n",
+        humantime::format_duration(interval),
+        interval.as_secs()
+    )
+}
+
+fn launchd_plist(
+    command: &[String],
+    env: &[(&str, String)],
+    interval: Duration,
+    log: &Path,
+) -> String {
+    let string = |s: &str| format!("<string>{}</string>", escape_xml(s));
+
+    let arguments: String = command
+        .iter()
+        .map(|arg| format!("        {}\n", string(arg)))
+        .collect();
+    let environment: String = env
+        .iter()
+        .map(|(name, value)| format!("        <key>{name}</key>\n        {}\n", string(value)))
+        .collect();
+    let log = string(&log.to_string_lossy());
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{LAUNCHD_LABEL}</string>
+    <key>ProgramArguments</key>
+    <array>
+{arguments}    </array>
+    <key>EnvironmentVariables</key>
+    <dict>
+{environment}    </dict>
+    <key>StartInterval</key>
+    <integer>{}</integer>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>StandardOutPath</key>
+    {log}
+    <key>StandardErrorPath</key>
+    {log}
+</dict>
+</plist>
+"#,
+        interval.as_secs()
+    )
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Vec<String> {
+        ["/opt/my tools/caldir", "--profile", "work", "sync"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    #[test]
+    fn systemd_units_quote_the_command_and_run_on_an_interval() {
+        let service = systemd_service(&command(), &[("PATH", "/usr/bin:/bin".to_string())]);
+
+        assert!(
+            service
+                .contains("ExecStart=\"/opt/my tools/caldir\" \"--profile\" \"work\" \"sync\"\n")
+        );
+        assert!(service.contains("Environment=\"PATH=/usr/bin:/bin\"\n"));
+        assert!(systemd_timer(Duration::from_secs(600)).contains("OnUnitActiveSec=600s\n"));
+    }
+
+    #[test]
+    fn launchd_plist_escapes_its_strings() {
+        let plist = launchd_plist(
+            &command(),
+            &[("PATH", "/a&b".to_string())],
+            Duration::from_secs(600),
+            Path::new("/Users/me/Library/Logs/caldir-sync.log"),
+        );
+
+        assert!(plist.contains("<string>/opt/my tools/caldir</string>"));
+        assert!(plist.contains("<key>PATH</key>\n        <string>/a&amp;b</string>"));
+        assert!(plist.contains("<integer>600</integer>"));
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result, bail};
+use caldir_core::{Caldir, CalendarEvent, DateBounds, Event, EventTime, Status};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, Utc, Weekday};
+use owo_colors::OwoColorize;
+
+use crate::render::time::{Locale, format_datetime};
+use crate::utils::{parse_date, require_calendars, resolve_calendars};
+
+/// How far to move each event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shift {
+    By(Duration),
+    /// To this day of the event's own week
+    ToWeekday(Weekday),
+}
+
+/// `summary~standup` (contains) or `location=Room B` (is), ignoring case.
+#[derive(Debug, PartialEq)]
+struct Filter {
+    field: String,
+    value: String,
+    exact: bool,
+}
+
+const FILTER_FIELDS: &[&str] = &["summary", "description", "location", "tag", "attendee"];
+
+/// Moves the upcoming events matching every filter by a duration or to
+/// another weekday. They're rewritten with a bumped SEQUENCE, so the next
+/// push sends them as reschedules.
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    caldir: &Caldir,
+    filters: Vec<String>,
+    by: Option<String>,
+    weekday: Option<String>,
+    calendar: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let filters: Vec<Filter> = filters
+        .iter()
+        .map(|f| Filter::parse(f))
+        .collect::<Result<_>>()?;
+    let shift = match (by, weekday) {
+        (Some(by), None) => Shift::By(parse_offset(&by)?),
+        (None, Some(day)) => Shift::ToWeekday(
+            day.parse()
+                .map_err(|_| anyhow::anyhow!("Unknown weekday: {day}"))?,
+        ),
+        _ => bail!("Pass either --by or --weekday"),
+    };
+
+    let from = match from {
+        Some(s) => local_bound(
+            parse_date(&s)
+                .with_context(|| format!("invalid --from date: {s}"))?
+                .start_of_date(),
+        ),
+        None => Utc::now(),
+    };
+    let to = match to {
+        Some(s) => Some(local_bound(
+            parse_date(&s)
+                .with_context(|| format!("invalid --to date: {s}"))?
+                .end_of_date(),
+        )),
+        None => None,
+    };
+
+    let week_start = caldir.config().week_start();
+    let locale = Locale::from_config(caldir.config());
+    let mut shifted = 0;
+
+    for calendar in resolve_calendars(caldir, calendar.as_deref())? {
+        if calendar.is_read_only() {
+            continue;
+        }
+
+        let events = calendar.events()?;
+        let edited_series: HashSet<String> = events
+            .iter()
+            .filter(|ce| ce.event().recurrence_id.is_some())
+            .map(|ce| ce.event().uid.as_str().to_string())
+            .collect();
+
+        for mut ce in events {
+            let event = ce.event();
+            if event.status == Status::Cancelled || !filters.iter().all(|f| f.matches(event)) {
+                continue;
+            }
+
+            let starts = event.start.to_utc();
+            let in_range = starts >= from && to.is_none_or(|to| starts <= to);
+            let label = event.summary.as_deref().unwrap_or("(Untitled)");
+
+            if event.recurrence.is_some() {
+                let skip_reason = if !in_range {
+                    // A series that started earlier, but still has occurrences to move
+                    event
+                        .has_occurrence_in_range(from, to.unwrap_or(from + Duration::days(365)))
+                        .then_some("the series started before --from, and shifting it would move its past occurrences too")
+                } else if edited_series.contains(event.uid.as_str()) {
+                    Some("the series has edited occurrences")
+                } else {
+                    None
+                };
+
+                if let Some(reason) = skip_reason {
+                    println!(
+                        "{}",
+                        format!("  Skipped {label}: {reason}. Edit the series instead.").yellow()
+                    );
+                    continue;
+                }
+            }
+
+            if !in_range {
+                continue;
+            }
+
+            let by = match shift {
+                Shift::By(by) => by,
+                Shift::ToWeekday(day) => {
+                    let current = event.start.to_local_tz(&Local).date_naive().weekday();
+                    Duration::days(
+                        week_start.days_into_week(day) as i64
+                            - week_start.days_into_week(current) as i64,
+                    )
+                }
+            };
+
+            if by.is_zero() {
+                continue;
+            }
+            if event.start.is_date() && by.num_seconds() % 86_400 != 0 {
+                println!(
+                    "{}",
+                    format!("  Skipped {label}: all-day events only move by whole days").yellow()
+                );
+                continue;
+            }
+            if moves_rule_day(event, by) {
+                println!(
+                    "{}",
+                    format!("  Skipped {label}: its recurrence rule pins the days it falls on")
+                        .yellow()
+                );
+                continue;
+            }
+
+            let mut moved = event.clone();
+            moved.shift(by);
+            println!(
+                "  {} {} {} → {}",
+                "~".yellow(),
+                label.yellow(),
+                format_datetime(&event.start, locale).dimmed(),
+                format_datetime(&moved.start, locale)
+            );
+
+            if !dry_run {
+                save(&mut ce, moved)?;
+            }
+            shifted += 1;
+        }
+    }
+
+    let noun = if shifted == 1 { "event" } else { "events" };
+    if dry_run {
+        println!("{}", format!("  Would shift {shifted} {noun}").dimmed());
+    } else if shifted > 0 {
+        println!("{}", format!("  Shifted {shifted} {noun}").green());
+        println!("{}", "  Run `caldir push` to sync them.".dimmed());
+    } else {
+        println!("{}", "  No matching events to shift".dimmed());
+    }
+
+    Ok(())
+}
+
+fn save(ce: &mut CalendarEvent, mut event: Event) -> Result<()> {
+    event.sequence += 1;
+    event.last_modified = Some(Utc::now());
+    ce.update(event)?;
+    Ok(())
+}
+
+/// `2026-04-01 00:00` on the local clock.
+fn local_bound(datetime: NaiveDateTime) -> DateTime<Utc> {
+    datetime
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+/// `30m`, `2h`, `1 day`, or `-15m` to move events earlier.
+fn parse_offset(input: &str) -> Result<Duration> {
+    let (sign, magnitude) = match input.trim().strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, input.trim().trim_start_matches('+')),
+    };
+
+    let duration = humantime::parse_duration(magnitude.trim())
+        .with_context(|| format!("Could not parse --by: \"{input}\""))?;
+    let duration = Duration::from_std(duration).context("--by is too large")?;
+
+    Ok(duration * sign)
+}
+
+/// A shift to another day would leave a BYDAY/BYMONTHDAY-style rule
+/// producing the old days.
+fn moves_rule_day(event: &Event, by: Duration) -> bool {
+    let Some(recurrence) = &event.recurrence else {
+        return false;
+    };
+    let day = |time: &EventTime| time.to_local_tz(&Local).date_naive();
+
+    recurrence.rrule.to_uppercase().contains("BY")
+        && day(&event.start) != day(&event.start.shifted(by))
+}
+
+impl Filter {
+    fn parse(input: &str) -> Result<Self> {
+        let Some(at) = input.find(['~', '=']) else {
+            bail!("Invalid --filter: \"{input}\" (use field~text or field=text)");
+        };
+
+        let field = input[..at].trim().to_lowercase();
+        if !FILTER_FIELDS.contains(&field.as_str()) {
+            bail!(
+                "Unknown --filter field: {field} (available: {})",
+                FILTER_FIELDS.join(", ")
+            );
+        }
+
+        Ok(Filter {
+            field,
+            value: input[at + 1..].trim().to_lowercase(),
+            exact: input[at..].starts_with('='),
+        })
+    }
+
+    fn matches(&self, event: &Event) -> bool {
+        let matches = |text: &str| {
+            let text = text.to_lowercase();
+            if self.exact {
+                text == self.value
+            } else {
+                text.contains(&self.value)
+            }
+        };
+
+        match self.field.as_str() {
+            "summary" => event.summary.as_deref().is_some_and(matches),
+            "description" => event.description.as_deref().is_some_and(matches),
+            "location" => event.location.as_deref().is_some_and(matches),
+            "tag" => event.categories.iter().any(|tag| matches(tag)),
+            "attendee" => event.attendees.iter().any(|a| matches(&a.email)),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_caldir;
+    use chrono::{NaiveDate, Timelike};
+
+    fn event_at(summary: &str, year: i32, month: u32, day: u32, hour: u32) -> Event {
+        let start = NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap();
+        let mut event = Event::new(summary, EventTime::DateTimeFloating(start));
+        event.end = Some(EventTime::DateTimeFloating(start + Duration::hours(1)));
+        event
+    }
+
+    #[test]
+    fn filter_parses_contains_and_exact() {
+        let contains = Filter::parse("summary~Standup").unwrap();
+        let exact = Filter::parse("location=Room B").unwrap();
+        let standup = event_at("Daily standup", 2030, 1, 7, 9);
+
+        assert!(contains.matches(&standup));
+        assert!(!exact.matches(&standup));
+        assert!(Filter::parse("summary").is_err());
+        assert!(Filter::parse("room~B").is_err());
+    }
+
+    #[test]
+    fn parse_offset_takes_a_sign() {
+        assert_eq!(parse_offset("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_offset("-1h").unwrap(), Duration::hours(-1));
+        assert!(parse_offset("later").is_err());
+    }
+
+    #[test]
+    fn shifts_matching_events_after_from_and_bumps_sequence() {
+        let (_tmp, caldir) = test_caldir();
+        let calendar = caldir.calendar("personal").unwrap();
+        calendar
+            .create_event(event_at("Standup", 2030, 1, 7, 9))
+            .unwrap();
+        calendar
+            .create_event(event_at("Standup", 2029, 12, 31, 9))
+            .unwrap();
+        calendar
+            .create_event(event_at("Lunch", 2030, 1, 7, 12))
+            .unwrap();
+
+        run(
+            &caldir,
+            vec!["summary~standup".to_string()],
+            Some("30m".to_string()),
+            None,
+            None,
+            Some("2030-01-01".to_string()),
+            None,
+            false,
+        )
+        .unwrap();
+
+        let events: Vec<Event> = calendar
+            .events()
+            .unwrap()
+            .into_iter()
+            .map(|ce| ce.event().clone())
+            .collect();
+        let start_of = |summary: &str, year: i32| {
+            events
+                .iter()
+                .find(|e| e.summary.as_deref() == Some(summary) && e.start.to_utc().year() == year)
+                .unwrap()
+        };
+
+        let moved = start_of("Standup", 2030);
+        assert_eq!(moved.start.to_utc().minute(), 30);
+        assert_eq!(moved.sequence, 1);
+        assert_eq!(start_of("Standup", 2029).sequence, 0);
+        assert_eq!(start_of("Lunch", 2030).sequence, 0);
+    }
+}
@@ -0,0 +1,167 @@
+use anyhow::{Context, Result, bail};
+use caldir_core::{
+    Caldir, Calendar, DateBounds, Event, EventInstanceId, EventTime, RecurrenceId, expand_in_range,
+};
+use chrono::{Duration, NaiveDate};
+use owo_colors::OwoColorize;
+
+use crate::render::time::{Locale, format_datetime};
+use crate::utils::{parse_date, require_calendars, resolve_calendars, resolve_event_uid};
+
+/// Leaves one occurrence out of a recurring series by adding an EXDATE to
+/// its master. `event` is the master's file name, UID or title.
+pub fn run(caldir: &Caldir, event: String, date: String, calendar: Option<String>) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let date = parse_date(&date).with_context(|| format!("invalid date: {date}"))?;
+    let calendars = resolve_calendars(caldir, calendar.as_deref())?;
+    let (calendar, master) = find_master(&calendars, &event)?;
+
+    if calendar.is_read_only() {
+        bail!(
+            "{} is read-only",
+            calendar.slug().unwrap_or("This calendar")
+        );
+    }
+
+    let label = master.summary.as_deref().unwrap_or("(Untitled)");
+    let Some(occurrence) = occurrence_on(&master, date) else {
+        bail!("{label} doesn't occur on {date}");
+    };
+
+    calendar.delete_recurring_instance(&EventInstanceId::new(
+        master.uid.clone(),
+        Some(occurrence.clone()),
+    ))?;
+
+    let locale = Locale::from_config(caldir.config());
+    println!(
+        "{}",
+        format!(
+            "  Skipped {label} on {}",
+            format_datetime(occurrence.as_event_time(), locale)
+        )
+        .green()
+    );
+    println!("{}", "  Run `caldir push` to sync it.".dimmed());
+
+    Ok(())
+}
+
+/// The one recurring master `arg` names, by file name or UID, else by title.
+fn find_master<'a>(calendars: &'a [Calendar], arg: &str) -> Result<(&'a Calendar, Event)> {
+    let mut masters: Vec<(&Calendar, Event)> = Vec::new();
+
+    for cal in calendars {
+        let uid = resolve_event_uid(cal, arg);
+        for ce in cal.events()? {
+            let event = ce.event();
+            let named = event.uid.as_str() == uid
+                || event
+                    .summary
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(arg));
+
+            if event.recurrence.is_some() && named {
+                masters.push((cal, event.clone()));
+            }
+        }
+    }
+
+    match masters.len() {
+        0 => bail!("No recurring event matches \"{arg}\""),
+        1 => Ok(masters.remove(0)),
+        _ => bail!(
+            "Several recurring events match \"{arg}\", pass a file name or UID instead:\n{}",
+            masters
+                .iter()
+                .map(|(cal, e)| format!("  {} ({})", e.uid.as_str(), cal.slug().unwrap_or("?")))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+    }
+}
+
+/// The recurrence id of `master`'s occurrence on `date`, on the master's
+/// own clock rather than the system's.
+fn occurrence_on(master: &Event, date: NaiveDate) -> Option<RecurrenceId> {
+    // A day either side covers any timezone the series is in
+    let from = (date - Duration::days(1)).start_of_date().and_utc();
+    let to = (date + Duration::days(1)).end_of_date().and_utc();
+
+    expand_in_range([master.clone()], from, to)
+        .into_iter()
+        .filter_map(|occurrence| occurrence.recurrence_id)
+        .find(|rid| wall_date(rid.as_event_time()) == date)
+}
+
+fn wall_date(time: &EventTime) -> NaiveDate {
+    match time {
+        EventTime::Date(date) => *date,
+        EventTime::DateTimeUtc(datetime) => datetime.date_naive(),
+        EventTime::DateTimeFloating(datetime) | EventTime::DateTimeZoned { datetime, .. } => {
+            datetime.date()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_caldir;
+    use caldir_core::Recurrence;
+
+    fn at_eight_in_tokyo(day: u32) -> EventTime {
+        EventTime::DateTimeZoned {
+            datetime: NaiveDate::from_ymd_opt(2030, 1, day)
+                .unwrap()
+                .and_hms_opt(8, 0, 0)
+                .unwrap(),
+            tzid: "Asia/Tokyo".to_string(),
+        }
+    }
+
+    #[test]
+    fn adds_an_exdate_on_the_series_own_clock() {
+        let (_tmp, caldir) = test_caldir();
+        let calendar = caldir.calendar("personal").unwrap();
+        // Monday 08:00 in Tokyo is still Sunday in UTC
+        let mut standup = Event::new("Standup", at_eight_in_tokyo(7));
+        standup.recurrence = Some(Recurrence::new("FREQ=WEEKLY"));
+        calendar.create_event(standup).unwrap();
+
+        run(
+            &caldir,
+            "standup".to_string(),
+            "2030-01-14".to_string(),
+            None,
+        )
+        .unwrap();
+
+        let master = calendar.events().unwrap().remove(0).event().clone();
+        assert_eq!(
+            master.recurrence.unwrap().exdates,
+            vec![at_eight_in_tokyo(14)]
+        );
+        assert_eq!(master.sequence, 1);
+    }
+
+    #[test]
+    fn fails_when_the_series_has_no_occurrence_that_day() {
+        let (_tmp, caldir) = test_caldir();
+        let calendar = caldir.calendar("personal").unwrap();
+        let mut standup = Event::new("Standup", at_eight_in_tokyo(7));
+        standup.recurrence = Some(Recurrence::new("FREQ=WEEKLY"));
+        calendar.create_event(standup).unwrap();
+
+        let err = run(
+            &caldir,
+            "Standup".to_string(),
+            "2030-01-15".to_string(),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("doesn't occur"));
+        assert!(run(&caldir, "Lunch".to_string(), "2030-01-14".to_string(), None).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use caldir_core::Caldir;
+use owo_colors::OwoColorize;
+
+use crate::render::time::{Locale, format_timestamp};
+use crate::utils::PathExt;
+
+pub fn run(caldir: &Caldir, list: bool) -> Result<()> {
+    if list {
+        return print_snapshots(caldir);
+    }
+
+    let snapshot = caldir.snapshot()?;
+
+    println!("Saved snapshot {}", snapshot.id().bold());
+    println!("   {}", snapshot.path().tilde().dimmed());
+    println!(
+        "   {}",
+        format!("Restore it with: caldir restore {}", snapshot.id()).dimmed()
+    );
+
+    Ok(())
+}
+
+fn print_snapshots(caldir: &Caldir) -> Result<()> {
+    let snapshots = caldir.snapshots()?;
+
+    if snapshots.is_empty() {
+        println!("No snapshots yet. Take one with: caldir snapshot");
+        return Ok(());
+    }
+
+    let locale = Locale::from_config(caldir.config());
+
+    for snapshot in snapshots {
+        let created_at = snapshot
+            .created_at()
+            .map(|time| format_timestamp(time, locale))
+            .unwrap_or_default();
+
+        println!("{}  {}", snapshot.id(), created_at.dimmed());
+    }
+
+    Ok(())
+}
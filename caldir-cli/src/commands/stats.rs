@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, bail};
+use caldir_core::{Caldir, Event, EventTime, QueryMatch};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+
+use crate::commands::events::resolve_range;
+use crate::utils::{display_timezone, require_calendars, resolve_calendars};
+
+/// What `caldir stats` adds time up by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GroupBy {
+    Calendar,
+    Tag,
+    Attendee,
+}
+
+impl GroupBy {
+    fn parse(input: &str) -> Result<Self> {
+        match input {
+            "calendar" => Ok(Self::Calendar),
+            "tag" => Ok(Self::Tag),
+            "attendee" => Ok(Self::Attendee),
+            other => bail!("Unknown --group-by: {other} (use calendar, tag or attendee)"),
+        }
+    }
+
+    /// The groups `m` counts towards. An event with several tags or
+    /// attendees counts fully towards each of them.
+    fn keys(self, m: &QueryMatch) -> Vec<String> {
+        let keys: Vec<String> = match self {
+            Self::Calendar => vec![m.calendar.clone()],
+            Self::Tag => m.event.categories.clone(),
+            Self::Attendee => m
+                .event
+                .attendees
+                .iter()
+                .map(|attendee| attendee.email.to_lowercase())
+                .collect(),
+        };
+
+        if !keys.is_empty() {
+            return keys;
+        }
+
+        match self {
+            Self::Calendar => keys,
+            Self::Tag => vec!["(untagged)".to_string()],
+            Self::Attendee => vec!["(no attendees)".to_string()],
+        }
+    }
+}
+
+/// Time spent in one group.
+#[derive(Debug, PartialEq)]
+struct Row {
+    name: String,
+    minutes: i64,
+    events: usize,
+}
+
+/// Adds up the time spent in busy events between `from` and `to`, grouped
+/// by calendar, tag or attendee.
+pub fn run(
+    caldir: &Caldir,
+    from: Option<String>,
+    to: Option<String>,
+    group_by: String,
+    json: bool,
+) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let group_by = GroupBy::parse(&group_by)?;
+    let tz = display_timezone(caldir, None)?;
+    let (from, to) = resolve_range(
+        Utc::now().with_timezone(&tz),
+        from.as_deref(),
+        to.as_deref(),
+    )?;
+
+    // An event in two calendars (e.g. an invite) counts once
+    let mut busy = Vec::new();
+    let mut seen = HashSet::new();
+
+    for cal in resolve_calendars(caldir, None)? {
+        let slug = cal.slug().unwrap_or_default().to_string();
+
+        for event in cal.busy_events(from, to)? {
+            if seen.insert(event.event_instance_id()) {
+                busy.push(QueryMatch {
+                    calendar: slug.clone(),
+                    event,
+                });
+            }
+        }
+    }
+
+    let rows = aggregate(&busy, group_by, from, to);
+
+    if json {
+        let rows: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|row| {
+                serde_json::json!({
+                    "name": row.name,
+                    "hours": row.minutes as f64 / 60.0,
+                    "events": row.events,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("{}", "No busy events in this range".dimmed());
+        return Ok(());
+    }
+
+    let width = rows
+        .iter()
+        .map(|row| row.name.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max("Total".len());
+
+    for row in &rows {
+        let events = if row.events == 1 { "event" } else { "events" };
+        println!(
+            "{:<width$}  {:>7}  {}",
+            row.name,
+            format_hours(row.minutes),
+            format!("{} {events}", row.events).dimmed(),
+        );
+    }
+
+    let total: i64 = busy
+        .iter()
+        .map(|m| minutes_in_range(&m.event, from, to))
+        .sum();
+    println!(
+        "{:<width$}  {:>7}  {}",
+        "Total".bold(),
+        format_hours(total).bold(),
+        format!("{} events", busy.len()).dimmed(),
+    );
+
+    Ok(())
+}
+
+/// Minutes per group, most time first. Events only count for the part of
+/// them that's between `from` and `to`.
+fn aggregate(
+    busy: &[QueryMatch],
+    group_by: GroupBy,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<Row> {
+    let mut groups: HashMap<String, (i64, usize)> = HashMap::new();
+
+    for m in busy {
+        let minutes = minutes_in_range(&m.event, from, to);
+
+        for key in group_by.keys(m) {
+            let group = groups.entry(key).or_default();
+            group.0 += minutes;
+            group.1 += 1;
+        }
+    }
+
+    let mut rows: Vec<Row> = groups
+        .into_iter()
+        .map(|(name, (minutes, events))| Row {
+            name,
+            minutes,
+            events,
+        })
+        .collect();
+
+    rows.sort_by(|a, b| b.minutes.cmp(&a.minutes).then_with(|| a.name.cmp(&b.name)));
+    rows
+}
+
+fn minutes_in_range(event: &Event, from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+    let start = event.start.to_utc().max(from);
+    let end = event.end.as_ref().map_or(start, EventTime::to_utc).min(to);
+
+    (end - start).num_minutes().max(0)
+}
+
+/// 90 → "1.5h"
+fn format_hours(minutes: i64) -> String {
+    format!("{:.1}h", minutes as f64 / 60.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use caldir_core::Attendee;
+    use chrono::{Duration, TimeZone};
+
+    fn busy(calendar: &str, start: DateTime<Utc>, minutes: i64) -> QueryMatch {
+        let mut event = Event::new("Meeting", EventTime::DateTimeUtc(start));
+        event.end = Some(EventTime::DateTimeUtc(start + Duration::minutes(minutes)));
+        QueryMatch {
+            calendar: calendar.to_string(),
+            event,
+        }
+    }
+
+    #[test]
+    fn adds_up_time_per_calendar_most_first() {
+        let from = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let to = from + Duration::days(7);
+        let events = vec![
+            busy("home", from + Duration::hours(9), 30),
+            busy("work", from + Duration::hours(10), 60),
+            busy("work", from + Duration::hours(14), 30),
+        ];
+
+        let rows = aggregate(&events, GroupBy::Calendar, from, to);
+
+        assert_eq!(
+            rows,
+            vec![
+                Row {
+                    name: "work".to_string(),
+                    minutes: 90,
+                    events: 2,
+                },
+                Row {
+                    name: "home".to_string(),
+                    minutes: 30,
+                    events: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_events_towards_each_tag_and_attendee() {
+        let from = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let to = from + Duration::days(1);
+        let mut tagged = busy("work", from + Duration::hours(9), 60);
+        tagged.event.categories = vec!["planning".to_string(), "team".to_string()];
+        tagged.event.attendees = vec![Attendee::new("Alice@example.com")];
+        let untagged = busy("work", from + Duration::hours(11), 30);
+
+        let tags = aggregate(&[tagged.clone(), untagged.clone()], GroupBy::Tag, from, to);
+        let attendees = aggregate(&[tagged, untagged], GroupBy::Attendee, from, to);
+
+        let names: Vec<&str> = tags.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, vec!["planning", "team", "(untagged)"]);
+        let names: Vec<&str> = attendees.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, vec!["alice@example.com", "(no attendees)"]);
+    }
+
+    #[test]
+    fn only_counts_the_part_inside_the_range() {
+        let from = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let to = from + Duration::days(1);
+        let overnight = busy("work", from - Duration::hours(1), 120);
+
+        let rows = aggregate(&[overnight], GroupBy::Calendar, from, to);
+
+        assert_eq!(rows[0].minutes, 60);
+        assert_eq!(format_hours(rows[0].minutes), "1.0h");
+    }
+}
@@ -3,15 +3,20 @@ use caldir_core::{Caldir, CaldirError, Connection, DateRange};
 use owo_colors::OwoColorize;
 
 use crate::render::diff::{CalendarDiffRender, Render};
+use crate::render::ics_diff::render_ics_diffs;
 use crate::utils::tui;
-use crate::utils::{require_calendars, resolve_sync_range};
+use crate::utils::{error_hint, require_calendars, resolve_sync_range};
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     caldir: &Caldir,
     calendar: Option<String>,
     from: Option<String>,
     to: Option<String>,
     verbose: bool,
+    offline: bool,
+    redact: bool,
+    show_diff: bool,
 ) -> Result<()> {
     require_calendars(caldir)?;
 
@@ -27,7 +32,16 @@ pub async fn run(
 
     let range = resolve_sync_range(from, to)?;
 
-    run_parsed(caldir, connections, range, verbose).await
+    run_parsed(
+        caldir,
+        connections,
+        range,
+        verbose,
+        offline,
+        redact,
+        show_diff,
+    )
+    .await
 }
 
 async fn run_parsed(
@@ -35,6 +49,9 @@ async fn run_parsed(
     connections: Vec<Result<Connection, CaldirError>>,
     range: DateRange,
     verbose: bool,
+    offline: bool,
+    redact: bool,
+    show_diff: bool,
 ) -> Result<()> {
     let total = connections.len();
 
@@ -47,15 +64,51 @@ async fn run_parsed(
                 } else {
                     cal.render(caldir)
                 };
-                let spinner = tui::create_spinner(header.clone());
-                let result = connection.diff(&range).await;
-                spinner.finish_and_clear();
+
+                let result = if offline {
+                    connection.offline_diff(&range)
+                } else {
+                    let spinner = tui::create_spinner(header.clone());
+                    let result = connection
+                        .diff_with_progress(&range, &tui::fetch_progress(&spinner, &header))
+                        .await;
+                    spinner.finish_and_clear();
+                    result
+                };
 
                 println!("{}", header);
 
+                // Can't reach the provider: still show what's waiting to be pushed
+                let result = match result {
+                    Err(e) if !offline && e.is_unreachable() => {
+                        println!(
+                            "   {}",
+                            format!("Offline ({e}), showing local changes only").dimmed()
+                        );
+                        connection.offline_diff(&range)
+                    }
+                    result => result,
+                };
+
                 match result {
-                    Ok(diff) => println!("{}", diff.render(verbose, caldir)),
-                    Err(e) => println!("   {}", e.to_string().red()),
+                    Ok(mut diff) => {
+                        if redact {
+                            diff.redact();
+                        }
+                        println!("{}", diff.render(verbose, caldir));
+                        if show_diff {
+                            let ics_diffs = render_ics_diffs(&diff, caldir);
+                            if !ics_diffs.is_empty() {
+                                println!("\n{ics_diffs}");
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        println!("   {}", e.to_string().red());
+                        if let Some(hint) = error_hint(&connection, e.code()) {
+                            println!("   {}", hint.dimmed());
+                        }
+                    }
                 }
             }
             Err(e) => {
@@ -0,0 +1,179 @@
+use anyhow::Result;
+use caldir_core::Caldir;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::commands::upcoming::{Found, minutes_until, next_event};
+use crate::render::time::{Locale, format_time_in, humanize_minutes};
+use crate::utils::display_timezone;
+
+/// How far ahead the status bar looks for the next event.
+const LOOKAHEAD_HOURS: i64 = 24;
+
+/// Titles longer than this are cut off.
+const MAX_TITLE_CHARS: usize = 30;
+
+/// What a status bar shows about the next event.
+#[derive(Debug, PartialEq)]
+struct Status {
+    text: String,
+    tooltip: String,
+    imminent: bool,
+}
+
+/// One line about the next event for polybar, tmux and the like, or a
+/// waybar custom module's JSON. Reads only local files (through the
+/// index), so it's quick enough to run every few seconds.
+pub fn run(
+    caldir: &Caldir,
+    calendar: Option<String>,
+    waybar: bool,
+    imminent: String,
+) -> Result<()> {
+    let imminent = humantime::parse_duration(&imminent)
+        .map_err(|_| anyhow::anyhow!("Invalid --imminent: {imminent} (e.g. 10m)"))?;
+    let imminent = Duration::from_std(imminent)?;
+
+    let tz = display_timezone(caldir, None)?;
+    let locale = Locale::from_config(caldir.config());
+    let now = Utc::now();
+
+    let next = next_event(
+        caldir,
+        calendar.as_deref(),
+        now,
+        Duration::hours(LOOKAHEAD_HOURS),
+    )?;
+    let status = status(next.as_ref(), now, imminent, locale, tz);
+
+    if waybar {
+        let json = serde_json::json!({
+            "text": status.text,
+            "tooltip": status.tooltip,
+            "class": if status.imminent { "imminent" } else { "upcoming" },
+        });
+        println!("{json}");
+    } else {
+        println!("{}", status.text);
+    }
+
+    Ok(())
+}
+
+fn status(
+    next: Option<&Found>,
+    now: DateTime<Utc>,
+    imminent: Duration,
+    locale: Locale,
+    tz: chrono_tz::Tz,
+) -> Status {
+    let Some(Found {
+        calendar, event, ..
+    }) = next
+    else {
+        return Status {
+            text: String::new(),
+            tooltip: "No events in the next 24 hours".to_string(),
+            imminent: false,
+        };
+    };
+
+    let summary = event.summary.as_deref().unwrap_or("(Untitled)");
+    let title: String = if summary.chars().count() > MAX_TITLE_CHARS {
+        let cut: String = summary.chars().take(MAX_TITLE_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        summary.to_string()
+    };
+
+    let start = event.start.to_utc();
+    let time = format_time_in(&event.start, locale, &tz);
+    let countdown = humanize_minutes(minutes_until(now, start));
+
+    let mut tooltip = format!("{summary}\n{} [{calendar}]", time.trim());
+    if let Some(location) = &event.location {
+        tooltip.push_str(&format!("\n{location}"));
+    }
+
+    Status {
+        text: format!("{} {title} (in {countdown})", time.trim()),
+        tooltip,
+        imminent: start - now <= imminent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use caldir_core::{Event, EventTime};
+    use chrono::TimeZone;
+
+    fn next(summary: &str, start: DateTime<Utc>) -> Found {
+        Found {
+            calendar: "work".to_string(),
+            color: None,
+            event: Event::new(summary, EventTime::DateTimeUtc(start)),
+        }
+    }
+
+    #[test]
+    fn shows_the_next_event_and_marks_it_imminent_when_close() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 13, 50, 0).unwrap();
+        let review = next("Design review", now + Duration::minutes(10));
+        let later = next("Design review", now + Duration::minutes(70));
+
+        let soon = status(
+            Some(&review),
+            now,
+            Duration::minutes(10),
+            Locale::default(),
+            chrono_tz::UTC,
+        );
+        let not_yet = status(
+            Some(&later),
+            now,
+            Duration::minutes(10),
+            Locale::default(),
+            chrono_tz::UTC,
+        );
+
+        assert_eq!(soon.text, "14:00 Design review (in 10m)");
+        assert!(soon.imminent);
+        assert_eq!(not_yet.text, "15:00 Design review (in 1h10m)");
+        assert!(!not_yet.imminent);
+    }
+
+    #[test]
+    fn cuts_off_long_titles() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 13, 0, 0).unwrap();
+        let long = next(
+            "Quarterly planning with the whole product organisation",
+            now + Duration::hours(1),
+        );
+
+        let status = status(
+            Some(&long),
+            now,
+            Duration::minutes(10),
+            Locale::default(),
+            chrono_tz::UTC,
+        );
+
+        assert_eq!(status.text, "14:00 Quarterly planning with the w… (in 1h)");
+    }
+
+    #[test]
+    fn is_empty_without_an_upcoming_event() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 13, 0, 0).unwrap();
+
+        let status = status(
+            None,
+            now,
+            Duration::minutes(10),
+            Locale::default(),
+            chrono_tz::UTC,
+        );
+
+        assert_eq!(status.text, "");
+        assert!(!status.imminent);
+    }
+}
@@ -1,12 +1,17 @@
 use anyhow::Result;
-use caldir_core::{Caldir, Connection, DateRange};
+use caldir_core::{Caldir, Connection, DateRange, EventChange};
 use owo_colors::OwoColorize;
 
 use crate::render::diff::{CalendarDiffRender, Render};
-use crate::utils::{allow_mass_delete, connections, count_changes, resolve_sync_range, tui};
+use crate::render::plan::render_plan;
+use crate::utils::{
+    allow_mass_delete, confirm_pull_deletes, connections, count_changes, error_hint, notify_for,
+    parse_notify, resolve_sync_range, send_invitations, tui,
+};
 
 type Counts = (usize, usize, usize);
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     caldir: &Caldir,
     calendar: Option<String>,
@@ -14,27 +19,36 @@ pub async fn run(
     to: Option<String>,
     verbose: bool,
     force: bool,
+    notify: Option<String>,
+    dry_run: bool,
 ) -> Result<()> {
+    let notify = parse_notify(notify.as_deref())?;
     let calendar_slugs: Vec<String> = calendar.into_iter().collect();
     let connections = connections(caldir, &calendar_slugs);
     let range = resolve_sync_range(from, to)?;
     let mut pulled: Counts = (0, 0, 0);
     let mut pushed: Counts = (0, 0, 0);
+    let mut synced: Vec<Connection> = Vec::new();
+    let mut changed: Vec<EventChange> = Vec::new();
     let total = connections.len();
 
     for (i, connection) in connections.into_iter().enumerate() {
         match connection {
             Ok(mut connection) => {
+                connection.set_notify_attendees(notify_for(&connection, notify));
                 sync_connection(
                     caldir,
                     &mut connection,
                     &range,
                     verbose,
                     force,
+                    dry_run,
                     &mut pulled,
                     &mut pushed,
+                    &mut changed,
                 )
                 .await;
+                synced.push(connection);
             }
             Err(e) => println!("   {}", e.to_string().red()),
         }
@@ -44,6 +58,17 @@ pub async fn run(
         }
     }
 
+    if !dry_run {
+        if let Err(e) = caldir.refresh_views() {
+            println!("{}", e.to_string().yellow());
+        }
+        if let Err(e) = caldir.update_contacts(&changed) {
+            println!("{}", e.to_string().yellow());
+        } else if let Err(e) = caldir.look_up_contact_names(&synced).await {
+            println!("{}", e.to_string().yellow());
+        }
+    }
+
     if pulled != (0, 0, 0) || pushed != (0, 0, 0) {
         println!();
     }
@@ -72,28 +97,60 @@ async fn sync_connection(
     range: &DateRange,
     verbose: bool,
     force: bool,
+    dry_run: bool,
     pulled: &mut Counts,
     pushed: &mut Counts,
+    changed: &mut Vec<EventChange>,
 ) {
     let header = connection.local().render(caldir);
     let spinner = tui::create_spinner(header.clone());
-    let result = connection.diff(range).await;
+    let result = connection
+        .diff_with_progress(range, &tui::fetch_progress(&spinner, &header))
+        .await;
     spinner.finish_and_clear();
 
     println!("{}", header);
 
-    let diff = match result {
+    let mut diff = match result {
         Ok(diff) => diff,
         Err(e) => {
             println!("   {}", e.to_string().red());
+            if let Some(hint) = error_hint(connection, e.code()) {
+                println!("   {}", hint.dimmed());
+            }
             return;
         }
     };
 
+    if force {
+        diff.overwrite_conflicts();
+    }
+
     println!("{}", diff.render(verbose, caldir));
 
+    if dry_run {
+        let steps = connection.plan_incoming_diff(&diff).and_then(|mut steps| {
+            if allow_mass_delete(&diff, force) {
+                steps.extend(connection.plan_outgoing_diff(&diff)?);
+            }
+            Ok(steps)
+        });
+        match steps {
+            Ok(steps) => println!("{}", render_plan(&steps)),
+            Err(e) => println!("   {}", e.to_string().red()),
+        }
+        return;
+    }
+
+    if !confirm_pull_deletes(&diff, caldir, force) {
+        return;
+    }
+
     match connection.apply_incoming_diff(&diff) {
-        Ok(()) => add_counts(pulled, count_changes(diff.incoming())),
+        Ok(()) => {
+            add_counts(pulled, count_changes(diff.incoming()));
+            changed.extend(diff.incoming().iter().cloned());
+        }
         Err(e) => println!("   {}", e.to_string().red()),
     }
 
@@ -102,7 +159,11 @@ async fn sync_connection(
     }
 
     match connection.apply_outgoing_diff(&diff).await {
-        Ok(()) => add_counts(pushed, count_changes(diff.outgoing())),
+        Ok(()) => {
+            send_invitations(caldir, connection, &diff).await;
+            add_counts(pushed, count_changes(diff.outgoing()));
+            changed.extend(diff.outgoing().iter().cloned());
+        }
         Err(e) => println!("   {}", e.to_string().red()),
     }
 }
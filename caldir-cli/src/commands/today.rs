@@ -4,18 +4,37 @@ use caldir_core::DateBounds;
 use chrono::{DateTime, TimeZone, Utc};
 
 use crate::render::events_in_range::render_events_in_range;
-use crate::utils::{require_calendars, resolve_calendars};
-
-pub fn run(caldir: &Caldir, calendar: Option<String>) -> Result<()> {
+use crate::render::template::Template;
+use crate::utils::{display_timezone, require_calendars, resolve_calendars};
+
+pub fn run(
+    caldir: &Caldir,
+    calendar: Option<String>,
+    tags: Vec<String>,
+    hide_declined: bool,
+    tz: Option<String>,
+    format: Option<String>,
+) -> Result<()> {
     require_calendars(caldir)?;
 
+    let template = format.as_deref().map(Template::parse).transpose()?;
+
     let calendars = resolve_calendars(caldir, calendar.as_deref())?;
 
-    let tz: chrono_tz::Tz = iana_time_zone::get_timezone()?.parse()?;
+    let tz = display_timezone(caldir, tz.as_deref())?;
 
     let (from, to) = day_range(Utc::now().with_timezone(&tz));
 
-    render_events_in_range(caldir, calendars, from, to)
+    render_events_in_range(
+        caldir,
+        calendars,
+        from,
+        to,
+        &tags,
+        hide_declined,
+        tz,
+        template.as_ref(),
+    )
 }
 
 fn day_range<Tz: TimeZone>(now: DateTime<Tz>) -> (DateTime<Utc>, DateTime<Utc>) {
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use caldir_core::Caldir;
+use owo_colors::OwoColorize;
+
+use crate::render::diff::Render;
+use crate::render::time::{Locale, format_datetime, format_timestamp};
+use crate::utils::{require_calendars, resolve_calendars};
+
+pub fn list(caldir: &Caldir, calendar: Option<String>) -> Result<()> {
+    require_calendars(caldir)?;
+    let calendars = resolve_calendars(caldir, calendar.as_deref())?;
+    let locale = Locale::from_config(caldir.config());
+
+    let mut empty = true;
+
+    for cal in &calendars {
+        let trashed = cal.trash()?;
+        if trashed.is_empty() {
+            continue;
+        }
+
+        if !empty {
+            println!();
+        }
+        empty = false;
+
+        println!("{}", cal.render(caldir));
+
+        for entry in trashed {
+            let event = entry.event();
+            let summary = event.summary.as_deref().unwrap_or("(Untitled)");
+            let trashed_at = format_timestamp(entry.trashed_at(), locale);
+
+            println!(
+                "   {} {}",
+                summary,
+                format!("({})", format_datetime(&event.start, locale)).dimmed()
+            );
+            println!(
+                "      {} {}",
+                entry.id().dimmed(),
+                format!("trashed {trashed_at}").dimmed()
+            );
+        }
+    }
+
+    if empty {
+        println!("{}", "Trash is empty.".dimmed());
+    }
+
+    Ok(())
+}
+
+pub fn restore(caldir: &Caldir, calendar: Option<String>, id: String) -> Result<()> {
+    require_calendars(caldir)?;
+    let calendars = resolve_calendars(caldir, calendar.as_deref())?;
+
+    for mut cal in calendars {
+        if !cal.trash()?.iter().any(|entry| entry.id() == id) {
+            continue;
+        }
+
+        let calendar_event = cal.restore_from_trash(&id)?;
+        let summary = calendar_event
+            .event()
+            .summary
+            .as_deref()
+            .unwrap_or("(Untitled)");
+
+        println!("Restored {} to {}", summary.bold(), cal.render(caldir));
+        if cal.has_remote() {
+            println!("   {}", "Run caldir push to recreate it remotely.".dimmed());
+        }
+
+        return Ok(());
+    }
+
+    anyhow::bail!("No trashed event {id}. See: caldir trash list")
+}
@@ -0,0 +1,220 @@
+use anyhow::Result;
+use caldir_core::{Caldir, Event, EventQuery};
+use chrono::{DateTime, Duration, Utc};
+use owo_colors::OwoColorize;
+
+use crate::render::event::{event_json, format_event_line};
+use crate::render::template::Template;
+use crate::render::time::humanize_minutes;
+use crate::utils::{display_timezone, require_calendars, resolve_calendars};
+
+/// How far ahead `caldir next` looks.
+const LOOKAHEAD_DAYS: i64 = 30;
+
+/// How `next` and `now` print what they found.
+pub struct Output {
+    pub tz: Option<String>,
+    pub json: bool,
+    pub format: Option<String>,
+}
+
+/// How long until an event starts, or how long it has left.
+enum Countdown {
+    StartsIn(i64),
+    Left(i64),
+}
+
+impl Countdown {
+    fn json_key(&self) -> &'static str {
+        match self {
+            Self::StartsIn(_) => "starts_in_minutes",
+            Self::Left(_) => "minutes_left",
+        }
+    }
+
+    fn minutes(&self) -> i64 {
+        match self {
+            Self::StartsIn(minutes) | Self::Left(minutes) => *minutes,
+        }
+    }
+}
+
+impl std::fmt::Display for Countdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StartsIn(minutes) => write!(f, "in {}", humanize_minutes(*minutes)),
+            Self::Left(minutes) => write!(f, "{} left", humanize_minutes(*minutes)),
+        }
+    }
+}
+
+/// A timed event and the calendar it's in.
+pub(crate) struct Found {
+    pub calendar: String,
+    pub color: Option<String>,
+    pub event: Event,
+}
+
+/// The next timed event to start, with a countdown.
+pub fn next(caldir: &Caldir, calendar: Option<String>, output: Output) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let now = Utc::now();
+    let next: Vec<Found> = next_event(
+        caldir,
+        calendar.as_deref(),
+        now,
+        Duration::days(LOOKAHEAD_DAYS),
+    )?
+    .into_iter()
+    .collect();
+
+    print(caldir, &next, output, "No upcoming events", |event| {
+        Countdown::StartsIn(minutes_until(now, event.start.to_utc()))
+    })
+}
+
+/// The timed events going on right now, with the time they have left.
+pub fn now(caldir: &Caldir, calendar: Option<String>, output: Output) -> Result<()> {
+    require_calendars(caldir)?;
+
+    let now = Utc::now();
+    let running = timed_events(caldir, calendar.as_deref(), now, now)?;
+
+    print(caldir, &running, output, "Nothing right now", |event| {
+        let end = event.end.as_ref().map_or(now, |end| end.to_utc());
+        Countdown::Left(minutes_until(now, end))
+    })
+}
+
+/// The first timed event starting after `now`, looking as far ahead as
+/// `lookahead`.
+pub(crate) fn next_event(
+    caldir: &Caldir,
+    calendar: Option<&str>,
+    now: DateTime<Utc>,
+    lookahead: Duration,
+) -> Result<Option<Found>> {
+    let upcoming = timed_events(caldir, calendar, now, now + lookahead)?;
+
+    Ok(upcoming
+        .into_iter()
+        .find(|found| found.event.start.to_utc() > now))
+}
+
+/// Timed events occurring between `from` and `to`, in start order.
+fn timed_events(
+    caldir: &Caldir,
+    calendar: Option<&str>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Found>> {
+    let query = EventQuery::between(from, to);
+    let mut found = Vec::new();
+
+    for cal in resolve_calendars(caldir, calendar)? {
+        let slug = cal.slug().unwrap_or("(Unknown calendar)").to_string();
+        let color = cal.color().map(str::to_string);
+
+        found.extend(
+            cal.query(&query)?
+                .into_iter()
+                .filter(|event| !event.start.is_date())
+                .map(|event| Found {
+                    calendar: slug.clone(),
+                    color: color.clone(),
+                    event,
+                }),
+        );
+    }
+
+    found.sort_by_key(|found| found.event.start.to_utc());
+    Ok(found)
+}
+
+/// Whole minutes from `now` until `at`, rounded up.
+pub(crate) fn minutes_until(now: DateTime<Utc>, at: DateTime<Utc>) -> i64 {
+    ((at - now).num_seconds() + 59).div_euclid(60)
+}
+
+/// Print `found` as JSON, with a template, or as event lines followed by
+/// their `countdown`.
+fn print(
+    caldir: &Caldir,
+    found: &[Found],
+    output: Output,
+    nothing: &str,
+    countdown: impl Fn(&Event) -> Countdown,
+) -> Result<()> {
+    let tz = display_timezone(caldir, output.tz.as_deref())?;
+
+    if output.json {
+        let events: Vec<serde_json::Value> = found
+            .iter()
+            .map(|found| {
+                let countdown = countdown(&found.event);
+                let mut json = event_json(&found.event, &found.calendar, tz);
+                json[countdown.json_key()] = countdown.minutes().into();
+                json
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&events)?);
+        return Ok(());
+    }
+
+    if let Some(format) = output.format {
+        let template = Template::parse(&format)?;
+        for found in found {
+            println!(
+                "{}",
+                template.render(
+                    &found.event,
+                    &found.calendar,
+                    &tz,
+                    caldir.config().floating_timezone()
+                )
+            );
+        }
+        return Ok(());
+    }
+
+    if found.is_empty() {
+        println!("{}", nothing.dimmed());
+        return Ok(());
+    }
+
+    for found in found {
+        let countdown = countdown(&found.event).to_string();
+
+        println!(
+            "{}",
+            format_event_line(
+                &found.event,
+                &found.calendar,
+                found.color.as_deref(),
+                &format!(" {}", countdown.dimmed()),
+                caldir,
+                tz
+            )
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn countdowns_round_up_to_the_minute() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+
+        let starts_in = minutes_until(now, now + Duration::seconds(85 * 60 + 10));
+        let left = minutes_until(now, now + Duration::seconds(20));
+
+        assert_eq!(Countdown::StartsIn(starts_in).to_string(), "in 1h26m");
+        assert_eq!(Countdown::Left(left).to_string(), "1m left");
+    }
+}
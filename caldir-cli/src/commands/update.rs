@@ -1,18 +1,18 @@
 use anyhow::{Result, bail};
 use owo_colors::OwoColorize;
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-const REPO: &str = "t4t5/caldir";
+pub(crate) const REPO: &str = "t4t5/caldir";
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub async fn run() -> Result<()> {
     let spinner = crate::utils::tui::create_spinner("Checking for updates...".to_string());
 
-    let latest = fetch_latest_release().await?;
+    let latest = fetch_latest_release(REPO).await?;
     spinner.finish_and_clear();
 
-    let latest_version = latest.tag_name.trim_start_matches('v');
+    let latest_version = latest.version();
 
     if latest_version == CURRENT_VERSION {
         println!(
@@ -28,28 +28,10 @@ pub async fn run() -> Result<()> {
     let tarball_name = format!("caldir-{}.tar.gz", target);
 
     let download_url = latest
-        .assets
-        .iter()
-        .find(|a| a.name == tarball_name)
-        .map(|a| &a.browser_download_url)
+        .asset_url(&tarball_name)
         .ok_or_else(|| anyhow::anyhow!("No release found for platform: {}", target))?;
 
-    let spinner = crate::utils::tui::create_spinner("Downloading...".to_string());
-
-    let client = http_client()?;
-    let response = client.get(download_url).send().await?;
-    if !response.status().is_success() {
-        bail!("Download failed (HTTP {})", response.status());
-    }
-    let bytes = response.bytes().await?;
-
-    spinner.finish_and_clear();
-
-    // Extract tarball to a temp directory
-    let tmp_dir = tempfile::tempdir()?;
-    let decoder = flate2::read::GzDecoder::new(&bytes[..]);
-    let mut archive = tar::Archive::new(decoder);
-    archive.unpack(tmp_dir.path())?;
+    let tmp_dir = download_and_unpack(download_url).await?;
 
     // Discover binaries from the tarball — the release is the source of truth
     // for what ships. Only update binaries that are also installed locally,
@@ -84,32 +66,59 @@ pub async fn run() -> Result<()> {
     println!();
 
     for bin in &to_update {
-        let src = tmp_dir.path().join(bin);
-        let dst = install_dir.join(bin);
+        install_binary(&tmp_dir.path().join(bin), &install_dir.join(bin))?;
+    }
+
+    println!("{}", format!("Updated to v{}!", latest_version).green());
+
+    Ok(())
+}
+
+/// Download a release tarball and extract it to a temp directory.
+pub(crate) async fn download_and_unpack(url: &str) -> Result<tempfile::TempDir> {
+    let spinner = crate::utils::tui::create_spinner("Downloading...".to_string());
+
+    let client = http_client()?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        bail!("Download failed (HTTP {})", response.status());
+    }
+    let bytes = response.bytes().await?;
+
+    spinner.finish_and_clear();
+
+    let tmp_dir = tempfile::tempdir()?;
+    let decoder = flate2::read::GzDecoder::new(&bytes[..]);
+    let mut archive = tar::Archive::new(decoder);
+    archive.unpack(tmp_dir.path())?;
 
+    Ok(tmp_dir)
+}
+
+/// Copy the binary at `src` to `dst`, replacing any existing one.
+pub(crate) fn install_binary(src: &Path, dst: &Path) -> Result<()> {
+    if dst.exists() {
         // Remove first to avoid ETXTBSY on Linux (can't write to a running executable,
         // but unlinking is fine — the kernel keeps the old inode mapped until the process exits)
-        std::fs::remove_file(&dst).map_err(|e| {
+        std::fs::remove_file(dst).map_err(|e| {
             anyhow::anyhow!(
-                "Failed to update {} (permission denied?). Try:\n  sudo caldir update\n\nError: {}",
+                "Failed to update {} (permission denied?). Try running it with sudo.\n\nError: {}",
                 dst.display(),
                 e
             )
         })?;
-        std::fs::copy(&src, &dst)?;
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(&dst, std::fs::Permissions::from_mode(0o755))?;
-        }
     }
-
-    println!("{}", format!("Updated to v{}!", latest_version).green());
+    std::fs::copy(src, dst)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(dst, std::fs::Permissions::from_mode(0o755))?;
+    }
 
     Ok(())
 }
 
-fn get_install_dir() -> Result<PathBuf> {
+pub(crate) fn get_install_dir() -> Result<PathBuf> {
     let exe = std::env::current_exe()?;
     let exe = exe.canonicalize()?;
     exe.parent()
@@ -117,7 +126,7 @@ fn get_install_dir() -> Result<PathBuf> {
         .ok_or_else(|| anyhow::anyhow!("Could not determine install directory"))
 }
 
-fn detect_target() -> Result<String> {
+pub(crate) fn detect_target() -> Result<String> {
     let arch = std::env::consts::ARCH;
     let os = std::env::consts::OS;
 
@@ -136,9 +145,9 @@ fn http_client() -> Result<reqwest::Client> {
         .build()?)
 }
 
-async fn fetch_latest_release() -> Result<GitHubRelease> {
+pub(crate) async fn fetch_latest_release(repo: &str) -> Result<GitHubRelease> {
     let client = http_client()?;
-    let url = format!("https://api.github.com/repos/{}/releases/latest", REPO);
+    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
     let response = client.get(&url).send().await?;
 
     if !response.status().is_success() {
@@ -154,11 +163,24 @@ async fn fetch_latest_release() -> Result<GitHubRelease> {
 }
 
 #[derive(Deserialize)]
-struct GitHubRelease {
-    tag_name: String,
+pub(crate) struct GitHubRelease {
+    pub(crate) tag_name: String,
     assets: Vec<GitHubAsset>,
 }
 
+impl GitHubRelease {
+    pub(crate) fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    pub(crate) fn asset_url(&self, name: &str) -> Option<&str> {
+        self.assets
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| a.browser_download_url.as_str())
+    }
+}
+
 #[derive(Deserialize)]
 struct GitHubAsset {
     name: String,
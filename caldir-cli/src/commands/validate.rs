@@ -0,0 +1,99 @@
+use anyhow::{Result, bail};
+use caldir_core::{Caldir, Severity, validate_ics};
+use owo_colors::OwoColorize;
+use std::path::{Path, PathBuf};
+
+use crate::utils::{PathExt, require_calendars, resolve_calendars};
+
+/// Checks `.ics` files against RFC 5545. `target` is a file, a directory or
+/// a calendar slug; all calendars are checked without one.
+pub fn run(caldir: &Caldir, target: Option<String>) -> Result<()> {
+    let files = match target.as_deref().map(Path::new) {
+        Some(path) if path.is_file() => vec![path.to_path_buf()],
+        Some(path) if path.is_dir() => ics_files(path)?,
+        _ => {
+            require_calendars(caldir)?;
+            let mut files = Vec::new();
+            for calendar in resolve_calendars(caldir, target.as_deref())? {
+                files.extend(
+                    calendar
+                        .event_files()?
+                        .into_iter()
+                        .filter(|path| path.extension().is_some_and(|ext| ext == "ics")),
+                );
+            }
+            files.sort();
+            files
+        }
+    };
+
+    let mut errors = 0;
+    let mut warnings = 0;
+
+    for file in &files {
+        let contents = match std::fs::read_to_string(file) {
+            Ok(contents) => contents,
+            Err(err) => {
+                println!("{}: {} {err}", file.tilde(), "error:".red().bold());
+                errors += 1;
+                continue;
+            }
+        };
+
+        for problem in validate_ics(&contents) {
+            let label = match problem.severity {
+                Severity::Error => {
+                    errors += 1;
+                    "error:".red().bold().to_string()
+                }
+                Severity::Warning => {
+                    warnings += 1;
+                    "warning:".yellow().bold().to_string()
+                }
+            };
+
+            println!(
+                "{}:{}: {label} {}",
+                file.tilde(),
+                problem.line,
+                problem.message
+            );
+        }
+    }
+
+    if errors == 0 && warnings == 0 {
+        println!(
+            "{} No problems found in {} file(s).",
+            "✓".green(),
+            files.len()
+        );
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "{errors} error(s), {warnings} warning(s) in {} file(s)",
+        files.len()
+    );
+
+    if errors > 0 {
+        bail!("Some files aren't valid iCalendar");
+    }
+
+    Ok(())
+}
+
+/// The `.ics` files directly in `dir`, sorted.
+fn ics_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "ics") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
@@ -1,33 +1,55 @@
 use anyhow::Result;
-use caldir_core::Caldir;
 use caldir_core::DateBounds;
+use caldir_core::{Caldir, WeekStart};
 use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
 
 use crate::render::events_in_range::render_events_in_range;
-use crate::utils::{require_calendars, resolve_calendars};
-
-pub fn run(caldir: &Caldir, calendar: Option<String>) -> Result<()> {
+use crate::render::template::Template;
+use crate::utils::{display_timezone, require_calendars, resolve_calendars};
+
+pub fn run(
+    caldir: &Caldir,
+    calendar: Option<String>,
+    tags: Vec<String>,
+    hide_declined: bool,
+    tz: Option<String>,
+    format: Option<String>,
+) -> Result<()> {
     require_calendars(caldir)?;
 
-    let calendars = resolve_calendars(caldir, calendar.as_deref())?;
+    let template = format.as_deref().map(Template::parse).transpose()?;
 
-    let tz: chrono_tz::Tz = iana_time_zone::get_timezone()?.parse()?;
-    let (from, to) = week_range(Utc::now().with_timezone(&tz));
+    let calendars = resolve_calendars(caldir, calendar.as_deref())?;
 
-    render_events_in_range(caldir, calendars, from, to)
+    let tz = display_timezone(caldir, tz.as_deref())?;
+    let (from, to) = week_range(Utc::now().with_timezone(&tz), caldir.config().week_start());
+
+    render_events_in_range(
+        caldir,
+        calendars,
+        from,
+        to,
+        &tags,
+        hide_declined,
+        tz,
+        template.as_ref(),
+    )
 }
 
-fn week_range<Tz: TimeZone>(now: DateTime<Tz>) -> (DateTime<Utc>, DateTime<Utc>) {
+fn week_range<Tz: TimeZone>(
+    now: DateTime<Tz>,
+    week_start: WeekStart,
+) -> (DateTime<Utc>, DateTime<Utc>) {
     let tz = now.timezone();
     let today = now.date_naive();
-    let weekday = today.weekday().num_days_from_monday();
+    let weekday = week_start.days_into_week(today.weekday());
 
-    // If today is Sunday, jump to the upcoming Mon–Sun week rather than showing a single day.
+    // On the last day of the week, jump to the upcoming week rather than showing a single day.
     let (start_date, end_date) = if weekday == 6 {
         (today + Duration::days(1), today + Duration::days(7))
     } else {
-        let days_until_sunday = 6 - weekday;
-        (today, today + Duration::days(days_until_sunday as i64))
+        let days_until_week_end = 6 - weekday;
+        (today, today + Duration::days(days_until_week_end as i64))
     };
 
     let start = start_date
@@ -60,7 +82,7 @@ mod tests {
     #[test]
     fn tuesday_shows_tuesday_through_sunday() {
         let now = Stockholm.with_ymd_and_hms(2026, 5, 12, 12, 0, 0).unwrap();
-        let (from, to) = week_range(now);
+        let (from, to) = week_range(now, WeekStart::Monday);
 
         assert_eq!(
             stockholm_date(from),
@@ -82,7 +104,7 @@ mod tests {
     #[test]
     fn sunday_shows_following_monday_through_sunday() {
         let now = Stockholm.with_ymd_and_hms(2026, 5, 17, 12, 0, 0).unwrap();
-        let (from, to) = week_range(now);
+        let (from, to) = week_range(now, WeekStart::Monday);
 
         assert_eq!(
             stockholm_date(from),
@@ -100,4 +122,16 @@ mod tests {
             "start should be midnight local time",
         );
     }
+
+    #[test]
+    fn sunday_start_week_ends_on_saturday() {
+        let now = Stockholm.with_ymd_and_hms(2026, 5, 12, 12, 0, 0).unwrap();
+        let (_, to) = week_range(now, WeekStart::Sunday);
+
+        assert_eq!(
+            stockholm_date(to),
+            NaiveDate::from_ymd_opt(2026, 5, 16).unwrap(),
+            "end should be Saturday May 16 in local time",
+        );
+    }
 }
@@ -5,9 +5,11 @@ mod utils;
 #[cfg(test)]
 mod test_utils;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use caldir_core::Caldir;
+use caldir_core::provider::{self, TraceTarget};
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "caldir-cli")]
@@ -16,6 +18,26 @@ use clap::{Parser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Use the caldir home at this path (its own config.toml and provider tokens).
+    /// Defaults to $CALDIR_HOME, then ~/.config/caldir
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "profile")]
+    caldir: Option<PathBuf>,
+
+    /// Use a named profile from [profiles] in the global config
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Print every message exchanged with providers (secrets redacted) to stderr,
+    /// or append them to FILE. Same as CALDIR_TRACE=1 (or CALDIR_TRACE=FILE)
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        num_args = 0..=1,
+        require_equals = true
+    )]
+    trace_protocol: Option<Option<PathBuf>>,
 }
 
 #[derive(Subcommand)]
@@ -28,6 +50,33 @@ enum Commands {
         /// Use hosted OAuth via caldir.org (default: true). Pass --hosted=false to use your own credentials.
         #[arg(long, default_value_t = true, action = clap::ArgAction::Set)]
         hosted: bool,
+
+        /// Sign in from another device and paste back the URL its browser is redirected to
+        #[arg(long)]
+        paste_redirect: bool,
+    },
+    #[command(about = "Stop syncing a provider account's calendars and sign out of it")]
+    Disconnect {
+        /// Provider name (e.g. "google", "caldav", "icloud", "outlook")
+        provider: String,
+
+        /// Account to sign out of (e.g. an email address), when several are connected
+        account: Option<String>,
+
+        /// Delete the calendars and their events too, instead of keeping them as local calendars
+        #[arg(long)]
+        remove: bool,
+
+        /// Don't ask before deleting calendars
+        #[arg(long)]
+        force: bool,
+    },
+    #[command(about = "List connected accounts and whether they're still signed in")]
+    Accounts,
+    #[command(about = "List calendars with their provider, event count and sync status")]
+    Calendars {
+        #[command(subcommand)]
+        action: Option<CalendarsAction>,
     },
     #[command(about = "Check if any events have changed (local and remote)")]
     Status {
@@ -46,6 +95,18 @@ enum Commands {
         /// Show all events (instead of compact view when >5 events)
         #[arg(short, long)]
         verbose: bool,
+
+        /// Only show local changes since the last sync, without contacting providers
+        #[arg(long)]
+        offline: bool,
+
+        /// Show events as "Busy" without descriptions, locations or attendees
+        #[arg(long)]
+        redact: bool,
+
+        /// Also print the ICS of each changed event as a unified diff
+        #[arg(long)]
+        diff: bool,
     },
     #[command(about = "Pull changes from remote calendars into local caldir")]
     Pull {
@@ -64,6 +125,18 @@ enum Commands {
         /// Show all events (instead of compact view when >5 events)
         #[arg(short, long)]
         verbose: bool,
+
+        /// Delete local events without asking, however many the pull removes
+        #[arg(long)]
+        force: bool,
+
+        /// Print the file changes and provider calls without making them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only pull changes to this event (by UID)
+        #[arg(long)]
+        event: Option<String>,
     },
     #[command(about = "Push changes from local caldir to remote calendars")]
     Push {
@@ -83,9 +156,23 @@ enum Commands {
         #[arg(short, long)]
         verbose: bool,
 
-        /// Bypass safety checks (e.g. allow deleting all remote events when local is empty)
+        /// Bypass safety checks (e.g. allow deleting all remote events when local is empty,
+        /// or overwrite events that also changed remotely since the last sync)
         #[arg(long)]
         force: bool,
+
+        /// Email attendees about created, changed or deleted events: all (the default
+        /// for a bare --notify), external or none. Overrides the calendar's `notify`
+        #[arg(long, num_args = 0..=1, default_missing_value = "all")]
+        notify: Option<String>,
+
+        /// Print the file changes and provider calls without making them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only push this event (by file name or UID), without fetching the calendar first
+        #[arg(long)]
+        event: Option<String>,
     },
     #[command(about = "Sync changes between caldir and remote calendars (push + pull)")]
     Sync {
@@ -105,9 +192,19 @@ enum Commands {
         #[arg(short, long)]
         verbose: bool,
 
-        /// Bypass safety checks (e.g. allow deleting many remote events at once)
+        /// Bypass safety checks (e.g. allow deleting many local or remote events at once,
+        /// or overwrite events that also changed remotely since the last sync)
         #[arg(long)]
         force: bool,
+
+        /// Email attendees about created, changed or deleted events: all (the default
+        /// for a bare --notify), external or none. Overrides the calendar's `notify`
+        #[arg(long, num_args = 0..=1, default_missing_value = "all")]
+        notify: Option<String>,
+
+        /// Print the file changes and provider calls without making them
+        #[arg(long)]
+        dry_run: bool,
     },
     #[command(about = "List upcoming events across all calendars")]
     Events {
@@ -122,18 +219,116 @@ enum Commands {
         /// Show events until this date (YYYY-MM-DD)
         #[arg(long)]
         to: Option<String>,
+
+        /// Only show events with this tag (CATEGORIES). Can be repeated.
+        #[arg(short, long)]
+        tag: Vec<String>,
+
+        /// Leave out invites you declined (always on with `hide_declined` set)
+        #[arg(long)]
+        hide_declined: bool,
+
+        /// Show times in this IANA timezone (defaults to display_timezone, else the system timezone)
+        #[arg(long)]
+        tz: Option<String>,
+
+        /// Print each event with a template, e.g. "{start:%H:%M} {summary} ({calendar})"
+        #[arg(long)]
+        format: Option<String>,
     },
     #[command(about = "Show today's events")]
     Today {
         /// Only show events from this calendar (by slug)
         #[arg(short, long)]
         calendar: Option<String>,
+
+        /// Only show events with this tag (CATEGORIES). Can be repeated.
+        #[arg(short, long)]
+        tag: Vec<String>,
+
+        /// Leave out invites you declined (always on with `hide_declined` set)
+        #[arg(long)]
+        hide_declined: bool,
+
+        /// Show times in this IANA timezone (defaults to display_timezone, else the system timezone)
+        #[arg(long)]
+        tz: Option<String>,
+
+        /// Print each event with a template, e.g. "{start:%H:%M} {summary} ({calendar})"
+        #[arg(long)]
+        format: Option<String>,
     },
-    #[command(about = "Show this week's events (through Sunday)")]
+    #[command(about = "Show the rest of this week's events")]
     Week {
         /// Only show events from this calendar (by slug)
         #[arg(short, long)]
         calendar: Option<String>,
+
+        /// Only show events with this tag (CATEGORIES). Can be repeated.
+        #[arg(short, long)]
+        tag: Vec<String>,
+
+        /// Leave out invites you declined (always on with `hide_declined` set)
+        #[arg(long)]
+        hide_declined: bool,
+
+        /// Show times in this IANA timezone (defaults to display_timezone, else the system timezone)
+        #[arg(long)]
+        tz: Option<String>,
+
+        /// Print each event with a template, e.g. "{start:%H:%M} {summary} ({calendar})"
+        #[arg(long)]
+        format: Option<String>,
+    },
+    #[command(about = "Show the next event, and how soon it starts")]
+    Next {
+        /// Only look in this calendar (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+
+        /// Show times in this IANA timezone (defaults to display_timezone, else the system timezone)
+        #[arg(long)]
+        tz: Option<String>,
+
+        /// Print the event as JSON
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Print the event with a template, e.g. "{start:%H:%M} {summary} ({calendar})"
+        #[arg(long)]
+        format: Option<String>,
+    },
+    #[command(about = "Show the events going on right now, and how long they have left")]
+    Now {
+        /// Only look in this calendar (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+
+        /// Show times in this IANA timezone (defaults to display_timezone, else the system timezone)
+        #[arg(long)]
+        tz: Option<String>,
+
+        /// Print the events as JSON
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Print each event with a template, e.g. "{start:%H:%M} {summary} ({calendar})"
+        #[arg(long)]
+        format: Option<String>,
+    },
+    #[command(about = "One line about the next event, for polybar, waybar or tmux")]
+    Statusbar {
+        /// Only look in this calendar (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+
+        /// Print JSON for a waybar custom module
+        #[arg(long)]
+        waybar: bool,
+
+        /// How soon an event is "imminent" (waybar class)
+        #[arg(long, default_value = "10m")]
+        imminent: String,
     },
     #[command(about = "Create a new event in caldir")]
     New {
@@ -167,6 +362,114 @@ enum Commands {
         /// Do not add any reminders (overrides default_reminders config)
         #[arg(long)]
         no_reminders: bool,
+
+        /// Tag(s) to add to the event (CATEGORIES). Can be repeated.
+        #[arg(short, long)]
+        tag: Vec<String>,
+
+        /// Attendee to invite: an email, or a name from past events. Can be repeated.
+        #[arg(short, long)]
+        attendee: Vec<String>,
+
+        /// Write the description in $EDITOR (like `git commit`)
+        #[arg(long)]
+        edit_description: bool,
+
+        /// Add a Google Meet link, created on the next push (Google only)
+        #[arg(long)]
+        meet: bool,
+
+        /// IANA timezone for timed events (defaults to the system timezone)
+        #[arg(long)]
+        tz: Option<String>,
+
+        /// Create one event per row of a .json, .csv or .tsv file
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["title", "start", "end", "duration"])]
+        from_file: Option<String>,
+    },
+    #[command(about = "Create an event from a single sentence")]
+    Quick {
+        /// Title, date/time, optional "at <location>" and "for <duration>"
+        text: String,
+
+        /// Calendar slug (defaults to default_calendar from config)
+        #[arg(short = 'C', long)]
+        calendar: Option<String>,
+
+        /// IANA timezone for timed events (defaults to the system timezone)
+        #[arg(long)]
+        tz: Option<String>,
+    },
+    #[command(about = "Move matching upcoming events by a duration or to another weekday")]
+    Shift {
+        /// field~text (contains) or field=text, on summary, description, location, tag or attendee. Can be repeated.
+        #[arg(long, required = true)]
+        filter: Vec<String>,
+
+        /// How far to move them, e.g. "30m", "1 day", or "-15m" for earlier
+        #[arg(
+            long,
+            allow_hyphen_values = true,
+            required_unless_present = "weekday",
+            conflicts_with = "weekday"
+        )]
+        by: Option<String>,
+
+        /// Move them to this day of their week, e.g. "fri"
+        #[arg(long)]
+        weekday: Option<String>,
+
+        /// Only shift events in this calendar (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+
+        /// Shift events from this date (YYYY-MM-DD, default: now)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Shift events until this date (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// List what would move, without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    #[command(about = "Move the next occurrence of an event later")]
+    Postpone {
+        /// The event: its file name, UID or title
+        event: String,
+
+        /// How much later, e.g. "30m", "2h" or "1 day"
+        by: String,
+
+        /// Only look in this calendar (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+    },
+    #[command(about = "Move the next occurrence of an event to another time")]
+    Reschedule {
+        /// The event: its file name, UID or title
+        event: String,
+
+        /// When it should start, e.g. "thu 2pm", "2025-03-21T09:00", or a date to keep its time
+        to: String,
+
+        /// Only look in this calendar (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+    },
+    #[command(about = "Skip one occurrence of a recurring event")]
+    Skip {
+        /// The recurring event: its file name, UID or title
+        event: String,
+
+        /// Day of the occurrence to skip (YYYY-MM-DD), in the event's own timezone
+        date: String,
+
+        /// Only look in this calendar (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
     },
     #[command(about = "Discard unpushed local changes (restore to remote state)")]
     Discard {
@@ -190,6 +493,32 @@ enum Commands {
         #[arg(long)]
         force: bool,
     },
+    #[command(about = "Archive all calendars and their sync state")]
+    Snapshot {
+        /// List snapshots instead of taking one
+        #[arg(long)]
+        list: bool,
+    },
+    #[command(about = "Restore calendars from a snapshot")]
+    Restore {
+        /// Snapshot id (see `caldir snapshot --list`)
+        id: String,
+    },
+    #[command(about = "List or restore events that pull deleted")]
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+    #[command(about = "Show what pull and push changed, and when")]
+    Log {
+        /// Only show this calendar's log (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+
+        /// Only show entries since this date (YYYY-MM-DD) or duration ago (e.g. 7d)
+        #[arg(long)]
+        since: Option<String>,
+    },
     #[command(about = "List pending invites across calendars")]
     Invites {
         /// Only show invites from this calendar (by slug)
@@ -200,6 +529,100 @@ enum Commands {
         #[arg(short, long)]
         all: bool,
     },
+    #[command(about = "Find times when you and other attendees are free")]
+    Schedule {
+        /// Someone to meet with (repeatable); their free/busy comes from your provider
+        #[arg(long = "with", value_name = "EMAIL")]
+        with: Vec<String>,
+
+        /// Length of the meeting (e.g. 30m, 1h)
+        #[arg(short, long, default_value = "30m")]
+        duration: String,
+
+        /// When to look: today, tomorrow, this week, next week, 5d or FROM..TO dates
+        #[arg(long, default_value = "this week")]
+        within: String,
+
+        /// Working hours to propose slots in, local time
+        #[arg(long, default_value = "9-17")]
+        hours: String,
+
+        /// Calendar whose provider looks up attendees' free/busy (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+
+        /// Maximum number of slots to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+    },
+    #[command(about = "List busy events that overlap, across all calendars")]
+    Conflicts {
+        /// Look for conflicts from this date (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Look for conflicts until this date (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<String>,
+    },
+    #[command(about = "Add up the time spent in meetings")]
+    Stats {
+        /// Count events from this date (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Count events until this date (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// calendar, tag or attendee
+        #[arg(long, default_value = "calendar")]
+        group_by: String,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    #[command(about = "Export events as CSV or TSV for spreadsheets")]
+    Export {
+        /// csv or tsv
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// Comma-separated columns: start, end, duration, summary, calendar, location, attendees (default: all)
+        #[arg(long)]
+        columns: Option<String>,
+
+        /// Only export events from this calendar (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+
+        /// Export events from this date (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Export events until this date (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Only export events with this tag (CATEGORIES). Can be repeated.
+        #[arg(short, long)]
+        tag: Vec<String>,
+
+        /// Export events as "Busy" without descriptions, locations or attendees
+        #[arg(long)]
+        redact: bool,
+    },
+    #[command(about = "Import an invitation, reply or cancellation received by email")]
+    Import {
+        /// The email (.eml) or its calendar attachment (.ics)
+        #[arg(long, value_name = "FILE")]
+        invite: String,
+
+        /// Calendar to import into (by slug); found from the event by default
+        #[arg(short, long)]
+        calendar: Option<String>,
+    },
     #[command(about = "Respond to a calendar invite")]
     Rsvp {
         /// Path to the .ics file (omit for interactive mode)
@@ -208,61 +631,313 @@ enum Commands {
         /// Response: accept, decline, maybe
         response: Option<String>,
     },
+    #[command(about = "Serve the calendars to AI assistants over MCP (Model Context Protocol)")]
+    Mcp {
+        /// Only offer the tools that read, not the ones that create or change events
+        #[arg(long)]
+        read_only: bool,
+    },
     #[command(about = "Show configuration paths and calendar info")]
-    Config,
-    #[command(about = "Check your caldir for bad data (e.g. duplicate files)")]
-    Doctor,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+    #[command(
+        about = "Check your setup, providers, sign-ins and calendars for problems, with fixes"
+    )]
+    Doctor {
+        /// Skip the checks that start providers and sign in
+        #[arg(long)]
+        offline: bool,
+
+        /// Repair files holding the same event first: merge, keep-newest or new-uids
+        #[arg(long, value_name = "HOW")]
+        fix_duplicates: Option<String>,
+    },
+    #[command(about = "Check .ics files against the iCalendar spec (RFC 5545)")]
+    Validate {
+        /// A .ics file, a directory or a calendar slug (omit for all calendars)
+        target: Option<String>,
+    },
+    #[command(about = "List, check, install and update calendar providers")]
+    Providers {
+        #[command(subcommand)]
+        action: Option<ProvidersAction>,
+    },
+    #[command(about = "Sync in the background on a timer (systemd on Linux, launchd on macOS)")]
+    Service {
+        #[command(subcommand)]
+        action: ServiceAction,
+    },
     #[command(about = "Update caldir and installed providers to the latest version")]
     Update,
 }
 
+#[derive(Subcommand)]
+enum CalendarsAction {
+    #[command(about = "Add more calendars from a connected account (e.g. shared calendars)")]
+    Add {
+        /// Provider name (e.g. "google", "caldav", "icloud", "outlook")
+        provider: String,
+
+        /// Account to list calendars for, when several are connected
+        account: Option<String>,
+    },
+    #[command(about = "Rename a calendar's directory, keeping its remote and sync state")]
+    Rename {
+        /// Current calendar slug
+        slug: String,
+
+        /// New calendar slug
+        new_slug: String,
+    },
+    #[command(about = "Stop syncing and listing a calendar, keeping its files")]
+    Archive {
+        /// Calendar slug
+        slug: String,
+    },
+    #[command(about = "Sync and list an archived calendar again")]
+    Unarchive {
+        /// Calendar slug
+        slug: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    #[command(about = "Print a config value (e.g. default_calendar, calendars.work.color)")]
+    Get {
+        /// Config key. Use calendars.<slug>.<key> for a calendar's own config.
+        key: String,
+    },
+    #[command(about = "Change a config value, rejecting unknown keys and invalid values")]
+    Set {
+        /// Config key. Use calendars.<slug>.<key> for a calendar's own config.
+        key: String,
+
+        /// New value (lists can be comma-separated, e.g. "10m, 1h")
+        value: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrashAction {
+    #[command(about = "List trashed events")]
+    List {
+        /// Only list this calendar's trash (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+    },
+    #[command(about = "Put a trashed event back in its calendar")]
+    Restore {
+        /// Trashed event id (see `caldir trash list`)
+        id: String,
+
+        /// Only look in this calendar's trash (by slug)
+        #[arg(short, long)]
+        calendar: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ServiceAction {
+    #[command(about = "Start syncing in the background, also after logging in again")]
+    Install {
+        /// How often to sync (e.g. 10m, 1h)
+        #[arg(long, default_value = "10m")]
+        every: String,
+    },
+    #[command(about = "Show when the background sync last ran and runs next")]
+    Status,
+    #[command(about = "Stop syncing in the background")]
+    Uninstall,
+}
+
+#[derive(Subcommand)]
+enum ProvidersAction {
+    #[command(about = "Check that providers start and connected calendars can sign in")]
+    Check {
+        /// Only check this provider (e.g. "google")
+        provider: Option<String>,
+    },
+    #[command(about = "Install a provider from its latest release")]
+    Install {
+        /// Provider name (e.g. "proton")
+        provider: String,
+
+        /// GitHub repo to install from (e.g. "someone/caldir-provider-foo"), for providers not in the registry
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    #[command(about = "Update installed third-party providers")]
+    Update {
+        /// Only update this provider
+        provider: Option<String>,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(file) = &cli.trace_protocol {
+        let target = file.clone().map_or(TraceTarget::Stderr, TraceTarget::File);
+        provider::enable_protocol_trace(target).context("Failed to open protocol trace file")?;
+    }
+
     // `update` doesn't touch the caldir, so dispatch it before loading anything.
     if let Commands::Update = cli.command {
         return commands::update::run().await;
     }
 
-    let mut caldir = Caldir::load()?;
+    // Neither does `service`, which only sets up `caldir sync` to run later.
+    if let Commands::Service { action } = &cli.command {
+        return match action {
+            ServiceAction::Install { every } => {
+                commands::service::install(every, cli.caldir.as_deref(), cli.profile.as_deref())
+            }
+            ServiceAction::Status => commands::service::status(),
+            ServiceAction::Uninstall => commands::service::uninstall(),
+        };
+    }
+
+    let mut caldir = match (cli.caldir, cli.profile) {
+        (Some(home), _) => Caldir::load_from_home(&home)?,
+        (None, Some(profile)) => Caldir::load_profile(&profile)?,
+        (None, None) => Caldir::load()?,
+    };
 
     match cli.command {
-        Commands::Connect { provider, hosted } => {
-            commands::connect::run(&mut caldir, provider, hosted).await
-        }
+        Commands::Connect {
+            provider,
+            hosted,
+            paste_redirect,
+        } => commands::connect::run(&mut caldir, provider, hosted, paste_redirect).await,
+        Commands::Disconnect {
+            provider,
+            account,
+            remove,
+            force,
+        } => commands::disconnect::run(&caldir, provider, account, remove, force).await,
+        Commands::Accounts => commands::accounts::run(&caldir).await,
+        Commands::Calendars { action } => match action {
+            None => commands::calendars::run(&caldir),
+            Some(CalendarsAction::Add { provider, account }) => {
+                commands::calendars::add(&mut caldir, provider, account).await
+            }
+            Some(CalendarsAction::Rename { slug, new_slug }) => {
+                commands::calendars::rename(&mut caldir, slug, new_slug)
+            }
+            Some(CalendarsAction::Archive { slug }) => {
+                commands::calendars::archive(&caldir, slug, true)
+            }
+            Some(CalendarsAction::Unarchive { slug }) => {
+                commands::calendars::archive(&caldir, slug, false)
+            }
+        },
         Commands::Status {
             calendar,
             from,
             to,
             verbose,
-        } => commands::status::run(&caldir, calendar, from, to, verbose).await,
+            offline,
+            redact,
+            diff,
+        } => {
+            commands::status::run(&caldir, calendar, from, to, verbose, offline, redact, diff).await
+        }
         Commands::Pull {
             calendar,
             from,
             to,
             verbose,
-        } => commands::pull::run(&caldir, calendar.into_iter().collect(), from, to, verbose).await,
+            force,
+            dry_run,
+            event,
+        } => {
+            let slugs = calendar.into_iter().collect();
+            commands::pull::run(&caldir, slugs, from, to, verbose, force, dry_run, event).await
+        }
         Commands::Push {
             calendar,
             from,
             to,
             verbose,
             force,
-        } => commands::push::run(&caldir, calendar, from, to, verbose, force).await,
+            notify,
+            dry_run,
+            event,
+        } => {
+            commands::push::run(
+                &caldir, calendar, from, to, verbose, force, notify, dry_run, event,
+            )
+            .await
+        }
         Commands::Sync {
             calendar,
             from,
             to,
             verbose,
             force,
-        } => commands::sync::run(&caldir, calendar, from, to, verbose, force).await,
-        Commands::Events { calendar, from, to } => {
-            commands::events::run(&caldir, calendar, from, to)
+            notify,
+            dry_run,
+        } => {
+            commands::sync::run(&caldir, calendar, from, to, verbose, force, notify, dry_run).await
+        }
+        Commands::Events {
+            calendar,
+            from,
+            to,
+            tag,
+            hide_declined,
+            tz,
+            format,
+        } => commands::events::run(&caldir, calendar, from, to, tag, hide_declined, tz, format),
+        Commands::Today {
+            calendar,
+            tag,
+            hide_declined,
+            tz,
+            format,
+        } => commands::today::run(&caldir, calendar, tag, hide_declined, tz, format),
+        Commands::Week {
+            calendar,
+            tag,
+            hide_declined,
+            tz,
+            format,
+        } => commands::week::run(&caldir, calendar, tag, hide_declined, tz, format),
+        Commands::Next {
+            calendar,
+            tz,
+            json,
+            format,
+        } => {
+            let output = commands::upcoming::Output { tz, json, format };
+            commands::upcoming::next(&caldir, calendar, output)
         }
-        Commands::Today { calendar } => commands::today::run(&caldir, calendar),
-        Commands::Week { calendar } => commands::week::run(&caldir, calendar),
+        Commands::Now {
+            calendar,
+            tz,
+            json,
+            format,
+        } => {
+            let output = commands::upcoming::Output { tz, json, format };
+            commands::upcoming::now(&caldir, calendar, output)
+        }
+        Commands::Statusbar {
+            calendar,
+            waybar,
+            imminent,
+        } => commands::statusbar::run(&caldir, calendar, waybar, imminent),
+        Commands::New {
+            from_file: Some(file),
+            calendar,
+            tz,
+            ..
+        } => commands::new::from_file(&caldir, file, calendar, tz),
         Commands::New {
+            from_file: None,
             title,
             start,
             end,
@@ -271,17 +946,54 @@ async fn main() -> Result<()> {
             calendar,
             reminder,
             no_reminders,
+            tag,
+            attendee,
+            edit_description,
+            meet,
+            tz,
         } => commands::new::run(
             &caldir,
-            title,
-            start,
-            end,
-            duration,
-            location,
-            calendar,
-            reminder,
-            no_reminders,
+            commands::new::Options {
+                title,
+                start,
+                end,
+                duration,
+                location,
+                calendar_slug: calendar,
+                reminders: reminder,
+                no_reminders,
+                tags: tag,
+                attendees: attendee,
+                edit_description,
+                meet,
+                tz,
+            },
         ),
+        Commands::Quick { text, calendar, tz } => commands::quick::run(&caldir, text, calendar, tz),
+        Commands::Shift {
+            filter,
+            by,
+            weekday,
+            calendar,
+            from,
+            to,
+            dry_run,
+        } => commands::shift::run(&caldir, filter, by, weekday, calendar, from, to, dry_run),
+        Commands::Postpone {
+            event,
+            by,
+            calendar,
+        } => commands::reschedule::postpone(&caldir, event, by, calendar),
+        Commands::Reschedule {
+            event,
+            to,
+            calendar,
+        } => commands::reschedule::reschedule(&caldir, event, to, calendar),
+        Commands::Skip {
+            event,
+            date,
+            calendar,
+        } => commands::skip::run(&caldir, event, date, calendar),
         Commands::Discard {
             calendar,
             from,
@@ -289,11 +1001,70 @@ async fn main() -> Result<()> {
             verbose,
             force,
         } => commands::discard::run(&caldir, calendar, from, to, verbose, force).await,
+        Commands::Snapshot { list } => commands::snapshot::run(&caldir, list),
+        Commands::Restore { id } => commands::restore::run(&caldir, id),
+        Commands::Trash { action } => match action {
+            TrashAction::List { calendar } => commands::trash::list(&caldir, calendar),
+            TrashAction::Restore { id, calendar } => {
+                commands::trash::restore(&caldir, calendar, id)
+            }
+        },
+        Commands::Log { calendar, since } => commands::log::run(&caldir, calendar, since),
         Commands::Invites { calendar, all } => commands::invites::run(&caldir, calendar, all),
+        Commands::Schedule {
+            with,
+            duration,
+            within,
+            hours,
+            calendar,
+            limit,
+        } => commands::schedule::run(&caldir, with, duration, within, hours, calendar, limit).await,
+        Commands::Conflicts { from, to } => commands::conflicts::run(&caldir, from, to),
+        Commands::Stats {
+            from,
+            to,
+            group_by,
+            json,
+        } => commands::stats::run(&caldir, from, to, group_by, json),
+        Commands::Export {
+            format,
+            columns,
+            calendar,
+            from,
+            to,
+            tag,
+            redact,
+        } => commands::export::run(&caldir, format, columns, calendar, from, to, tag, redact),
+        Commands::Import { invite, calendar } => {
+            commands::import::invite(&caldir, invite, calendar)
+        }
         Commands::Rsvp { path, response } => commands::rsvp::run(&caldir, path, response),
-        Commands::Config => commands::config::run(&caldir),
-        Commands::Doctor => commands::doctor::run(&caldir),
-        Commands::Update => unreachable!("handled above"),
+        Commands::Mcp { read_only } => commands::mcp::run(&caldir, read_only),
+        Commands::Config { action } => match action {
+            None => commands::config::run(&caldir),
+            Some(ConfigAction::Get { key }) => commands::config::get(&caldir, key),
+            Some(ConfigAction::Set { key, value }) => {
+                commands::config::set(&mut caldir, key, value)
+            }
+        },
+        Commands::Doctor {
+            offline,
+            fix_duplicates,
+        } => commands::doctor::run(&caldir, offline, fix_duplicates).await,
+        Commands::Validate { target } => commands::validate::run(&caldir, target),
+        Commands::Providers { action } => match action {
+            None => commands::providers::list(&caldir).await,
+            Some(ProvidersAction::Check { provider }) => {
+                commands::providers::check(&caldir, provider).await
+            }
+            Some(ProvidersAction::Install { provider, repo }) => {
+                commands::providers::install(provider, repo).await
+            }
+            Some(ProvidersAction::Update { provider }) => {
+                commands::providers::update(&caldir, provider).await
+            }
+        },
+        Commands::Update | Commands::Service { .. } => unreachable!("handled above"),
     }
 }
 
@@ -311,6 +1082,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn connect_paste_redirect_flag_defaults_to_off() {
+        let paste_redirect = |args: &[&str]| match Cli::parse_from(args).command {
+            Commands::Connect { paste_redirect, .. } => paste_redirect,
+            _ => panic!("expected connect command"),
+        };
+
+        assert!(!paste_redirect(&["caldir", "connect", "google"]));
+        assert!(paste_redirect(&[
+            "caldir",
+            "connect",
+            "google",
+            "--paste-redirect"
+        ]));
+    }
+
+    #[test]
+    fn caldir_and_profile_flags_are_global_and_exclusive() {
+        let cli = Cli::parse_from(["caldir", "events", "--caldir", "/tmp/work"]);
+        assert_eq!(cli.caldir, Some(PathBuf::from("/tmp/work")));
+
+        let cli = Cli::parse_from(["caldir", "--profile", "work", "events"]);
+        assert_eq!(cli.profile.as_deref(), Some("work"));
+
+        assert!(
+            Cli::try_parse_from(["caldir", "--caldir", "/tmp/a", "--profile", "b", "events"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn trace_protocol_flag_takes_an_optional_file() {
+        let trace = |args: &[&str]| Cli::parse_from(args).trace_protocol;
+
+        assert_eq!(trace(&["caldir", "sync"]), None);
+        assert_eq!(trace(&["caldir", "--trace-protocol", "sync"]), Some(None));
+        assert_eq!(
+            trace(&["caldir", "--trace-protocol=/tmp/trace.log", "sync"]),
+            Some(Some(PathBuf::from("/tmp/trace.log")))
+        );
+    }
+
     #[test]
     fn hosted_flag_parses_explicit_values_and_defaults_to_true() {
         assert!(!parse_hosted(&[
@@ -3,7 +3,11 @@
 //! This module provides extension traits that add colored terminal rendering
 //! to caldir-core types using owo_colors.
 
+pub mod color;
 pub mod diff;
 pub mod event;
 pub mod events_in_range;
+pub mod ics_diff;
+pub mod plan;
+pub mod template;
 pub mod time;
@@ -1,7 +1,7 @@
-use crate::render::time::format_datetime;
+use crate::render::time::{Locale, format_datetime};
 use caldir_core::{
     Attachment, Attendee, Caldir, Calendar, CalendarDiff, EventChange, Recurrence, Reminder,
-    TimeFormat, XProperty,
+    XProperty,
 };
 use owo_colors::OwoColorize;
 use std::collections::{HashMap, HashSet};
@@ -42,7 +42,7 @@ impl Render for EventChange {
 
         let summary = colorize_diff(self, summary_text);
 
-        let time = format_datetime(&event.start, caldir.config().time_format());
+        let time = format_datetime(&event.start, Locale::from_config(caldir.config()));
 
         let recurring = if event.recurrence.is_some() {
             " 🔁"
@@ -167,9 +167,28 @@ fn render_bidirectional(
         render_diff_list(diff.incoming(), verbose, caldir, &mut lines);
     }
 
+    render_conflicts(diff, verbose, caldir, &mut lines);
+
     lines.join("\n")
 }
 
+/// Local changes held back because the remote changed since the last sync.
+fn render_conflicts(diff: &CalendarDiff, verbose: bool, caldir: &Caldir, lines: &mut Vec<String>) {
+    if diff.conflicts().is_empty() {
+        return;
+    }
+
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(
+        "   Changed remotely since the last sync (not pushed, --force to overwrite):"
+            .yellow()
+            .to_string(),
+    );
+    render_diff_list(diff.conflicts(), verbose, caldir, lines);
+}
+
 impl CalendarDiffRender for CalendarDiff {
     fn render(&self, verbose: bool, caldir: &Caldir) -> String {
         render_bidirectional(
@@ -192,12 +211,13 @@ impl CalendarDiffRender for CalendarDiff {
     }
 
     fn render_push(&self, verbose: bool, caldir: &Caldir) -> String {
-        if self.outgoing().is_empty() {
+        if self.outgoing().is_empty() && self.conflicts().is_empty() {
             return "   No changes to push".dimmed().to_string();
         }
 
         let mut lines = Vec::new();
         render_diff_list(self.outgoing(), verbose, caldir, &mut lines);
+        render_conflicts(self, verbose, caldir, &mut lines);
         lines.join("\n")
     }
 
@@ -220,7 +240,7 @@ impl CalendarDiffRender for CalendarDiff {
 /// Render field-by-field differences for an EventDiff (only for updates)
 fn render_field_diffs(diff: &EventChange, caldir: &Caldir) -> Vec<String> {
     let mut lines = Vec::new();
-    let time_format = caldir.config().time_format();
+    let locale = Locale::from_config(caldir.config());
 
     // Only show field diffs for updates
     if let EventChange::Update { from: old, to: new } = diff {
@@ -250,8 +270,8 @@ fn render_field_diffs(diff: &EventChange, caldir: &Caldir) -> Vec<String> {
             lines.push(format!(
                 "{}: {} → {}",
                 "start".dimmed(),
-                format_datetime(&old.start, time_format).red(),
-                format_datetime(&new.start, time_format).green()
+                format_datetime(&old.start, locale).red(),
+                format_datetime(&new.start, locale).green()
             ));
         }
         if old.end != new.end {
@@ -260,11 +280,11 @@ fn render_field_diffs(diff: &EventChange, caldir: &Caldir) -> Vec<String> {
                 "end".dimmed(),
                 old.end
                     .as_ref()
-                    .map_or("(none)".into(), |e| format_datetime(e, time_format))
+                    .map_or("(none)".into(), |e| format_datetime(e, locale))
                     .red(),
                 new.end
                     .as_ref()
-                    .map_or("(none)".into(), |e| format_datetime(e, time_format))
+                    .map_or("(none)".into(), |e| format_datetime(e, locale))
                     .green()
             ));
         }
@@ -289,6 +309,13 @@ fn render_field_diffs(diff: &EventChange, caldir: &Caldir) -> Vec<String> {
                 lines.extend(reminder_lines.into_iter().map(|l| format!("  {}", l)));
             }
         }
+        if old.reminders_default != new.reminders_default {
+            lines.push(render_display(
+                "default reminders",
+                &old.reminders_default,
+                &new.reminders_default,
+            ));
+        }
         if old.availability != new.availability {
             lines.push(render_display(
                 "availability",
@@ -296,6 +323,9 @@ fn render_field_diffs(diff: &EventChange, caldir: &Caldir) -> Vec<String> {
                 &new.availability,
             ));
         }
+        if old.event_type != new.event_type {
+            lines.push(render_display("type", &old.event_type, &new.event_type));
+        }
         if old.visibility != new.visibility {
             lines.push(render_optional_display(
                 "visibility",
@@ -311,7 +341,8 @@ fn render_field_diffs(diff: &EventChange, caldir: &Caldir) -> Vec<String> {
             ));
         }
         if old.attendees != new.attendees {
-            let attendee_lines = render_attendee_diffs(&old.attendees, &new.attendees);
+            let names = caldir.contact_names();
+            let attendee_lines = render_attendee_diffs(&old.attendees, &new.attendees, &names);
             if !attendee_lines.is_empty() {
                 lines.push(format!("{}:", "attendees".dimmed()));
                 lines.extend(attendee_lines.into_iter().map(|l| format!("  {}", l)));
@@ -320,6 +351,13 @@ fn render_field_diffs(diff: &EventChange, caldir: &Caldir) -> Vec<String> {
         if old.url != new.url {
             lines.push(render_optional_diff("url", &old.url, &new.url));
         }
+        if old.conferences != new.conferences {
+            lines.push(render_optional_diff(
+                "conference",
+                &old.conference_url().map(str::to_string),
+                &new.conference_url().map(str::to_string),
+            ));
+        }
         if old.attachments != new.attachments {
             let attachment_lines = render_attachment_diffs(&old.attachments, &new.attachments);
             if !attachment_lines.is_empty() {
@@ -500,7 +538,12 @@ fn render_reminder_diffs(old: &[Reminder], new: &[Reminder]) -> Vec<String> {
 }
 
 /// Render attendee changes, showing only what actually changed per attendee
-fn render_attendee_diffs(old: &[Attendee], new: &[Attendee]) -> Vec<String> {
+/// Attendees without a name get the one from `names` (contacts), if any.
+fn render_attendee_diffs(
+    old: &[Attendee],
+    new: &[Attendee],
+    names: &HashMap<String, String>,
+) -> Vec<String> {
     let mut lines = Vec::new();
 
     let old_by_email: HashMap<String, &Attendee> =
@@ -514,7 +557,7 @@ fn render_attendee_diffs(old: &[Attendee], new: &[Attendee]) -> Vec<String> {
         if let Some(new_att) = new_by_email.get(email)
             && old_att.status != new_att.status
         {
-            let label = attendee_label(new_att);
+            let label = attendee_label(new_att, names);
             let old_status = old_att
                 .status
                 .map_or("(none)".to_string(), |s| s.to_string());
@@ -535,14 +578,22 @@ fn render_attendee_diffs(old: &[Attendee], new: &[Attendee]) -> Vec<String> {
     // Added attendees
     for (email, att) in &new_by_email {
         if !old_by_email.contains_key(email) {
-            lines.push(format!("{} {}", "+".green(), attendee_label(att).green()));
+            lines.push(format!(
+                "{} {}",
+                "+".green(),
+                attendee_label(att, names).green()
+            ));
         }
     }
 
     // Removed attendees
     for (email, att) in &old_by_email {
         if !new_by_email.contains_key(email) {
-            lines.push(format!("{} {}", "-".red(), attendee_label(att).red()));
+            lines.push(format!(
+                "{} {}",
+                "-".red(),
+                attendee_label(att, names).red()
+            ));
         }
     }
 
@@ -550,8 +601,13 @@ fn render_attendee_diffs(old: &[Attendee], new: &[Attendee]) -> Vec<String> {
 }
 
 /// Format an attendee as "Name (email)" or just "email"
-fn attendee_label(att: &Attendee) -> String {
-    match &att.name {
+fn attendee_label(att: &Attendee, names: &HashMap<String, String>) -> String {
+    let name = att
+        .name
+        .as_ref()
+        .or_else(|| names.get(&att.email.to_lowercase()));
+
+    match name {
         Some(name) if !name.is_empty() => format!("{} ({})", name, att.email),
         _ => att.email.clone(),
     }
@@ -606,13 +662,13 @@ fn render_recurrence_diff(old: &Option<Recurrence>, new: &Option<Recurrence>) ->
             let old_set: HashSet<_> = old_rec
                 .exdates
                 .iter()
-                .map(|e| format_datetime(e, TimeFormat::H24))
+                .map(|e| format_datetime(e, Locale::default()))
                 .collect();
 
             let new_set: HashSet<_> = new_rec
                 .exdates
                 .iter()
-                .map(|e| format_datetime(e, TimeFormat::H24))
+                .map(|e| format_datetime(e, Locale::default()))
                 .collect();
 
             for ex in old_set.difference(&new_set) {
@@ -629,7 +685,7 @@ fn render_recurrence_diff(old: &Option<Recurrence>, new: &Option<Recurrence>) ->
                 lines.push(format!(
                     "{} exdate {}",
                     "+".green(),
-                    format_datetime(ex, TimeFormat::H24).green()
+                    format_datetime(ex, Locale::default()).green()
                 ));
             }
         }
@@ -639,7 +695,7 @@ fn render_recurrence_diff(old: &Option<Recurrence>, new: &Option<Recurrence>) ->
                 lines.push(format!(
                     "{} exdate {}",
                     "-".red(),
-                    format_datetime(ex, TimeFormat::H24).red()
+                    format_datetime(ex, Locale::default()).red()
                 ));
             }
         }
@@ -1,20 +1,66 @@
-use caldir_core::{Caldir, Event, ParticipationStatus, Status};
+use caldir_core::{Caldir, Event, EventTime, ParticipationStatus};
 use owo_colors::OwoColorize;
 
-use crate::render::time::format_time_only;
+use crate::render::color::{color_dot, parse_color};
+use crate::render::time::{Locale, format_time_in};
 
-pub fn is_visible(event: &Event) -> bool {
-    event.status != Status::Cancelled
-}
-
-/// Format a standard event line: "  {time} {summary} [{cal_slug}]{status}"
-pub fn format_event_line(event: &Event, cal_slug: &str, status: &str, caldir: &Caldir) -> String {
-    let time = format_time_only(&event.start, caldir.config().time_format());
+/// Format a standard event line: "  {time} {dot} {summary} [{cal_slug}]{status}",
+/// with the time as it reads in `tz`.
+///
+/// The dot uses the event's own color, falling back to the calendar's, and is
+/// left out when neither is set (or parseable).
+pub fn format_event_line(
+    event: &Event,
+    cal_slug: &str,
+    cal_color: Option<&str>,
+    status: &str,
+    caldir: &Caldir,
+    tz: chrono_tz::Tz,
+) -> String {
+    let time = format_time_in(&event.start, Locale::from_config(caldir.config()), &tz);
     let cal_tag = format!("[{}]", cal_slug);
 
     let summary_text = &event.summary.clone().unwrap_or("(Untitled)".to_string());
 
-    format!("  {} {} {}{}", time, summary_text, cal_tag.dimmed(), status)
+    let dot = event_color(event, cal_color)
+        .and_then(color_dot)
+        .map(|dot| format!("{} ", dot))
+        .unwrap_or_default();
+
+    format!(
+        "  {} {}{} {}{}",
+        time,
+        dot,
+        summary_text,
+        cal_tag.dimmed(),
+        status
+    )
+}
+
+/// `event` for `--json` output, with its times as they read in `tz`.
+pub fn event_json(event: &Event, calendar: &str, tz: chrono_tz::Tz) -> serde_json::Value {
+    let time = |time: &EventTime| match time {
+        EventTime::Date(date) => date.to_string(),
+        _ => time.to_local_tz(&tz).to_rfc3339(),
+    };
+
+    serde_json::json!({
+        "summary": event.summary,
+        "start": time(&event.start),
+        "end": event.end.as_ref().map(time),
+        "calendar": calendar,
+        "location": event.location,
+        "uid": event.uid.as_str(),
+    })
+}
+
+/// The color to show for an event: its own COLOR if valid, else the calendar's
+fn event_color<'a>(event: &'a Event, cal_color: Option<&'a str>) -> Option<&'a str> {
+    event
+        .color
+        .as_deref()
+        .filter(|c| parse_color(c).is_some())
+        .or(cal_color)
 }
 
 /// Render a participation status as colored text (e.g. "accepted" in green, "pending" in yellow)
@@ -33,17 +79,20 @@ pub fn render_participation_status(status: ParticipationStatus) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use caldir_core::EventTime;
     use chrono::NaiveDate;
 
     #[test]
-    fn confirmed_events_are_visible_cancelled_are_not() {
+    fn event_color_overrides_calendar_color() {
         let start = EventTime::Date(NaiveDate::from_ymd_opt(2026, 5, 27).unwrap());
-        let confirmed = Event::new("Standup", start.clone());
-        let mut cancelled = Event::new("yolo", start);
-        cancelled.status = Status::Cancelled;
+        let mut event = Event::new("Standup", start);
+
+        assert_eq!(event_color(&event, Some("#ac725e")), Some("#ac725e"));
+
+        event.color = Some("tomato".to_string());
+        assert_eq!(event_color(&event, Some("#ac725e")), Some("tomato"));
 
-        assert!(is_visible(&confirmed));
-        assert!(!is_visible(&cancelled));
+        event.color = Some("not-a-color".to_string());
+        assert_eq!(event_color(&event, Some("#ac725e")), Some("#ac725e"));
+        assert_eq!(event_color(&event, None), None);
     }
 }
@@ -1,37 +1,48 @@
 use anyhow::Result;
-use caldir_core::{Caldir, Calendar, Event, EventTime};
+use caldir_core::{Caldir, Calendar, Event, EventQuery, EventTime};
 use chrono::{DateTime, Duration, NaiveDate, Utc};
 use owo_colors::OwoColorize;
 
-use crate::render::event::{format_event_line, is_visible, render_participation_status};
-use crate::render::time::{format_date_label, local_date};
+use crate::render::event::{format_event_line, render_participation_status};
+use crate::render::template::Template;
+use crate::render::time::{Locale, date_in, format_date_label_in};
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_events_in_range(
     caldir: &Caldir,
     calendars: Vec<Calendar>,
     from: DateTime<Utc>,
     to: DateTime<Utc>,
+    tags: &[String],
+    hide_declined: bool,
+    tz: chrono_tz::Tz,
+    template: Option<&Template>,
 ) -> Result<()> {
-    let range_start = from.with_timezone(&chrono::Local).date_naive();
-    let range_end = to.with_timezone(&chrono::Local).date_naive();
+    let hide_declined = hide_declined || caldir.config().hide_declined();
+    let locale = Locale::from_config(caldir.config());
+
+    let range_start = from.with_timezone(&tz).date_naive();
+    let range_end = to.with_timezone(&tz).date_naive();
 
     // One entry per (day, event)
     // Note: a multi-day all-day event is repeated under every day it spans
-    // (day, cal_slug, account_email, event)
-    let mut entries: Vec<(NaiveDate, Option<&str>, Option<&str>, Event)> = Vec::new();
+    // (day, calendar, account_email, event)
+    let mut entries: Vec<(NaiveDate, &Calendar, Option<&str>, Event)> = Vec::new();
 
-    for cal in &calendars {
-        let events = cal.expanded_events_in_range(from, to)?;
+    let query = EventQuery::between(from, to).tags(tags);
 
+    for cal in &calendars {
         // Used to check the user's attendance status:
         let remote_email = cal.remote_email();
 
+        let events = match remote_email.filter(|_| hide_declined) {
+            Some(email) => cal.query(&query.clone().hide_declined_by(email))?,
+            None => cal.query(&query)?,
+        };
+
         for event in events {
-            if !is_visible(&event) {
-                continue;
-            }
-            for day in display_days(&event, range_start, range_end) {
-                entries.push((day, cal.slug(), remote_email, event.clone()));
+            for day in display_days(&event, range_start, range_end, locale, tz) {
+                entries.push((day, cal, remote_email, event.clone()));
             }
         }
     }
@@ -43,6 +54,25 @@ pub fn render_events_in_range(
             .then_with(|| a.3.start.to_utc().cmp(&b.3.start.to_utc()))
     });
 
+    // One line per event, without day headings, for scripts
+    if let Some(template) = template {
+        for (day, cal, _, event) in &entries {
+            // A multi-day event only on the first day it's listed under
+            if *day == date_in(&event.start, locale, &tz).max(range_start) {
+                println!(
+                    "{}",
+                    template.render(
+                        event,
+                        cal.slug().unwrap_or_default(),
+                        &tz,
+                        locale.floating_timezone
+                    )
+                );
+            }
+        }
+        return Ok(());
+    }
+
     if entries.is_empty() {
         println!("{}", "No events found".dimmed());
         return Ok(());
@@ -51,15 +81,19 @@ pub fn render_events_in_range(
     // Group events by day and print
     let mut current_date: Option<NaiveDate> = None;
 
-    for (day, cal_slug, email, event) in &entries {
+    for (day, cal, email, event) in &entries {
         if current_date != Some(*day) {
             if current_date.is_some() {
                 println!();
             }
-            println!("{}", format_date_label(*day).bold());
+            println!("{}", format_date_label_in(*day, locale, &tz).bold());
             current_date = Some(*day);
         }
 
+        let day_marker = day_of_span(event, *day)
+            .map(|(n, total)| format!(" (day {n}/{total})"))
+            .unwrap_or_default();
+
         let invite_indicator = email
             .as_deref()
             .filter(|email| event.is_invite_for(email))
@@ -71,9 +105,11 @@ pub fn render_events_in_range(
             "{}",
             format_event_line(
                 event,
-                cal_slug.unwrap_or("(Unknown calendar)"),
-                &invite_indicator,
-                caldir
+                cal.slug().unwrap_or("(Unknown calendar)"),
+                Calendar::color(cal),
+                &format!("{day_marker}{invite_indicator}"),
+                caldir,
+                tz
             )
         );
     }
@@ -82,32 +118,48 @@ pub fn render_events_in_range(
 }
 
 /// The day(s) an event should be listed under, clamped to `[range_start, range_end]`.
-/// Most events render once, on their start day.
+/// Most events render once, on their start day in `tz`.
 /// A multi-day all-day event renders under every day it covers
-fn display_days(event: &Event, range_start: NaiveDate, range_end: NaiveDate) -> Vec<NaiveDate> {
-    if let (EventTime::Date(start), Some(EventTime::Date(end))) = (&event.start, &event.end) {
-        // All-day DTEND is exclusive, so the last day covered is `end - 1`.
-        let last_day = *end - Duration::days(1);
-        if last_day > *start {
-            let first = (*start).max(range_start);
-            let last = last_day.min(range_end);
-            let mut days = Vec::new();
-            let mut day = first;
-            while day <= last {
-                days.push(day);
-                day += Duration::days(1);
-            }
-            return days;
-        }
-    }
+fn display_days(
+    event: &Event,
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+    locale: Locale,
+    tz: chrono_tz::Tz,
+) -> Vec<NaiveDate> {
+    let Some((start, last_day)) = all_day_span(event) else {
+        return vec![date_in(&event.start, locale, &tz)];
+    };
+
+    let first = start.max(range_start);
+    let last = last_day.min(range_end);
+    first.iter_days().take_while(|day| *day <= last).collect()
+}
 
-    vec![local_date(&event.start)]
+/// Which day of a multi-day all-day event `day` is, and how many days the
+/// event spans: `(2, 5)` for its second day of five.
+fn day_of_span(event: &Event, day: NaiveDate) -> Option<(i64, i64)> {
+    let (start, last_day) = all_day_span(event)?;
+    let total = (last_day - start).num_days() + 1;
+    Some(((day - start).num_days() + 1, total))
+}
+
+/// First and last day of an all-day event covering more than one day.
+fn all_day_span(event: &Event) -> Option<(NaiveDate, NaiveDate)> {
+    let (EventTime::Date(start), Some(EventTime::Date(end))) = (&event.start, &event.end) else {
+        return None;
+    };
+
+    // All-day DTEND is exclusive, so the last day covered is `end - 1`.
+    let last_day = *end - Duration::days(1);
+    (last_day > *start).then_some((*start, last_day))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::TimeZone;
+    use chrono_tz::UTC;
 
     fn date(y: i32, m: u32, d: u32) -> NaiveDate {
         NaiveDate::from_ymd_opt(y, m, d).unwrap()
@@ -124,7 +176,13 @@ mod tests {
         // Spans one day (DTEND is exclusive): May 27 only.
         let event = all_day(date(2026, 5, 27), date(2026, 5, 28));
 
-        let days = display_days(&event, date(2026, 5, 25), date(2026, 6, 1));
+        let days = display_days(
+            &event,
+            date(2026, 5, 25),
+            date(2026, 6, 1),
+            Locale::default(),
+            UTC,
+        );
 
         assert_eq!(days, vec![date(2026, 5, 27)]);
     }
@@ -134,7 +192,13 @@ mod tests {
         // May 27 through May 29 inclusive (DTEND May 30 exclusive).
         let event = all_day(date(2026, 5, 27), date(2026, 5, 30));
 
-        let days = display_days(&event, date(2026, 5, 25), date(2026, 6, 1));
+        let days = display_days(
+            &event,
+            date(2026, 5, 25),
+            date(2026, 6, 1),
+            Locale::default(),
+            UTC,
+        );
 
         assert_eq!(
             days,
@@ -148,7 +212,13 @@ mod tests {
         // appear from the window start onward, not under the past start day.
         let event = all_day(date(2026, 5, 27), date(2026, 6, 5));
 
-        let days = display_days(&event, date(2026, 6, 2), date(2026, 6, 7));
+        let days = display_days(
+            &event,
+            date(2026, 6, 2),
+            date(2026, 6, 7),
+            Locale::default(),
+            UTC,
+        );
 
         assert_eq!(
             days,
@@ -160,7 +230,13 @@ mod tests {
     fn multi_day_event_extending_past_window_is_clamped_to_window_end() {
         let event = all_day(date(2026, 6, 1), date(2026, 6, 20));
 
-        let days = display_days(&event, date(2026, 6, 1), date(2026, 6, 3));
+        let days = display_days(
+            &event,
+            date(2026, 6, 1),
+            date(2026, 6, 3),
+            Locale::default(),
+            UTC,
+        );
 
         assert_eq!(
             days,
@@ -168,6 +244,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multi_day_all_day_event_counts_its_days() {
+        // May 27 through May 31 inclusive (DTEND June 1 exclusive).
+        let event = all_day(date(2026, 5, 27), date(2026, 6, 1));
+
+        assert_eq!(day_of_span(&event, date(2026, 5, 27)), Some((1, 5)));
+        assert_eq!(day_of_span(&event, date(2026, 5, 28)), Some((2, 5)));
+        assert_eq!(day_of_span(&event, date(2026, 5, 31)), Some((5, 5)));
+    }
+
+    #[test]
+    fn single_day_all_day_event_has_no_day_marker() {
+        let event = all_day(date(2026, 5, 27), date(2026, 5, 28));
+
+        assert_eq!(day_of_span(&event, date(2026, 5, 27)), None);
+    }
+
     #[test]
     fn timed_event_shows_only_on_its_start_day() {
         let mut event = Event::new(
@@ -178,8 +271,33 @@ mod tests {
             Utc.with_ymd_and_hms(2026, 6, 2, 15, 0, 0).unwrap(),
         ));
 
-        let days = display_days(&event, date(2026, 6, 1), date(2026, 6, 7));
+        let days = display_days(
+            &event,
+            date(2026, 6, 1),
+            date(2026, 6, 7),
+            Locale::default(),
+            UTC,
+        );
+
+        assert_eq!(days, vec![date(2026, 6, 2)]);
+    }
+
+    #[test]
+    fn timed_event_shows_on_its_start_day_in_the_display_timezone() {
+        // 20:00 UTC on June 2 is already June 3 in Tokyo.
+        let event = Event::new(
+            "Standup",
+            EventTime::DateTimeUtc(Utc.with_ymd_and_hms(2026, 6, 2, 20, 0, 0).unwrap()),
+        );
+
+        let days = display_days(
+            &event,
+            date(2026, 6, 1),
+            date(2026, 6, 7),
+            Locale::default(),
+            chrono_tz::Asia::Tokyo,
+        );
 
-        assert_eq!(days, vec![local_date(&event.start)]);
+        assert_eq!(days, vec![date(2026, 6, 3)]);
     }
 }
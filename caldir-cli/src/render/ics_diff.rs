@@ -0,0 +1,197 @@
+//! Line-by-line diffs of the ICS that updates replace, like `diff -u`.
+
+use caldir_core::{Caldir, CalendarDiff, Event, EventChange};
+use owo_colors::OwoColorize;
+
+use crate::render::diff::Render;
+
+/// Unchanged lines shown around each change
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Same,
+    Removed,
+    Added,
+}
+
+/// The ICS diff of every update in `diff`, each under its event's line.
+pub fn render_ics_diffs(diff: &CalendarDiff, caldir: &Caldir) -> String {
+    let updates = diff
+        .outgoing()
+        .iter()
+        .chain(diff.incoming())
+        .chain(diff.conflicts());
+
+    let mut lines = Vec::new();
+    for change in updates {
+        let EventChange::Update { from, to } = change else {
+            continue;
+        };
+        lines.push(format!("   {}", change.render(caldir)));
+        lines.extend(
+            unified_diff(&ics_lines(from), &ics_lines(to))
+                .into_iter()
+                .map(|l| format!("     {l}")),
+        );
+    }
+
+    lines.join("\n")
+}
+
+// DTSTAMP is when the ICS was written, so it would differ on every run
+fn ics_lines(event: &Event) -> Vec<String> {
+    event
+        .to_ics_string()
+        .lines()
+        .filter(|line| !line.starts_with("DTSTAMP:"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Hunks of `-`/`+` lines with a few unchanged lines around them.
+fn unified_diff(old: &[String], new: &[String]) -> Vec<String> {
+    let ops = line_ops(old, new);
+    let mut lines = Vec::new();
+
+    for (start, end) in hunks(&ops) {
+        lines.push(
+            hunk_header(&ops[..start], &ops[start..end])
+                .cyan()
+                .to_string(),
+        );
+
+        for (op, line) in &ops[start..end] {
+            lines.push(match op {
+                Op::Same => format!(" {line}").dimmed().to_string(),
+                Op::Removed => format!("-{line}").red().to_string(),
+                Op::Added => format!("+{line}").green().to_string(),
+            });
+        }
+    }
+
+    lines
+}
+
+/// `@@ -old_start,old_count +new_start,new_count @@` for the `hunk` after `before`.
+fn hunk_header(before: &[(Op, &str)], hunk: &[(Op, &str)]) -> String {
+    let count = |ops: &[(Op, &str)], skip: Op| ops.iter().filter(|(op, _)| *op != skip).count();
+
+    format!(
+        "@@ -{},{} +{},{} @@",
+        count(before, Op::Added) + 1,
+        count(hunk, Op::Added),
+        count(before, Op::Removed) + 1,
+        count(hunk, Op::Removed)
+    )
+}
+
+/// Ranges of `ops` to print: each change plus `CONTEXT` lines either side,
+/// merged when they overlap.
+fn hunks(ops: &[(Op, &str)]) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+
+    for (i, _) in ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != Op::Same)
+    {
+        let start = i.saturating_sub(CONTEXT);
+        let end = (i + CONTEXT + 1).min(ops.len());
+
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    hunks
+}
+
+/// Lines of `old` and `new` in order, as kept, removed or added (longest
+/// common subsequence, which is plenty for a single event).
+fn line_ops<'a>(old: &'a [String], new: &'a [String]) -> Vec<(Op, &'a str)> {
+    let (n, m) = (old.len(), new.len());
+
+    // common[i][j]: length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push((Op::Same, old[i].as_str()));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            ops.push((Op::Removed, old[i].as_str()));
+            i += 1;
+        } else {
+            ops.push((Op::Added, new[j].as_str()));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|l| (Op::Removed, l.as_str())));
+    ops.extend(new[j..].iter().map(|l| (Op::Added, l.as_str())));
+
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    fn headers(old: &[String], new: &[String]) -> Vec<String> {
+        let ops = line_ops(old, new);
+        hunks(&ops)
+            .into_iter()
+            .map(|(start, end)| hunk_header(&ops[..start], &ops[start..end]))
+            .collect()
+    }
+
+    #[test]
+    fn line_ops_keeps_common_lines() {
+        let old = lines("A\nB\nC");
+        let new = lines("A\nX\nC");
+        let ops = line_ops(&old, &new);
+
+        assert_eq!(
+            ops,
+            vec![
+                (Op::Same, "A"),
+                (Op::Removed, "B"),
+                (Op::Added, "X"),
+                (Op::Same, "C")
+            ]
+        );
+    }
+
+    #[test]
+    fn hunks_show_context_and_split_when_far_apart() {
+        let old = lines("A\nB\nC\nD\nE\nF\nG\nH\nI");
+        let new = lines("A\nB\nC\nD\nX\nF\nG\nH\nI");
+        assert_eq!(headers(&old, &new), vec!["@@ -2,7 +2,7 @@"]);
+
+        let old = lines("1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11");
+        let new = lines("0\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10");
+        assert_eq!(
+            headers(&old, &new),
+            vec!["@@ -1,3 +1,4 @@", "@@ -8,4 +9,3 @@"]
+        );
+
+        assert!(unified_diff(&old, &old).is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+use caldir_core::{Layout, PlannedStep};
+use owo_colors::OwoColorize;
+use std::path::Path;
+
+/// The path within its calendar, e.g. `2025/03/…​.ics` in year/month layouts.
+fn file_name(path: &Path) -> String {
+    let (_, calendar_dir) = Layout::of_path(path);
+    path.strip_prefix(calendar_dir)
+        .map(|relative| relative.display().to_string())
+        .unwrap_or_else(|_| path.display().to_string())
+}
+
+fn render_step(step: &PlannedStep) -> String {
+    match step {
+        PlannedStep::CreateFile(path) => format!("{} {}", "create".green(), file_name(path)),
+        PlannedStep::UpdateFile(path) => format!("{} {}", "update".yellow(), file_name(path)),
+        PlannedStep::RenameFile { from, to } => format!(
+            "{} {} → {}",
+            "rename".yellow(),
+            file_name(from),
+            file_name(to)
+        ),
+        PlannedStep::DeleteFile(path) => format!("{} {}", "delete".red(), file_name(path)),
+        PlannedStep::Call { method, events } => {
+            format!("{} {} ({})", "call".blue(), method, events.join(", "))
+        }
+    }
+}
+
+/// What a dry run would have done, one step per line.
+pub fn render_plan(steps: &[PlannedStep]) -> String {
+    if steps.is_empty() {
+        return format!("   {}", "Nothing to do".dimmed());
+    }
+
+    steps
+        .iter()
+        .map(|step| format!("   {}", render_step(step)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
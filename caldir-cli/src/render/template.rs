@@ -0,0 +1,256 @@
+//! `--format` templates for event listings, e.g.
+//! `"{start:%H:%M} {summary} ({calendar})"`.
+//!
+//! `{field}` is replaced by the event's value, `{start:FMT}` and `{end:FMT}`
+//! take a strftime format, and `{{` / `}}` are literal braces.
+
+use anyhow::{Result, bail};
+use caldir_core::{Event, EventTime, FloatingTimezone};
+use chrono::format::{Item, StrftimeItems};
+
+use crate::render::time::humanize_minutes;
+
+const FIELDS: &[&str] = &[
+    "summary",
+    "start",
+    "end",
+    "duration",
+    "calendar",
+    "location",
+    "description",
+    "status",
+    "attendees",
+    "tags",
+    "url",
+    "conference",
+    "uid",
+];
+
+const DEFAULT_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Field {
+        name: String,
+        format: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = input.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => field.push(c),
+                            None => bail!("Unclosed {{ in --format: {input}"),
+                        }
+                    }
+
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(parse_field(&field)?);
+                }
+                '}' => bail!("Unmatched }} in --format (use }}}} for a literal brace)"),
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// `event` with its fields filled in. Times are shown in `tz`, with
+    /// floating times in `floating`.
+    pub fn render(
+        &self,
+        event: &Event,
+        calendar: &str,
+        tz: &chrono_tz::Tz,
+        floating: FloatingTimezone,
+    ) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Field { name, format } => {
+                    field_value(name, format.as_deref(), event, calendar, tz, floating)
+                }
+            })
+            .collect()
+    }
+}
+
+/// `name` or `name:format`
+fn parse_field(field: &str) -> Result<Segment> {
+    let (name, format) = match field.split_once(':') {
+        Some((name, format)) => (name.trim(), Some(format.to_string())),
+        None => (field.trim(), None),
+    };
+
+    if !FIELDS.contains(&name) {
+        bail!(
+            "Unknown field {{{name}}} in --format (available: {})",
+            FIELDS.join(", ")
+        );
+    }
+
+    if let Some(format) = &format {
+        if !matches!(name, "start" | "end") {
+            bail!("Only {{start}} and {{end}} take a format, not {{{name}}}");
+        }
+        if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+            bail!("Invalid time format in {{{field}}}");
+        }
+    }
+
+    Ok(Segment::Field {
+        name: name.to_string(),
+        format,
+    })
+}
+
+fn field_value(
+    name: &str,
+    format: Option<&str>,
+    event: &Event,
+    calendar: &str,
+    tz: &chrono_tz::Tz,
+    floating: FloatingTimezone,
+) -> String {
+    match name {
+        "summary" => event.summary.clone().unwrap_or_default(),
+        "start" => format_time(&event.start, format, tz, floating),
+        "end" => event
+            .end
+            .as_ref()
+            .map(|end| format_time(end, format, tz, floating))
+            .unwrap_or_default(),
+        "duration" => event
+            .end
+            .as_ref()
+            .map(|end| humanize_minutes((end.to_utc() - event.start.to_utc()).num_minutes()))
+            .unwrap_or_default(),
+        "calendar" => calendar.to_string(),
+        "location" => event.location.clone().unwrap_or_default(),
+        "description" => event.description.clone().unwrap_or_default(),
+        "status" => event.status.to_string(),
+        "attendees" => event
+            .attendees
+            .iter()
+            .map(|attendee| attendee.email.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        "tags" => event.categories.join(", "),
+        "url" => event.url.clone().unwrap_or_default(),
+        "conference" => event.conference_url().unwrap_or_default().to_string(),
+        "uid" => event.uid.as_str().to_string(),
+        _ => String::new(),
+    }
+}
+
+/// All-day events are formatted as midnight, so time-only formats still
+/// work on them.
+fn format_time(
+    time: &EventTime,
+    format: Option<&str>,
+    tz: &chrono_tz::Tz,
+    floating: FloatingTimezone,
+) -> String {
+    let (datetime, default_format) = match time {
+        EventTime::Date(date) => (date.and_time(chrono::NaiveTime::MIN), DEFAULT_DATE_FORMAT),
+        _ => (
+            time.to_local_tz_with(tz, floating).naive_local(),
+            DEFAULT_DATETIME_FORMAT,
+        ),
+    };
+
+    datetime
+        .format(format.unwrap_or(default_format))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn standup() -> Event {
+        let mut event = Event::new(
+            "Standup",
+            EventTime::DateTimeUtc(Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap()),
+        );
+        event.end = Some(EventTime::DateTimeUtc(
+            Utc.with_ymd_and_hms(2026, 3, 2, 9, 15, 0).unwrap(),
+        ));
+        event
+    }
+
+    #[test]
+    fn renders_fields_with_time_formats() {
+        let template = Template::parse("{start:%H:%M} {summary} ({calendar}, {duration})").unwrap();
+
+        let line = template.render(&standup(), "work", &chrono_tz::UTC, FloatingTimezone::Local);
+
+        assert_eq!(line, "09:00 Standup (work, 15m)");
+    }
+
+    #[test]
+    fn all_day_events_use_the_date() {
+        let event = Event::new(
+            "Holiday",
+            EventTime::Date(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()),
+        );
+
+        let template = Template::parse("{start} {start:%H:%M}").unwrap();
+
+        assert_eq!(
+            template.render(&event, "home", &chrono_tz::UTC, FloatingTimezone::Local),
+            "2026-03-02 00:00"
+        );
+    }
+
+    #[test]
+    fn double_braces_are_literal() {
+        let template = Template::parse("{{{summary}}}").unwrap();
+
+        assert_eq!(
+            template.render(&standup(), "work", &chrono_tz::UTC, FloatingTimezone::Local),
+            "{Standup}"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_bad_formats() {
+        assert!(Template::parse("{title}").is_err());
+        assert!(Template::parse("{summary:%H}").is_err());
+        assert!(Template::parse("{start:%Q}").is_err());
+        assert!(Template::parse("{summary").is_err());
+    }
+}
@@ -1,67 +1,119 @@
-use caldir_core::{EventTime, TimeFormat};
-use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use caldir_core::{CaldirConfig, DateOrder, EventTime, FloatingTimezone, TimeFormat, WeekStart};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+
+/// How dates and times are written, from the config. Output goes through
+/// the helpers below rather than format strings of its own.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Locale {
+    pub time_format: TimeFormat,
+    pub date_order: DateOrder,
+    pub week_start: WeekStart,
+    pub floating_timezone: FloatingTimezone,
+}
+
+impl Locale {
+    pub fn from_config(config: &CaldirConfig) -> Self {
+        Self {
+            time_format: config.time_format(),
+            date_order: config.date_order(),
+            week_start: config.week_start(),
+            floating_timezone: config.floating_timezone(),
+        }
+    }
+}
 
 /// The local calendar date an event time falls on.
-pub fn local_date(time: &EventTime) -> NaiveDate {
+pub fn local_date(time: &EventTime, locale: Locale) -> NaiveDate {
+    date_in(time, locale, &chrono::Local)
+}
+
+/// The calendar date an event time falls on in `tz`. Dates are the same
+/// everywhere, and so are floating times unless `floating_timezone` pins them.
+pub fn date_in<Tz: TimeZone>(time: &EventTime, locale: Locale, tz: &Tz) -> NaiveDate {
     match time {
         EventTime::Date(d) => *d,
-        EventTime::DateTimeUtc(dt) => dt.with_timezone(&chrono::Local).date_naive(),
-        EventTime::DateTimeFloating(dt) => dt.date(),
-        EventTime::DateTimeZoned { datetime, tzid } => zoned_to_local(datetime, tzid).date(),
+        _ => time
+            .to_local_tz_with(tz, locale.floating_timezone)
+            .date_naive(),
     }
 }
 
 /// Format a date as a human-readable label (e.g. "Today", "Tomorrow", "Wed Feb 25").
 /// The year is appended when the date is not in the current year (e.g. "Wed Feb 25 2023").
-pub fn format_date_label(date: NaiveDate) -> String {
-    let today = chrono::Local::now().date_naive();
+pub fn format_date_label(date: NaiveDate, locale: Locale) -> String {
+    format_date_label_in(date, locale, &chrono::Local)
+}
+
+/// [`format_date_label`], with "Today" being today in `tz`.
+pub fn format_date_label_in<Tz: TimeZone>(date: NaiveDate, locale: Locale, tz: &Tz) -> String {
+    let today = Utc::now().with_timezone(tz).date_naive();
 
     let diff = (date - today).num_days();
     match diff {
         0 => "Today".to_string(),
         1 => "Tomorrow".to_string(),
-        _ if date.year() == today.year() => date.format("%a %b %-d").to_string(),
-        _ => date.format("%a %b %-d %Y").to_string(),
+        _ => format_date(date, locale.date_order, date.year() != today.year()),
     }
 }
 
+/// "Wed Feb 25", "Wed 25 Feb" or "Wed 2026-02-25" (which always has the year).
+fn format_date(date: NaiveDate, order: DateOrder, with_year: bool) -> String {
+    let format = match (order, with_year) {
+        (DateOrder::Mdy, false) => "%a %b %-d",
+        (DateOrder::Mdy, true) => "%a %b %-d %Y",
+        (DateOrder::Dmy, false) => "%a %-d %b",
+        (DateOrder::Dmy, true) => "%a %-d %b %Y",
+        (DateOrder::Ymd, _) => "%a %Y-%m-%d",
+    };
+    date.format(format).to_string()
+}
+
 /// Format an event time as a human-readable date label.
-pub fn format_date_only(time: &EventTime) -> String {
-    format_date_label(local_date(time))
+pub fn format_date_only(time: &EventTime, locale: Locale) -> String {
+    format_date_label(local_date(time, locale), locale)
 }
 
 /// Format the time portion of an event (e.g. "  15:00" or " 3:00pm" or "all-day"), right-padded to 7 chars
-pub fn format_time_only(time: &EventTime, time_format: TimeFormat) -> String {
+pub fn format_time_only(time: &EventTime, locale: Locale) -> String {
+    format_time_in(time, locale, &chrono::Local)
+}
+
+/// [`format_time_only`], as the clock reads in `tz`.
+pub fn format_time_in<Tz: TimeZone>(time: &EventTime, locale: Locale, tz: &Tz) -> String {
     match time {
         EventTime::Date(_) => "all-day".to_string(),
-        EventTime::DateTimeUtc(dt) => {
-            let local = dt.with_timezone(&chrono::Local).naive_local();
-            format_naive_time(&local, time_format)
-        }
-        EventTime::DateTimeFloating(dt) => format_naive_time(dt, time_format),
-        EventTime::DateTimeZoned { datetime, tzid } => {
-            format_naive_time(&zoned_to_local(datetime, tzid), time_format)
-        }
+        _ => format_naive_time(
+            &time
+                .to_local_tz_with(tz, locale.floating_timezone)
+                .naive_local(),
+            locale.time_format,
+        ),
     }
 }
 
 // Format a compact date+time string (e.g. "Today 15:00", "Tomorrow all-day", "Wed Mar 20 15:00")
 // Used in contexts where events are not grouped by date (e.g. status/diff output).
-pub fn format_datetime(time: &EventTime, time_format: TimeFormat) -> String {
-    let date_label = format_date_only(time);
-    let time_label = format_time_only(time, time_format).trim_start().to_string();
+pub fn format_datetime(time: &EventTime, locale: Locale) -> String {
+    let date_label = format_date_only(time, locale);
+    let time_label = format_time_only(time, locale).trim_start().to_string();
     format!("{}, {}", date_label, time_label)
 }
 
-/// Convert a zoned datetime to the system's local NaiveDateTime.
-/// Falls back to the original datetime if the timezone can't be parsed.
-fn zoned_to_local(datetime: &NaiveDateTime, tzid: &str) -> NaiveDateTime {
-    if let Ok(tz) = tzid.parse::<chrono_tz::Tz>()
-        && let Some(zoned) = datetime.and_local_timezone(tz).single()
-    {
-        return zoned.with_timezone(&chrono::Local).naive_local();
+/// When something happened, for logs and listings (e.g. "2026-03-02 15:04").
+/// The date stays ISO so these sort and compare the same in every locale.
+pub fn format_timestamp(at: DateTime<Utc>, locale: Locale) -> String {
+    let local = at.with_timezone(&chrono::Local).naive_local();
+    let time = format_naive_time(&local, locale.time_format);
+    format!("{} {}", local.format("%Y-%m-%d"), time.trim_start())
+}
+
+/// 90 → "1h30m"
+pub fn humanize_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h{m}m"),
     }
-    *datetime
 }
 
 /// Format a NaiveDateTime's time portion according to the given format.
@@ -84,3 +136,17 @@ fn format_naive_time(dt: &NaiveDateTime, time_format: TimeFormat) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_date_follows_date_order() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 25).unwrap();
+
+        assert_eq!(format_date(date, DateOrder::Mdy, false), "Wed Feb 25");
+        assert_eq!(format_date(date, DateOrder::Dmy, true), "Wed 25 Feb 2026");
+        assert_eq!(format_date(date, DateOrder::Ymd, false), "Wed 2026-02-25");
+    }
+}
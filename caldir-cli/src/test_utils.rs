@@ -1,4 +1,5 @@
 use anyhow::Result;
+use caldir_core::Caldir;
 
 pub fn capture<F>(f: F) -> String
 where
@@ -8,3 +9,17 @@ where
     f(&mut out).unwrap();
     String::from_utf8(out).unwrap()
 }
+
+/// A caldir in a temp dir with one empty calendar, `personal`, as the default.
+pub fn test_caldir() -> (tempfile::TempDir, Caldir) {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = format!(
+        "calendar_dir = {:?}\ndefault_calendar = \"personal\"\n",
+        tmp.path().join("calendars")
+    );
+    std::fs::write(tmp.path().join("config.toml"), config).unwrap();
+
+    let caldir = Caldir::load_from_home(tmp.path()).unwrap();
+    caldir.create_calendar("personal", None).unwrap();
+    (tmp, caldir)
+}
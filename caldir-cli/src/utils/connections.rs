@@ -1,3 +1,4 @@
+use caldir_core::rpc::ErrorCode;
 use caldir_core::{Caldir, CaldirError, Connection, EventChange};
 
 /// Return the caldir's connections, optionally narrowed to specific calendar slugs.
@@ -23,6 +24,22 @@ pub fn connections(
         .collect()
 }
 
+/// What the user can do about a failure the provider tagged with `code`.
+pub fn error_hint(connection: &Connection, code: Option<ErrorCode>) -> Option<String> {
+    match code? {
+        ErrorCode::AuthExpired => {
+            let provider = connection.local().remote_config()?.provider_slug();
+            Some(format!(
+                "Sign-in expired, run `caldir connect {provider}` to reconnect."
+            ))
+        }
+        ErrorCode::RateLimited => {
+            Some("Rate limited by the provider, try again later.".to_string())
+        }
+        _ => None,
+    }
+}
+
 /// Count `(created, updated, deleted)` over a sequence of event changes.
 pub fn count_changes<'a, I>(changes: I) -> (usize, usize, usize)
 where
@@ -0,0 +1,17 @@
+use anyhow::Result;
+use caldir_core::Caldir;
+
+/// The timezone to show event times in: `--tz` if given, else
+/// `display_timezone` from config, else the system timezone.
+pub fn display_timezone(caldir: &Caldir, tz: Option<&str>) -> Result<chrono_tz::Tz> {
+    let name = match tz.or(caldir.config().display_timezone()) {
+        Some(name) => name.to_string(),
+        None => iana_time_zone::get_timezone()?,
+    };
+
+    name.parse().map_err(|_| {
+        anyhow::anyhow!(
+            "Unknown timezone: \"{name}\" (expected an IANA name like Europe/Stockholm)"
+        )
+    })
+}
@@ -0,0 +1,21 @@
+use std::path::Path;
+
+use caldir_core::Calendar;
+
+/// The UID of the event `arg` names in `calendar`: its file name (with or
+/// without its extension), or the UID itself.
+pub fn resolve_event_uid(calendar: &Calendar, arg: &str) -> String {
+    let file_name = Path::new(arg)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(arg);
+    let slug = file_name
+        .strip_suffix(".ics")
+        .or_else(|| file_name.strip_suffix(".md"))
+        .unwrap_or(file_name);
+
+    match calendar.event(slug) {
+        Ok(cal_event) => cal_event.event().uid.as_str().to_string(),
+        Err(_) => arg.to_string(),
+    }
+}
@@ -1,6 +1,11 @@
-use caldir_core::{CalendarDiff, EventChange};
+use std::io::IsTerminal;
+
+use caldir_core::{Caldir, CalendarDiff, EventChange};
+use dialoguer::Confirm;
 use owo_colors::OwoColorize;
 
+use crate::render::diff::Render;
+
 /// Number of pending deletions that triggers the safeguard.
 const MASS_DELETE_THRESHOLD: usize = 10;
 
@@ -25,3 +30,40 @@ pub fn allow_mass_delete(diff: &CalendarDiff, force: bool) -> bool {
     );
     false
 }
+
+/// Asks before a pull deletes more local events than the configured
+/// `pull_delete_threshold`, listing them. Returns `false` when the pull
+/// should be skipped; without a terminal to ask on, it always is.
+pub fn confirm_pull_deletes(diff: &CalendarDiff, caldir: &Caldir, force: bool) -> bool {
+    let deletes: Vec<&EventChange> = diff
+        .incoming()
+        .iter()
+        .filter(|d| matches!(d, EventChange::Delete(_)))
+        .collect();
+
+    if force || deletes.len() <= caldir.config().pull_delete_threshold() {
+        return true;
+    }
+
+    println!(
+        "   {}",
+        format!("This pull would delete {} local events:", deletes.len()).red()
+    );
+    for delete in &deletes {
+        println!("   {}", delete.render(caldir));
+    }
+
+    if !std::io::stdin().is_terminal() {
+        println!(
+            "   {}",
+            "Not pulling. If you're sure, re-run with --force.".red()
+        );
+        return false;
+    }
+
+    Confirm::new()
+        .with_prompt("Delete them?")
+        .default(false)
+        .interact()
+        .unwrap_or(false)
+}
@@ -0,0 +1,143 @@
+use anyhow::{Context, Result, anyhow, bail};
+use caldir_core::rpc::Notify;
+use caldir_core::{Caldir, CalendarDiff, Connection, ImipMessage, SmtpConfig, imip_messages};
+use lettre::message::header::ContentType;
+use lettre::message::{Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use owo_colors::OwoColorize;
+
+/// Port where SMTP starts with TLS instead of upgrading via STARTTLS.
+const IMPLICIT_TLS_PORT: u16 = 465;
+
+/// The `--notify` value, if one was given.
+pub fn parse_notify(input: Option<&str>) -> Result<Option<Notify>> {
+    input
+        .map(|input| {
+            Notify::parse(input)
+                .ok_or_else(|| anyhow!("Unknown --notify: {input} (use all, external or none)"))
+        })
+        .transpose()
+}
+
+/// Which attendees to email about pushes to `connection`: `--notify`, or
+/// else the calendar's `notify` setting, or else nobody.
+pub fn notify_for(connection: &Connection, flag: Option<Notify>) -> Notify {
+    flag.or_else(|| connection.local().notify())
+        .unwrap_or_default()
+}
+
+/// Emails attendees about the outgoing changes in `diff` (iMIP), when
+/// they're to be notified, the provider doesn't do it itself, and `[smtp]`
+/// is configured. Prints what was sent.
+pub async fn send_invitations(caldir: &Caldir, connection: &Connection, diff: &CalendarDiff) {
+    let notify = connection.remote().notify_attendees();
+    if notify == Notify::None || connection.remote().capabilities().await.invitations {
+        return;
+    }
+
+    let Some(smtp) = caldir.config().smtp() else {
+        println!(
+            "   {}",
+            "This provider doesn't email attendees. Add an [smtp] section to the config to send invitations.".dimmed()
+        );
+        return;
+    };
+
+    let organizer = connection.local().remote_email().unwrap_or(smtp.from());
+    let mut messages = imip_messages(diff.outgoing(), organizer);
+    for message in &mut messages {
+        message.to.retain(|to| notify.includes(to, organizer));
+    }
+    messages.retain(|message| !message.to.is_empty());
+
+    if messages.is_empty() {
+        return;
+    }
+
+    let transport = match transport(smtp) {
+        Ok(transport) => transport,
+        Err(e) => {
+            println!("   {}", format!("Couldn't send invitations: {e:#}").red());
+            return;
+        }
+    };
+
+    for message in &messages {
+        let result = match email(smtp, message) {
+            Ok(email) => transport.send(email).await.map_err(anyhow::Error::from),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(_) => println!(
+                "   {} {} → {}",
+                "✉".green(),
+                message.subject,
+                message.to.join(", ").dimmed()
+            ),
+            Err(e) => println!(
+                "   {}",
+                format!("Couldn't send \"{}\": {e:#}", message.subject).red()
+            ),
+        }
+    }
+}
+
+fn transport(smtp: &SmtpConfig) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
+    let builder = if smtp.port() == IMPLICIT_TLS_PORT {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(smtp.host())?
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(smtp.host())?
+    };
+    let mut builder = builder.port(smtp.port());
+
+    if let Some(username) = smtp.username() {
+        builder = builder.credentials(Credentials::new(username.to_string(), password(smtp)?));
+    }
+
+    Ok(builder.build())
+}
+
+fn password(smtp: &SmtpConfig) -> Result<String> {
+    let Some(command) = smtp.password_command() else {
+        bail!("smtp.username is set but smtp.password_command isn't");
+    };
+
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .context("Couldn't run smtp.password_command")?;
+
+    if !output.status.success() {
+        bail!("smtp.password_command failed ({})", output.status);
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim_end().to_string())
+}
+
+/// A plain-text note plus the `text/calendar` part mail clients act on.
+fn email(smtp: &SmtpConfig, message: &ImipMessage) -> Result<Message> {
+    let mut builder = Message::builder()
+        .from(smtp.from().parse::<Mailbox>()?)
+        .subject(&message.subject);
+    for to in &message.to {
+        builder = builder.to(to.parse::<Mailbox>()?);
+    }
+
+    let calendar_type = ContentType::parse(&format!(
+        "text/calendar; method={}; charset=UTF-8",
+        message.method.as_str()
+    ))?;
+
+    Ok(builder.multipart(
+        MultiPart::alternative()
+            .singlepart(SinglePart::plain(message.subject.clone()))
+            .singlepart(
+                SinglePart::builder()
+                    .header(calendar_type)
+                    .body(message.ics.clone()),
+            ),
+    )?)
+}
@@ -1,15 +1,21 @@
 mod connections;
 mod date;
+mod display_timezone;
+mod event_uid;
 mod guards;
+mod invitations;
 mod path;
 mod require_calendars;
 mod resolve_calendars;
 mod sync_range;
 pub mod tui;
 
-pub use connections::{connections, count_changes};
+pub use connections::{connections, count_changes, error_hint};
 pub use date::parse_date;
-pub use guards::allow_mass_delete;
+pub use display_timezone::display_timezone;
+pub use event_uid::resolve_event_uid;
+pub use guards::{allow_mass_delete, confirm_pull_deletes};
+pub use invitations::{notify_for, parse_notify, send_invitations};
 pub use path::PathExt;
 pub use require_calendars::require_calendars;
 pub use resolve_calendars::resolve_calendars;
@@ -1,5 +1,6 @@
 use caldir_core::{Caldir, Calendar};
 
+/// The calendar named `calendar_filter`, or all calendars that aren't archived.
 pub fn resolve_calendars(
     caldir: &Caldir,
     calendar_filter: Option<&str>,
@@ -27,6 +28,10 @@ pub fn resolve_calendars(
                 );
             }
         },
-        None => Ok(all_calendars),
+        // Archived calendars only when asked for by name
+        None => Ok(all_calendars
+            .into_iter()
+            .filter(|c| !c.is_archived())
+            .collect()),
     }
 }
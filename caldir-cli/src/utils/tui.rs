@@ -12,3 +12,8 @@ pub fn create_spinner(message: String) -> ProgressBar {
     spinner.enable_steady_tick(std::time::Duration::from_millis(80));
     spinner
 }
+
+/// Progress callback showing how many events have been fetched next to `header`.
+pub fn fetch_progress<'a>(spinner: &'a ProgressBar, header: &'a str) -> impl Fn(usize) + Sync + 'a {
+    move |count| spinner.set_message(format!("{header} ({count} events)"))
+}
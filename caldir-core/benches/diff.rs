@@ -0,0 +1,89 @@
+//! `Connection::diff` against a calendar of a few thousand events, to catch
+//! lookups in the diff that grow with the calendar size.
+//!
+//! Run with `cargo bench -p caldir-core --features in-process`.
+
+use async_trait::async_trait;
+use caldir_core::provider::{Handler, Result};
+use caldir_core::rpc::{Connect, ConnectResponse, ListEvents};
+use caldir_core::{
+    Calendar, Connection, DateRange, Event, EventTime, Provider, Remote, RemoteConfigParams,
+};
+use chrono::{Duration, TimeZone, Utc};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+struct StaticHandler {
+    events: Vec<Event>,
+}
+
+#[async_trait]
+impl Handler for StaticHandler {
+    async fn connect(&self, _cmd: Connect) -> Result<ConnectResponse> {
+        Err("not used in benchmarks".into())
+    }
+
+    async fn list_events(&self, _cmd: ListEvents) -> Result<Vec<Event>> {
+        Ok(self.events.clone())
+    }
+}
+
+/// A calendar with `size` events, and a remote holding the same events with
+/// a tenth of them edited and a tenth more added.
+fn connection(dir: &std::path::Path, size: usize) -> Connection {
+    let calendar = Calendar::create(&dir.join("bench"), None).unwrap();
+    let start = Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap();
+
+    let mut remote_events = Vec::with_capacity(size + size / 10);
+    for i in 0..size {
+        let time = EventTime::DateTimeUtc(start + Duration::hours(i as i64));
+        let event = calendar
+            .create_event(Event::new(format!("Event {i}"), time))
+            .unwrap()
+            .event()
+            .clone();
+
+        let mut remote = event;
+        if i % 10 == 0 {
+            remote.summary = Some(format!("Edited {i}"));
+        }
+        remote_events.push(remote);
+    }
+    for i in 0..size / 10 {
+        let time = EventTime::DateTimeUtc(start - Duration::hours(i as i64 + 1));
+        remote_events.push(Event::new(format!("New {i}"), time));
+    }
+
+    let handler = StaticHandler {
+        events: remote_events,
+    };
+    let provider = Provider::in_process("bench", handler);
+    Connection::new(calendar, Remote::new(provider, RemoteConfigParams::new()))
+}
+
+fn diff(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("diff");
+    group.sample_size(10);
+
+    for size in [1_000, 5_000] {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut connection = connection(dir.path(), size);
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| {
+                runtime
+                    .block_on(connection.diff(&DateRange::default()))
+                    .unwrap()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, diff);
+criterion_main!(benches);
@@ -1,14 +1,40 @@
 mod config;
+mod contacts;
 mod error;
+mod snapshot;
+mod views;
 
+use crate::provider::ProviderEnv;
 use crate::{
-    Calendar, CalendarConfig, Connection, Provider, ProviderRegistry, ProviderSlug, Remote,
+    Calendar, CalendarConfig, Connection, Event, EventChange, EventQuery, EventTime, Provider,
+    ProviderRegistry, ProviderSlug, Remote,
 };
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 pub use config::CaldirConfig;
-pub use config::TimeFormat;
+pub use config::SmtpConfig;
+pub use config::{DateOrder, FloatingTimezone, TimeFormat, WeekStart};
+pub use contacts::Contact;
 pub use error::CaldirError;
+pub use snapshot::{Snapshot, SnapshotError};
+
+/// An event found by [`Caldir::query`].
+#[derive(Debug, Clone)]
+pub struct QueryMatch {
+    /// Slug of the calendar it's in
+    pub calendar: String,
+    pub event: Event,
+}
+
+/// Two busy events that overlap, found by [`Caldir::conflicts`]. `first`
+/// starts no later than `second`.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub first: QueryMatch,
+    pub second: QueryMatch,
+}
 
 pub struct Caldir {
     config: CaldirConfig,
@@ -26,10 +52,38 @@ impl Caldir {
         }
     }
 
+    /// Load the default caldir home (`$CALDIR_HOME` or `~/.config/caldir`).
     pub fn load() -> Result<Self, CaldirError> {
         let config_path = CaldirConfig::default_system_config_path()?;
+
+        Self::load_with(config_path, None)
+    }
+
+    /// Load a fully separate caldir rooted at `home`: its own `config.toml`,
+    /// and providers keep their tokens under `home/providers/`.
+    pub fn load_from_home(home: &Path) -> Result<Self, CaldirError> {
+        let config_path = CaldirConfig::config_path_in(home);
+
+        Self::load_with(config_path, Some(home))
+    }
+
+    /// Load a profile listed under `[profiles]` in the default config.
+    pub fn load_profile(name: &str) -> Result<Self, CaldirError> {
+        let default_config =
+            CaldirConfig::load_or_default(&CaldirConfig::default_system_config_path()?)?;
+
+        let home = default_config
+            .profile_home(name)
+            .ok_or_else(|| CaldirError::UnknownProfile(name.to_string()))?;
+
+        Self::load_from_home(&home)
+    }
+
+    fn load_with(config_path: PathBuf, home: Option<&Path>) -> Result<Self, CaldirError> {
         let config = CaldirConfig::load_or_default(&config_path)?;
-        let providers = ProviderRegistry::from_system_path();
+
+        let env = ProviderEnv::new(home.map(Path::to_path_buf), config.token_storage());
+        let providers = ProviderRegistry::from_system_path_with_env(env);
 
         Ok(Self {
             config,
@@ -44,6 +98,12 @@ impl Caldir {
         self
     }
 
+    /// Register `provider`, overriding any discovered one with the same slug.
+    pub fn with_provider(mut self, provider: Provider) -> Self {
+        self.providers.add(provider);
+        self
+    }
+
     pub fn data_dir(&self) -> PathBuf {
         self.config.data_dir()
     }
@@ -54,7 +114,7 @@ impl Caldir {
             .default_calendar_slug()
             .ok_or(CaldirError::NoDefaultCalendar)?;
 
-        Ok(Calendar::load(&self.data_dir().join(slug))?)
+        Ok(self.load_calendar(&self.data_dir().join(slug))?)
     }
 
     pub fn create_calendar(
@@ -65,16 +125,22 @@ impl Caldir {
         let unique_slug = self.unique_slug_for(desired_slug);
         let calendar_path = self.data_dir().join(unique_slug);
 
-        Ok(Calendar::create(&calendar_path, config)?)
+        Ok(Calendar::create(&calendar_path, config)?
+            .with_floating_timezone(self.config.floating_timezone()))
     }
 
     pub fn calendars(&self) -> Vec<Result<Calendar, CaldirError>> {
         let mut calendars = Vec::new();
 
+        let views_dir = self.config.views_dir();
+
         if let Ok(entries) = std::fs::read_dir(self.data_dir()) {
             for entry in entries.flatten() {
+                if views_dir.as_ref() == Some(&entry.path()) {
+                    continue;
+                }
                 if entry.path().is_dir() && !entry.file_name().to_string_lossy().starts_with('.') {
-                    calendars.push(Calendar::load(&entry.path()).map_err(CaldirError::from));
+                    calendars.push(self.load_calendar(&entry.path()).map_err(CaldirError::from));
                 }
             }
         }
@@ -83,9 +149,290 @@ impl Caldir {
     }
 
     pub fn calendar(&self, slug: &str) -> Result<Calendar, CaldirError> {
-        Calendar::load(&self.data_dir().join(slug)).map_err(CaldirError::from)
+        self.load_calendar(&self.data_dir().join(slug))
+            .map_err(CaldirError::from)
+    }
+
+    /// The calendar at `path`, with floating times where this caldir's
+    /// config places them.
+    fn load_calendar(&self, path: &Path) -> Result<Calendar, crate::calendar::CalendarError> {
+        Ok(Calendar::load(path)?.with_floating_timezone(self.config.floating_timezone()))
+    }
+
+    /// Move calendar `slug` to `new_slug`. Its sync state lives inside the
+    /// calendar's directory, so the remote link survives. The default
+    /// calendar and views follow the new slug.
+    pub fn rename_calendar(&mut self, slug: &str, new_slug: &str) -> Result<Calendar, CaldirError> {
+        let calendar = self.calendar(slug)?;
+
+        let is_plain_name =
+            !new_slug.is_empty() && !new_slug.starts_with('.') && !new_slug.contains(['/', '\\']);
+        if !is_plain_name {
+            return Err(CaldirError::InvalidSlug(new_slug.to_string()));
+        }
+
+        let new_path = self.data_dir().join(new_slug);
+        if new_path.exists() {
+            return Err(CaldirError::CalendarExists(new_slug.to_string()));
+        }
+
+        std::fs::rename(calendar.path(), &new_path).map_err(CaldirError::Rename)?;
+
+        if self.config.default_calendar_slug() == Some(slug) {
+            let mut config = self.config.clone();
+            config.set_default_calendar_slug(Some(new_slug.to_string()));
+            self.save_config(config)?;
+        }
+
+        self.refresh_views()?;
+
+        Ok(self.load_calendar(&new_path)?)
+    }
+
+    /// Rebuild the symlinks in `views_dir`, if one is configured.
+    pub fn refresh_views(&self) -> Result<(), CaldirError> {
+        let Some(views_dir) = self.config.views_dir() else {
+            return Ok(());
+        };
+
+        views::refresh(&views_dir, &self.active_calendars(), chrono::Local::now())
+    }
+
+    /// Rebuild the contacts that [`Caldir::contacts`] completes from, out of
+    /// the attendees and organizers of past events in calendars that aren't
+    /// archived.
+    pub fn refresh_contacts(&self) -> Result<Vec<Contact>, CaldirError> {
+        let calendars = self.active_calendars();
+
+        self.save_contacts(&calendars, &contacts::seen(&calendars))
+    }
+
+    /// Bring the contacts up to date with events a pull or sync created,
+    /// changed or deleted, without reading the others. Rebuilds them if
+    /// they were never built.
+    pub fn update_contacts<'a>(
+        &self,
+        changes: impl IntoIterator<Item = &'a EventChange>,
+    ) -> Result<Vec<Contact>, CaldirError> {
+        let Some(mut seen) = contacts::load_seen(&self.data_dir()) else {
+            return self.refresh_contacts();
+        };
+        contacts::apply_changes(&mut seen, changes);
+
+        self.save_contacts(&self.active_calendars(), &seen)
+    }
+
+    fn save_contacts(
+        &self,
+        calendars: &[Calendar],
+        seen: &contacts::SeenEvents,
+    ) -> Result<Vec<Contact>, CaldirError> {
+        let own = contacts::own_emails(calendars);
+        let mut contacts = contacts::collect(seen, &own, Utc::now());
+        // Names looked up in address books aren't in the events
+        if let Some(previous) = contacts::load(&self.data_dir()) {
+            contacts::keep_names(&mut contacts, &previous);
+        }
+
+        contacts::save_seen(&self.data_dir(), seen).map_err(CaldirError::Contacts)?;
+        contacts::save(&self.data_dir(), &contacts).map_err(CaldirError::Contacts)?;
+        Ok(contacts)
+    }
+
+    /// The calendars that load and aren't archived.
+    fn active_calendars(&self) -> Vec<Calendar> {
+        self.calendars()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|calendar| !calendar.is_archived())
+            .collect()
+    }
+
+    /// With `look_up_contacts` set, ask the address books of `connections`
+    /// (the ones with the `contacts` capability) for the names of contacts
+    /// that don't have one yet. Contacts an address book had no name for
+    /// aren't asked about again. Returns how many got a name, or the first
+    /// address book that couldn't be asked.
+    pub async fn look_up_contact_names(
+        &self,
+        connections: &[Connection],
+    ) -> Result<usize, CaldirError> {
+        if !self.config.look_up_contacts() {
+            return Ok(0);
+        }
+        let Some(mut contacts) = contacts::load(&self.data_dir()) else {
+            return Ok(0);
+        };
+
+        let mut asked = HashSet::new();
+        let mut named = 0;
+        let mut first_error = None;
+
+        for connection in connections {
+            let unnamed = contacts::to_look_up(&contacts);
+            if unnamed.is_empty() {
+                break;
+            }
+
+            // Calendars of the same account share an address book
+            let account = connection.local().remote_config().map(|config| {
+                (
+                    config.provider_slug().to_string(),
+                    config.account_identifier().map(str::to_string),
+                )
+            });
+            if !asked.insert(account) {
+                continue;
+            }
+
+            let remote = connection.remote();
+            if !remote.capabilities().await.contacts {
+                continue;
+            }
+            match remote.lookup_contacts(unnamed.clone()).await {
+                Ok(cards) => named += contacts::add_names(&mut contacts, &unnamed, &cards),
+                Err(e) => {
+                    first_error.get_or_insert(CaldirError::ContactLookup(e.into()));
+                }
+            }
+        }
+
+        contacts::save(&self.data_dir(), &contacts).map_err(CaldirError::Contacts)?;
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(named),
+        }
+    }
+
+    /// Names of the people in [`Caldir::contacts`], by lowercased email.
+    pub fn contact_names(&self) -> HashMap<String, String> {
+        self.contacts("")
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|contact| Some((contact.email, contact.name?)))
+            .collect()
+    }
+
+    /// People from past events matching `query` (the start of an email or a
+    /// name), most frequent and recent first. Built on first use, then
+    /// refreshed by pull and sync.
+    pub fn contacts(&self, query: &str) -> Result<Vec<Contact>, CaldirError> {
+        let contacts = match contacts::load(&self.data_dir()) {
+            Some(contacts) => contacts,
+            None => self.refresh_contacts()?,
+        };
+
+        Ok(contacts
+            .into_iter()
+            .filter(|contact| contact.matches(query))
+            .collect())
+    }
+
+    /// Archive every calendar, with its config and sync state.
+    pub fn snapshot(&self) -> Result<Snapshot, CaldirError> {
+        Ok(snapshot::create(
+            &self.data_dir(),
+            self.config.views_dir().as_deref(),
+        )?)
+    }
+
+    /// Snapshots taken so far, oldest first.
+    pub fn snapshots(&self) -> Result<Vec<Snapshot>, CaldirError> {
+        Ok(snapshot::list(&self.data_dir())?)
+    }
+
+    /// Put back the calendars archived in snapshot `id`. Their sync state is
+    /// dropped and every event counts as changed locally, so the next sync
+    /// pushes the restored events rather than pulling the remote over them.
+    pub fn restore(&self, id: &str) -> Result<Vec<Calendar>, CaldirError> {
+        let mut calendars = Vec::new();
+
+        for path in snapshot::restore(&self.data_dir(), id)? {
+            Calendar::load(&path)?.mark_all_changed()?;
+            calendars.push(self.load_calendar(&path)?);
+        }
+
+        Ok(calendars)
+    }
+
+    /// Events matching `query` across all calendars (or the ones it names).
+    /// Calendars that fail to load are skipped.
+    pub fn query(&self, query: &EventQuery) -> Result<Vec<QueryMatch>, CaldirError> {
+        let mut matches = Vec::new();
+
+        for calendar in self.calendars().into_iter().filter_map(Result::ok) {
+            if !query.includes_calendar(&calendar) {
+                continue;
+            }
+
+            let slug = calendar.slug().unwrap_or_default().to_string();
+
+            matches.extend(calendar.query(query)?.into_iter().map(|event| QueryMatch {
+                calendar: slug.clone(),
+                event,
+            }));
+        }
+
+        query.sort_and_limit(&mut matches, |m| &m.event);
+
+        Ok(matches)
+    }
+
+    /// Busy events between `from` and `to` that overlap each other, across
+    /// all calendars, in order of when they start. An event in two calendars
+    /// (e.g. an invite) counts once, from the first calendar it's in.
+    pub fn conflicts(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Conflict>, CaldirError> {
+        let mut busy = Vec::new();
+        let mut seen = HashSet::new();
+
+        for calendar in self.calendars().into_iter().filter_map(Result::ok) {
+            if calendar.is_archived() {
+                continue;
+            }
+            let slug = calendar.slug().unwrap_or_default().to_string();
+
+            for event in calendar.busy_events(from, to)? {
+                if seen.insert(event.event_instance_id()) {
+                    busy.push(QueryMatch {
+                        calendar: slug.clone(),
+                        event,
+                    });
+                }
+            }
+        }
+
+        let span = |m: &QueryMatch| {
+            let start = m.event.start.to_utc();
+            (start, m.event.end.as_ref().map_or(start, EventTime::to_utc))
+        };
+        busy.sort_by_key(span);
+
+        let mut conflicts = Vec::new();
+
+        for (i, first) in busy.iter().enumerate() {
+            let (_, first_end) = span(first);
+
+            for second in &busy[i + 1..] {
+                let (second_start, _) = span(second);
+                if second_start >= first_end {
+                    break;
+                }
+                conflicts.push(Conflict {
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+
+        Ok(conflicts)
     }
 
+    /// Connections of the calendars with a remote, leaving out archived ones.
     pub fn connections(&self) -> Vec<Result<Connection, CaldirError>> {
         let mut connections = Vec::new();
 
@@ -101,14 +448,18 @@ impl Caldir {
             let Some(remote_config) = calendar.remote_config().cloned() else {
                 continue;
             };
+            if calendar.is_archived() {
+                continue;
+            }
 
             let connection = self
                 .provider(remote_config.provider_slug())
                 .map(|provider| {
-                    Connection::new(
-                        calendar,
-                        Remote::new(provider.clone(), remote_config.params().clone()),
-                    )
+                    let mut remote = Remote::new(provider.clone(), remote_config.params().clone());
+                    remote.set_max_attempts(self.config.max_attempts());
+                    let mut connection = Connection::new(calendar, remote);
+                    connection.set_ignored_properties(self.config.ignore_properties().to_vec());
+                    connection
                 });
 
             connections.push(connection);
@@ -131,6 +482,11 @@ impl Caldir {
         &self.config
     }
 
+    /// Where the config is saved (None for in-memory test caldirs).
+    pub fn config_path(&self) -> Option<&Path> {
+        self.config_path.as_deref()
+    }
+
     /// Persist `new_config` to disk and adopt it as the in-memory config.
     /// Either both sides commit or neither — on write failure the in-memory
     /// config is left untouched.
@@ -170,9 +526,58 @@ mod tests {
     use super::*;
     use crate::provider::ProviderError;
     use crate::test_utils::{
-        test_caldir, test_caldir_config, test_calendar_config, test_provider, test_remote_config,
+        test_caldir, test_caldir_config, test_calendar_config, test_event, test_provider,
+        test_remote_config,
     };
 
+    #[test]
+    fn restore_brings_back_snapshotted_events_as_unsynced() {
+        let (_tmp, caldir) = test_caldir();
+        let mut calendar = caldir.create_calendar("work", None).unwrap();
+        let event = calendar.create_event(test_event()).unwrap();
+        calendar
+            .record_sync_bases(vec![event.event().clone()])
+            .unwrap();
+
+        let snapshot = caldir.snapshot().unwrap();
+        std::fs::remove_file(event.path()).unwrap();
+
+        let restored = caldir.restore(snapshot.id()).unwrap();
+
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].events().unwrap().len(), 1);
+        assert!(restored[0].state().sync_bases().iter().next().is_none());
+        assert_eq!(caldir.snapshots().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn restore_errors_for_unknown_snapshot() {
+        let (_tmp, caldir) = test_caldir();
+
+        assert!(matches!(
+            caldir.restore("20260101T000000000Z"),
+            Err(CaldirError::Snapshot(SnapshotError::NotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn load_from_home_reads_config_in_home() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("config.toml"),
+            "calendar_dir = \"/tmp/work-calendars\"\n",
+        )
+        .unwrap();
+
+        let caldir = Caldir::load_from_home(tmp.path()).unwrap();
+
+        assert_eq!(caldir.data_dir(), PathBuf::from("/tmp/work-calendars"));
+        assert_eq!(
+            caldir.config_path(),
+            Some(tmp.path().join("config.toml").as_path())
+        );
+    }
+
     #[test]
     fn create_calendar_creates_directory_with_desired_slug() {
         let (_tmp, caldir) = test_caldir();
@@ -184,6 +589,45 @@ mod tests {
         assert!(calendar.path().is_dir());
     }
 
+    #[test]
+    fn rename_calendar_keeps_its_remote_and_the_default() {
+        let (_tmp, mut config) = test_caldir_config();
+        config.set_default_calendar_slug(Some("work".to_string()));
+        let mut caldir = Caldir::new(config, ProviderRegistry::new());
+
+        let mut calendar_config = test_calendar_config();
+        calendar_config.set_remote(test_remote_config("hooli"));
+        let calendar = caldir
+            .create_calendar("work", Some(calendar_config))
+            .unwrap();
+        calendar.create_event(test_event()).unwrap();
+
+        let renamed = caldir.rename_calendar("work", "acme").unwrap();
+
+        assert_eq!(renamed.slug(), Some("acme"));
+        assert!(renamed.has_remote());
+        assert_eq!(renamed.events().unwrap().len(), 1);
+        assert!(caldir.calendar("work").is_err());
+        assert_eq!(caldir.config().default_calendar_slug(), Some("acme"));
+    }
+
+    #[test]
+    fn rename_calendar_refuses_taken_or_nested_slugs() {
+        let (_tmp, mut caldir) = test_caldir();
+        caldir.create_calendar("work", None).unwrap();
+        caldir.create_calendar("home", None).unwrap();
+
+        assert!(matches!(
+            caldir.rename_calendar("work", "home"),
+            Err(CaldirError::CalendarExists(_))
+        ));
+        assert!(matches!(
+            caldir.rename_calendar("work", "../work"),
+            Err(CaldirError::InvalidSlug(_))
+        ));
+        assert!(caldir.calendar("work").is_ok());
+    }
+
     #[test]
     fn create_appends_suffix_on_slug_collision() {
         let (_tmp, caldir) = test_caldir();
@@ -198,6 +642,85 @@ mod tests {
         assert_eq!(calendar_3.slug().unwrap(), "work-3");
     }
 
+    #[test]
+    fn query_merges_calendars_in_start_order() {
+        use crate::EventTime;
+        use chrono::{TimeZone, Utc};
+
+        let (_tmp, caldir) = test_caldir();
+        let at = |d| EventTime::DateTimeUtc(Utc.with_ymd_and_hms(2026, 3, d, 9, 0, 0).unwrap());
+
+        let work = caldir.create_calendar("work", None).unwrap();
+        let home = caldir.create_calendar("home", None).unwrap();
+        work.create_event(Event::new("Review", at(3))).unwrap();
+        home.create_event(Event::new("Dentist", at(2))).unwrap();
+        work.create_event(Event::new("Offsite", at(20))).unwrap();
+
+        let from = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
+
+        let matches = caldir.query(&EventQuery::between(from, to)).unwrap();
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.calendar.as_str(), m.event.summary.as_deref().unwrap()))
+            .collect();
+        assert_eq!(found, [("home", "Dentist"), ("work", "Review")]);
+
+        let only_work = caldir
+            .query(&EventQuery::between(from, to).calendars(["work"]))
+            .unwrap();
+        assert_eq!(only_work.len(), 1);
+    }
+
+    #[test]
+    fn conflicts_pair_overlapping_busy_events_across_calendars() {
+        use crate::{Availability, EventTime};
+        use chrono::{TimeZone, Utc};
+
+        let (_tmp, caldir) = test_caldir();
+        let meeting = |summary: &str, hour: u32, minutes: i64| {
+            let start = Utc.with_ymd_and_hms(2026, 3, 2, hour, 0, 0).unwrap();
+            Event {
+                end: Some(EventTime::DateTimeUtc(
+                    start + chrono::Duration::minutes(minutes),
+                )),
+                ..Event::new(summary, EventTime::DateTimeUtc(start))
+            }
+        };
+
+        let work = caldir.create_calendar("work", None).unwrap();
+        let home = caldir.create_calendar("home", None).unwrap();
+        let standup = meeting("Standup", 9, 90);
+        work.create_event(standup.clone()).unwrap();
+        home.create_event(meeting("Dentist", 10, 60)).unwrap();
+        // Back to back, free, or the same event twice: not conflicts
+        work.create_event(meeting("Review", 11, 60)).unwrap();
+        home.create_event(Event {
+            availability: Availability::Free,
+            ..meeting("Focus time", 9, 60)
+        })
+        .unwrap();
+        home.create_event(standup).unwrap();
+
+        let conflicts = caldir
+            .conflicts(
+                Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 3, 0, 0, 0).unwrap(),
+            )
+            .unwrap();
+
+        let pairs: Vec<_> = conflicts
+            .iter()
+            .map(|c| {
+                (
+                    c.first.event.summary.as_deref().unwrap(),
+                    c.second.event.summary.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(pairs, [("Standup", "Dentist")]);
+    }
+
     #[test]
     fn calendars_returns_empty_if_no_calendars() {
         let (_tmp, caldir) = test_caldir();
@@ -258,6 +781,33 @@ mod tests {
         assert!(caldir.connections().is_empty());
     }
 
+    #[test]
+    fn connections_and_queries_skip_archived_calendars() {
+        use chrono::{TimeZone, Utc};
+
+        let (_tmp, caldir) = test_caldir();
+        let mut config = test_calendar_config();
+        config.set_remote(test_remote_config("hooli"));
+        config.set_archived(true);
+        let old = caldir.create_calendar("old", Some(config)).unwrap();
+        old.create_event(test_event()).unwrap();
+
+        assert!(caldir.connections().is_empty());
+
+        let from = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+        assert!(
+            caldir
+                .query(&EventQuery::between(from, to))
+                .unwrap()
+                .is_empty()
+        );
+        let named = caldir
+            .query(&EventQuery::between(from, to).calendars(["old"]))
+            .unwrap();
+        assert_eq!(named.len(), 1);
+    }
+
     #[test]
     fn connections_returns_calendar_with_remote() {
         let (_tmp_bin, provider) = test_provider("hooli");
@@ -1,14 +1,30 @@
+mod date_order;
 mod error;
+mod floating_timezone;
+mod smtp;
 mod time_format;
+mod week_start;
 
-use crate::{Reminder, utils::expand_tilde};
+use crate::{
+    Reminder,
+    provider::TokenStorage,
+    remote::DEFAULT_MAX_ATTEMPTS,
+    utils::{ConfigKeyError, expand_tilde, get_key, set_key},
+};
+pub use date_order::DateOrder;
 pub(crate) use error::CaldirConfigError;
+pub use floating_timezone::FloatingTimezone;
 use serde::{Deserialize, Serialize};
+pub use smtp::SmtpConfig;
 use std::{
+    collections::BTreeMap,
     fmt::Display,
     path::{Path, PathBuf},
 };
 pub use time_format::TimeFormat;
+pub use week_start::WeekStart;
+
+const DEFAULT_PULL_DELETE_THRESHOLD: usize = 10;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
@@ -19,11 +35,56 @@ pub struct CaldirConfig {
     #[serde(default)]
     time_format: TimeFormat,
 
+    week_start: WeekStart,
+
+    date_order: DateOrder,
+
     #[serde(rename = "default_calendar", skip_serializing_if = "Option::is_none")]
     default_calendar_slug: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     default_reminders: Option<Vec<Reminder>>,
+
+    /// IANA timezone event listings show times in, instead of the system's
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_timezone: Option<String>,
+
+    /// Leave invites you declined out of event listings
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    hide_declined: bool,
+
+    /// Name contacts from providers' address books after each pull and sync
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    look_up_contacts: bool,
+
+    /// Where times written without a timezone happen
+    #[serde(skip_serializing_if = "FloatingTimezone::is_local")]
+    floating_timezone: FloatingTimezone,
+
+    /// Directory of symlinks to the events by date, refreshed after each sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    views_dir: Option<PathBuf>,
+
+    /// Where providers keep OAuth tokens
+    token_storage: TokenStorage,
+
+    /// Named caldir homes, e.g. `work = "~/.config/caldir-work"`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    profiles: BTreeMap<String, PathBuf>,
+
+    /// Tries per provider call before a rate limit or server error fails the sync
+    max_attempts: u32,
+
+    /// Local events a pull may delete before asking for confirmation
+    pull_delete_threshold: usize,
+
+    /// Mail server for invitations, for providers that don't send their own
+    #[serde(skip_serializing_if = "Option::is_none")]
+    smtp: Option<SmtpConfig>,
+
+    /// ICS properties left out when comparing local and remote events
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ignore_properties: Vec<String>,
 }
 
 impl Display for CaldirConfig {
@@ -39,8 +100,21 @@ impl Default for CaldirConfig {
         Self {
             data_dir: PathBuf::from("~/caldir"),
             time_format: TimeFormat::default(),
+            week_start: WeekStart::default(),
+            date_order: DateOrder::default(),
             default_calendar_slug: None,
             default_reminders: None,
+            display_timezone: None,
+            hide_declined: false,
+            look_up_contacts: false,
+            floating_timezone: FloatingTimezone::default(),
+            views_dir: None,
+            token_storage: TokenStorage::default(),
+            profiles: BTreeMap::new(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            pull_delete_threshold: DEFAULT_PULL_DELETE_THRESHOLD,
+            smtp: None,
+            ignore_properties: Vec::new(),
         }
     }
 }
@@ -55,8 +129,21 @@ impl CaldirConfig {
         Self {
             data_dir,
             time_format,
+            week_start: WeekStart::default(),
+            date_order: DateOrder::default(),
             default_calendar_slug,
             default_reminders,
+            display_timezone: None,
+            hide_declined: false,
+            look_up_contacts: false,
+            floating_timezone: FloatingTimezone::default(),
+            views_dir: None,
+            token_storage: TokenStorage::default(),
+            profiles: BTreeMap::new(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            pull_delete_threshold: DEFAULT_PULL_DELETE_THRESHOLD,
+            smtp: None,
+            ignore_properties: Vec::new(),
         }
     }
 
@@ -77,6 +164,14 @@ impl CaldirConfig {
         self.time_format
     }
 
+    pub fn week_start(&self) -> WeekStart {
+        self.week_start
+    }
+
+    pub fn date_order(&self) -> DateOrder {
+        self.date_order
+    }
+
     pub fn default_calendar_slug(&self) -> Option<&str> {
         self.default_calendar_slug.as_deref()
     }
@@ -89,6 +184,51 @@ impl CaldirConfig {
         self.default_reminders.clone()
     }
 
+    pub fn display_timezone(&self) -> Option<&str> {
+        self.display_timezone.as_deref()
+    }
+
+    pub fn hide_declined(&self) -> bool {
+        self.hide_declined
+    }
+
+    pub fn look_up_contacts(&self) -> bool {
+        self.look_up_contacts
+    }
+
+    pub fn floating_timezone(&self) -> FloatingTimezone {
+        self.floating_timezone
+    }
+
+    pub fn views_dir(&self) -> Option<PathBuf> {
+        self.views_dir.as_deref().map(expand_tilde)
+    }
+
+    pub fn token_storage(&self) -> TokenStorage {
+        self.token_storage
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn pull_delete_threshold(&self) -> usize {
+        self.pull_delete_threshold
+    }
+
+    pub fn smtp(&self) -> Option<&SmtpConfig> {
+        self.smtp.as_ref()
+    }
+
+    pub fn ignore_properties(&self) -> &[String] {
+        &self.ignore_properties
+    }
+
+    /// The caldir home of the profile called `name`, if one is configured.
+    pub fn profile_home(&self, name: &str) -> Option<PathBuf> {
+        self.profiles.get(name).map(|home| expand_tilde(home))
+    }
+
     pub fn set_data_dir(&mut self, path: std::path::PathBuf) {
         self.data_dir = path;
     }
@@ -101,6 +241,17 @@ impl CaldirConfig {
         self.default_reminders = reminders;
     }
 
+    /// Read a single value by key (e.g. `default_calendar`).
+    pub fn get(&self, key: &str) -> Result<Option<toml::Value>, ConfigKeyError> {
+        get_key(self, key)
+    }
+
+    /// Set a single value by key, validated against the config schema.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), ConfigKeyError> {
+        *self = set_key(self, key, value)?;
+        Ok(())
+    }
+
     pub fn write(&self, path: &Path) -> Result<(), CaldirConfigError> {
         let contents = self.to_toml().map_err(CaldirConfigError::InvalidConfig)?;
 
@@ -113,14 +264,21 @@ impl CaldirConfig {
         Ok(())
     }
 
-    /// Caldir config directory:
+    /// Caldir home directory, `$CALDIR_HOME` if set, otherwise:
     /// - Linux/BSD: `$XDG_CONFIG_HOME/caldir` or `~/.config/caldir`
     /// - macOS: `~/.config/caldir`
     /// - Windows: `%APPDATA%\caldir`
+    pub fn default_home() -> Result<PathBuf, CaldirConfigError> {
+        crate::utils::paths::default_caldir_home().ok_or(CaldirConfigError::UnknownConfigDirectory)
+    }
+
     pub fn default_system_config_path() -> Result<PathBuf, CaldirConfigError> {
-        let dir = Self::platform_config_dir()?.join("caldir");
+        Ok(Self::config_path_in(&Self::default_home()?))
+    }
 
-        Ok(dir.join("config.toml"))
+    /// The config file of the caldir home at `home`.
+    pub fn config_path_in(home: &Path) -> PathBuf {
+        home.join("config.toml")
     }
 
     fn load(path: &Path) -> Result<Self, CaldirConfigError> {
@@ -139,10 +297,6 @@ impl CaldirConfig {
     fn to_toml(&self) -> Result<String, toml::ser::Error> {
         toml::to_string(self)
     }
-
-    fn platform_config_dir() -> Result<PathBuf, CaldirConfigError> {
-        crate::utils::paths::platform_config_dir().ok_or(CaldirConfigError::UnknownConfigDirectory)
-    }
 }
 
 #[cfg(test)]
@@ -179,6 +333,8 @@ mod tests {
                 r#"
                 calendar_dir = "{data_dir}"
                 time_format = "12h"
+                week_start = "sunday"
+                date_order = "dmy"
                 default_calendar = "personal"
                 "#
             ),
@@ -189,6 +345,8 @@ mod tests {
 
         assert_eq!(config.data_dir, PathBuf::from(data_dir));
         assert_eq!(config.time_format, TimeFormat::H12);
+        assert_eq!(config.week_start, WeekStart::Sunday);
+        assert_eq!(config.date_order, DateOrder::Dmy);
         assert_eq!(config.default_calendar_slug.as_deref(), Some("personal"));
     }
 
@@ -215,6 +373,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_validates_keys_and_values() {
+        let mut config = CaldirConfig::default();
+
+        config.set("time_format", "12h").unwrap();
+        config.set("default_reminders", "10m, 1h").unwrap();
+
+        assert_eq!(config.time_format, TimeFormat::H12);
+        assert_eq!(
+            config.default_reminders,
+            Some(vec![Reminder::from_minutes(10), Reminder::from_minutes(60)])
+        );
+        assert!(config.set("time_format", "13h").is_err());
+        assert!(config.set("week_start", "tuesday").is_err());
+        assert!(config.set("default_calender", "work").is_err());
+    }
+
+    #[test]
+    fn display_timezone_is_unset_by_default_and_settable() {
+        let mut config = CaldirConfig::default();
+        assert_eq!(config.display_timezone(), None);
+
+        config.set("display_timezone", "America/New_York").unwrap();
+
+        assert_eq!(config.display_timezone(), Some("America/New_York"));
+    }
+
+    #[test]
+    fn floating_timezone_is_local_by_default_and_takes_utc_or_a_zone() {
+        let mut config = CaldirConfig::default();
+        assert_eq!(config.floating_timezone(), FloatingTimezone::Local);
+
+        config.set("floating_timezone", "utc").unwrap();
+        assert_eq!(config.floating_timezone(), FloatingTimezone::Utc);
+
+        config.set("floating_timezone", "Europe/Stockholm").unwrap();
+        assert_eq!(
+            config.floating_timezone(),
+            FloatingTimezone::Zone(chrono_tz::Europe::Stockholm)
+        );
+        assert!(
+            config
+                .to_toml()
+                .unwrap()
+                .contains("floating_timezone = \"Europe/Stockholm\"")
+        );
+
+        assert!(config.set("floating_timezone", "Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn views_dir_is_off_by_default_and_expands_tilde() {
+        let mut config = CaldirConfig::default();
+        assert_eq!(config.views_dir(), None);
+
+        config.set("views_dir", "~/caldir/views").unwrap();
+
+        assert_eq!(
+            config.views_dir(),
+            Some(expand_tilde(Path::new("~/caldir/views")))
+        );
+    }
+
+    #[test]
+    fn profile_home_expands_configured_path() {
+        let config = CaldirConfig::from_toml(
+            r#"
+            [profiles]
+            work = "/srv/caldir-work"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.profile_home("work"),
+            Some(PathBuf::from("/srv/caldir-work"))
+        );
+        assert_eq!(config.profile_home("personal"), None);
+    }
+
+    #[test]
+    fn smtp_port_defaults_to_submission() {
+        let config = CaldirConfig::from_toml(
+            r#"
+            [smtp]
+            host = "smtp.example.com"
+            from = "me@example.com"
+            "#,
+        )
+        .unwrap();
+
+        let smtp = config.smtp().unwrap();
+        assert_eq!(smtp.host(), "smtp.example.com");
+        assert_eq!(smtp.port(), 587);
+        assert_eq!(smtp.username(), None);
+    }
+
     #[test]
     fn load_or_default_returns_default_on_missing_file() {
         let tmp = tempfile::TempDir::new().unwrap();
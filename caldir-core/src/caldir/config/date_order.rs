@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// How dates are written: "Wed Feb 25" (mdy), "Wed 25 Feb" (dmy) or
+/// "Wed 2026-02-25" (ymd).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateOrder {
+    #[default]
+    Mdy,
+    Dmy,
+    Ymd,
+}
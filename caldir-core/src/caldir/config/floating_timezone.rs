@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// Where floating times (written without a timezone) happen: on the
+/// clock of whoever is looking, or in one fixed zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum FloatingTimezone {
+    /// The system timezone, or the timezone times are being shown in
+    #[default]
+    Local,
+    Utc,
+    /// An IANA timezone, e.g. `Europe/Stockholm`
+    Zone(chrono_tz::Tz),
+}
+
+impl FloatingTimezone {
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Local)
+    }
+
+    /// The fixed zone floating times are in, if they aren't local.
+    pub fn zone(&self) -> Option<chrono_tz::Tz> {
+        match self {
+            Self::Local => None,
+            Self::Utc => Some(chrono_tz::UTC),
+            Self::Zone(tz) => Some(*tz),
+        }
+    }
+}
+
+impl TryFrom<String> for FloatingTimezone {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "local" => Ok(Self::Local),
+            "utc" | "UTC" => Ok(Self::Utc),
+            name => name.parse().map(Self::Zone).map_err(|_| {
+                format!("unknown timezone \"{name}\" (expected local, utc or an IANA name)")
+            }),
+        }
+    }
+}
+
+impl From<FloatingTimezone> for String {
+    fn from(value: FloatingTimezone) -> Self {
+        match value {
+            FloatingTimezone::Local => "local".to_string(),
+            FloatingTimezone::Utc => "utc".to_string(),
+            FloatingTimezone::Zone(tz) => tz.name().to_string(),
+        }
+    }
+}
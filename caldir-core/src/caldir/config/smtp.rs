@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_PORT: u16 = 587;
+
+/// Mail server for iMIP invitations, e.g.
+///
+/// ```toml
+/// [smtp]
+/// host = "smtp.fastmail.com"
+/// from = "me@example.com"
+/// username = "me@example.com"
+/// password_command = "pass show fastmail/smtp"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    host: String,
+
+    /// 465 for implicit TLS, anything else uses STARTTLS
+    #[serde(default = "default_port")]
+    port: u16,
+
+    /// Sender address, also used as the organizer of invitations
+    from: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+
+    /// Shell command that prints the password, so it stays out of the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    password_command: Option<String>,
+}
+
+fn default_port() -> u16 {
+    DEFAULT_PORT
+}
+
+impl SmtpConfig {
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    pub fn password_command(&self) -> Option<&str> {
+        self.password_command.as_deref()
+    }
+}
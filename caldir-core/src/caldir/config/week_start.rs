@@ -0,0 +1,21 @@
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+
+/// First day of the week, for "this week" and `caldir week`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    /// How far into the week `weekday` is (0 on the first day).
+    pub fn days_into_week(&self, weekday: Weekday) -> u32 {
+        match self {
+            Self::Monday => weekday.num_days_from_monday(),
+            Self::Sunday => weekday.num_days_from_sunday(),
+        }
+    }
+}
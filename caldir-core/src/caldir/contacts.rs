@@ -0,0 +1,396 @@
+//! People seen as attendees or organizers of past events, kept in
+//! `.caldir/contacts.json` so names can be completed to email addresses
+//! without reading every event.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::rpc::ContactCard;
+use crate::{Calendar, Event, EventChange};
+
+const CONTACTS_FILE: &str = ".caldir/contacts.json";
+const SEEN_FILE: &str = ".caldir/contact-events.json";
+
+/// Someone from past events, and how often and lately they were in one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    pub email: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Past events they were in
+    pub events: usize,
+    /// Start of the latest of them
+    pub last_seen: DateTime<Utc>,
+    /// The address books were asked for a name and had none, so they
+    /// aren't asked again
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub looked_up: bool,
+}
+
+impl Contact {
+    /// Whether the email, or a word of the name, starts with `query`
+    /// (ignoring case).
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+
+        self.email.to_lowercase().starts_with(&query)
+            || self.name.as_deref().is_some_and(|name| {
+                let name = name.to_lowercase();
+                name.starts_with(&query) || name.split_whitespace().any(|w| w.starts_with(&query))
+            })
+    }
+}
+
+impl std::fmt::Display for Contact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name} <{}>", self.email),
+            None => write!(f, "{}", self.email),
+        }
+    }
+}
+
+fn contacts_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(CONTACTS_FILE)
+}
+
+fn seen_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(SEEN_FILE)
+}
+
+/// The people in one event, kept so contacts can be brought up to date from
+/// the events a sync changed without reading all the others.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Seen {
+    start: DateTime<Utc>,
+    /// Lowercased email and name of each attendee and the organizer
+    people: Vec<(String, Option<String>)>,
+}
+
+impl Seen {
+    fn of(event: &Event) -> Self {
+        let mut people: Vec<(String, Option<String>)> = Vec::new();
+        let everyone = event
+            .attendees
+            .iter()
+            .map(|a| (&a.email, &a.name))
+            .chain(event.organizer.iter().map(|o| (&o.email, &o.name)));
+        for (email, name) in everyone {
+            let email = email.trim().to_lowercase();
+            if !email.is_empty() && !people.iter().any(|(seen, _)| *seen == email) {
+                people.push((email, name.clone()));
+            }
+        }
+
+        Self {
+            start: event.start.to_utc(),
+            people,
+        }
+    }
+}
+
+/// Who was in which event, by event instance id. An event in more than one
+/// calendar counts once.
+pub(crate) type SeenEvents = BTreeMap<String, Seen>;
+
+/// The people in every event of `calendars`.
+pub(crate) fn seen(calendars: &[Calendar]) -> SeenEvents {
+    let mut seen = SeenEvents::new();
+
+    for calendar in calendars {
+        let Ok(events) = calendar.events() else {
+            continue;
+        };
+        for calendar_event in &events {
+            let event = calendar_event.event();
+            seen.insert(event.event_instance_id().to_string(), Seen::of(event));
+        }
+    }
+
+    seen
+}
+
+/// Update `seen` with events created, changed or deleted by a sync.
+pub(crate) fn apply_changes<'a>(
+    seen: &mut SeenEvents,
+    changes: impl IntoIterator<Item = &'a EventChange>,
+) {
+    for change in changes {
+        match change {
+            EventChange::Create(event) | EventChange::Update { to: event, .. } => {
+                seen.insert(event.event_instance_id().to_string(), Seen::of(event));
+            }
+            EventChange::Delete(event) => {
+                seen.remove(&event.event_instance_id().to_string());
+            }
+        }
+    }
+}
+
+/// The lowercased emails of the accounts behind `calendars`.
+pub(crate) fn own_emails(calendars: &[Calendar]) -> Vec<String> {
+    calendars
+        .iter()
+        .filter_map(|calendar| calendar.remote_email())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Contacts from the events in `seen` that started before `now`, most
+/// frequent first, then most recent. The `own` accounts are left out.
+pub(crate) fn collect(seen: &SeenEvents, own: &[String], now: DateTime<Utc>) -> Vec<Contact> {
+    let mut contacts: HashMap<String, Contact> = HashMap::new();
+
+    for event in seen.values().filter(|event| event.start <= now) {
+        let start = event.start;
+        for (email, name) in &event.people {
+            if own.contains(email) {
+                continue;
+            }
+
+            let contact = contacts.entry(email.clone()).or_insert_with(|| Contact {
+                email: email.clone(),
+                name: None,
+                events: 0,
+                last_seen: start,
+                looked_up: false,
+            });
+            contact.events += 1;
+            // The name from the latest event that has one
+            if name.is_some() && (contact.name.is_none() || start >= contact.last_seen) {
+                contact.name = name.clone();
+            }
+            contact.last_seen = contact.last_seen.max(start);
+        }
+    }
+
+    let mut contacts: Vec<Contact> = contacts.into_values().collect();
+    contacts.sort_by(|a, b| {
+        b.events
+            .cmp(&a.events)
+            .then(b.last_seen.cmp(&a.last_seen))
+            .then_with(|| a.email.cmp(&b.email))
+    });
+    contacts
+}
+
+/// Give contacts without a name the one they had in `previous`, and
+/// remember which of them were already looked up.
+pub(crate) fn keep_names(contacts: &mut [Contact], previous: &[Contact]) {
+    for contact in contacts.iter_mut().filter(|c| c.name.is_none()) {
+        if let Some(previous) = previous.iter().find(|p| p.email == contact.email) {
+            contact.name = previous.name.clone();
+            contact.looked_up = previous.looked_up;
+        }
+    }
+}
+
+/// Emails of the contacts without a name that no address book was asked
+/// about yet.
+pub(crate) fn to_look_up(contacts: &[Contact]) -> Vec<String> {
+    contacts
+        .iter()
+        .filter(|contact| contact.name.is_none() && !contact.looked_up)
+        .map(|contact| contact.email.clone())
+        .collect()
+}
+
+/// Name the contacts in `asked` after the address book `cards` for their
+/// email, and mark the ones it had no name for as looked up. Returns how
+/// many got a name.
+pub(crate) fn add_names(
+    contacts: &mut [Contact],
+    asked: &[String],
+    cards: &[ContactCard],
+) -> usize {
+    let mut named = 0;
+
+    for contact in contacts
+        .iter_mut()
+        .filter(|c| c.name.is_none() && asked.contains(&c.email))
+    {
+        let card = cards
+            .iter()
+            .find(|card| card.email.eq_ignore_ascii_case(&contact.email));
+        match card.filter(|card| !card.name.trim().is_empty()) {
+            Some(card) => {
+                contact.name = Some(card.name.trim().to_string());
+                named += 1;
+            }
+            None => contact.looked_up = true,
+        }
+    }
+
+    named
+}
+
+pub(crate) fn save(data_dir: &Path, contacts: &[Contact]) -> std::io::Result<()> {
+    write_json(&contacts_path(data_dir), contacts)
+}
+
+pub(crate) fn save_seen(data_dir: &Path, seen: &SeenEvents) -> std::io::Result<()> {
+    write_json(&seen_path(data_dir), seen)
+}
+
+fn write_json(path: &Path, value: &(impl Serialize + ?Sized)) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let json = serde_json::to_string_pretty(value).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+/// The saved contacts, or `None` if there are none yet (or they can't be
+/// read).
+pub(crate) fn load(data_dir: &Path) -> Option<Vec<Contact>> {
+    let json = std::fs::read_to_string(contacts_path(data_dir)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Who was in which event as of the last update, or `None` if that isn't
+/// known (or can't be read).
+pub(crate) fn load_seen(data_dir: &Path) -> Option<SeenEvents> {
+    let json = std::fs::read_to_string(seen_path(data_dir)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Attendee, Event, EventTime, Organizer};
+    use chrono::{Duration, TimeZone};
+
+    fn meeting(start: DateTime<Utc>, people: &[(&str, Option<&str>)]) -> Event {
+        let mut event = Event::new("Meeting", EventTime::DateTimeUtc(start));
+        event.attendees = people
+            .iter()
+            .map(|(email, name)| Attendee {
+                name: name.map(str::to_string),
+                ..Attendee::new(*email)
+            })
+            .collect();
+        event
+    }
+
+    #[test]
+    fn ranks_past_attendees_by_frequency_then_recency() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let calendar = Calendar::create(&tmp.path().join("work"), None).unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+
+        let mut planning = meeting(
+            now - Duration::days(3),
+            &[("alice@example.com", Some("Alice Smith"))],
+        );
+        planning.organizer = Some(Organizer::new("bob@example.com"));
+        for event in [
+            meeting(now - Duration::days(9), &[("Alice@example.com", None)]),
+            planning,
+            meeting(now - Duration::days(1), &[("carol@example.com", None)]),
+            meeting(now + Duration::days(1), &[("dave@example.com", None)]),
+        ] {
+            calendar.create_event(event).unwrap();
+        }
+
+        let contacts = collect(&seen(&[calendar]), &[], now);
+
+        let emails: Vec<&str> = contacts.iter().map(|c| c.email.as_str()).collect();
+        assert_eq!(
+            emails,
+            vec!["alice@example.com", "carol@example.com", "bob@example.com"]
+        );
+        assert_eq!(contacts[0].events, 2);
+        assert_eq!(contacts[0].name.as_deref(), Some("Alice Smith"));
+        assert_eq!(contacts[0].last_seen, now - Duration::days(3));
+    }
+
+    #[test]
+    fn changes_update_contacts_without_counting_an_event_twice() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let review = meeting(now - Duration::days(2), &[("alice@example.com", None)]);
+        let mut seen = SeenEvents::new();
+
+        apply_changes(&mut seen, &[EventChange::Create(review.clone())]);
+        let mut moved = review.clone();
+        moved.attendees.push(Attendee::new("bob@example.com"));
+        apply_changes(
+            &mut seen,
+            &[EventChange::Update {
+                from: review,
+                to: moved.clone(),
+            }],
+        );
+
+        let contacts = collect(&seen, &[], now);
+        assert_eq!(contacts.len(), 2);
+        assert!(contacts.iter().all(|contact| contact.events == 1));
+
+        apply_changes(&mut seen, &[EventChange::Delete(moved)]);
+        assert!(collect(&seen, &[], now).is_empty());
+    }
+
+    #[test]
+    fn matches_the_start_of_the_email_or_a_name() {
+        let contact = Contact {
+            email: "asmith@example.com".to_string(),
+            name: Some("Alice Smith".to_string()),
+            events: 1,
+            last_seen: Utc::now(),
+            looked_up: false,
+        };
+
+        assert!(contact.matches("asm"));
+        assert!(contact.matches("alice"));
+        assert!(contact.matches("Smi"));
+        assert!(!contact.matches("example"));
+    }
+
+    #[test]
+    fn names_contacts_from_address_book_cards() {
+        let unnamed = |email: &str| Contact {
+            email: email.to_string(),
+            name: None,
+            events: 1,
+            last_seen: Utc::now(),
+            looked_up: false,
+        };
+        let mut contacts = vec![unnamed("alice@example.com"), unnamed("bob@example.com")];
+        let cards = vec![ContactCard {
+            email: "Alice@Example.com".to_string(),
+            name: "Alice Smith".to_string(),
+        }];
+
+        let asked = to_look_up(&contacts);
+        assert_eq!(add_names(&mut contacts, &asked, &cards), 1);
+        assert_eq!(contacts[0].name.as_deref(), Some("Alice Smith"));
+        assert_eq!(contacts[1].name, None);
+        // Bob isn't in the address book, so he isn't asked about again
+        assert!(contacts[1].looked_up);
+        assert!(to_look_up(&contacts).is_empty());
+
+        let mut rebuilt = vec![unnamed("alice@example.com"), unnamed("bob@example.com")];
+        keep_names(&mut rebuilt, &contacts);
+        assert_eq!(rebuilt[0].name.as_deref(), Some("Alice Smith"));
+        assert!(rebuilt[1].looked_up);
+    }
+
+    #[test]
+    fn saves_and_loads_contacts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let contacts = vec![Contact {
+            email: "alice@example.com".to_string(),
+            name: None,
+            events: 3,
+            last_seen: Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap(),
+            looked_up: false,
+        }];
+
+        assert_eq!(load(tmp.path()), None);
+        save(tmp.path(), &contacts).unwrap();
+        assert_eq!(load(tmp.path()), Some(contacts));
+    }
+}
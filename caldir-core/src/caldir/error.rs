@@ -1,6 +1,9 @@
 use super::config::CaldirConfigError;
+use super::snapshot::SnapshotError;
 use crate::calendar::CalendarError;
+use crate::connection::ConnectionError;
 use crate::provider::ProviderError;
+use crate::rpc::ErrorCode;
 
 #[derive(Debug, thiserror::Error)]
 pub enum CaldirError {
@@ -15,4 +18,38 @@ pub enum CaldirError {
 
     #[error("no default calendar configured")]
     NoDefaultCalendar,
+
+    #[error("unknown profile \"{0}\" (add it under [profiles] in the global config)")]
+    UnknownProfile(String),
+
+    #[error("a calendar named \"{0}\" already exists")]
+    CalendarExists(String),
+
+    #[error("\"{0}\" isn't a valid calendar name (no slashes or leading dots)")]
+    InvalidSlug(String),
+
+    #[error("couldn't rename calendar: {0}")]
+    Rename(std::io::Error),
+
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+
+    #[error("couldn't update views: {0}")]
+    Views(std::io::Error),
+
+    #[error("couldn't update contacts: {0}")]
+    Contacts(std::io::Error),
+
+    #[error("couldn't look up contact names: {0}")]
+    ContactLookup(ConnectionError),
+}
+
+impl CaldirError {
+    /// The provider's error code, if it sent one.
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Self::Provider(e) => e.code(),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,139 @@
+//! Archives of every calendar in the data directory, with their config and
+//! sync state, kept as `.caldir/snapshots/<id>.tar.gz`.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+const SNAPSHOTS_DIR: &str = ".caldir/snapshots";
+const EXTENSION: &str = ".tar.gz";
+const ID_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("No snapshot with id {0}")]
+    NotFound(String),
+
+    #[error("Snapshot I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    id: String,
+    path: PathBuf,
+}
+
+impl Snapshot {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        NaiveDateTime::parse_from_str(&self.id, ID_FORMAT)
+            .ok()
+            .map(|time| time.and_utc())
+    }
+}
+
+fn snapshots_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(SNAPSHOTS_DIR)
+}
+
+/// Archive the calendar directories in `data_dir`, leaving out `views_dir`.
+pub(crate) fn create(data_dir: &Path, views_dir: Option<&Path>) -> Result<Snapshot, SnapshotError> {
+    let dir = snapshots_dir(data_dir);
+    std::fs::create_dir_all(&dir)?;
+
+    let id = Utc::now().format(ID_FORMAT).to_string();
+    let path = dir.join(format!("{id}{EXTENSION}"));
+
+    // Written under another name first, so a failed snapshot never looks complete
+    let tmp = tempfile::NamedTempFile::new_in(&dir)?;
+    let mut archive = tar::Builder::new(GzEncoder::new(tmp.as_file(), Compression::default()));
+
+    for entry in std::fs::read_dir(data_dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+
+        if views_dir == Some(entry.path().as_path()) {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() && !name.to_string_lossy().starts_with('.') {
+            archive.append_dir_all(&name, entry.path())?;
+        }
+    }
+
+    archive.into_inner()?.finish()?;
+    tmp.persist(&path).map_err(|err| err.error)?;
+
+    Ok(Snapshot { id, path })
+}
+
+/// Snapshots in `data_dir`, oldest first.
+pub(crate) fn list(data_dir: &Path) -> Result<Vec<Snapshot>, SnapshotError> {
+    let dir = snapshots_dir(data_dir);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let id = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(EXTENSION));
+
+        if let Some(id) = id {
+            snapshots.push(Snapshot {
+                id: id.to_string(),
+                path: path.clone(),
+            });
+        }
+    }
+
+    snapshots.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(snapshots)
+}
+
+/// Replace the calendars in snapshot `id` with their archived copies, and
+/// return their paths. Calendars created since are left alone.
+pub(crate) fn restore(data_dir: &Path, id: &str) -> Result<Vec<PathBuf>, SnapshotError> {
+    let dir = snapshots_dir(data_dir);
+    let path = dir.join(format!("{id}{EXTENSION}"));
+    if !path.is_file() {
+        return Err(SnapshotError::NotFound(id.to_string()));
+    }
+
+    // Unpacked in full before anything is replaced, so a broken archive
+    // leaves the calendars as they were
+    let staging = tempfile::TempDir::new_in(&dir)?;
+    tar::Archive::new(GzDecoder::new(File::open(&path)?)).unpack(staging.path())?;
+
+    let mut restored = Vec::new();
+
+    for entry in std::fs::read_dir(staging.path())? {
+        let entry = entry?;
+        let target = data_dir.join(entry.file_name());
+
+        if target.exists() {
+            std::fs::remove_dir_all(&target)?;
+        }
+        std::fs::rename(entry.path(), &target)?;
+
+        restored.push(target);
+    }
+
+    Ok(restored)
+}
@@ -0,0 +1,250 @@
+//! Symlink views of the event files, for browsing by date in a file manager
+//! or shell: `by-date/2026-03-20/` and `upcoming/`. Rebuilt from scratch on
+//! every refresh; nothing but symlinks is ever removed.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Local, NaiveDate};
+
+use super::CaldirError;
+use crate::{Calendar, Event, EventTime, FloatingTimezone, expand_in_range_with};
+
+const BY_DATE_DIR: &str = "by-date";
+const UPCOMING_DIR: &str = "upcoming";
+
+/// Days back and ahead of today that `by-date` covers, like a default pull
+const BY_DATE_DAYS: i64 = 365;
+
+/// Days ahead that `upcoming` covers
+const UPCOMING_DAYS: i64 = 7;
+
+/// Longest span an event gets a link on every day of
+const MAX_SPAN_DAYS: usize = 62;
+
+/// A symlink at `link` (relative to the views directory) to `target`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Link {
+    link: PathBuf,
+    target: PathBuf,
+}
+
+pub(crate) fn refresh(
+    views_dir: &Path,
+    calendars: &[Calendar],
+    now: DateTime<Local>,
+) -> Result<(), CaldirError> {
+    let mut links = Vec::new();
+    for calendar in calendars {
+        links.extend(calendar_links(calendar, now)?);
+    }
+
+    write_links(views_dir, &links).map_err(CaldirError::Views)
+}
+
+fn calendar_links(calendar: &Calendar, now: DateTime<Local>) -> Result<Vec<Link>, CaldirError> {
+    let slug = calendar.slug().unwrap_or_default();
+    let floating = calendar.floating_timezone();
+    let calendar_events = calendar.events()?;
+
+    // Occurrences point at their override's file, or else their series'
+    let mut files = HashMap::new();
+    let mut series_files = HashMap::new();
+    for calendar_event in &calendar_events {
+        let event = calendar_event.event();
+        files.insert(event.event_instance_id(), calendar_event.path());
+        if event.recurrence.is_some() {
+            series_files.insert(event.uid.clone(), calendar_event.path());
+        }
+    }
+
+    let occurrences = expand_in_range_with(
+        calendar_events.iter().map(|ce| ce.event().clone()),
+        (now - Duration::days(BY_DATE_DAYS)).to_utc(),
+        (now + Duration::days(BY_DATE_DAYS)).to_utc(),
+        floating,
+    );
+    let upcoming_until = now + Duration::days(UPCOMING_DAYS);
+
+    let mut links = Vec::new();
+    for occurrence in occurrences {
+        let Some(target) = files
+            .get(&occurrence.event_instance_id())
+            .or_else(|| series_files.get(&occurrence.uid))
+        else {
+            continue;
+        };
+        let Some(file_name) = target.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        // `2026-03-20T0900__standup.ics` → `standup.ics`
+        let name = file_name
+            .split_once("__")
+            .map_or(file_name, |(_, name)| name);
+
+        let start = occurrence.start.to_local_tz_with(&Local, floating);
+        let time = match occurrence.start {
+            EventTime::Date(_) => "all-day".to_string(),
+            _ => start.format("%H%M").to_string(),
+        };
+
+        for day in days(&occurrence, floating) {
+            links.push(Link {
+                link: Path::new(BY_DATE_DIR)
+                    .join(day.format("%Y-%m-%d").to_string())
+                    .join(format!("{time}__{slug}__{name}")),
+                target: target.to_path_buf(),
+            });
+        }
+
+        let end = occurrence.end.as_ref().unwrap_or(&occurrence.start);
+        if end.to_local_tz_with(&Local, floating) > now && start < upcoming_until {
+            links.push(Link {
+                link: Path::new(UPCOMING_DIR)
+                    .join(format!("{}__{slug}__{name}", start.format("%Y-%m-%dT%H%M"))),
+                target: target.to_path_buf(),
+            });
+        }
+    }
+
+    Ok(links)
+}
+
+/// The local dates `event` is on. Ends are exclusive, so an event ending at
+/// midnight (or an all-day event ending the next day) is on one day.
+fn days(event: &Event, floating: FloatingTimezone) -> Vec<NaiveDate> {
+    let start = event.start.to_local_tz_with(&Local, floating);
+    let last = event
+        .end
+        .as_ref()
+        .map(|end| end.to_local_tz_with(&Local, floating) - Duration::seconds(1))
+        .filter(|last| *last > start)
+        .unwrap_or(start);
+
+    start
+        .date_naive()
+        .iter_days()
+        .take_while(|day| *day <= last.date_naive())
+        .take(MAX_SPAN_DAYS)
+        .collect()
+}
+
+fn write_links(views_dir: &Path, links: &[Link]) -> io::Result<()> {
+    for dir in [BY_DATE_DIR, UPCOMING_DIR] {
+        remove_links(&views_dir.join(dir))?;
+    }
+
+    let mut written = HashSet::new();
+    for Link { link, target } in links {
+        if !written.insert(link) {
+            continue;
+        }
+        let path = views_dir.join(link);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        symlink(target, &path)?;
+    }
+
+    Ok(())
+}
+
+/// Remove the symlinks under `dir`, then the directories left empty.
+fn remove_links(dir: &Path) -> io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            std::fs::remove_file(entry.path())?;
+        } else if file_type.is_dir() {
+            remove_links(&entry.path())?;
+        }
+    }
+
+    // Fails if something other than our links is in there, which is fine
+    let _ = std::fs::remove_dir(dir);
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_caldir;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> EventTime {
+        EventTime::DateTimeFloating(
+            NaiveDate::from_ymd_opt(2026, 3, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn links_events_by_date_and_upcoming() {
+        let (tmp, caldir) = test_caldir();
+        let calendar = caldir.create_calendar("work", None).unwrap();
+        let mut trip = Event::new("Trip", at(20, 9));
+        trip.end = Some(at(22, 18));
+        let trip = calendar.create_event(trip).unwrap();
+        calendar
+            .create_event(Event::new("Retro", at(1, 9)))
+            .unwrap();
+
+        let views = tmp.path().join("views");
+        let now = Local.with_ymd_and_hms(2026, 3, 18, 12, 0, 0).unwrap();
+        refresh(&views, &[calendar], now).unwrap();
+
+        for day in ["2026-03-20", "2026-03-21", "2026-03-22"] {
+            let link = views.join("by-date").join(day).join("0900__work__trip.ics");
+            assert_eq!(std::fs::read_link(&link).unwrap(), trip.path());
+        }
+        assert!(
+            views
+                .join("by-date/2026-03-01/0900__work__retro.ics")
+                .exists()
+        );
+
+        let upcoming: Vec<_> = std::fs::read_dir(views.join("upcoming"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(upcoming, ["2026-03-20T0900__work__trip.ics"]);
+    }
+
+    #[test]
+    fn refresh_replaces_old_links_but_nothing_else() {
+        let (tmp, caldir) = test_caldir();
+        let calendar = caldir.create_calendar("work", None).unwrap();
+        let views = tmp.path().join("views");
+        let stale = views.join("upcoming/stale.ics");
+        let note = views.join("by-date/notes.txt");
+        std::fs::create_dir_all(stale.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(note.parent().unwrap()).unwrap();
+        symlink(Path::new("/nowhere"), &stale).unwrap();
+        std::fs::write(&note, "mine").unwrap();
+
+        refresh(&views, &[calendar], Local::now()).unwrap();
+
+        assert!(std::fs::symlink_metadata(&stale).is_err());
+        assert!(note.is_file());
+    }
+}
@@ -1,20 +1,39 @@
 mod config;
+mod duplicates;
 mod error;
 mod event;
+mod file_stamp;
+#[cfg(feature = "index")]
+mod index;
+mod query;
 mod state;
+mod sync_log;
+mod trash;
 
-use crate::event::{EventInstanceId, EventTime, EventUid, Recurrence, expand_in_range};
+use crate::event::{EventInstanceId, EventTime, EventUid, Recurrence, expand_in_range_with};
+use crate::rpc::{Notify, TimePeriod};
 use crate::utils::slugify;
-use crate::{Event, RemoteConfig};
+use crate::{
+    Availability, Event, FloatingTimezone, ImipOutcome, ImipPayload, ParticipationStatus, Reminder,
+    RemoteConfig, Status,
+};
+use rayon::prelude::*;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use chrono::{DateTime, Utc};
-pub use config::CalendarConfig;
+pub use config::{CalendarConfig, Layout, PullFilter, PullRule, StorageFormat};
+pub use duplicates::DuplicateRepair;
 pub use error::CalendarError;
 pub use event::CalendarEvent;
-pub(crate) use event::CalendarEventError;
+pub(crate) use event::{CalendarEventError, best_event_path};
+pub(crate) use file_stamp::FileStamp;
+pub use query::EventQuery;
 pub use state::CalendarState;
-pub(crate) use state::SyncBases;
+pub(crate) use state::{ParseCache, SyncBases};
+pub use sync_log::{SyncChangeKind, SyncDirection, SyncLogEntry};
+pub use trash::TrashedEvent;
 
 const DOTDIR_NAME: &str = ".caldir";
 
@@ -24,6 +43,12 @@ const CONFIG_FILE_NAME: &str = "config.toml";
 // ~/caldir/my_calendar/.caldir/state/known_event_ids
 const STATE_DIR_NAME: &str = "state";
 
+// ~/caldir/my_calendar/.caldir/sync_log.jsonl
+const SYNC_LOG_FILE_NAME: &str = "sync_log.jsonl";
+
+// ~/caldir/my_calendar/.caldir/last_synced
+const LAST_SYNCED_FILE_NAME: &str = "last_synced";
+
 fn calendar_dotdir(calendar_path: &Path) -> PathBuf {
     calendar_path.join(DOTDIR_NAME)
 }
@@ -41,6 +66,9 @@ pub struct Calendar {
     path: PathBuf,
     config: Option<CalendarConfig>,
     state: CalendarState,
+    floating_timezone: FloatingTimezone,
+    /// Loaded on the first `events()` call and kept for the ones after
+    parse_cache: Mutex<Option<ParseCache>>,
 }
 
 impl Calendar {
@@ -64,6 +92,8 @@ impl Calendar {
             path: path.to_path_buf(),
             config,
             state: CalendarState::new(),
+            floating_timezone: FloatingTimezone::default(),
+            parse_cache: Mutex::new(None),
         })
     }
 
@@ -83,9 +113,22 @@ impl Calendar {
             path: path.to_path_buf(),
             config,
             state,
+            floating_timezone: FloatingTimezone::default(),
+            parse_cache: Mutex::new(None),
         })
     }
 
+    /// Place this calendar's floating times in `floating_timezone`.
+    pub fn with_floating_timezone(mut self, floating_timezone: FloatingTimezone) -> Self {
+        self.floating_timezone = floating_timezone;
+        self
+    }
+
+    /// Where this calendar's floating times happen.
+    pub fn floating_timezone(&self) -> FloatingTimezone {
+        self.floating_timezone
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -102,25 +145,187 @@ impl Calendar {
         self.path().file_name().and_then(|s| s.to_str())
     }
 
-    /// Load all events in calendar
+    /// Load all events in calendar. Files unchanged since the last call are
+    /// read from a cache of parsed events rather than parsed again. The
+    /// cache is read once per `Calendar`, and written when it changed.
     pub fn events(&self) -> Result<Vec<CalendarEvent>, CalendarError> {
+        let state_dir = calendar_state_dir(self.path());
+        let mut guard = self
+            .parse_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let cache = guard.get_or_insert_with(|| ParseCache::load(&state_dir));
+        let mut files = HashSet::new();
         let mut events: Vec<CalendarEvent> = Vec::new();
+        // Files to parse, with their cache key if they have one
+        let mut to_parse: Vec<(PathBuf, Option<(String, FileStamp)>)> = Vec::new();
+
+        for path in self.event_files()? {
+            // Relative, so files in year/month directories get their own keys
+            let Some(file) = path
+                .strip_prefix(self.path())
+                .ok()
+                .and_then(|relative| relative.to_str())
+                .map(str::to_string)
+            else {
+                to_parse.push((path, None));
+                continue;
+            };
 
-        for entry in std::fs::read_dir(self.path())? {
-            let entry = entry?;
-            let path = entry.path();
+            let stamp = FileStamp::of(&std::fs::metadata(&path)?)?;
+            match cache.get(&file, stamp) {
+                Some(cached) => events.extend(
+                    cached
+                        .into_iter()
+                        .map(|event| CalendarEvent::from_parsed(event.clone(), path.clone())),
+                ),
+                None => to_parse.push((path, Some((file.clone(), stamp)))),
+            }
+
+            files.insert(file);
+        }
 
-            if entry.file_type()?.is_file() && path.extension().is_some_and(|ext| ext == "ics") {
-                events.push(CalendarEvent::load(path)?);
+        let parsed = to_parse
+            .into_par_iter()
+            .map(|(path, key)| Ok((CalendarEvent::load_all(path)?, key)))
+            .collect::<Result<Vec<_>, CalendarError>>()?;
+
+        for (calendar_events, key) in parsed {
+            if let Some((file, stamp)) = key {
+                let parsed = calendar_events
+                    .iter()
+                    .map(|ce| ce.event().clone())
+                    .collect();
+                cache.insert(file, stamp, parsed);
             }
+            events.extend(calendar_events);
         }
 
+        cache.retain(&files);
+        cache.save(&state_dir)?;
+
+        // Same order every time, whatever order the directory lists them in
+        events.sort_by(|a, b| a.path().cmp(b.path()));
+
         Ok(events)
     }
 
-    /// Load specific event in calendar
+    /// Like `events`, but refusing files that hold the same event: which
+    /// one sync would pick is anyone's guess.
+    pub(crate) fn unique_events(&self) -> Result<Vec<CalendarEvent>, CalendarError> {
+        let events = self.events()?;
+
+        let mut seen = HashSet::new();
+        let duplicate = events
+            .iter()
+            .find(|ce| !seen.insert(ce.event().event_instance_id()));
+
+        if let Some(duplicate) = duplicate {
+            let id = duplicate.event().event_instance_id();
+            let files = events
+                .iter()
+                .filter(|ce| ce.event().event_instance_id() == id)
+                .map(|ce| ce.path().to_path_buf())
+                .collect();
+            return Err(CalendarError::DuplicateEvent {
+                uid: id.uid().as_str().to_string(),
+                files,
+            });
+        }
+
+        Ok(events)
+    }
+
+    /// Sets of files holding the same event, newest first within each set.
+    pub fn duplicate_events(&self) -> Result<Vec<Vec<CalendarEvent>>, CalendarError> {
+        Ok(duplicates::duplicate_sets(self.events()?))
+    }
+
+    /// Repair every set of files holding the same event. Returns how many
+    /// sets there were.
+    pub fn repair_duplicates(&self, how: DuplicateRepair) -> Result<usize, CalendarError> {
+        let sets = self.duplicate_events()?;
+        let count = sets.len();
+
+        for set in sets {
+            duplicates::repair(set, how)?;
+        }
+
+        Ok(count)
+    }
+
+    /// Paths of the event files in the calendar, in no particular order.
+    pub fn event_files(&self) -> Result<Vec<PathBuf>, CalendarError> {
+        Ok(config::event_files(self.path(), self.storage_format())?)
+    }
+
+    /// Give event files renamed by hand (or by another tool) their usual name
+    /// back. Events are tracked by UID, so a file's name never counts as a
+    /// change to sync either way. Returns how many files were renamed.
+    pub(crate) fn normalize_file_names(&self) -> Result<usize, CalendarError> {
+        let mut renamed = 0;
+        let mut seen = HashSet::new();
+
+        for calendar_event in self.events()? {
+            let path = calendar_event.path();
+            // A series file is named after its master, which comes first
+            if !seen.insert(path.to_path_buf()) {
+                continue;
+            }
+            let event = calendar_event.event();
+            let best = best_event_path(
+                &calendar_event.dir_for(event),
+                &event.base_slug(),
+                calendar_event.storage_format(),
+                Some(path),
+                Path::exists,
+            );
+
+            if best != path {
+                if let Some(dir) = best.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                // Keeps the mtime, so sync directions are unaffected
+                std::fs::rename(path, &best)?;
+                renamed += 1;
+            }
+        }
+
+        Ok(renamed)
+    }
+
+    /// Forget what was last synced and count every event as changed locally.
+    /// Other state (parse cache, index, sync log) is kept.
+    pub(crate) fn mark_all_changed(&mut self) -> Result<(), CalendarError> {
+        let now = filetime::FileTime::now();
+        for calendar_event in self.events()? {
+            filetime::set_file_mtime(calendar_event.path(), now)?;
+        }
+
+        self.state
+            .clear_sync_bases(&calendar_state_dir(&self.path))?;
+
+        Ok(())
+    }
+
+    /// Load specific event in calendar, by its file name without extension.
+    /// `.ics` files are found in Markdown calendars too, and files in
+    /// year/month directories in either layout.
     pub fn event(&self, event_slug: &str) -> Result<CalendarEvent, CalendarError> {
-        let event_path = self.path().join(format!("{}.ics", event_slug));
+        let extension = self.storage_format().extension();
+        let top_level = [
+            self.path().join(format!("{event_slug}.{extension}")),
+            self.path().join(format!("{event_slug}.ics")),
+        ];
+
+        let event_path = match top_level.iter().find(|path| path.is_file()) {
+            Some(path) => path.clone(),
+            None => self
+                .event_files()?
+                .into_iter()
+                .find(|path| path.file_stem().is_some_and(|stem| stem == event_slug))
+                .unwrap_or_else(|| top_level[0].clone()),
+        };
         let calendar_event = CalendarEvent::load(event_path)?;
         Ok(calendar_event)
     }
@@ -156,6 +361,19 @@ impl Calendar {
             .unwrap_or(false)
     }
 
+    pub fn is_archived(&self) -> bool {
+        self.config.as_ref().is_some_and(|c| c.archived())
+    }
+
+    /// Archive or unarchive the calendar, writing its config.
+    pub fn set_archived(&mut self, archived: bool) -> Result<(), CalendarError> {
+        let config = self.config.get_or_insert_with(CalendarConfig::default);
+        config.set_archived(archived);
+        config.write(&calendar_config_path(&self.path))?;
+
+        Ok(())
+    }
+
     pub fn name(&self) -> Option<&str> {
         self.config.as_ref().and_then(|c| c.name())
     }
@@ -164,6 +382,15 @@ impl Calendar {
         self.config.as_ref().and_then(|c| c.color())
     }
 
+    pub fn default_reminders(&self) -> Option<Vec<Reminder>> {
+        self.config.as_ref().and_then(|c| c.default_reminders())
+    }
+
+    /// Which attendees pushes email unless told otherwise
+    pub fn notify(&self) -> Option<Notify> {
+        self.config.as_ref().and_then(|c| c.notify())
+    }
+
     pub fn read_only_setting(&self) -> Option<bool> {
         self.config.as_ref().and_then(|c| c.read_only())
     }
@@ -330,16 +557,163 @@ impl Calendar {
         from: DateTime<Utc>,
         to: DateTime<Utc>,
     ) -> Result<Vec<Event>, CalendarError> {
-        let events = self.events()?.into_iter().map(|ce| ce.event().clone());
-        Ok(expand_in_range(events, from, to))
+        let events = self
+            .events_near(from, to)?
+            .into_iter()
+            .map(|ce| ce.event().clone());
+        Ok(expand_in_range_with(
+            events,
+            from,
+            to,
+            self.floating_timezone,
+        ))
+    }
+
+    /// This calendar's timed events between `from` and `to` that keep its
+    /// owner busy: not free, cancelled or declined.
+    pub fn busy_events(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Event>, CalendarError> {
+        let email = self.remote_email();
+
+        let events = self
+            .expanded_events_in_range(from, to)?
+            .into_iter()
+            .filter(|event| {
+                event.availability == Availability::Busy
+                    && event.status != Status::Cancelled
+                    && !event.start.is_date()
+                    && event.end.is_some()
+                    && email.is_none_or(|email| {
+                        event.attendee_status(email) != Some(ParticipationStatus::Declined)
+                    })
+            })
+            .collect();
+
+        Ok(events)
+    }
+
+    /// When this calendar's [busy events](Self::busy_events) are.
+    pub fn busy_periods(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<TimePeriod>, CalendarError> {
+        let periods = self
+            .busy_events(from, to)?
+            .into_iter()
+            .filter_map(|event| {
+                Some(TimePeriod {
+                    start: event.start.to_utc_with(self.floating_timezone),
+                    end: event.end.as_ref()?.to_utc_with(self.floating_timezone),
+                })
+            })
+            .collect();
+
+        Ok(periods)
+    }
+
+    /// Events that may occur between `from` and `to`. Without the `index`
+    /// feature, that's all of them.
+    #[cfg(not(feature = "index"))]
+    fn events_near(
+        &self,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> Result<Vec<CalendarEvent>, CalendarError> {
+        self.events()
+    }
+
+    #[cfg(feature = "index")]
+    fn events_near(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<CalendarEvent>, CalendarError> {
+        let mut index = index::EventIndex::open(&calendar_state_dir(&self.path))?;
+        index.refresh(&self.path, self.storage_format())?;
+
+        let events = index
+            .files_in_range(&self.path, from, to)?
+            .into_par_iter()
+            .map(CalendarEvent::load_all)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(events.into_iter().flatten().collect())
+    }
+
+    /// Events matching `query`, with recurring events expanded
+    pub fn query(&self, query: &EventQuery) -> Result<Vec<Event>, CalendarError> {
+        let (from, to) = query.range();
+
+        let mut events: Vec<Event> = self
+            .expanded_events_in_range(from, to)?
+            .into_iter()
+            .filter(|event| query.matches(event))
+            .collect();
+
+        query.sort_and_limit(&mut events, |event| event);
+
+        Ok(events)
     }
 
-    /// Create new event in calendar
+    /// Create new event in calendar. With `single_file_series` set, an
+    /// override goes into its master's file.
     pub fn create_event(&self, event: Event) -> Result<CalendarEvent, CalendarError> {
+        let series_file = self.series_file_for(&event)?;
+        self.create_event_in(event, series_file)
+    }
+
+    /// Like `create_event`, with the file to add an override to (see
+    /// `series_file_with`) already looked up.
+    pub(crate) fn create_event_in(
+        &self,
+        event: Event,
+        series_file: Option<PathBuf>,
+    ) -> Result<CalendarEvent, CalendarError> {
+        if let Some(path) = series_file {
+            return Ok(CalendarEvent::add_to_file(path, event)?);
+        }
+
         let calendar_event = CalendarEvent::create(self, event)?;
         Ok(calendar_event)
     }
 
+    /// The file `create_event` would add `event` to rather than giving it
+    /// its own: its master's, for an override in a calendar with
+    /// `single_file_series` set.
+    pub(crate) fn series_file_for(&self, event: &Event) -> Result<Option<PathBuf>, CalendarError> {
+        if event.recurrence_id.is_none() || !self.single_file_series() {
+            return Ok(None);
+        }
+
+        let events = self.events()?;
+        let master = events
+            .iter()
+            .find(|ce| ce.event().uid == event.uid && ce.event().recurrence.is_some());
+
+        Ok(self.series_file_with(event, master))
+    }
+
+    /// Like `series_file_for`, with the `master` of `event`'s series already
+    /// found, so creating many overrides doesn't read the calendar for each.
+    pub(crate) fn series_file_with(
+        &self,
+        event: &Event,
+        master: Option<&CalendarEvent>,
+    ) -> Option<PathBuf> {
+        if event.recurrence_id.is_none() || !self.single_file_series() {
+            return None;
+        }
+
+        master
+            .filter(|ce| ce.event().recurrence.is_some())
+            .map(|ce| ce.path().to_path_buf())
+            .filter(|path| StorageFormat::of_path(path) == Some(StorageFormat::Ics))
+    }
+
     /// Delete event from calendar
     pub fn delete_event(&self, event_slug: &str) -> Result<(), CalendarError> {
         let event = self.event(event_slug)?;
@@ -347,10 +721,52 @@ impl Calendar {
         Ok(())
     }
 
+    /// Move event to the calendar's trash instead of deleting it.
+    pub(crate) fn trash_event(&self, calendar_event: CalendarEvent) -> Result<(), CalendarError> {
+        trash::move_to_trash(self.path(), calendar_event)
+    }
+
+    /// Apply an invitation, reply or cancellation received by email.
+    pub fn apply_imip(&self, payload: &ImipPayload) -> Result<Vec<ImipOutcome>, CalendarError> {
+        crate::imip::apply(self, payload)
+    }
+
+    /// Events that pull deleted, most recently trashed first.
+    pub fn trash(&self) -> Result<Vec<TrashedEvent>, CalendarError> {
+        trash::list(self.path())
+    }
+
+    /// Put a trashed event back in the calendar. Its sync base is forgotten,
+    /// so the next push recreates it remotely rather than deleting it again.
+    pub fn restore_from_trash(&mut self, id: &str) -> Result<CalendarEvent, CalendarError> {
+        let trashed = trash::get(self.path(), id)?;
+
+        let calendar_event = self.create_event(trashed.event().clone())?;
+        self.state.forget_sync_base(
+            &trashed.event().event_instance_id(),
+            &calendar_state_dir(&self.path),
+        )?;
+        std::fs::remove_file(trashed.path())?;
+
+        Ok(calendar_event)
+    }
+
     pub fn remote_config(&self) -> Option<&RemoteConfig> {
         self.config.as_ref().and_then(|c| c.remote_config())
     }
 
+    /// Stop syncing with the remote. The events stay, as a local calendar.
+    pub fn disconnect(&mut self) -> Result<(), CalendarError> {
+        let Some(config) = &mut self.config else {
+            return Ok(());
+        };
+
+        config.remove_remote();
+        config.write(&calendar_config_path(&self.path))?;
+
+        Ok(())
+    }
+
     pub fn has_remote(&self) -> bool {
         self.remote_config().is_some()
     }
@@ -359,6 +775,37 @@ impl Calendar {
         self.config.as_ref()
     }
 
+    /// Where new events go in the directory, flat unless the config says
+    /// otherwise.
+    pub fn layout(&self) -> Layout {
+        self.config
+            .as_ref()
+            .map(CalendarConfig::layout)
+            .unwrap_or_default()
+    }
+
+    /// Whether overrides are written into their master's file.
+    /// Only `.ics` files can hold more than one event.
+    pub fn single_file_series(&self) -> bool {
+        self.config
+            .as_ref()
+            .is_some_and(CalendarConfig::single_file_series)
+    }
+
+    pub fn restore_file_names(&self) -> bool {
+        self.config
+            .as_ref()
+            .is_some_and(CalendarConfig::restore_file_names)
+    }
+
+    /// How new events are written, `.ics` unless the config says otherwise.
+    pub fn storage_format(&self) -> StorageFormat {
+        self.config
+            .as_ref()
+            .map(CalendarConfig::storage)
+            .unwrap_or_default()
+    }
+
     pub fn remote_email(&self) -> Option<&str> {
         self.remote_config()
             .and_then(|remote_config| remote_config.account_identifier())
@@ -374,6 +821,47 @@ impl Calendar {
         Ok(())
     }
 
+    pub(crate) fn forget_sync_bases(
+        &mut self,
+        ids: &[EventInstanceId],
+    ) -> Result<(), CalendarError> {
+        for id in ids {
+            self.state
+                .forget_sync_base(id, &calendar_state_dir(&self.path))?;
+        }
+        Ok(())
+    }
+
+    /// What pull and push did to this calendar, oldest first.
+    pub fn sync_log(&self) -> Result<Vec<SyncLogEntry>, CalendarError> {
+        Ok(sync_log::read(&self.sync_log_path())?)
+    }
+
+    pub(crate) fn append_sync_log(&self, entries: &[SyncLogEntry]) -> Result<(), CalendarError> {
+        Ok(sync_log::append(&self.sync_log_path(), entries)?)
+    }
+
+    fn sync_log_path(&self) -> PathBuf {
+        calendar_dotdir(&self.path).join(SYNC_LOG_FILE_NAME)
+    }
+
+    /// When a pull or push last went through without errors.
+    pub fn last_synced(&self) -> Option<DateTime<Utc>> {
+        let contents = std::fs::read_to_string(self.last_synced_path()).ok()?;
+        DateTime::parse_from_rfc3339(contents.trim())
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+
+    pub(crate) fn record_synced(&self, at: DateTime<Utc>) -> Result<(), CalendarError> {
+        std::fs::write(self.last_synced_path(), at.to_rfc3339())?;
+        Ok(())
+    }
+
+    fn last_synced_path(&self) -> PathBuf {
+        calendar_dotdir(&self.path).join(LAST_SYNCED_FILE_NAME)
+    }
+
     pub fn base_slug_for(name: Option<&str>) -> String {
         name.map(slugify).unwrap_or_else(|| "calendar".to_string())
     }
@@ -386,6 +874,33 @@ mod tests {
         test_caldir, test_calendar, test_calendar_config, test_calendar_path, test_event,
     };
 
+    #[test]
+    fn mark_all_changed_forgets_sync_bases_but_keeps_other_state() {
+        let (_tmp, mut calendar) = test_calendar();
+        let event = calendar.create_event(test_event()).unwrap();
+        calendar
+            .record_sync_bases(vec![event.event().clone()])
+            .unwrap();
+        let state_dir = calendar_state_dir(calendar.path());
+        let parse_cache = state_dir.join("parsed_events.json");
+        calendar.events().unwrap();
+        assert!(parse_cache.is_file());
+
+        calendar.mark_all_changed().unwrap();
+
+        assert!(calendar.state().sync_bases().iter().next().is_none());
+        assert!(
+            Calendar::load(calendar.path())
+                .unwrap()
+                .state()
+                .sync_bases()
+                .iter()
+                .next()
+                .is_none()
+        );
+        assert!(parse_cache.is_file());
+    }
+
     #[test]
     fn create_creates_caldir_subdirectory() {
         let (_, path) = test_calendar_path();
@@ -422,6 +937,47 @@ mod tests {
         assert_eq!(loaded_config, config);
     }
 
+    #[test]
+    fn disconnect_keeps_the_config_without_the_remote() {
+        let (_, path) = test_calendar_path();
+        let mut config = test_calendar_config();
+        config.set_remote(crate::test_utils::test_remote_config("hooli"));
+        let mut calendar = Calendar::create(&path, Some(config)).unwrap();
+        calendar.create_event(test_event()).unwrap();
+
+        calendar.disconnect().unwrap();
+
+        let reloaded = Calendar::load(&path).unwrap();
+        assert!(!reloaded.has_remote());
+        assert_eq!(reloaded.name(), Some("Test Calendar"));
+        assert_eq!(reloaded.events().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn set_archived_writes_the_config() {
+        let (_, path) = test_calendar_path();
+        let mut calendar = Calendar::create(&path, None).unwrap();
+        assert!(!calendar.is_archived());
+
+        calendar.set_archived(true).unwrap();
+        assert!(Calendar::load(&path).unwrap().is_archived());
+
+        calendar.set_archived(false).unwrap();
+        assert!(!Calendar::load(&path).unwrap().is_archived());
+    }
+
+    #[test]
+    fn last_synced_round_trips() {
+        let (_, path) = test_calendar_path();
+        let calendar = Calendar::create(&path, Some(test_calendar_config())).unwrap();
+        assert_eq!(calendar.last_synced(), None);
+
+        let at = Utc.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap();
+        calendar.record_synced(at).unwrap();
+
+        assert_eq!(calendar.last_synced(), Some(at));
+    }
+
     #[test]
     fn load_returns_existing_calendar() {
         let (_, path) = test_calendar_path();
@@ -484,6 +1040,111 @@ mod tests {
         assert_eq!(events.len(), 1);
     }
 
+    #[test]
+    fn markdown_calendars_write_md_files_and_still_read_ics() {
+        let (_tmp, caldir) = test_caldir();
+        let ics = caldir.create_calendar("notes", None).unwrap();
+        ics.create_event(test_event()).unwrap();
+
+        let mut config = test_calendar_config();
+        config.set("storage", "markdown").unwrap();
+        config.write(&ics.config_path()).unwrap();
+        let calendar = Calendar::load(ics.path()).unwrap();
+
+        let mut event = test_event();
+        event.summary = Some("Written as markdown".to_string());
+        let created = calendar.create_event(event).unwrap();
+
+        assert_eq!(
+            created.path().extension().and_then(|ext| ext.to_str()),
+            Some("md")
+        );
+        assert_eq!(calendar.events().unwrap().len(), 2);
+        assert_eq!(
+            calendar
+                .event("2026-01-01T1200__written-as-markdown")
+                .unwrap()
+                .event(),
+            created.event()
+        );
+    }
+
+    #[test]
+    fn year_month_layout_moves_files_when_the_date_changes() {
+        let (_tmp, caldir) = test_caldir();
+        let mut config = test_calendar_config();
+        config.set("layout", "year-month").unwrap();
+        let calendar = caldir.create_calendar("dated", Some(config)).unwrap();
+
+        let mut cal_event = calendar.create_event(test_event()).unwrap();
+        assert!(
+            cal_event
+                .path()
+                .starts_with(calendar.path().join("2026/01"))
+        );
+
+        let mut event = cal_event.event().clone();
+        event.start = EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap());
+        cal_event.update(event).unwrap();
+
+        assert!(
+            cal_event
+                .path()
+                .starts_with(calendar.path().join("2026/03"))
+        );
+        assert!(!calendar.path().join("2026/01").exists());
+        assert_eq!(calendar.events().unwrap().len(), 1);
+        assert!(calendar.event("2026-03-02__test-event").is_ok());
+    }
+
+    #[test]
+    fn single_file_series_keeps_overrides_in_the_master_file() {
+        let (_tmp, caldir) = test_caldir();
+        let mut config = test_calendar_config();
+        config.set("single_file_series", "true").unwrap();
+        let calendar = caldir.create_calendar("series", Some(config)).unwrap();
+
+        let mut master = test_event();
+        master.recurrence = Some(Recurrence::new("FREQ=DAILY"));
+        let master = calendar.create_event(master).unwrap();
+        let mut moved = master.event().clone();
+        moved.recurrence = None;
+        moved.recurrence_id = Some(RecurrenceId::from_event_time(master.event().start.clone()));
+        let mut moved = calendar.create_event(moved).unwrap();
+
+        assert_eq!(moved.path(), master.path());
+        assert_eq!(calendar.event_files().unwrap().len(), 1);
+        assert_eq!(calendar.events().unwrap().len(), 2);
+
+        let mut event = moved.event().clone();
+        event.summary = Some("Moved".to_string());
+        moved.update(event).unwrap();
+        let events = calendar.events().unwrap();
+        assert!(events[0].event().recurrence.is_some());
+        assert_eq!(events[1].event().summary.as_deref(), Some("Moved"));
+
+        moved.delete().unwrap();
+        let events = calendar.events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].path(), master.path());
+    }
+
+    #[test]
+    fn events_picks_up_files_rewritten_since_last_load() {
+        let (_tmp, calendar) = test_calendar();
+        let mut cal_event = calendar.create_event(test_event()).unwrap();
+        calendar.events().unwrap();
+
+        // Same LAST-MODIFIED, so the file keeps its mtime
+        let mut event = cal_event.event().clone();
+        event.location = Some("Room 2".to_string());
+        cal_event.update(event).unwrap();
+
+        let events = calendar.events().unwrap();
+
+        assert_eq!(events[0].event().location.as_deref(), Some("Room 2"));
+    }
+
     #[test]
     fn event_returns_event_by_slug() {
         let (_tmp, calendar) = test_calendar();
@@ -1152,4 +1813,37 @@ mod tests {
 
         assert!(matches!(err, CalendarError::MasterNotFound(_)));
     }
+
+    #[test]
+    fn busy_periods_skip_free_and_cancelled_events() {
+        let (_tmp, cal) = test_calendar();
+        let timed = |summary: &str, hour: u32| Event {
+            end: Some(EventTime::DateTimeUtc(t(2026, 4, 3, hour + 1, 0))),
+            ..Event::new(summary, EventTime::DateTimeUtc(t(2026, 4, 3, hour, 0)))
+        };
+
+        cal.create_event(timed("Busy", 10)).unwrap();
+        cal.create_event(Event {
+            availability: Availability::Free,
+            ..timed("Free", 12)
+        })
+        .unwrap();
+        cal.create_event(Event {
+            status: Status::Cancelled,
+            ..timed("Cancelled", 14)
+        })
+        .unwrap();
+
+        let busy = cal
+            .busy_periods(t(2026, 4, 3, 0, 0), t(2026, 4, 4, 0, 0))
+            .unwrap();
+
+        assert_eq!(
+            busy,
+            [TimePeriod {
+                start: t(2026, 4, 3, 10, 0),
+                end: t(2026, 4, 3, 11, 0),
+            }]
+        );
+    }
 }
@@ -1,10 +1,23 @@
 mod error;
+mod layout;
+mod pattern;
+mod pull_filter;
+mod pull_rule;
+mod storage_format;
 
+use crate::Reminder;
 use crate::remote::RemoteConfig;
+use crate::rpc::Notify;
+use crate::utils::{ConfigKeyError, get_key, set_key};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 pub(crate) use error::CalendarConfigError;
+pub use layout::Layout;
+pub(crate) use layout::event_files;
+pub use pull_filter::PullFilter;
+pub use pull_rule::PullRule;
+pub use storage_format::StorageFormat;
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct CalendarConfig {
@@ -12,6 +25,42 @@ pub struct CalendarConfig {
     color: Option<String>,
     read_only: Option<bool>,
 
+    /// Left out of syncing and event listings, but kept on disk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived: Option<bool>,
+
+    /// Overrides the global `default_reminders` for events created in this calendar
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_reminders: Option<Vec<Reminder>>,
+
+    /// Which attendees `push` and `sync` email when `--notify` isn't given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notify: Option<Notify>,
+
+    /// Remote events that `pull` leaves out of this calendar
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pull_filter: Option<PullFilter>,
+
+    /// Changes `pull` makes to matching remote events
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pull_rules: Vec<PullRule>,
+
+    /// How events are written to disk (`ics` unless set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<StorageFormat>,
+
+    /// Where event files go in the directory (`flat` unless set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layout: Option<Layout>,
+
+    /// Write a recurring event's overrides into its master's file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    single_file_series: Option<bool>,
+
+    /// Give event files renamed by hand their usual name back on pull
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restore_file_names: Option<bool>,
+
     #[serde(rename = "remote")]
     remote_config: Option<RemoteConfig>,
 }
@@ -27,6 +76,15 @@ impl CalendarConfig {
             name,
             color,
             read_only,
+            archived: None,
+            default_reminders: None,
+            notify: None,
+            pull_filter: None,
+            pull_rules: Vec::new(),
+            storage: None,
+            layout: None,
+            single_file_series: None,
+            restore_file_names: None,
             remote_config,
         }
     }
@@ -77,6 +135,61 @@ impl CalendarConfig {
         self.read_only
     }
 
+    pub fn archived(&self) -> bool {
+        self.archived.unwrap_or(false)
+    }
+
+    pub fn set_archived(&mut self, archived: bool) {
+        self.archived = archived.then_some(true);
+    }
+
+    pub fn default_reminders(&self) -> Option<Vec<Reminder>> {
+        self.default_reminders.clone()
+    }
+
+    pub fn set_default_reminders(&mut self, reminders: Option<Vec<Reminder>>) {
+        self.default_reminders = reminders;
+    }
+
+    pub fn notify(&self) -> Option<Notify> {
+        self.notify
+    }
+
+    /// Read a single value by key (e.g. `remote.google_calendar_id`).
+    pub fn get(&self, key: &str) -> Result<Option<toml::Value>, ConfigKeyError> {
+        get_key(self, key)
+    }
+
+    /// Set a single value by key, validated against the config schema.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), ConfigKeyError> {
+        *self = set_key(self, key, value)?;
+        Ok(())
+    }
+
+    pub fn storage(&self) -> StorageFormat {
+        self.storage.unwrap_or_default()
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout.unwrap_or_default()
+    }
+
+    pub fn single_file_series(&self) -> bool {
+        self.single_file_series.unwrap_or(false)
+    }
+
+    pub fn restore_file_names(&self) -> bool {
+        self.restore_file_names.unwrap_or(false)
+    }
+
+    pub fn pull_filter(&self) -> Option<&PullFilter> {
+        self.pull_filter.as_ref()
+    }
+
+    pub fn pull_rules(&self) -> &[PullRule] {
+        &self.pull_rules
+    }
+
     fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
         toml::from_str(s)
     }
@@ -89,6 +202,10 @@ impl CalendarConfig {
         self.remote_config.as_ref()
     }
 
+    pub(crate) fn remove_remote(&mut self) {
+        self.remote_config = None;
+    }
+
     #[cfg(test)]
     pub(crate) fn set_remote(&mut self, remote_config: RemoteConfig) {
         self.remote_config = Some(remote_config);
@@ -177,6 +294,70 @@ hooli_account = "user@hmail.com"
         );
     }
 
+    #[test]
+    fn from_toml_parses_default_reminders() {
+        let toml_str = r#"
+name = "Work"
+default_reminders = ["10m", "1d"]
+"#;
+
+        let config = CalendarConfig::from_toml(toml_str).unwrap();
+
+        assert_eq!(
+            config.default_reminders(),
+            Some(vec![
+                Reminder::from_minutes(10),
+                Reminder::from_minutes(24 * 60)
+            ])
+        );
+    }
+
+    #[test]
+    fn from_toml_parses_notify() {
+        let config = CalendarConfig::from_toml(r#"notify = "external""#).unwrap();
+
+        assert_eq!(config.notify(), Some(Notify::External));
+        assert_eq!(CalendarConfig::default().notify(), None);
+    }
+
+    #[test]
+    fn set_updates_existing_remote_param() {
+        let mut params = RemoteConfigParams::new();
+        params.insert(
+            "hooli_calendar_id".to_string(),
+            toml::Value::String("abc".to_string()),
+        );
+        let mut config = test_calendar_config();
+        config.set_remote(RemoteConfig::new(ProviderSlug::from("hooli"), params));
+
+        config.set("remote.hooli_calendar_id", "xyz").unwrap();
+        config.set("read_only", "true").unwrap();
+
+        assert_eq!(
+            config.get("remote.hooli_calendar_id").unwrap(),
+            Some(toml::Value::String("xyz".to_string()))
+        );
+        assert_eq!(config.read_only(), Some(true));
+        assert!(config.set("remote.hooli_calendar_idd", "xyz").is_err());
+    }
+
+    #[test]
+    fn from_toml_parses_pull_filter() {
+        let toml_str = r#"
+name = "Team"
+
+[pull_filter]
+skip_all_day = true
+"#;
+
+        let config = CalendarConfig::from_toml(toml_str).unwrap();
+
+        let mut all_day = crate::test_utils::test_event();
+        all_day.start =
+            crate::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        assert!(config.pull_filter().unwrap().excludes(&all_day, None));
+    }
+
     #[test]
     fn load_optional_errors_on_invalid_toml() {
         let tmp = tempfile::TempDir::new().unwrap();
@@ -0,0 +1,138 @@
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::StorageFormat;
+use crate::EventTime;
+
+/// Where in a calendar's directory its event files go.
+///
+/// Files are found in both layouts, so switching only affects where new and
+/// moved events are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+    /// All events directly in the calendar directory
+    #[default]
+    Flat,
+    /// Events under `2025/03/` by their start date
+    YearMonth,
+}
+
+impl Layout {
+    /// The directory an event starting at `start` belongs in. Like file
+    /// names, dates are local.
+    pub(crate) fn event_dir(&self, calendar_dir: &Path, start: &EventTime) -> PathBuf {
+        match self {
+            Self::Flat => calendar_dir.to_path_buf(),
+            Self::YearMonth => {
+                let date = match start {
+                    EventTime::Date(date) => *date,
+                    _ => start.to_local_tz(&Local).date_naive(),
+                };
+                calendar_dir
+                    .join(format!("{:04}", date.year()))
+                    .join(format!("{:02}", date.month()))
+            }
+        }
+    }
+
+    /// The layout the event file at `path` is stored in, and the directory of
+    /// its calendar.
+    pub fn of_path(path: &Path) -> (Self, &Path) {
+        let parent = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let year_month = parent.parent().filter(|year_dir| {
+            is_year_dir(year_dir) && parent.file_name().is_some_and(is_month_name)
+        });
+
+        match year_month.and_then(Path::parent) {
+            Some(calendar_dir) => (Self::YearMonth, calendar_dir),
+            None => (Self::Flat, parent),
+        }
+    }
+}
+
+/// The event files in `calendar_dir`, including those in `YYYY/MM`
+/// subdirectories.
+pub(crate) fn event_files(calendar_dir: &Path, storage: StorageFormat) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![calendar_dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+
+            if file_type.is_file() && storage.is_event_file(&path) {
+                files.push(path);
+            } else if file_type.is_dir() && is_layout_dir(calendar_dir, &path) {
+                dirs.push(path);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// `calendar_dir/YYYY` or `calendar_dir/YYYY/MM`
+fn is_layout_dir(calendar_dir: &Path, dir: &Path) -> bool {
+    match dir.parent() {
+        Some(parent) if parent == calendar_dir => is_year_dir(dir),
+        Some(parent) => {
+            is_year_dir(parent)
+                && parent.parent() == Some(calendar_dir)
+                && dir.file_name().is_some_and(is_month_name)
+        }
+        None => false,
+    }
+}
+
+fn is_year_dir(dir: &Path) -> bool {
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.len() == 4 && name.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn is_month_name(name: &std::ffi::OsStr) -> bool {
+    name.to_str()
+        .filter(|name| name.len() == 2)
+        .and_then(|name| name.parse::<u32>().ok())
+        .is_some_and(|month| NaiveDate::from_ymd_opt(2000, month, 1).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn year_month_dirs_come_from_the_start_date() {
+        let start = EventTime::Date(NaiveDate::from_ymd_opt(2025, 3, 14).unwrap());
+
+        let dir = Layout::YearMonth.event_dir(Path::new("/cal"), &start);
+
+        assert_eq!(dir, Path::new("/cal/2025/03"));
+        assert_eq!(
+            Layout::Flat.event_dir(Path::new("/cal"), &start),
+            Path::new("/cal")
+        );
+    }
+
+    #[test]
+    fn of_path_finds_the_calendar_dir() {
+        assert_eq!(
+            Layout::of_path(Path::new("/cal/2025/03/a.ics")),
+            (Layout::YearMonth, Path::new("/cal"))
+        );
+        assert_eq!(
+            Layout::of_path(Path::new("/cal/a.ics")),
+            (Layout::Flat, Path::new("/cal"))
+        );
+        assert_eq!(
+            Layout::of_path(Path::new("/cal/2025/notes/a.ics")),
+            (Layout::Flat, Path::new("/cal/2025/notes"))
+        );
+    }
+}
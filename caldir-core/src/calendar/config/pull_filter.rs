@@ -0,0 +1,113 @@
+use super::pattern::Pattern;
+use crate::{Event, ParticipationStatus, Status};
+use serde::{Deserialize, Serialize};
+
+/// Rules for remote events that `pull` should not bring into the calendar.
+/// Events already on disk are trashed when a pulled change makes them match.
+///
+/// ```toml
+/// [pull_filter]
+/// skip_declined = true
+/// skip_cancelled = true
+/// skip_all_day = true
+/// skip_summaries = ["^Focus time$", "(?i)birthday"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PullFilter {
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    skip_declined: bool,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    skip_cancelled: bool,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    skip_all_day: bool,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    skip_summaries: Vec<Pattern>,
+}
+
+impl PullFilter {
+    /// Whether `event` should be left out of the calendar.
+    /// `account_email` identifies "me" for `skip_declined`.
+    pub fn excludes(&self, event: &Event, account_email: Option<&str>) -> bool {
+        if self.skip_all_day && event.start.is_date()
+            || self.skip_cancelled && event.status == Status::Cancelled
+        {
+            return true;
+        }
+
+        if self.skip_declined
+            && account_email.is_some_and(|email| {
+                event.attendee_status(email) == Some(ParticipationStatus::Declined)
+            })
+        {
+            return true;
+        }
+
+        let summary = event.summary.as_deref().unwrap_or_default();
+        self.skip_summaries.iter().any(|p| p.is_match(summary))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_event;
+    use crate::{Attendee, EventTime};
+    use chrono::NaiveDate;
+
+    fn filter(toml_str: &str) -> PullFilter {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn empty_filter_excludes_nothing() {
+        assert!(!PullFilter::default().excludes(&test_event(), None));
+    }
+
+    #[test]
+    fn skips_all_day_events() {
+        let mut event = test_event();
+        event.start = EventTime::Date(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+
+        assert!(filter("skip_all_day = true").excludes(&event, None));
+    }
+
+    #[test]
+    fn skips_cancelled_events() {
+        let mut event = test_event();
+        event.status = Status::Cancelled;
+
+        assert!(filter("skip_cancelled = true").excludes(&event, None));
+        assert!(!filter("skip_cancelled = true").excludes(&test_event(), None));
+    }
+
+    #[test]
+    fn skips_events_declined_by_account() {
+        let mut attendee = Attendee::new("me@example.com");
+        attendee.status = Some(ParticipationStatus::Declined);
+        let mut event = test_event();
+        event.attendees = vec![attendee];
+
+        let filter = filter("skip_declined = true");
+
+        assert!(filter.excludes(&event, Some("me@example.com")));
+        assert!(!filter.excludes(&event, Some("someone@example.com")));
+        assert!(!filter.excludes(&event, None));
+    }
+
+    #[test]
+    fn skips_matching_summaries() {
+        let mut event = test_event();
+        event.summary = Some("Anna's Birthday".to_string());
+
+        assert!(filter(r#"skip_summaries = ["(?i)birthday"]"#).excludes(&event, None));
+        assert!(!filter(r#"skip_summaries = ["^Focus"]"#).excludes(&event, None));
+    }
+
+    #[test]
+    fn rejects_invalid_regex() {
+        assert!(toml::from_str::<PullFilter>(r#"skip_summaries = ["("]"#).is_err());
+    }
+}
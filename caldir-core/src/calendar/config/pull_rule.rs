@@ -0,0 +1,150 @@
+use super::pattern::Pattern;
+use crate::{Availability, Event};
+use serde::{Deserialize, Serialize};
+
+/// A change `pull` makes to matching remote events before they're written.
+/// Every pattern given has to match; rules run top to bottom.
+///
+/// ```toml
+/// [[pull_rules]]
+/// organizer = "@calendly\\.com$"
+/// availability = "Free"
+/// add_tags = ["booking"]
+///
+/// [[pull_rules]]
+/// summary = "^Lunch$"
+/// drop = true
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PullRule {
+    /// Matched against the event's title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<Pattern>,
+
+    /// Matched against the organizer's email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    organizer: Option<Pattern>,
+
+    /// Matched against each attendee's email, any of them can match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attendee: Option<Pattern>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    availability: Option<Availability>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    add_tags: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rename: Option<String>,
+
+    /// Leave matching events out of the calendar
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    drop: bool,
+}
+
+impl PullRule {
+    pub fn matches(&self, event: &Event) -> bool {
+        let summary = event.summary.as_deref().unwrap_or_default();
+
+        self.summary.as_ref().is_none_or(|p| p.is_match(summary))
+            && self.organizer.as_ref().is_none_or(|p| {
+                event
+                    .organizer
+                    .as_ref()
+                    .is_some_and(|o| p.is_match(&o.email))
+            })
+            && self
+                .attendee
+                .as_ref()
+                .is_none_or(|p| event.attendees.iter().any(|a| p.is_match(&a.email)))
+    }
+
+    /// Whether matching events are left out instead of changed.
+    pub fn drops(&self, event: &Event) -> bool {
+        self.drop && self.matches(event)
+    }
+
+    /// Set the rule's availability, tags and title on `event` if it matches.
+    pub fn apply(&self, event: &mut Event) {
+        if self.drop || !self.matches(event) {
+            return;
+        }
+
+        if let Some(availability) = self.availability {
+            event.availability = availability;
+        }
+        for tag in &self.add_tags {
+            if !event.has_category(tag) {
+                event.categories.push(tag.clone());
+            }
+        }
+        if let Some(rename) = &self.rename {
+            event.summary = Some(rename.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Organizer;
+    use crate::test_utils::test_event;
+
+    fn rule(toml_str: &str) -> PullRule {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    fn booking() -> Event {
+        let mut event = test_event();
+        event.summary = Some("Intro call".to_string());
+        event.organizer = Some(Organizer::new("bookings@calendly.com"));
+        event
+    }
+
+    #[test]
+    fn applies_to_events_matching_every_pattern() {
+        let rule = rule(
+            r#"
+            organizer = "@calendly\\.com$"
+            summary = "(?i)intro"
+            availability = "Free"
+            add_tags = ["booking"]
+            rename = "Booked call"
+            "#,
+        );
+
+        let mut event = booking();
+        rule.apply(&mut event);
+        assert_eq!(event.availability, Availability::Free);
+        assert_eq!(event.categories, vec!["booking"]);
+        assert_eq!(event.summary.as_deref(), Some("Booked call"));
+
+        // Tags aren't added twice
+        rule.apply(&mut event);
+        assert_eq!(event.categories, vec!["booking"]);
+
+        let untouched = test_event();
+        let mut other = untouched.clone();
+        rule.apply(&mut other);
+        assert_eq!(other, untouched);
+    }
+
+    #[test]
+    fn drop_rules_leave_events_unchanged() {
+        let rule = rule("summary = \"^Intro\"\ndrop = true\nrename = \"Ignored\"");
+        let event = booking();
+        let mut applied = event.clone();
+
+        assert!(rule.drops(&event));
+        rule.apply(&mut applied);
+        assert_eq!(applied, event);
+        assert!(!rule.drops(&test_event()));
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(toml::from_str::<PullRule>("title = \"x\"").is_err());
+    }
+}
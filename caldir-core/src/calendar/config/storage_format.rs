@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::Event;
+
+/// How a calendar's events are written to disk.
+///
+/// `.ics` files are always read, so a calendar switched to `markdown` keeps
+/// its existing events; new and rewritten-by-pull ones become `.md` files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageFormat {
+    #[default]
+    Ics,
+    /// Markdown with YAML frontmatter, the description as the body
+    Markdown,
+}
+
+impl StorageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Ics => "ics",
+            Self::Markdown => "md",
+        }
+    }
+
+    /// The format of the event file at `path`, by its extension.
+    pub(crate) fn of_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ics" => Some(Self::Ics),
+            "md" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+
+    /// Whether `path` holds an event in a calendar stored as `self`.
+    /// Other Markdown files (e.g. a README) are left alone in `.ics` calendars.
+    pub(crate) fn is_event_file(&self, path: &Path) -> bool {
+        match Self::of_path(path) {
+            Some(Self::Ics) => true,
+            Some(Self::Markdown) => *self == Self::Markdown,
+            None => false,
+        }
+    }
+
+    pub(crate) fn write(&self, event: &Event) -> String {
+        match self {
+            Self::Ics => event.to_ics_string(),
+            Self::Markdown => event.to_markdown_string(),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Several files holding the same event (same UID and recurrence id), e.g.
+//! after copying a file by hand. Sync can't tell which one is the event, so
+//! they're repaired first.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use chrono::Utc;
+
+use super::{CalendarError, CalendarEvent};
+use crate::event::new_uid;
+use crate::{Event, EventType};
+
+/// How to repair a set of duplicate files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateRepair {
+    /// Combine them into the newest file: its fields win, the others fill in
+    /// what it's missing.
+    Merge,
+    /// Keep the newest file and delete the others
+    KeepNewest,
+    /// Keep them all as separate events, giving all but the newest a new UID
+    NewUids,
+}
+
+/// Sets of files holding the same event, newest first within each set.
+pub(crate) fn duplicate_sets(events: Vec<CalendarEvent>) -> Vec<Vec<CalendarEvent>> {
+    let mut by_id: HashMap<_, Vec<CalendarEvent>> = HashMap::new();
+    for calendar_event in events {
+        by_id
+            .entry(calendar_event.event().event_instance_id())
+            .or_default()
+            .push(calendar_event);
+    }
+
+    let mut sets: Vec<Vec<CalendarEvent>> =
+        by_id.into_values().filter(|set| set.len() > 1).collect();
+
+    for set in &mut sets {
+        set.sort_by_key(|ce| {
+            Reverse((
+                ce.event().last_modified,
+                ce.modified_at(),
+                ce.path().to_path_buf(),
+            ))
+        });
+    }
+    sets.sort_by(|a, b| a[0].path().cmp(b[0].path()));
+    sets
+}
+
+pub(crate) fn repair(set: Vec<CalendarEvent>, how: DuplicateRepair) -> Result<(), CalendarError> {
+    let mut set = set.into_iter();
+    let Some(mut newest) = set.next() else {
+        return Ok(());
+    };
+    let others: Vec<CalendarEvent> = set.collect();
+
+    match how {
+        DuplicateRepair::Merge => {
+            let mut merged = others.iter().fold(newest.event().clone(), |merged, other| {
+                merge(merged, other.event())
+            });
+            merged.last_modified = Some(Utc::now());
+            newest.update(merged)?;
+
+            for other in others {
+                other.delete()?;
+            }
+        }
+        DuplicateRepair::KeepNewest => {
+            for other in others {
+                other.delete()?;
+            }
+        }
+        DuplicateRepair::NewUids => {
+            for mut other in others {
+                let mut event = other.event().clone();
+                event.uid = new_uid();
+                event.last_modified = Some(Utc::now());
+                other.update(event)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `into` with what it's missing taken from `other`.
+fn merge(mut into: Event, other: &Event) -> Event {
+    fill(&mut into.summary, &other.summary);
+    fill(&mut into.description, &other.description);
+    fill(&mut into.location, &other.location);
+    fill(&mut into.end, &other.end);
+    fill(&mut into.visibility, &other.visibility);
+    if into.event_type == EventType::Default {
+        into.event_type = other.event_type;
+    }
+    fill(&mut into.recurrence, &other.recurrence);
+    fill(&mut into.organizer, &other.organizer);
+    fill(&mut into.url, &other.url);
+    fill(&mut into.color, &other.color);
+
+    union_by(&mut into.attendees, &other.attendees, |a| {
+        a.email.to_lowercase()
+    });
+    union_by(&mut into.reminders, &other.reminders, |r| *r);
+    into.reminders_default |= other.reminders_default;
+    union_by(&mut into.categories, &other.categories, Clone::clone);
+    union_by(&mut into.conferences, &other.conferences, |c| c.uri.clone());
+    union_by(&mut into.attachments, &other.attachments, |a| a.uri.clone());
+    union_by(&mut into.x_properties, &other.x_properties, |x| {
+        x.name.clone()
+    });
+
+    into.sequence = into.sequence.max(other.sequence);
+    into
+}
+
+fn fill<T: Clone>(value: &mut Option<T>, other: &Option<T>) {
+    if value.is_none() {
+        value.clone_from(other);
+    }
+}
+
+fn union_by<T: Clone, K: PartialEq>(items: &mut Vec<T>, others: &[T], key: impl Fn(&T) -> K) {
+    for other in others {
+        if !items.iter().any(|item| key(item) == key(other)) {
+            items.push(other.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Attendee;
+    use crate::test_utils::{test_calendar, test_event};
+    use chrono::TimeZone;
+
+    /// Two files for `event`, the second one newer with `newer` applied.
+    fn duplicate_files(newer: impl FnOnce(&mut Event)) -> (tempfile::TempDir, crate::Calendar) {
+        let (tmp, calendar) = test_calendar();
+        let mut event = test_event();
+        event.last_modified = Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap());
+        calendar.create_event(event.clone()).unwrap();
+
+        event.last_modified = Some(Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+        newer(&mut event);
+        calendar.create_event(event).unwrap();
+
+        (tmp, calendar)
+    }
+
+    #[test]
+    fn finds_sets_newest_first() {
+        let (_tmp, calendar) = duplicate_files(|event| {
+            event.location = Some("Room 2".to_string());
+        });
+
+        let sets = duplicate_sets(calendar.events().unwrap());
+
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0][0].event().location.as_deref(), Some("Room 2"));
+    }
+
+    #[test]
+    fn merge_keeps_the_newest_fields_and_fills_in_the_rest() {
+        let (_tmp, calendar) = duplicate_files(|event| {
+            event.location = Some("Room 2".to_string());
+            event.attendees = vec![Attendee::new("ann@example.com")];
+        });
+        let mut older = calendar.events().unwrap();
+        older.sort_by_key(|ce| ce.event().last_modified);
+        let mut event = older[0].event().clone();
+        event.description = Some("Agenda".to_string());
+        event.attendees = vec![Attendee::new("bob@example.com")];
+        older.remove(0).update(event).unwrap();
+
+        assert_eq!(
+            calendar.repair_duplicates(DuplicateRepair::Merge).unwrap(),
+            1
+        );
+
+        let events = calendar.events().unwrap();
+        assert_eq!(events.len(), 1);
+        let merged = events[0].event();
+        assert_eq!(merged.location.as_deref(), Some("Room 2"));
+        assert_eq!(merged.description.as_deref(), Some("Agenda"));
+        assert_eq!(merged.attendees.len(), 2);
+    }
+
+    #[test]
+    fn new_uids_keeps_every_file_as_its_own_event() {
+        let (_tmp, calendar) = duplicate_files(|_| {});
+
+        calendar
+            .repair_duplicates(DuplicateRepair::NewUids)
+            .unwrap();
+
+        let events = calendar.events().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_ne!(events[0].event().uid, events[1].event().uid);
+    }
+}
@@ -29,4 +29,14 @@ pub enum CalendarError {
 
     #[error("event {0} is not recurring")]
     NotRecurring(String),
+
+    #[error("no trashed event {0}")]
+    NotInTrash(String),
+
+    #[error("event {uid} is saved in {} files", files.len())]
+    DuplicateEvent { uid: String, files: Vec<PathBuf> },
+
+    #[cfg(feature = "index")]
+    #[error("event index error: {0}")]
+    Index(#[from] rusqlite::Error),
 }
@@ -1,6 +1,7 @@
 mod error;
 
-use crate::{Calendar, Event, EventTime, ParticipationStatus};
+use crate::event::EventError;
+use crate::{Calendar, Event, EventTime, Layout, ParticipationStatus, StorageFormat};
 use std::fs::OpenOptions;
 use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
@@ -17,15 +18,32 @@ pub struct CalendarEvent {
 impl CalendarEvent {
     pub fn create(calendar: &Calendar, event: Event) -> Result<Self, CalendarEventError> {
         let base_slug = event.base_slug();
-        let contents = event.to_ics_string();
+        let format = calendar.storage_format();
+        let contents = format.write(&event);
+        let dir = calendar.layout().event_dir(calendar.path(), &event.start);
+        std::fs::create_dir_all(&dir)?;
 
-        let path = write_best_event_file(calendar.path(), &base_slug, None, contents.as_bytes())?;
+        let path = write_best_event_file(&dir, &base_slug, format, None, contents.as_bytes())?;
         sync_file_mtime(&path, event.last_modified)?;
 
         Ok(CalendarEvent { event, path })
     }
 
+    /// An event already parsed from the file at `path`.
+    pub(crate) fn from_parsed(event: Event, path: PathBuf) -> Self {
+        CalendarEvent { event, path }
+    }
+
+    /// The event in the file at `path`. For a file holding a whole recurring
+    /// series, that's its master.
     pub fn load(path: impl Into<PathBuf>) -> Result<Self, CalendarEventError> {
+        let mut events = Self::load_all(path)?;
+        Ok(events.remove(0))
+    }
+
+    /// Every event in the file at `path`: one, or a recurring master and its
+    /// overrides, master first.
+    pub fn load_all(path: impl Into<PathBuf>) -> Result<Vec<Self>, CalendarEventError> {
         let path = path.into();
 
         if !path.is_file() {
@@ -34,32 +52,71 @@ impl CalendarEvent {
 
         let contents = std::fs::read_to_string(&path)?;
 
+        if StorageFormat::of_path(&path) == Some(StorageFormat::Markdown) {
+            let event = Event::from_markdown_str(&contents)
+                .map_err(|err| CalendarEventError::InvalidEvent(path.clone(), err))?;
+            return Ok(vec![CalendarEvent { event, path }]);
+        }
+
         let events = Event::from_ics_str(&contents)
+            .map_err(|err| match err {
+                EventError::InvalidIcs(_, ref message) => CalendarEventError::ParseError {
+                    file: path.clone(),
+                    line: err.ics_line(),
+                    message: message.clone(),
+                },
+                err => CalendarEventError::InvalidEvent(path.clone(), err),
+            })?
+            .into_iter()
+            .collect::<Result<Vec<Event>, _>>()
             .map_err(|err| CalendarEventError::InvalidEvent(path.clone(), err))?;
 
-        let event = match <[Result<Event, _>; 1]>::try_from(events) {
-            Ok([result]) => {
-                result.map_err(|err| CalendarEventError::InvalidEvent(path.clone(), err))?
-            }
-            Err(events) => {
-                return Err(CalendarEventError::ExpectedSingleEvent {
-                    path,
-                    found: events.len(),
-                });
-            }
-        };
+        let one_series = events.windows(2).all(|pair| pair[0].uid == pair[1].uid);
+        if events.is_empty() || !one_series {
+            return Err(CalendarEventError::ExpectedSingleEvent {
+                path,
+                found: events.len(),
+            });
+        }
 
-        Ok(CalendarEvent { event, path })
+        Ok(series_order(events)
+            .into_iter()
+            .map(|event| CalendarEvent {
+                event,
+                path: path.clone(),
+            })
+            .collect())
     }
 
+    /// Rewrite the event in the format and layout its file already has,
+    /// moving it to another month's directory if its start date changed.
+    /// Files holding a whole series are rewritten with the other events
+    /// kept, and named after the master.
     pub fn update(&mut self, event: Event) -> Result<(), CalendarEventError> {
-        let base_slug = event.base_slug();
-        let contents = event.to_ics_string();
-        let dir = self.path.parent().unwrap_or_else(|| Path::new("."));
-
-        let new_path =
-            write_best_event_file(dir, &base_slug, Some(&self.path), contents.as_bytes())?;
-        sync_file_mtime(&new_path, event.last_modified)?;
+        let mut events = self.others_in_file();
+        if events.first().is_some_and(|other| other.uid != event.uid) {
+            return self.split_off(event, events);
+        }
+        events.push(event.clone());
+        let events = series_order(events);
+        let main = &events[0];
+
+        let format = self.storage_format();
+        let contents = match events.as_slice() {
+            [only] => format.write(only),
+            series => Event::series_to_ics_string(series),
+        };
+        let dir = self.dir_for(main);
+        std::fs::create_dir_all(&dir)?;
+
+        let new_path = write_best_event_file(
+            &dir,
+            &main.base_slug(),
+            format,
+            Some(&self.path),
+            contents.as_bytes(),
+        )?;
+        sync_file_mtime(&new_path, latest_modified(&events))?;
 
         if new_path == self.path {
             self.event = event;
@@ -71,14 +128,108 @@ impl CalendarEvent {
             return Err(err.into());
         }
 
+        remove_empty_layout_dirs(&self.path);
+
         self.event = event;
         self.path = new_path;
 
         Ok(())
     }
 
+    /// Add `event` to the series file at `path`, e.g. an override next to
+    /// its master.
+    pub(crate) fn add_to_file(path: PathBuf, event: Event) -> Result<Self, CalendarEventError> {
+        let mut events: Vec<Event> = Self::load_all(&path)?
+            .into_iter()
+            .map(|calendar_event| calendar_event.event)
+            .collect();
+        events.push(event.clone());
+
+        rewrite_file(&path, series_order(events))?;
+
+        Ok(CalendarEvent { event, path })
+    }
+
+    /// The other events in this event's file, if it holds a whole series.
+    /// A file that's gone or doesn't parse is simply written anew.
+    fn others_in_file(&self) -> Vec<Event> {
+        if self.storage_format() == StorageFormat::Markdown {
+            return Vec::new();
+        }
+        let Ok(calendar_events) = Self::load_all(&self.path) else {
+            return Vec::new();
+        };
+
+        let mut events: Vec<Event> = calendar_events
+            .into_iter()
+            .map(|calendar_event| calendar_event.event)
+            .collect();
+        let id = self.event.event_instance_id();
+        let own = events
+            .iter()
+            .position(|event| *event == self.event)
+            .or_else(|| {
+                events
+                    .iter()
+                    .position(|event| event.event_instance_id() == id)
+            });
+        if let Some(own) = own {
+            events.remove(own);
+        }
+
+        events
+    }
+
+    /// Whether other events share this event's file.
+    pub(crate) fn shares_file(&self) -> bool {
+        !self.others_in_file().is_empty()
+    }
+
+    /// Give `event` a file of its own, e.g. when it got a new UID and so
+    /// isn't part of the series it was in anymore.
+    fn split_off(&mut self, event: Event, others: Vec<Event>) -> Result<(), CalendarEventError> {
+        let format = self.storage_format();
+        let dir = self.dir_for(&event);
+        std::fs::create_dir_all(&dir)?;
+
+        let path = write_best_event_file(
+            &dir,
+            &event.base_slug(),
+            format,
+            None,
+            format.write(&event).as_bytes(),
+        )?;
+        sync_file_mtime(&path, event.last_modified)?;
+        rewrite_file(&self.path, others)?;
+
+        self.event = event;
+        self.path = path;
+
+        Ok(())
+    }
+
+    /// Point at the file this event's series was moved to.
+    pub(crate) fn moved_to(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    /// The directory this event's file would be in after changing to `event`.
+    pub(crate) fn dir_for(&self, event: &Event) -> PathBuf {
+        let (layout, calendar_dir) = Layout::of_path(&self.path);
+        layout.event_dir(calendar_dir, &event.start)
+    }
+
+    /// Delete the event's file, or only the event, if it shares its file
+    /// with the rest of its series.
     pub fn delete(self) -> Result<(), CalendarEventError> {
-        std::fs::remove_file(self.path).map_err(Into::into)
+        let others = self.others_in_file();
+        if !others.is_empty() {
+            return rewrite_file(&self.path, others);
+        }
+
+        std::fs::remove_file(&self.path)?;
+        remove_empty_layout_dirs(&self.path);
+        Ok(())
     }
 
     pub fn event(&self) -> &Event {
@@ -89,6 +240,10 @@ impl CalendarEvent {
         &self.path
     }
 
+    pub fn storage_format(&self) -> StorageFormat {
+        StorageFormat::of_path(&self.path).unwrap_or_default()
+    }
+
     pub fn filename(&self) -> Option<&str> {
         self.path.file_name().and_then(|name| name.to_str())
     }
@@ -145,6 +300,46 @@ impl CalendarEvent {
     }
 }
 
+/// Master first, then its overrides by the occurrence they replace.
+fn series_order(mut events: Vec<Event>) -> Vec<Event> {
+    events.sort_by_key(|event| {
+        (
+            event.recurrence.is_none(),
+            event
+                .recurrence_id
+                .as_ref()
+                .map(|id| id.as_event_time().to_utc()),
+        )
+    });
+    events
+}
+
+/// The newest LAST-MODIFIED in a file, so no event in it looks older than
+/// it is.
+fn latest_modified(events: &[Event]) -> Option<DateTime<Utc>> {
+    events.iter().filter_map(|event| event.last_modified).max()
+}
+
+/// Write `events` (in series order) to the `.ics` file at `path`, in place.
+fn rewrite_file(path: &Path, events: Vec<Event>) -> Result<(), CalendarEventError> {
+    std::fs::write(path, Event::series_to_ics_string(&events))?;
+    sync_file_mtime(path, latest_modified(&events))
+}
+
+/// Clean up the month and year directories a removed file at `path` leaves
+/// empty. Removing a directory that isn't empty fails, which is fine.
+fn remove_empty_layout_dirs(path: &Path) {
+    let (Layout::YearMonth, _) = Layout::of_path(path) else {
+        return;
+    };
+
+    for dir in path.ancestors().skip(1).take(2) {
+        if std::fs::remove_dir(dir).is_err() {
+            return;
+        }
+    }
+}
+
 // Pin the file mtime to the event's LAST-MODIFIED so direction detection
 // reflects when the event was changed, not when bytes hit disk. Without this,
 // every pull leaves the file appearing newer than its remote counterpart —
@@ -162,21 +357,48 @@ fn sync_file_mtime(
     Ok(())
 }
 
+fn event_file_name(base_slug: &str, format: StorageFormat, suffix: usize) -> String {
+    let extension = format.extension();
+    if suffix == 1 {
+        format!("{base_slug}.{extension}")
+    } else {
+        format!("{base_slug}-{suffix}.{extension}")
+    }
+}
+
+/// The path `write_best_event_file` would pick, if the paths `is_taken`
+/// returns true for were the ones in use.
+pub(crate) fn best_event_path(
+    calendar_dir: &Path,
+    base_slug: &str,
+    format: StorageFormat,
+    current_path: Option<&Path>,
+    is_taken: impl Fn(&Path) -> bool,
+) -> PathBuf {
+    let mut suffix = 1;
+
+    loop {
+        let path = calendar_dir.join(event_file_name(base_slug, format, suffix));
+
+        if current_path == Some(path.as_path()) || !is_taken(&path) {
+            return path;
+        }
+
+        suffix += 1;
+    }
+}
+
 fn write_best_event_file(
     calendar_dir: &Path,
     base_slug: &str,
+    format: StorageFormat,
     current_path: Option<&Path>,
     contents: &[u8],
 ) -> Result<PathBuf, CalendarEventError> {
     let mut suffix = 1;
 
     loop {
-        let filename = if suffix == 1 {
-            format!("{base_slug}.ics")
-        } else {
-            format!("{base_slug}-{suffix}.ics")
-        };
-        let path = calendar_dir.join(filename);
+        let path = calendar_dir.join(event_file_name(base_slug, format, suffix));
 
         if current_path == Some(path.as_path()) {
             std::fs::write(&path, contents)?;
@@ -289,7 +511,9 @@ mod tests {
 
         let err = CalendarEvent::load(path).unwrap_err();
 
-        assert!(matches!(err, CalendarEventError::InvalidEvent(p, _) if p.ends_with("test.ics")));
+        assert!(
+            matches!(err, CalendarEventError::ParseError { file, .. } if file.ends_with("test.ics"))
+        );
     }
 
     #[test]
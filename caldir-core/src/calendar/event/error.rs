@@ -7,7 +7,19 @@ pub enum CalendarEventError {
     #[error("invalid event in ICS file {0}: {1}")]
     InvalidEvent(PathBuf, EventError),
 
-    #[error("expected exactly one event in {path}, found {found}")]
+    #[error(
+        "failed to parse {}{}: {message}",
+        .file.display(),
+        .line.map(|line| format!(":{line}")).unwrap_or_default()
+    )]
+    ParseError {
+        file: PathBuf,
+        /// Where the parser gave up, counting folded lines as one
+        line: Option<usize>,
+        message: String,
+    },
+
+    #[error("expected one event or one recurring series in {path}, found {found} events")]
     ExpectedSingleEvent { path: PathBuf, found: usize },
 
     #[error("io error: {0}")]
@@ -0,0 +1,47 @@
+use std::fs::Metadata;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Changes whenever an event file is rewritten, for caches of its contents.
+///
+/// The mtime alone isn't enough: caldir pins it to the event's LAST-MODIFIED,
+/// which some providers don't bump. The inode change time (on Unix) can't be
+/// set, so it catches those rewrites.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FileStamp {
+    pub(crate) modified: i64,
+    pub(crate) changed: i64,
+    pub(crate) size: i64,
+}
+
+impl FileStamp {
+    pub(crate) fn of(metadata: &Metadata) -> std::io::Result<Self> {
+        Ok(Self {
+            modified: nanos_since_epoch(metadata.modified()?),
+            changed: changed(metadata),
+            size: metadata.len() as i64,
+        })
+    }
+}
+
+fn nanos_since_epoch(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as i64
+}
+
+#[cfg(unix)]
+fn changed(metadata: &Metadata) -> i64 {
+    use std::os::unix::fs::MetadataExt;
+
+    metadata
+        .ctime()
+        .saturating_mul(1_000_000_000)
+        .saturating_add(metadata.ctime_nsec())
+}
+
+#[cfg(not(unix))]
+fn changed(_metadata: &Metadata) -> i64 {
+    0
+}
@@ -0,0 +1,253 @@
+//! SQLite index of a calendar's events by time, so range queries only parse
+//! the files that can match. Kept in `.caldir/state/index.sqlite` and brought
+//! up to date from file stamps (mtime, ctime, size) before each query.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+
+use super::config::event_files;
+use super::{CalendarError, CalendarEvent, FileStamp, StorageFormat};
+
+const INDEX_FILE_NAME: &str = "index.sqlite";
+
+/// Bumped when the table changes; older indexes are rebuilt from scratch.
+const SCHEMA_VERSION: i32 = 1;
+
+/// Rows are keyed by file rather than UID: a recurring event's overrides
+/// share its UID.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS events (
+        file TEXT PRIMARY KEY,
+        uid TEXT NOT NULL,
+        summary TEXT,
+        starts_at INTEGER NOT NULL,
+        ends_at INTEGER NOT NULL,
+        recurring INTEGER NOT NULL,
+        modified INTEGER NOT NULL,
+        changed INTEGER NOT NULL,
+        size INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS events_by_time ON events (starts_at, ends_at);
+";
+
+pub(crate) struct EventIndex {
+    conn: Connection,
+}
+
+impl EventIndex {
+    pub(crate) fn open(state_dir: &Path) -> Result<Self, CalendarError> {
+        std::fs::create_dir_all(state_dir)?;
+
+        let conn = Connection::open(state_dir.join(INDEX_FILE_NAME))?;
+        // Another caldir may be refreshing the same index
+        conn.busy_timeout(Duration::from_secs(5))?;
+
+        let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version != SCHEMA_VERSION {
+            conn.execute_batch("DROP TABLE IF EXISTS events")?;
+            conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        }
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Re-index the event files in `dir` that were added or changed since
+    /// the last refresh, and forget the ones that are gone. Files that don't
+    /// parse are skipped.
+    pub(crate) fn refresh(
+        &mut self,
+        dir: &Path,
+        storage: StorageFormat,
+    ) -> Result<(), CalendarError> {
+        let tx = self.conn.transaction()?;
+
+        let mut indexed: HashMap<String, FileStamp> = {
+            let mut stmt = tx.prepare("SELECT file, modified, changed, size FROM events")?;
+            let rows = stmt.query_map([], |row| {
+                let stamp = FileStamp {
+                    modified: row.get(1)?,
+                    changed: row.get(2)?,
+                    size: row.get(3)?,
+                };
+                Ok((row.get(0)?, stamp))
+            })?;
+            rows.collect::<Result<_, _>>()?
+        };
+
+        for path in event_files(dir, storage)? {
+            // Relative to `dir`, so files in year/month directories keep theirs
+            let Some(file) = path
+                .strip_prefix(dir)
+                .ok()
+                .and_then(|relative| relative.to_str())
+                .map(str::to_string)
+            else {
+                continue;
+            };
+
+            let stamp = FileStamp::of(&std::fs::metadata(&path)?)?;
+            if indexed.remove(&file) == Some(stamp) {
+                continue;
+            }
+
+            // A file holding a whole series is indexed as its master. One that
+            // doesn't parse is left out rather than failing every query, and
+            // tried again on the next refresh.
+            let Ok(calendar_event) = CalendarEvent::load(path) else {
+                tx.execute("DELETE FROM events WHERE file = ?1", [&file])?;
+                continue;
+            };
+            let event = calendar_event.event();
+            let start = event.start.to_utc().timestamp();
+            let end = event
+                .end
+                .as_ref()
+                .map_or(start, |end| end.to_utc().timestamp());
+
+            tx.execute(
+                "INSERT OR REPLACE INTO events
+                 (file, uid, summary, starts_at, ends_at, recurring, modified, changed, size)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    file,
+                    event.uid.as_str(),
+                    event.summary,
+                    start,
+                    end,
+                    event.recurrence.is_some() || event.recurrence_id.is_some(),
+                    stamp.modified,
+                    stamp.changed,
+                    stamp.size,
+                ],
+            )?;
+        }
+
+        for file in indexed.keys() {
+            tx.execute("DELETE FROM events WHERE file = ?1", [file])?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Files in `dir` holding events that may occur between `from` and `to`.
+    /// Recurring events and their overrides are always included.
+    pub(crate) fn files_in_range(
+        &self,
+        dir: &Path,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PathBuf>, CalendarError> {
+        // All-day and floating times are indexed in the local timezone of the
+        // time, which may not be today's. Results get filtered exactly later.
+        let slack = chrono::Duration::days(1);
+
+        let mut stmt = self.conn.prepare(
+            "SELECT file FROM events WHERE recurring OR (starts_at < ?2 AND ends_at >= ?1)",
+        )?;
+        let files = stmt.query_map(
+            params![(from - slack).timestamp(), (to + slack).timestamp()],
+            |row| row.get::<_, String>(0),
+        )?;
+
+        Ok(files
+            .map(|file| file.map(|file| dir.join(file)))
+            .collect::<Result<_, _>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_calendar;
+    use crate::{Event, EventTime};
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap()
+    }
+
+    fn index_files(index: &EventIndex, dir: &Path, from: DateTime<Utc>) -> Vec<String> {
+        let mut files: Vec<_> = index
+            .files_in_range(dir, from, from + chrono::Duration::days(1))
+            .unwrap()
+            .into_iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn finds_only_files_near_the_range() {
+        let (tmp, cal) = test_calendar();
+        let jan = cal
+            .create_event(Event::new(
+                "January",
+                EventTime::DateTimeUtc(utc(2026, 1, 10)),
+            ))
+            .unwrap();
+        cal.create_event(Event::new("June", EventTime::DateTimeUtc(utc(2026, 6, 10))))
+            .unwrap();
+
+        let mut index = EventIndex::open(&tmp.path().join("state")).unwrap();
+        index.refresh(cal.path(), cal.storage_format()).unwrap();
+
+        let jan_file = jan
+            .path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        assert_eq!(
+            index_files(&index, cal.path(), utc(2026, 1, 10)),
+            [jan_file]
+        );
+    }
+
+    #[test]
+    fn refresh_forgets_deleted_files() {
+        let (tmp, cal) = test_calendar();
+        let event = cal
+            .create_event(Event::new(
+                "January",
+                EventTime::DateTimeUtc(utc(2026, 1, 10)),
+            ))
+            .unwrap();
+
+        let mut index = EventIndex::open(&tmp.path().join("state")).unwrap();
+        index.refresh(cal.path(), cal.storage_format()).unwrap();
+        std::fs::remove_file(event.path()).unwrap();
+        index.refresh(cal.path(), cal.storage_format()).unwrap();
+
+        assert!(index_files(&index, cal.path(), utc(2026, 1, 10)).is_empty());
+    }
+
+    #[test]
+    fn refresh_skips_files_that_do_not_parse() {
+        let (tmp, cal) = test_calendar();
+        let event = cal
+            .create_event(Event::new(
+                "January",
+                EventTime::DateTimeUtc(utc(2026, 1, 10)),
+            ))
+            .unwrap();
+        std::fs::write(cal.path().join("broken.ics"), "not a calendar").unwrap();
+
+        let mut index = EventIndex::open(&tmp.path().join("state")).unwrap();
+        index.refresh(cal.path(), cal.storage_format()).unwrap();
+
+        let file = event
+            .path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        assert_eq!(index_files(&index, cal.path(), utc(2026, 1, 10)), [file]);
+    }
+}
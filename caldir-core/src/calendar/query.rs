@@ -0,0 +1,270 @@
+use chrono::{DateTime, Utc};
+
+use crate::{Calendar, Event, ParticipationStatus, Status};
+
+/// Filters for [`Calendar::query`](crate::Calendar::query) and
+/// [`Caldir::query`](crate::Caldir::query).
+///
+/// Recurring events are expanded into the occurrences within the range.
+/// Results are sorted by start time, all-day events before timed ones
+/// starting at the same moment.
+#[derive(Debug, Clone)]
+pub struct EventQuery {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    text: Option<String>,
+    attendee: Option<String>,
+    /// Leave out events this attendee declined
+    declined_by: Option<String>,
+    /// `None` matches everything but cancelled events
+    statuses: Option<Vec<Status>>,
+    tags: Vec<String>,
+    calendars: Vec<String>,
+    descending: bool,
+    limit: Option<usize>,
+}
+
+impl EventQuery {
+    /// Events occurring between `from` and `to`.
+    pub fn between(from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        Self {
+            from,
+            to,
+            text: None,
+            attendee: None,
+            declined_by: None,
+            statuses: None,
+            tags: Vec::new(),
+            calendars: Vec::new(),
+            descending: false,
+            limit: None,
+        }
+    }
+
+    /// Case-insensitive match on summary, description or location.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into().to_lowercase());
+        self
+    }
+
+    /// Events `email` attends or organizes.
+    pub fn attendee(mut self, email: impl Into<String>) -> Self {
+        self.attendee = Some(email.into());
+        self
+    }
+
+    /// Leave out events `email` has declined.
+    pub fn hide_declined_by(mut self, email: impl Into<String>) -> Self {
+        self.declined_by = Some(email.into());
+        self
+    }
+
+    /// Only events with this status. Can be called more than once.
+    pub fn status(mut self, status: Status) -> Self {
+        self.statuses.get_or_insert_with(Vec::new).push(status);
+        self
+    }
+
+    /// Events carrying at least one of `tags` (CATEGORIES).
+    pub fn tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Only search calendars with these slugs. Ignored by `Calendar::query`.
+    pub fn calendars(mut self, slugs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.calendars.extend(slugs.into_iter().map(Into::into));
+        self
+    }
+
+    /// Latest events first.
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn range(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        (self.from, self.to)
+    }
+
+    /// Archived calendars are only included when named.
+    pub(crate) fn includes_calendar(&self, calendar: &Calendar) -> bool {
+        let named = calendar
+            .slug()
+            .is_some_and(|slug| self.calendars.iter().any(|s| s == slug));
+
+        named || (self.calendars.is_empty() && !calendar.is_archived())
+    }
+
+    pub(crate) fn matches(&self, event: &Event) -> bool {
+        self.matches_status(event)
+            && self.matches_text(event)
+            && self.matches_attendee(event)
+            && !self.is_declined(event)
+            && self.matches_tags(event)
+    }
+
+    /// Sort `items` by their event's start and apply the limit.
+    pub(crate) fn sort_and_limit<T>(&self, items: &mut Vec<T>, event: impl Fn(&T) -> &Event) {
+        items.sort_by(|a, b| {
+            let (a, b) = (event(a), event(b));
+            a.start
+                .to_utc()
+                .cmp(&b.start.to_utc())
+                .then_with(|| a.start.is_date().cmp(&b.start.is_date()).reverse())
+        });
+
+        if self.descending {
+            items.reverse();
+        }
+
+        if let Some(limit) = self.limit {
+            items.truncate(limit);
+        }
+    }
+
+    fn matches_status(&self, event: &Event) -> bool {
+        match &self.statuses {
+            Some(statuses) => statuses.contains(&event.status),
+            None => event.status != Status::Cancelled,
+        }
+    }
+
+    fn matches_text(&self, event: &Event) -> bool {
+        let Some(text) = &self.text else {
+            return true;
+        };
+
+        [&event.summary, &event.description, &event.location]
+            .into_iter()
+            .flatten()
+            .any(|field| field.to_lowercase().contains(text))
+    }
+
+    fn matches_attendee(&self, event: &Event) -> bool {
+        let Some(email) = &self.attendee else {
+            return true;
+        };
+
+        let is_organizer = event
+            .organizer
+            .as_ref()
+            .is_some_and(|o| o.email.eq_ignore_ascii_case(email));
+
+        is_organizer || event.find_attendee(email).is_some()
+    }
+
+    fn is_declined(&self, event: &Event) -> bool {
+        self.declined_by.as_deref().is_some_and(|email| {
+            event.attendee_status(email) == Some(ParticipationStatus::Declined)
+        })
+    }
+
+    fn matches_tags(&self, event: &Event) -> bool {
+        self.tags.is_empty() || self.tags.iter().any(|tag| event.has_category(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Attendee, EventTime};
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+    }
+
+    fn query() -> EventQuery {
+        EventQuery::between(utc(2026, 1, 1, 0), utc(2026, 2, 1, 0))
+    }
+
+    fn event(summary: &str, start: DateTime<Utc>) -> Event {
+        Event::new(summary, EventTime::DateTimeUtc(start))
+    }
+
+    #[test]
+    fn skips_cancelled_events_unless_asked_for() {
+        let mut cancelled = event("Standup", utc(2026, 1, 5, 9));
+        cancelled.status = Status::Cancelled;
+
+        assert!(!query().matches(&cancelled));
+        assert!(query().status(Status::Cancelled).matches(&cancelled));
+    }
+
+    #[test]
+    fn text_matches_summary_description_and_location() {
+        let mut lunch = event("Lunch", utc(2026, 1, 5, 12));
+        lunch.location = Some("Café Oslo".to_string());
+
+        assert!(query().text("lunch").matches(&lunch));
+        assert!(query().text("OSLO").matches(&lunch));
+        assert!(!query().text("dinner").matches(&lunch));
+    }
+
+    #[test]
+    fn attendee_matches_attendees_and_organizer() {
+        let mut meeting = event("Planning", utc(2026, 1, 5, 12));
+        meeting.attendees = vec![Attendee::new("bob@example.com")];
+
+        assert!(query().attendee("BOB@example.com").matches(&meeting));
+        assert!(!query().attendee("alice@example.com").matches(&meeting));
+    }
+
+    #[test]
+    fn hides_events_declined_by_the_given_attendee() {
+        let mut declined = Attendee::new("me@example.com");
+        declined.status = Some(ParticipationStatus::Declined);
+        let mut meeting = event("Planning", utc(2026, 1, 5, 12));
+        meeting.attendees = vec![declined, Attendee::new("bob@example.com")];
+
+        assert!(query().matches(&meeting));
+        assert!(!query().hide_declined_by("me@example.com").matches(&meeting));
+        assert!(
+            query()
+                .hide_declined_by("bob@example.com")
+                .matches(&meeting)
+        );
+    }
+
+    #[test]
+    fn tags_match_any_requested_tag() {
+        let mut trip = event("Trip", utc(2026, 1, 5, 12));
+        trip.categories = vec!["Travel".to_string()];
+
+        assert!(query().matches(&trip));
+        assert!(query().tags(["work", "travel"]).matches(&trip));
+        assert!(!query().tags(["work"]).matches(&trip));
+    }
+
+    #[test]
+    fn sorts_by_start_and_applies_limit() {
+        let mut events = vec![
+            event("Third", utc(2026, 1, 7, 9)),
+            event("First", utc(2026, 1, 5, 9)),
+            event("Second", utc(2026, 1, 6, 9)),
+        ];
+
+        query().limit(2).sort_and_limit(&mut events, |e| e);
+
+        let summaries: Vec<_> = events.iter().map(|e| e.summary.as_deref()).collect();
+        assert_eq!(summaries, [Some("First"), Some("Second")]);
+    }
+
+    #[test]
+    fn descending_sorts_latest_first() {
+        let mut events = vec![
+            event("First", utc(2026, 1, 5, 9)),
+            event("Second", utc(2026, 1, 6, 9)),
+        ];
+
+        query().descending().sort_and_limit(&mut events, |e| e);
+
+        assert_eq!(events[0].summary.as_deref(), Some("Second"));
+    }
+}
@@ -1,6 +1,7 @@
 mod error;
 mod event_bases;
 mod known_event_ids;
+mod parse_cache;
 mod sync_bases;
 
 pub use error::CalendarStateError;
@@ -9,11 +10,10 @@ use std::path::Path;
 #[cfg(test)]
 use std::collections::HashSet;
 
+pub(crate) use parse_cache::ParseCache;
 pub(crate) use sync_bases::SyncBases;
 
-use crate::Event;
-#[cfg(test)]
-use crate::EventInstanceId;
+use crate::{Event, EventInstanceId};
 
 #[derive(Debug)]
 pub struct CalendarState {
@@ -41,6 +41,18 @@ impl CalendarState {
         self.sync_bases.record(events, state_dir)
     }
 
+    pub(crate) fn forget_sync_base(
+        &mut self,
+        id: &EventInstanceId,
+        state_dir: &Path,
+    ) -> Result<(), CalendarStateError> {
+        self.sync_bases.forget(id, state_dir)
+    }
+
+    pub(crate) fn clear_sync_bases(&mut self, state_dir: &Path) -> Result<(), CalendarStateError> {
+        self.sync_bases.clear(state_dir)
+    }
+
     pub(crate) fn sync_bases(&self) -> &SyncBases {
         &self.sync_bases
     }
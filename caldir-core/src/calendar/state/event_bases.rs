@@ -31,7 +31,15 @@ impl EventBases {
 
             // Skip unchanged bases to avoid churning every file on every sync.
             // Parse-compare, not byte-compare: to_ics_string stamps a fresh DTSTAMP.
-            if Event::load_single(&event_path).is_ok_and(|existing| &existing == event) {
+            // Event equality ignores SEQUENCE and LAST-MODIFIED, which pushes
+            // check the remote against, so compare those too (to the second,
+            // as ICS stores them).
+            let seconds = |event: &Event| event.last_modified.map(|t| t.timestamp());
+            if Event::load_single(&event_path).is_ok_and(|existing| {
+                &existing == event
+                    && existing.sequence == event.sequence
+                    && seconds(&existing) == seconds(event)
+            }) {
                 continue;
             }
 
@@ -43,6 +51,16 @@ impl EventBases {
         Ok(())
     }
 
+    /// Delete the base file of `id`, if there is one.
+    pub(crate) fn remove(id: &EventInstanceId, path: &Path) -> Result<(), CalendarStateError> {
+        let event_path = path.join(format!("{}.ics", hash_filename(&id.to_string())));
+
+        match std::fs::remove_file(event_path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
     pub(crate) fn load(path: &Path) -> Result<Self, CalendarStateError> {
         let mut event_bases = HashMap::new();
 
@@ -0,0 +1,176 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::CalendarStateError;
+use crate::Event;
+use crate::calendar::FileStamp;
+use crate::event::{
+    Attachment, Attendee, Availability, Conference, EventTime, EventType, EventUid, Organizer,
+    Recurrence, RecurrenceId, Reminder, Status, Visibility, XProperty,
+};
+
+pub(crate) const PARSE_CACHE_FILE_NAME: &str = "parsed_events.json";
+
+/// Caches written by another caldir-core version are dropped, in case
+/// `Event` changed shape in between. The suffix counts shape changes
+/// within a version.
+const CACHE_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+2");
+
+/// Events parsed by earlier `Calendar::events()` calls, keyed by file name,
+/// so files that haven't changed since aren't parsed again. A file holding a
+/// whole recurring series has all of its events in one entry.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ParseCache {
+    version: String,
+    entries: HashMap<String, Entry>,
+    #[serde(skip)]
+    changed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    stamp: FileStamp,
+    events: Vec<CachedEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedEvent(#[serde(with = "EventDef")] Event);
+
+impl ParseCache {
+    fn new() -> Self {
+        Self {
+            version: CACHE_VERSION.to_string(),
+            entries: HashMap::new(),
+            changed: false,
+        }
+    }
+
+    /// The cache in `state_dir`. Missing, unreadable or outdated caches
+    /// start out empty.
+    pub(crate) fn load(state_dir: &Path) -> Self {
+        std::fs::read(state_dir.join(PARSE_CACHE_FILE_NAME))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .unwrap_or_else(Self::new)
+    }
+
+    /// The events parsed from `file`, if the file hasn't changed since.
+    pub(crate) fn get(&self, file: &str, stamp: FileStamp) -> Option<Vec<&Event>> {
+        self.entries
+            .get(file)
+            .filter(|entry| entry.stamp == stamp)
+            .map(|entry| entry.events.iter().map(|cached| &cached.0).collect())
+    }
+
+    pub(crate) fn insert(&mut self, file: String, stamp: FileStamp, events: Vec<Event>) {
+        let events = events.into_iter().map(CachedEvent).collect();
+        self.entries.insert(file, Entry { stamp, events });
+        self.changed = true;
+    }
+
+    /// Forget files that aren't in `files` anymore.
+    pub(crate) fn retain(&mut self, files: &HashSet<String>) {
+        let before = self.entries.len();
+        self.entries.retain(|file, _| files.contains(file));
+        self.changed |= self.entries.len() != before;
+    }
+
+    /// Write the cache to `state_dir`, if anything changed since it was
+    /// loaded or last saved.
+    pub(crate) fn save(&mut self, state_dir: &Path) -> Result<(), CalendarStateError> {
+        if !self.changed {
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(state_dir)?;
+        let json = serde_json::to_vec(self).map_err(std::io::Error::from)?;
+
+        let mut tmp = tempfile::NamedTempFile::new_in(state_dir)?;
+        std::io::Write::write_all(&mut tmp, &json)?;
+        tmp.persist(state_dir.join(PARSE_CACHE_FILE_NAME))
+            .map_err(|err| err.error)?;
+        self.changed = false;
+
+        Ok(())
+    }
+}
+
+/// `Event` field by field: its own serde impls go through ICS, which is
+/// the parsing this cache is here to skip.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Event")]
+struct EventDef {
+    uid: EventUid,
+    summary: Option<String>,
+    description: Option<String>,
+    location: Option<String>,
+    start: EventTime,
+    end: Option<EventTime>,
+    status: Status,
+    availability: Availability,
+    visibility: Option<Visibility>,
+    event_type: EventType,
+    recurrence: Option<Recurrence>,
+    recurrence_id: Option<RecurrenceId>,
+    organizer: Option<Organizer>,
+    attendees: Vec<Attendee>,
+    reminders: Vec<Reminder>,
+    reminders_default: bool,
+    url: Option<String>,
+    categories: Vec<String>,
+    color: Option<String>,
+    conferences: Vec<Conference>,
+    attachments: Vec<Attachment>,
+    x_properties: Vec<XProperty>,
+    last_modified: Option<DateTime<Utc>>,
+    sequence: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_event;
+
+    fn stamp(size: i64) -> FileStamp {
+        FileStamp {
+            modified: 1,
+            changed: 1,
+            size,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_state_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let event = test_event();
+
+        let mut cache = ParseCache::load(dir.path());
+        cache.insert("event.ics".to_string(), stamp(10), vec![event.clone()]);
+        cache.save(dir.path()).unwrap();
+
+        let loaded = ParseCache::load(dir.path());
+        assert_eq!(loaded.get("event.ics", stamp(10)), Some(vec![&event]));
+    }
+
+    #[test]
+    fn misses_when_file_changed() {
+        let mut cache = ParseCache::new();
+        cache.insert("event.ics".to_string(), stamp(10), vec![test_event()]);
+
+        assert!(cache.get("event.ics", stamp(11)).is_none());
+    }
+
+    #[test]
+    fn retain_forgets_deleted_files() {
+        let mut cache = ParseCache::new();
+        cache.insert("gone.ics".to_string(), stamp(10), vec![test_event()]);
+
+        cache.retain(&HashSet::new());
+
+        assert!(cache.get("gone.ics", stamp(10)).is_none());
+    }
+}
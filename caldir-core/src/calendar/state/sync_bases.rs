@@ -20,7 +20,6 @@ impl SyncBases {
         self.0.get(id)
     }
 
-    #[cfg(test)]
     pub(crate) fn iter(&self) -> impl Iterator<Item = (&EventInstanceId, &Option<Box<Event>>)> {
         self.0.iter()
     }
@@ -65,6 +64,34 @@ impl SyncBases {
         Ok(())
     }
 
+    /// Forget `id` was ever synced, so the event counts as new on both sides.
+    pub(crate) fn forget(
+        &mut self,
+        id: &EventInstanceId,
+        state_dir: &Path,
+    ) -> Result<(), CalendarStateError> {
+        if self.0.remove(id).is_none() {
+            return Ok(());
+        }
+
+        KnownEventIds::write_from(self.0.keys(), &state_dir.join(KNOWN_IDS_FILE_NAME))?;
+        EventBases::remove(id, &state_dir.join(EVENT_BASES_DIR_NAME))
+    }
+
+    /// Forget every synced event, so they all count as new on both sides.
+    pub(crate) fn clear(&mut self, state_dir: &Path) -> Result<(), CalendarStateError> {
+        self.0.clear();
+
+        match std::fs::remove_file(state_dir.join(KNOWN_IDS_FILE_NAME)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+        match std::fs::remove_dir_all(state_dir.join(EVENT_BASES_DIR_NAME)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
     // Legacy file:
     fn load_known_event_ids(state_dir: &Path) -> Result<KnownEventIds, CalendarStateError> {
         let known_ids_path = state_dir.join(KNOWN_IDS_FILE_NAME);
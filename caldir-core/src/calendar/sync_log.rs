@@ -0,0 +1,148 @@
+//! Append-only record of what pull and push did to a calendar, one JSON
+//! object per line in `.caldir/sync_log.jsonl`.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::EventChange;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncDirection {
+    Pull,
+    Push,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncChangeKind {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncLogEntry {
+    pub at: DateTime<Utc>,
+    pub direction: SyncDirection,
+    pub kind: SyncChangeKind,
+    pub uid: String,
+    /// Start of the occurrence, for changes to one occurrence of a series
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Fields an update changed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<String>,
+    /// Why the change failed, if it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SyncLogEntry {
+    pub(crate) fn new(
+        direction: SyncDirection,
+        change: &EventChange,
+        error: Option<String>,
+    ) -> Self {
+        let (kind, changed) = match change {
+            EventChange::Create(_) => (SyncChangeKind::Create, Vec::new()),
+            EventChange::Update { from, to } => (
+                SyncChangeKind::Update,
+                from.changed_fields(to)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            EventChange::Delete(_) => (SyncChangeKind::Delete, Vec::new()),
+        };
+        let event = change.event();
+
+        Self {
+            at: Utc::now(),
+            direction,
+            kind,
+            uid: event.uid.as_str().to_string(),
+            recurrence_id: event
+                .recurrence_id
+                .as_ref()
+                .map(|id| id.as_event_time().to_utc().to_rfc3339()),
+            summary: event.summary.clone(),
+            changed,
+            error,
+        }
+    }
+}
+
+pub(crate) fn append(path: &Path, entries: &[SyncLogEntry]) -> std::io::Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut lines = Vec::new();
+    for entry in entries {
+        serde_json::to_writer(&mut lines, entry)?;
+        lines.push(b'\n');
+    }
+
+    // One write, so concurrent syncs don't interleave inside a line
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(&lines)
+}
+
+/// Entries in `path`, oldest first. Lines that don't parse are skipped.
+pub(crate) fn read(path: &Path) -> std::io::Result<Vec<SyncLogEntry>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(entry) = serde_json::from_str(&line?) {
+            entries.push(entry);
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_event;
+
+    #[test]
+    fn appends_and_reads_back() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("sync_log.jsonl");
+        let event = test_event();
+
+        let created = SyncLogEntry::new(
+            SyncDirection::Pull,
+            &EventChange::Create(event.clone()),
+            None,
+        );
+        let deleted = SyncLogEntry::new(
+            SyncDirection::Push,
+            &EventChange::Delete(event),
+            Some("gone".to_string()),
+        );
+        append(&path, std::slice::from_ref(&created)).unwrap();
+        append(&path, std::slice::from_ref(&deleted)).unwrap();
+
+        assert_eq!(read(&path).unwrap(), [created, deleted]);
+    }
+}
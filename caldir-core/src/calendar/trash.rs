@@ -0,0 +1,169 @@
+//! Event files deleted by pull, kept in `<calendar>/.trash/` for a while so
+//! an accidental remote deletion can be undone locally.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::{CalendarError, CalendarEvent};
+use crate::Event;
+
+const TRASH_DIR_NAME: &str = ".trash";
+
+/// Trashed files older than this are deleted for good.
+const EXPIRY_DAYS: i64 = 30;
+
+/// Separates the trashing time from the original file name.
+const SEPARATOR: &str = "__";
+
+#[derive(Debug, Clone)]
+pub struct TrashedEvent {
+    path: PathBuf,
+    trashed_at: DateTime<Utc>,
+    event: Event,
+}
+
+impl TrashedEvent {
+    /// What `caldir trash restore` takes: the file name in the trash.
+    pub fn id(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+    }
+
+    pub fn trashed_at(&self) -> DateTime<Utc> {
+        self.trashed_at
+    }
+
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn trash_dir(calendar_path: &Path) -> PathBuf {
+    calendar_path.join(TRASH_DIR_NAME)
+}
+
+/// When the trash file `name` was trashed.
+fn trashed_at(name: &str) -> Option<DateTime<Utc>> {
+    let (secs, _) = name.split_once(SEPARATOR)?;
+    DateTime::from_timestamp(secs.parse().ok()?, 0)
+}
+
+/// Move `calendar_event` into the trash, and empty out expired entries.
+pub(crate) fn move_to_trash(
+    calendar_path: &Path,
+    calendar_event: CalendarEvent,
+) -> Result<(), CalendarError> {
+    let dir = trash_dir(calendar_path);
+    std::fs::create_dir_all(&dir)?;
+
+    let now = Utc::now();
+    let file_name = calendar_event.filename().unwrap_or("event.ics");
+    let timestamp = now.timestamp();
+    // Events from one series file can be trashed in the same second
+    let mut target = dir.join(format!("{timestamp}{SEPARATOR}{file_name}"));
+    let mut suffix = 2;
+    while target.exists() {
+        target = dir.join(format!("{timestamp}{SEPARATOR}{suffix}-{file_name}"));
+        suffix += 1;
+    }
+
+    // Only this event goes to the trash; the rest of its series stays
+    if calendar_event.shares_file() {
+        std::fs::write(&target, calendar_event.event().to_ics_string())?;
+        calendar_event.delete()?;
+    } else {
+        std::fs::rename(calendar_event.path(), target)?;
+    }
+
+    purge_expired(&dir, now)
+}
+
+fn purge_expired(dir: &Path, now: DateTime<Utc>) -> Result<(), CalendarError> {
+    let cutoff = now - Duration::days(EXPIRY_DAYS);
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let expired = entry
+            .file_name()
+            .to_str()
+            .and_then(trashed_at)
+            .is_some_and(|at| at < cutoff);
+
+        if expired {
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Trashed events in the calendar at `calendar_path`, most recent first.
+/// Files that don't parse are skipped.
+pub(crate) fn list(calendar_path: &Path) -> Result<Vec<TrashedEvent>, CalendarError> {
+    let dir = trash_dir(calendar_path);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut trashed = Vec::new();
+
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let Some(at) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(trashed_at)
+        else {
+            continue;
+        };
+        let Ok(calendar_event) = CalendarEvent::load(path.clone()) else {
+            continue;
+        };
+
+        trashed.push(TrashedEvent {
+            path,
+            trashed_at: at,
+            event: calendar_event.event().clone(),
+        });
+    }
+
+    trashed.sort_by(|a, b| b.trashed_at.cmp(&a.trashed_at).then(a.id().cmp(b.id())));
+    Ok(trashed)
+}
+
+/// The trashed event with file name `id`.
+pub(crate) fn get(calendar_path: &Path, id: &str) -> Result<TrashedEvent, CalendarError> {
+    list(calendar_path)?
+        .into_iter()
+        .find(|trashed| trashed.id() == id)
+        .ok_or_else(|| CalendarError::NotInTrash(id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn purge_expired_keeps_recent_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let now = Utc::now();
+        let old = now - Duration::days(EXPIRY_DAYS + 1);
+
+        let recent = dir.path().join(format!("{}__recent.ics", now.timestamp()));
+        let expired = dir.path().join(format!("{}__expired.ics", old.timestamp()));
+        std::fs::write(&recent, "").unwrap();
+        std::fs::write(&expired, "").unwrap();
+
+        purge_expired(dir.path(), now).unwrap();
+
+        assert!(recent.exists());
+        assert!(!expired.exists());
+    }
+}
@@ -1,22 +1,37 @@
 mod error;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use crate::calendar::{CalendarError, SyncBases};
-use crate::diff::EventChange;
+use chrono::Utc;
+
+use crate::calendar::{CalendarError, SyncBases, SyncDirection, SyncLogEntry, best_event_path};
+use crate::diff::{EventChange, PlannedStep};
 use crate::event::EventInstanceId;
-use crate::{Calendar, CalendarDiff, CalendarEvent, DateRange, Event, Remote, RemoteEvent};
-use error::ConnectionError;
+use crate::remote::RemoteError;
+use crate::rpc::{Capabilities, Notify};
+use crate::{
+    Calendar, CalendarDiff, CalendarEvent, DateRange, Event, FloatingTimezone, PullRule, Remote,
+    RemoteEvent,
+};
+pub(crate) use error::ConnectionError;
 
 /// A connection is a [local calendar] + [remote calendar] pair
 pub struct Connection {
     local: Calendar,
     remote: Remote,
+    /// ICS properties that don't count as changes (`ignore_properties`)
+    ignored_properties: Vec<String>,
 }
 
 impl Connection {
-    pub fn new(local: Calendar, remote: Remote) -> Self {
-        Self { local, remote }
+    pub fn new(local: Calendar, mut remote: Remote) -> Self {
+        remote.set_floating_timezone(local.floating_timezone());
+        Self {
+            local,
+            remote,
+            ignored_properties: Vec::new(),
+        }
     }
 
     pub fn local(&self) -> &Calendar {
@@ -27,6 +42,14 @@ impl Connection {
         &self.remote
     }
 
+    pub fn set_notify_attendees(&mut self, notify: Notify) {
+        self.remote.set_notify_attendees(notify);
+    }
+
+    pub fn set_ignored_properties(&mut self, properties: Vec<String>) {
+        self.ignored_properties = properties;
+    }
+
     pub fn read_only(&self) -> bool {
         self.local
             .config()
@@ -35,14 +58,47 @@ impl Connection {
     }
 
     pub async fn diff(&mut self, range: &DateRange) -> Result<CalendarDiff, ConnectionError> {
-        let local_events = self.local().events()?;
-        let remote_events = self.remote().list_events(range).await?;
+        self.diff_with_progress(range, &|_| {}).await
+    }
+
+    /// Like `diff`, reporting how many remote events have been fetched so far.
+    pub async fn diff_with_progress(
+        &mut self,
+        range: &DateRange,
+        on_progress: &(dyn Fn(usize) + Sync),
+    ) -> Result<CalendarDiff, ConnectionError> {
+        let capabilities = self.remote().capabilities().await;
+        let local_events = self.local().unique_events()?;
+        let mut remote_events = self
+            .remote()
+            .list_events_with_progress(range, on_progress)
+            .await?;
+
+        let local_by_id: HashMap<_, _> = local_events
+            .iter()
+            .map(|e| (e.event().event_instance_id(), e.event()))
+            .collect();
+
+        let pull_rules = self
+            .local
+            .config()
+            .map(|c| c.pull_rules().to_vec())
+            .unwrap_or_default();
+        apply_pull_rules(&pull_rules, &mut remote_events);
+
+        keep_unsupported_fields(
+            &capabilities,
+            self.local.floating_timezone(),
+            &local_by_id,
+            &mut remote_events,
+        );
+        keep_ignored_properties(&self.ignored_properties, &local_by_id, &mut remote_events);
 
         // State migration: in-sync pairs never produce a change to apply, so
         // this is the only place their base can be recorded. Without it,
         // legacy known-id entries would sit on the mtime fallback forever.
         let backfill = bases_to_backfill(
-            &local_events,
+            &local_by_id,
             &remote_events,
             self.local.state().sync_bases(),
         );
@@ -58,19 +114,54 @@ impl Connection {
             diff.discard_outgoing();
         }
 
+        // Rather than failing mid-push on something the provider can't do
+        diff.discard_outgoing_where(|change| !supports(&capabilities, change));
+
+        if let Some(filter) = self.local.config().and_then(|c| c.pull_filter()) {
+            let email = self.local.remote_email();
+            diff.discard_incoming_creates(|event| filter.excludes(event, email));
+            diff.delete_incoming_updates(|event| filter.excludes(event, email));
+        }
+        diff.discard_incoming_creates(|event| pull_rules.iter().any(|r| r.drops(event)));
+
+        Ok(diff)
+    }
+
+    /// Local changes since the last sync, without contacting the provider.
+    pub fn offline_diff(&self, range: &DateRange) -> Result<CalendarDiff, ConnectionError> {
+        let local_events = self.local().unique_events()?;
+        let sync_bases = self.local().state().sync_bases();
+
+        let mut diff = CalendarDiff::compute_offline(local_events, sync_bases, range);
+
+        if self.read_only() {
+            diff.discard_outgoing();
+        }
+
+        if !self.ignored_properties.is_empty() {
+            diff.discard_outgoing_where(|change| match change {
+                EventChange::Update { from, to } => {
+                    let mut to = to.clone();
+                    to.keep_properties(from, &self.ignored_properties);
+                    &to == from
+                }
+                _ => false,
+            });
+        }
+
         Ok(diff)
     }
 
     // pull
     pub fn apply_incoming_diff(&mut self, diff: &CalendarDiff) -> Result<(), ConnectionError> {
-        let mut events_by_instance_id: HashMap<EventInstanceId, CalendarEvent> = self
-            .local
-            .events()?
-            .into_iter()
-            .map(|e| (e.event().event_instance_id(), e))
-            .collect();
+        if self.local.restore_file_names() {
+            self.local.normalize_file_names()?;
+        }
+        let mut events_by_instance_id = by_instance_id(self.local.unique_events()?);
 
         let mut sync_bases = Vec::new();
+        let mut deleted = Vec::new();
+        let mut log = Vec::new();
 
         // Same partial-failure flush pattern as `apply_outgoing_diff`: a
         // local-fs error mid-loop must not drop changes already applied to disk.
@@ -79,12 +170,20 @@ impl Connection {
             diff,
             &mut events_by_instance_id,
             &mut sync_bases,
+            &mut deleted,
+            &mut log,
         );
 
-        let record_result = self.local.record_sync_bases(sync_bases);
+        let record_result = self
+            .local
+            .record_sync_bases(sync_bases)
+            .and_then(|()| self.local.forget_sync_bases(&deleted));
+        let log_result = self.local.append_sync_log(&log);
 
         loop_result?;
         record_result?;
+        log_result?;
+        self.local.record_synced(Utc::now())?;
         Ok(())
     }
 
@@ -93,14 +192,10 @@ impl Connection {
         &mut self,
         diff: &CalendarDiff,
     ) -> Result<(), ConnectionError> {
-        let mut events_by_instance_id: HashMap<EventInstanceId, CalendarEvent> = self
-            .local
-            .events()?
-            .into_iter()
-            .map(|e| (e.event().event_instance_id(), e))
-            .collect();
+        let mut events_by_instance_id = by_instance_id(self.local.unique_events()?);
 
         let mut sync_bases = Vec::new();
+        let mut log = Vec::new();
 
         // Handles mid-loop errors gracefully
         let loop_result = push_outgoing_changes(
@@ -108,24 +203,127 @@ impl Connection {
             diff,
             &mut events_by_instance_id,
             &mut sync_bases,
+            &mut log,
         )
         .await;
 
         let record_result = self.local.record_sync_bases(sync_bases);
+        let log_result = self.local.append_sync_log(&log);
 
         loop_result?;
         record_result?;
+        log_result?;
+        self.local.record_synced(Utc::now())?;
         Ok(())
     }
 
+    /// The file changes `apply_incoming_diff` would make, without making them.
+    pub fn plan_incoming_diff(
+        &self,
+        diff: &CalendarDiff,
+    ) -> Result<Vec<PlannedStep>, ConnectionError> {
+        let events_by_instance_id = by_instance_id(self.local.unique_events()?);
+        let dir = self.local.path();
+
+        // Paths earlier steps would create or free up
+        let mut created: HashSet<PathBuf> = HashSet::new();
+        let mut removed: HashSet<PathBuf> = HashSet::new();
+        let mut steps = Vec::new();
+
+        for change in diff.incoming() {
+            let is_taken =
+                |path: &Path| created.contains(path) || (path.exists() && !removed.contains(path));
+
+            match change {
+                EventChange::Create(event) => {
+                    let master = master_of(event, &events_by_instance_id);
+                    if let Some(path) = self.local.series_file_with(event, master) {
+                        steps.push(PlannedStep::UpdateFile(path));
+                        continue;
+                    }
+                    let event_dir = self.local.layout().event_dir(dir, &event.start);
+                    let path = best_event_path(
+                        &event_dir,
+                        &event.base_slug(),
+                        self.local.storage_format(),
+                        None,
+                        is_taken,
+                    );
+                    created.insert(path.clone());
+                    steps.push(PlannedStep::CreateFile(path));
+                }
+                EventChange::Update { to, .. } => {
+                    let Some(cal_event) = events_by_instance_id.get(&to.event_instance_id()) else {
+                        continue;
+                    };
+                    let from = cal_event.path().to_path_buf();
+                    // Series files are named after their master
+                    if to.recurrence.is_none() && cal_event.shares_file() {
+                        steps.push(PlannedStep::UpdateFile(from));
+                        continue;
+                    }
+                    let path = best_event_path(
+                        &cal_event.dir_for(to),
+                        &to.base_slug(),
+                        cal_event.storage_format(),
+                        Some(&from),
+                        is_taken,
+                    );
+
+                    if path == from {
+                        steps.push(PlannedStep::UpdateFile(path));
+                    } else {
+                        created.insert(path.clone());
+                        removed.insert(from.clone());
+                        steps.push(PlannedStep::RenameFile { from, to: path });
+                    }
+                }
+                EventChange::Delete(event) => {
+                    if let Some(cal_event) = events_by_instance_id.get(&event.event_instance_id()) {
+                        let path = cal_event.path().to_path_buf();
+                        if cal_event.shares_file() {
+                            steps.push(PlannedStep::UpdateFile(path));
+                        } else {
+                            removed.insert(path.clone());
+                            steps.push(PlannedStep::DeleteFile(path));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(steps)
+    }
+
+    /// The provider calls `apply_outgoing_diff` would make, followed by the
+    /// local files it would rewrite with the events the provider sends back.
+    pub fn plan_outgoing_diff(
+        &self,
+        diff: &CalendarDiff,
+    ) -> Result<Vec<PlannedStep>, ConnectionError> {
+        let changes: Vec<&EventChange> = diff.outgoing().iter().collect();
+        let mut steps = Remote::planned_calls(&changes);
+
+        let events_by_instance_id = by_instance_id(self.local.unique_events()?);
+
+        for change in changes {
+            let pushed = match change {
+                EventChange::Create(event) => event,
+                EventChange::Update { to, .. } => to,
+                EventChange::Delete(_) => continue,
+            };
+
+            if let Some(cal_event) = events_by_instance_id.get(&pushed.event_instance_id()) {
+                steps.push(PlannedStep::UpdateFile(cal_event.path().to_path_buf()));
+            }
+        }
+
+        Ok(steps)
+    }
+
     // discard
     pub fn discard_outgoing_diff(&self, diff: &CalendarDiff) -> Result<(), ConnectionError> {
-        let mut events_by_instance_id: HashMap<EventInstanceId, CalendarEvent> = self
-            .local
-            .events()?
-            .into_iter()
-            .map(|e| (e.event().event_instance_id(), e))
-            .collect();
+        let mut events_by_instance_id = by_instance_id(self.local.unique_events()?);
 
         for change in diff.outgoing() {
             match change {
@@ -137,12 +335,11 @@ impl Connection {
                     }
                 }
                 EventChange::Update { from, to } => {
-                    if let Some(cal_event) = events_by_instance_id.get_mut(&to.event_instance_id())
-                    {
-                        cal_event
-                            .update(from.clone())
-                            .map_err(CalendarError::from)?;
-                    }
+                    update_local(
+                        &mut events_by_instance_id,
+                        &to.event_instance_id(),
+                        from.clone(),
+                    )?;
                 }
                 EventChange::Delete(event) => {
                     let cal_event = self.local.create_event(event.clone())?;
@@ -155,20 +352,98 @@ impl Connection {
     }
 }
 
+fn by_instance_id(events: Vec<CalendarEvent>) -> HashMap<EventInstanceId, CalendarEvent> {
+    events
+        .into_iter()
+        .map(|e| (e.event().event_instance_id(), e))
+        .collect()
+}
+
+/// Rewrite the file of the local event `id` with `event`. Updating a master
+/// can rename the file its overrides are in too, so they follow along.
+fn update_local(
+    events_by_instance_id: &mut HashMap<EventInstanceId, CalendarEvent>,
+    id: &EventInstanceId,
+    event: Event,
+) -> Result<(), CalendarError> {
+    let Some(cal_event) = events_by_instance_id.get_mut(id) else {
+        return Ok(());
+    };
+
+    let from = cal_event.path().to_path_buf();
+    cal_event.update(event)?;
+    let to = cal_event.path().to_path_buf();
+
+    if to != from {
+        for other in events_by_instance_id.values_mut() {
+            if other.path() == from {
+                other.moved_to(to.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the provider can apply `change` at all.
+fn supports(capabilities: &Capabilities, change: &EventChange) -> bool {
+    match change {
+        EventChange::Create(_) => capabilities.create,
+        EventChange::Update { .. } => capabilities.update,
+        EventChange::Delete(_) => capabilities.delete,
+    }
+}
+
+/// Give remote events the local value of fields their provider can't store.
+fn keep_unsupported_fields(
+    capabilities: &Capabilities,
+    floating: FloatingTimezone,
+    local_by_id: &HashMap<EventInstanceId, &Event>,
+    remote_events: &mut [RemoteEvent],
+) {
+    for remote in remote_events {
+        if let Some(local) = local_by_id.get(&remote.event().event_instance_id()) {
+            capabilities.keep_unsupported_fields(local, remote.event_mut(), floating);
+        }
+    }
+}
+
+/// Rewrite remote events the way the calendar's pull rules say, before
+/// they're compared, so events pulled that way don't show up as updates.
+fn apply_pull_rules(rules: &[PullRule], remote_events: &mut [RemoteEvent]) {
+    for remote in remote_events {
+        for rule in rules {
+            rule.apply(remote.event_mut());
+        }
+    }
+}
+
+/// Take the ignored properties of remote events from their local copies,
+/// so provider-managed fields that keep changing don't show up as updates.
+fn keep_ignored_properties(
+    properties: &[String],
+    local_by_id: &HashMap<EventInstanceId, &Event>,
+    remote_events: &mut [RemoteEvent],
+) {
+    if properties.is_empty() {
+        return;
+    }
+    for remote in remote_events {
+        if let Some(local) = local_by_id.get(&remote.event().event_instance_id()) {
+            remote.event_mut().keep_properties(local, properties);
+        }
+    }
+}
+
 /// Events present and identical on both sides whose base is missing (legacy
 /// known-id entry) or stale. Both sides agreeing *is* the base — record it.
 /// Pairs with no sync state at all are left alone: they were never synced,
 /// and recording a base would silently change their delete semantics.
 fn bases_to_backfill(
-    local_events: &[CalendarEvent],
+    local_by_id: &HashMap<EventInstanceId, &Event>,
     remote_events: &[RemoteEvent],
     sync_bases: &SyncBases,
 ) -> Vec<Event> {
-    let local_by_id: HashMap<_, _> = local_events
-        .iter()
-        .map(|e| (e.event().event_instance_id(), e.event()))
-        .collect();
-
     let mut backfill = Vec::new();
 
     for remote in remote_events {
@@ -179,7 +454,7 @@ fn bases_to_backfill(
 
         let base_is_current = match sync_bases.get(&id) {
             None => continue,
-            Some(Some(base)) => base.as_ref() == remote,
+            Some(Some(base)) => !remote.changed_since(base),
             Some(None) => false,
         };
 
@@ -196,57 +471,220 @@ fn pull_incoming_changes(
     diff: &CalendarDiff,
     events_by_instance_id: &mut HashMap<EventInstanceId, CalendarEvent>,
     sync_bases: &mut Vec<Event>,
+    deleted: &mut Vec<EventInstanceId>,
+    log: &mut Vec<SyncLogEntry>,
 ) -> Result<(), ConnectionError> {
     for change in diff.incoming() {
-        match change {
-            EventChange::Create(event) => {
-                let cal_event = local.create_event(event.clone())?;
-                let id = cal_event.event().event_instance_id();
-                events_by_instance_id.insert(id, cal_event);
-                sync_bases.push(event.clone());
-            }
-            EventChange::Update { to, .. } => {
-                if let Some(cal_event) = events_by_instance_id.get_mut(&to.event_instance_id()) {
-                    cal_event.update(to.clone()).map_err(CalendarError::from)?;
-                }
-                sync_bases.push(to.clone());
-            }
-            EventChange::Delete(event) => {
-                if let Some(cal_event) = events_by_instance_id.remove(&event.event_instance_id()) {
-                    cal_event.delete().map_err(CalendarError::from)?;
-                }
+        let result =
+            pull_incoming_change(local, change, events_by_instance_id, sync_bases, deleted);
+        let error = result.as_ref().err().map(ToString::to_string);
+        log.push(SyncLogEntry::new(SyncDirection::Pull, change, error));
+        result?;
+    }
+
+    Ok(())
+}
+
+fn pull_incoming_change(
+    local: &Calendar,
+    change: &EventChange,
+    events_by_instance_id: &mut HashMap<EventInstanceId, CalendarEvent>,
+    sync_bases: &mut Vec<Event>,
+    deleted: &mut Vec<EventInstanceId>,
+) -> Result<(), ConnectionError> {
+    match change {
+        EventChange::Create(event) => {
+            let series_file =
+                local.series_file_with(event, master_of(event, events_by_instance_id));
+            let cal_event = local.create_event_in(event.clone(), series_file)?;
+            let id = cal_event.event().event_instance_id();
+            events_by_instance_id.insert(id, cal_event);
+            sync_bases.push(event.clone());
+        }
+        EventChange::Update { to, .. } => {
+            update_local(events_by_instance_id, &to.event_instance_id(), to.clone())?;
+            sync_bases.push(to.clone());
+        }
+        EventChange::Delete(event) => {
+            if let Some(cal_event) = events_by_instance_id.remove(&event.event_instance_id()) {
+                local.trash_event(cal_event)?;
             }
+            // A filtered-out event still exists remotely; with its base kept,
+            // the next push would take the missing file for a local delete.
+            deleted.push(event.event_instance_id());
         }
     }
 
     Ok(())
 }
 
+/// The master of the series `event` overrides an occurrence of, among
+/// `events`.
+fn master_of<'a>(
+    event: &Event,
+    events: &'a HashMap<EventInstanceId, CalendarEvent>,
+) -> Option<&'a CalendarEvent> {
+    event.recurrence_id.as_ref()?;
+    events.get(&EventInstanceId::new(event.uid.clone(), None))
+}
+
 async fn push_outgoing_changes(
     remote: &Remote,
     diff: &CalendarDiff,
     events_by_instance_id: &mut HashMap<EventInstanceId, CalendarEvent>,
     sync_bases: &mut Vec<Event>,
+    log: &mut Vec<SyncLogEntry>,
 ) -> Result<(), ConnectionError> {
-    for change in diff.outgoing() {
-        if let Some(remote_event) = remote.apply_change(change).await? {
-            let returned_event = remote_event.event();
-
-            // Sometimes provider overwrite the event's UID:
-            let original_event_id = match change {
-                EventChange::Create(event) => event.event_instance_id(),
-                EventChange::Update { to, .. } => to.event_instance_id(),
-                EventChange::Delete(_) => unreachable!("apply_change returns None for Delete"),
-            };
+    let capabilities = remote.capabilities().await;
+
+    // Changes to single occurrences go last: providers address an
+    // occurrence through its series, which a series created or edited in
+    // this same push is only up to date on once it's been pushed.
+    let (occurrences, changes): (Vec<&EventChange>, Vec<&EventChange>) = diff
+        .outgoing()
+        .iter()
+        .partition(|change| pushed_through_series(change, &capabilities));
+
+    // Record every change that made it, then surface the first failure.
+    let mut first_error = None;
+
+    let results = remote.apply_changes(&changes).await;
+    for (change, result) in changes.into_iter().zip(results) {
+        record_push(
+            &capabilities,
+            remote.floating_timezone(),
+            change,
+            result,
+            events_by_instance_id,
+            sync_bases,
+            log,
+            &mut first_error,
+        )?;
+    }
 
-            if let Some(cal_event) = events_by_instance_id.get_mut(&original_event_id) {
-                cal_event
-                    .update(returned_event.clone())
-                    .map_err(CalendarError::from)?;
+    if !occurrences.is_empty() {
+        let occurrences: Vec<EventChange> = occurrences
+            .into_iter()
+            .map(|change| through_series(change, events_by_instance_id))
+            .collect();
+        let occurrences: Vec<&EventChange> = occurrences.iter().collect();
+        let results = remote.apply_changes(&occurrences).await;
+
+        for (change, result) in occurrences.into_iter().zip(results) {
+            record_push(
+                &capabilities,
+                remote.floating_timezone(),
+                change,
+                result,
+                events_by_instance_id,
+                sync_bases,
+                log,
+                &mut first_error,
+            )?;
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e.into()),
+        None => Ok(()),
+    }
+}
+
+/// New overrides, and deleted ones the provider can't cancel on their own.
+fn pushed_through_series(change: &EventChange, capabilities: &Capabilities) -> bool {
+    match change {
+        EventChange::Create(event) => event.recurrence_id.is_some(),
+        EventChange::Delete(event) => {
+            event.recurrence_id.is_some() && !capabilities.cancel_occurrences
+        }
+        EventChange::Update { .. } => false,
+    }
+}
+
+/// The change to push for an occurrence, given its master as it is locally:
+/// - a new override gets the provider metadata (like the remote ID) the
+///   master has that it lacks
+/// - a deleted one becomes an EXDATE on the master
+fn through_series(
+    change: &EventChange,
+    events_by_instance_id: &HashMap<EventInstanceId, CalendarEvent>,
+) -> EventChange {
+    let occurrence = change.event();
+    let master = events_by_instance_id
+        .values()
+        .map(CalendarEvent::event)
+        .find(|candidate| {
+            candidate.uid == occurrence.uid
+                && candidate.recurrence_id.is_none()
+                && candidate.recurrence.is_some()
+        });
+
+    let (Some(master), Some(rid)) = (master, &occurrence.recurrence_id) else {
+        return change.clone();
+    };
+
+    match change {
+        EventChange::Create(event) => {
+            EventChange::Create(event.clone().with_x_properties_merged_from(master))
+        }
+        EventChange::Delete(_) => {
+            let mut to = master.clone();
+            if let Some(recurrence) = &mut to.recurrence
+                && !recurrence.exdates.contains(rid.as_event_time())
+            {
+                recurrence.exdates.push(rid.as_event_time().clone());
             }
+            EventChange::Update {
+                from: master.clone(),
+                to,
+            }
+        }
+        EventChange::Update { .. } => change.clone(),
+    }
+}
 
-            sync_bases.push(returned_event.clone());
+/// Log a pushed change and write the event the provider sent back to disk.
+#[allow(clippy::too_many_arguments)]
+fn record_push(
+    capabilities: &Capabilities,
+    floating: FloatingTimezone,
+    change: &EventChange,
+    result: Result<Option<RemoteEvent>, RemoteError>,
+    events_by_instance_id: &mut HashMap<EventInstanceId, CalendarEvent>,
+    sync_bases: &mut Vec<Event>,
+    log: &mut Vec<SyncLogEntry>,
+    first_error: &mut Option<RemoteError>,
+) -> Result<(), ConnectionError> {
+    let error = result.as_ref().err().map(ToString::to_string);
+    log.push(SyncLogEntry::new(SyncDirection::Push, change, error));
+
+    let remote_event = match result {
+        Ok(remote_event) => remote_event,
+        Err(e) => {
+            first_error.get_or_insert(e);
+            return Ok(());
         }
+    };
+
+    if let Some(mut remote_event) = remote_event {
+        let pushed = match change {
+            EventChange::Create(event) => event,
+            EventChange::Update { to, .. } => to,
+            EventChange::Delete(_) => unreachable!("apply_change returns None for Delete"),
+        };
+        // Sometimes provider overwrite the event's UID:
+        let original_event_id = pushed.event_instance_id();
+
+        capabilities.keep_unsupported_fields(pushed, remote_event.event_mut(), floating);
+        let returned_event = remote_event.event();
+
+        update_local(
+            events_by_instance_id,
+            &original_event_id,
+            returned_event.clone(),
+        )?;
+
+        sync_bases.push(returned_event.clone());
     }
 
     Ok(())
@@ -256,7 +694,7 @@ async fn push_outgoing_changes(
 mod tests {
     use super::*;
     use crate::diff::EventChange;
-    use crate::event::{EventUid, XProperty};
+    use crate::event::{EventUid, Recurrence, RecurrenceId, XProperty};
     use crate::provider::mock_provider::MockProvider;
     use crate::test_utils::{
         incoming_create_diff, incoming_delete_diff, incoming_update_diff, outgoing_create_diff,
@@ -327,6 +765,110 @@ mod tests {
         assert_eq!(diff.outgoing(), &[EventChange::Create(event)]);
     }
 
+    #[tokio::test]
+    async fn diff_discards_outgoing_the_provider_cannot_apply() {
+        let (_tmp, caldir) = test_caldir();
+        let calendar = caldir
+            .create_calendar("writable-cal", Some(calendar_config(Some(false))))
+            .unwrap();
+        calendar.create_event(test_event()).unwrap();
+
+        // Providers built from the mock snapshot its description, so the
+        // capabilities go in before the connection is built
+        let mock = test_mock_provider();
+        mock.set_capabilities(Capabilities::read_only());
+        mock.reply::<rpc::ListEvents>(vec![]);
+        let remote = Remote::new(mock.provider(), test_remote_params());
+
+        let mut connection = Connection::new(calendar, remote);
+        let diff = connection.diff(&DateRange::default()).await.unwrap();
+
+        assert!(diff.outgoing().is_empty());
+    }
+
+    #[tokio::test]
+    async fn diff_keeps_local_reminders_when_provider_has_none() {
+        let (_tmp, caldir) = test_caldir();
+        let calendar = caldir
+            .create_calendar("writable-cal", Some(calendar_config(Some(false))))
+            .unwrap();
+        let remote_event = test_event();
+        let mut local_event = remote_event.clone();
+        local_event.reminders = vec![crate::Reminder {
+            minutes_before_start: 10,
+        }];
+        calendar.create_event(local_event).unwrap();
+
+        let mock = test_mock_provider();
+        mock.set_capabilities(Capabilities {
+            reminders: false,
+            ..Capabilities::default()
+        });
+        mock.reply::<rpc::ListEvents>(vec![remote_event]);
+        let remote = Remote::new(mock.provider(), test_remote_params());
+
+        let mut connection = Connection::new(calendar, remote);
+        let diff = connection.diff(&DateRange::default()).await.unwrap();
+
+        assert!(diff.is_empty(), "got {:?}", diff.outgoing());
+    }
+
+    #[tokio::test]
+    async fn diff_skips_changes_to_ignored_properties() {
+        let (_tmp, mock, mut connection) = writable_connection();
+        let local_event = test_event();
+        let mut remote_event = local_event.clone();
+        remote_event.x_properties = vec![XProperty::new("X-GOOGLE-CONFERENCE", "https://meet")];
+        connection.local().create_event(local_event).unwrap();
+        mock.reply::<rpc::ListEvents>(vec![remote_event.clone()]);
+        mock.reply::<rpc::ListEvents>(vec![remote_event]);
+
+        let diff = connection.diff(&DateRange::default()).await.unwrap();
+        assert!(!diff.is_empty());
+
+        connection.set_ignored_properties(vec!["X-GOOGLE-CONFERENCE".to_string()]);
+        let diff = connection.diff(&DateRange::default()).await.unwrap();
+        assert!(diff.is_empty(), "got {:?}", diff.incoming());
+    }
+
+    #[tokio::test]
+    async fn diff_applies_pull_rules_to_remote_events() {
+        let (_tmp, caldir) = test_caldir();
+        let rules = r#"
+[[pull_rules]]
+organizer = "@calendly\\.com$"
+availability = "Free"
+add_tags = ["booking"]
+
+[[pull_rules]]
+summary = "^Lunch$"
+drop = true
+"#;
+        let config: CalendarConfig = toml::from_str(&format!(
+            "{}{rules}",
+            toml::to_string(&calendar_config(Some(false))).unwrap()
+        ))
+        .unwrap();
+        let calendar = caldir
+            .create_calendar("writable-cal", Some(config))
+            .unwrap();
+
+        let mut booking = test_event();
+        booking.organizer = Some(crate::Organizer::new("bookings@calendly.com"));
+        let lunch = Event::new("Lunch", booking.start.clone());
+
+        let mock = test_mock_provider();
+        mock.reply::<rpc::ListEvents>(vec![booking.clone(), lunch]);
+        let remote = Remote::new(mock.provider(), test_remote_params());
+
+        let mut connection = Connection::new(calendar, remote);
+        let diff = connection.diff(&DateRange::default()).await.unwrap();
+
+        booking.availability = crate::Availability::Free;
+        booking.categories = vec!["booking".to_string()];
+        assert_eq!(diff.incoming(), &[EventChange::Create(booking)]);
+    }
+
     #[tokio::test]
     async fn diff_backfills_base_for_in_sync_legacy_known_id() {
         let (_tmp, caldir) = test_caldir();
@@ -409,17 +951,223 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn apply_incoming_diff_deletes_file_for_incoming_delete() {
+    async fn plan_incoming_diff_lists_renames_without_touching_files() {
+        let (_tmp, _mock, connection) = writable_connection();
+        let from = test_event();
+        let cal_event = connection.local().create_event(from.clone()).unwrap();
+        let old_path = cal_event.path().to_path_buf();
+
+        let mut to = from.clone();
+        to.summary = Some("Updated Test Event".to_string());
+
+        let steps = connection
+            .plan_incoming_diff(&incoming_update_diff(from, to))
+            .unwrap();
+
+        let new_path = connection
+            .local()
+            .path()
+            .join("2026-01-01T1200__updated-test-event.ics");
+        assert_eq!(
+            steps,
+            vec![PlannedStep::RenameFile {
+                from: old_path.clone(),
+                to: new_path.clone(),
+            }]
+        );
+        assert!(old_path.is_file());
+        assert!(!new_path.exists());
+    }
+
+    #[tokio::test]
+    async fn plan_incoming_diff_gives_colliding_creates_distinct_files() {
+        let (_tmp, _mock, connection) = writable_connection();
+        let diff = CalendarDiff::from_changes(
+            vec![],
+            vec![
+                EventChange::Create(test_event()),
+                EventChange::Create(test_event()),
+            ],
+        );
+
+        let steps = connection.plan_incoming_diff(&diff).unwrap();
+
+        let dir = connection.local().path();
+        assert_eq!(
+            steps,
+            vec![
+                PlannedStep::CreateFile(dir.join("2026-01-01T1200__test-event.ics")),
+                PlannedStep::CreateFile(dir.join("2026-01-01T1200__test-event-2.ics")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn renamed_files_are_not_a_change_and_keep_their_name() {
+        let (_tmp, _mock, mut connection) = writable_connection();
+        let event = test_event();
+        connection
+            .apply_incoming_diff(&incoming_create_diff(event))
+            .unwrap();
+
+        let dir = connection.local().path().to_path_buf();
+        let original = dir.join("2026-01-01T1200__test-event.ics");
+        let renamed = dir.join("standup notes.ics");
+        std::fs::rename(&original, &renamed).unwrap();
+
+        let diff = connection.offline_diff(&DateRange::default()).unwrap();
+        assert!(diff.is_empty(), "got {:?}", diff.outgoing());
+
+        connection
+            .apply_incoming_diff(&CalendarDiff::from_changes(vec![], vec![]))
+            .unwrap();
+
+        assert!(renamed.is_file());
+        assert!(!original.exists());
+    }
+
+    #[tokio::test]
+    async fn renamed_files_get_their_name_back_with_restore_file_names() {
+        let (_tmp, caldir) = test_caldir();
+        let mut config = calendar_config(Some(false));
+        config.set("restore_file_names", "true").unwrap();
+        let calendar = caldir.create_calendar("restored", Some(config)).unwrap();
+        let mock = test_mock_provider();
+        let mut connection =
+            Connection::new(calendar, Remote::new(mock.provider(), test_remote_params()));
+        connection
+            .apply_incoming_diff(&incoming_create_diff(test_event()))
+            .unwrap();
+
+        let dir = connection.local().path().to_path_buf();
+        let original = dir.join("2026-01-01T1200__test-event.ics");
+        let renamed = dir.join("standup notes.ics");
+        std::fs::rename(&original, &renamed).unwrap();
+
+        connection
+            .apply_incoming_diff(&CalendarDiff::from_changes(vec![], vec![]))
+            .unwrap();
+
+        assert!(original.is_file());
+        assert!(!renamed.exists());
+    }
+
+    #[tokio::test]
+    async fn apply_incoming_diff_trashes_file_for_incoming_delete() {
         let (_tmp, _mock, mut connection) = writable_connection();
         let event = test_event();
         let cal_event = connection.local().create_event(event.clone()).unwrap();
         let path = cal_event.path().to_path_buf();
 
         connection
-            .apply_incoming_diff(&incoming_delete_diff(event))
+            .apply_incoming_diff(&incoming_delete_diff(event.clone()))
             .unwrap();
 
         assert!(!path.exists());
+        let trash = connection.local().trash().unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].event(), &event);
+    }
+
+    #[tokio::test]
+    async fn incoming_updates_follow_a_renamed_series_file() {
+        let (_tmp, caldir) = test_caldir();
+        let mut config = calendar_config(Some(false));
+        config.set("single_file_series", "true").unwrap();
+        let calendar = caldir.create_calendar("series", Some(config)).unwrap();
+        let mock = test_mock_provider();
+        let mut connection =
+            Connection::new(calendar, Remote::new(mock.provider(), test_remote_params()));
+
+        let mut master = test_event();
+        master.recurrence = Some(Recurrence::new("FREQ=DAILY"));
+        let mut moved = master.clone();
+        moved.recurrence = None;
+        moved.recurrence_id = Some(RecurrenceId::from_event_time(master.start.clone()));
+        connection.local().create_event(master.clone()).unwrap();
+        connection.local().create_event(moved.clone()).unwrap();
+
+        let mut renamed = master.clone();
+        renamed.summary = Some("Renamed".to_string());
+        let mut relocated = moved.clone();
+        relocated.location = Some("Room 2".to_string());
+        let diff = CalendarDiff::from_changes(
+            Vec::new(),
+            vec![
+                EventChange::Update {
+                    from: master,
+                    to: renamed,
+                },
+                EventChange::Update {
+                    from: moved,
+                    to: relocated,
+                },
+            ],
+        );
+        connection.apply_incoming_diff(&diff).unwrap();
+
+        let events = connection.local().events().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].path(), events[1].path());
+        assert!(events[0].filename().unwrap().ends_with("renamed.ics"));
+        assert_eq!(events[1].event().location.as_deref(), Some("Room 2"));
+    }
+
+    #[tokio::test]
+    async fn incoming_overrides_go_into_the_series_file_created_in_the_same_pull() {
+        let (_tmp, caldir) = test_caldir();
+        let mut config = calendar_config(Some(false));
+        config.set("single_file_series", "true").unwrap();
+        let calendar = caldir.create_calendar("series", Some(config)).unwrap();
+        let mock = test_mock_provider();
+        let mut connection =
+            Connection::new(calendar, Remote::new(mock.provider(), test_remote_params()));
+
+        let mut master = test_event();
+        master.recurrence = Some(Recurrence::new("FREQ=DAILY"));
+        let overrides = (1..=3).map(|day| {
+            let mut moved = master.clone();
+            moved.recurrence = None;
+            moved.recurrence_id = Some(RecurrenceId::from_event_time(
+                master.start.shifted(chrono::Duration::days(day)),
+            ));
+            EventChange::Create(moved)
+        });
+        let diff = CalendarDiff::from_changes(
+            Vec::new(),
+            std::iter::once(EventChange::Create(master.clone()))
+                .chain(overrides)
+                .collect(),
+        );
+        connection.apply_incoming_diff(&diff).unwrap();
+
+        let events = connection.local().events().unwrap();
+        assert_eq!(events.len(), 4);
+        assert!(events.iter().all(|ce| ce.path() == events[0].path()));
+    }
+
+    #[tokio::test]
+    async fn restored_event_is_pushed_as_create() {
+        let (_tmp, _mock, mut connection) = writable_connection();
+        let event = test_event();
+        connection
+            .apply_incoming_diff(&incoming_create_diff(event.clone()))
+            .unwrap();
+        connection
+            .apply_incoming_diff(&incoming_delete_diff(event.clone()))
+            .unwrap();
+
+        let id = connection.local().trash().unwrap()[0].id().to_string();
+        let mut local = Calendar::load(connection.local().path()).unwrap();
+        local.restore_from_trash(&id).unwrap();
+
+        assert!(local.trash().unwrap().is_empty());
+        assert!(
+            !local
+                .state()
+                .synced_event_ids()
+                .contains(&event.event_instance_id())
+        );
     }
 
     #[tokio::test]
@@ -463,6 +1211,22 @@ mod tests {
         assert!(!connection.local().state().synced_event_ids().contains(&id));
     }
 
+    #[tokio::test]
+    async fn apply_incoming_diff_forgets_the_base_of_deleted_events() {
+        let (_tmp, _mock, mut connection) = writable_connection();
+        let event = test_event();
+        let id = event.event_instance_id();
+        connection
+            .apply_incoming_diff(&incoming_create_diff(event.clone()))
+            .unwrap();
+
+        connection
+            .apply_incoming_diff(&incoming_delete_diff(event))
+            .unwrap();
+
+        assert!(!connection.local().state().synced_event_ids().contains(&id));
+    }
+
     #[tokio::test]
     async fn apply_outgoing_diff_sends_create_event_for_outgoing_create() {
         let (_tmp, mock, mut connection) = writable_connection();
@@ -588,9 +1352,6 @@ mod tests {
 
     #[tokio::test]
     async fn apply_outgoing_diff_persists_synced_ids_on_partial_success() {
-        use crate::provider::transport::ProviderTransportError;
-        use std::time::Duration;
-
         let (_tmp, mock, mut connection) = writable_connection();
 
         let event_a = test_event();
@@ -600,9 +1361,13 @@ mod tests {
         connection.local().create_event(event_b.clone()).unwrap();
 
         // First Create succeeds!
-        // Second errors mid-loop!
-        mock.reply::<rpc::CreateEvent>(event_a.clone());
-        mock.reply_error(ProviderTransportError::Timeout(Duration::from_secs(1)));
+        // Second is rejected by the provider!
+        mock.reply::<rpc::BatchWrite>(vec![
+            rpc::Response::Success {
+                data: event_a.clone(),
+            },
+            rpc::Response::failure("rejected"),
+        ]);
 
         let diff = CalendarDiff::from_changes(
             vec![
@@ -628,6 +1393,183 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn apply_outgoing_diff_falls_back_to_single_calls_without_batch_support() {
+        use crate::provider::transport::ProviderTransportError;
+        use std::time::Duration;
+
+        let (_tmp, mock, mut connection) = writable_connection();
+
+        let event_a = test_event();
+        let event_b = test_event();
+        let id_a = event_a.event_instance_id();
+        connection.local().create_event(event_a.clone()).unwrap();
+        connection.local().create_event(event_b.clone()).unwrap();
+
+        // A provider built before batch_write existed:
+        mock.reply_failure("Failed to parse request: unknown variant `batch_write`");
+        mock.reply::<rpc::CreateEvent>(event_a.clone());
+        mock.reply_error(ProviderTransportError::Timeout(Duration::from_secs(1)));
+
+        let diff = CalendarDiff::from_changes(
+            vec![
+                EventChange::Create(event_a.clone()),
+                EventChange::Create(event_b),
+            ],
+            vec![],
+        );
+
+        let result = connection.apply_outgoing_diff(&diff).await;
+
+        assert!(result.is_err(), "expected the second create to fail");
+
+        let reloaded = Calendar::load(connection.local().path()).unwrap();
+        assert_eq!(reloaded.state().sync_base(&id_a), Some(&event_a));
+    }
+
+    #[tokio::test]
+    async fn apply_outgoing_diff_pushes_new_overrides_after_their_master() {
+        let (_tmp, mock, mut connection) = writable_connection();
+
+        let mut master = test_event();
+        master.recurrence = Some(Recurrence::new("FREQ=DAILY"));
+        let mut moved = master.clone();
+        moved.recurrence = None;
+        moved.recurrence_id = Some(RecurrenceId::from_event_time(master.start.clone()));
+        moved.summary = Some("Moved".to_string());
+        connection.local().create_event(master.clone()).unwrap();
+        connection.local().create_event(moved.clone()).unwrap();
+
+        let mut created_master = master.clone();
+        created_master
+            .x_properties
+            .push(XProperty::new("X-GOOGLE-EVENT-ID", "abc123"));
+        mock.reply::<rpc::CreateEvent>(created_master);
+        mock.reply::<rpc::CreateEvent>(moved.clone());
+
+        // The override comes first, as the diff may well list it
+        let diff = CalendarDiff::from_changes(
+            vec![
+                EventChange::Create(moved.clone()),
+                EventChange::Create(master),
+            ],
+            vec![],
+        );
+        connection.apply_outgoing_diff(&diff).await.unwrap();
+
+        let pushed = mock.captured_request::<rpc::CreateEvent>().event;
+        assert_eq!(pushed.recurrence_id, moved.recurrence_id);
+        assert_eq!(pushed.x_property("X-GOOGLE-EVENT-ID"), Some("abc123"));
+    }
+
+    #[tokio::test]
+    async fn apply_outgoing_diff_pushes_a_deleted_occurrence_as_an_exdate() {
+        let (_tmp, mock, mut connection) = writable_connection();
+
+        let mut master = test_event();
+        master.recurrence = Some(Recurrence::new("FREQ=DAILY"));
+        let mut moved = master.clone();
+        moved.recurrence = None;
+        moved.recurrence_id = Some(RecurrenceId::from_event_time(master.start.clone()));
+        connection.local().create_event(master.clone()).unwrap();
+
+        let mut excluded = master.clone();
+        excluded
+            .recurrence
+            .as_mut()
+            .unwrap()
+            .exdates
+            .push(master.start.clone());
+        mock.reply::<rpc::UpdateEvent>(excluded.clone());
+
+        connection
+            .apply_outgoing_diff(&outgoing_delete_diff(moved))
+            .await
+            .unwrap();
+
+        assert_eq!(mock.captured_request::<rpc::UpdateEvent>().event, excluded);
+        let reloaded = connection.local().events().unwrap();
+        assert_eq!(reloaded[0].event(), &excluded);
+    }
+
+    #[tokio::test]
+    async fn apply_outgoing_diff_cancels_occurrences_where_the_provider_can() {
+        let (_tmp, caldir) = test_caldir();
+        let calendar = caldir
+            .create_calendar("writable-cal", Some(calendar_config(Some(false))))
+            .unwrap();
+        let mock = test_mock_provider();
+        mock.set_capabilities(Capabilities {
+            cancel_occurrences: true,
+            ..Capabilities::default()
+        });
+        let mut connection =
+            Connection::new(calendar, Remote::new(mock.provider(), test_remote_params()));
+
+        let mut master = test_event();
+        master.recurrence = Some(Recurrence::new("FREQ=DAILY"));
+        let mut moved = master.clone();
+        moved.recurrence = None;
+        moved.recurrence_id = Some(RecurrenceId::from_event_time(master.start.clone()));
+        connection.local().create_event(master).unwrap();
+
+        mock.reply::<rpc::DeleteEvent>(());
+
+        connection
+            .apply_outgoing_diff(&outgoing_delete_diff(moved.clone()))
+            .await
+            .unwrap();
+
+        assert_eq!(mock.captured_request::<rpc::DeleteEvent>().event, moved);
+    }
+
+    #[tokio::test]
+    async fn apply_incoming_diff_logs_changed_fields() {
+        let (_tmp, _mock, mut connection) = writable_connection();
+        let from = test_event();
+        connection.local().create_event(from.clone()).unwrap();
+
+        let mut to = from.clone();
+        to.summary = Some("Updated Test Event".to_string());
+
+        connection
+            .apply_incoming_diff(&incoming_update_diff(from, to))
+            .unwrap();
+
+        let log = connection.local().sync_log().unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].direction, SyncDirection::Pull);
+        assert_eq!(log[0].kind, crate::SyncChangeKind::Update);
+        assert_eq!(log[0].changed, ["summary"]);
+        assert_eq!(log[0].error, None);
+        assert!(connection.local().last_synced().is_some());
+    }
+
+    #[tokio::test]
+    async fn apply_outgoing_diff_logs_failed_changes() {
+        let (_tmp, mock, mut connection) = writable_connection();
+        let event = test_event();
+        connection.local().create_event(event.clone()).unwrap();
+
+        mock.reply_failure("Calendar is full");
+        let result = connection
+            .apply_outgoing_diff(&outgoing_create_diff(event))
+            .await;
+        assert!(result.is_err());
+
+        assert_eq!(connection.local().last_synced(), None);
+
+        let log = connection.local().sync_log().unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].direction, SyncDirection::Push);
+        assert!(
+            log[0]
+                .error
+                .as_deref()
+                .is_some_and(|error| error.contains("Calendar is full"))
+        );
+    }
+
     #[tokio::test]
     async fn discard_outgoing_diff_deletes_file_for_outgoing_create() {
         let (_tmp, _mock, connection) = writable_connection();
@@ -1,5 +1,6 @@
 use crate::calendar::CalendarError;
 use crate::remote::RemoteError;
+use crate::rpc::ErrorCode;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ConnectionError {
@@ -9,3 +10,18 @@ pub enum ConnectionError {
     #[error("Local calendar error: {0}")]
     Calendar(#[from] CalendarError),
 }
+
+impl ConnectionError {
+    /// The provider's error code, if it sent one.
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Self::Remote(e) => e.code(),
+            Self::Calendar(_) => None,
+        }
+    }
+
+    /// Whether the provider couldn't be reached, e.g. because the network is down.
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, Self::Remote(e) if e.is_unreachable())
+    }
+}
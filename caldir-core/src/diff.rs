@@ -1,5 +1,7 @@
 mod calendar_diff;
 mod event_change;
+mod planned_step;
 
 pub use calendar_diff::CalendarDiff;
 pub use event_change::EventChange;
+pub use planned_step::PlannedStep;
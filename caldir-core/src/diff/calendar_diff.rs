@@ -3,11 +3,14 @@ use std::collections::{HashMap, HashSet};
 use super::event_change::EventChange;
 use crate::calendar::SyncBases;
 use crate::event::Status;
-use crate::{CalendarEvent, DateRange, RemoteEvent};
+use crate::{CalendarEvent, DateRange, Event, RemoteEvent};
 
 pub struct CalendarDiff {
     outgoing: Vec<EventChange>,
     incoming: Vec<EventChange>,
+    /// Local changes held back because the remote changed since the last
+    /// sync; pushing them would overwrite that change
+    conflicts: Vec<EventChange>,
 }
 
 impl CalendarDiff {
@@ -17,25 +20,29 @@ impl CalendarDiff {
         sync_bases: &SyncBases,
         range: &DateRange,
     ) -> Self {
-        let local_event_ids: HashSet<_> = local_events
+        // Instance ids are derived from several fields, so work them out
+        // once per event rather than per lookup
+        let local: Vec<_> = local_events
             .iter()
-            .map(|e| e.event().event_instance_id())
+            .map(|e| (e.event().event_instance_id(), e))
             .collect();
-
-        let remote_by_id: HashMap<_, &RemoteEvent> = remote_events
+        let remote: Vec<_> = remote_events
             .iter()
             .map(|e| (e.event().event_instance_id(), e))
             .collect();
 
+        let local_event_ids: HashSet<_> = local.iter().map(|(id, _)| id).collect();
+        let remote_by_id: HashMap<_, _> = remote.iter().map(|(id, e)| (id, *e)).collect();
+
         let mut outgoing = Vec::new();
         let mut incoming = Vec::new();
+        let mut conflicts = Vec::new();
 
-        for local_event in &local_events {
+        for (id, local_event) in &local {
             let event = local_event.event();
-            let id = event.event_instance_id();
 
             // In both local and remote: skip if equal, otherwise update
-            if let Some(remote_event) = remote_by_id.get(&id) {
+            if let Some(remote_event) = remote_by_id.get(id) {
                 if event == remote_event.event() {
                     continue;
                 }
@@ -54,7 +61,7 @@ impl CalendarDiff {
                     to_push.visibility = remote_event.event().visibility;
                 }
 
-                let base = sync_bases.get(&id).and_then(Option::as_deref);
+                let base = sync_bases.get(id).and_then(Option::as_deref);
                 let direction = if &to_push == remote_event.event() {
                     Some(UpdateDirection::Pull)
                 } else {
@@ -62,10 +69,17 @@ impl CalendarDiff {
                 };
 
                 match direction {
-                    Some(UpdateDirection::Push) => outgoing.push(EventChange::Update {
-                        from: remote_event.event().clone(),
-                        to: to_push,
-                    }),
+                    Some(UpdateDirection::Push) => {
+                        let change = EventChange::Update {
+                            from: remote_event.event().clone(),
+                            to: to_push,
+                        };
+                        if base.is_some_and(|base| remote_event.event().changed_since(base)) {
+                            conflicts.push(change);
+                        } else {
+                            outgoing.push(change);
+                        }
+                    }
                     Some(UpdateDirection::Pull) => incoming.push(EventChange::Update {
                         from: event.clone(),
                         to: remote_event.event().clone(),
@@ -83,18 +97,16 @@ impl CalendarDiff {
                 continue;
             }
 
-            if sync_bases.get(&id).is_some() {
+            if sync_bases.get(id).is_some() {
                 incoming.push(EventChange::Delete(event.clone()));
             } else {
                 outgoing.push(EventChange::Create(event.clone()));
             }
         }
 
-        for remote_event in &remote_events {
-            let id = remote_event.event().event_instance_id();
-
+        for (id, remote_event) in &remote {
             // Already in local and remote, skip
-            if local_event_ids.contains(&id) {
+            if local_event_ids.contains(id) {
                 continue;
             }
 
@@ -107,16 +119,85 @@ impl CalendarDiff {
                 continue;
             }
 
-            if sync_bases.get(&id).is_some() {
-                // Remote event was in local, gone now. Delete remotely.
-                outgoing.push(EventChange::Delete(remote_event.event().clone()));
+            if let Some(base) = sync_bases.get(id) {
+                // Remote event was in local, gone now. Delete remotely,
+                // unless it changed there since.
+                let change = EventChange::Delete(remote_event.event().clone());
+                if base
+                    .as_deref()
+                    .is_some_and(|base| remote_event.event().changed_since(base))
+                {
+                    conflicts.push(change);
+                } else {
+                    outgoing.push(change);
+                }
             } else {
                 // Not in local, create it!
                 incoming.push(EventChange::Create(remote_event.event().clone()));
             }
         }
 
-        CalendarDiff { outgoing, incoming }
+        CalendarDiff {
+            outgoing,
+            incoming,
+            conflicts,
+        }
+    }
+
+    /// Local changes since the last sync, from comparing `local_events` with
+    /// their sync bases rather than with the remote. Everything is outgoing.
+    /// Events only known by id (legacy state) can't be compared and are skipped.
+    pub(crate) fn compute_offline(
+        local_events: Vec<CalendarEvent>,
+        sync_bases: &SyncBases,
+        range: &DateRange,
+    ) -> Self {
+        let in_range = |event: &Event| match (range.from, range.to) {
+            (Some(from), Some(to)) => event.has_occurrence_in_range(from, to),
+            _ => true,
+        };
+
+        let mut outgoing = Vec::new();
+        let mut local_event_ids = HashSet::new();
+
+        for local_event in &local_events {
+            let event = local_event.event();
+            let id = event.event_instance_id();
+
+            // Both cancelled counts as in sync, as in `compute`
+            let both_cancelled = |base: &Event| {
+                base.status == Status::Cancelled && event.status == Status::Cancelled
+            };
+
+            match sync_bases.get(&id) {
+                Some(Some(base)) if **base != *event && !both_cancelled(base) => {
+                    outgoing.push(EventChange::Update {
+                        from: base.as_ref().clone(),
+                        to: event.clone(),
+                    })
+                }
+                Some(_) => {}
+                None if in_range(event) => outgoing.push(EventChange::Create(event.clone())),
+                None => {}
+            }
+
+            local_event_ids.insert(id);
+        }
+
+        let mut deleted: Vec<&Event> = sync_bases
+            .iter()
+            .filter(|(id, _)| !local_event_ids.contains(*id))
+            .filter_map(|(_, base)| base.as_deref())
+            .filter(|base| base.status != Status::Cancelled && in_range(base))
+            .collect();
+        deleted.sort_by_key(|base| base.start.to_utc());
+        outgoing.extend(deleted.into_iter().cloned().map(EventChange::Delete));
+
+        CalendarDiff {
+            outgoing,
+            incoming: Vec::new(),
+            conflicts: Vec::new(),
+        }
     }
 
     pub fn incoming(&self) -> &[EventChange] {
@@ -127,21 +208,84 @@ impl CalendarDiff {
         &self.outgoing
     }
 
+    /// Local changes not pushed because the remote changed too.
+    pub fn conflicts(&self) -> &[EventChange] {
+        &self.conflicts
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.outgoing.is_empty() && self.incoming.is_empty()
+        self.outgoing.is_empty() && self.incoming.is_empty() && self.conflicts.is_empty()
+    }
+
+    /// Push the conflicting local changes anyway, overwriting the remote's.
+    pub fn overwrite_conflicts(&mut self) {
+        self.outgoing.append(&mut self.conflicts);
     }
 
     /// Drop outgoing changes. Used for read-only calendars where outgoing
     /// could never be applied — surfacing them as pending pushes is misleading.
     pub fn discard_outgoing(&mut self) {
         self.outgoing.clear();
+        self.conflicts.clear();
+    }
+
+    /// Drop outgoing changes matching `excluded`, e.g. deletes for a provider
+    /// that can't delete.
+    pub fn discard_outgoing_where(&mut self, excluded: impl Fn(&EventChange) -> bool) {
+        self.outgoing.retain(|change| !excluded(change));
+        self.conflicts.retain(|change| !excluded(change));
+    }
+
+    /// Keep only changes to the event with this UID (and its overrides).
+    pub fn retain_uid(&mut self, uid: &str) {
+        self.outgoing
+            .retain(|change| change.event().uid.as_str() == uid);
+        self.incoming
+            .retain(|change| change.event().uid.as_str() == uid);
+        self.conflicts
+            .retain(|change| change.event().uid.as_str() == uid);
+    }
+
+    /// Hide what the changed events are about, keeping when they happen.
+    pub fn redact(&mut self) {
+        for change in self
+            .outgoing
+            .iter_mut()
+            .chain(&mut self.incoming)
+            .chain(&mut self.conflicts)
+        {
+            *change = change.redacted();
+        }
+    }
+
+    /// Drop incoming creates for events matching `excluded`, so they are never
+    /// pulled. Updates and deletes for events already on disk are kept.
+    pub fn discard_incoming_creates(&mut self, excluded: impl Fn(&Event) -> bool) {
+        self.incoming
+            .retain(|change| !matches!(change, EventChange::Create(event) if excluded(event)));
+    }
+
+    /// Turn incoming updates that now match `excluded` into deletes, so an
+    /// event already on disk leaves the calendar once it starts matching.
+    pub fn delete_incoming_updates(&mut self, excluded: impl Fn(&Event) -> bool) {
+        for change in &mut self.incoming {
+            if let EventChange::Update { from, to } = change
+                && excluded(to)
+            {
+                *change = EventChange::Delete(from.clone());
+            }
+        }
     }
 }
 
 #[cfg(test)]
 impl CalendarDiff {
     pub(crate) fn from_changes(outgoing: Vec<EventChange>, incoming: Vec<EventChange>) -> Self {
-        Self { outgoing, incoming }
+        Self {
+            outgoing,
+            incoming,
+            conflicts: Vec::new(),
+        }
     }
 }
 
@@ -199,6 +343,71 @@ mod tests {
     use chrono::{TimeZone, Utc};
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn retain_uid_keeps_only_changes_to_that_event() {
+        let kept = test_event();
+        let dropped = test_event();
+        let mut diff = CalendarDiff::from_changes(
+            vec![
+                EventChange::Create(kept.clone()),
+                EventChange::Create(dropped.clone()),
+            ],
+            vec![EventChange::Delete(dropped)],
+        );
+
+        diff.retain_uid(kept.uid.as_str());
+
+        assert_eq!(diff.outgoing, vec![EventChange::Create(kept)]);
+        assert_eq!(diff.incoming, vec![]);
+    }
+
+    #[test]
+    fn discard_incoming_creates_keeps_updates_and_deletes() {
+        let event = test_event();
+        let mut diff = CalendarDiff::from_changes(
+            vec![],
+            vec![
+                EventChange::Create(event.clone()),
+                EventChange::Update {
+                    from: event.clone(),
+                    to: event.clone(),
+                },
+                EventChange::Delete(event.clone()),
+            ],
+        );
+
+        diff.discard_incoming_creates(|_| true);
+
+        assert_eq!(
+            diff.incoming,
+            vec![
+                EventChange::Update {
+                    from: event.clone(),
+                    to: event.clone(),
+                },
+                EventChange::Delete(event),
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_incoming_updates_deletes_the_local_copy() {
+        let from = test_event();
+        let mut to = from.clone();
+        to.summary = Some("Declined".to_string());
+        let mut diff = CalendarDiff::from_changes(
+            vec![],
+            vec![EventChange::Update {
+                from: from.clone(),
+                to: to.clone(),
+            }],
+        );
+
+        diff.delete_incoming_updates(|event| event.summary.as_deref() == Some("Declined"));
+
+        assert_eq!(diff.incoming, vec![EventChange::Delete(from)]);
+    }
+
     #[test]
     fn new_local_event_becomes_outgoing_create() {
         let (_tmp, calendar_event) = test_calendar_event();
@@ -578,6 +787,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn local_update_is_held_back_when_remote_changed_since_base() {
+        let (_tmp, calendar) = test_calendar();
+        let base = test_event();
+
+        let mut local = base.clone();
+        local.summary = Some("Edited locally".to_string());
+        local.last_modified = Some(Utc.with_ymd_and_hms(2026, 3, 5, 8, 45, 16).unwrap());
+        let calendar_event = calendar.create_event(local.clone()).unwrap();
+
+        let mut remote = base.clone();
+        remote.location = Some("Room 2".to_string());
+        remote.last_modified = Some(Utc.with_ymd_and_hms(2025, 6, 9, 10, 42, 20).unwrap());
+
+        let mut sync_bases = SyncBases::new();
+        sync_bases.insert_event_base(base.event_instance_id(), base);
+
+        let mut diff = CalendarDiff::compute(
+            vec![calendar_event],
+            vec![RemoteEvent::new(remote.clone())],
+            &sync_bases,
+            &DateRange::default(),
+        );
+
+        let update = EventChange::Update {
+            from: remote,
+            to: local,
+        };
+        assert_eq!(diff.outgoing, vec![]);
+        assert_eq!(diff.conflicts, vec![update.clone()]);
+
+        diff.overwrite_conflicts();
+        assert_eq!(diff.outgoing, vec![update]);
+        assert_eq!(diff.conflicts, vec![]);
+    }
+
+    #[test]
+    fn remote_sequence_bump_holds_back_local_changes() {
+        let (_tmp, calendar) = test_calendar();
+        let mut base = test_event();
+        base.sequence = 1;
+        base.last_modified = Some(Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap());
+
+        let mut local = base.clone();
+        local.summary = Some("Edited locally".to_string());
+        let calendar_event = calendar.create_event(local).unwrap();
+
+        // Same fields as far as caldir can tell, but rescheduled remotely
+        let mut remote = base.clone();
+        remote.sequence = 2;
+
+        let mut deleted = test_event();
+        deleted.summary = Some("Deleted locally".to_string());
+        deleted.last_modified = base.last_modified;
+        let mut remote_deleted = deleted.clone();
+        remote_deleted.last_modified = Some(Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap());
+
+        let mut sync_bases = SyncBases::new();
+        sync_bases.insert_event_base(base.event_instance_id(), base);
+        sync_bases.insert_event_base(deleted.event_instance_id(), deleted);
+
+        let diff = CalendarDiff::compute(
+            vec![calendar_event],
+            vec![
+                RemoteEvent::new(remote),
+                RemoteEvent::new(remote_deleted.clone()),
+            ],
+            &sync_bases,
+            &DateRange::default(),
+        );
+
+        assert_eq!(diff.outgoing, vec![]);
+        assert_eq!(diff.conflicts.len(), 2);
+        assert!(
+            diff.conflicts
+                .contains(&EventChange::Delete(remote_deleted))
+        );
+    }
+
     #[test]
     fn sync_metadata_only_differences_produce_no_diff() {
         // Differences confined to LAST-MODIFIED and SEQUENCE are sync noise —
@@ -857,4 +1145,56 @@ mod tests {
         assert_eq!(diff.outgoing, vec![]);
         assert_eq!(diff.incoming, vec![EventChange::Delete(event)]);
     }
+
+    #[test]
+    fn offline_diff_compares_local_events_with_their_bases() {
+        let (_tmp, calendar) = test_calendar();
+
+        let base = test_event();
+        let mut edited = base.clone();
+        edited.location = Some("Room 2".into());
+        let edited_file = calendar.create_event(edited.clone()).unwrap();
+
+        let created = Event::new("New", base.start.clone());
+        let created_file = calendar.create_event(created.clone()).unwrap();
+
+        let deleted = Event::new("Gone", base.start.clone());
+
+        let mut sync_bases = SyncBases::new();
+        sync_bases.insert_event_base(base.event_instance_id(), base.clone());
+        sync_bases.insert_event_base(deleted.event_instance_id(), deleted.clone());
+
+        let diff = CalendarDiff::compute_offline(
+            vec![edited_file, created_file],
+            &sync_bases,
+            &DateRange::default(),
+        );
+
+        assert_eq!(
+            diff.outgoing,
+            vec![
+                EventChange::Update {
+                    from: base,
+                    to: edited,
+                },
+                EventChange::Create(created),
+                EventChange::Delete(deleted),
+            ]
+        );
+        assert_eq!(diff.incoming, vec![]);
+    }
+
+    #[test]
+    fn offline_diff_skips_events_only_known_by_id() {
+        let (_tmp, calendar_event) = test_calendar_event();
+
+        let mut sync_bases = SyncBases::new();
+        sync_bases.insert_known_event_id(calendar_event.event().event_instance_id());
+        sync_bases.insert_known_event_id(test_event().event_instance_id());
+
+        let diff =
+            CalendarDiff::compute_offline(vec![calendar_event], &sync_bases, &DateRange::default());
+
+        assert!(diff.is_empty());
+    }
 }
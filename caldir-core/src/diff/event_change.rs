@@ -1,9 +1,31 @@
 use crate::Event;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(clippy::large_enum_variant)]
 pub enum EventChange {
     Create(Event),
     Update { from: Event, to: Event },
     Delete(Event),
 }
+
+impl EventChange {
+    /// The event as the change leaves it (as it was, for deletes).
+    pub fn event(&self) -> &Event {
+        match self {
+            EventChange::Create(event) | EventChange::Delete(event) => event,
+            EventChange::Update { to, .. } => to,
+        }
+    }
+
+    /// The change with its events [redacted](Event::redacted).
+    pub fn redacted(&self) -> Self {
+        match self {
+            EventChange::Create(event) => EventChange::Create(event.redacted()),
+            EventChange::Update { from, to } => EventChange::Update {
+                from: from.redacted(),
+                to: to.redacted(),
+            },
+            EventChange::Delete(event) => EventChange::Delete(event.redacted()),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use crate::rpc::Method;
+
+/// Something applying a diff would do, for `--dry-run`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlannedStep {
+    CreateFile(PathBuf),
+    UpdateFile(PathBuf),
+    RenameFile {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    DeleteFile(PathBuf),
+    /// A provider call writing `events` (summaries)
+    Call {
+        method: Method,
+        events: Vec<String>,
+    },
+}
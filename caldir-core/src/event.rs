@@ -1,9 +1,12 @@
 mod attachment;
 mod attendee;
 mod availability;
+mod conference;
 mod error;
+mod event_type;
 mod from_icalendar;
 mod instance_id;
+mod markdown;
 mod occurrences;
 mod organizer;
 mod recurrence;
@@ -16,13 +19,16 @@ pub mod tz_normalize;
 mod visibility;
 mod x_property;
 
+use crate::FloatingTimezone;
 pub use attachment::Attachment;
 pub use attendee::{Attendee, ParticipationStatus};
 pub use availability::Availability;
 use chrono::{DateTime, Utc};
+pub use conference::Conference;
 pub use error::EventError;
+pub use event_type::EventType;
 pub use instance_id::{EventInstanceId, EventUid, RecurrenceId};
-pub use occurrences::expand_in_range;
+pub use occurrences::{expand_in_range, expand_in_range_with};
 pub use organizer::Organizer;
 pub use recurrence::Recurrence;
 pub use reminder::Reminder;
@@ -35,6 +41,9 @@ pub use x_property::XProperty;
 const ICS_PRODID: &str = "CALDIR";
 const ICS_VERSION: &str = "2.0";
 const ICS_UID_DOMAIN: &str = "caldir";
+/// Properties that may repeat (RFC 7986) but the icalendar parser doesn't
+/// know about.
+const REPEATED_PROPERTIES: [&str; 1] = ["CONFERENCE"];
 
 #[derive(Debug, Clone, Eq, educe::Educe)]
 #[educe(PartialEq)]
@@ -48,12 +57,21 @@ pub struct Event {
     pub status: Status,
     pub availability: Availability,
     pub visibility: Option<Visibility>,
+    /// Out of office, focus time and the like. Kept in an X-property.
+    pub event_type: EventType,
     pub recurrence: Option<Recurrence>,
     pub recurrence_id: Option<RecurrenceId>,
     pub organizer: Option<Organizer>,
     pub attendees: Vec<Attendee>,
     pub reminders: Vec<Reminder>,
+    /// Use the calendar's default reminders (as set at the provider) instead
+    /// of `reminders`. Without it, no `reminders` means none at all.
+    pub reminders_default: bool,
     pub url: Option<String>,
+    pub categories: Vec<String>,
+    /// RFC 7986 COLOR: a CSS3 color name (e.g. "tomato")
+    pub color: Option<String>,
+    pub conferences: Vec<Conference>,
 
     #[educe(PartialEq(method(attachments_eq)))]
     pub attachments: Vec<Attachment>,
@@ -80,6 +98,7 @@ impl Event {
             status: Status::default(),
             availability: Availability::default(),
             visibility: None,
+            event_type: EventType::Default,
             recurrence: None,
             recurrence_id: None,
             last_modified: None,
@@ -87,7 +106,11 @@ impl Event {
             organizer: None,
             attendees: Vec::new(),
             reminders: Vec::new(),
+            reminders_default: false,
             url: None,
+            categories: Vec::new(),
+            color: None,
+            conferences: Vec::new(),
             attachments: Vec::new(),
             x_properties: Vec::new(),
         }
@@ -95,11 +118,42 @@ impl Event {
 
     /// Parse ICS document to list of events
     pub fn from_ics_str(contents: &str) -> Result<Vec<Result<Self, EventError>>, EventError> {
-        let icalendar: icalendar::Calendar = contents
-            .parse()
+        use icalendar::Component;
+
+        let unfolded =
+            from_icalendar::protect_escaped_category_commas(&icalendar::parser::unfold(contents));
+        let parsed = icalendar::parser::read_calendar(&unfolded)
             .map_err(|err| EventError::InvalidIcs(contents.to_string(), err))?;
 
-        Ok(icalendar.events().map(Event::try_from).collect())
+        let events = parsed
+            .components
+            .into_iter()
+            .filter_map(|component| {
+                // The parser keeps only the last line of properties it doesn't
+                // know may repeat, so those are read off the raw component
+                let repeated: Vec<icalendar::Property> = component
+                    .properties
+                    .iter()
+                    .filter(|p| REPEATED_PROPERTIES.contains(&p.name.as_str()))
+                    .cloned()
+                    .map(Into::into)
+                    .collect();
+
+                let icalendar::CalendarComponent::Event(mut event) = component.into() else {
+                    return None;
+                };
+                for name in REPEATED_PROPERTIES {
+                    event.remove_property(name);
+                }
+                for property in repeated {
+                    event.append_multi_property(property);
+                }
+
+                Some(Event::try_from(event))
+            })
+            .collect();
+
+        Ok(events)
     }
 
     pub(crate) fn load_single(path: &std::path::Path) -> Result<Self, EventError> {
@@ -122,21 +176,72 @@ impl Event {
     }
 
     pub fn to_ics_string(&self) -> String {
+        self.ics_document(None)
+    }
+
+    /// One ICS document holding all of `events` as separate VEVENTs, the way
+    /// a recurring master and its overrides are usually exported.
+    pub fn series_to_ics_string(events: &[Event]) -> String {
+        let documents: Vec<String> = events.iter().map(Event::to_ics_string).collect();
+        let Some(first) = documents.first() else {
+            return String::new();
+        };
+
+        let header_end = first.find("BEGIN:VEVENT").unwrap_or(first.len());
+        let vevents: String = documents
+            .iter()
+            .filter_map(|ics| {
+                let start = ics.find("BEGIN:VEVENT")?;
+                let end = ics.rfind("END:VEVENT\r\n")? + "END:VEVENT\r\n".len();
+                Some(&ics[start..end])
+            })
+            .collect();
+
+        format!("{}{vevents}END:VCALENDAR\r\n", &first[..header_end])
+    }
+
+    /// ICS for an iTIP message (RFC 5546), with `method` (e.g. `REQUEST`)
+    /// set on the calendar.
+    pub fn to_itip_string(&self, method: &str) -> String {
+        self.ics_document(Some(method))
+    }
+
+    fn ics_document(&self, method: Option<&str>) -> String {
         let ical_event: icalendar::Event = self.into();
 
-        let ics = icalendar::Calendar::empty()
+        let mut calendar = icalendar::Calendar::empty();
+        calendar
             .append_property(icalendar::Property::new("VERSION", ICS_VERSION))
-            .append_property(icalendar::Property::new("PRODID", ICS_PRODID))
-            .push(ical_event)
-            .done()
-            .to_string();
+            .append_property(icalendar::Property::new("PRODID", ICS_PRODID));
+        if let Some(method) = method {
+            calendar.append_property(icalendar::Property::new("METHOD", method));
+        }
+
+        let ics = calendar.push(ical_event).done().to_string();
 
         self.splice_valarms_into_vevent(ics)
     }
 
     pub fn occurs_in_range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
-        let event_start = self.start.to_utc();
-        let event_end = self.end.as_ref().unwrap_or(&self.start).to_utc();
+        self.occurs_in_range_with(from, to, FloatingTimezone::Local)
+    }
+
+    /// Like occurs_in_range(), with floating times in `floating`.
+    pub fn occurs_in_range_with(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        floating: FloatingTimezone,
+    ) -> bool {
+        let event_start = self.start.to_utc_with(floating);
+        let event_end = match (&self.end, &self.start) {
+            (Some(end), _) => end.to_utc_with(floating),
+            // An all-day event without DTEND takes up its whole day
+            (None, EventTime::Date(date)) => {
+                EventTime::Date(*date + chrono::Duration::days(1)).to_utc_with(floating)
+            }
+            (None, start) => start.to_utc_with(floating),
+        };
 
         // Check if event overlaps with the range [from, to]
         event_start < to && event_end > from
@@ -146,8 +251,14 @@ impl Event {
     /// so a master with `UNTIL` in the past correctly reports no overlap.
     pub fn has_occurrence_in_range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
         if self.recurrence.is_some() {
-            !occurrences::expand_master(self, from, to, &std::collections::HashMap::new())
-                .is_empty()
+            !occurrences::expand_master(
+                self,
+                from,
+                to,
+                &std::collections::HashMap::new(),
+                FloatingTimezone::Local,
+            )
+            .is_empty()
         } else {
             self.occurs_in_range(from, to)
         }
@@ -203,6 +314,20 @@ impl Event {
             && self.attendee_status(email) == Some(ParticipationStatus::NeedsAction)
     }
 
+    /// True if any of the event's CATEGORIES matches `tag` (case-insensitive)
+    pub fn has_category(&self, tag: &str) -> bool {
+        self.categories.iter().any(|c| c.eq_ignore_ascii_case(tag))
+    }
+
+    /// Join link for the event: the first video conference, else the first conference
+    pub fn conference_url(&self) -> Option<&str> {
+        self.conferences
+            .iter()
+            .find(|c| c.has_feature("VIDEO"))
+            .or(self.conferences.first())
+            .map(|c| c.uri.as_str())
+    }
+
     /// Return a clone of this event with a fresh UID.
     pub fn with_new_uid(&self) -> Self {
         Event {
@@ -211,6 +336,85 @@ impl Event {
         }
     }
 
+    /// This event with only its timing left, for sharing availability
+    /// without what it's about: the title becomes "Busy" and descriptions,
+    /// locations, people, links and attachments are dropped.
+    pub fn redacted(&self) -> Self {
+        Event {
+            uid: self.uid.clone(),
+            end: self.end.clone(),
+            status: self.status,
+            availability: self.availability,
+            event_type: self.event_type,
+            recurrence: self.recurrence.clone(),
+            recurrence_id: self.recurrence_id.clone(),
+            ..Event::new("Busy", self.start.clone())
+        }
+    }
+
+    /// Move the event `by` later (earlier when negative), keeping its length.
+    /// EXDATEs move along, so the same occurrences stay excluded.
+    pub fn shift(&mut self, by: chrono::Duration) {
+        self.start = self.start.shifted(by);
+        self.end = self.end.as_ref().map(|end| end.shifted(by));
+        if let Some(recurrence) = self.recurrence.as_mut() {
+            for exdate in &mut recurrence.exdates {
+                *exdate = exdate.shifted(by);
+            }
+        }
+    }
+
+    /// Copy the ICS properties named in `properties` (e.g. `ATTENDEE`,
+    /// `X-GOOGLE-CONFERENCE`) from `other`, so differences in them don't
+    /// count as changes. Names the event doesn't model are skipped.
+    pub(crate) fn keep_properties(&mut self, other: &Event, properties: &[String]) {
+        for property in properties {
+            match property.to_ascii_uppercase().as_str() {
+                "SUMMARY" => self.summary = other.summary.clone(),
+                "DESCRIPTION" => self.description = other.description.clone(),
+                "LOCATION" => self.location = other.location.clone(),
+                "STATUS" => self.status = other.status,
+                "TRANSP" => self.availability = other.availability,
+                "CLASS" => self.visibility = other.visibility,
+                "ORGANIZER" => self.organizer = other.organizer.clone(),
+                "ATTENDEE" => self.attendees = other.attendees.clone(),
+                "VALARM" => {
+                    self.reminders = other.reminders.clone();
+                    self.reminders_default = other.reminders_default;
+                }
+                "URL" => self.url = other.url.clone(),
+                "CATEGORIES" => self.categories = other.categories.clone(),
+                "COLOR" => self.color = other.color.clone(),
+                "CONFERENCE" => self.conferences = other.conferences.clone(),
+                "ATTACH" => self.attachments = other.attachments.clone(),
+                name if name.starts_with("X-") => {
+                    self.x_properties
+                        .retain(|x| !x.name.eq_ignore_ascii_case(name));
+                    self.x_properties.extend(
+                        other
+                            .x_properties
+                            .iter()
+                            .filter(|x| x.name.eq_ignore_ascii_case(name))
+                            .cloned(),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether this (remote) event changed since it was synced as `base`:
+    /// its content differs, or the provider bumped its SEQUENCE or
+    /// LAST-MODIFIED. Bases are stored as ICS, so only whole seconds count.
+    pub(crate) fn changed_since(&self, base: &Event) -> bool {
+        let modified_later = matches!(
+            (self.last_modified, base.last_modified),
+            (Some(modified), Some(synced)) if modified.timestamp() > synced.timestamp()
+        );
+
+        self != base || self.sequence > base.sequence || modified_later
+    }
+
     /// Find the first x-property value matching the given name.
     pub fn x_property(&self, name: &str) -> Option<&str> {
         self.x_properties
@@ -272,6 +476,61 @@ impl Event {
         }
         self
     }
+
+    /// This event with its floating times placed in `floating`, for
+    /// providers that can't store a time without a timezone.
+    pub fn with_floating_times_pinned(mut self, floating: FloatingTimezone) -> Self {
+        self.start = self.start.pinned(floating);
+        self.end = self.end.as_ref().map(|end| end.pinned(floating));
+        if let Some(recurrence) = &mut self.recurrence {
+            for time in recurrence.exdates.iter_mut().chain(&mut recurrence.rdates) {
+                *time = time.pinned(floating);
+            }
+        }
+        self
+    }
+
+    /// Names of the fields that differ from `other`, compared like `==` does.
+    pub fn changed_fields(&self, other: &Event) -> Vec<&'static str> {
+        let fields = [
+            ("summary", self.summary != other.summary),
+            ("description", self.description != other.description),
+            ("location", self.location != other.location),
+            ("start", self.start != other.start),
+            ("end", self.end != other.end),
+            ("status", self.status != other.status),
+            ("availability", self.availability != other.availability),
+            ("visibility", self.visibility != other.visibility),
+            ("event_type", self.event_type != other.event_type),
+            ("recurrence", self.recurrence != other.recurrence),
+            ("recurrence_id", self.recurrence_id != other.recurrence_id),
+            ("organizer", self.organizer != other.organizer),
+            ("attendees", self.attendees != other.attendees),
+            (
+                "reminders",
+                self.reminders != other.reminders
+                    || self.reminders_default != other.reminders_default,
+            ),
+            ("url", self.url != other.url),
+            ("categories", self.categories != other.categories),
+            ("color", self.color != other.color),
+            ("conferences", self.conferences != other.conferences),
+            (
+                "attachments",
+                !attachments_eq(&self.attachments, &other.attachments),
+            ),
+            (
+                "x_properties",
+                !x_properties_eq(&self.x_properties, &other.x_properties),
+            ),
+        ];
+
+        fields
+            .into_iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(name, _)| name)
+            .collect()
+    }
 }
 
 // Wire format for events is ICS, not JSON
@@ -289,7 +548,7 @@ impl<'de> Deserialize<'de> for Event {
     }
 }
 
-fn new_uid() -> EventUid {
+pub(crate) fn new_uid() -> EventUid {
     let uid = format!("{}@{}", uuid::Uuid::new_v4(), ICS_UID_DOMAIN);
     EventUid::new(uid)
 }
@@ -325,6 +584,49 @@ mod tests {
         assert!(uuid::Uuid::parse_str(prefix).is_ok());
     }
 
+    #[test]
+    fn redacted_keeps_only_the_timing() {
+        let mut event = Event::new(
+            "Layoffs planning",
+            time::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+        );
+        event.description = Some("Confidential".to_string());
+        event.location = Some("Board room".to_string());
+        event.attendees = vec![Attendee::new("ceo@example.com")];
+        event.recurrence = Some(Recurrence::new("FREQ=WEEKLY"));
+
+        let redacted = event.redacted();
+
+        assert_eq!(redacted.summary.as_deref(), Some("Busy"));
+        assert_eq!(redacted.description, None);
+        assert_eq!(redacted.location, None);
+        assert!(redacted.attendees.is_empty());
+        assert_eq!(redacted.uid, event.uid);
+        assert_eq!(redacted.start, event.start);
+        assert_eq!(redacted.recurrence, event.recurrence);
+    }
+
+    #[test]
+    fn keep_properties_copies_only_the_named_ones() {
+        let local = Event::new(
+            "Standup",
+            time::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+        );
+        let mut remote = local.clone();
+        remote.summary = Some("Daily standup".to_string());
+        remote.attendees = vec![Attendee::new("bob@example.com")];
+        remote.x_properties = vec![XProperty::new("X-GOOGLE-CONFERENCE", "https://meet")];
+
+        remote.keep_properties(
+            &local,
+            &["attendee".to_string(), "X-GOOGLE-CONFERENCE".to_string()],
+        );
+
+        assert!(remote.attendees.is_empty());
+        assert!(remote.x_properties.is_empty());
+        assert_eq!(remote.summary.as_deref(), Some("Daily standup"));
+    }
+
     #[test]
     fn new_generates_unique_uids() {
         let start = time::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
@@ -334,6 +636,18 @@ mod tests {
         assert_ne!(a.uid, b.uid);
     }
 
+    #[test]
+    fn changed_fields_lists_what_differs() {
+        let start = time::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        let a = Event::new("Test", start);
+        let mut b = a.clone();
+        b.summary = Some("Renamed".to_string());
+        b.location = Some("Office".to_string());
+        b.sequence += 1;
+
+        assert_eq!(a.changed_fields(&b), ["summary", "location"]);
+    }
+
     #[test]
     fn rejects_invalid_ics() {
         // Missing "END:VCALENDAR"
@@ -437,6 +751,28 @@ END:VCALENDAR"
         assert!(ics.contains("PRODID:CALDIR"));
     }
 
+    #[test]
+    fn series_to_ics_string_writes_one_vevent_per_event() {
+        let date = |day| EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, day).unwrap());
+        let mut master = Event::new("Standup", date(5));
+        master.recurrence = Some(Recurrence::new("FREQ=DAILY"));
+        master.reminders = vec![Reminder::from_minutes(10)];
+        let mut moved = master.clone();
+        moved.recurrence = None;
+        moved.recurrence_id = Some(RecurrenceId::from_event_time(date(6)));
+        moved.start = date(7);
+
+        let ics = Event::series_to_ics_string(&[master.clone(), moved.clone()]);
+
+        assert_eq!(ics.matches("BEGIN:VCALENDAR").count(), 1);
+        let parsed: Vec<Event> = Event::from_ics_str(&ics)
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(parsed, [master, moved]);
+    }
+
     #[test]
     fn to_ics_string_updates_dtstamp() {
         let original_ics = r"BEGIN:VCALENDAR
@@ -507,6 +843,55 @@ END:VCALENDAR
         assert_eq!(strip_dtstamp(&original_ics), strip_dtstamp(&serialized_ics));
     }
 
+    #[test]
+    fn round_trips_long_multiline_description() {
+        let description = "Agenda; part 1, part 2\n\n- Review last week's notes\n\
+            - A line long enough that the writer has to fold it across several physical lines";
+        let mut event = Event::new(
+            "Planning",
+            EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+        );
+        event.description = Some(description.to_string());
+
+        let ics = event.to_ics_string();
+        let parsed = Event::parse_single_ics(&ics);
+
+        assert_eq!(parsed.description.as_deref(), Some(description));
+    }
+
+    #[test]
+    fn round_trips_categories_containing_commas() {
+        let mut event = Event::new(
+            "Lunch",
+            EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+        );
+        event.categories = vec!["Smith, John".to_string(), "work".to_string()];
+
+        let ics = event.to_ics_string();
+        let parsed = Event::parse_single_ics(&ics);
+
+        assert!(ics.contains("CATEGORIES:Smith\\, John"));
+        assert_eq!(parsed.categories, event.categories);
+    }
+
+    #[test]
+    fn round_trips_video_and_phone_conferences() {
+        let mut event = Event::new(
+            "Standup",
+            EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+        );
+        event.conferences = vec![
+            Conference::new("https://meet.example.com/abc")
+                .with_feature("VIDEO")
+                .with_label("meet.example.com/abc"),
+            Conference::new("tel:+1-555-0100").with_feature("PHONE"),
+        ];
+
+        let parsed = Event::parse_single_ics(&event.to_ics_string());
+
+        assert_eq!(parsed.conferences, event.conferences);
+    }
+
     #[test]
     fn occurs_in_range_returns_true_for_event_inside_range() {
         let mut event = Event::new(
@@ -600,6 +985,18 @@ END:VCALENDAR
         assert!(event.occurs_in_range(from, to));
     }
 
+    #[test]
+    fn occurs_in_range_counts_all_day_event_without_end_as_its_whole_day() {
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 5, 15).unwrap();
+        let event = Event::new("Holiday", EventTime::Date(date));
+
+        let day = |d: chrono::NaiveDate| EventTime::Date(d).to_utc();
+        let next = date + chrono::Duration::days(1);
+
+        assert!(event.occurs_in_range(day(date), day(next)));
+        assert!(!event.occurs_in_range(day(next), day(next + chrono::Duration::days(1))));
+    }
+
     #[test]
     fn occurs_in_range_excludes_event_ending_exactly_at_range_start() {
         let mut event = Event::new(
@@ -821,6 +1218,19 @@ END:VCALENDAR
         assert_eq!(cloned.start, event.start);
     }
 
+    #[test]
+    fn has_category_matches_case_insensitively() {
+        let mut event = Event::new(
+            "Test",
+            EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+        );
+        event.categories = vec!["Deep Work".to_string(), "client".to_string()];
+
+        assert!(event.has_category("deep work"));
+        assert!(event.has_category("CLIENT"));
+        assert!(!event.has_category("admin"));
+    }
+
     #[test]
     fn is_pending_invite_for_true_when_attendee_needs_action() {
         let mut event = Event::new(
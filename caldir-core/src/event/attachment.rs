@@ -1,6 +1,7 @@
 use icalendar::Property;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Attachment {
     pub uri: String,
     pub params: Vec<(String, String)>,
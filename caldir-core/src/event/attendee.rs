@@ -3,8 +3,9 @@ mod participation_status;
 pub use participation_status::ParticipationStatus;
 
 use icalendar::Property;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Attendee {
     pub email: String,
     pub name: Option<String>,
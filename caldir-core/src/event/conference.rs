@@ -0,0 +1,133 @@
+use icalendar::Property;
+use serde::{Deserialize, Serialize};
+
+/// RFC 7986 `CONFERENCE`: how to join the event remotely (video link, dial-in, …).
+///
+/// Kept apart from `Event::url`, which is the event's own web page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conference {
+    pub uri: String,
+    /// `FEATURE` values, e.g. `VIDEO`, `AUDIO`, `PHONE`, `CHAT`
+    pub features: Vec<String>,
+    /// Human-readable `LABEL`, e.g. "Google Meet" or a dial-in PIN
+    pub label: Option<String>,
+}
+
+impl Conference {
+    pub fn new(uri: impl Into<String>) -> Self {
+        Conference {
+            uri: uri.into(),
+            features: Vec::new(),
+            label: None,
+        }
+    }
+
+    pub fn with_feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features
+            .iter()
+            .any(|f| f.eq_ignore_ascii_case(feature))
+    }
+
+    pub fn from_property(prop: &Property) -> Option<Self> {
+        let uri = prop.value().trim();
+        if uri.is_empty() {
+            return None;
+        }
+
+        let param = |key: &str| {
+            prop.params()
+                .values()
+                .find(|p| p.key().eq_ignore_ascii_case(key))
+                .map(|p| p.value().to_string())
+        };
+
+        // FEATURE is a comma-separated list: FEATURE=AUDIO,VIDEO
+        let features = param("FEATURE")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .map(str::to_ascii_uppercase)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Conference {
+            uri: uri.to_string(),
+            features,
+            label: param("LABEL").filter(|l| !l.is_empty()),
+        })
+    }
+}
+
+impl From<&Conference> for Property {
+    fn from(value: &Conference) -> Self {
+        let mut prop = Property::new("CONFERENCE", &value.uri);
+        prop.add_parameter("VALUE", "URI");
+        if !value.features.is_empty() {
+            prop.add_parameter("FEATURE", &value.features.join(","));
+        }
+        if let Some(label) = &value.label {
+            prop.add_parameter("LABEL", label);
+        }
+        prop.done()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_features_and_label() {
+        let mut prop = Property::new("CONFERENCE", "https://meet.example.com/abc");
+        prop.add_parameter("VALUE", "URI");
+        prop.add_parameter("FEATURE", "audio,VIDEO");
+        prop.add_parameter("LABEL", "Team sync");
+
+        let conference = Conference::from_property(&prop.done()).unwrap();
+
+        assert_eq!(conference.uri, "https://meet.example.com/abc");
+        assert_eq!(conference.features, vec!["AUDIO", "VIDEO"]);
+        assert_eq!(conference.label.as_deref(), Some("Team sync"));
+        assert!(conference.has_feature("video"));
+    }
+
+    #[test]
+    fn skips_empty_uri() {
+        let prop = Property::new("CONFERENCE", "").done();
+
+        assert!(Conference::from_property(&prop).is_none());
+    }
+
+    #[test]
+    fn writes_conference_property_with_params() {
+        let conference = Conference::new("tel:+1-555-0100")
+            .with_feature("PHONE")
+            .with_label("Dial-in");
+
+        let prop = Property::from(&conference);
+
+        assert_eq!(prop.key(), "CONFERENCE");
+        assert_eq!(prop.value(), "tel:+1-555-0100");
+        assert_eq!(prop.params().get("VALUE").map(|p| p.value()), Some("URI"));
+        assert_eq!(
+            prop.params().get("FEATURE").map(|p| p.value()),
+            Some("PHONE")
+        );
+        assert_eq!(
+            prop.params().get("LABEL").map(|p| p.value()),
+            Some("Dial-in")
+        );
+    }
+}
@@ -11,6 +11,9 @@ pub enum EventError {
     #[error("expected {expected} event(s) in ICS, found {found}")]
     UnexpectedEventCount { expected: usize, found: usize },
 
+    #[error("failed to parse Markdown event: {0}")]
+    InvalidMarkdown(String),
+
     #[error("event is missing a start time (DTSTART)")]
     MissingStart,
 
@@ -20,3 +23,32 @@ pub enum EventError {
     #[error("no attendee matching {email}")]
     AttendeeNotFound { email: String },
 }
+
+impl EventError {
+    /// The line an ICS parse error points at, if the parser said.
+    pub(crate) fn ics_line(&self) -> Option<usize> {
+        let Self::InvalidIcs(_, message) = self else {
+            return None;
+        };
+
+        let (_, rest) = message.split_once("line ")?;
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        rest[..digits].parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ics_line_reads_parser_position() {
+        let error = EventError::InvalidIcs(String::new(), "0: at line 3:\nFOO".to_string());
+        assert_eq!(error.ics_line(), Some(3));
+
+        let error = EventError::InvalidIcs(String::new(), "unexpected end".to_string());
+        assert_eq!(error.ics_line(), None);
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Marks an event of a special type, since ICS has no way to say so.
+pub(crate) const EVENT_TYPE_PROPERTY: &str = "X-CALDIR-EVENT-TYPE";
+
+/// What kind of event this is. Providers like Google treat the special
+/// types differently (e.g. out-of-office events decline new invitations).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EventType {
+    #[default]
+    Default,
+    OutOfOffice,
+    FocusTime,
+    WorkingLocation,
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ics_str())
+    }
+}
+
+impl EventType {
+    pub fn as_ics_str(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::OutOfOffice => "out-of-office",
+            Self::FocusTime => "focus-time",
+            Self::WorkingLocation => "working-location",
+        }
+    }
+
+    pub fn from_ics_str(s: &str) -> Option<Self> {
+        match s {
+            "default" => Some(Self::Default),
+            "out-of-office" => Some(Self::OutOfOffice),
+            "focus-time" => Some(Self::FocusTime),
+            "working-location" => Some(Self::WorkingLocation),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_each_type() {
+        for event_type in [
+            EventType::Default,
+            EventType::OutOfOffice,
+            EventType::FocusTime,
+            EventType::WorkingLocation,
+        ] {
+            assert_eq!(
+                EventType::from_ics_str(event_type.as_ics_str()),
+                Some(event_type)
+            );
+        }
+        assert_eq!(EventType::from_ics_str("birthday"), None);
+    }
+}
@@ -1,6 +1,8 @@
+use crate::event::event_type::EVENT_TYPE_PROPERTY;
+use crate::event::reminder::DEFAULT_REMINDERS_PROPERTY;
 use crate::event::{
-    Attachment, Attendee, Availability, Event, EventError, EventTime, EventUid, Organizer,
-    Recurrence, RecurrenceId, Reminder, Status, Visibility, XProperty,
+    Attachment, Attendee, Availability, Conference, Event, EventError, EventTime, EventType,
+    EventUid, Organizer, Recurrence, RecurrenceId, Reminder, Status, Visibility, XProperty,
 };
 use icalendar::{Component, EventLike};
 
@@ -47,7 +49,15 @@ impl TryFrom<&icalendar::Event> for Event {
             .property_value("CLASS")
             .and_then(Visibility::from_ics_str);
 
+        let event_type = value
+            .property_value(EVENT_TYPE_PROPERTY)
+            .and_then(EventType::from_ics_str)
+            .unwrap_or_default();
+
         let reminders = Reminder::from_ical_event(value);
+        let reminders_default = value
+            .property_value(DEFAULT_REMINDERS_PROPERTY)
+            .is_some_and(|v| v.eq_ignore_ascii_case("TRUE"));
 
         // ATTACH is a multi-property (RFC 5545); URI links are kept, inline
         // binary blobs are dropped — see `Attachment::from_property`.
@@ -57,10 +67,20 @@ impl TryFrom<&icalendar::Event> for Event {
             .map(|props| props.iter().filter_map(Attachment::from_property).collect())
             .unwrap_or_default();
 
+        let categories = parse_categories(value);
+
+        let conferences = multi_valued(value, "CONFERENCE")
+            .filter_map(Conference::from_property)
+            .collect();
+
         let x_properties = value
             .properties()
             .iter()
-            .filter(|(name, _)| name.starts_with("X-"))
+            .filter(|(name, _)| {
+                name.starts_with("X-")
+                    && *name != DEFAULT_REMINDERS_PROPERTY
+                    && *name != EVENT_TYPE_PROPERTY
+            })
             .map(|(_, prop)| XProperty::from(prop))
             .collect();
 
@@ -74,6 +94,7 @@ impl TryFrom<&icalendar::Event> for Event {
             status,
             availability,
             visibility,
+            event_type,
             recurrence,
             recurrence_id,
             last_modified: value.get_last_modified(),
@@ -86,7 +107,11 @@ impl TryFrom<&icalendar::Event> for Event {
             organizer,
             attendees,
             reminders,
+            reminders_default,
             url: value.property_value("URL").map(ToString::to_string),
+            categories,
+            color: value.property_value("COLOR").map(ToString::to_string),
+            conferences,
             attachments,
             x_properties,
         })
@@ -108,6 +133,75 @@ fn non_empty(s: Option<&str>) -> Option<String> {
     s.filter(|v| !v.is_empty()).map(ToString::to_string)
 }
 
+/// Properties that may repeat can land in either `properties()` or
+/// `multi_properties()` depending on how the component was built.
+fn multi_valued<'a>(
+    value: &'a icalendar::Event,
+    name: &str,
+) -> impl Iterator<Item = &'a icalendar::Property> {
+    let single = value.properties().get(name).into_iter();
+    let multi = value.multi_properties().get(name).into_iter().flatten();
+
+    single.chain(multi)
+}
+
+/// Stands in for an escaped `\,` in CATEGORIES, which the parser would
+/// otherwise unescape into a comma that splits the category in two.
+const ESCAPED_COMMA: char = '\u{E000}';
+
+/// Swap the escaped commas on CATEGORIES lines of unfolded ICS for
+/// [`ESCAPED_COMMA`], so `parse_categories` splits only on unescaped ones.
+pub(crate) fn protect_escaped_category_commas(ics: &str) -> String {
+    ics.split_inclusive('\n')
+        .map(|line| {
+            let is_categories = line
+                .get(..10)
+                .is_some_and(|name| name.eq_ignore_ascii_case("CATEGORIES"))
+                && matches!(line.as_bytes().get(10), Some(b':' | b';'));
+            if !is_categories {
+                return line.to_string();
+            }
+
+            let mut protected = String::with_capacity(line.len());
+            let mut chars = line.chars();
+            // Parameter values may be quoted and contain `:`
+            let mut quoted = false;
+            for c in chars.by_ref() {
+                protected.push(c);
+                match c {
+                    '"' => quoted = !quoted,
+                    ':' if !quoted => break,
+                    _ => {}
+                }
+            }
+            while let Some(c) = chars.next() {
+                if c != '\\' {
+                    protected.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some(',') => protected.push(ESCAPED_COMMA),
+                    next => {
+                        protected.push(c);
+                        protected.extend(next);
+                    }
+                }
+            }
+            protected
+        })
+        .collect()
+}
+
+// CATEGORIES may repeat and each line may pack several comma-separated
+// values (RFC 5545 §3.8.1.2). Flatten both forms into one list.
+fn parse_categories(value: &icalendar::Event) -> Vec<String> {
+    multi_valued(value, "CATEGORIES")
+        .flat_map(|prop| prop.value().split(','))
+        .map(|v| v.trim().replace(ESCAPED_COMMA, ","))
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -479,6 +573,22 @@ mod tests {
         let event = Event::try_from(ical_event).unwrap();
 
         assert!(event.reminders.is_empty());
+        assert!(!event.reminders_default);
+    }
+
+    #[test]
+    fn converts_default_reminders_marker() {
+        let ical_event = test_icalendar_event()
+            .append_property(icalendar::Property::new(
+                "X-CALDIR-DEFAULT-REMINDERS",
+                "TRUE",
+            ))
+            .done();
+
+        let event = Event::try_from(ical_event).unwrap();
+
+        assert!(event.reminders_default);
+        assert!(event.x_properties.is_empty());
     }
 
     #[test]
@@ -560,6 +670,91 @@ mod tests {
         assert!(event.attachments.is_empty());
     }
 
+    #[test]
+    fn converts_categories() {
+        let ical_event = test_icalendar_event()
+            .append_multi_property(icalendar::Property::new("CATEGORIES", "work,client"))
+            .append_multi_property(icalendar::Property::new("CATEGORIES", "deep work"))
+            .done();
+
+        let event = Event::try_from(ical_event).unwrap();
+
+        assert_eq!(event.categories, vec!["work", "client", "deep work"]);
+    }
+
+    #[test]
+    fn parses_categories_from_ics_string() {
+        let ics = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:test@caldir\r\nDTSTART:20260101T120000Z\r\nCATEGORIES:WORK,TRAVEL\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let event = crate::event::Event::parse_single_ics(ics);
+
+        assert_eq!(event.categories, vec!["WORK", "TRAVEL"]);
+    }
+
+    #[test]
+    fn splits_categories_only_on_unescaped_commas() {
+        let ics = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:test@caldir\r\nDTSTART:20260101T120000Z\r\nCATEGORIES:Smith\\, John,WORK\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let event = crate::event::Event::parse_single_ics(ics);
+
+        assert_eq!(event.categories, vec!["Smith, John", "WORK"]);
+    }
+
+    #[test]
+    fn parses_conferences_from_ics_string() {
+        let ics = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:test@caldir\r\nDTSTART:20260101T120000Z\r\nURL:https://example.com/event\r\nCONFERENCE;VALUE=URI;FEATURE=VIDEO;LABEL=Meet:https://meet.example.com/abc\r\nCONFERENCE;VALUE=URI;FEATURE=PHONE:tel:+1-555-0100\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let event = crate::event::Event::parse_single_ics(ics);
+
+        assert_eq!(event.url.as_deref(), Some("https://example.com/event"));
+        assert_eq!(event.conferences.len(), 2);
+        assert_eq!(event.conference_url(), Some("https://meet.example.com/abc"));
+        assert!(
+            event
+                .conferences
+                .iter()
+                .any(|c| c.uri == "tel:+1-555-0100" && c.has_feature("PHONE"))
+        );
+    }
+
+    #[test]
+    fn conferences_is_empty_when_missing() {
+        let ical_event = test_icalendar_event().done();
+
+        let event = Event::try_from(ical_event).unwrap();
+
+        assert!(event.conferences.is_empty());
+    }
+
+    #[test]
+    fn converts_color() {
+        let ical_event = test_icalendar_event()
+            .append_property(icalendar::Property::new("COLOR", "tomato"))
+            .done();
+
+        let event = Event::try_from(ical_event).unwrap();
+
+        assert_eq!(event.color.as_deref(), Some("tomato"));
+    }
+
+    #[test]
+    fn color_is_none_when_missing() {
+        let ical_event = test_icalendar_event().done();
+
+        let event = Event::try_from(ical_event).unwrap();
+
+        assert_eq!(event.color, None);
+    }
+
+    #[test]
+    fn categories_is_empty_when_missing() {
+        let ical_event = test_icalendar_event().done();
+
+        let event = Event::try_from(ical_event).unwrap();
+
+        assert!(event.categories.is_empty());
+    }
+
     #[test]
     fn converts_x_properties() {
         let ical_event = test_icalendar_event()
@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 // Recurring events share the same UID (stupid design)
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct EventUid(String);
 
 impl EventUid {
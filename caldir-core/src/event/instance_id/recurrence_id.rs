@@ -1,9 +1,10 @@
 use super::super::time::NormalizedEventTime;
 use crate::EventTime;
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
 // The instance identifier in a recurring event
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct RecurrenceId(EventTime);
 
 impl RecurrenceId {
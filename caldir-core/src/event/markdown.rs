@@ -0,0 +1,311 @@
+//! Events as Markdown files, for calendars with `storage = "markdown"`:
+//! the structured fields as YAML frontmatter, the description as the body.
+//!
+//! ```markdown
+//! ---
+//! uid: 7f2c…@caldir
+//! title: Planning
+//! start: 2026-03-02T09:00:00[Europe/Stockholm]
+//! end: 2026-03-02T10:00:00[Europe/Stockholm]
+//! ---
+//!
+//! Agenda for the quarter.
+//! ```
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    Attachment, Attendee, Availability, Conference, Event, EventError, EventTime, EventType,
+    EventUid, Organizer, Recurrence, RecurrenceId, Reminder, Status, Visibility, XProperty,
+};
+
+const DELIMITER: &str = "---";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Frontmatter {
+    uid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    start: Time,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<Time>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    status: Status,
+    #[serde(default, skip_serializing_if = "is_default")]
+    availability: Availability,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    visibility: Option<Visibility>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    event_type: EventType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recurrence: Option<FrontmatterRecurrence>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recurrence_id: Option<Time>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    organizer: Option<Organizer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attendees: Vec<Attendee>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    reminders: Vec<Reminder>,
+    /// Use the calendar's default reminders
+    #[serde(default, skip_serializing_if = "is_default")]
+    default_reminders: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    conferences: Vec<Conference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<Attachment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    x_properties: Vec<XProperty>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    sequence: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FrontmatterRecurrence {
+    rrule: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exdates: Vec<Time>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rdates: Vec<Time>,
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// An `EventTime` written the way people read it: `2026-03-02` (all-day),
+/// `2026-03-02T09:00:00Z` (UTC), `2026-03-02T09:00:00` (floating) or
+/// `2026-03-02T09:00:00[Europe/Stockholm]` (zoned).
+#[derive(Debug)]
+struct Time(EventTime);
+
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+impl Serialize for Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = match &self.0 {
+            EventTime::Date(date) => date.format("%Y-%m-%d").to_string(),
+            EventTime::DateTimeUtc(dt) => format!("{}Z", dt.format(DATETIME_FORMAT)),
+            EventTime::DateTimeFloating(dt) => dt.format(DATETIME_FORMAT).to_string(),
+            EventTime::DateTimeZoned { datetime, tzid } => {
+                format!("{}[{tzid}]", datetime.format(DATETIME_FORMAT))
+            }
+        };
+        serializer.serialize_str(&value)
+    }
+}
+
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_time(value.trim())
+            .map(Time)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid date/time: {value}")))
+    }
+}
+
+fn parse_time(value: &str) -> Option<EventTime> {
+    let datetime = |s: &str| NaiveDateTime::parse_from_str(s, DATETIME_FORMAT).ok();
+
+    if let Some((datetime_part, tzid)) = value
+        .strip_suffix(']')
+        .and_then(|rest| rest.split_once('['))
+    {
+        return Some(EventTime::DateTimeZoned {
+            datetime: datetime(datetime_part)?,
+            tzid: tzid.to_string(),
+        });
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        return Some(EventTime::DateTimeUtc(datetime(utc)?.and_utc()));
+    }
+
+    if value.contains('T') {
+        return datetime(value).map(EventTime::DateTimeFloating);
+    }
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .map(EventTime::Date)
+}
+
+impl Event {
+    pub fn to_markdown_string(&self) -> String {
+        let frontmatter = Frontmatter {
+            uid: self.uid.as_str().to_string(),
+            title: self.summary.clone(),
+            start: Time(self.start.clone()),
+            end: self.end.clone().map(Time),
+            location: self.location.clone(),
+            status: self.status,
+            availability: self.availability,
+            visibility: self.visibility,
+            event_type: self.event_type,
+            recurrence: self
+                .recurrence
+                .as_ref()
+                .map(|recurrence| FrontmatterRecurrence {
+                    rrule: recurrence.rrule.clone(),
+                    exdates: recurrence.exdates.iter().cloned().map(Time).collect(),
+                    rdates: recurrence.rdates.iter().cloned().map(Time).collect(),
+                }),
+            recurrence_id: self
+                .recurrence_id
+                .as_ref()
+                .map(|id| Time(id.as_event_time().clone())),
+            organizer: self.organizer.clone(),
+            attendees: self.attendees.clone(),
+            reminders: self.reminders.clone(),
+            default_reminders: self.reminders_default,
+            url: self.url.clone(),
+            tags: self.categories.clone(),
+            color: self.color.clone(),
+            conferences: self.conferences.clone(),
+            attachments: self.attachments.clone(),
+            x_properties: self.x_properties.clone(),
+            last_modified: self.last_modified,
+            sequence: self.sequence,
+        };
+
+        // Plain structs of strings and numbers always serialize
+        let yaml = serde_yaml::to_string(&frontmatter).unwrap_or_default();
+
+        match &self.description {
+            Some(description) => format!("{DELIMITER}\n{yaml}{DELIMITER}\n\n{description}\n"),
+            None => format!("{DELIMITER}\n{yaml}{DELIMITER}\n"),
+        }
+    }
+
+    pub fn from_markdown_str(contents: &str) -> Result<Self, EventError> {
+        let invalid = |message: &str| EventError::InvalidMarkdown(message.to_string());
+
+        let contents = contents.replace("\r\n", "\n");
+        let rest = contents
+            .strip_prefix(DELIMITER)
+            .and_then(|rest| rest.strip_prefix('\n'))
+            .ok_or_else(|| invalid("missing frontmatter (the file should start with ---)"))?;
+
+        let (yaml, body) = match rest.split_once(&format!("\n{DELIMITER}\n")) {
+            Some((yaml, body)) => (yaml, body),
+            None => (
+                rest.strip_suffix(&format!("\n{DELIMITER}"))
+                    .ok_or_else(|| invalid("frontmatter is never closed with ---"))?,
+                "",
+            ),
+        };
+
+        let frontmatter: Frontmatter =
+            serde_yaml::from_str(yaml).map_err(|err| invalid(&err.to_string()))?;
+
+        if frontmatter.uid.trim().is_empty() {
+            return Err(EventError::MissingUid);
+        }
+
+        let body = body.strip_prefix('\n').unwrap_or(body);
+        let body = body.strip_suffix('\n').unwrap_or(body);
+        let description = (!body.is_empty()).then(|| body.to_string());
+
+        Ok(Event {
+            uid: EventUid::new(frontmatter.uid),
+            summary: frontmatter.title,
+            description,
+            location: frontmatter.location,
+            start: frontmatter.start.0,
+            end: frontmatter.end.map(|end| end.0),
+            status: frontmatter.status,
+            availability: frontmatter.availability,
+            visibility: frontmatter.visibility,
+            event_type: frontmatter.event_type,
+            recurrence: frontmatter.recurrence.map(|recurrence| Recurrence {
+                rrule: recurrence.rrule,
+                exdates: recurrence.exdates.into_iter().map(|t| t.0).collect(),
+                rdates: recurrence.rdates.into_iter().map(|t| t.0).collect(),
+            }),
+            recurrence_id: frontmatter
+                .recurrence_id
+                .map(|id| RecurrenceId::from_event_time(id.0)),
+            organizer: frontmatter.organizer,
+            attendees: frontmatter.attendees,
+            reminders: frontmatter.reminders,
+            reminders_default: frontmatter.default_reminders,
+            url: frontmatter.url,
+            categories: frontmatter.tags,
+            color: frontmatter.color,
+            conferences: frontmatter.conferences,
+            attachments: frontmatter.attachments,
+            x_properties: frontmatter.x_properties,
+            last_modified: frontmatter.last_modified,
+            sequence: frontmatter.sequence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_event;
+
+    #[test]
+    fn round_trips_through_markdown() {
+        let event = Event {
+            description: Some("# Agenda\n\n- budget\n- hiring".to_string()),
+            location: Some("Room 4".to_string()),
+            start: EventTime::DateTimeZoned {
+                datetime: NaiveDate::from_ymd_opt(2026, 3, 2)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap(),
+                tzid: "Europe/Stockholm".to_string(),
+            },
+            recurrence: Some(Recurrence {
+                rrule: "FREQ=WEEKLY;BYDAY=MO".to_string(),
+                exdates: vec![EventTime::Date(
+                    NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(),
+                )],
+                rdates: Vec::new(),
+            }),
+            attendees: vec![Attendee::new("ann@example.com")],
+            reminders: vec![Reminder::from_minutes(10)],
+            categories: vec!["work".to_string()],
+            ..test_event()
+        };
+
+        let markdown = event.to_markdown_string();
+
+        assert!(markdown.starts_with("---\nuid: "));
+        assert!(markdown.contains("2026-03-02T09:00:00[Europe/Stockholm]"));
+        assert!(markdown.ends_with("---\n\n# Agenda\n\n- budget\n- hiring\n"));
+        assert_eq!(Event::from_markdown_str(&markdown).unwrap(), event);
+    }
+
+    #[test]
+    fn reads_hand_written_files() {
+        let markdown =
+            "---\nuid: abc\ntitle: Lunch\nstart: 2026-03-02T12:00:00Z\n---\nBring snacks\n";
+
+        let event = Event::from_markdown_str(markdown).unwrap();
+
+        assert_eq!(event.uid.as_str(), "abc");
+        assert_eq!(event.summary.as_deref(), Some("Lunch"));
+        assert_eq!(event.description.as_deref(), Some("Bring snacks"));
+        assert!(matches!(event.start, EventTime::DateTimeUtc(_)));
+    }
+
+    #[test]
+    fn rejects_files_without_frontmatter() {
+        assert!(Event::from_markdown_str("# Just notes\n").is_err());
+        assert!(Event::from_markdown_str("---\nuid: abc\n").is_err());
+    }
+}
@@ -1,16 +1,30 @@
 use std::collections::HashMap;
 
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use rrule::{RRuleSet, Tz as RTz};
 
-use crate::Event;
+use super::time::parse_tzid;
 use crate::event::{EventTime, Recurrence, RecurrenceId, Status};
+use crate::{Event, FloatingTimezone};
+
+/// Most occurrences a single series expands to in one range.
+const MAX_OCCURRENCES: u16 = 366;
 
 /// Expand a set of events into all occurrences overlapping `[from, to)`.
 pub fn expand_in_range(
     events: impl IntoIterator<Item = Event>,
     from: DateTime<Utc>,
     to: DateTime<Utc>,
+) -> Vec<Event> {
+    expand_in_range_with(events, from, to, FloatingTimezone::Local)
+}
+
+/// Like [`expand_in_range`], with floating times in `floating`.
+pub fn expand_in_range_with(
+    events: impl IntoIterator<Item = Event>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    floating: FloatingTimezone,
 ) -> Vec<Event> {
     let mut singles: Vec<Event> = Vec::new();
     let mut masters: Vec<Event> = Vec::new();
@@ -30,25 +44,25 @@ pub fn expand_in_range(
     let mut result: Vec<Event> = Vec::new();
 
     for event in singles {
-        if event.occurs_in_range(from, to) {
+        if event.occurs_in_range_with(from, to, floating) {
             result.push(event);
         }
     }
 
     for master in &masters {
         let uid_overrides = overrides.remove(master.uid.as_str()).unwrap_or_default();
-        result.extend(expand_master(master, from, to, &uid_overrides));
+        result.extend(expand_master(master, from, to, &uid_overrides, floating));
     }
 
     for (_uid, orphans) in overrides {
         for (_rid, event) in orphans {
-            if event.occurs_in_range(from, to) {
+            if event.occurs_in_range_with(from, to, floating) {
                 result.push(event);
             }
         }
     }
 
-    result.sort_by_key(|e| e.start.to_utc());
+    result.sort_by_key(|e| e.start.to_utc_with(floating));
 
     result
 }
@@ -58,31 +72,25 @@ pub(crate) fn expand_master(
     from: DateTime<Utc>,
     to: DateTime<Utc>,
     overrides: &HashMap<RecurrenceId, Event>,
+    floating: FloatingTimezone,
 ) -> Vec<Event> {
     let Some(recurrence) = master.recurrence.as_ref() else {
         return Vec::new();
     };
 
-    let rrule_str = build_rrule_set_string(&master.start, recurrence);
-    let Ok(rrule_set) = rrule_str.parse::<RRuleSet>() else {
-        return if master.occurs_in_range(from, to) {
+    let Some(starts) = occurrence_starts(master, recurrence, from, to, floating) else {
+        return if master.occurs_in_range_with(from, to, floating) {
             vec![master.clone()]
         } else {
             Vec::new()
         };
     };
 
-    let tz: RTz = Utc.into();
-    let after = from.with_timezone(&tz);
-    let before = to.with_timezone(&tz);
-    let dates = rrule_set.after(after).before(before).all(366).dates;
-
-    let duration = master_duration(master);
+    let duration = master_duration(master, floating);
 
-    dates
+    starts
         .into_iter()
-        .filter_map(|occ| {
-            let occ_time = occurrence_to_event_time(&occ, &master.start);
+        .filter_map(|occ_time| {
             let rid = RecurrenceId::from_event_time(occ_time.clone());
 
             if let Some(override_event) = overrides.get(&rid) {
@@ -97,19 +105,84 @@ pub(crate) fn expand_master(
         .collect()
 }
 
+/// Start times of `master`'s occurrences in `[from, to]`, or `None` if its
+/// rule doesn't parse.
+fn occurrence_starts(
+    master: &Event,
+    recurrence: &Recurrence,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    floating: FloatingTimezone,
+) -> Option<Vec<EventTime>> {
+    let utc: RTz = Utc.into();
+
+    let wall_start = match &master.start {
+        EventTime::DateTimeFloating(datetime) | EventTime::DateTimeZoned { datetime, .. } => {
+            *datetime
+        }
+        EventTime::Date(_) | EventTime::DateTimeUtc(_) => {
+            let rrule_set = build_rrule_set_string(&master.start, recurrence)
+                .parse::<RRuleSet>()
+                .ok()?;
+            let dates = rrule_set
+                .after(from.with_timezone(&utc))
+                .before(to.with_timezone(&utc))
+                .all(MAX_OCCURRENCES)
+                .dates;
+
+            return Some(
+                dates
+                    .iter()
+                    .map(|occ| occurrence_to_event_time(occ, &master.start))
+                    .collect(),
+            );
+        }
+    };
+
+    // Expand on the series' wall clock, where DST doesn't exist, and only
+    // then resolve each occurrence in its timezone: a weekly 09:00 stays at
+    // 09:00 local on both sides of a clock change. The wall clock can be up
+    // to a day off UTC, so the range is widened and checked exactly below.
+    let rrule_set =
+        build_wall_clock_rrule_set_string(&master.start, wall_start, recurrence, floating)
+            .parse::<RRuleSet>()
+            .ok()?;
+    let after =
+        wall_clock(&EventTime::DateTimeUtc(from), &master.start, floating) - Duration::days(1);
+    let before =
+        wall_clock(&EventTime::DateTimeUtc(to), &master.start, floating) + Duration::days(1);
+    let dates = rrule_set
+        .after(after.and_utc().with_timezone(&utc))
+        .before(before.and_utc().with_timezone(&utc))
+        .all(MAX_OCCURRENCES)
+        .dates;
+
+    Some(
+        dates
+            .iter()
+            .map(|occ| with_wall_clock(&master.start, occ.naive_utc()))
+            .filter(|start| (from..=to).contains(&start.to_utc_with(floating)))
+            .collect(),
+    )
+}
+
 impl Event {
     /// Synthesize this recurring master's occurrence at `start` (a recurrence
     /// id) as a concrete, non-recurring event — the same shape `expand_*`
     /// produces for a non-overridden instance. Inherits the master's metadata
     /// and shifts the end by the master's duration.
     pub(crate) fn occurrence_at(&self, start: EventTime) -> Event {
-        synthesize_instance(self, start, master_duration(self))
+        synthesize_instance(self, start, master_duration(self, FloatingTimezone::Local))
     }
 }
 
-fn master_duration(master: &Event) -> Duration {
-    let start = master.start.to_utc();
-    let end = master.end.as_ref().map(|e| e.to_utc()).unwrap_or(start);
+fn master_duration(master: &Event, floating: FloatingTimezone) -> Duration {
+    let start = master.start.to_utc_with(floating);
+    let end = master
+        .end
+        .as_ref()
+        .map(|e| e.to_utc_with(floating))
+        .unwrap_or(start);
     end - start
 }
 
@@ -138,12 +211,11 @@ fn shift_end_time(
             let day_diff = (*e - *s).num_days();
             EventTime::Date(*new_start + Duration::days(day_diff))
         }
-        (_, _, EventTime::DateTimeUtc(dt)) => EventTime::DateTimeUtc(*dt + duration),
-        (_, _, EventTime::DateTimeFloating(dt)) => EventTime::DateTimeFloating(*dt + duration),
-        (_, _, EventTime::DateTimeZoned { datetime, tzid }) => EventTime::DateTimeZoned {
-            datetime: *datetime + duration,
-            tzid: tzid.clone(),
-        },
+        // Exact durations, so an instance spanning a DST change ends when
+        // the master's duration is up, not at the master's wall-clock end
+        (_, _, EventTime::DateTimeUtc(_))
+        | (_, _, EventTime::DateTimeFloating(_))
+        | (_, _, EventTime::DateTimeZoned { .. }) => instance_start.plus_elapsed(duration),
         // Master is timed but instance came back as a Date — preserve master_end as-is.
         (_, _, EventTime::Date(_)) => master_end.clone(),
     };
@@ -163,6 +235,106 @@ fn occurrence_to_event_time(occ: &DateTime<RTz>, master_start: &EventTime) -> Ev
     }
 }
 
+/// `time` on the wall clock of the floating or zoned `start`, with floating
+/// times in `floating`.
+fn wall_clock(time: &EventTime, start: &EventTime, floating: FloatingTimezone) -> NaiveDateTime {
+    match (start, time) {
+        (EventTime::DateTimeFloating(_), EventTime::DateTimeFloating(datetime)) => *datetime,
+        (
+            EventTime::DateTimeZoned { tzid, .. },
+            EventTime::DateTimeZoned {
+                datetime,
+                tzid: own,
+            },
+        ) if own == tzid => *datetime,
+        (EventTime::DateTimeZoned { tzid, .. }, _) => match parse_tzid(tzid) {
+            Some(tz) => time.to_local_tz_with(&tz, floating).naive_local(),
+            None => time
+                .to_local_tz_with(&chrono::Local, floating)
+                .naive_local(),
+        },
+        _ => match floating.zone() {
+            Some(zone) => time.to_local_tz_with(&zone, floating).naive_local(),
+            None => time
+                .to_local_tz_with(&chrono::Local, floating)
+                .naive_local(),
+        },
+    }
+}
+
+/// An occurrence of the floating or zoned `start` at wall-clock `datetime`.
+fn with_wall_clock(start: &EventTime, datetime: NaiveDateTime) -> EventTime {
+    match start {
+        EventTime::DateTimeZoned { tzid, .. } => EventTime::DateTimeZoned {
+            datetime,
+            tzid: tzid.clone(),
+        },
+        _ => EventTime::DateTimeFloating(datetime),
+    }
+}
+
+/// Like [`build_rrule_set_string`], but with every time written as the
+/// wall clock of `start`, passed off as UTC so the rrule crate applies no
+/// DST of its own.
+fn build_wall_clock_rrule_set_string(
+    start: &EventTime,
+    wall_start: NaiveDateTime,
+    recurrence: &Recurrence,
+    floating: FloatingTimezone,
+) -> String {
+    let as_utc = |datetime: NaiveDateTime| datetime.format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = Vec::new();
+    lines.push(format!("DTSTART:{}", as_utc(wall_start)));
+    lines.push(format!(
+        "RRULE:{}",
+        wall_clock_until(&recurrence.rrule, start, floating)
+    ));
+    for (name, times) in [
+        ("EXDATE", &recurrence.exdates),
+        ("RDATE", &recurrence.rdates),
+    ] {
+        for time in times {
+            lines.push(match time {
+                EventTime::Date(d) => format!("{};VALUE=DATE:{}", name, d.format("%Y%m%d")),
+                _ => format!("{}:{}", name, as_utc(wall_clock(time, start, floating))),
+            });
+        }
+    }
+    lines.join("\n")
+}
+
+/// UNTIL on the wall clock of `start`, written as UTC to match the DTSTART
+/// of [`build_wall_clock_rrule_set_string`]. A UTC UNTIL is converted; a
+/// floating or date-only one is already on the wall clock.
+fn wall_clock_until(rrule: &str, start: &EventTime, floating: FloatingTimezone) -> String {
+    rrule
+        .split(';')
+        .map(|part| {
+            let Some(value) = part.strip_prefix("UNTIL=") else {
+                return part.to_string();
+            };
+
+            let until = if let Some(utc) = value.strip_suffix('Z') {
+                NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").map(|datetime| {
+                    wall_clock(&EventTime::DateTimeUtc(datetime.and_utc()), start, floating)
+                })
+            } else if value.contains('T') {
+                NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            } else {
+                NaiveDate::parse_from_str(value, "%Y%m%d")
+                    .map(|date| date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap()))
+            };
+
+            match until {
+                Ok(until) => format!("UNTIL={}", until.format("%Y%m%dT%H%M%SZ")),
+                Err(_) => part.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
 fn build_rrule_set_string(start: &EventTime, recurrence: &Recurrence) -> String {
     let mut lines = Vec::new();
     lines.push(format_dtstart(start));
@@ -192,10 +364,17 @@ fn format_dtstart(start: &EventTime) -> String {
 
 fn format_exdate_or_rdate(name: &str, time: &EventTime, start: &EventTime) -> String {
     match (start, time) {
-        (EventTime::Date(_), _) => {
-            format!("{};VALUE=DATE:{}", name, time.to_utc().format("%Y%m%d"))
-        }
         (_, EventTime::Date(d)) => format!("{};VALUE=DATE:{}", name, d.format("%Y%m%d")),
+        // The day it falls on where it was written; in UTC it can be the day before
+        (EventTime::Date(_), EventTime::DateTimeFloating(dt))
+        | (EventTime::Date(_), EventTime::DateTimeZoned { datetime: dt, .. }) => {
+            format!("{};VALUE=DATE:{}", name, dt.format("%Y%m%d"))
+        }
+        (EventTime::Date(_), EventTime::DateTimeUtc(dt)) => format!(
+            "{};VALUE=DATE:{}",
+            name,
+            dt.with_timezone(&chrono::Local).format("%Y%m%d")
+        ),
         (_, EventTime::DateTimeUtc(dt)) => {
             format!("{}:{}", name, dt.format("%Y%m%dT%H%M%SZ"))
         }
@@ -252,7 +431,7 @@ fn normalize_until(rrule: &str, start: &EventTime) -> String {
 mod tests {
     use super::*;
     use crate::event::{Recurrence, RecurrenceId, Status};
-    use chrono::{NaiveDate, TimeZone};
+    use chrono::TimeZone;
     use pretty_assertions::assert_eq;
 
     fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
@@ -632,6 +811,171 @@ mod tests {
         assert_eq!(starts_at(&result[1]), utc(2026, 1, 6, 8, 0));
     }
 
+    #[test]
+    fn zoned_recurring_event_keeps_its_wall_clock_across_dst() {
+        let datetime = NaiveDate::from_ymd_opt(2026, 3, 28)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let mut master = Event::new(
+            "Standup",
+            EventTime::DateTimeZoned {
+                datetime,
+                tzid: "Europe/Stockholm".to_string(),
+            },
+        );
+        master.recurrence = Some(Recurrence::new("FREQ=DAILY;COUNT=2"));
+
+        // Clocks go forward on 2026-03-29, so the second 09:00 is an hour
+        // earlier in UTC, and a range ending at 07:30 UTC still has it.
+        let result = expand_in_range(
+            vec![master],
+            utc(2026, 3, 28, 0, 0),
+            utc(2026, 3, 29, 7, 30),
+        );
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(starts_at(&result[0]), utc(2026, 3, 28, 8, 0));
+        assert_eq!(starts_at(&result[1]), utc(2026, 3, 29, 7, 0));
+    }
+
+    fn stockholm_series(start: NaiveDateTime, rrule: &str) -> Event {
+        let mut master = Event::new(
+            "Weekly sync",
+            EventTime::DateTimeZoned {
+                datetime: start,
+                tzid: "Europe/Stockholm".to_string(),
+            },
+        );
+        master.recurrence = Some(Recurrence::new(rrule));
+        master
+    }
+
+    fn wall(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn weekly_zoned_event_stays_at_nine_across_spring_forward() {
+        let master = stockholm_series(wall(2026, 3, 19, 9, 0), "FREQ=WEEKLY;COUNT=3");
+
+        let result = expand_in_range(vec![master], utc(2026, 3, 1, 0, 0), utc(2026, 5, 1, 0, 0));
+
+        let starts: Vec<_> = result.iter().map(starts_at).collect();
+        assert_eq!(
+            starts,
+            vec![
+                utc(2026, 3, 19, 8, 0),
+                utc(2026, 3, 26, 8, 0),
+                utc(2026, 4, 2, 7, 0),
+            ]
+        );
+        assert_eq!(
+            result[2].recurrence_id,
+            Some(RecurrenceId::from_event_time(EventTime::DateTimeZoned {
+                datetime: wall(2026, 4, 2, 9, 0),
+                tzid: "Europe/Stockholm".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn weekly_zoned_event_stays_at_nine_across_fall_back() {
+        let master = stockholm_series(wall(2026, 10, 15, 9, 0), "FREQ=WEEKLY;COUNT=3");
+
+        let result = expand_in_range(vec![master], utc(2026, 10, 1, 0, 0), utc(2026, 12, 1, 0, 0));
+
+        let starts: Vec<_> = result.iter().map(starts_at).collect();
+        assert_eq!(
+            starts,
+            vec![
+                utc(2026, 10, 15, 7, 0),
+                utc(2026, 10, 22, 7, 0),
+                utc(2026, 10, 29, 8, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn zoned_occurrence_in_a_spring_forward_gap_moves_past_it() {
+        // 02:30 doesn't exist in Stockholm on 2026-03-29; it happens at 03:30
+        let master = stockholm_series(wall(2026, 3, 28, 2, 30), "FREQ=DAILY;COUNT=3");
+
+        let result = expand_in_range(vec![master], utc(2026, 3, 1, 0, 0), utc(2026, 4, 1, 0, 0));
+
+        let starts: Vec<_> = result.iter().map(starts_at).collect();
+        assert_eq!(
+            starts,
+            vec![
+                utc(2026, 3, 28, 1, 30),
+                utc(2026, 3, 29, 1, 30),
+                utc(2026, 3, 30, 0, 30),
+            ]
+        );
+    }
+
+    #[test]
+    fn zoned_occurrence_in_a_repeated_hour_happens_once() {
+        let master = stockholm_series(wall(2026, 10, 24, 2, 30), "FREQ=DAILY;COUNT=3");
+
+        let result = expand_in_range(vec![master], utc(2026, 10, 1, 0, 0), utc(2026, 11, 1, 0, 0));
+
+        let starts: Vec<_> = result.iter().map(starts_at).collect();
+        assert_eq!(
+            starts,
+            vec![
+                utc(2026, 10, 24, 0, 30),
+                utc(2026, 10, 25, 0, 30),
+                utc(2026, 10, 26, 1, 30),
+            ]
+        );
+    }
+
+    #[test]
+    fn zoned_range_edges_are_exact_instants_across_dst() {
+        let master = stockholm_series(wall(2026, 3, 26, 9, 0), "FREQ=WEEKLY;COUNT=2");
+
+        // 09:00 on 2026-04-02 is 07:00 UTC, after clocks went forward
+        let before = expand_in_range(
+            vec![master.clone()],
+            utc(2026, 4, 1, 0, 0),
+            utc(2026, 4, 2, 6, 59),
+        );
+        let after = expand_in_range(vec![master], utc(2026, 4, 2, 7, 0), utc(2026, 4, 3, 0, 0));
+
+        assert!(before.is_empty());
+        assert_eq!(after.len(), 1);
+    }
+
+    #[test]
+    fn utc_until_bounds_a_zoned_series_on_its_wall_clock() {
+        // UNTIL is the 09:00 CEST occurrence's own instant
+        let master = stockholm_series(
+            wall(2026, 3, 26, 9, 0),
+            "FREQ=WEEKLY;UNTIL=20260402T070000Z",
+        );
+
+        let result = expand_in_range(vec![master], utc(2026, 3, 1, 0, 0), utc(2026, 5, 1, 0, 0));
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(starts_at(&result[1]), utc(2026, 4, 2, 7, 0));
+    }
+
+    #[test]
+    fn zoned_exdate_after_dst_removes_its_instance() {
+        let mut master = stockholm_series(wall(2026, 3, 19, 9, 0), "FREQ=WEEKLY;COUNT=3");
+        master.recurrence.as_mut().unwrap().exdates =
+            vec![EventTime::DateTimeUtc(utc(2026, 4, 2, 7, 0))];
+
+        let result = expand_in_range(vec![master], utc(2026, 3, 1, 0, 0), utc(2026, 5, 1, 0, 0));
+
+        let starts: Vec<_> = result.iter().map(starts_at).collect();
+        assert_eq!(starts, vec![utc(2026, 3, 19, 8, 0), utc(2026, 3, 26, 8, 0)]);
+    }
+
     #[test]
     fn all_day_recurring_event_keeps_date_variant() {
         let mut master = Event::new(
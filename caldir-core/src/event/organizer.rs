@@ -1,7 +1,8 @@
 use icalendar::Property;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Organizer {
     pub email: String,
     pub name: Option<String>,
@@ -1,7 +1,8 @@
 use crate::event::EventTime;
 use icalendar::{Component, DatePerhapsTime, Property};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct Recurrence {
     /// RRULE value (after "RRULE:"), e.g. "FREQ=WEEKLY;BYDAY=MO".
     ///
@@ -5,6 +5,11 @@ use std::fmt;
 use std::time::Duration;
 
 const DEFAULT_REMINDER_DESCRIPTION: &str = "Reminder";
+
+/// Marks an event that uses its calendar's default reminders, since ICS has
+/// no way to say so.
+pub(crate) const DEFAULT_REMINDERS_PROPERTY: &str = "X-CALDIR-DEFAULT-REMINDERS";
+
 const MINUTES_PER_HOUR: u64 = 60;
 const MINUTES_PER_DAY: u64 = 24 * MINUTES_PER_HOUR;
 const MINUTES_PER_WEEK: u64 = 7 * MINUTES_PER_DAY;
@@ -1,8 +1,10 @@
 use super::tz_normalize::{self, Tzid};
-use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use crate::FloatingTimezone;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use icalendar::{CalendarDateTime, DatePerhapsTime};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EventTime {
     Date(NaiveDate),
     DateTimeUtc(DateTime<Utc>),
@@ -23,14 +25,27 @@ pub(crate) enum NormalizedEventTime {
 }
 
 impl EventTime {
+    /// This time on the clock in `tz`. Floating times happen in `tz` too.
     pub fn to_local_tz<Tz: TimeZone>(&self, tz: &Tz) -> DateTime<Tz> {
+        self.to_local_tz_with(tz, FloatingTimezone::Local)
+    }
+
+    /// Like `to_local_tz`, with floating times in `floating`.
+    pub fn to_local_tz_with<Tz: TimeZone>(
+        &self,
+        tz: &Tz,
+        floating: FloatingTimezone,
+    ) -> DateTime<Tz> {
         match self {
             EventTime::Date(date) => resolve_local(
                 date.and_hms_opt(0, 0, 0)
                     .expect("midnight should be a valid NaiveDateTime"),
                 tz,
             ),
-            EventTime::DateTimeFloating(datetime) => resolve_local(*datetime, tz),
+            EventTime::DateTimeFloating(datetime) => match floating.zone() {
+                Some(zone) => resolve_local(*datetime, &zone).with_timezone(tz),
+                None => resolve_local(*datetime, tz),
+            },
             EventTime::DateTimeUtc(datetime) => datetime.with_timezone(tz),
             EventTime::DateTimeZoned { datetime, tzid } => match parse_tzid(tzid) {
                 Some(event_tz) => resolve_local(*datetime, &event_tz).with_timezone(tz),
@@ -39,28 +54,72 @@ impl EventTime {
         }
     }
 
+    /// The instant this time is at. Zoned times go through their IANA
+    /// timezone; dates, floating times and unknown zones are local. Wall
+    /// times a DST change skips or repeats resolve like in `to_local_tz`.
     pub fn to_utc(&self) -> DateTime<Utc> {
+        self.to_utc_with(FloatingTimezone::Local)
+    }
+
+    /// Like `to_utc`, with floating times in `floating`.
+    pub fn to_utc_with(&self, floating: FloatingTimezone) -> DateTime<Utc> {
         match self {
-            EventTime::Date(date) => date
-                .and_hms_opt(0, 0, 0)
-                .expect("midnight should be a valid NaiveDateTime")
-                .and_local_timezone(chrono::Local)
-                .unwrap()
-                .with_timezone(&Utc),
-            EventTime::DateTimeFloating(datetime) => datetime
-                .and_local_timezone(chrono::Local)
-                .unwrap()
-                .with_timezone(&Utc),
             EventTime::DateTimeUtc(datetime) => *datetime,
-            EventTime::DateTimeZoned { datetime, tzid } => match parse_tzid(tzid) {
-                Some(event_tz) => datetime
-                    .and_local_timezone(event_tz)
-                    .unwrap()
-                    .with_timezone(&Utc),
-                None => datetime
-                    .and_local_timezone(chrono::Local)
-                    .unwrap()
-                    .with_timezone(&Utc),
+            _ => self
+                .to_local_tz_with(&chrono::Local, floating)
+                .with_timezone(&Utc),
+        }
+    }
+
+    /// This time, `elapsed` later in real time. Across a DST change, a zoned
+    /// time's wall clock moves by an hour more or less than `elapsed`.
+    pub(crate) fn plus_elapsed(&self, elapsed: Duration) -> EventTime {
+        match self {
+            EventTime::Date(date) => EventTime::Date(*date + Duration::days(elapsed.num_days())),
+            EventTime::DateTimeUtc(datetime) => EventTime::DateTimeUtc(*datetime + elapsed),
+            EventTime::DateTimeFloating(datetime) => {
+                EventTime::DateTimeFloating(*datetime + elapsed)
+            }
+            EventTime::DateTimeZoned { datetime, tzid } => {
+                let datetime = match parse_tzid(tzid) {
+                    Some(tz) => (resolve_local(*datetime, &tz) + elapsed).naive_local(),
+                    None => *datetime + elapsed,
+                };
+                EventTime::DateTimeZoned {
+                    datetime,
+                    tzid: tzid.clone(),
+                }
+            }
+        }
+    }
+
+    /// This time `by` later on the wall clock, so 09:00 shifted by a day is
+    /// 09:00 the next day even across a DST change. Dates move by whole days.
+    pub fn shifted(&self, by: Duration) -> EventTime {
+        match self {
+            EventTime::Date(date) => EventTime::Date(*date + Duration::days(by.num_days())),
+            EventTime::DateTimeUtc(datetime) => EventTime::DateTimeUtc(*datetime + by),
+            EventTime::DateTimeFloating(datetime) => EventTime::DateTimeFloating(*datetime + by),
+            EventTime::DateTimeZoned { datetime, tzid } => EventTime::DateTimeZoned {
+                datetime: *datetime + by,
+                tzid: tzid.clone(),
+            },
+        }
+    }
+
+    /// A floating time placed in `floating`, as a zoned or UTC time. Other
+    /// times are returned as they are.
+    pub(crate) fn pinned(&self, floating: FloatingTimezone) -> EventTime {
+        let EventTime::DateTimeFloating(datetime) = self else {
+            return self.clone();
+        };
+
+        match floating {
+            FloatingTimezone::Local => EventTime::DateTimeUtc(self.to_utc()),
+            FloatingTimezone::Utc => EventTime::DateTimeUtc(datetime.and_utc()),
+            FloatingTimezone::Zone(tz) => EventTime::DateTimeZoned {
+                datetime: *datetime,
+                tzid: tz.name().to_string(),
             },
         }
     }
@@ -85,7 +144,7 @@ impl EventTime {
     }
 }
 
-fn parse_tzid(tzid: &str) -> Option<chrono_tz::Tz> {
+pub(super) fn parse_tzid(tzid: &str) -> Option<chrono_tz::Tz> {
     match tzid.parse() {
         Ok(tz) => Some(tz),
         Err(_) => {
@@ -277,6 +336,53 @@ mod tests {
         assert_eq!(local.format("%Y-%m-%dT%H%M").to_string(), "2024-10-27T0230");
     }
 
+    #[test]
+    fn floating_time_follows_the_viewer_unless_pinned() {
+        let naive = NaiveDate::from_ymd_opt(2026, 1, 15)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let floating = EventTime::DateTimeFloating(naive);
+        let in_stockholm = |policy| {
+            floating
+                .to_local_tz_with(&chrono_tz::Europe::Stockholm, policy)
+                .format("%H%M")
+                .to_string()
+        };
+
+        assert_eq!(in_stockholm(FloatingTimezone::Local), "0900");
+        assert_eq!(in_stockholm(FloatingTimezone::Utc), "1000");
+        assert_eq!(
+            in_stockholm(FloatingTimezone::Zone(chrono_tz::America::New_York)),
+            "1500"
+        );
+    }
+
+    #[test]
+    fn pinned_floating_time_keeps_its_wall_clock_in_a_fixed_zone() {
+        let naive = NaiveDate::from_ymd_opt(2026, 1, 15)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let floating = EventTime::DateTimeFloating(naive);
+
+        assert_eq!(
+            floating.pinned(FloatingTimezone::Utc),
+            EventTime::DateTimeUtc(naive.and_utc())
+        );
+        assert_eq!(
+            floating.pinned(FloatingTimezone::Zone(chrono_tz::Europe::Stockholm)),
+            EventTime::DateTimeZoned {
+                datetime: naive,
+                tzid: "Europe/Stockholm".to_string(),
+            }
+        );
+        assert_eq!(
+            EventTime::Date(naive.date()).pinned(FloatingTimezone::Utc),
+            EventTime::Date(naive.date())
+        );
+    }
+
     #[test]
     fn fractional_offset_tzid_parses_to_utc() {
         let datetime = NaiveDate::from_ymd_opt(2026, 7, 24)
@@ -395,4 +501,60 @@ mod tests {
 
         assert_eq!(utc.format("%Y-%m-%dT%H%M").to_string(), "2024-07-01T1600");
     }
+
+    fn stockholm(month: u32, day: u32, hour: u32, minute: u32) -> EventTime {
+        EventTime::DateTimeZoned {
+            datetime: NaiveDate::from_ymd_opt(2026, month, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap(),
+            tzid: "Europe/Stockholm".to_string(),
+        }
+    }
+
+    #[test]
+    fn to_utc_skips_past_a_spring_forward_gap() {
+        // 02:30 doesn't exist on 2026-03-29; it's read as 03:30 CEST
+        let utc = stockholm(3, 29, 2, 30).to_utc();
+
+        assert_eq!(utc, Utc.with_ymd_and_hms(2026, 3, 29, 1, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn to_utc_picks_the_first_of_a_repeated_hour() {
+        // 02:30 happens twice on 2026-10-25; the first one is still CEST
+        let utc = stockholm(10, 25, 2, 30).to_utc();
+
+        assert_eq!(utc, Utc.with_ymd_and_hms(2026, 10, 25, 0, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn to_utc_orders_zoned_times_by_instant() {
+        // 09:00 in Stockholm is before 08:30 in London
+        let london = EventTime::DateTimeZoned {
+            datetime: NaiveDate::from_ymd_opt(2026, 7, 1)
+                .unwrap()
+                .and_hms_opt(8, 30, 0)
+                .unwrap(),
+            tzid: "Europe/London".to_string(),
+        };
+
+        assert!(stockholm(7, 1, 9, 0).to_utc() < london.to_utc());
+    }
+
+    #[test]
+    fn plus_elapsed_follows_the_wall_clock_across_dst() {
+        // Eight hours from 22:00 the night clocks go forward is 07:00
+        let end = stockholm(3, 28, 22, 0).plus_elapsed(Duration::hours(8));
+
+        assert_eq!(end, stockholm(3, 29, 7, 0));
+    }
+
+    #[test]
+    fn shifted_keeps_the_wall_clock_across_dst() {
+        assert_eq!(
+            stockholm(3, 28, 9, 0).shifted(Duration::days(1)),
+            stockholm(3, 29, 9, 0)
+        );
+    }
 }
@@ -1,4 +1,6 @@
-use crate::event::{Availability, Event, Status};
+use crate::event::event_type::EVENT_TYPE_PROPERTY;
+use crate::event::reminder::DEFAULT_REMINDERS_PROPERTY;
+use crate::event::{Availability, Event, EventType, Status};
 use icalendar::{Component, EventLike};
 
 impl From<&Event> for icalendar::Event {
@@ -34,6 +36,10 @@ impl From<&Event> for icalendar::Event {
             event.append_property(icalendar::Property::new("CLASS", visibility.as_ics_str()));
         }
 
+        if value.event_type != EventType::default() {
+            event.add_property(EVENT_TYPE_PROPERTY, value.event_type.as_ics_str());
+        }
+
         if let Some(recurrence) = &value.recurrence {
             recurrence.apply_to(&mut event);
         }
@@ -86,10 +92,27 @@ impl From<&Event> for icalendar::Event {
             event.append_property(icalendar::Property::new("URL", url));
         }
 
+        if let Some(color) = &value.color {
+            event.append_property(icalendar::Property::new("COLOR", color));
+        }
+
+        // One CATEGORIES line per tag rather than a comma-packed list.
+        for category in &value.categories {
+            event.append_multi_property(icalendar::Property::new("CATEGORIES", category));
+        }
+
+        for conference in &value.conferences {
+            event.append_multi_property(icalendar::Property::from(conference));
+        }
+
         for attachment in &value.attachments {
             event.append_multi_property(icalendar::Property::from(attachment));
         }
 
+        if value.reminders_default {
+            event.add_property(DEFAULT_REMINDERS_PROPERTY, "TRUE");
+        }
+
         for x in &value.x_properties {
             event.append_property(icalendar::Property::from(x));
         }
@@ -477,6 +500,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn converts_conferences_separately_from_url() {
+        let mut event = test_event();
+        event.url = Some("https://example.com/event".to_string());
+        event.conferences = vec![
+            crate::event::Conference::new("https://meet.example.com/abc").with_feature("VIDEO"),
+        ];
+
+        let ical_event: icalendar::Event = event.into();
+
+        let conferences = ical_event
+            .multi_properties()
+            .get("CONFERENCE")
+            .expect("CONFERENCE multi-property should be present");
+        assert_eq!(conferences.len(), 1);
+        assert_eq!(conferences[0].value(), "https://meet.example.com/abc");
+        assert_eq!(
+            ical_event.property_value("URL"),
+            Some("https://example.com/event")
+        );
+    }
+
     #[test]
     fn omits_attachments_when_empty() {
         let mut event = test_event();
@@ -487,6 +532,53 @@ mod tests {
         assert!(ical_event.multi_properties().get("ATTACH").is_none());
     }
 
+    #[test]
+    fn converts_color() {
+        let mut event = test_event();
+        event.color = Some("tomato".to_string());
+
+        let ical_event: icalendar::Event = event.into();
+
+        assert_eq!(ical_event.property_value("COLOR"), Some("tomato"));
+    }
+
+    #[test]
+    fn omits_color_when_none() {
+        let mut event = test_event();
+        event.color = None;
+
+        let ical_event: icalendar::Event = event.into();
+
+        assert_eq!(ical_event.property_value("COLOR"), None);
+    }
+
+    #[test]
+    fn converts_categories() {
+        let mut event = test_event();
+        event.categories = vec!["work".to_string(), "deep work".to_string()];
+
+        let ical_event: icalendar::Event = event.into();
+
+        let categories = ical_event
+            .multi_properties()
+            .get("CATEGORIES")
+            .expect("CATEGORIES multi-property should be present");
+        assert_eq!(
+            categories.iter().map(|p| p.value()).collect::<Vec<_>>(),
+            vec!["work", "deep work"]
+        );
+    }
+
+    #[test]
+    fn omits_categories_when_empty() {
+        let mut event = test_event();
+        event.categories = vec![];
+
+        let ical_event: icalendar::Event = event.into();
+
+        assert!(ical_event.multi_properties().get("CATEGORIES").is_none());
+    }
+
     #[test]
     fn converts_x_properties() {
         let mut event = test_event();
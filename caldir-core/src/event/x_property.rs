@@ -1,6 +1,7 @@
 use icalendar::Property;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct XProperty {
     pub name: String,
     pub value: String,
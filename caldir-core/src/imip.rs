@@ -0,0 +1,241 @@
+//! iMIP (RFC 6047): the emails that invite attendees to events we organize,
+//! for providers that don't send invitations themselves, and the ones we
+//! receive.
+
+mod error;
+mod incoming;
+
+use crate::{Event, EventChange, Organizer, Status};
+pub use error::ImipError;
+pub(crate) use incoming::apply;
+pub use incoming::{ImipOutcome, ImipPayload};
+
+/// Fields only the organizer sees, so changing them doesn't re-invite anyone.
+const PRIVATE_FIELDS: &[&str] = &["reminders", "color", "categories", "x_properties"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImipMethod {
+    Request,
+    Reply,
+    Cancel,
+}
+
+impl ImipMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Request => "REQUEST",
+            Self::Reply => "REPLY",
+            Self::Cancel => "CANCEL",
+        }
+    }
+}
+
+/// One email to send: `ics` goes in a `text/calendar` part with `method`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImipMessage {
+    pub method: ImipMethod,
+    pub to: Vec<String>,
+    pub subject: String,
+    pub ics: String,
+}
+
+/// The invitations and cancellations that `changes` call for. Only events
+/// organized by `organizer_email` (or with no organizer) are considered.
+pub fn imip_messages(changes: &[EventChange], organizer_email: &str) -> Vec<ImipMessage> {
+    let mut messages = Vec::new();
+
+    for change in changes {
+        if !is_organized_by(change.event(), organizer_email) {
+            continue;
+        }
+
+        match change {
+            EventChange::Create(event) => {
+                messages.extend(message(ImipMethod::Request, event, organizer_email, &[]));
+            }
+            EventChange::Update { from, to } => {
+                let removed: Vec<&str> = from
+                    .attendees
+                    .iter()
+                    .map(|attendee| attendee.email.as_str())
+                    .filter(|email| !to.attendees.iter().any(|a| a.email == *email))
+                    .collect();
+
+                if !removed.is_empty() {
+                    let cancel = Event {
+                        attendees: from
+                            .attendees
+                            .iter()
+                            .filter(|a| removed.contains(&a.email.as_str()))
+                            .cloned()
+                            .collect(),
+                        ..to.clone()
+                    };
+                    messages.extend(message(
+                        ImipMethod::Cancel,
+                        &cancel,
+                        organizer_email,
+                        &[from.sequence],
+                    ));
+                }
+
+                let significant = from
+                    .changed_fields(to)
+                    .iter()
+                    .any(|field| !PRIVATE_FIELDS.contains(field));
+
+                if significant {
+                    let method = if to.status == Status::Cancelled {
+                        ImipMethod::Cancel
+                    } else {
+                        ImipMethod::Request
+                    };
+                    messages.extend(message(method, to, organizer_email, &[from.sequence]));
+                }
+            }
+            EventChange::Delete(event) => {
+                messages.extend(message(
+                    ImipMethod::Cancel,
+                    event,
+                    organizer_email,
+                    &[event.sequence],
+                ));
+            }
+        }
+    }
+
+    messages
+}
+
+fn is_organized_by(event: &Event, email: &str) -> bool {
+    event
+        .organizer
+        .as_ref()
+        .is_none_or(|organizer| organizer.email.eq_ignore_ascii_case(email))
+}
+
+/// `None` when nobody but the organizer is invited. The sequence ends up
+/// above every one in `sent_sequences`, so attendees' clients take the
+/// message as newer than what they have.
+fn message(
+    method: ImipMethod,
+    event: &Event,
+    organizer_email: &str,
+    sent_sequences: &[i32],
+) -> Option<ImipMessage> {
+    let to: Vec<String> = event
+        .attendees
+        .iter()
+        .filter(|attendee| !attendee.email.eq_ignore_ascii_case(organizer_email))
+        .map(|attendee| attendee.email.clone())
+        .collect();
+
+    if to.is_empty() {
+        return None;
+    }
+
+    let mut event = event.clone();
+    event
+        .organizer
+        .get_or_insert_with(|| Organizer::new(organizer_email));
+    event.reminders.clear();
+    event.reminders_default = false;
+    event.sequence = sent_sequences
+        .iter()
+        .map(|sequence| sequence + 1)
+        .fold(event.sequence, i32::max);
+    if method == ImipMethod::Cancel {
+        event.status = Status::Cancelled;
+    }
+
+    let summary = event.summary.as_deref().unwrap_or("(Untitled)");
+    let subject = match method {
+        ImipMethod::Request => format!("Invitation: {summary}"),
+        ImipMethod::Reply => format!("Reply: {summary}"),
+        ImipMethod::Cancel => format!("Canceled: {summary}"),
+    };
+
+    Some(ImipMessage {
+        method,
+        to,
+        subject,
+        ics: event.to_itip_string(method.as_str()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Attendee;
+    use crate::test_utils::test_event;
+
+    const ORGANIZER: &str = "me@example.com";
+
+    fn meeting() -> Event {
+        Event {
+            organizer: Some(Organizer::new(ORGANIZER)),
+            attendees: vec![Attendee::new(ORGANIZER), Attendee::new("ann@example.com")],
+            ..test_event()
+        }
+    }
+
+    #[test]
+    fn create_invites_attendees_but_not_the_organizer() {
+        let messages = imip_messages(&[EventChange::Create(meeting())], ORGANIZER);
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].method, ImipMethod::Request);
+        assert_eq!(messages[0].to, ["ann@example.com"]);
+        assert!(messages[0].ics.contains("METHOD:REQUEST"));
+    }
+
+    #[test]
+    fn skips_events_organized_by_someone_else() {
+        let event = Event {
+            organizer: Some(Organizer::new("boss@example.com")),
+            ..meeting()
+        };
+
+        assert!(imip_messages(&[EventChange::Create(event)], ORGANIZER).is_empty());
+    }
+
+    #[test]
+    fn update_cancels_for_removed_attendees_and_bumps_sequence() {
+        let from = meeting();
+        let to = Event {
+            summary: Some("Moved".to_string()),
+            attendees: vec![Attendee::new("bob@example.com")],
+            ..meeting()
+        };
+
+        let messages = imip_messages(&[EventChange::Update { from, to }], ORGANIZER);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].method, ImipMethod::Cancel);
+        assert_eq!(messages[0].to, ["ann@example.com"]);
+        assert_eq!(messages[1].method, ImipMethod::Request);
+        assert_eq!(messages[1].to, ["bob@example.com"]);
+        assert!(messages[1].ics.contains("SEQUENCE:1"));
+    }
+
+    #[test]
+    fn private_changes_send_nothing() {
+        let from = meeting();
+        let to = Event {
+            color: Some("#ff0000".to_string()),
+            ..meeting()
+        };
+
+        assert!(imip_messages(&[EventChange::Update { from, to }], ORGANIZER).is_empty());
+    }
+
+    #[test]
+    fn delete_sends_cancel() {
+        let messages = imip_messages(&[EventChange::Delete(meeting())], ORGANIZER);
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].method, ImipMethod::Cancel);
+        assert!(messages[0].ics.contains("METHOD:CANCEL"));
+        assert!(messages[0].ics.contains("STATUS:CANCELLED"));
+    }
+}
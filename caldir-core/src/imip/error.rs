@@ -0,0 +1,16 @@
+use crate::event::EventError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImipError {
+    #[error("not an invitation: the calendar has no METHOD")]
+    MissingMethod,
+
+    #[error("unsupported iTIP method {0} (expected REQUEST, REPLY or CANCEL)")]
+    UnsupportedMethod(String),
+
+    #[error("invitation has no events")]
+    NoEvents,
+
+    #[error(transparent)]
+    Event(#[from] EventError),
+}
@@ -0,0 +1,267 @@
+//! Invitations, replies and cancellations received by email, applied to a
+//! calendar.
+
+use super::{ImipError, ImipMethod};
+use crate::calendar::CalendarError;
+use crate::{Attendee, Calendar, Event, ParticipationStatus, Status};
+
+/// A parsed iTIP message (RFC 5546).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImipPayload {
+    pub method: ImipMethod,
+    pub events: Vec<Event>,
+}
+
+/// What applying one event of an [`ImipPayload`] did.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImipOutcome {
+    Created(Event),
+    Updated(Event),
+    Cancelled(Event),
+    Replied {
+        event: Event,
+        email: String,
+        status: ParticipationStatus,
+    },
+    /// The calendar already has a newer version (higher SEQUENCE)
+    Outdated(Event),
+    /// A reply or cancellation for an event the calendar doesn't have
+    Unknown(Event),
+}
+
+impl ImipPayload {
+    pub fn parse(ics: &str) -> Result<Self, ImipError> {
+        let method = calendar_method(ics).ok_or(ImipError::MissingMethod)?;
+        let method = match method.to_ascii_uppercase().as_str() {
+            "REQUEST" => ImipMethod::Request,
+            "REPLY" => ImipMethod::Reply,
+            "CANCEL" => ImipMethod::Cancel,
+            _ => return Err(ImipError::UnsupportedMethod(method)),
+        };
+
+        let events = Event::from_ics_str(ics)?
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if events.is_empty() {
+            return Err(ImipError::NoEvents);
+        }
+
+        Ok(Self { method, events })
+    }
+}
+
+/// The value of the top-level METHOD property.
+fn calendar_method(ics: &str) -> Option<String> {
+    ics.lines()
+        .take_while(|line| !line.starts_with("BEGIN:VEVENT"))
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("METHOD")
+                .then(|| value.trim().to_string())
+        })
+}
+
+pub(crate) fn apply(
+    calendar: &Calendar,
+    payload: &ImipPayload,
+) -> Result<Vec<ImipOutcome>, CalendarError> {
+    let mut outcomes = Vec::new();
+
+    for incoming in &payload.events {
+        let existing = calendar.event_by_instance_id(&incoming.event_instance_id())?;
+
+        let outcome = match (payload.method, existing) {
+            (_, Some(existing))
+                if payload.method != ImipMethod::Reply
+                    && incoming.sequence < existing.event().sequence =>
+            {
+                ImipOutcome::Outdated(existing.event().clone())
+            }
+            (ImipMethod::Request, None) => {
+                calendar.create_event(incoming.clone())?;
+                ImipOutcome::Created(incoming.clone())
+            }
+            (ImipMethod::Request, Some(mut existing)) => {
+                // Reminders are ours, not the organizer's
+                let updated = Event {
+                    reminders: existing.event().reminders.clone(),
+                    ..incoming.clone()
+                }
+                .with_x_properties_merged_from(existing.event());
+                existing.update(updated.clone())?;
+                ImipOutcome::Updated(updated)
+            }
+            (ImipMethod::Reply, Some(mut existing)) => {
+                let Some(replied) = incoming.attendees.first() else {
+                    continue;
+                };
+                let status = replied.status.unwrap_or(ParticipationStatus::NeedsAction);
+
+                let mut updated = existing.event().clone();
+                match updated
+                    .attendees
+                    .iter_mut()
+                    .find(|a| a.email.eq_ignore_ascii_case(&replied.email))
+                {
+                    Some(attendee) => attendee.status = Some(status),
+                    // Someone the invitation was forwarded to
+                    None => updated.attendees.push(Attendee {
+                        status: Some(status),
+                        ..replied.clone()
+                    }),
+                }
+                existing.update(updated.clone())?;
+
+                ImipOutcome::Replied {
+                    event: updated,
+                    email: replied.email.clone(),
+                    status,
+                }
+            }
+            (ImipMethod::Cancel, Some(mut existing)) => {
+                let updated = Event {
+                    status: Status::Cancelled,
+                    sequence: incoming.sequence,
+                    ..existing.event().clone()
+                };
+                existing.update(updated.clone())?;
+                ImipOutcome::Cancelled(updated)
+            }
+            // One occurrence of a series we have: drop it from the series
+            (ImipMethod::Cancel, None)
+                if incoming.recurrence_id.is_some()
+                    && calendar.master_event_for(incoming.uid.as_str())?.is_some() =>
+            {
+                calendar.delete_recurring_instance(&incoming.event_instance_id())?;
+                ImipOutcome::Cancelled(Event {
+                    status: Status::Cancelled,
+                    ..incoming.clone()
+                })
+            }
+            (ImipMethod::Reply | ImipMethod::Cancel, None) => {
+                ImipOutcome::Unknown(incoming.clone())
+            }
+        };
+
+        outcomes.push(outcome);
+    }
+
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Organizer;
+    use crate::test_utils::{test_calendar, test_event};
+
+    // Same UID every time, so each test's payloads are about one event
+    fn invitation() -> Event {
+        Event {
+            uid: crate::event::EventUid::new("invitation@example.com"),
+            organizer: Some(Organizer::new("boss@example.com")),
+            attendees: vec![Attendee {
+                status: Some(ParticipationStatus::NeedsAction),
+                ..Attendee::new("me@example.com")
+            }],
+            ..test_event()
+        }
+    }
+
+    fn payload(method: ImipMethod, event: Event) -> ImipPayload {
+        ImipPayload::parse(&event.to_itip_string(method.as_str())).unwrap()
+    }
+
+    #[test]
+    fn parse_reads_method_and_events() {
+        let parsed = payload(ImipMethod::Reply, invitation());
+
+        assert_eq!(parsed.method, ImipMethod::Reply);
+        assert_eq!(parsed.events[0].uid, invitation().uid);
+    }
+
+    #[test]
+    fn parse_rejects_plain_calendars() {
+        let result = ImipPayload::parse(&invitation().to_ics_string());
+
+        assert!(matches!(result, Err(ImipError::MissingMethod)));
+    }
+
+    #[test]
+    fn request_creates_then_updates() {
+        let (_tmp, calendar) = test_calendar();
+
+        let created = apply(&calendar, &payload(ImipMethod::Request, invitation())).unwrap();
+        assert!(matches!(created.as_slice(), [ImipOutcome::Created(_)]));
+
+        let moved = Event {
+            summary: Some("Moved".to_string()),
+            sequence: 1,
+            ..invitation()
+        };
+        let updated = apply(&calendar, &payload(ImipMethod::Request, moved)).unwrap();
+        assert!(matches!(updated.as_slice(), [ImipOutcome::Updated(_)]));
+
+        let events = calendar.events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event().summary.as_deref(), Some("Moved"));
+    }
+
+    #[test]
+    fn outdated_request_is_ignored() {
+        let (_tmp, calendar) = test_calendar();
+        calendar
+            .create_event(Event {
+                sequence: 2,
+                ..invitation()
+            })
+            .unwrap();
+
+        let outcomes = apply(&calendar, &payload(ImipMethod::Request, invitation())).unwrap();
+
+        assert!(matches!(outcomes.as_slice(), [ImipOutcome::Outdated(_)]));
+    }
+
+    #[test]
+    fn reply_updates_attendee_status() {
+        let (_tmp, calendar) = test_calendar();
+        calendar.create_event(invitation()).unwrap();
+
+        let reply = Event {
+            attendees: vec![Attendee {
+                status: Some(ParticipationStatus::Accepted),
+                ..Attendee::new("ME@example.com")
+            }],
+            ..invitation()
+        };
+        apply(&calendar, &payload(ImipMethod::Reply, reply)).unwrap();
+
+        let event = calendar.events().unwrap().remove(0);
+        assert_eq!(
+            event.event().attendee_status("me@example.com"),
+            Some(ParticipationStatus::Accepted)
+        );
+    }
+
+    #[test]
+    fn cancel_marks_event_cancelled() {
+        let (_tmp, calendar) = test_calendar();
+        calendar.create_event(invitation()).unwrap();
+
+        apply(&calendar, &payload(ImipMethod::Cancel, invitation())).unwrap();
+
+        let event = calendar.events().unwrap().remove(0);
+        assert_eq!(event.event().status, Status::Cancelled);
+    }
+
+    #[test]
+    fn cancel_for_unknown_event_changes_nothing() {
+        let (_tmp, calendar) = test_calendar();
+
+        let outcomes = apply(&calendar, &payload(ImipMethod::Cancel, invitation())).unwrap();
+
+        assert!(matches!(outcomes.as_slice(), [ImipOutcome::Unknown(_)]));
+        assert!(calendar.events().unwrap().is_empty());
+    }
+}
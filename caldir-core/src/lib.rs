@@ -3,24 +3,34 @@ mod calendar;
 mod connection;
 mod diff;
 mod event;
+mod imip;
 pub mod provider;
 mod remote;
 pub mod rpc;
 mod utils;
+mod validate;
 
 #[cfg(test)]
 mod test_utils;
 
 // Public API:
-pub use caldir::{Caldir, CaldirConfig, CaldirError, TimeFormat};
-pub use calendar::{Calendar, CalendarConfig, CalendarEvent};
+pub use caldir::{
+    Caldir, CaldirConfig, CaldirError, Conflict, Contact, DateOrder, FloatingTimezone, QueryMatch,
+    SmtpConfig, Snapshot, SnapshotError, TimeFormat, WeekStart,
+};
+pub use calendar::{
+    Calendar, CalendarConfig, CalendarEvent, DuplicateRepair, EventQuery, Layout, PullFilter,
+    PullRule, StorageFormat, SyncChangeKind, SyncDirection, SyncLogEntry, TrashedEvent,
+};
 pub use connection::Connection;
-pub use diff::{CalendarDiff, EventChange};
+pub use diff::{CalendarDiff, EventChange, PlannedStep};
 pub use event::{
-    Attachment, Attendee, Availability, Event, EventInstanceId, EventTime, EventUid, Organizer,
-    ParticipationStatus, Recurrence, RecurrenceId, Reminder, Status, Visibility, XProperty,
-    expand_in_range, tz_normalize,
+    Attachment, Attendee, Availability, Conference, Event, EventInstanceId, EventTime, EventType,
+    EventUid, Organizer, ParticipationStatus, Recurrence, RecurrenceId, Reminder, Status,
+    Visibility, XProperty, expand_in_range, expand_in_range_with, tz_normalize,
 };
+pub use imip::{ImipError, ImipMessage, ImipMethod, ImipOutcome, ImipPayload, imip_messages};
 pub use provider::{Provider, ProviderRegistry, ProviderSlug};
 pub use remote::{Remote, RemoteConfig, RemoteConfigParams, RemoteEvent};
-pub use utils::{DateBounds, DateRange};
+pub use utils::{ConfigKeyError, DateBounds, DateRange};
+pub use validate::{IcsProblem, Severity, validate_ics};
@@ -1,9 +1,11 @@
 mod account;
+mod env;
 mod error;
 mod handler;
 mod registry;
 mod slug;
 mod storage;
+mod token_storage;
 pub(crate) mod transport;
 
 #[cfg(test)]
@@ -11,25 +13,49 @@ pub(crate) mod mock_provider;
 
 use crate::rpc;
 use account::ProviderAccount;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tokio::sync::{OnceCell, mpsc};
 use transport::{ProviderTransport, SubprocessTransport};
 
 pub(crate) use error::ProviderError;
-pub use handler::{Error, Handler, Result, process_request, run_provider};
+pub use handler::{
+    CodedError, Error, EventSink, Handler, Result, TransientError, process_request, run_provider,
+};
 pub use registry::ProviderRegistry;
 pub use slug::{ProviderSlug, provider_slug_from_filename};
-pub use storage::{ProviderStorage, StorageError};
+pub use storage::{ProviderStorage, StorageError, StorageLock};
+#[cfg(feature = "keyring")]
+pub use token_storage::KeyringToken;
+pub use token_storage::TokenStorage;
+pub use transport::{TRACE_ENV, TraceTarget, enable_protocol_trace};
+
+pub(crate) use env::ProviderEnv;
 
 #[derive(Debug, Clone)]
 pub struct Provider {
     slug: ProviderSlug,
+    /// `None` for in-process and test transports
+    binary_path: Option<PathBuf>,
     transport: Arc<dyn ProviderTransport>,
+    /// Asked for on first use, then shared by every clone
+    description: Arc<OnceCell<rpc::ProviderDescription>>,
 }
 
 impl Provider {
+    #[cfg(test)]
     pub(crate) fn from_binary_path(
         binary_path: PathBuf,
+    ) -> std::result::Result<Self, ProviderError> {
+        Self::from_binary_path_with_env(binary_path, ProviderEnv::default())
+    }
+
+    /// Like `from_binary_path`, with `env` set on every provider process.
+    pub(crate) fn from_binary_path_with_env(
+        binary_path: PathBuf,
+        env: ProviderEnv,
     ) -> std::result::Result<Self, ProviderError> {
         if !is_executable(&binary_path) {
             return Err(ProviderError::NotExecutable(binary_path));
@@ -41,18 +67,41 @@ impl Provider {
             .and_then(provider_slug_from_filename)
             .ok_or_else(|| ProviderError::InvalidProviderFilename(binary_path.clone()))?;
 
-        let transport = SubprocessTransport::new(binary_path);
+        let transport = SubprocessTransport::new(binary_path.clone()).with_env(env);
 
         Ok(Provider {
             slug,
+            binary_path: Some(binary_path),
             transport: Arc::new(transport),
+            description: Arc::default(),
         })
     }
 
+    /// A provider served by `handler` inside this process rather than by a
+    /// `caldir-provider-{slug}` binary. Add it with [`Caldir::with_provider`].
+    ///
+    /// [`Caldir::with_provider`]: crate::Caldir::with_provider
+    #[cfg(any(test, feature = "in-process"))]
+    pub fn in_process(slug: impl Into<ProviderSlug>, handler: impl Handler + 'static) -> Self {
+        let slug = slug.into();
+        let transport = transport::InProcessTransport::new(slug.to_string(), handler);
+
+        Provider {
+            slug,
+            binary_path: None,
+            transport: Arc::new(transport),
+            description: Arc::default(),
+        }
+    }
+
     pub fn slug(&self) -> &ProviderSlug {
         &self.slug
     }
 
+    pub fn binary_path(&self) -> Option<&Path> {
+        self.binary_path.as_deref()
+    }
+
     pub fn provider_account(&self, identifier: String) -> ProviderAccount {
         ProviderAccount::new(self.clone(), identifier)
     }
@@ -67,24 +116,85 @@ impl Provider {
         Ok(result)
     }
 
+    /// What the provider supports. Providers that predate `describe` (or
+    /// fail to answer it) are assumed to support what caldir always relied on.
+    pub async fn describe(&self) -> &rpc::ProviderDescription {
+        self.description
+            .get_or_init(|| async {
+                self.call(rpc::Describe {})
+                    .await
+                    .unwrap_or_else(|_| rpc::ProviderDescription::legacy())
+            })
+            .await
+    }
+
+    /// Start the provider and ask it to `describe` itself, failing if it
+    /// can't be run or doesn't answer. Providers that predate `describe` pass.
+    pub async fn handshake(&self) -> std::result::Result<&rpc::ProviderDescription, ProviderError> {
+        self.description
+            .get_or_try_init(|| async {
+                match self.call(rpc::Describe {}).await {
+                    Err(ProviderError::Provider(_)) => Ok(rpc::ProviderDescription::legacy()),
+                    result => result,
+                }
+            })
+            .await
+    }
+
     pub(crate) async fn call<C: rpc::Rpc>(
         &self,
         call: C,
     ) -> std::result::Result<C::Response, ProviderError> {
-        let request_value = call.to_json().map_err(ProviderError::Serialize)?;
-        let request_json =
-            serde_json::to_string(&request_value).map_err(ProviderError::Serialize)?;
+        let request_json = request_json(&call)?;
 
         // Make call:
         let response_json = self.transport.exchange(&request_json, C::TIMEOUT).await?;
 
-        let response: rpc::Response<C::Response> =
-            serde_json::from_str(&response_json).map_err(ProviderError::Deserialize)?;
+        parse_response::<C::Response>(&response_json)
+    }
 
-        match response {
-            rpc::Response::Success { data } => Ok(data),
-            rpc::Response::Error { error } => Err(ProviderError::Provider(error)),
+    /// Like `call`, for commands whose response is a list the provider may
+    /// stream in chunks. `on_progress` gets the number of items received so far.
+    pub(crate) async fn call_streaming<C, T>(
+        &self,
+        call: C,
+        on_progress: &(dyn Fn(usize) + Sync),
+    ) -> std::result::Result<Vec<T>, ProviderError>
+    where
+        C: rpc::Rpc<Response = Vec<T>>,
+        T: DeserializeOwned,
+    {
+        #[derive(Deserialize)]
+        struct Chunk<T> {
+            data: Vec<T>,
         }
+
+        let request_json = request_json(&call)?;
+        let (chunks_tx, mut chunks) = mpsc::unbounded_channel();
+
+        let exchange = self
+            .transport
+            .exchange_streaming(&request_json, C::TIMEOUT, chunks_tx);
+
+        let collect = async {
+            let mut items = Vec::new();
+            while let Some(line) = chunks.recv().await {
+                let chunk: Chunk<T> =
+                    serde_json::from_str(&line).map_err(ProviderError::Deserialize)?;
+                items.extend(chunk.data);
+                on_progress(items.len());
+            }
+            Ok::<_, ProviderError>(items)
+        };
+
+        let (response_json, items) = tokio::join!(exchange, collect);
+        let mut items = items?;
+
+        // The final response holds whatever wasn't streamed (everything, for
+        // providers that don't stream)
+        items.extend(parse_response::<Vec<T>>(&response_json?)?);
+
+        Ok(items)
     }
 
     #[cfg(test)]
@@ -92,7 +202,19 @@ impl Provider {
         slug: ProviderSlug,
         transport: Arc<dyn ProviderTransport>,
     ) -> Self {
-        Provider { slug, transport }
+        Provider {
+            slug,
+            binary_path: None,
+            transport,
+            description: Arc::default(),
+        }
+    }
+
+    /// Skip asking the provider to `describe` itself.
+    #[cfg(test)]
+    pub(crate) fn with_description(self, description: rpc::ProviderDescription) -> Self {
+        let _ = self.description.set(description);
+        self
     }
 
     #[cfg(test)]
@@ -101,6 +223,31 @@ impl Provider {
     }
 }
 
+fn request_json<C: rpc::Rpc>(call: &C) -> std::result::Result<String, ProviderError> {
+    let request_value = call.to_json().map_err(ProviderError::Serialize)?;
+    serde_json::to_string(&request_value).map_err(ProviderError::Serialize)
+}
+
+fn parse_response<T: DeserializeOwned>(json: &str) -> std::result::Result<T, ProviderError> {
+    let response: rpc::Response<T> =
+        serde_json::from_str(json).map_err(ProviderError::Deserialize)?;
+
+    match response {
+        rpc::Response::Success { data } => Ok(data),
+        rpc::Response::Error {
+            error,
+            transient,
+            retry_after,
+            code,
+        } => Err(ProviderError::from_response(
+            error,
+            transient,
+            retry_after,
+            code,
+        )),
+    }
+}
+
 #[cfg(unix)]
 fn is_executable(path: &Path) -> bool {
     use std::os::unix::fs::PermissionsExt;
@@ -263,6 +410,36 @@ mod tests {
         assert!(matches!(err, ProviderError::Provider(msg) if msg == "oh no"));
     }
 
+    #[tokio::test]
+    async fn call_maps_error_codes_to_variants() {
+        let mock = Arc::new(MockTransport::with_response(
+            r#"{"status":"error","error":"token revoked","code":"auth_expired"}"#,
+        ));
+        let provider = provider_with_transport(mock);
+
+        let err = provider
+            .call(EchoCommand { value: "x".into() })
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProviderError::AuthExpired(msg) if msg == "token revoked"));
+    }
+
+    #[tokio::test]
+    async fn call_reports_provider_exit_as_crash() {
+        let mock = Arc::new(MockTransport::with_error(
+            ProviderTransportError::NonZeroExit { code: Some(101) },
+        ));
+        let provider = provider_with_transport(mock);
+
+        let err = provider
+            .call(EchoCommand { value: "x".into() })
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProviderError::Crashed { code: Some(101) }));
+    }
+
     #[tokio::test]
     async fn call_returns_deserialize_error_on_garbage_response() {
         let mock = Arc::new(MockTransport::with_response("not json at all"));
@@ -293,4 +470,75 @@ mod tests {
             ProviderError::Transport(ProviderTransportError::Timeout(_))
         ));
     }
+
+    #[tokio::test]
+    async fn describe_is_asked_once() {
+        let mock = Arc::new(MockTransport::with_response(
+            r#"{"status":"success","data":{"protocol_version":1,"auth":"local","capabilities":{"reminders":false}}}"#,
+        ));
+        let provider = provider_with_transport(mock);
+
+        provider.describe().await;
+        let description = provider.clone().describe().await.clone();
+
+        assert_eq!(description.auth, rpc::AuthStyle::Local);
+        assert!(!description.capabilities.reminders);
+    }
+
+    #[tokio::test]
+    async fn describe_falls_back_for_providers_without_it() {
+        let mock = Arc::new(MockTransport::with_response(
+            r#"{"status":"error","error":"Failed to parse request: unknown variant `describe`"}"#,
+        ));
+        let provider = provider_with_transport(mock);
+
+        let description = provider.describe().await;
+
+        assert_eq!(description, &rpc::ProviderDescription::legacy());
+    }
+
+    #[tokio::test]
+    async fn handshake_fails_when_provider_does_not_answer() {
+        let mock = Arc::new(MockTransport::with_error(
+            ProviderTransportError::EmptyResponse,
+        ));
+        let provider = provider_with_transport(mock);
+
+        let result = provider.handshake().await;
+
+        assert!(matches!(
+            result,
+            Err(ProviderError::Transport(
+                ProviderTransportError::EmptyResponse
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn in_process_provider_is_served_by_its_handler() {
+        struct AccountHandler;
+        #[async_trait::async_trait]
+        impl Handler for AccountHandler {
+            async fn connect(&self, _cmd: rpc::Connect) -> Result<rpc::ConnectResponse> {
+                Ok(rpc::ConnectResponse::Done {
+                    account_identifier: Some("me@example.com".to_string()),
+                    calendars: None,
+                })
+            }
+        }
+
+        let provider = Provider::in_process("hooli", AccountHandler);
+
+        let response = provider
+            .connect(Default::default(), Default::default())
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            response,
+            rpc::ConnectResponse::Done { account_identifier: Some(id), .. } if id == "me@example.com"
+        ));
+        assert_eq!(provider.slug(), &ProviderSlug::from("hooli"));
+        assert!(provider.handshake().await.is_ok());
+    }
 }
@@ -1,5 +1,5 @@
 use crate::provider::ProviderError;
-use crate::rpc::ListCalendars;
+use crate::rpc::{AccountStatus, CheckAccount, Disconnect, ListCalendars};
 use crate::{CalendarConfig, Provider};
 
 pub struct ProviderAccount {
@@ -22,4 +22,20 @@ impl ProviderAccount {
             })
             .await
     }
+
+    pub async fn check(&self) -> Result<AccountStatus, ProviderError> {
+        self.provider
+            .call(CheckAccount {
+                account_identifier: self.identifier.clone(),
+            })
+            .await
+    }
+
+    pub async fn disconnect(&self) -> Result<(), ProviderError> {
+        self.provider
+            .call(Disconnect {
+                account_identifier: self.identifier.clone(),
+            })
+            .await
+    }
 }
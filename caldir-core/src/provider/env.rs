@@ -0,0 +1,34 @@
+//! Process environment caldir hands to provider subprocesses.
+//!
+//! Providers read these back via [`ProviderStorage`](super::ProviderStorage)
+//! and [`TokenStorage::from_env`](super::TokenStorage::from_env).
+
+use std::path::PathBuf;
+
+use tokio::process::Command;
+
+use super::TokenStorage;
+use crate::utils::paths::CALDIR_HOME_ENV;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProviderEnv {
+    caldir_home: Option<PathBuf>,
+    token_storage: TokenStorage,
+}
+
+impl ProviderEnv {
+    pub(crate) fn new(caldir_home: Option<PathBuf>, token_storage: TokenStorage) -> Self {
+        Self {
+            caldir_home,
+            token_storage,
+        }
+    }
+
+    pub(crate) fn apply(&self, command: &mut Command) {
+        if let Some(home) = &self.caldir_home {
+            command.env(CALDIR_HOME_ENV, home);
+        }
+
+        command.env(TokenStorage::ENV, self.token_storage.as_str());
+    }
+}
@@ -1,6 +1,8 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::transport::ProviderTransportError;
+use crate::rpc::ErrorCode;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ProviderError {
@@ -14,7 +16,10 @@ pub enum ProviderError {
     ProviderNotFound(String),
 
     #[error("Provider transport error: {0}")]
-    Transport(#[from] ProviderTransportError),
+    Transport(ProviderTransportError),
+
+    #[error("Provider exited unexpectedly with status {code:?}")]
+    Crashed { code: Option<i32> },
 
     #[error("Failed to serialize provider request: {0}")]
     Serialize(serde_json::Error),
@@ -24,4 +29,79 @@ pub enum ProviderError {
 
     #[error("Provider returned error: {0}")]
     Provider(String),
+
+    #[error("Provider returned error: {message}")]
+    Transient {
+        message: String,
+        retry_after: Option<Duration>,
+    },
+
+    #[error("Sign-in expired: {0}")]
+    AuthExpired(String),
+
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        retry_after: Option<Duration>,
+    },
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+}
+
+impl ProviderError {
+    /// From the fields of an error response.
+    pub(crate) fn from_response(
+        message: String,
+        transient: bool,
+        retry_after: Option<u64>,
+        code: Option<ErrorCode>,
+    ) -> Self {
+        let retry_after = retry_after.map(Duration::from_secs);
+
+        match code {
+            Some(ErrorCode::AuthExpired) => Self::AuthExpired(message),
+            Some(ErrorCode::RateLimited) => Self::RateLimited {
+                message,
+                retry_after,
+            },
+            Some(ErrorCode::NotFound) => Self::NotFound(message),
+            Some(ErrorCode::Conflict) => Self::Conflict(message),
+            _ if transient => Self::Transient {
+                message,
+                retry_after,
+            },
+            _ => Self::Provider(message),
+        }
+    }
+
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Self::AuthExpired(_) => Some(ErrorCode::AuthExpired),
+            Self::RateLimited { .. } => Some(ErrorCode::RateLimited),
+            Self::NotFound(_) => Some(ErrorCode::NotFound),
+            Self::Conflict(_) => Some(ErrorCode::Conflict),
+            _ => None,
+        }
+    }
+
+    /// Whether the provider couldn't be reached, e.g. because the network is down.
+    pub fn is_unreachable(&self) -> bool {
+        matches!(
+            self,
+            Self::Transient { .. } | Self::Transport(ProviderTransportError::Timeout(_))
+        )
+    }
+}
+
+impl From<ProviderTransportError> for ProviderError {
+    fn from(error: ProviderTransportError) -> Self {
+        match error {
+            ProviderTransportError::NonZeroExit { code } => Self::Crashed { code },
+            error => Self::Transport(error),
+        }
+    }
 }
@@ -7,13 +7,21 @@
 use async_trait::async_trait;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::error::Error as StdError;
 use std::future::Future;
-use std::io::{self, BufRead, Write};
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::{self, AbortHandle, JoinSet};
 
 use crate::rpc::{
-    Connect, ConnectResponse, CreateEvent, DeleteEvent, ListCalendars, ListEvents, Method, Request,
-    Response, UpdateEvent,
+    AccountStatus, BatchWrite, BusySchedule, Capabilities, CheckAccount, Connect, ConnectResponse,
+    ContactCard, CreateEvent, DeleteEvent, Describe, Disconnect, ErrorCode, EventWrite, FreeBusy,
+    ListCalendars, ListEvents, LookupContacts, Method, ProviderDescription, Request, Response,
+    UpdateEvent,
 };
 use crate::{CalendarConfig, Event};
 
@@ -28,6 +36,15 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub trait Handler: Send + Sync {
     async fn connect(&self, cmd: Connect) -> Result<ConnectResponse>;
 
+    /// What this provider supports. Override to turn off what isn't, so
+    /// caldir skips it rather than failing mid-sync.
+    async fn describe(&self, _cmd: Describe) -> Result<ProviderDescription> {
+        Ok(ProviderDescription::new(
+            Default::default(),
+            Capabilities::default(),
+        ))
+    }
+
     async fn list_calendars(&self, _cmd: ListCalendars) -> Result<Vec<CalendarConfig>> {
         Err("list_calendars is not supported by this provider".into())
     }
@@ -36,6 +53,13 @@ pub trait Handler: Send + Sync {
         Err("list_events is not supported by this provider".into())
     }
 
+    /// `list_events`, sending events to `sink` as they come in. Providers
+    /// that fetch page by page override this so the CLI can show progress.
+    async fn stream_events(&self, cmd: ListEvents, sink: &EventSink) -> Result<()> {
+        sink.send(self.list_events(cmd).await?);
+        Ok(())
+    }
+
     async fn create_event(&self, _cmd: CreateEvent) -> Result<Event> {
         Err("This provider does not support creating events".into())
     }
@@ -47,24 +71,143 @@ pub trait Handler: Send + Sync {
     async fn delete_event(&self, _cmd: DeleteEvent) -> Result<()> {
         Err("This provider does not support deleting events".into())
     }
+
+    /// Other people's busy times. Providers that can look them up also set
+    /// the `free_busy` capability.
+    async fn free_busy(&self, _cmd: FreeBusy) -> Result<Vec<BusySchedule>> {
+        Err("free_busy is not supported by this provider".into())
+    }
+
+    /// Names from the account's address book. Providers that have one also
+    /// set the `contacts` capability.
+    async fn lookup_contacts(&self, _cmd: LookupContacts) -> Result<Vec<ContactCard>> {
+        Err("lookup_contacts is not supported by this provider".into())
+    }
+
+    /// What's stored for an account. Providers with accounts override this.
+    async fn check_account(&self, _cmd: CheckAccount) -> Result<AccountStatus> {
+        Err("check_account is not supported by this provider".into())
+    }
+
+    /// Forget an account's stored credentials. Providers with accounts
+    /// override this.
+    async fn disconnect(&self, _cmd: Disconnect) -> Result<()> {
+        Err("disconnect is not supported by this provider".into())
+    }
+
+    /// Runs the writes one at a time through `create_event`/`update_event`.
+    /// Providers with a native batch API override this.
+    async fn batch_write(&self, cmd: BatchWrite) -> Result<Vec<Response<Event>>> {
+        let mut results = Vec::with_capacity(cmd.writes.len());
+
+        for write in cmd.writes {
+            let result = match write {
+                EventWrite::Create(event) => {
+                    self.create_event(CreateEvent {
+                        remote: cmd.remote.clone(),
+                        event,
+                        notify_attendees: cmd.notify_attendees,
+                    })
+                    .await
+                }
+                EventWrite::Update(event) => {
+                    self.update_event(UpdateEvent {
+                        remote: cmd.remote.clone(),
+                        event,
+                        notify_attendees: cmd.notify_attendees,
+                    })
+                    .await
+                }
+            };
+
+            results.push(match result {
+                Ok(data) => Response::Success { data },
+                Err(e) => error_response(format_chain(&*e), &*e),
+            });
+        }
+
+        Ok(results)
+    }
 }
 
 /// Run a provider as a subprocess speaking the caldir RPC protocol over
-/// stdin/stdout. Blocks until stdin closes.
-pub async fn run_provider<H: Handler>(handler: H) {
-    let input = io::stdin().lock();
+/// stdin/stdout. Returns once stdin closes and every request is answered.
+///
+/// Requests are handled concurrently; each response echoes its request's
+/// `id`. A `cancel` request drops the request with the same `id`, or, with
+/// no `id`, everything in flight, and returns so the process exits cleanly.
+pub async fn run_provider<H: Handler + 'static>(handler: H) {
+    let handler = Arc::new(handler);
+    let mut input = BufReader::new(tokio::io::stdin()).lines();
     let mut output = io::stdout();
 
-    for line in input.lines() {
-        let Ok(line) = line else { break };
+    // Responses and streamed chunks, in the order they're to be written
+    let (lines_tx, mut lines) = mpsc::unbounded_channel::<String>();
+
+    let mut tasks = JoinSet::new();
+    // Request id of each running task, to cancel it or to answer if it panics
+    let mut in_flight: HashMap<task::Id, (Option<u64>, AbortHandle)> = HashMap::new();
+    let mut stdin_open = true;
 
-        if line.trim().is_empty() {
-            continue;
+    loop {
+        if !stdin_open && tasks.is_empty() && lines.is_empty() {
+            break;
         }
 
-        let response = process_request(&handler, &line).await;
+        let line = tokio::select! {
+            Some(line) = lines.recv() => line,
+            line = input.next_line(), if stdin_open => {
+                let Ok(Some(line)) = line else {
+                    stdin_open = false;
+                    continue;
+                };
+
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                match serde_json::from_str::<Request>(&line) {
+                    Ok(request) if request.is_cancel() => match request.id {
+                        Some(id) => {
+                            in_flight
+                                .values()
+                                .filter(|(request_id, _)| *request_id == Some(id))
+                                .for_each(|(_, task)| task.abort());
+                            continue;
+                        }
+                        None => break,
+                    },
+                    request => {
+                        let id = request.ok().and_then(|r| r.id);
+                        let handler = handler.clone();
+                        let output = lines_tx.clone();
+                        let task = tasks.spawn(async move {
+                            let response = handle_line(&*handler, &line, Some(&output)).await;
+                            let _ = output.send(response);
+                        });
+                        in_flight.insert(task.id(), (id, task));
+                        continue;
+                    }
+                }
+            }
+            Some(done) = tasks.join_next_with_id() => {
+                let task_id = match &done {
+                    Ok((task_id, ())) => *task_id,
+                    Err(e) => e.id(),
+                };
+                let id = in_flight.remove(&task_id).and_then(|(id, _)| id);
+
+                match done {
+                    Err(e) if e.is_panic() => reply(
+                        &Response::<()>::failure("Provider panicked while handling request"),
+                        id,
+                    ),
+                    _ => continue,
+                }
+            }
+        };
 
-        if writeln!(output, "{}", response).is_err() || output.flush().is_err() {
+        if writeln!(output, "{}", line).is_err() || output.flush().is_err() {
             break;
         }
     }
@@ -73,17 +216,168 @@ pub async fn run_provider<H: Handler>(handler: H) {
 /// Process a single JSON-encoded request line and return the JSON-encoded
 /// response. Exposed for unit tests — most providers only need [`run_provider`].
 pub async fn process_request<H: Handler>(handler: &H, line: &str) -> String {
+    handle_line(handler, line, None).await
+}
+
+/// Like [`process_request`]. With `output`, streamed `list_events` requests
+/// send their events there as `chunk` lines before the final response.
+pub(crate) async fn handle_line<H: Handler>(
+    handler: &H,
+    line: &str,
+    output: Option<&mpsc::UnboundedSender<String>>,
+) -> String {
     let request: Request = match serde_json::from_str(line) {
         Ok(r) => r,
-        Err(e) => return Response::error(&format!("Failed to parse request: {e}")),
+        Err(e) => {
+            // Still echo the id, so an unknown command doesn't leave the CLI waiting
+            let id = serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .and_then(|v| v.get("id")?.as_u64());
+            return reply(
+                &Response::<()>::failure(format!("Failed to parse request: {e}")),
+                id,
+            );
+        }
     };
 
-    match dispatch(handler, request).await {
-        Ok(data) => Response::success(data),
-        Err(e) => Response::error(&format!("Error handling request: {}", format_chain(&*e))),
+    let id = request.id;
+    let sink = output.map(|output| EventSink {
+        id,
+        output: output.clone(),
+    });
+
+    match dispatch(handler, request, sink.as_ref()).await {
+        Ok(data) => reply(&Response::Success { data }, id),
+        Err(e) => {
+            let error = format!("Error handling request: {}", format_chain(&*e));
+            reply(&error_response::<()>(error, &*e), id)
+        }
     }
 }
 
+/// Where [`Handler::stream_events`] sends events as it fetches them.
+pub struct EventSink {
+    id: Option<u64>,
+    output: mpsc::UnboundedSender<String>,
+}
+
+impl EventSink {
+    /// Send a batch of events to the CLI right away.
+    pub fn send(&self, events: Vec<Event>) {
+        if events.is_empty() {
+            return;
+        }
+
+        let mut chunk = serde_json::json!({ "status": "chunk", "data": events });
+        if let Some(id) = self.id {
+            chunk["id"] = id.into();
+        }
+
+        let _ = self.output.send(chunk.to_string());
+    }
+}
+
+/// Serialize `response`, echoing the request's `id` so the CLI can match them up.
+fn reply<T: Serialize>(response: &Response<T>, id: Option<u64>) -> String {
+    let mut value = serde_json::to_value(response).expect("Response serialization is infallible");
+
+    if let (Some(id), Some(fields)) = (id, value.as_object_mut()) {
+        fields.insert("id".to_string(), id.into());
+    }
+
+    value.to_string()
+}
+
+/// The error response for `err`, flagged as transient and tagged with a
+/// code if a [`TransientError`] or [`CodedError`] is in its source chain.
+fn error_response<T>(error: String, err: &(dyn StdError + 'static)) -> Response<T> {
+    let transient = find_in_chain::<TransientError>(err);
+    let code = find_in_chain::<CodedError>(err)
+        .map(|coded| coded.code)
+        .or_else(|| transient.and_then(|t| t.code));
+
+    Response::Error {
+        error,
+        transient: transient.is_some(),
+        retry_after: transient.and_then(|t| t.retry_after).map(|d| d.as_secs()),
+        code,
+    }
+}
+
+/// A failure worth retrying, e.g. a rate limit or a 5xx from the provider's API.
+///
+/// Return it (anywhere in the error's source chain) from a [`Handler`] method
+/// and caldir retries the call with backoff, waiting at least `retry_after`.
+#[derive(Debug)]
+pub struct TransientError {
+    message: String,
+    retry_after: Option<Duration>,
+    code: Option<ErrorCode>,
+}
+
+impl TransientError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retry_after: None,
+            code: None,
+        }
+    }
+
+    pub fn with_retry_after(mut self, retry_after: Option<Duration>) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Usually [`ErrorCode::RateLimited`].
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+}
+
+impl std::fmt::Display for TransientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl StdError for TransientError {}
+
+/// A failure the CLI should be able to tell apart from others, e.g. expired
+/// credentials. Return it (anywhere in the error's source chain) from a
+/// [`Handler`] method to send `code` along with the error.
+#[derive(Debug)]
+pub struct CodedError {
+    code: ErrorCode,
+    message: String,
+}
+
+impl CodedError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+}
+
+impl std::fmt::Display for CodedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl StdError for CodedError {}
+
+fn find_in_chain<'a, E: StdError + 'static>(err: &'a (dyn StdError + 'static)) -> Option<&'a E> {
+    std::iter::successors(Some(err), |e| (*e).source()).find_map(|e| e.downcast_ref())
+}
+
 /// Preserves context from providers' `anyhow::Context`
 fn format_chain(err: &(dyn StdError + 'static)) -> String {
     let mut out = err.to_string();
@@ -96,19 +390,44 @@ fn format_chain(err: &(dyn StdError + 'static)) -> String {
     out
 }
 
-async fn dispatch<H: Handler>(handler: &H, request: Request) -> Result<serde_json::Value> {
-    let Request { method, params } = request;
+async fn dispatch<H: Handler>(
+    handler: &H,
+    request: Request,
+    sink: Option<&EventSink>,
+) -> Result<serde_json::Value> {
+    let Request { method, params, .. } = request;
 
     match method {
+        Method::ListEvents if wants_stream(&params) && sink.is_some() => {
+            let sink = sink.expect("checked above");
+            call(params, |c| handler.stream_events(c, sink)).await?;
+            // Everything went out as chunks; the response just ends the stream
+            Ok(serde_json::Value::Array(Vec::new()))
+        }
         Method::Connect => call(params, |c| handler.connect(c)).await,
+        Method::Describe => call(params, |c| handler.describe(c)).await,
         Method::ListCalendars => call(params, |c| handler.list_calendars(c)).await,
         Method::ListEvents => call(params, |c| handler.list_events(c)).await,
         Method::CreateEvent => call(params, |c| handler.create_event(c)).await,
         Method::UpdateEvent => call(params, |c| handler.update_event(c)).await,
         Method::DeleteEvent => call(params, |c| handler.delete_event(c)).await,
+        Method::BatchWrite => call(params, |c| handler.batch_write(c)).await,
+        Method::FreeBusy => call(params, |c| handler.free_busy(c)).await,
+        Method::LookupContacts => call(params, |c| handler.lookup_contacts(c)).await,
+        Method::CheckAccount => call(params, |c| handler.check_account(c)).await,
+        Method::Disconnect => call(params, |c| handler.disconnect(c)).await,
+        // Nothing in flight to cancel
+        Method::Cancel => Ok(serde_json::Value::Null),
     }
 }
 
+fn wants_stream(params: &serde_json::Value) -> bool {
+    params
+        .get("stream")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
+
 async fn call<C, R, F, Fut>(params: serde_json::Value, handler: F) -> Result<serde_json::Value>
 where
     C: DeserializeOwned,
@@ -170,6 +489,47 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn cancel_with_nothing_in_flight_succeeds() {
+        let response = process_request(&StubHandler, &Request::cancel_json(None)).await;
+
+        let parsed: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(parsed["status"], "success");
+    }
+
+    #[tokio::test]
+    async fn streamed_list_events_sends_chunks_before_response() {
+        struct ListHandler;
+        #[async_trait]
+        impl Handler for ListHandler {
+            async fn connect(&self, _cmd: Connect) -> Result<ConnectResponse> {
+                unimplemented!()
+            }
+
+            async fn list_events(&self, _cmd: ListEvents) -> Result<Vec<Event>> {
+                Ok(vec![crate::test_utils::test_event()])
+            }
+        }
+
+        let request = serde_json::json!({
+            "command": "list_events",
+            "params": { "remote": {}, "from": "", "to": "", "stream": true },
+            "id": 7,
+        });
+        let (output, mut lines) = mpsc::unbounded_channel();
+
+        let response = handle_line(&ListHandler, &request.to_string(), Some(&output)).await;
+
+        let chunk: serde_json::Value = serde_json::from_str(&lines.recv().await.unwrap()).unwrap();
+        assert_eq!(chunk["status"], "chunk");
+        assert_eq!(chunk["id"], 7);
+        assert_eq!(chunk["data"].as_array().unwrap().len(), 1);
+
+        let parsed: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(parsed["status"], "success");
+        assert_eq!(parsed["data"], serde_json::json!([]));
+    }
+
     #[tokio::test]
     async fn error_response_includes_source_chain() {
         #[derive(Debug)]
@@ -213,6 +573,83 @@ mod tests {
         assert_eq!(parsed["error"], "Error handling request: outer: inner");
     }
 
+    #[tokio::test]
+    async fn default_batch_write_reports_each_write() {
+        let event = crate::test_utils::test_event();
+        let request = serde_json::json!({
+            "command": "batch_write",
+            "params": {
+                "writes": [
+                    { "action": "create", "event": event },
+                    { "action": "update", "event": event },
+                ],
+            },
+        });
+
+        let response = process_request(&StubHandler, &request.to_string()).await;
+
+        let parsed: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(parsed["status"], "success");
+        assert_eq!(parsed["data"].as_array().unwrap().len(), 2);
+        assert_eq!(parsed["data"][0]["status"], "error");
+        assert!(
+            parsed["data"][1]["error"]
+                .as_str()
+                .unwrap()
+                .contains("does not support updating events")
+        );
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_flagged_with_retry_after() {
+        struct RateLimitedHandler;
+        #[async_trait]
+        impl Handler for RateLimitedHandler {
+            async fn connect(&self, _cmd: Connect) -> Result<ConnectResponse> {
+                Err(Box::new(
+                    TransientError::new("rate limited")
+                        .with_retry_after(Some(Duration::from_secs(30))),
+                ))
+            }
+        }
+
+        let response = process_request(
+            &RateLimitedHandler,
+            r#"{"command":"connect","params":{"options":{},"data":{}}}"#,
+        )
+        .await;
+
+        let parsed: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(parsed["status"], "error");
+        assert_eq!(parsed["transient"], true);
+        assert_eq!(parsed["retry_after"], 30);
+    }
+
+    #[tokio::test]
+    async fn coded_errors_send_their_code() {
+        struct ExpiredHandler;
+        #[async_trait]
+        impl Handler for ExpiredHandler {
+            async fn connect(&self, _cmd: Connect) -> Result<ConnectResponse> {
+                Err(Box::new(CodedError::new(
+                    ErrorCode::AuthExpired,
+                    "refresh token revoked",
+                )))
+            }
+        }
+
+        let response = process_request(
+            &ExpiredHandler,
+            r#"{"command":"connect","params":{"options":{},"data":{}}}"#,
+        )
+        .await;
+
+        let parsed: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(parsed["status"], "error");
+        assert_eq!(parsed["code"], "auth_expired");
+        assert!(parsed.get("transient").is_none());
+    }
+
     #[tokio::test]
     async fn malformed_json_returns_parse_error() {
         let response = process_request(&StubHandler, "not json").await;
@@ -2,14 +2,15 @@ use super::transport::ProviderTransport;
 use super::transport::ProviderTransportError;
 use super::transport::mock_transport::MockTransport;
 use super::{Provider, ProviderSlug};
-use crate::rpc::{Request, Rpc};
+use crate::rpc::{AuthStyle, Capabilities, ProviderDescription, Request, Rpc};
 use serde::de::DeserializeOwned;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// Test helper for stubbing a `Provider`'s transport with typed RPC expectations.
 pub(crate) struct MockProvider {
     slug: ProviderSlug,
     transport: Arc<MockTransport>,
+    description: Mutex<ProviderDescription>,
 }
 
 impl MockProvider {
@@ -17,9 +18,18 @@ impl MockProvider {
         Self {
             slug: slug.into(),
             transport: Arc::new(MockTransport::empty()),
+            description: Mutex::new(ProviderDescription::new(
+                AuthStyle::Unknown,
+                Capabilities::default(),
+            )),
         }
     }
 
+    /// What providers built from this mock say they support.
+    pub(crate) fn set_capabilities(&self, capabilities: Capabilities) {
+        self.description.lock().unwrap().capabilities = capabilities;
+    }
+
     /// Stub the next RPC call to return `response` (typed by `C::Response`).
     pub(crate) fn reply<C: Rpc>(&self, response: C::Response) {
         let envelope = serde_json::json!({
@@ -29,6 +39,25 @@ impl MockProvider {
         self.transport.set_response(envelope.to_string());
     }
 
+    /// Stub the next RPC call to return a provider error envelope.
+    pub(crate) fn reply_failure(&self, error: &str) {
+        let envelope = serde_json::json!({
+            "status": "error",
+            "error": error,
+        });
+        self.transport.set_response(envelope.to_string());
+    }
+
+    /// Stub the next RPC call to return a transient (retryable) error envelope.
+    pub(crate) fn reply_transient(&self, error: &str) {
+        let envelope = serde_json::json!({
+            "status": "error",
+            "error": error,
+            "transient": true,
+        });
+        self.transport.set_response(envelope.to_string());
+    }
+
     /// Stub the next RPC call to fail with a transport-level error.
     pub(crate) fn reply_error(&self, error: ProviderTransportError) {
         self.transport.set_error(error);
@@ -39,6 +68,7 @@ impl MockProvider {
             self.slug.clone(),
             self.transport.clone() as Arc<dyn ProviderTransport>,
         )
+        .with_description(self.description.lock().unwrap().clone())
     }
 
     /// Decode the captured request as `C`, asserting the wire method matches `C::METHOD`.
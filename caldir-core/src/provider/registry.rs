@@ -1,11 +1,17 @@
 use super::error::ProviderError;
+use crate::provider::ProviderEnv;
 use crate::{Provider, ProviderSlug};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use super::slug::PROVIDER_BINARY_PREFIX;
 
-pub struct ProviderRegistry(HashMap<ProviderSlug, Provider>);
+pub struct ProviderRegistry {
+    providers: HashMap<ProviderSlug, Provider>,
+
+    /// Environment handed to discovered providers (caldir home, token storage)
+    env: ProviderEnv,
+}
 
 impl Default for ProviderRegistry {
     fn default() -> Self {
@@ -15,28 +21,38 @@ impl Default for ProviderRegistry {
 
 impl ProviderRegistry {
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self {
+            providers: HashMap::new(),
+            env: ProviderEnv::default(),
+        }
     }
 
     /// Find all "caldir-provider-{xxx}" binaries in the system `PATH`:
     pub fn from_system_path() -> Self {
-        let path_var = std::env::var_os("PATH").unwrap_or_default();
-        Self::from_dirs(std::env::split_paths(&path_var))
+        Self::from_dirs(system_path_dirs())
+    }
+
+    /// Like `from_system_path`, with `env` set on every provider process.
+    pub(crate) fn from_system_path_with_env(env: ProviderEnv) -> Self {
+        let mut registry = Self::new();
+        registry.env = env;
+        registry.discover(system_path_dirs());
+        registry
     }
 
     pub(crate) fn get(&self, slug: &ProviderSlug) -> Result<&Provider, ProviderError> {
-        self.0
+        self.providers
             .get(slug)
             .ok_or_else(|| ProviderError::ProviderNotFound(slug.to_string()))
     }
 
     pub fn add(&mut self, provider: Provider) {
-        self.0.insert(provider.slug().clone(), provider);
+        self.providers.insert(provider.slug().clone(), provider);
     }
 
     /// Slugs of all registered providers. Order is not stable.
     pub fn slugs(&self) -> Vec<&ProviderSlug> {
-        self.0.keys().collect()
+        self.providers.keys().collect()
     }
 
     fn from_dirs<I>(dirs: I) -> Self
@@ -45,27 +61,39 @@ impl ProviderRegistry {
         I::Item: AsRef<Path>,
     {
         let mut registry = Self::new();
+        registry.discover(dirs);
+        registry
+    }
 
+    fn discover<I>(&mut self, dirs: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<Path>,
+    {
         for dir in dirs {
-            for provider in discover_providers_in(dir.as_ref()) {
-                registry
-                    .0
+            for provider in discover_providers_in(dir.as_ref(), &self.env) {
+                self.providers
                     .entry(provider.slug().clone())
                     .or_insert(provider);
             }
         }
-
-        registry
     }
 
     /// Add providers found in `dir`, overriding any with a conflicting slug.
     pub fn add_from_dir(&mut self, dir: impl AsRef<Path>) {
-        for provider in discover_providers_in(dir.as_ref()) {
+        let env = self.env.clone();
+
+        for provider in discover_providers_in(dir.as_ref(), &env) {
             self.add(provider);
         }
     }
 }
 
+fn system_path_dirs() -> Vec<PathBuf> {
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    std::env::split_paths(&path_var).collect()
+}
+
 fn has_provider_prefix(path: &Path) -> bool {
     path.file_name()
         .and_then(|name| name.to_str())
@@ -73,14 +101,14 @@ fn has_provider_prefix(path: &Path) -> bool {
 }
 
 // Find all provider binaries in a directory:
-fn discover_providers_in(dir: &Path) -> impl Iterator<Item = Provider> {
+fn discover_providers_in(dir: &Path, env: &ProviderEnv) -> impl Iterator<Item = Provider> {
     let entries = std::fs::read_dir(dir).into_iter().flatten();
 
     entries
         .filter_map(|entry| entry.ok())
         .map(|entry| entry.path())
         .filter(|path| has_provider_prefix(path))
-        .filter_map(|path| Provider::from_binary_path(path).ok())
+        .filter_map(move |path| Provider::from_binary_path_with_env(path, env.clone()).ok())
 }
 
 #[cfg(test)]
@@ -109,7 +137,7 @@ mod tests {
 
         let registry = ProviderRegistry::from_dirs([dir1.path(), dir2.path()]);
 
-        assert!(registry.0.is_empty());
+        assert!(registry.providers.is_empty());
     }
 
     #[cfg(unix)]
@@ -129,7 +157,7 @@ mod tests {
 
         let registry = ProviderRegistry::from_dirs([dir.path()]);
 
-        assert!(registry.0.is_empty());
+        assert!(registry.providers.is_empty());
     }
 
     #[test]
@@ -171,6 +199,20 @@ mod tests {
         assert!(debug.contains(bundled_bin.to_str().unwrap()));
     }
 
+    #[test]
+    fn providers_run_with_registry_env() {
+        let (dir, _) = test_binary("caldir-provider-hooli");
+        let home = PathBuf::from("/tmp/work-caldir");
+
+        let mut registry = ProviderRegistry::new();
+        registry.env = ProviderEnv::new(Some(home.clone()), Default::default());
+        registry.add_from_dir(dir.path());
+
+        let provider = registry.get(&ProviderSlug::from("hooli")).unwrap();
+        let debug = format!("{:?}", provider.transport());
+        assert!(debug.contains(home.to_str().unwrap()));
+    }
+
     #[test]
     fn add_overwrites_existing_provider_with_same_slug() {
         let (dir, bin_path) = test_binary("caldir-provider-hooli");
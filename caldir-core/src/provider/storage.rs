@@ -1,5 +1,6 @@
 //! Provider on-disk storage root.
 //! For providers that need to persist session files, OAuth tokens etc.
+use std::fs::{File, OpenOptions};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, thiserror::Error)]
@@ -31,12 +32,39 @@ impl ProviderStorage {
     pub fn root(&self) -> &Path {
         &self.root
     }
+
+    /// Take an exclusive lock on `{root}/{name}`, blocking while another
+    /// process holds it. Released when the returned guard is dropped.
+    ///
+    /// Used to serialize read-modify-write cycles (e.g. OAuth refreshes)
+    /// between concurrent caldir runs.
+    pub fn lock(&self, name: &str) -> std::io::Result<StorageLock> {
+        let path = self.root.join(name);
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        file.lock()?;
+
+        Ok(StorageLock { _file: file })
+    }
+}
+
+/// Guard for [`ProviderStorage::lock`]. Closing the file releases the lock.
+#[derive(Debug)]
+pub struct StorageLock {
+    _file: File,
 }
 
 fn default_root(provider_name: &str) -> Result<PathBuf, StorageError> {
-    Ok(crate::utils::paths::platform_config_dir()
+    Ok(crate::utils::paths::default_caldir_home()
         .ok_or(StorageError::UnknownStorageDirectory)?
-        .join("caldir")
         .join("providers")
         .join(provider_name))
 }
@@ -51,6 +79,20 @@ mod tests {
         assert_eq!(s.root(), Path::new("/tmp/foo"));
     }
 
+    #[test]
+    fn lock_is_exclusive_until_dropped() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = ProviderStorage::new(tmp.path());
+        let path = tmp.path().join("session").join("me.lock");
+
+        let guard = storage.lock("session/me.lock").unwrap();
+        let other = File::open(&path).unwrap();
+        assert!(other.try_lock().is_err());
+
+        drop(guard);
+        assert!(other.try_lock().is_ok());
+    }
+
     #[test]
     fn default_root_includes_provider_name_under_caldir_providers() {
         let path = default_root("hooli").unwrap();
@@ -0,0 +1,91 @@
+//! Where providers keep OAuth tokens: plaintext files under their
+//! [`ProviderStorage`](super::ProviderStorage) root, or the OS keychain.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenStorage {
+    /// `0600` files under the provider's storage root
+    #[default]
+    File,
+    /// Secret Service, macOS Keychain or Windows Credential Manager
+    Keyring,
+}
+
+impl TokenStorage {
+    /// Set by caldir on every provider subprocess.
+    pub const ENV: &str = "CALDIR_TOKEN_STORAGE";
+
+    /// The backend caldir selected for this provider process.
+    pub fn from_env() -> Self {
+        match std::env::var(Self::ENV).as_deref() {
+            Ok("keyring") => Self::Keyring,
+            _ => Self::File,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Keyring => "keyring",
+        }
+    }
+}
+
+/// One provider account's token in the OS keychain, stored under the service
+/// `caldir-provider-{provider}` and the account identifier as user.
+#[cfg(feature = "keyring")]
+pub struct KeyringToken {
+    entry: keyring::Entry,
+}
+
+#[cfg(feature = "keyring")]
+impl KeyringToken {
+    pub fn new(provider: &str, account: &str) -> keyring::Result<Self> {
+        let service = format!("{}{provider}", super::slug::PROVIDER_BINARY_PREFIX);
+
+        Ok(Self {
+            entry: keyring::Entry::new(&service, account)?,
+        })
+    }
+
+    /// The stored token, or `None` if the keychain has no entry yet.
+    pub fn load(&self) -> keyring::Result<Option<String>> {
+        match self.entry.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, secret: &str) -> keyring::Result<()> {
+        self.entry.set_password(secret)
+    }
+
+    /// Remove the token. Succeeds if there was none.
+    pub fn delete(&self) -> keyring::Result<()> {
+        match self.entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_lowercase_names() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            token_storage: TokenStorage,
+        }
+
+        let parsed: Wrapper = toml::from_str(r#"token_storage = "keyring""#).unwrap();
+
+        assert_eq!(parsed.token_storage, TokenStorage::Keyring);
+        assert_eq!(TokenStorage::Keyring.as_str(), "keyring");
+    }
+}
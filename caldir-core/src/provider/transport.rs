@@ -1,13 +1,20 @@
 mod error;
+#[cfg(any(test, feature = "in-process"))]
+mod in_process_transport;
 mod subprocess_transport;
+mod trace;
 
 #[cfg(test)]
 pub(crate) mod mock_transport;
 pub(crate) use error::ProviderTransportError;
+#[cfg(any(test, feature = "in-process"))]
+pub(crate) use in_process_transport::InProcessTransport;
 pub(crate) use subprocess_transport::SubprocessTransport;
+pub use trace::{TRACE_ENV, TraceTarget, enable_protocol_trace};
 
 use async_trait::async_trait;
 use std::time::Duration;
+use tokio::sync::mpsc;
 
 /// Provider transports take JSON strings in and return JSON strings out
 #[async_trait]
@@ -17,4 +24,15 @@ pub(crate) trait ProviderTransport: std::fmt::Debug + Send + Sync {
         request: &str,
         timeout_dur: Duration,
     ) -> Result<String, ProviderTransportError>;
+
+    /// Like `exchange`, passing each `chunk` line the provider streams before
+    /// its response to `chunks`. Transports that can't stream ignore them.
+    async fn exchange_streaming(
+        &self,
+        request: &str,
+        timeout_dur: Duration,
+        _chunks: mpsc::UnboundedSender<String>,
+    ) -> Result<String, ProviderTransportError> {
+        self.exchange(request, timeout_dur).await
+    }
 }
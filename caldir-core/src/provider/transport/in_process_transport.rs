@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use super::trace::{Direction, trace};
+use super::{ProviderTransport, ProviderTransportError};
+use crate::provider::handler::{Handler, handle_line};
+
+/// Serves requests with a [`Handler`] in this process, for providers
+/// written in Rust that don't ship a `caldir-provider-*` binary.
+pub(crate) struct InProcessTransport<H> {
+    name: String,
+    handler: H,
+}
+
+impl<H: Handler> InProcessTransport<H> {
+    pub(crate) fn new(name: impl Into<String>, handler: H) -> Self {
+        Self {
+            name: name.into(),
+            handler,
+        }
+    }
+
+    async fn handle(
+        &self,
+        request: &str,
+        timeout_dur: Duration,
+        chunks: Option<&mpsc::UnboundedSender<String>>,
+    ) -> Result<String, ProviderTransportError> {
+        trace(&self.name, Direction::Sent, request);
+
+        let response = timeout(timeout_dur, handle_line(&self.handler, request, chunks))
+            .await
+            .map_err(|_| ProviderTransportError::Timeout(timeout_dur))?;
+
+        trace(&self.name, Direction::Received, &response);
+        Ok(response)
+    }
+}
+
+impl<H> std::fmt::Debug for InProcessTransport<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InProcessTransport")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<H: Handler> ProviderTransport for InProcessTransport<H> {
+    async fn exchange(
+        &self,
+        request: &str,
+        timeout_dur: Duration,
+    ) -> Result<String, ProviderTransportError> {
+        self.handle(request, timeout_dur, None).await
+    }
+
+    async fn exchange_streaming(
+        &self,
+        request: &str,
+        timeout_dur: Duration,
+        chunks: mpsc::UnboundedSender<String>,
+    ) -> Result<String, ProviderTransportError> {
+        self.handle(request, timeout_dur, Some(&chunks)).await
+    }
+}
@@ -1,27 +1,102 @@
-use std::path::PathBuf;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_trait::async_trait;
-use tokio::io::AsyncWriteExt;
-use tokio::process::Command;
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::{Notify, mpsc, oneshot, watch};
 use tokio::time::timeout;
 
+use super::trace::{Direction, trace};
 use super::{ProviderTransport, ProviderTransportError};
+use crate::provider::env::ProviderEnv;
+use crate::rpc::Request;
+
+/// How long a provider gets to wind down after being cancelled before it's killed.
+const CANCEL_GRACE: Duration = Duration::from_secs(2);
+
+type Reply = Result<String, ProviderTransportError>;
 
 #[derive(Debug)]
 pub(crate) struct SubprocessTransport {
     bin_path: PathBuf,
+    env: ProviderEnv,
+    /// The running provider, started on first use and shared by every call
+    process: tokio::sync::Mutex<Option<Arc<ProviderProcess>>>,
+    next_id: AtomicU64,
 }
 
 impl SubprocessTransport {
     pub(crate) fn new(bin_path: PathBuf) -> Self {
-        Self { bin_path }
+        Self {
+            bin_path,
+            env: ProviderEnv::default(),
+            process: tokio::sync::Mutex::new(None),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn with_env(mut self, env: ProviderEnv) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Register a request with the running provider, starting one if needed.
+    async fn start_request(
+        &self,
+        id: u64,
+        chunks: Option<mpsc::UnboundedSender<String>>,
+    ) -> Result<(Arc<ProviderProcess>, oneshot::Receiver<Reply>), ProviderTransportError> {
+        let (reply, receiver) = oneshot::channel();
+        let mut waiter = Waiter { reply, chunks };
+
+        let mut process = self.process.lock().await;
+
+        if let Some(running) = process.as_ref() {
+            match running.register(id, waiter) {
+                Ok(()) => return Ok((running.clone(), receiver)),
+                Err(closed) => waiter = closed,
+            }
+        }
+
+        let spawned = Arc::new(ProviderProcess::spawn(&self.bin_path, &self.env)?);
+        spawned
+            .register(id, waiter)
+            .expect("a freshly spawned provider accepts requests");
+        *process = Some(spawned.clone());
+
+        Ok((spawned, receiver))
+    }
+
+    /// Stop waiting for `id`. Shuts the provider down if nothing else is in
+    /// flight, so a hung provider doesn't outlive its deadline.
+    async fn abandon(&self, process: &Arc<ProviderProcess>, id: u64) {
+        let mut current = self.process.lock().await;
+
+        if !process.abandon(id).await {
+            return;
+        }
+
+        if current.as_ref().is_some_and(|p| Arc::ptr_eq(p, process)) {
+            current.take();
+        }
+        drop(current);
+
+        process.shutdown().await;
     }
 }
 
 /// The subprocess transport runs a provider binary as a subprocess.
 /// It then sends JSON strings to it via stdin, and reads JSON strings from its stdout
+///
+/// One subprocess serves every call for as long as the transport lives.
+/// Requests are tagged with an `id` that the provider echoes back, so several
+/// can be in flight at once.
 #[async_trait]
 impl ProviderTransport for SubprocessTransport {
     async fn exchange(
@@ -29,51 +104,315 @@ impl ProviderTransport for SubprocessTransport {
         request: &str,
         timeout_dur: Duration,
     ) -> Result<String, ProviderTransportError> {
+        self.send_request(request, timeout_dur, None).await
+    }
+
+    async fn exchange_streaming(
+        &self,
+        request: &str,
+        timeout_dur: Duration,
+        chunks: mpsc::UnboundedSender<String>,
+    ) -> Result<String, ProviderTransportError> {
+        self.send_request(request, timeout_dur, Some(chunks)).await
+    }
+}
+
+impl SubprocessTransport {
+    async fn send_request(
+        &self,
+        request: &str,
+        timeout_dur: Duration,
+        chunks: Option<mpsc::UnboundedSender<String>>,
+    ) -> Result<String, ProviderTransportError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (process, reply) = self.start_request(id, chunks).await?;
+
         let exchange = async {
-            let mut child = Command::new(&self.bin_path)
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::inherit())
-                .kill_on_drop(true)
-                .spawn()
-                .map_err(ProviderTransportError::Spawn)?;
-
-            let mut stdin = child.stdin.take().expect("stdin was piped above");
-
-            // BrokenPipe means the child exited before reading the request — let
-            // wait_with_output below report its exit code instead of masking it.
-            if let Err(e) = stdin.write_all(format!("{request}\n").as_bytes()).await
-                && e.kind() != std::io::ErrorKind::BrokenPipe
-            {
-                return Err(ProviderTransportError::Io(e));
+            if let Err(e) = process.send(&tag(request, id)).await {
+                process.pending.lock().unwrap().remove(id);
+                return Err(e);
             }
 
-            drop(stdin);
-
-            let output = child
-                .wait_with_output()
+            reply
                 .await
-                .map_err(ProviderTransportError::Io)?;
+                .unwrap_or(Err(ProviderTransportError::EmptyResponse))
+        };
 
-            if !output.status.success() {
-                return Err(ProviderTransportError::NonZeroExit {
-                    code: output.status.code(),
-                });
+        match timeout(timeout_dur, exchange).await {
+            Ok(reply) => reply,
+            Err(_) => {
+                self.abandon(&process, id).await;
+                Err(ProviderTransportError::Timeout(timeout_dur))
             }
+        }
+    }
+}
+
+/// A running provider subprocess and the requests waiting on it.
+#[derive(Debug)]
+struct ProviderProcess {
+    /// Binary name, for the protocol trace
+    name: String,
+    stdin: tokio::sync::Mutex<Option<ChildStdin>>,
+    pending: Arc<Mutex<Pending>>,
+    kill: Arc<Notify>,
+    exited: watch::Receiver<bool>,
+}
 
-            let response =
-                String::from_utf8(output.stdout).map_err(|_| ProviderTransportError::BadUtf8)?;
+/// A request waiting for its response, and for any chunks streamed before it.
+#[derive(Debug)]
+struct Waiter {
+    reply: oneshot::Sender<Reply>,
+    chunks: Option<mpsc::UnboundedSender<String>>,
+}
 
-            if response.is_empty() {
-                return Err(ProviderTransportError::EmptyResponse);
+/// Requests waiting for a response, oldest first.
+#[derive(Debug, Default)]
+struct Pending {
+    waiting: VecDeque<(u64, Waiter)>,
+    /// Whether the provider echoes request ids. Providers that don't answer
+    /// one request at a time, in order.
+    echoes_ids: bool,
+    /// The provider's output has ended; it can't take new requests
+    closed: bool,
+}
+
+impl Pending {
+    /// Position of the waiter for `id`, or of the oldest one for a line without an id.
+    fn find(&self, id: Option<u64>) -> Option<usize> {
+        match id {
+            Some(id) => self.waiting.iter().position(|(waiting, _)| *waiting == id),
+            None => (!self.waiting.is_empty()).then_some(0),
+        }
+    }
+
+    fn take(&mut self, id: Option<u64>) -> Option<Waiter> {
+        let index = self.find(id)?;
+        self.waiting.remove(index).map(|(_, waiter)| waiter)
+    }
+
+    fn remove(&mut self, id: u64) {
+        self.waiting.retain(|(waiting, _)| *waiting != id);
+    }
+}
+
+impl ProviderProcess {
+    fn spawn(bin_path: &Path, env: &ProviderEnv) -> Result<Self, ProviderTransportError> {
+        let mut command = Command::new(bin_path);
+        env.apply(&mut command);
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(ProviderTransportError::Spawn)?;
+
+        let stdin = child.stdin.take().expect("stdin was piped above");
+        let stdout = child.stdout.take().expect("stdout was piped above");
+
+        let name = bin_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let pending = Arc::new(Mutex::new(Pending::default()));
+        let kill = Arc::new(Notify::new());
+        let (exited_tx, exited) = watch::channel(false);
+
+        tokio::spawn(read_responses(
+            name.clone(),
+            child,
+            stdout,
+            pending.clone(),
+            kill.clone(),
+            exited_tx,
+        ));
+
+        Ok(Self {
+            name,
+            stdin: tokio::sync::Mutex::new(Some(stdin)),
+            pending,
+            kill,
+            exited,
+        })
+    }
+
+    /// Wait for a response to `id`. Hands `waiter` back if the provider has
+    /// already exited.
+    fn register(&self, id: u64, waiter: Waiter) -> Result<(), Waiter> {
+        let mut pending = self.pending.lock().unwrap();
+
+        if pending.closed {
+            return Err(waiter);
+        }
+
+        pending.waiting.push_back((id, waiter));
+        Ok(())
+    }
+
+    async fn send(&self, line: &str) -> Result<(), ProviderTransportError> {
+        let mut stdin = self.stdin.lock().await;
+        let Some(pipe) = stdin.as_mut() else {
+            return Ok(());
+        };
+
+        trace(&self.name, Direction::Sent, line);
+
+        match pipe.write_all(format!("{line}\n").as_bytes()).await {
+            // BrokenPipe means the provider exited — its exit status gets
+            // reported to the waiting requests instead.
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                Err(ProviderTransportError::Io(e))
             }
+            _ => Ok(()),
+        }
+    }
 
-            Ok(response)
+    /// Stop waiting for `id`, and ask the provider to drop it too. Returns
+    /// whether nothing else is in flight.
+    async fn abandon(&self, id: u64) -> bool {
+        let (idle, echoes_ids) = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.remove(id);
+            (pending.waiting.is_empty(), pending.echoes_ids)
         };
 
-        timeout(timeout_dur, exchange)
-            .await
-            .map_err(|_| ProviderTransportError::Timeout(timeout_dur))?
+        // Providers that don't know about ids don't know about cancelling one either
+        if !idle && echoes_ids {
+            let _ = self.send(&Request::cancel_json(Some(id))).await;
+        }
+
+        idle
+    }
+
+    /// Cancel everything and close stdin, then kill the provider if it hasn't
+    /// exited after [`CANCEL_GRACE`].
+    async fn shutdown(&self) {
+        let mut exited = self.exited.clone();
+
+        let wind_down = async {
+            if let Some(mut stdin) = self.stdin.lock().await.take() {
+                let line = Request::cancel_json(None);
+                trace(&self.name, Direction::Sent, &line);
+                let _ = stdin.write_all(format!("{line}\n").as_bytes()).await;
+            }
+
+            let _ = exited.wait_for(|exited| *exited).await;
+        };
+
+        if timeout(CANCEL_GRACE, wind_down).await.is_err() {
+            self.kill.notify_one();
+        }
+    }
+}
+
+/// `request` with an `id` the provider will echo back. Left as is if it isn't
+/// a JSON object.
+fn tag(request: &str, id: u64) -> String {
+    match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(request) {
+        Ok(mut fields) => {
+            fields.insert("id".to_string(), id.into());
+            serde_json::Value::Object(fields).to_string()
+        }
+        Err(_) => request.to_string(),
+    }
+}
+
+/// Hand each line the provider writes to the request it answers. Once its
+/// output ends, fail whatever is still waiting with its exit status.
+async fn read_responses(
+    name: String,
+    mut child: Child,
+    stdout: ChildStdout,
+    pending: Arc<Mutex<Pending>>,
+    kill: Arc<Notify>,
+    exited: watch::Sender<bool>,
+) {
+    let mut stdout = BufReader::new(stdout);
+
+    loop {
+        let mut line = Vec::new();
+
+        tokio::select! {
+            read = stdout.read_until(b'\n', &mut line) => match read {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    trace(&name, Direction::Received, &String::from_utf8_lossy(&line));
+                    deliver(&pending, line);
+                }
+            },
+            () = kill.notified() => {
+                let _ = child.start_kill();
+                break;
+            }
+        }
+    }
+
+    let status = child.wait().await;
+
+    let waiting = {
+        let mut pending = pending.lock().unwrap();
+        pending.closed = true;
+        std::mem::take(&mut pending.waiting)
+    };
+
+    for (_, waiter) in waiting {
+        let error = match &status {
+            Ok(status) if !status.success() => ProviderTransportError::NonZeroExit {
+                code: status.code(),
+            },
+            Ok(_) => ProviderTransportError::EmptyResponse,
+            Err(e) => ProviderTransportError::Io(std::io::Error::new(e.kind(), e.to_string())),
+        };
+
+        let _ = waiter.reply.send(Err(error));
+    }
+
+    let _ = exited.send(true);
+}
+
+fn deliver(pending: &Mutex<Pending>, line: Vec<u8>) {
+    #[derive(Deserialize)]
+    struct Tagged {
+        id: Option<u64>,
+        status: Option<String>,
+    }
+
+    let mut pending = pending.lock().unwrap();
+
+    let Ok(line) = String::from_utf8(line) else {
+        if let Some(waiter) = pending.take(None) {
+            let _ = waiter.reply.send(Err(ProviderTransportError::BadUtf8));
+        }
+        return;
+    };
+
+    if line.trim().is_empty() {
+        return;
+    }
+
+    let (id, status) = serde_json::from_str::<Tagged>(&line)
+        .map(|tagged| (tagged.id, tagged.status))
+        .unwrap_or_default();
+
+    if id.is_some() {
+        pending.echoes_ids = true;
+    }
+
+    // Chunks come ahead of the response, so the request keeps waiting
+    if status.as_deref() == Some("chunk") {
+        if let Some(index) = pending.find(id)
+            && let Some(chunks) = &pending.waiting[index].1.chunks
+        {
+            let _ = chunks.send(line);
+        }
+        return;
+    }
+
+    if let Some(waiter) = pending.take(id) {
+        let _ = waiter.reply.send(Ok(line));
     }
 }
 
@@ -101,10 +440,10 @@ mod tests {
     #[tokio::test]
     async fn subprocess_exchange_returns_stdout_of_provider_binary() {
         let tmp = tempfile::TempDir::new().unwrap();
-        // Drain stdin first so we don't race the parent's write to a closed pipe.
+        // Read the request first so we don't race the parent's write to a closed pipe.
         let bin = echo_script(
             &tmp,
-            r#"cat > /dev/null; echo '{"status":"success","data":42}'"#,
+            r#"read request; echo '{"status":"success","data":42}'"#,
         );
         let transport = SubprocessTransport::new(bin);
 
@@ -116,6 +455,34 @@ mod tests {
         assert_eq!(response.trim(), r#"{"status":"success","data":42}"#);
     }
 
+    #[serial_test::serial]
+    #[tokio::test]
+    async fn subprocess_exchange_streaming_forwards_chunks() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let bin = echo_script(
+            &tmp,
+            r#"read request; echo '{"status":"chunk","data":[1]}'; echo '{"status":"chunk","data":[2]}'; echo '{"status":"success","data":[]}'"#,
+        );
+        let transport = SubprocessTransport::new(bin);
+        let (chunks_tx, mut chunks) = mpsc::unbounded_channel();
+
+        let response = transport
+            .exchange_streaming("{}", Duration::from_secs(5), chunks_tx)
+            .await
+            .unwrap();
+
+        assert_eq!(response.trim(), r#"{"status":"success","data":[]}"#);
+        assert_eq!(
+            chunks.recv().await.unwrap().trim(),
+            r#"{"status":"chunk","data":[1]}"#
+        );
+        assert_eq!(
+            chunks.recv().await.unwrap().trim(),
+            r#"{"status":"chunk","data":[2]}"#
+        );
+        assert!(chunks.recv().await.is_none());
+    }
+
     #[serial_test::serial]
     #[tokio::test]
     async fn subprocess_exchange_errors_on_non_zero_exit() {
@@ -138,9 +505,9 @@ mod tests {
     #[tokio::test]
     async fn subprocess_exchange_errors_on_empty_stdout() {
         let tmp = tempfile::TempDir::new().unwrap();
-        // Drain stdin so we don't race the subprocess to a broken-pipe write,
+        // Read the request so we don't race the subprocess to a broken-pipe write,
         // then exit 0 with empty stdout.
-        let bin = echo_script(&tmp, "cat > /dev/null");
+        let bin = echo_script(&tmp, "read request");
         let transport = SubprocessTransport::new(bin);
 
         let err = transport
@@ -165,4 +532,84 @@ mod tests {
 
         assert!(matches!(err, ProviderTransportError::Timeout(_)));
     }
+
+    #[serial_test::serial]
+    #[tokio::test]
+    async fn subprocess_exchange_sends_cancel_after_timeout() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let seen = tmp.path().join("seen");
+        let bin = echo_script(
+            &tmp,
+            &format!(
+                "read request; read cancel; echo \"$cancel\" > {}",
+                seen.display()
+            ),
+        );
+        let transport = SubprocessTransport::new(bin);
+
+        let err = transport
+            .exchange("req", Duration::from_millis(50))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProviderTransportError::Timeout(_)));
+        let cancel = std::fs::read_to_string(seen).unwrap();
+        assert_eq!(cancel.trim(), r#"{"command":"cancel"}"#);
+    }
+
+    #[serial_test::serial]
+    #[tokio::test]
+    async fn subprocess_is_reused_across_exchanges() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let spawns = tmp.path().join("spawns");
+        let bin = echo_script(
+            &tmp,
+            &format!(
+                r#"echo spawned >> {}
+while read request; do echo '{{"status":"success","data":1}}'; done"#,
+                spawns.display()
+            ),
+        );
+        let transport = SubprocessTransport::new(bin);
+
+        for _ in 0..2 {
+            transport
+                .exchange("{}", Duration::from_secs(5))
+                .await
+                .unwrap();
+        }
+
+        let spawned = std::fs::read_to_string(spawns).unwrap();
+        assert_eq!(spawned.lines().count(), 1);
+    }
+
+    #[serial_test::serial]
+    #[tokio::test]
+    async fn subprocess_matches_out_of_order_responses_by_id() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let bin = echo_script(
+            &tmp,
+            r#"read first; read second
+echo '{"id":1,"status":"success","data":"second"}'
+echo '{"id":0,"status":"success","data":"first"}'"#,
+        );
+        let transport = SubprocessTransport::new(bin);
+
+        let (first, second) = tokio::join!(
+            transport.exchange(r#"{"command":"list_events"}"#, Duration::from_secs(5)),
+            transport.exchange(r#"{"command":"list_events"}"#, Duration::from_secs(5)),
+        );
+
+        assert!(first.unwrap().contains("\"first\""));
+        assert!(second.unwrap().contains("\"second\""));
+    }
+
+    #[test]
+    fn tag_adds_id_to_request() {
+        let tagged = tag(r#"{"command":"list_events","params":{}}"#, 3);
+
+        let value: serde_json::Value = serde_json::from_str(&tagged).unwrap();
+        assert_eq!(value["id"], 3);
+        assert_eq!(value["command"], "list_events");
+    }
 }
@@ -1,34 +1,74 @@
 mod config;
 mod error;
 mod event;
+mod retry;
 
-use crate::diff::EventChange;
+use crate::diff::{EventChange, PlannedStep};
 use crate::provider::ProviderError;
-use crate::{DateRange, Event, Provider, rpc};
+use crate::{DateRange, Event, FloatingTimezone, Provider, rpc};
+use chrono::{DateTime, Utc};
 
 pub use config::{RemoteConfig, RemoteConfigParams};
 pub(crate) use error::RemoteError;
 pub use event::RemoteEvent;
+pub use retry::DEFAULT_MAX_ATTEMPTS;
 
 /// provider with config should resolve to a unique remote
 pub struct Remote {
     provider: Provider,
     params: RemoteConfigParams,
+    notify_attendees: rpc::Notify,
+    max_attempts: u32,
+    floating_timezone: FloatingTimezone,
 }
 
 impl Remote {
     pub fn new(provider: Provider, params: RemoteConfigParams) -> Self {
-        Self { provider, params }
+        Self {
+            provider,
+            params,
+            notify_attendees: rpc::Notify::None,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            floating_timezone: FloatingTimezone::default(),
+        }
+    }
+
+    /// What the provider supports
+    pub async fn capabilities(&self) -> rpc::Capabilities {
+        self.provider.describe().await.capabilities
+    }
+
+    /// How many times to try a call that keeps failing transiently
+    pub fn set_max_attempts(&mut self, max_attempts: u32) {
+        self.max_attempts = max_attempts.max(1);
+    }
+
+    /// Where floating times are pinned for a provider that can't store them
+    pub fn floating_timezone(&self) -> FloatingTimezone {
+        self.floating_timezone
+    }
+
+    pub fn set_floating_timezone(&mut self, floating_timezone: FloatingTimezone) {
+        self.floating_timezone = floating_timezone;
+    }
+
+    /// Which attendees pushed changes should send invitations/updates to
+    pub fn notify_attendees(&self) -> rpc::Notify {
+        self.notify_attendees
+    }
+
+    pub fn set_notify_attendees(&mut self, notify: rpc::Notify) {
+        self.notify_attendees = notify;
     }
 
     pub async fn list_events(&self, range: &DateRange) -> Result<Vec<RemoteEvent>, RemoteError> {
         let (from, to) = range.to_rfc3339();
         let events = self
-            .provider
             .call(rpc::ListEvents {
                 remote: self.params.clone(),
                 from,
                 to,
+                stream: false,
             })
             .await?
             .into_iter()
@@ -38,18 +78,184 @@ impl Remote {
         Ok(events)
     }
 
+    /// Busy times of `emails` between `from` and `to`, one schedule each.
+    pub async fn free_busy(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        emails: Vec<String>,
+    ) -> Result<Vec<rpc::BusySchedule>, RemoteError> {
+        self.call(rpc::FreeBusy {
+            remote: self.params.clone(),
+            from: from.to_rfc3339(),
+            to: to.to_rfc3339(),
+            emails,
+        })
+        .await
+    }
+
+    /// Names the account's address book has for `emails`.
+    pub async fn lookup_contacts(
+        &self,
+        emails: Vec<String>,
+    ) -> Result<Vec<rpc::ContactCard>, RemoteError> {
+        self.call(rpc::LookupContacts {
+            remote: self.params.clone(),
+            emails,
+        })
+        .await
+    }
+
+    /// Like `list_events`, asking the provider to stream events as it fetches
+    /// them. `on_progress` gets the number of events received so far.
+    pub async fn list_events_with_progress(
+        &self,
+        range: &DateRange,
+        on_progress: &(dyn Fn(usize) + Sync),
+    ) -> Result<Vec<RemoteEvent>, RemoteError> {
+        let (from, to) = range.to_rfc3339();
+        let cmd = rpc::ListEvents {
+            remote: self.params.clone(),
+            from,
+            to,
+            stream: true,
+        };
+
+        let events = self
+            .with_retries(true, || {
+                self.provider.call_streaming(cmd.clone(), on_progress)
+            })
+            .await?
+            .into_iter()
+            .map(RemoteEvent::new)
+            .collect();
+
+        Ok(events)
+    }
+
+    /// Push `changes`, returning one result per change in the same order.
+    ///
+    /// Two or more creates/updates go to the provider as a single
+    /// `batch_write`; providers that predate it get one call per change.
+    pub async fn apply_changes(
+        &self,
+        changes: &[&EventChange],
+    ) -> Vec<Result<Option<RemoteEvent>, RemoteError>> {
+        let capabilities = self.capabilities().await;
+        let writes: Vec<(usize, rpc::EventWrite)> = changes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, change)| match change {
+                EventChange::Create(event) => Some((
+                    i,
+                    rpc::EventWrite::Create(self.storable(event.clone(), &capabilities)),
+                )),
+                EventChange::Update { from, to } => Some((
+                    i,
+                    rpc::EventWrite::Update(self.storable(
+                        to.clone().with_x_properties_merged_from(from),
+                        &capabilities,
+                    )),
+                )),
+                EventChange::Delete(_) => None,
+            })
+            .collect();
+
+        let mut results: Vec<Option<Result<Option<RemoteEvent>, RemoteError>>> =
+            changes.iter().map(|_| None).collect();
+
+        if writes.len() > 1 {
+            let (indices, writes): (Vec<usize>, Vec<rpc::EventWrite>) = writes.into_iter().unzip();
+
+            match self.batch_write(writes).await {
+                Ok(batch_results) => {
+                    for (i, result) in indices.into_iter().zip(batch_results) {
+                        results[i] = Some(result.map(Some));
+                    }
+                }
+                Err(RemoteError::Provider(ProviderError::Provider(msg)))
+                    if is_unknown_method(&msg, rpc::Method::BatchWrite) => {}
+                Err(e) => {
+                    // The whole call failed: report it once, on the first write.
+                    results[indices[0]] = Some(Err(e));
+                    for &i in &indices[1..] {
+                        results[i] = Some(Err(RemoteError::BatchAborted));
+                    }
+                }
+            }
+        }
+
+        let mut out = Vec::with_capacity(changes.len());
+        for (change, result) in changes.iter().zip(results) {
+            out.push(match result {
+                Some(result) => result,
+                None => self.apply_change(change).await,
+            });
+        }
+        out
+    }
+
+    /// The provider calls `apply_changes` would make for `changes`, assuming
+    /// the provider supports `batch_write`.
+    pub fn planned_calls(changes: &[&EventChange]) -> Vec<PlannedStep> {
+        let summary = |event: &Event| event.summary.clone().unwrap_or_default();
+
+        let writes: Vec<(rpc::Method, String)> = changes
+            .iter()
+            .filter_map(|change| match change {
+                EventChange::Create(event) => Some((rpc::Method::CreateEvent, summary(event))),
+                EventChange::Update { to, .. } => Some((rpc::Method::UpdateEvent, summary(to))),
+                EventChange::Delete(_) => None,
+            })
+            .collect();
+
+        let mut calls = Vec::new();
+
+        if writes.len() > 1 {
+            calls.push(PlannedStep::Call {
+                method: rpc::Method::BatchWrite,
+                events: writes.into_iter().map(|(_, summary)| summary).collect(),
+            });
+        } else {
+            calls.extend(
+                writes
+                    .into_iter()
+                    .map(|(method, summary)| PlannedStep::Call {
+                        method,
+                        events: vec![summary],
+                    }),
+            );
+        }
+
+        for change in changes {
+            if let EventChange::Delete(event) = change {
+                calls.push(PlannedStep::Call {
+                    method: rpc::Method::DeleteEvent,
+                    events: vec![summary(event)],
+                });
+            }
+        }
+
+        calls
+    }
+
     pub async fn apply_change(
         &self,
         change: &EventChange,
     ) -> Result<Option<RemoteEvent>, RemoteError> {
+        let capabilities = self.capabilities().await;
+
         match change {
             EventChange::Create(event) => {
-                let remote_event = self.create_event(event.clone()).await?;
+                let event = self.storable(event.clone(), &capabilities);
+                let remote_event = self.create_event(event).await?;
                 Ok(Some(remote_event))
             }
             EventChange::Update { from, to } => {
                 let merged = to.clone().with_x_properties_merged_from(from);
-                let remote_event = self.update_event(merged).await?;
+                let remote_event = self
+                    .update_event(self.storable(merged, &capabilities))
+                    .await?;
                 Ok(Some(remote_event))
             }
             EventChange::Delete(event) => {
@@ -61,38 +267,130 @@ impl Remote {
 
     async fn create_event(&self, event: Event) -> Result<RemoteEvent, RemoteError> {
         let event = self
-            .provider
             .call(rpc::CreateEvent {
                 remote: self.params.clone(),
                 event,
+                notify_attendees: self.notify_attendees,
             })
             .await?;
 
         Ok(RemoteEvent::new(event))
     }
 
-    async fn delete_event(&self, event: Event) -> Result<(), RemoteError> {
-        self.provider
-            .call(rpc::DeleteEvent {
+    /// Call the provider, retrying transient failures (rate limits, 5xx)
+    /// with jittered exponential backoff or the provider's `retry_after`.
+    /// Non-idempotent calls are only retried on rate limits, since a 5xx can
+    /// arrive after the provider already made the event.
+    async fn call<C: rpc::Rpc + Clone>(&self, cmd: C) -> Result<C::Response, RemoteError> {
+        self.with_retries(C::IDEMPOTENT, || self.provider.call(cmd.clone()))
+            .await
+    }
+
+    async fn with_retries<T, F, Fut>(
+        &self,
+        idempotent: bool,
+        mut attempt_call: F,
+    ) -> Result<T, RemoteError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ProviderError>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match attempt_call().await {
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    let delay = retry::retry_delay(&error, attempt, idempotent)
+                        .filter(|_| attempt < self.max_attempts);
+
+                    let Some(delay) = delay else {
+                        return Err(error.into());
+                    };
+
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    async fn batch_write(
+        &self,
+        writes: Vec<rpc::EventWrite>,
+    ) -> Result<Vec<Result<RemoteEvent, RemoteError>>, RemoteError> {
+        let count = writes.len();
+        let results = self
+            .call(rpc::BatchWrite {
                 remote: self.params.clone(),
-                event,
+                writes,
+                notify_attendees: self.notify_attendees,
             })
             .await?;
 
+        if results.len() != count {
+            return Err(RemoteError::BatchLength {
+                expected: count,
+                actual: results.len(),
+            });
+        }
+
+        Ok(results
+            .into_iter()
+            .map(|result| match result {
+                rpc::Response::Success { data } => Ok(RemoteEvent::new(data)),
+                rpc::Response::Error {
+                    error,
+                    transient,
+                    retry_after,
+                    code,
+                } => Err(RemoteError::Provider(ProviderError::from_response(
+                    error,
+                    transient,
+                    retry_after,
+                    code,
+                ))),
+            })
+            .collect())
+    }
+
+    async fn delete_event(&self, event: Event) -> Result<(), RemoteError> {
+        self.call(rpc::DeleteEvent {
+            remote: self.params.clone(),
+            event,
+            notify_attendees: self.notify_attendees,
+        })
+        .await?;
+
         Ok(())
     }
 
     async fn update_event(&self, event: Event) -> Result<RemoteEvent, RemoteError> {
         let event = self
-            .provider
             .call(rpc::UpdateEvent {
                 remote: self.params.clone(),
                 event,
+                notify_attendees: self.notify_attendees,
             })
             .await?;
 
         Ok(RemoteEvent::new(event))
     }
+
+    /// `event` as a provider with `capabilities` can store it.
+    fn storable(&self, event: Event, capabilities: &rpc::Capabilities) -> Event {
+        if capabilities.floating_times {
+            event
+        } else {
+            event.with_floating_times_pinned(self.floating_timezone)
+        }
+    }
+}
+
+/// Whether a provider's error is serde rejecting `method` as an unknown
+/// command, i.e. the provider was built before `method` existed.
+fn is_unknown_method(msg: &str, method: rpc::Method) -> bool {
+    msg.contains(&format!("unknown variant `{method}`"))
 }
 
 #[cfg(test)]
@@ -101,6 +399,32 @@ mod tests {
     use crate::test_utils::{test_event, test_remote};
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn planned_calls_batch_writes_and_send_deletes_one_by_one() {
+        let create = EventChange::Create(Event::new("Lunch", test_event().start));
+        let update = EventChange::Update {
+            from: test_event(),
+            to: Event::new("Dinner", test_event().start),
+        };
+        let delete = EventChange::Delete(Event::new("Breakfast", test_event().start));
+
+        let calls = Remote::planned_calls(&[&delete, &create, &update]);
+
+        assert_eq!(
+            calls,
+            vec![
+                PlannedStep::Call {
+                    method: rpc::Method::BatchWrite,
+                    events: vec!["Lunch".to_string(), "Dinner".to_string()],
+                },
+                PlannedStep::Call {
+                    method: rpc::Method::DeleteEvent,
+                    events: vec!["Breakfast".to_string()],
+                },
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn apply_change_sends_create_event_for_outgoing_create() {
         let (mock, remote) = test_remote();
@@ -170,6 +494,75 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn apply_change_does_not_notify_attendees_by_default() {
+        let (mock, remote) = test_remote();
+        let event = test_event();
+        mock.reply::<rpc::CreateEvent>(event.clone());
+
+        remote
+            .apply_change(&EventChange::Create(event))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock.captured_request::<rpc::CreateEvent>().notify_attendees,
+            rpc::Notify::None
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_change_forwards_notify_attendees() {
+        let (mock, mut remote) = test_remote();
+        remote.set_notify_attendees(rpc::Notify::External);
+        let event = test_event();
+        mock.reply::<rpc::CreateEvent>(event.clone());
+
+        remote
+            .apply_change(&EventChange::Create(event))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock.captured_request::<rpc::CreateEvent>().notify_attendees,
+            rpc::Notify::External
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_transient_failures() {
+        let (mock, remote) = test_remote();
+        mock.reply_transient("503 Service Unavailable");
+        mock.reply_transient("503 Service Unavailable");
+        mock.reply::<rpc::ListEvents>(vec![test_event()]);
+
+        let events = remote.list_events(&DateRange::default()).await.unwrap();
+
+        assert_eq!(events.len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_after_max_attempts() {
+        let (mock, mut remote) = test_remote();
+        remote.set_max_attempts(2);
+        mock.reply_transient("503 Service Unavailable");
+        mock.reply_transient("503 Service Unavailable");
+
+        let result = remote.list_events(&DateRange::default()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_permanent_failures() {
+        let (mock, remote) = test_remote();
+        mock.reply_failure("Event not found");
+
+        let result = remote.list_events(&DateRange::default()).await;
+
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn apply_change_sends_delete_event_for_outgoing_delete() {
         let (mock, remote) = test_remote();
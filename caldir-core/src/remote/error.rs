@@ -1,7 +1,28 @@
 use super::ProviderError;
+use crate::rpc::ErrorCode;
 
 #[derive(Debug, thiserror::Error)]
 pub enum RemoteError {
     #[error("Provider error: {0}")]
     Provider(#[from] ProviderError),
+
+    #[error("Not pushed: an earlier error aborted the batch")]
+    BatchAborted,
+
+    #[error("Provider answered {actual} results for a batch of {expected}")]
+    BatchLength { expected: usize, actual: usize },
+}
+
+impl RemoteError {
+    /// The provider's error code, if it sent one.
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Self::Provider(e) => e.code(),
+            _ => None,
+        }
+    }
+
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, Self::Provider(e) if e.is_unreachable())
+    }
 }
@@ -16,4 +16,8 @@ impl RemoteEvent {
     pub fn event(&self) -> &Event {
         &self.0
     }
+
+    pub(crate) fn event_mut(&mut self) -> &mut Event {
+        &mut self.0
+    }
 }
@@ -0,0 +1,120 @@
+//! Backoff for transient provider failures.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::provider::ProviderError;
+
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 4;
+
+const BASE_DELAY: Duration = Duration::from_millis(500);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+/// Longest `Retry-After` we're willing to sit through.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+/// How long to wait before retrying `error`, or `None` if it isn't worth retrying.
+/// `attempt` is the number of attempts made so far (1 after the first failure).
+/// Calls that aren't `idempotent` are only retried on rate limits, which the
+/// provider rejects before acting on them.
+pub(crate) fn retry_delay(
+    error: &ProviderError,
+    attempt: u32,
+    idempotent: bool,
+) -> Option<Duration> {
+    let retry_after = match error {
+        ProviderError::RateLimited { retry_after, .. } => retry_after,
+        ProviderError::Transient { retry_after, .. } if idempotent => retry_after,
+        _ => return None,
+    };
+
+    match retry_after {
+        Some(wait) if *wait > MAX_RETRY_AFTER => None,
+        Some(wait) => Some(*wait),
+        None => Some(backoff(attempt)),
+    }
+}
+
+/// Exponential backoff with jitter: somewhere in [d/2, d] for d = 500ms * 2^(attempt-1).
+fn backoff(attempt: u32) -> Duration {
+    let exp = BASE_DELAY.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+    let delay = exp.min(MAX_DELAY);
+
+    delay.mul_f64(0.5 + jitter() / 2.0)
+}
+
+/// Pseudo-random in [0, 1). Good enough to spread out concurrent retries.
+fn jitter() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+
+    f64::from(nanos % 1_000) / 1_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transient(retry_after: Option<Duration>) -> ProviderError {
+        ProviderError::Transient {
+            message: "rate limited".to_string(),
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn permanent_errors_are_not_retried() {
+        let error = ProviderError::Provider("Event not found".to_string());
+
+        assert_eq!(retry_delay(&error, 1, true), None);
+    }
+
+    #[test]
+    fn backoff_grows_and_stays_capped() {
+        let first = retry_delay(&transient(None), 1, true).unwrap();
+        let third = retry_delay(&transient(None), 3, true).unwrap();
+        let late = retry_delay(&transient(None), 30, true).unwrap();
+
+        assert!(first >= Duration::from_millis(250) && first <= BASE_DELAY);
+        assert!(third >= Duration::from_secs(1) && third <= Duration::from_secs(2));
+        assert!(late <= MAX_DELAY);
+    }
+
+    #[test]
+    fn honours_retry_after() {
+        let wait = Duration::from_secs(10);
+
+        assert_eq!(retry_delay(&transient(Some(wait)), 1, true), Some(wait));
+    }
+
+    #[test]
+    fn rate_limits_are_retried() {
+        let error = ProviderError::RateLimited {
+            message: "quota exceeded".to_string(),
+            retry_after: Some(Duration::from_secs(5)),
+        };
+
+        assert_eq!(retry_delay(&error, 1, true), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn non_idempotent_calls_only_retry_rate_limits() {
+        let rate_limited = ProviderError::RateLimited {
+            message: "quota exceeded".to_string(),
+            retry_after: Some(Duration::from_secs(5)),
+        };
+
+        assert_eq!(retry_delay(&transient(None), 1, false), None);
+        assert_eq!(
+            retry_delay(&rate_limited, 1, false),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn gives_up_on_very_long_retry_after() {
+        let error = transient(Some(Duration::from_secs(3600)));
+
+        assert_eq!(retry_delay(&error, 1, true), None);
+    }
+}
@@ -1,23 +1,38 @@
+mod batch_write;
+mod check_account;
 mod connect;
 mod create_event;
 mod delete_event;
+mod describe;
+mod disconnect;
+mod free_busy;
 mod list_calendars;
 mod list_events;
+mod lookup_contacts;
+mod notify;
 mod update_event;
 
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::time::Duration;
 
 // actions:
+pub use batch_write::{BatchWrite, EventWrite};
+pub use check_account::{AccountStatus, CheckAccount};
 pub use connect::{
     Connect, ConnectResponse, ConnectStepKind, CredentialField, CredentialsData, FieldType,
     HostedOAuthData, OAuthData, SetupData,
 };
 pub use create_event::CreateEvent;
 pub use delete_event::DeleteEvent;
+pub use describe::{AuthStyle, Capabilities, Describe, PROTOCOL_VERSION, ProviderDescription};
+pub use disconnect::Disconnect;
+pub use free_busy::{BusySchedule, FreeBusy, TimePeriod};
 pub use list_calendars::ListCalendars;
 pub use list_events::ListEvents;
+pub use lookup_contacts::{ContactCard, LookupContacts};
+pub use notify::Notify;
 pub use update_event::UpdateEvent;
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
@@ -27,6 +42,9 @@ pub(crate) trait Rpc: Serialize {
     type Response: Serialize + DeserializeOwned;
     const METHOD: Method;
     const TIMEOUT: Duration = DEFAULT_TIMEOUT;
+    /// Whether sending the call twice does no harm. Calls that aren't are
+    /// only retried when the provider says it didn't act on them.
+    const IDEMPOTENT: bool = true;
 
     fn to_json(&self) -> Result<serde_json::Value, serde_json::Error>
     where
@@ -45,14 +63,35 @@ pub enum Method {
     CreateEvent,
     UpdateEvent,
     DeleteEvent,
+    BatchWrite,
+    Describe,
+    FreeBusy,
+    LookupContacts,
+    CheckAccount,
+    Disconnect,
+    /// Sent when the CLI stops waiting for a request; see [`Request::cancel_json`]
+    Cancel,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::String(name)) => f.write_str(&name),
+            _ => write!(f, "{self:?}"),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Request {
     #[serde(rename = "command")]
     pub method: Method,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub params: serde_json::Value,
+    /// Set by the CLI so responses to concurrent requests can be told apart.
+    /// Providers echo it back as the response's `id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
 }
 
 impl Request {
@@ -60,15 +99,72 @@ impl Request {
         Ok(Self {
             method: C::METHOD,
             params: serde_json::to_value(cmd)?,
+            id: None,
+        })
+    }
+
+    /// The line sent to a provider that missed a deadline. With an `id`,
+    /// providers drop that request; without, they drop everything and exit.
+    pub fn cancel_json(id: Option<u64>) -> String {
+        serde_json::to_string(&Request {
+            method: Method::Cancel,
+            params: serde_json::Value::Null,
+            id,
         })
+        .expect("Request serialization is infallible")
+    }
+
+    pub fn is_cancel(&self) -> bool {
+        self.method == Method::Cancel
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "status", rename_all = "snake_case")]
 pub enum Response<T> {
-    Success { data: T },
-    Error { error: String },
+    Success {
+        data: T,
+    },
+    Error {
+        error: String,
+        /// Worth retrying: a rate limit or a hiccup on the server's side
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        transient: bool,
+        /// Seconds the server asked to wait before retrying
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry_after: Option<u64>,
+        /// What went wrong, for failures the CLI handles specially
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        code: Option<ErrorCode>,
+    },
+}
+
+/// Machine-readable reason for an error response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The stored credentials were rejected; the user needs to `caldir connect` again
+    AuthExpired,
+    RateLimited,
+    /// The calendar or event doesn't exist (anymore) on the remote
+    NotFound,
+    /// The remote changed since it was last read
+    Conflict,
+    /// Sent by a newer provider
+    #[serde(other)]
+    Unknown,
+}
+
+impl<T> Response<T> {
+    /// A non-transient error.
+    pub fn failure(error: impl Into<String>) -> Self {
+        Response::Error {
+            error: error.into(),
+            transient: false,
+            retry_after: None,
+            code: None,
+        }
+    }
 }
 
 impl<T: Serialize> Response<T> {
@@ -82,9 +178,7 @@ impl<T: Serialize> Response<T> {
 impl Response<()> {
     /// Serialize an error response to a JSON string for stdout.
     pub fn error(msg: &str) -> String {
-        serde_json::to_string(&Response::<()>::Error {
-            error: msg.to_string(),
-        })
-        .expect("Response::Error serialization is infallible")
+        serde_json::to_string(&Response::<()>::failure(msg))
+            .expect("Response::Error serialization is infallible")
     }
 }
@@ -0,0 +1,66 @@
+use super::{Method, Notify, Response, Rpc};
+use crate::{Event, RemoteConfigParams};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// One create or update in a [`BatchWrite`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "action", content = "event", rename_all = "snake_case")]
+pub enum EventWrite {
+    Create(Event),
+    Update(Event),
+}
+
+/// Several creates/updates in one call, so providers with a batch API can
+/// push many events in a single round trip.
+///
+/// Each write gets its own result (in order), so one rejected event doesn't
+/// fail the rest.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchWrite {
+    #[serde(flatten)]
+    pub remote: RemoteConfigParams,
+    pub writes: Vec<EventWrite>,
+    /// Which attendees the provider should email about these changes
+    #[serde(default)]
+    pub notify_attendees: Notify,
+}
+
+impl Rpc for BatchWrite {
+    const METHOD: Method = Method::BatchWrite;
+    const TIMEOUT: Duration = Duration::from_secs(60);
+    const IDEMPOTENT: bool = false;
+    type Response = Vec<Response<Event>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventTime;
+
+    #[test]
+    fn batch_write_serializes_json() {
+        let event = Event::new(
+            "Test",
+            EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+        );
+
+        let cmd = BatchWrite {
+            remote: RemoteConfigParams::new(),
+            writes: vec![EventWrite::Create(event.clone()), EventWrite::Update(event)],
+            notify_attendees: Notify::None,
+        };
+
+        let json = cmd.to_json().unwrap();
+
+        assert_eq!(json["command"], "batch_write");
+        assert_eq!(json["params"]["writes"][0]["action"], "create");
+        assert_eq!(json["params"]["writes"][1]["action"], "update");
+        assert!(
+            json["params"]["writes"][0]["event"]
+                .as_str()
+                .unwrap()
+                .starts_with("BEGIN:VCALENDAR")
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use super::{Method, Rpc};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What the provider has stored for an account, for `caldir accounts`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckAccount {
+    pub account_identifier: String,
+}
+
+impl Rpc for CheckAccount {
+    const METHOD: Method = Method::CheckAccount;
+    type Response = AccountStatus;
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AccountStatus {
+    /// Credentials are stored for the account
+    pub signed_in: bool,
+    /// When the stored access token expires, for providers whose tokens do
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// An expired access token is renewed without signing in again
+    #[serde(default)]
+    pub refreshable: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_account_serializes_json() {
+        let cmd = CheckAccount {
+            account_identifier: "user@hmail.com".to_string(),
+        };
+
+        let json = cmd.to_json().unwrap();
+
+        assert_eq!(json["command"], "check_account");
+        assert_eq!(json["params"]["account_identifier"], "user@hmail.com");
+    }
+
+    #[test]
+    fn status_fields_default_when_missing() {
+        let status: AccountStatus = serde_json::from_str(r#"{"signed_in":true}"#).unwrap();
+
+        assert!(status.signed_in);
+        assert_eq!(status.expires_at, None);
+        assert!(!status.refreshable);
+    }
+}
@@ -1,16 +1,20 @@
-use super::{Method, Rpc};
+use super::{Method, Notify, Rpc};
 use crate::{Event, RemoteConfigParams};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CreateEvent {
     #[serde(flatten)]
     pub remote: RemoteConfigParams,
     pub event: Event,
+    /// Which attendees the provider should email about this change
+    #[serde(default)]
+    pub notify_attendees: Notify,
 }
 
 impl Rpc for CreateEvent {
     const METHOD: Method = Method::CreateEvent;
+    const IDEMPOTENT: bool = false;
     type Response = Event;
 }
 
@@ -35,6 +39,7 @@ mod tests {
         let cmd = CreateEvent {
             remote: params,
             event: event.clone(),
+            notify_attendees: Notify::None,
         };
 
         let json = cmd.to_json().unwrap();
@@ -1,12 +1,15 @@
-use super::{Method, Rpc};
+use super::{Method, Notify, Rpc};
 use crate::{Event, RemoteConfigParams};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DeleteEvent {
     #[serde(flatten)]
     pub remote: RemoteConfigParams,
     pub event: Event,
+    /// Which attendees the provider should email about this change
+    #[serde(default)]
+    pub notify_attendees: Notify,
 }
 
 impl Rpc for DeleteEvent {
@@ -38,6 +41,7 @@ mod tests {
         let cmd = DeleteEvent {
             remote: params,
             event,
+            notify_attendees: Notify::None,
         };
 
         let json = cmd.to_json().unwrap();
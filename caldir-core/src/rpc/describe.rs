@@ -0,0 +1,302 @@
+use super::{Method, Rpc};
+use crate::{Event, EventTime, FloatingTimezone};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Version of the provider protocol spoken by this build of caldir.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Ask a provider what it supports, so caldir can skip what it can't do
+/// instead of failing mid-sync.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Describe {}
+
+impl Rpc for Describe {
+    const METHOD: Method = Method::Describe;
+    const TIMEOUT: Duration = Duration::from_secs(5);
+    type Response = ProviderDescription;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderDescription {
+    pub protocol_version: u32,
+    /// The provider's own version, for `caldir providers`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub auth: AuthStyle,
+    #[serde(default)]
+    pub capabilities: Capabilities,
+}
+
+impl ProviderDescription {
+    pub fn new(auth: AuthStyle, capabilities: Capabilities) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            version: None,
+            auth,
+            capabilities,
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Assumed for providers that predate `describe`.
+    pub fn legacy() -> Self {
+        Self {
+            protocol_version: 0,
+            version: None,
+            auth: AuthStyle::Unknown,
+            capabilities: Capabilities::default(),
+        }
+    }
+}
+
+/// How a provider signs in.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthStyle {
+    #[serde(rename = "oauth")]
+    OAuth,
+    /// Username/password or app-specific password
+    Credentials,
+    /// Public feeds
+    None,
+    /// Data on this machine (e.g. macOS Calendar)
+    Local,
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// Operations and event fields a provider supports.
+///
+/// Missing fields take the default, which is what caldir assumed before
+/// providers could describe themselves.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Capabilities {
+    pub create: bool,
+    pub update: bool,
+    pub delete: bool,
+    /// Can list only what changed since the last sync
+    pub incremental_sync: bool,
+    pub attendees: bool,
+    pub attachments: bool,
+    /// Stores a link per event (URL). Without it, the local one is kept.
+    pub urls: bool,
+    pub reminders: bool,
+    /// Has calendar-wide default reminders that events can use
+    pub default_reminders: bool,
+    pub free_busy: bool,
+    /// Can look up people's names in the account's address book
+    pub contacts: bool,
+    /// Emails attendees about changes itself. Without it, caldir can send
+    /// iMIP invitations over SMTP instead. Assumed for providers that don't
+    /// say: caldir never emailed attendees for them before, and a second
+    /// copy of every invitation would be worse than none from caldir.
+    pub invitations: bool,
+    /// Stores times without a timezone. Without it, caldir pins them to
+    /// `floating_timezone` before pushing.
+    pub floating_times: bool,
+    /// Keeps each event's own timezone. Without it, caldir keeps the local
+    /// TZID when the provider returns the same time in another zone.
+    pub time_zones: bool,
+    /// Can cancel one occurrence of a recurring event. Without it, caldir
+    /// adds an EXDATE to the series instead.
+    pub cancel_occurrences: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            create: true,
+            update: true,
+            delete: true,
+            incremental_sync: false,
+            attendees: true,
+            attachments: true,
+            urls: true,
+            reminders: true,
+            default_reminders: false,
+            free_busy: false,
+            contacts: false,
+            invitations: true,
+            floating_times: true,
+            time_zones: true,
+            cancel_occurrences: false,
+        }
+    }
+}
+
+impl Capabilities {
+    /// A provider that can't write anything (e.g. a subscribed feed).
+    pub fn read_only() -> Self {
+        Self {
+            create: false,
+            update: false,
+            delete: false,
+            ..Self::default()
+        }
+    }
+
+    /// Copy the fields this provider can't store from `local` onto `remote`,
+    /// so they stay local instead of showing up as changes on every sync.
+    /// Floating times are compared as happening in `floating`.
+    pub fn keep_unsupported_fields(
+        &self,
+        local: &Event,
+        remote: &mut Event,
+        floating: FloatingTimezone,
+    ) {
+        if !self.attendees {
+            remote.attendees = local.attendees.clone();
+        }
+        if !self.attachments {
+            remote.attachments = local.attachments.clone();
+        }
+        if !self.urls {
+            remote.url = local.url.clone();
+        }
+        if !self.reminders {
+            remote.reminders = local.reminders.clone();
+        }
+        if !self.reminders || !self.default_reminders {
+            remote.reminders_default = local.reminders_default;
+        }
+        self.keep_time(&local.start, &mut remote.start, floating);
+        if let (Some(local_end), Some(remote_end)) = (&local.end, &mut remote.end) {
+            self.keep_time(local_end, remote_end, floating);
+        }
+        if let (Some(local_rec), Some(remote_rec)) = (&local.recurrence, &mut remote.recurrence)
+            && local_rec.exdates.len() == remote_rec.exdates.len()
+        {
+            for (local_exdate, remote_exdate) in
+                local_rec.exdates.iter().zip(&mut remote_rec.exdates)
+            {
+                self.keep_time(local_exdate, remote_exdate, floating);
+            }
+        }
+    }
+
+    /// Put `local` back in place of `remote` if it's the same time, which the
+    /// provider only stored pinned (floating) or in another zone.
+    fn keep_time(&self, local: &EventTime, remote: &mut EventTime, floating: FloatingTimezone) {
+        let unsupported = match local {
+            EventTime::DateTimeFloating(_) => !self.floating_times,
+            EventTime::DateTimeZoned { .. } => !self.time_zones,
+            _ => false,
+        };
+
+        if unsupported && local.to_utc_with(floating) == remote.to_utc_with(floating) {
+            *remote = local.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_serializes_json() {
+        let json = Describe {}.to_json().unwrap();
+
+        assert_eq!(json["command"], "describe");
+    }
+
+    #[test]
+    fn missing_capabilities_fall_back_to_defaults() {
+        let description: ProviderDescription = serde_json::from_str(
+            r#"{"protocol_version":1,"auth":"oauth","capabilities":{"reminders":false}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(description.auth, AuthStyle::OAuth);
+        assert!(!description.capabilities.reminders);
+        assert!(description.capabilities.delete);
+    }
+
+    #[test]
+    fn urls_stay_local_when_the_provider_has_none() {
+        let capabilities = Capabilities {
+            urls: false,
+            ..Capabilities::default()
+        };
+        let mut local = Event::new(
+            "Standup",
+            EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()),
+        );
+        local.url = Some("https://meet.google.com/abc-defg-hij".to_string());
+
+        let mut remote = local.clone();
+        remote.url = None;
+        capabilities.keep_unsupported_fields(&local, &mut remote, FloatingTimezone::Local);
+
+        assert_eq!(remote.url, local.url);
+    }
+
+    #[test]
+    fn floating_times_stay_floating_when_the_provider_pins_them() {
+        let capabilities = Capabilities {
+            floating_times: false,
+            ..Capabilities::default()
+        };
+        let datetime = chrono::NaiveDate::from_ymd_opt(2026, 1, 15)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let local = Event::new("Standup", EventTime::DateTimeFloating(datetime));
+        let floating = FloatingTimezone::Zone(chrono_tz::Europe::Stockholm);
+
+        let mut pinned = local.clone().with_floating_times_pinned(floating);
+        capabilities.keep_unsupported_fields(&local, &mut pinned, floating);
+        assert_eq!(pinned.start, local.start);
+
+        let mut moved = local.clone();
+        moved.start =
+            EventTime::DateTimeUtc(local.start.to_utc_with(floating) + chrono::Duration::hours(1));
+        capabilities.keep_unsupported_fields(&local, &mut moved, floating);
+        assert_ne!(moved.start, local.start);
+    }
+
+    #[test]
+    fn time_zones_stay_when_the_provider_returns_utc() {
+        let capabilities = Capabilities {
+            time_zones: false,
+            ..Capabilities::default()
+        };
+        let datetime = chrono::NaiveDate::from_ymd_opt(2026, 1, 15)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let start = EventTime::DateTimeZoned {
+            datetime,
+            tzid: "America/New_York".to_string(),
+        };
+        let mut local = Event::new("Standup", start.clone());
+        let mut recurrence = crate::Recurrence::new("FREQ=DAILY");
+        recurrence.exdates = vec![start.shifted(chrono::Duration::days(1))];
+        local.recurrence = Some(recurrence);
+
+        let mut remote = local.clone();
+        remote.start = EventTime::DateTimeUtc(start.to_utc());
+        remote.recurrence.as_mut().unwrap().exdates = vec![EventTime::DateTimeUtc(
+            start.shifted(chrono::Duration::days(1)).to_utc(),
+        )];
+        capabilities.keep_unsupported_fields(&local, &mut remote, FloatingTimezone::Local);
+
+        assert_eq!(remote, local);
+    }
+
+    #[test]
+    fn unknown_auth_style_deserializes() {
+        let description: ProviderDescription =
+            serde_json::from_str(r#"{"protocol_version":2,"auth":"passkey"}"#).unwrap();
+
+        assert_eq!(description.auth, AuthStyle::Unknown);
+    }
+}
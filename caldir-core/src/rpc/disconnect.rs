@@ -0,0 +1,31 @@
+use super::{Method, Rpc};
+use serde::{Deserialize, Serialize};
+
+/// Forget an account's stored credentials, revoking them with the provider
+/// where it can. Disconnecting an account that isn't stored succeeds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Disconnect {
+    pub account_identifier: String,
+}
+
+impl Rpc for Disconnect {
+    const METHOD: Method = Method::Disconnect;
+    type Response = ();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disconnect_serializes_json() {
+        let cmd = Disconnect {
+            account_identifier: "user@hmail.com".to_string(),
+        };
+
+        let json = cmd.to_json().unwrap();
+
+        assert_eq!(json["command"], "disconnect");
+        assert_eq!(json["params"]["account_identifier"], "user@hmail.com");
+    }
+}
@@ -0,0 +1,71 @@
+use super::{Method, Rpc};
+use crate::RemoteConfigParams;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// When `emails` are busy between `from` and `to`, as far as the
+/// provider's account can see. Only for providers with the `free_busy`
+/// capability.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FreeBusy {
+    #[serde(flatten)]
+    pub remote: RemoteConfigParams,
+    pub from: String,
+    pub to: String,
+    pub emails: Vec<String>,
+}
+
+impl Rpc for FreeBusy {
+    const METHOD: Method = Method::FreeBusy;
+    type Response = Vec<BusySchedule>;
+}
+
+/// One person's busy times.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BusySchedule {
+    pub email: String,
+    #[serde(default)]
+    pub busy: Vec<TimePeriod>,
+    /// Why their schedule couldn't be read (e.g. not shared)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimePeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl TimePeriod {
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.start < end && start < self.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_busy_serializes_json() {
+        let mut params = RemoteConfigParams::new();
+        params.insert(
+            "hooli_account".to_string(),
+            toml::Value::String("user@hmail.com".to_string()),
+        );
+
+        let cmd = FreeBusy {
+            remote: params,
+            from: "2026-01-01T00:00:00+00:00".into(),
+            to: "2026-01-08T00:00:00+00:00".into(),
+            emails: vec!["bob@corp.com".into()],
+        };
+
+        let json = cmd.to_json().unwrap();
+
+        assert_eq!(json["command"], "free_busy");
+        assert_eq!(json["params"]["hooli_account"], "user@hmail.com");
+        assert_eq!(json["params"]["emails"][0], "bob@corp.com");
+    }
+}
@@ -1,17 +1,24 @@
 use super::{Method, Rpc};
 use crate::{Event, RemoteConfigParams};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ListEvents {
     #[serde(flatten)]
     pub remote: RemoteConfigParams,
     pub from: String,
     pub to: String,
+    /// Ask for events as `chunk` lines while they're fetched, ending with an
+    /// empty success response. Providers that don't stream just respond.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
 }
 
 impl Rpc for ListEvents {
     const METHOD: Method = Method::ListEvents;
+    /// Big calendars come back in many pages
+    const TIMEOUT: Duration = Duration::from_secs(120);
     type Response = Vec<Event>;
 }
 
@@ -31,6 +38,7 @@ mod tests {
             remote: params,
             from: "2026-01-01T00:00:00+00:00".into(),
             to: "2026-12-31T23:59:59+00:00".into(),
+            stream: false,
         };
 
         let json = cmd.to_json().unwrap();
@@ -0,0 +1,50 @@
+use super::{Method, Rpc};
+use crate::RemoteConfigParams;
+use serde::{Deserialize, Serialize};
+
+/// Names of `emails` from the account's address book (e.g. CardDAV). Only
+/// for providers with the `contacts` capability.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LookupContacts {
+    #[serde(flatten)]
+    pub remote: RemoteConfigParams,
+    pub emails: Vec<String>,
+}
+
+impl Rpc for LookupContacts {
+    const METHOD: Method = Method::LookupContacts;
+    type Response = Vec<ContactCard>;
+}
+
+/// The name an address book has for an email. Emails it doesn't know are
+/// left out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContactCard {
+    pub email: String,
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_contacts_serializes_json() {
+        let mut params = RemoteConfigParams::new();
+        params.insert(
+            "hooli_account".to_string(),
+            toml::Value::String("user@hmail.com".to_string()),
+        );
+
+        let cmd = LookupContacts {
+            remote: params,
+            emails: vec!["bob@corp.com".into()],
+        };
+
+        let json = cmd.to_json().unwrap();
+
+        assert_eq!(json["command"], "lookup_contacts");
+        assert_eq!(json["params"]["hooli_account"], "user@hmail.com");
+        assert_eq!(json["params"]["emails"][0], "bob@corp.com");
+    }
+}
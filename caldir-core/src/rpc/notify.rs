@@ -0,0 +1,100 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Which attendees a provider emails about a pushed change.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Notify {
+    /// Everyone on the guest list
+    All,
+    /// Only guests outside the organizer's domain
+    External,
+    #[default]
+    None,
+}
+
+impl Notify {
+    /// `"all"`, `"external"` or `"none"`.
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "all" => Some(Self::All),
+            "external" => Some(Self::External),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::External => "external",
+            Self::None => "none",
+        }
+    }
+
+    /// Whether `email` gets emailed about a change `organizer` made.
+    pub fn includes(&self, email: &str, organizer: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::External => !domain(email).eq_ignore_ascii_case(domain(organizer)),
+            Self::None => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Notify {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Also reads the `true`/`false` older versions of caldir sent.
+impl<'de> Deserialize<'de> for Notify {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Bool(bool),
+            Name(String),
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::Bool(true) => Ok(Self::All),
+            Value::Bool(false) => Ok(Self::None),
+            Value::Name(name) => Self::parse(&name).ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "unknown notify value: {name} (use all, external or none)"
+                ))
+            }),
+        }
+    }
+}
+
+fn domain(email: &str) -> &str {
+    email.rsplit_once('@').map_or("", |(_, domain)| domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn external_only_includes_other_domains() {
+        let organizer = "alice@hooli.com";
+
+        assert!(Notify::External.includes("bob@example.com", organizer));
+        assert!(!Notify::External.includes("carol@Hooli.com", organizer));
+        assert!(Notify::All.includes("carol@hooli.com", organizer));
+        assert!(!Notify::None.includes("bob@example.com", organizer));
+    }
+
+    #[test]
+    fn deserializes_names_and_legacy_bools() {
+        let parse = |json: &str| serde_json::from_str::<Notify>(json).unwrap();
+
+        assert_eq!(parse(r#""external""#), Notify::External);
+        assert_eq!(parse("true"), Notify::All);
+        assert_eq!(parse("false"), Notify::None);
+        assert!(serde_json::from_str::<Notify>(r#""some""#).is_err());
+        assert_eq!(serde_json::to_string(&Notify::All).unwrap(), r#""all""#);
+    }
+}
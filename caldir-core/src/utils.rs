@@ -1,9 +1,12 @@
+mod config_keys;
 mod date_bounds;
 mod date_range;
 pub(crate) mod paths;
 mod slugify;
 mod tilde_expansion;
 
+pub use config_keys::ConfigKeyError;
+pub(crate) use config_keys::{get_key, set_key};
 pub use date_bounds::DateBounds;
 pub use date_range::DateRange;
 pub(crate) use slugify::slugify;
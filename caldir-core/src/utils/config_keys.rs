@@ -0,0 +1,242 @@
+//! Get/set single values in a TOML-backed config by dotted key
+//! (e.g. `default_calendar`, `remote.google_calendar_id`).
+//!
+//! Setting a value round-trips the whole config through its serde schema,
+//! so typos in keys and badly typed values are rejected instead of written.
+
+use serde::{Serialize, de::DeserializeOwned};
+use toml::{Table, Value};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigKeyError {
+    #[error("unknown config key: {0}")]
+    UnknownKey(String),
+
+    #[error("invalid value for {0}: {1}")]
+    InvalidValue(String, toml::de::Error),
+
+    #[error("could not serialize config: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+/// Look up `key` in `config`. Returns `None` for unknown and unset keys alike.
+pub fn get_key<T: Serialize>(config: &T, key: &str) -> Result<Option<Value>, ConfigKeyError> {
+    let table = to_table(config)?;
+
+    Ok(lookup(&table, key).cloned())
+}
+
+/// Return a copy of `config` with `key` set to `raw`.
+///
+/// `raw` is read as a TOML value when it parses as one (`false`, `["10m"]`),
+/// otherwise as a plain string. Keys that currently hold a string always take
+/// `raw` verbatim, and array keys also accept a comma-separated list.
+///
+/// Keys under `remote.` are provider-defined, so only existing ones may be set.
+pub fn set_key<T>(config: &T, key: &str, raw: &str) -> Result<T, ConfigKeyError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let table = to_table(config)?;
+    let current = lookup(&table, key).cloned();
+
+    if key.starts_with("remote.") && current.is_none() {
+        return Err(ConfigKeyError::UnknownKey(key.to_string()));
+    }
+
+    let value = parse_value(raw, current.as_ref());
+    let (is_str, is_array) = (value.is_str(), value.is_array());
+    let mut candidates = vec![value];
+    // `name = 2024` should still be accepted as the string "2024"
+    if !is_str {
+        candidates.push(Value::String(raw.to_string()));
+    }
+    // Whether the key is a list is up to the schema, not its current value
+    if !is_array {
+        candidates.push(comma_separated(raw));
+    }
+
+    let mut attempts = candidates
+        .into_iter()
+        .map(|candidate| with_value(&table, key, candidate));
+    let first = attempts.next().expect("the parsed value is always tried");
+    let updated = match first {
+        Err(ConfigKeyError::InvalidValue(..)) => attempts.find(Result::is_ok).unwrap_or(first)?,
+        result => result?,
+    };
+
+    // serde silently drops fields it doesn't know, so a key that vanishes
+    // on the way back out was never part of the schema.
+    if lookup(&to_table(&updated)?, key).is_none() {
+        return Err(ConfigKeyError::UnknownKey(key.to_string()));
+    }
+
+    Ok(updated)
+}
+
+fn with_value<T: DeserializeOwned>(
+    table: &Table,
+    key: &str,
+    value: Value,
+) -> Result<T, ConfigKeyError> {
+    let mut table = table.clone();
+    insert(&mut table, key, value)?;
+
+    Value::Table(table)
+        .try_into()
+        .map_err(|e| ConfigKeyError::InvalidValue(key.to_string(), e))
+}
+
+fn to_table<T: Serialize>(config: &T) -> Result<Table, ConfigKeyError> {
+    match Value::try_from(config)? {
+        Value::Table(table) => Ok(table),
+        _ => unreachable!("configs serialize to tables"),
+    }
+}
+
+fn lookup<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    let mut parts = key.split('.');
+    let mut value = table.get(parts.next()?)?;
+
+    for part in parts {
+        value = value.as_table()?.get(part)?;
+    }
+
+    Some(value)
+}
+
+fn insert(table: &mut Table, key: &str, value: Value) -> Result<(), ConfigKeyError> {
+    let (parents, leaf) = match key.rsplit_once('.') {
+        Some((parents, leaf)) => (Some(parents), leaf),
+        None => (None, key),
+    };
+
+    let mut target = table;
+    for part in parents.into_iter().flat_map(|p| p.split('.')) {
+        target = target
+            .entry(part)
+            .or_insert_with(|| Value::Table(Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| ConfigKeyError::UnknownKey(key.to_string()))?;
+    }
+
+    target.insert(leaf.to_string(), value);
+
+    Ok(())
+}
+
+fn parse_value(raw: &str, current: Option<&Value>) -> Value {
+    if let Some(Value::String(_)) = current {
+        return Value::String(raw.to_string());
+    }
+
+    toml::from_str::<Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// `10m, 1h` as `["10m", "1h"]`
+fn comma_separated(raw: &str) -> Value {
+    Value::Array(
+        raw.split(',')
+            .map(|item| Value::String(item.trim().to_string()))
+            .filter(|item| item.as_str() != Some(""))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Demo {
+        name: Option<String>,
+        read_only: Option<bool>,
+        tags: Vec<String>,
+        remote: Option<Table>,
+    }
+
+    fn demo() -> Demo {
+        let mut remote = Table::new();
+        remote.insert("calendar_id".to_string(), Value::String("abc".to_string()));
+
+        Demo {
+            name: Some("Work".to_string()),
+            read_only: None,
+            tags: vec!["a".to_string()],
+            remote: Some(remote),
+        }
+    }
+
+    #[test]
+    fn gets_nested_key() {
+        let value = get_key(&demo(), "remote.calendar_id").unwrap();
+
+        assert_eq!(value, Some(Value::String("abc".to_string())));
+    }
+
+    #[test]
+    fn sets_typed_value() {
+        let updated = set_key(&demo(), "read_only", "true").unwrap();
+
+        assert_eq!(updated.read_only, Some(true));
+    }
+
+    #[test]
+    fn string_keys_take_raw_value_verbatim() {
+        let updated = set_key(&demo(), "name", "true").unwrap();
+
+        assert_eq!(updated.name.as_deref(), Some("true"));
+    }
+
+    #[test]
+    fn falls_back_to_string_when_typed_value_does_not_fit() {
+        let mut config = demo();
+        config.name = None;
+
+        let updated = set_key(&config, "name", "2024").unwrap();
+
+        assert_eq!(updated.name.as_deref(), Some("2024"));
+    }
+
+    #[test]
+    fn array_keys_accept_comma_separated_list() {
+        let updated = set_key(&demo(), "tags", "x, y").unwrap();
+
+        assert_eq!(updated.tags, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn rejects_wrongly_typed_value() {
+        let result = set_key(&demo(), "read_only", "maybe");
+
+        assert!(matches!(result, Err(ConfigKeyError::InvalidValue(_, _))));
+    }
+
+    #[test]
+    fn rejects_unknown_key() {
+        let result = set_key(&demo(), "nmae", "Typo");
+
+        assert!(matches!(result, Err(ConfigKeyError::UnknownKey(_))));
+    }
+
+    #[test]
+    fn rejects_new_remote_key() {
+        let result = set_key(&demo(), "remote.calendar_idd", "xyz");
+
+        assert!(matches!(result, Err(ConfigKeyError::UnknownKey(_))));
+    }
+
+    #[test]
+    fn updates_existing_remote_key() {
+        let updated = set_key(&demo(), "remote.calendar_id", "xyz").unwrap();
+
+        assert_eq!(
+            updated.remote.unwrap().get("calendar_id"),
+            Some(&Value::String("xyz".to_string()))
+        );
+    }
+}
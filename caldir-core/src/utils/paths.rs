@@ -1,11 +1,25 @@
 //! Shared platform-aware path resolution.
 //!
 //! Both `CaldirConfig` (CLI config dir) and `ProviderStorage` (per-provider
-//! storage dir) anchor their defaults on the same platform-native config
-//! directory; this module is the single source of truth for that resolution.
+//! storage dir) anchor their defaults on the same caldir home directory;
+//! this module is the single source of truth for that resolution.
 
 use std::path::PathBuf;
 
+use super::expand_tilde;
+
+/// Overrides the caldir home (config + provider storage) for this process
+/// and the providers it spawns.
+pub const CALDIR_HOME_ENV: &str = "CALDIR_HOME";
+
+/// The caldir home: `$CALDIR_HOME`, or `caldir/` in the platform config dir.
+pub(crate) fn default_caldir_home() -> Option<PathBuf> {
+    match std::env::var_os(CALDIR_HOME_ENV) {
+        Some(home) if !home.is_empty() => Some(expand_tilde(&PathBuf::from(home))),
+        _ => platform_config_dir().map(|dir| dir.join("caldir")),
+    }
+}
+
 /// The platform-native config-root directory:
 /// - Linux/BSD: `$XDG_CONFIG_HOME` or `~/.config`
 /// - macOS:     `~/.config` (override; `dirs::config_dir` returns `~/Library/Application Support`)
@@ -0,0 +1,526 @@
+//! Strict RFC 5545 checks for `.ics` files, stricter than the parser used
+//! for reading them, so broken files can be found and fixed instead of
+//! being skipped by sync.
+
+mod content_line;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use rrule::{RRule, Unvalidated};
+
+use crate::event::tz_normalize::{Tzid, classify};
+use content_line::{ContentLine, content_lines};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The file breaks RFC 5545 in a way readers may reject.
+    Error,
+    /// The file breaks RFC 5545, but most readers cope.
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcsProblem {
+    /// 1-based line in the file
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl IcsProblem {
+    fn error(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            severity: Severity::Error,
+            message: message.into(),
+        }
+    }
+
+    fn warning(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            severity: Severity::Warning,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Properties each component must have.
+fn required_properties(component: &str) -> &'static [&'static str] {
+    match component {
+        "VCALENDAR" => &["PRODID", "VERSION"],
+        "VEVENT" => &["UID", "DTSTAMP", "DTSTART"],
+        "VTIMEZONE" => &["TZID"],
+        "STANDARD" | "DAYLIGHT" => &["DTSTART", "TZOFFSETFROM", "TZOFFSETTO"],
+        "VALARM" => &["ACTION", "TRIGGER"],
+        _ => &[],
+    }
+}
+
+/// Properties a VEVENT may have at most once.
+const SINGLE_EVENT_PROPERTIES: &[&str] = &[
+    "UID",
+    "DTSTAMP",
+    "DTSTART",
+    "DTEND",
+    "DURATION",
+    "SUMMARY",
+    "DESCRIPTION",
+    "LOCATION",
+    "STATUS",
+    "CLASS",
+    "SEQUENCE",
+    "RECURRENCE-ID",
+    "RRULE",
+    "LAST-MODIFIED",
+    "CREATED",
+];
+
+const DATE_PROPERTIES: &[&str] = &[
+    "DTSTART",
+    "DTEND",
+    "DTSTAMP",
+    "RECURRENCE-ID",
+    "EXDATE",
+    "CREATED",
+    "LAST-MODIFIED",
+];
+
+/// Properties that must be in UTC.
+const UTC_PROPERTIES: &[&str] = &["DTSTAMP", "CREATED", "LAST-MODIFIED"];
+
+/// TEXT properties holding a single value, where `,` and `;` must be escaped.
+const TEXT_PROPERTIES: &[&str] = &["SUMMARY", "DESCRIPTION", "LOCATION", "COMMENT", "CONTACT"];
+
+/// TEXT properties holding comma-separated lists.
+const TEXT_LIST_PROPERTIES: &[&str] = &["CATEGORIES", "RESOURCES"];
+
+struct Component {
+    name: String,
+    line: usize,
+    properties: HashMap<String, Vec<ContentLine>>,
+}
+
+/// Problems in the `.ics` file `contents`, by line.
+pub fn validate_ics(contents: &str) -> Vec<IcsProblem> {
+    let mut problems = Vec::new();
+    let lines = content_lines(contents, &mut problems);
+
+    if lines
+        .first()
+        .is_none_or(|first| first.name != "BEGIN" || !first.value.eq_ignore_ascii_case("VCALENDAR"))
+    {
+        problems.push(IcsProblem::error(
+            1,
+            "file doesn't start with BEGIN:VCALENDAR",
+        ));
+    }
+
+    let mut stack: Vec<Component> = Vec::new();
+    let mut defined_tzids = HashSet::new();
+    let mut tzid_references = Vec::new();
+
+    for content_line in lines {
+        match content_line.name.as_str() {
+            "BEGIN" => {
+                if stack.is_empty() && !content_line.value.eq_ignore_ascii_case("VCALENDAR") {
+                    problems.push(IcsProblem::error(
+                        content_line.line,
+                        format!("BEGIN:{} outside VCALENDAR", content_line.value),
+                    ));
+                }
+                stack.push(Component {
+                    name: content_line.value.to_ascii_uppercase(),
+                    line: content_line.line,
+                    properties: HashMap::new(),
+                });
+            }
+            "END" => {
+                let name = content_line.value.to_ascii_uppercase();
+                match stack.pop() {
+                    Some(component) if component.name == name => {
+                        if component.name == "VTIMEZONE"
+                            && let Some(tzid) = component.properties.get("TZID")
+                        {
+                            defined_tzids.extend(tzid.iter().map(|p| p.value.clone()));
+                        }
+                        check_component(&component, &mut problems);
+                    }
+                    Some(component) => {
+                        problems.push(IcsProblem::error(
+                            content_line.line,
+                            format!(
+                                "END:{name} doesn't match BEGIN:{} on line {}",
+                                component.name, component.line
+                            ),
+                        ));
+                        stack.push(component);
+                    }
+                    None => problems.push(IcsProblem::error(
+                        content_line.line,
+                        format!("END:{name} without BEGIN"),
+                    )),
+                }
+            }
+            _ => {
+                check_property(&content_line, &mut problems);
+                if let Some(tzid) = content_line.param("TZID") {
+                    tzid_references.push((content_line.line, tzid.to_string()));
+                }
+
+                match stack.last_mut() {
+                    Some(component) => component
+                        .properties
+                        .entry(content_line.name.clone())
+                        .or_default()
+                        .push(content_line),
+                    None => problems.push(IcsProblem::error(
+                        content_line.line,
+                        format!("{} outside VCALENDAR", content_line.name),
+                    )),
+                }
+            }
+        }
+    }
+
+    for component in stack {
+        problems.push(IcsProblem::error(
+            component.line,
+            format!("BEGIN:{} is never closed", component.name),
+        ));
+    }
+
+    // Timezones caldir knows by name don't need a VTIMEZONE to be read right
+    for (line, tzid) in tzid_references {
+        if !defined_tzids.contains(&tzid) && matches!(classify(&tzid), Tzid::Unknown) {
+            problems.push(IcsProblem::error(
+                line,
+                format!("TZID \"{tzid}\" has no VTIMEZONE and isn't a known timezone"),
+            ));
+        }
+    }
+
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+fn check_component(component: &Component, problems: &mut Vec<IcsProblem>) {
+    for property in required_properties(&component.name) {
+        if !component.properties.contains_key(*property) {
+            problems.push(IcsProblem::error(
+                component.line,
+                format!("{} is missing {property}", component.name),
+            ));
+        }
+    }
+
+    if component.name != "VEVENT" {
+        return;
+    }
+
+    for property in SINGLE_EVENT_PROPERTIES {
+        if let Some(lines) = component.properties.get(*property)
+            && lines.len() > 1
+        {
+            problems.push(IcsProblem::error(
+                lines[1].line,
+                format!("{property} appears more than once"),
+            ));
+        }
+    }
+
+    let first = |name: &str| component.properties.get(name).and_then(|p| p.first());
+
+    if let (Some(_), Some(duration)) = (first("DTEND"), first("DURATION")) {
+        problems.push(IcsProblem::error(
+            duration.line,
+            "VEVENT has both DTEND and DURATION",
+        ));
+    }
+
+    if let (Some(start), Some(end)) = (first("DTSTART"), first("DTEND")) {
+        check_end_after_start(start, end, problems);
+    }
+}
+
+fn check_end_after_start(start: &ContentLine, end: &ContentLine, problems: &mut Vec<IcsProblem>) {
+    let (Some(start_value), Some(end_value)) = (parse_date(&start.value), parse_date(&end.value))
+    else {
+        return;
+    };
+
+    if start_value.is_date() != end_value.is_date() {
+        problems.push(IcsProblem::error(
+            end.line,
+            "DTSTART and DTEND must both be dates or both date-times",
+        ));
+        return;
+    }
+
+    // Times in different zones can't be compared without resolving them
+    let same_zone = start.param("TZID") == end.param("TZID")
+        && start.value.ends_with('Z') == end.value.ends_with('Z');
+
+    if same_zone && end_value.time() <= start_value.time() {
+        problems.push(IcsProblem::error(end.line, "DTEND isn't after DTSTART"));
+    }
+}
+
+fn check_property(content_line: &ContentLine, problems: &mut Vec<IcsProblem>) {
+    let name = content_line.name.as_str();
+    let line = content_line.line;
+
+    if DATE_PROPERTIES.contains(&name) {
+        check_date_property(content_line, problems);
+    }
+
+    if UTC_PROPERTIES.contains(&name) && !content_line.value.ends_with('Z') {
+        problems.push(IcsProblem::error(line, format!("{name} must be in UTC")));
+    }
+
+    if TEXT_PROPERTIES.contains(&name) {
+        check_escaping(content_line, false, problems);
+    } else if TEXT_LIST_PROPERTIES.contains(&name) {
+        check_escaping(content_line, true, problems);
+    }
+
+    if name == "RRULE"
+        && let Err(err) = content_line.value.parse::<RRule<Unvalidated>>()
+    {
+        problems.push(IcsProblem::error(line, format!("invalid RRULE: {err}")));
+    }
+}
+
+fn check_date_property(content_line: &ContentLine, problems: &mut Vec<IcsProblem>) {
+    let name = &content_line.name;
+
+    // EXDATE may list several
+    for value in content_line.value.split(',') {
+        let Some(parsed) = parse_date(value) else {
+            problems.push(IcsProblem::error(
+                content_line.line,
+                format!("{name} value \"{value}\" isn't a date or date-time"),
+            ));
+            continue;
+        };
+
+        if content_line.param("VALUE") == Some("DATE") && !parsed.is_date() {
+            problems.push(IcsProblem::error(
+                content_line.line,
+                format!("{name} has VALUE=DATE but \"{value}\" is a date-time"),
+            ));
+        }
+
+        if value.ends_with('Z') && content_line.param("TZID").is_some() {
+            problems.push(IcsProblem::error(
+                content_line.line,
+                format!("{name} is in UTC but also has a TZID"),
+            ));
+        }
+    }
+}
+
+/// Backslashes may only escape `\`, `;`, `,` and newlines. Unescaped `,`
+/// and `;` are also flagged, except for commas between list items.
+fn check_escaping(content_line: &ContentLine, is_list: bool, problems: &mut Vec<IcsProblem>) {
+    let name = &content_line.name;
+    let mut chars = content_line.value.chars();
+    let mut unescaped = HashSet::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\\' | ';' | ',' | 'n' | 'N') => {}
+                Some(other) => problems.push(IcsProblem::error(
+                    content_line.line,
+                    format!("{name} has an invalid escape \"\\{other}\""),
+                )),
+                None => problems.push(IcsProblem::error(
+                    content_line.line,
+                    format!("{name} ends with a lone backslash"),
+                )),
+            },
+            ';' => {
+                unescaped.insert(';');
+            }
+            ',' if !is_list => {
+                unescaped.insert(',');
+            }
+            _ => {}
+        }
+    }
+
+    let mut unescaped: Vec<_> = unescaped.into_iter().collect();
+    unescaped.sort();
+
+    for c in unescaped {
+        problems.push(IcsProblem::warning(
+            content_line.line,
+            format!("{name} has an unescaped '{c}' (write \\{c})"),
+        ));
+    }
+}
+
+enum DateValue {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl DateValue {
+    fn is_date(&self) -> bool {
+        matches!(self, DateValue::Date(_))
+    }
+
+    fn time(&self) -> NaiveDateTime {
+        match self {
+            DateValue::Date(date) => date.and_time(chrono::NaiveTime::MIN),
+            DateValue::DateTime(time) => *time,
+        }
+    }
+}
+
+/// `YYYYMMDD`, `YYYYMMDDTHHMMSS` or `YYYYMMDDTHHMMSSZ`
+fn parse_date(value: &str) -> Option<DateValue> {
+    if value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(DateValue::Date);
+    }
+
+    let value = value.strip_suffix('Z').unwrap_or(value);
+    if value.len() != 15 {
+        return None;
+    }
+
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .map(DateValue::DateTime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn ics(event_lines: &[&str]) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR",
+            "VERSION:2.0",
+            "PRODID:-//caldir//EN",
+            "BEGIN:VEVENT",
+            "UID:abc",
+            "DTSTAMP:20260101T000000Z",
+        ];
+        lines.extend(event_lines);
+        lines.extend(["END:VEVENT", "END:VCALENDAR", ""]);
+        lines.join("\r\n")
+    }
+
+    fn messages(contents: &str) -> Vec<(usize, String)> {
+        validate_ics(contents)
+            .into_iter()
+            .map(|problem| (problem.line, problem.message))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_valid_event() {
+        let contents = ics(&[
+            "DTSTART;TZID=Europe/Stockholm:20260101T120000",
+            "DTEND;TZID=Europe/Stockholm:20260101T130000",
+            "SUMMARY:Lunch\\, with Bob",
+            "RRULE:FREQ=WEEKLY;BYDAY=MO,WE",
+        ]);
+
+        assert_eq!(messages(&contents), []);
+    }
+
+    #[test]
+    fn reports_missing_properties_at_component_start() {
+        let contents = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:x\r\nBEGIN:VEVENT\r\nUID:abc\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        assert_eq!(
+            messages(contents),
+            [
+                (4, "VEVENT is missing DTSTAMP".to_string()),
+                (4, "VEVENT is missing DTSTART".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_end_before_start() {
+        let contents = ics(&["DTSTART:20260101T120000Z", "DTEND:20260101T110000Z"]);
+
+        assert_eq!(
+            messages(&contents),
+            [(8, "DTEND isn't after DTSTART".to_string())]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_tzid() {
+        let contents = ics(&["DTSTART;TZID=Mars/Olympus:20260101T120000"]);
+
+        assert_eq!(
+            messages(&contents),
+            [(
+                7,
+                "TZID \"Mars/Olympus\" has no VTIMEZONE and isn't a known timezone".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_bad_escapes_and_rrules() {
+        let contents = ics(&[
+            "DTSTART:20260101T120000Z",
+            "SUMMARY:C:\\temp",
+            "RRULE:FREQ=SOMETIMES",
+        ]);
+
+        let problems = messages(&contents);
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(
+            problems[0],
+            (8, "SUMMARY has an invalid escape \"\\t\"".to_string())
+        );
+        assert_eq!(problems[1].0, 9);
+    }
+
+    #[test]
+    fn reports_broken_folding() {
+        let contents = ics(&[
+            "DTSTART:20260101T120000Z",
+            "DESCRIPTION:first",
+            "second half",
+        ]);
+
+        assert_eq!(
+            messages(&contents),
+            [(9, "expected NAME:value, found \"second half\"".to_string())]
+        );
+    }
+
+    #[test]
+    fn warns_about_lf_line_endings_once() {
+        let contents = ics(&["DTSTART:20260101T120000Z"]).replace("\r\n", "\n");
+
+        let problems = validate_ics(&contents);
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].severity, Severity::Warning);
+    }
+}
@@ -0,0 +1,171 @@
+//! Unfolding and parsing of RFC 5545 content lines (§3.1), keeping the
+//! line each one starts on.
+
+use super::IcsProblem;
+
+/// Lines longer than this many octets must be folded.
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Debug)]
+pub(super) struct ContentLine {
+    pub(super) line: usize,
+    pub(super) name: String,
+    pub(super) params: Vec<(String, String)>,
+    pub(super) value: String,
+}
+
+impl ContentLine {
+    pub(super) fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Unfolded content lines of `contents`, with the problems found on the way.
+pub(super) fn content_lines(contents: &str, problems: &mut Vec<IcsProblem>) -> Vec<ContentLine> {
+    let mut unfolded: Vec<(usize, String)> = Vec::new();
+    let mut warned_lf = false;
+
+    for (index, raw) in contents.split_inclusive('\n').enumerate() {
+        let line = index + 1;
+
+        let text = match raw.strip_suffix("\r\n") {
+            Some(text) => text,
+            None => match raw.strip_suffix('\n') {
+                Some(text) => {
+                    if !warned_lf {
+                        problems.push(IcsProblem::warning(
+                            line,
+                            "line ends with LF instead of CRLF",
+                        ));
+                        warned_lf = true;
+                    }
+                    text
+                }
+                None => raw,
+            },
+        };
+
+        if text.len() > MAX_LINE_OCTETS {
+            problems.push(IcsProblem::warning(
+                line,
+                format!(
+                    "line is {} octets long; fold lines after {MAX_LINE_OCTETS}",
+                    text.len()
+                ),
+            ));
+        }
+
+        if let Some(continuation) = text.strip_prefix([' ', '\t']) {
+            match unfolded.last_mut() {
+                Some((_, previous)) => previous.push_str(continuation),
+                None => problems.push(IcsProblem::error(
+                    line,
+                    "folded line doesn't continue anything",
+                )),
+            }
+        } else if !text.is_empty() {
+            unfolded.push((line, text.to_string()));
+        }
+    }
+
+    unfolded
+        .into_iter()
+        .filter_map(|(line, text)| match parse(line, &text) {
+            Ok(content_line) => Some(content_line),
+            Err(problem) => {
+                problems.push(problem);
+                None
+            }
+        })
+        .collect()
+}
+
+/// `NAME;PARAM=value;PARAM="quoted:value":value`
+fn parse(line: usize, text: &str) -> Result<ContentLine, IcsProblem> {
+    let name_end = text.find([';', ':']).unwrap_or(text.len());
+    let name = &text[..name_end];
+
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(IcsProblem::error(
+            line,
+            format!("expected NAME:value, found \"{}\"", truncate(text)),
+        ));
+    }
+
+    let mut params = Vec::new();
+    let mut rest = &text[name_end..];
+
+    while let Some(param) = rest.strip_prefix(';') {
+        let (param_name, after_name) = param.split_once('=').ok_or_else(|| {
+            IcsProblem::error(line, format!("{name} has a parameter without '='"))
+        })?;
+
+        let (value, after_value) = match after_name.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').ok_or_else(|| {
+                    IcsProblem::error(line, format!("{name} has an unclosed quote"))
+                })?;
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => {
+                let end = after_name.find([';', ':']).unwrap_or(after_name.len());
+                (&after_name[..end], &after_name[end..])
+            }
+        };
+
+        params.push((param_name.to_ascii_uppercase(), value.to_string()));
+        rest = after_value;
+    }
+
+    let value = rest.strip_prefix(':').ok_or_else(|| {
+        IcsProblem::error(
+            line,
+            format!("{name} has no ':' before its value (badly folded line?)"),
+        )
+    })?;
+
+    Ok(ContentLine {
+        line,
+        name: name.to_ascii_uppercase(),
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(30) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unfolds_continuation_lines() {
+        let mut problems = Vec::new();
+        let lines = content_lines("SUMMARY:Long\r\n  title\r\nUID:1\r\n", &mut problems);
+
+        assert!(problems.is_empty());
+        assert_eq!(lines[0].value, "Long title");
+        assert_eq!(lines[1].line, 3);
+    }
+
+    #[test]
+    fn parses_quoted_params() {
+        let mut problems = Vec::new();
+        let lines = content_lines(
+            "ATTENDEE;CN=\"Doe; Jane\";RSVP=TRUE:mailto:jane@example.com\r\n",
+            &mut problems,
+        );
+
+        assert_eq!(lines[0].param("CN"), Some("Doe; Jane"));
+        assert_eq!(lines[0].param("RSVP"), Some("TRUE"));
+        assert_eq!(lines[0].value, "mailto:jane@example.com");
+    }
+}
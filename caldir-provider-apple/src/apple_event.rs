@@ -0,0 +1,341 @@
+//! Conversion between caldir events and the JSON shape the Calendar bridge
+//! speaks.
+//!
+//! Calendar's scripting interface only exposes absolute times, so timed
+//! events come back in the system timezone the bridge reports alongside
+//! them. All-day events come back as plain dates.
+
+use anyhow::{Context, Result};
+use caldir_core::{Event, EventTime, EventUid, Recurrence, Status};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppleEvent {
+    #[serde(default)]
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub start: String,
+    pub end: Option<String>,
+    pub all_day: bool,
+    /// IANA name of the timezone to show timed events in.
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    /// One of `none`, `confirmed`, `tentative`, `cancelled`.
+    pub status: String,
+    pub url: Option<String>,
+    /// RRULE value without the `RRULE:` prefix.
+    pub recurrence: Option<String>,
+    #[serde(default)]
+    pub excluded_dates: Vec<String>,
+    #[serde(default)]
+    pub stamp_date: Option<String>,
+    #[serde(default)]
+    pub sequence: i32,
+}
+
+impl TryFrom<AppleEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(value: AppleEvent) -> Result<Self> {
+        let uid = value
+            .uid
+            .context("Calendar returned an event without a UID")?;
+        let zone = value.time_zone.as_deref();
+        let start = parse_time(&value.start, value.all_day, zone)?;
+        let end = value
+            .end
+            .as_deref()
+            .map(|end| parse_time(end, value.all_day, zone))
+            .transpose()?;
+
+        let recurrence = match non_empty(value.recurrence) {
+            Some(rrule) => {
+                let mut recurrence = Recurrence::new(rrule);
+                recurrence.exdates = value
+                    .excluded_dates
+                    .iter()
+                    .map(|d| parse_time(d, value.all_day, zone))
+                    .collect::<Result<_>>()?;
+                Some(recurrence)
+            }
+            None => None,
+        };
+
+        let mut event = Event::new(String::new(), start);
+        event.uid = EventUid::new(uid);
+        event.summary = non_empty(value.summary);
+        event.description = non_empty(value.description);
+        event.location = non_empty(value.location);
+        event.end = end;
+        event.status = match value.status.as_str() {
+            "tentative" => Status::Tentative,
+            "cancelled" => Status::Cancelled,
+            _ => Status::Confirmed,
+        };
+        event.url = non_empty(value.url);
+        event.recurrence = recurrence;
+        event.last_modified = value
+            .stamp_date
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        event.sequence = value.sequence;
+
+        Ok(event)
+    }
+}
+
+impl From<&Event> for AppleEvent {
+    fn from(event: &Event) -> Self {
+        let all_day = event.start.is_date();
+
+        // Calendar requires an end; mirror ICS defaults (one day for
+        // all-day events, zero duration otherwise).
+        let end = match (&event.end, &event.start) {
+            (Some(end), _) => end.clone(),
+            (None, EventTime::Date(start)) => EventTime::Date(*start + Duration::days(1)),
+            (None, start) => start.clone(),
+        };
+
+        let time_zone = match &event.start {
+            EventTime::DateTimeZoned { tzid, .. } => Some(tzid.clone()),
+            _ => None,
+        };
+
+        let status = match event.status {
+            Status::Confirmed => "confirmed",
+            Status::Tentative => "tentative",
+            Status::Cancelled => "cancelled",
+        };
+
+        AppleEvent {
+            uid: Some(event.uid.as_str().to_string()),
+            summary: event.summary.clone(),
+            description: event.description.clone(),
+            location: event.location.clone(),
+            start: format_time(&event.start),
+            end: Some(format_time(&end)),
+            all_day,
+            time_zone,
+            status: status.to_string(),
+            url: event.url.clone(),
+            recurrence: event.recurrence.as_ref().map(|r| r.rrule.clone()),
+            excluded_dates: event
+                .recurrence
+                .iter()
+                .flat_map(|r| &r.exdates)
+                .map(format_time)
+                .collect(),
+            stamp_date: None,
+            sequence: event.sequence,
+        }
+    }
+}
+
+/// Timed values are kept in `zone` if it's a timezone chrono knows, else UTC.
+fn parse_time(value: &str, all_day: bool, zone: Option<&str>) -> Result<EventTime> {
+    if all_day {
+        let date = NaiveDate::parse_from_str(value, DATE_FORMAT)
+            .with_context(|| format!("Invalid all-day date from Calendar: {value}"))?;
+        return Ok(EventTime::Date(date));
+    }
+
+    let datetime = DateTime::parse_from_rfc3339(value)
+        .with_context(|| format!("Invalid date-time from Calendar: {value}"))?;
+
+    match zone.and_then(|z| z.parse::<Tz>().ok()) {
+        Some(tz) => Ok(EventTime::DateTimeZoned {
+            datetime: datetime.with_timezone(&tz).naive_local(),
+            tzid: tz.name().to_string(),
+        }),
+        None => Ok(EventTime::DateTimeUtc(datetime.with_timezone(&Utc))),
+    }
+}
+
+fn format_time(time: &EventTime) -> String {
+    match time {
+        EventTime::Date(date) => date.format(DATE_FORMAT).to_string(),
+        other => other.to_utc().to_rfc3339(),
+    }
+}
+
+// Calendar reports unset text fields as either `null` or "".
+fn non_empty(s: Option<String>) -> Option<String> {
+    s.filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn apple_event() -> AppleEvent {
+        AppleEvent {
+            uid: Some("ABC-123".to_string()),
+            summary: Some("Standup".to_string()),
+            description: None,
+            location: Some(String::new()),
+            start: "2026-03-02T09:00:00.000Z".to_string(),
+            end: Some("2026-03-02T09:15:00.000Z".to_string()),
+            all_day: false,
+            time_zone: None,
+            status: "none".to_string(),
+            url: None,
+            recurrence: None,
+            excluded_dates: Vec::new(),
+            stamp_date: Some("2026-02-01T10:00:00.000Z".to_string()),
+            sequence: 2,
+        }
+    }
+
+    #[test]
+    fn timed_event_converts_to_utc() {
+        let event = Event::try_from(apple_event()).unwrap();
+
+        assert_eq!(event.uid.as_str(), "ABC-123");
+        assert_eq!(
+            event.start,
+            EventTime::DateTimeUtc(Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap())
+        );
+        assert_eq!(
+            event.end,
+            Some(EventTime::DateTimeUtc(
+                Utc.with_ymd_and_hms(2026, 3, 2, 9, 15, 0).unwrap()
+            ))
+        );
+        assert_eq!(event.sequence, 2);
+        assert_eq!(
+            event.last_modified,
+            Some(Utc.with_ymd_and_hms(2026, 2, 1, 10, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn timed_event_keeps_the_system_timezone() {
+        let mut apple = apple_event();
+        apple.time_zone = Some("Europe/Stockholm".to_string());
+
+        let event = Event::try_from(apple).unwrap();
+
+        assert_eq!(
+            event.start,
+            EventTime::DateTimeZoned {
+                datetime: NaiveDate::from_ymd_opt(2026, 3, 2)
+                    .unwrap()
+                    .and_hms_opt(10, 0, 0)
+                    .unwrap(),
+                tzid: "Europe/Stockholm".to_string(),
+            }
+        );
+        assert_eq!(
+            event.end.unwrap().to_utc(),
+            Utc.with_ymd_and_hms(2026, 3, 2, 9, 15, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn empty_text_fields_become_none() {
+        let event = Event::try_from(apple_event()).unwrap();
+
+        assert_eq!(event.location, None);
+        assert_eq!(event.description, None);
+    }
+
+    #[test]
+    fn status_none_maps_to_confirmed() {
+        let event = Event::try_from(apple_event()).unwrap();
+
+        assert_eq!(event.status, Status::Confirmed);
+    }
+
+    #[test]
+    fn all_day_event_converts_to_dates() {
+        let mut apple = apple_event();
+        apple.all_day = true;
+        apple.start = "2026-03-02".to_string();
+        apple.end = Some("2026-03-03".to_string());
+
+        let event = Event::try_from(apple).unwrap();
+
+        assert_eq!(
+            event.start,
+            EventTime::Date(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap())
+        );
+        assert_eq!(
+            event.end,
+            Some(EventTime::Date(
+                NaiveDate::from_ymd_opt(2026, 3, 3).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn recurrence_carries_excluded_dates() {
+        let mut apple = apple_event();
+        apple.recurrence = Some("FREQ=WEEKLY;INTERVAL=1".to_string());
+        apple.excluded_dates = vec!["2026-03-09T09:00:00.000Z".to_string()];
+
+        let event = Event::try_from(apple).unwrap();
+
+        let recurrence = event.recurrence.unwrap();
+        assert_eq!(recurrence.rrule, "FREQ=WEEKLY;INTERVAL=1");
+        assert_eq!(
+            recurrence.exdates,
+            vec![EventTime::DateTimeUtc(
+                Utc.with_ymd_and_hms(2026, 3, 9, 9, 0, 0).unwrap()
+            )]
+        );
+    }
+
+    #[test]
+    fn missing_uid_errors() {
+        let mut apple = apple_event();
+        apple.uid = None;
+
+        assert!(Event::try_from(apple).is_err());
+    }
+
+    #[test]
+    fn round_trips_through_apple_event() {
+        let event = Event::try_from(apple_event()).unwrap();
+
+        let back = Event::try_from(AppleEvent::from(&event)).unwrap();
+
+        assert_eq!(back, event);
+    }
+
+    #[test]
+    fn excluded_dates_round_trip() {
+        let mut apple = apple_event();
+        apple.time_zone = Some("Europe/Stockholm".to_string());
+        apple.recurrence = Some("FREQ=WEEKLY;INTERVAL=1".to_string());
+        apple.excluded_dates = vec!["2026-03-09T09:00:00.000Z".to_string()];
+        let event = Event::try_from(apple).unwrap();
+
+        let back = AppleEvent::from(&event);
+
+        assert_eq!(back.excluded_dates, ["2026-03-09T09:00:00+00:00"]);
+        assert_eq!(back.time_zone.as_deref(), Some("Europe/Stockholm"));
+        assert_eq!(Event::try_from(back).unwrap(), event);
+    }
+
+    #[test]
+    fn all_day_event_without_end_gets_one_day() {
+        let event = Event::new(
+            "Holiday",
+            EventTime::Date(NaiveDate::from_ymd_opt(2026, 12, 25).unwrap()),
+        );
+
+        let apple = AppleEvent::from(&event);
+
+        assert!(apple.all_day);
+        assert_eq!(apple.start, "2026-12-25");
+        assert_eq!(apple.end.as_deref(), Some("2026-12-26"));
+    }
+}
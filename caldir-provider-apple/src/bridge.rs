@@ -0,0 +1,72 @@
+//! Thin bridge to the macOS Calendar store.
+//!
+//! Each operation is a JavaScript for Automation (JXA) script run through
+//! `osascript`. Input goes in as a single JSON argument, output comes back as
+//! JSON on stdout — the same shape as the provider protocol itself.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::process::Command;
+
+const PRELUDE: &str = include_str!("bridge/prelude.js");
+
+pub const LIST_CALENDARS: &str = include_str!("bridge/list_calendars.js");
+pub const LIST_EVENTS: &str = include_str!("bridge/list_events.js");
+pub const SAVE_EVENT: &str = include_str!("bridge/save_event.js");
+pub const DELETE_EVENT: &str = include_str!("bridge/delete_event.js");
+
+// osascript error -1743: the user hasn't granted (or revoked) Automation
+// access to Calendar for the terminal running caldir.
+const NOT_AUTHORIZED: &str = "-1743";
+
+pub async fn run<I: Serialize, O: DeserializeOwned>(script: &str, input: &I) -> Result<O> {
+    let input = serde_json::to_string(input)?;
+    let script = format!("{PRELUDE}\n{script}");
+
+    let output = Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", &script, &input])
+        .output()
+        .await
+        .context("Failed to run osascript (the apple provider only works on macOS)")?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        if stderr.contains(NOT_AUTHORIZED) {
+            bail!(
+                "Calendar access denied. Allow your terminal under System Settings → \
+                 Privacy & Security → Automation → Calendar"
+            );
+        }
+        bail!("Calendar bridge failed: {}", stderr.trim());
+    }
+
+    let stdout = String::from_utf8(output.stdout).context("Calendar bridge returned bad UTF-8")?;
+
+    parse_output(&stdout)
+}
+
+fn parse_output<O: DeserializeOwned>(stdout: &str) -> Result<O> {
+    serde_json::from_str(stdout.trim())
+        .with_context(|| format!("Unexpected output from Calendar bridge: {}", stdout.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_output_ignores_trailing_newline() {
+        let value: Vec<String> = parse_output("[\"a\",\"b\"]\n").unwrap();
+
+        assert_eq!(value, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn parse_output_errors_on_non_json() {
+        let err = parse_output::<Vec<String>>("execution error").unwrap_err();
+
+        assert!(err.to_string().contains("execution error"));
+    }
+}
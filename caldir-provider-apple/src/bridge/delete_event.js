@@ -0,0 +1,6 @@
+function run(argv) {
+  const input = JSON.parse(argv[0]);
+  Calendar.delete(findEvent(findCalendar(input.calendar_id), input.uid));
+
+  return 'null';
+}
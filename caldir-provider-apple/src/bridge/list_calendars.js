@@ -0,0 +1,16 @@
+// AppleScript colors are 16-bit RGB triples.
+function colorToHex(rgb) {
+  if (!Array.isArray(rgb) || rgb.length < 3) return null;
+  return '#' + rgb.slice(0, 3).map((v) => pad(Math.round(v / 257).toString(16))).join('');
+}
+
+function run(argv) {
+  return JSON.stringify(
+    Calendar.calendars().map((c) => ({
+      id: c.calendarIdentifier(),
+      name: c.name(),
+      writable: c.writable(),
+      color: colorToHex(c.color()),
+    })),
+  );
+}
@@ -0,0 +1,10 @@
+// Only bounded by `to`: a recurring master keeps its first occurrence as
+// startDate, so filtering on `from` here would drop long-running series.
+// The provider trims the result to the requested range.
+function run(argv) {
+  const input = JSON.parse(argv[0]);
+  const calendar = findCalendar(input.calendar_id);
+  const events = calendar.events.whose({ startDate: { _lessThan: new Date(input.to) } })();
+
+  return JSON.stringify(events.map(eventToJson));
+}
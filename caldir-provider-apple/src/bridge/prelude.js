@@ -0,0 +1,65 @@
+// Shared helpers prepended to every bridge script.
+
+ObjC.import('Foundation');
+
+const Calendar = Application('Calendar');
+
+function findCalendar(id) {
+  const matches = Calendar.calendars.whose({ calendarIdentifier: id })();
+  if (matches.length === 0) throw new Error('Calendar not found: ' + id);
+  return matches[0];
+}
+
+function findEvent(calendar, uid) {
+  const matches = calendar.events.whose({ uid: uid })();
+  if (matches.length === 0) throw new Error('Event not found: ' + uid);
+  return matches[0];
+}
+
+function pad(n) {
+  return String(n).padStart(2, '0');
+}
+
+// All-day dates are local midnights; report them as a plain YYYY-MM-DD so
+// the caller never has to guess which timezone they were meant in.
+function formatDate(date, allDay) {
+  if (!date) return null;
+  if (allDay) {
+    return date.getFullYear() + '-' + pad(date.getMonth() + 1) + '-' + pad(date.getDate());
+  }
+  return date.toISOString();
+}
+
+// Calendar shows timed events in the system timezone, so report it alongside
+// their (absolute) times.
+function localTimeZone() {
+  return $.NSTimeZone.localTimeZone.name.js;
+}
+
+function parseDate(value, allDay) {
+  if (allDay) {
+    const [y, m, d] = value.split('-').map(Number);
+    return new Date(y, m - 1, d);
+  }
+  return new Date(value);
+}
+
+function eventToJson(ev) {
+  const allDay = ev.alldayEvent();
+  return {
+    uid: ev.uid(),
+    summary: ev.summary(),
+    description: ev.description(),
+    location: ev.location(),
+    start: formatDate(ev.startDate(), allDay),
+    end: formatDate(ev.endDate(), allDay),
+    all_day: allDay,
+    time_zone: allDay ? null : localTimeZone(),
+    status: ev.status(),
+    url: ev.url(),
+    recurrence: ev.recurrence(),
+    excluded_dates: (ev.excludedDates() || []).map((d) => formatDate(d, allDay)),
+    stamp_date: formatDate(ev.stampDate(), false),
+    sequence: ev.sequence(),
+  };
+}
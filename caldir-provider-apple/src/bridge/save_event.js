@@ -0,0 +1,34 @@
+// Updates the event matching `uid` in place, or creates a new one when `uid`
+// is null. Calendar assigns its own UID to new events; the saved event is
+// echoed back so caldir can adopt it.
+function run(argv) {
+  const input = JSON.parse(argv[0]);
+  const calendar = findCalendar(input.calendar_id);
+  const data = input.event;
+
+  const props = {
+    summary: data.summary || '',
+    description: data.description || '',
+    location: data.location || '',
+    startDate: parseDate(data.start, data.all_day),
+    endDate: parseDate(data.end, data.all_day),
+    alldayEvent: data.all_day,
+    status: data.status,
+    url: data.url || '',
+    recurrence: data.recurrence || '',
+    excludedDates: (data.excluded_dates || []).map((d) => parseDate(d, data.all_day)),
+  };
+
+  let ev;
+  if (input.uid) {
+    ev = findEvent(calendar, input.uid);
+    Object.keys(props).forEach((key) => {
+      ev[key] = props[key];
+    });
+  } else {
+    ev = Calendar.Event(props);
+    calendar.events.push(ev);
+  }
+
+  return JSON.stringify(eventToJson(ev));
+}
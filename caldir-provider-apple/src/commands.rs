@@ -0,0 +1,6 @@
+pub mod connect;
+pub mod create_event;
+pub mod delete_event;
+pub mod list_calendars;
+pub mod list_events;
+pub mod update_event;
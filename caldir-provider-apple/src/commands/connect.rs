@@ -0,0 +1,19 @@
+//! Handle the connect flow for the local macOS Calendar store.
+//!
+//! No credentials: access is granted by macOS itself the first time the
+//! bridge talks to Calendar. Connect lists every calendar straight away and
+//! returns them in `Done`, so the CLI never needs an account identifier.
+
+use anyhow::Result;
+use caldir_core::rpc::{Connect, ConnectResponse};
+
+use crate::commands::list_calendars;
+
+pub async fn handle(_cmd: Connect) -> Result<ConnectResponse> {
+    let calendars = list_calendars::fetch_calendar_configs().await?;
+
+    Ok(ConnectResponse::Done {
+        account_identifier: None,
+        calendars: Some(calendars),
+    })
+}
@@ -0,0 +1,14 @@
+//! Create a new event in a macOS Calendar calendar.
+
+use anyhow::Result;
+use caldir_core::Event;
+use caldir_core::rpc::CreateEvent;
+
+use crate::commands::update_event::save_event;
+use crate::remote_config::AppleRemoteConfig;
+
+pub async fn handle(cmd: CreateEvent) -> Result<Event> {
+    let config = AppleRemoteConfig::try_from(&cmd.remote)?;
+
+    save_event(&config, None, &cmd.event).await
+}
@@ -0,0 +1,26 @@
+//! Delete an event from a macOS Calendar calendar.
+
+use anyhow::Result;
+use caldir_core::rpc::DeleteEvent;
+use serde::Serialize;
+
+use crate::bridge;
+use crate::remote_config::AppleRemoteConfig;
+
+#[derive(Serialize)]
+struct DeleteEventInput<'a> {
+    calendar_id: &'a str,
+    uid: &'a str,
+}
+
+pub async fn handle(cmd: DeleteEvent) -> Result<()> {
+    let config = AppleRemoteConfig::try_from(&cmd.remote)?;
+
+    let input = DeleteEventInput {
+        calendar_id: &config.apple_calendar_id,
+        uid: cmd.event.uid.as_str(),
+    };
+    bridge::run::<_, serde_json::Value>(bridge::DELETE_EVENT, &input).await?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! List calendars in the local macOS Calendar store.
+
+use anyhow::Result;
+use caldir_core::rpc::ListCalendars;
+use caldir_core::{CalendarConfig, ProviderSlug, RemoteConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::bridge;
+use crate::constants::PROVIDER_NAME;
+use crate::remote_config::AppleRemoteConfig;
+
+#[derive(Debug, Deserialize)]
+struct AppleCalendar {
+    id: String,
+    name: String,
+    writable: bool,
+    color: Option<String>,
+}
+
+#[derive(Serialize)]
+struct NoInput {}
+
+pub async fn handle(_cmd: ListCalendars) -> Result<Vec<CalendarConfig>> {
+    fetch_calendar_configs().await
+}
+
+pub async fn fetch_calendar_configs() -> Result<Vec<CalendarConfig>> {
+    let calendars: Vec<AppleCalendar> = bridge::run(bridge::LIST_CALENDARS, &NoInput {}).await?;
+
+    Ok(calendars.into_iter().map(to_calendar_config).collect())
+}
+
+fn to_calendar_config(cal: AppleCalendar) -> CalendarConfig {
+    let params = AppleRemoteConfig::new(cal.id).into_remote_config_params();
+    let remote_config = RemoteConfig::new(ProviderSlug::from(PROVIDER_NAME), params);
+
+    // Subscribed and delegated calendars report `writable = false`.
+    CalendarConfig::new(
+        Some(cal.name),
+        cal.color,
+        Some(!cal.writable),
+        Some(remote_config),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar(writable: bool) -> AppleCalendar {
+        AppleCalendar {
+            id: "CAL-1".to_string(),
+            name: "Work (Exchange)".to_string(),
+            writable,
+            color: Some("#1badf8".to_string()),
+        }
+    }
+
+    #[test]
+    fn carries_name_color_and_calendar_id() {
+        let config = to_calendar_config(calendar(true));
+
+        assert_eq!(config.name(), Some("Work (Exchange)"));
+        assert_eq!(config.color(), Some("#1badf8"));
+
+        let remote = config.remote_config().unwrap();
+        assert_eq!(remote.provider_slug().to_string(), PROVIDER_NAME);
+        assert_eq!(
+            remote.get("apple_calendar_id").and_then(|v| v.as_str()),
+            Some("CAL-1")
+        );
+    }
+
+    #[test]
+    fn non_writable_calendar_is_read_only() {
+        assert_eq!(to_calendar_config(calendar(false)).read_only(), Some(true));
+        assert_eq!(to_calendar_config(calendar(true)).read_only(), Some(false));
+    }
+}
@@ -0,0 +1,87 @@
+//! List events within a time range from a macOS Calendar calendar.
+
+use anyhow::Result;
+use caldir_core::Event;
+use caldir_core::rpc::ListEvents;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::apple_event::AppleEvent;
+use crate::bridge;
+use crate::remote_config::AppleRemoteConfig;
+
+#[derive(Serialize)]
+struct ListEventsInput<'a> {
+    calendar_id: &'a str,
+    to: &'a str,
+}
+
+pub async fn handle(cmd: ListEvents) -> Result<Vec<Event>> {
+    let config = AppleRemoteConfig::try_from(&cmd.remote)?;
+
+    let input = ListEventsInput {
+        calendar_id: &config.apple_calendar_id,
+        to: &cmd.to,
+    };
+    let apple_events: Vec<AppleEvent> = bridge::run(bridge::LIST_EVENTS, &input).await?;
+
+    let from_utc = DateTime::parse_from_rfc3339(&cmd.from)?.with_timezone(&Utc);
+    let to_utc = DateTime::parse_from_rfc3339(&cmd.to)?.with_timezone(&Utc);
+
+    let events = apple_events
+        .into_iter()
+        .map(Event::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(in_range(events, from_utc, to_utc))
+}
+
+// Master recurring events pass through; core's recurrence expansion handles
+// per-occurrence range selection later.
+fn in_range(events: Vec<Event>, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Event> {
+    events
+        .into_iter()
+        .filter(|event| event.recurrence.is_some() || event.occurs_in_range(from, to))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use caldir_core::{EventTime, Recurrence};
+    use chrono::TimeZone;
+
+    fn event_at(day: u32) -> Event {
+        let mut event = Event::new(
+            "Test",
+            EventTime::DateTimeUtc(Utc.with_ymd_and_hms(2026, 3, day, 9, 0, 0).unwrap()),
+        );
+        event.end = Some(EventTime::DateTimeUtc(
+            Utc.with_ymd_and_hms(2026, 3, day, 10, 0, 0).unwrap(),
+        ));
+        event
+    }
+
+    #[test]
+    fn drops_single_events_before_range() {
+        let from = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 3, 20, 0, 0, 0).unwrap();
+
+        let kept = in_range(vec![event_at(2), event_at(12)], from, to);
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].start, event_at(12).start);
+    }
+
+    #[test]
+    fn keeps_recurring_masters_that_started_before_range() {
+        let from = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 3, 20, 0, 0, 0).unwrap();
+        let mut master = event_at(2);
+        master.recurrence = Some(Recurrence::new("FREQ=WEEKLY"));
+
+        let kept = in_range(vec![master], from, to);
+
+        assert_eq!(kept.len(), 1);
+    }
+}
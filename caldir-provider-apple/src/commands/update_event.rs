@@ -0,0 +1,45 @@
+//! Update an existing event in a macOS Calendar calendar.
+
+use anyhow::{Result, bail};
+use caldir_core::Event;
+use caldir_core::rpc::UpdateEvent;
+use serde::Serialize;
+
+use crate::apple_event::AppleEvent;
+use crate::bridge;
+use crate::remote_config::AppleRemoteConfig;
+
+#[derive(Serialize)]
+struct SaveEventInput<'a> {
+    calendar_id: &'a str,
+    uid: Option<&'a str>,
+    event: AppleEvent,
+}
+
+pub async fn handle(cmd: UpdateEvent) -> Result<Event> {
+    let config = AppleRemoteConfig::try_from(&cmd.remote)?;
+
+    save_event(&config, Some(cmd.event.uid.as_str()), &cmd.event).await
+}
+
+/// Create (`uid = None`) or update an event and return Calendar's copy of it.
+pub(crate) async fn save_event(
+    config: &AppleRemoteConfig,
+    uid: Option<&str>,
+    event: &Event,
+) -> Result<Event> {
+    // Calendar's scripting interface has no handle on individual occurrences
+    // of a series, so overrides can't be written back.
+    if event.recurrence_id.is_some() {
+        bail!("Apple Calendar can't edit single occurrences of a recurring event");
+    }
+
+    let input = SaveEventInput {
+        calendar_id: &config.apple_calendar_id,
+        uid,
+        event: AppleEvent::from(event),
+    };
+    let saved: AppleEvent = bridge::run(bridge::SAVE_EVENT, &input).await?;
+
+    Event::try_from(saved)
+}
@@ -0,0 +1 @@
+pub const PROVIDER_NAME: &str = "apple";
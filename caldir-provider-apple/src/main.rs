@@ -0,0 +1,66 @@
+//! Apple Calendar provider for caldir.
+//!
+//! Bridges to the on-device macOS Calendar store through `osascript`, so
+//! calendars that only exist locally (e.g. managed Exchange accounts added in
+//! System Settings) can sync like any other remote.
+
+mod apple_event;
+mod bridge;
+mod commands;
+mod constants;
+mod remote_config;
+
+use async_trait::async_trait;
+use caldir_core::rpc::{
+    AuthStyle, Capabilities, Connect, ConnectResponse, CreateEvent, DeleteEvent, Describe,
+    ListCalendars, ListEvents, ProviderDescription, UpdateEvent,
+};
+use caldir_core::{CalendarConfig, Event, provider};
+
+struct AppleProvider;
+
+#[async_trait]
+impl provider::Handler for AppleProvider {
+    async fn connect(&self, cmd: Connect) -> provider::Result<ConnectResponse> {
+        Ok(commands::connect::handle(cmd).await?)
+    }
+
+    async fn describe(&self, _cmd: Describe) -> provider::Result<ProviderDescription> {
+        Ok(ProviderDescription::new(
+            AuthStyle::Local,
+            Capabilities {
+                attendees: false,
+                attachments: false,
+                reminders: false,
+                time_zones: false,
+                ..Capabilities::default()
+            },
+        )
+        .with_version(env!("CARGO_PKG_VERSION")))
+    }
+
+    async fn list_calendars(&self, cmd: ListCalendars) -> provider::Result<Vec<CalendarConfig>> {
+        Ok(commands::list_calendars::handle(cmd).await?)
+    }
+
+    async fn list_events(&self, cmd: ListEvents) -> provider::Result<Vec<Event>> {
+        Ok(commands::list_events::handle(cmd).await?)
+    }
+
+    async fn create_event(&self, cmd: CreateEvent) -> provider::Result<Event> {
+        Ok(commands::create_event::handle(cmd).await?)
+    }
+
+    async fn update_event(&self, cmd: UpdateEvent) -> provider::Result<Event> {
+        Ok(commands::update_event::handle(cmd).await?)
+    }
+
+    async fn delete_event(&self, cmd: DeleteEvent) -> provider::Result<()> {
+        Ok(commands::delete_event::handle(cmd).await?)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    provider::run_provider(AppleProvider).await
+}
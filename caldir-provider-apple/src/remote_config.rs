@@ -0,0 +1,63 @@
+//! Apple Calendar-specific remote configuration.
+
+use anyhow::Result;
+use caldir_core::RemoteConfigParams;
+use serde::{Deserialize, Serialize};
+
+/// Strongly-typed remote configuration for a macOS Calendar calendar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppleRemoteConfig {
+    pub apple_calendar_id: String,
+}
+
+impl AppleRemoteConfig {
+    pub fn new(calendar_id: impl Into<String>) -> Self {
+        Self {
+            apple_calendar_id: calendar_id.into(),
+        }
+    }
+
+    pub fn into_remote_config_params(self) -> RemoteConfigParams {
+        let mut params = RemoteConfigParams::new();
+        params.insert(
+            "apple_calendar_id".to_string(),
+            toml::Value::String(self.apple_calendar_id),
+        );
+        params
+    }
+}
+
+impl TryFrom<&RemoteConfigParams> for AppleRemoteConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(params: &RemoteConfigParams) -> Result<Self> {
+        let apple_calendar_id = params
+            .get("apple_calendar_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required field: apple_calendar_id"))?
+            .to_string();
+
+        Ok(Self { apple_calendar_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_remote_config_params_round_trips() {
+        let original = AppleRemoteConfig::new("8A1D6D9E-2C4F-4C47-9F0B-3B1C2D3E4F50");
+        let params = original.clone().into_remote_config_params();
+
+        let restored = AppleRemoteConfig::try_from(&params).unwrap();
+
+        assert_eq!(restored.apple_calendar_id, original.apple_calendar_id);
+    }
+
+    #[test]
+    fn try_from_missing_calendar_id_errors() {
+        let err = AppleRemoteConfig::try_from(&RemoteConfigParams::new()).unwrap_err();
+        assert!(err.to_string().contains("apple_calendar_id"));
+    }
+}
@@ -16,6 +16,7 @@ pub mod delete_event;
 pub mod discover;
 pub mod list_calendars;
 pub mod list_events;
+pub mod lookup_contacts;
 pub mod update_event;
 
 pub use create_event::create_event;
@@ -23,6 +24,7 @@ pub use delete_event::delete_event;
 pub use discover::{DiscoveredEndpoints, discover_endpoints};
 pub use list_calendars::{RawCalendar, list_calendars_raw};
 pub use list_events::fetch_events;
+pub use lookup_contacts::lookup_contacts;
 pub use update_event::update_event;
 
 /// Parse the first valid event out of a single-resource ICS document.
@@ -0,0 +1,338 @@
+//! Look up people's names in the account's CardDAV address books.
+//!
+//! Many CalDAV servers (Nextcloud, Radicale, Baïkal...) serve CardDAV from
+//! the same principal, so the address book home is found the same way as the
+//! calendar home.
+
+use anyhow::{Context, Result};
+use caldir_core::rpc::ContactCard;
+use http::{Method, Request, Uri};
+use libdav::dav::make_relative_url;
+use libdav::requests::{DavRequest, ParseResponseError};
+
+use crate::caldav::{create_caldav_client, url_to_href};
+
+/// Names the address books under `principal_url` have for `emails`. A server
+/// without CardDAV has no names.
+pub async fn lookup_contacts(
+    username: &str,
+    password: &str,
+    principal_url: &str,
+    emails: &[String],
+) -> Result<Vec<ContactCard>> {
+    if emails.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let client = create_caldav_client(principal_url, username, password)?;
+
+    let Ok(homes) = client
+        .request(FindAddressBookHomes(&url_to_href(principal_url)))
+        .await
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut cards = Vec::new();
+
+    for home in homes {
+        let address_books = client
+            .request(FindAddressBooks(&home))
+            .await
+            .context("Failed to list address books")?;
+
+        for address_book in address_books {
+            let vcards = client
+                .request(FindCardsByEmail {
+                    address_book_href: &address_book,
+                    emails,
+                })
+                .await
+                .context("Failed to query address book")?;
+
+            for vcard in vcards {
+                cards.extend(cards_for(&vcard, emails));
+            }
+        }
+    }
+
+    Ok(cards)
+}
+
+/// A card for each of `emails` that `vcard` has, named after its `FN`.
+fn cards_for(vcard: &str, emails: &[String]) -> Vec<ContactCard> {
+    // Long lines are folded onto lines starting with a space or tab
+    let unfolded = vcard
+        .replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut name = None;
+    let mut card_emails = Vec::new();
+
+    for line in unfolded.lines() {
+        let Some((property, value)) = line.split_once(':') else {
+            continue;
+        };
+        // `item1.EMAIL;TYPE=work` → `EMAIL`
+        let property = property.split(';').next().unwrap_or_default();
+        let property = property.rsplit('.').next().unwrap_or_default();
+
+        if property.eq_ignore_ascii_case("FN") {
+            name = Some(unescape(value.trim()));
+        } else if property.eq_ignore_ascii_case("EMAIL") {
+            card_emails.push(value.trim().to_string());
+        }
+    }
+
+    let Some(name) = name.filter(|name| !name.is_empty()) else {
+        return Vec::new();
+    };
+
+    emails
+        .iter()
+        .filter(|email| card_emails.iter().any(|e| e.eq_ignore_ascii_case(email)))
+        .map(|email| ContactCard {
+            email: email.clone(),
+            name: name.clone(),
+        })
+        .collect()
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\n", " ")
+        .replace("\\\\", "\\")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn propfind(
+    href: &str,
+    base_url: Uri,
+    depth: &str,
+    body: String,
+) -> std::result::Result<Request<String>, http::Error> {
+    Request::builder()
+        .method(Method::from_bytes(b"PROPFIND")?)
+        .uri(make_relative_url(base_url, href)?)
+        .header("Depth", depth)
+        .header("Content-Type", "application/xml")
+        .body(body)
+}
+
+/// The `<response>` elements of a multistatus body.
+fn responses<'a>(
+    doc: &'a roxmltree::Document<'a>,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'a>> {
+    doc.root_element()
+        .descendants()
+        .filter(|n| n.tag_name().name() == "response")
+}
+
+fn child_text<'a>(node: roxmltree::Node<'a, 'a>, name: &str) -> Option<&'a str> {
+    node.descendants()
+        .find(|n| n.tag_name().name() == name)
+        .and_then(|n| n.text())
+}
+
+// ============================================================================
+// Custom CardDAV requests
+// ============================================================================
+
+/// PROPFIND for the principal's `addressbook-home-set` hrefs.
+struct FindAddressBookHomes<'a>(&'a str);
+
+impl DavRequest for FindAddressBookHomes<'_> {
+    type Response = Vec<String>;
+    type ParseError = ParseResponseError;
+    type Error<E> = libdav::dav::WebDavError<E>;
+
+    fn prepare_request(&self, base_url: Uri) -> std::result::Result<Request<String>, http::Error> {
+        let body = r#"<propfind xmlns="DAV:" xmlns:CARD="urn:ietf:params:xml:ns:carddav">
+    <prop>
+        <CARD:addressbook-home-set/>
+    </prop>
+</propfind>"#;
+
+        propfind(self.0, base_url, "0", body.to_string())
+    }
+
+    fn parse_response(
+        &self,
+        parts: &http::response::Parts,
+        body: &[u8],
+    ) -> std::result::Result<Self::Response, ParseResponseError> {
+        if !parts.status.is_success() {
+            return Err(ParseResponseError::BadStatusCode(parts.status));
+        }
+
+        let doc = roxmltree::Document::parse(std::str::from_utf8(body)?)?;
+
+        let homes = doc
+            .descendants()
+            .filter(|n| n.tag_name().name() == "addressbook-home-set")
+            .flat_map(|set| set.descendants().filter(|n| n.tag_name().name() == "href"))
+            .filter_map(|href| href.text())
+            .map(|href| url_to_href(href.trim()))
+            .collect();
+
+        Ok(homes)
+    }
+}
+
+/// PROPFIND for the address book collections in an address book home.
+struct FindAddressBooks<'a>(&'a str);
+
+impl DavRequest for FindAddressBooks<'_> {
+    type Response = Vec<String>;
+    type ParseError = ParseResponseError;
+    type Error<E> = libdav::dav::WebDavError<E>;
+
+    fn prepare_request(&self, base_url: Uri) -> std::result::Result<Request<String>, http::Error> {
+        let body = r#"<propfind xmlns="DAV:">
+    <prop>
+        <resourcetype/>
+    </prop>
+</propfind>"#;
+
+        propfind(self.0, base_url, "1", body.to_string())
+    }
+
+    fn parse_response(
+        &self,
+        parts: &http::response::Parts,
+        body: &[u8],
+    ) -> std::result::Result<Self::Response, ParseResponseError> {
+        if !parts.status.is_success() {
+            return Err(ParseResponseError::BadStatusCode(parts.status));
+        }
+
+        let doc = roxmltree::Document::parse(std::str::from_utf8(body)?)?;
+
+        let address_books = responses(&doc)
+            .filter(|response| {
+                response
+                    .descendants()
+                    .any(|n| n.tag_name().name() == "addressbook")
+            })
+            .filter_map(|response| child_text(response, "href"))
+            .map(|href| url_to_href(href.trim()))
+            .collect();
+
+        Ok(address_books)
+    }
+}
+
+/// `addressbook-query` REPORT for the vCards with any of `emails`.
+struct FindCardsByEmail<'a> {
+    address_book_href: &'a str,
+    emails: &'a [String],
+}
+
+impl DavRequest for FindCardsByEmail<'_> {
+    type Response = Vec<String>;
+    type ParseError = ParseResponseError;
+    type Error<E> = libdav::dav::WebDavError<E>;
+
+    fn prepare_request(&self, base_url: Uri) -> std::result::Result<Request<String>, http::Error> {
+        let matches: String = self
+            .emails
+            .iter()
+            .map(|email| {
+                format!(
+                    r#"
+        <CARD:prop-filter name="EMAIL">
+            <CARD:text-match collation="i;unicode-casemap" match-type="equals">{}</CARD:text-match>
+        </CARD:prop-filter>"#,
+                    escape_xml(email)
+                )
+            })
+            .collect();
+
+        let body = format!(
+            r#"<CARD:addressbook-query xmlns="DAV:" xmlns:CARD="urn:ietf:params:xml:ns:carddav">
+    <prop>
+        <CARD:address-data>
+            <CARD:prop name="FN"/>
+            <CARD:prop name="EMAIL"/>
+        </CARD:address-data>
+    </prop>
+    <CARD:filter test="anyof">{matches}
+    </CARD:filter>
+</CARD:addressbook-query>"#
+        );
+
+        Request::builder()
+            .method(Method::from_bytes(b"REPORT")?)
+            .uri(make_relative_url(base_url, self.address_book_href)?)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml")
+            .body(body)
+    }
+
+    fn parse_response(
+        &self,
+        parts: &http::response::Parts,
+        body: &[u8],
+    ) -> std::result::Result<Self::Response, ParseResponseError> {
+        if !parts.status.is_success() {
+            return Err(ParseResponseError::BadStatusCode(parts.status));
+        }
+
+        let doc = roxmltree::Document::parse(std::str::from_utf8(body)?)?;
+
+        let vcards = responses(&doc)
+            .filter_map(|response| child_text(response, "address-data"))
+            .map(str::to_string)
+            .collect();
+
+        Ok(vcards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_requested_emails_after_the_cards_full_name() {
+        let vcard = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Smith\\, Alice\r\nitem1.EMAIL;TYPE=work:Alice@Example.com\r\nEMAIL:alice@home.example\r\nEND:VCARD\r\n";
+        let emails = vec![
+            "alice@example.com".to_string(),
+            "bob@example.com".to_string(),
+        ];
+
+        assert_eq!(
+            cards_for(vcard, &emails),
+            vec![ContactCard {
+                email: "alice@example.com".to_string(),
+                name: "Smith, Alice".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn unfolds_long_lines() {
+        let vcard =
+            "BEGIN:VCARD\r\nFN:Alice \r\n Smith\r\nEMAIL:alice@example.com\r\nEND:VCARD\r\n";
+        let cards = cards_for(vcard, &["alice@example.com".to_string()]);
+
+        assert_eq!(cards[0].name, "Alice Smith");
+    }
+
+    #[test]
+    fn cards_without_a_name_are_skipped() {
+        let vcard = "BEGIN:VCARD\r\nEMAIL:alice@example.com\r\nEND:VCARD\r\n";
+
+        assert!(cards_for(vcard, &["alice@example.com".to_string()]).is_empty());
+    }
+}
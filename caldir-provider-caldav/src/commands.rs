@@ -1,6 +1,9 @@
+pub mod check_account;
 pub mod connect;
 pub mod create_event;
 pub mod delete_event;
+pub mod disconnect;
 pub mod list_calendars;
 pub mod list_events;
+pub mod lookup_contacts;
 pub mod update_event;
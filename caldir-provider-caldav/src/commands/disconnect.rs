@@ -0,0 +1,12 @@
+use anyhow::Result;
+use caldir_core::provider::ProviderStorage;
+use caldir_core::rpc::Disconnect;
+
+use crate::constants::PROVIDER_NAME;
+use crate::session::SessionStore;
+
+/// Forget the account's stored credentials.
+pub async fn handle(cmd: Disconnect) -> Result<()> {
+    let store = SessionStore::new(ProviderStorage::for_provider(PROVIDER_NAME)?);
+    store.delete(&cmd.account_identifier)
+}
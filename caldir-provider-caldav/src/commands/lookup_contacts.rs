@@ -0,0 +1,19 @@
+//! Look up attendees' names in the account's CardDAV address books.
+
+use anyhow::Result;
+use caldir_core::provider::ProviderStorage;
+use caldir_core::rpc::{ContactCard, LookupContacts};
+use caldir_provider_caldav::caldav::ops;
+
+use crate::constants::PROVIDER_NAME;
+use crate::remote_config::CaldavRemoteConfig;
+use crate::session::SessionStore;
+
+pub async fn handle(cmd: LookupContacts) -> Result<Vec<ContactCard>> {
+    let config = CaldavRemoteConfig::try_from(&cmd.remote)?;
+    let store = SessionStore::new(ProviderStorage::for_provider(PROVIDER_NAME)?);
+    let session = store.load(&config.caldav_account)?;
+    let (username, password) = session.credentials();
+
+    ops::lookup_contacts(username, password, &session.principal_url, &cmd.emails).await
+}
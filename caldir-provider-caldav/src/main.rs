@@ -7,7 +7,9 @@ mod session;
 
 use async_trait::async_trait;
 use caldir_core::rpc::{
-    Connect, ConnectResponse, CreateEvent, DeleteEvent, ListCalendars, ListEvents, UpdateEvent,
+    AccountStatus, AuthStyle, Capabilities, CheckAccount, Connect, ConnectResponse, ContactCard,
+    CreateEvent, DeleteEvent, Describe, Disconnect, ListCalendars, ListEvents, LookupContacts,
+    ProviderDescription, UpdateEvent,
 };
 use caldir_core::{CalendarConfig, Event, provider};
 
@@ -19,6 +21,20 @@ impl provider::Handler for CaldavProvider {
         Ok(commands::connect::handle(cmd).await?)
     }
 
+    async fn describe(&self, _cmd: Describe) -> provider::Result<ProviderDescription> {
+        // Scheduling (RFC 6638) is optional and most servers don't email
+        // attendees, so leave that to caldir's iMIP support
+        Ok(ProviderDescription::new(
+            AuthStyle::Credentials,
+            Capabilities {
+                invitations: false,
+                contacts: true,
+                ..Capabilities::default()
+            },
+        )
+        .with_version(env!("CARGO_PKG_VERSION")))
+    }
+
     async fn list_calendars(&self, cmd: ListCalendars) -> provider::Result<Vec<CalendarConfig>> {
         Ok(commands::list_calendars::handle(cmd).await?)
     }
@@ -38,6 +54,18 @@ impl provider::Handler for CaldavProvider {
     async fn delete_event(&self, cmd: DeleteEvent) -> provider::Result<()> {
         Ok(commands::delete_event::handle(cmd).await?)
     }
+
+    async fn check_account(&self, cmd: CheckAccount) -> provider::Result<AccountStatus> {
+        Ok(commands::check_account::handle(cmd).await?)
+    }
+
+    async fn disconnect(&self, cmd: Disconnect) -> provider::Result<()> {
+        Ok(commands::disconnect::handle(cmd).await?)
+    }
+
+    async fn lookup_contacts(&self, cmd: LookupContacts) -> provider::Result<Vec<ContactCard>> {
+        Ok(commands::lookup_contacts::handle(cmd).await?)
+    }
 }
 
 #[tokio::main]
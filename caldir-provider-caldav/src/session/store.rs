@@ -46,13 +46,28 @@ impl SessionStore {
     }
 
     /// Find a session by its `account_identifier()` form ("user@host").
-    ///
+    pub fn load(&self, account_identifier: &str) -> Result<Session> {
+        match self.find(account_identifier)? {
+            Some((_, session)) => Ok(session),
+            None => anyhow::bail!("CalDAV session for {} not found!", account_identifier),
+        }
+    }
+
+    /// Remove the account's session file. Succeeds if there was none.
+    pub fn delete(&self, account_identifier: &str) -> Result<()> {
+        if let Some((path, _)) = self.find(account_identifier)? {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+
     /// Scans the session directory rather than computing the filename
     /// directly, since the on-disk slug encoding (`.` → `_`) is one-way.
-    pub fn load(&self, account_identifier: &str) -> Result<Session> {
+    fn find(&self, account_identifier: &str) -> Result<Option<(PathBuf, Session)>> {
         let session_dir = self.session_dir();
         if !session_dir.exists() {
-            anyhow::bail!("CalDAV session for {} not found!", account_identifier);
+            return Ok(None);
         }
 
         for entry in std::fs::read_dir(&session_dir)? {
@@ -63,13 +78,13 @@ impl SessionStore {
                 if let Ok(session) = toml::from_str::<Session>(&contents) {
                     let id = Session::account_identifier(&session.username, &session.server_url);
                     if id == account_identifier {
-                        return Ok(session);
+                        return Ok(Some((path, session)));
                     }
                 }
             }
         }
 
-        anyhow::bail!("CalDAV session for {} not found!", account_identifier);
+        Ok(None)
     }
 
     fn session_dir(&self) -> PathBuf {
@@ -157,6 +172,20 @@ mod tests {
         assert!(err.to_string().contains("ghost@example.com@nowhere"));
     }
 
+    #[test]
+    fn delete_removes_only_that_account() {
+        let (_tmp, store) = store();
+        let session = sample_session();
+        store.save(&session).unwrap();
+
+        store.delete("ghost@example.com@nowhere").unwrap();
+        let account_id = Session::account_identifier(&session.username, &session.server_url);
+        assert!(store.load(&account_id).is_ok());
+
+        store.delete(&account_id).unwrap();
+        assert!(store.load(&account_id).is_err());
+    }
+
     #[cfg(unix)]
     #[test]
     fn save_chmods_session_file_to_0600() {
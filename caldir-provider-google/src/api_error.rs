@@ -0,0 +1,224 @@
+//! Turn Google API failures into errors caldir knows to retry or explain.
+//!
+//! Rate limits (429, 403 `rateLimitExceeded`) and 5xx responses are reported
+//! as [`TransientError`]s, carrying Google's `Retry-After` when it sends one.
+//! Revoked credentials, missing events and conflicting edits are tagged with
+//! an [`ErrorCode`].
+
+use std::time::Duration;
+
+use caldir_core::provider::{self, CodedError, TransientError};
+use caldir_core::rpc::ErrorCode;
+use google_calendar::ClientError;
+
+/// A failed response from a Calendar API call made with `reqwest` directly.
+#[derive(Debug)]
+pub struct GoogleApiError {
+    status: u16,
+    retry_after: Option<Duration>,
+    body: String,
+}
+
+impl GoogleApiError {
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_retry_after);
+        let body = response.text().await.unwrap_or_default();
+
+        Self {
+            status,
+            retry_after,
+            body,
+        }
+    }
+}
+
+impl std::fmt::Display for GoogleApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Google API returned {}: {}", self.status, self.body)
+    }
+}
+
+impl std::error::Error for GoogleApiError {}
+
+/// Convert a command's error for the provider protocol, flagging transient
+/// ones and tagging those the CLI can explain.
+pub fn into_provider_error(err: anyhow::Error) -> provider::Error {
+    let message = format!("{err:#}");
+
+    if let Some(coded) = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<CodedError>())
+    {
+        return Box::new(CodedError::new(coded.code(), message));
+    }
+
+    let failure = err.chain().find_map(api_failure);
+    let Some((status, body, retry_after)) = failure else {
+        return err.into();
+    };
+
+    if is_transient(status, body) {
+        let mut transient = TransientError::new(message).with_retry_after(retry_after);
+        if is_rate_limit(status, body) {
+            transient = transient.with_code(ErrorCode::RateLimited);
+        }
+        return Box::new(transient);
+    }
+
+    match error_code(status, body) {
+        Some(code) => Box::new(CodedError::new(code, message)),
+        None => err.into(),
+    }
+}
+
+/// Status, body and `Retry-After` of a failed Google API response.
+fn api_failure<'a>(
+    cause: &'a (dyn std::error::Error + 'static),
+) -> Option<(u16, &'a str, Option<Duration>)> {
+    if let Some(api) = cause.downcast_ref::<GoogleApiError>() {
+        return Some((api.status, api.body.as_str(), api.retry_after));
+    }
+
+    if let Some(ClientError::HttpError {
+        status,
+        headers,
+        error,
+    }) = cause.downcast_ref::<ClientError>()
+    {
+        let retry_after = headers
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_retry_after);
+        return Some((status.as_u16(), error.as_str(), retry_after));
+    }
+
+    None
+}
+
+fn is_transient(status: u16, body: &str) -> bool {
+    is_rate_limit(status, body) || (500..=599).contains(&status)
+}
+
+fn is_rate_limit(status: u16, body: &str) -> bool {
+    match status {
+        429 => true,
+        // Google reports quota exhaustion as 403 with a rate-limit reason
+        403 => error_reasons(body)
+            .iter()
+            .any(|reason| RATE_LIMIT_REASONS.contains(&reason.as_str())),
+        _ => false,
+    }
+}
+
+const RATE_LIMIT_REASONS: [&str; 2] = ["rateLimitExceeded", "userRateLimitExceeded"];
+
+/// The `reason`s in a Google API error body:
+/// `{"error":{"errors":[{"reason":"..."}]}}`
+fn error_reasons(body: &str) -> Vec<String> {
+    let Ok(body) = serde_json::from_str::<serde_json::Value>(body) else {
+        return Vec::new();
+    };
+
+    body["error"]["errors"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|error| error["reason"].as_str())
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn error_code(status: u16, body: &str) -> Option<ErrorCode> {
+    match status {
+        401 => Some(ErrorCode::AuthExpired),
+        // What the token endpoint answers for a revoked refresh token
+        400 if body.contains("invalid_grant") => Some(ErrorCode::AuthExpired),
+        // Signed in before caldir asked for the scope, so reconnecting fixes it
+        403 if body.contains("ACCESS_TOKEN_SCOPE_INSUFFICIENT") => Some(ErrorCode::AuthExpired),
+        404 | 410 => Some(ErrorCode::NotFound),
+        409 | 412 => Some(ErrorCode::Conflict),
+        _ => None,
+    }
+}
+
+/// `Retry-After` in seconds. (The HTTP-date form isn't used by Google.)
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limits_and_server_errors_are_transient() {
+        assert!(is_transient(429, ""));
+        assert!(is_transient(503, ""));
+        assert!(is_transient(
+            403,
+            r#"{"error":{"errors":[{"reason":"rateLimitExceeded"}]}}"#
+        ));
+        assert!(is_transient(
+            403,
+            r#"{"error":{"errors":[{"reason":"userRateLimitExceeded"}]}}"#
+        ));
+    }
+
+    #[test]
+    fn other_client_errors_are_permanent() {
+        assert!(!is_transient(404, "Not Found"));
+        assert!(!is_transient(
+            403,
+            r#"{"error":{"errors":[{"reason":"forbidden"}]}}"#
+        ));
+    }
+
+    #[test]
+    fn transient_api_errors_keep_retry_after() {
+        let err = anyhow::Error::new(GoogleApiError {
+            status: 429,
+            retry_after: Some(Duration::from_secs(20)),
+            body: String::new(),
+        })
+        .context("Failed to fetch events");
+
+        let converted = into_provider_error(err);
+
+        assert!(converted.downcast_ref::<TransientError>().is_some());
+    }
+
+    #[test]
+    fn client_errors_get_codes() {
+        assert_eq!(error_code(401, ""), Some(ErrorCode::AuthExpired));
+        assert_eq!(
+            error_code(400, r#"{"error":"invalid_grant"}"#),
+            Some(ErrorCode::AuthExpired)
+        );
+        assert_eq!(
+            error_code(
+                403,
+                r#"{"error":{"status":"PERMISSION_DENIED","details":[{"reason":"ACCESS_TOKEN_SCOPE_INSUFFICIENT"}]}}"#
+            ),
+            Some(ErrorCode::AuthExpired)
+        );
+        assert_eq!(error_code(410, "Gone"), Some(ErrorCode::NotFound));
+        assert_eq!(error_code(412, ""), Some(ErrorCode::Conflict));
+        assert_eq!(error_code(400, "Bad Request"), None);
+    }
+
+    #[test]
+    fn coded_errors_keep_their_code() {
+        let err = anyhow::Error::new(CodedError::new(ErrorCode::AuthExpired, "revoked"))
+            .context("Failed to load session");
+
+        let converted = into_provider_error(err);
+
+        let coded = converted.downcast_ref::<CodedError>().unwrap();
+        assert_eq!(coded.code(), ErrorCode::AuthExpired);
+    }
+}
@@ -0,0 +1,409 @@
+//! Push many creates/updates through Google's batch endpoint.
+//!
+//! Plain creates and organizer-side updates go out as one `multipart/mixed`
+//! request per chunk. Writes that need special handling (recurring instance
+//! overrides, RSVPs) or that Google rejects inside the batch are run through
+//! the single-event handlers instead, which also gives them their retries.
+
+use anyhow::{Context, Result, anyhow};
+use caldir_core::provider::ProviderStorage;
+use caldir_core::rpc::{BatchWrite, CreateEvent, EventWrite, Notify, Response, UpdateEvent};
+use caldir_core::{Event, RemoteConfigParams};
+
+use crate::api_error::GoogleApiError;
+use crate::app_config::AppConfigStore;
+use crate::commands::instance::{instance_patch_body, is_instance_id};
+use crate::commands::list_events::events_url;
+use crate::commands::update_event::patch_body_without_attendees;
+use crate::commands::{create_event, ensure_writable, send_updates_param, update_event};
+use crate::constants::{PROVIDER_EVENT_ID_PROPERTY, PROVIDER_NAME};
+use crate::google_event::{FromGoogle, ToGoogle};
+use crate::remote_config::GoogleRemoteConfig;
+use crate::session::SessionStore;
+
+const BATCH_URL: &str = "https://www.googleapis.com/batch/calendar/v3";
+const BOUNDARY: &str = "caldir_batch";
+/// Google recommends keeping batches at or below 50 calls.
+const CHUNK_SIZE: usize = 50;
+
+pub async fn handle(cmd: BatchWrite) -> Result<Vec<Response<Event>>> {
+    let config = GoogleRemoteConfig::try_from(&cmd.remote)?;
+    let account_email = &config.google_account;
+    let calendar_id = &config.google_calendar_id;
+
+    let storage = ProviderStorage::for_provider(PROVIDER_NAME)?;
+    let session_store = SessionStore::new(storage.clone());
+    let app_config_store = AppConfigStore::new(storage);
+
+    let session = session_store
+        .load_for_remote(&config, &app_config_store)
+        .await?;
+
+    let events_path = events_url(calendar_id)?.path().to_string();
+
+    // `None` until the batch settles a write; still `None` means "run it singly".
+    let mut results: Vec<Option<Result<Event>>> = cmd.writes.iter().map(|_| None).collect();
+
+    let batchable: Vec<(usize, BatchRequest)> = cmd
+        .writes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, write)| {
+            batch_request(write, account_email, &events_path, cmd.notify_attendees)
+                .map(|request| (i, request))
+        })
+        .collect();
+
+    for chunk in batchable.chunks(CHUNK_SIZE) {
+        let requests: Vec<&BatchRequest> = chunk.iter().map(|(_, request)| request).collect();
+        let responses = match send_batch(session.access_token(), &requests).await {
+            Ok(responses) => responses,
+            Err(e) => {
+                // Google may have applied some of them, so they mustn't be retried singly
+                let message = format!("{e:#}");
+                for (i, _) in chunk {
+                    results[*i] = Some(Err(anyhow!("{message}")));
+                }
+                continue;
+            }
+        };
+
+        for ((i, _), response) in chunk.iter().zip(responses) {
+            // A write Google accepted must not be retried, even if we can't read the reply.
+            results[*i] = response.map(|parsed| {
+                parsed
+                    .context("Failed to parse batched event")
+                    .and_then(Event::from_google)
+            });
+        }
+    }
+
+    // Everything not done by the batch goes through the normal path.
+    let mut out = Vec::with_capacity(results.len());
+    for (write, result) in cmd.writes.into_iter().zip(results) {
+        let result = match result {
+            Some(result) => result,
+            None => write_one(write, &cmd.remote, cmd.notify_attendees).await,
+        };
+
+        out.push(match result {
+            Ok(data) => Response::Success { data },
+            Err(e) => Response::failure(format!("{e:#}")),
+        });
+    }
+
+    Ok(out)
+}
+
+async fn write_one(
+    write: EventWrite,
+    remote: &RemoteConfigParams,
+    notify_attendees: Notify,
+) -> Result<Event> {
+    match write {
+        EventWrite::Create(event) => {
+            create_event::handle(CreateEvent {
+                remote: remote.clone(),
+                event,
+                notify_attendees,
+            })
+            .await
+        }
+        EventWrite::Update(event) => {
+            update_event::handle(UpdateEvent {
+                remote: remote.clone(),
+                event,
+                notify_attendees,
+            })
+            .await
+        }
+    }
+}
+
+/// One HTTP call inside a batch.
+struct BatchRequest {
+    method: &'static str,
+    path: String,
+    body: serde_json::Value,
+}
+
+/// The batch call for `write`, or `None` if it needs the single-event path.
+fn batch_request(
+    write: &EventWrite,
+    account_email: &str,
+    events_path: &str,
+    notify_attendees: Notify,
+) -> Option<BatchRequest> {
+    let query = format!(
+        "sendUpdates={}&conferenceDataVersion=1",
+        send_updates_param(notify_attendees)
+    );
+
+    // The single-event path explains why it can't be written
+    let (EventWrite::Create(event) | EventWrite::Update(event)) = write;
+    ensure_writable(event).ok()?;
+
+    match write {
+        EventWrite::Create(event) if event.recurrence_id.is_none() => {
+            let mut google_event = event.to_google();
+            // Let google pick the ID (see create_event)
+            google_event.id = String::new();
+
+            Some(BatchRequest {
+                method: "POST",
+                path: format!("{events_path}?{query}"),
+                body: serde_json::to_value(google_event).ok()?,
+            })
+        }
+        EventWrite::Update(event) if !event.is_invite_for(account_email) => {
+            let event_id = event.x_property(PROVIDER_EVENT_ID_PROPERTY)?;
+
+            let body = if event.recurrence_id.is_some() {
+                // An override still carrying its master's ID needs its
+                // instance looked up first
+                if !is_instance_id(event_id) {
+                    return None;
+                }
+                instance_patch_body(event).ok()?
+            } else {
+                patch_body_without_attendees(event).ok()?
+            };
+
+            Some(BatchRequest {
+                method: "PATCH",
+                path: format!("{events_path}/{}?{query}", escape_segment(event_id)),
+                body,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn escape_segment(segment: &str) -> String {
+    url::form_urlencoded::byte_serialize(segment.as_bytes()).collect()
+}
+
+type BatchReply = serde_json::Result<google_calendar::types::Event>;
+
+/// Send one batch. Returns each call's reply, or `None` where Google rejected that call.
+async fn send_batch(
+    access_token: &str,
+    requests: &[&BatchRequest],
+) -> Result<Vec<Option<BatchReply>>> {
+    let response = reqwest::Client::new()
+        .post(BATCH_URL)
+        .bearer_auth(access_token)
+        .header(
+            "Content-Type",
+            format!("multipart/mixed; boundary={BOUNDARY}"),
+        )
+        .body(batch_body(requests))
+        .send()
+        .await
+        .context("Failed to send batch request")?;
+
+    if !response.status().is_success() {
+        let error = GoogleApiError::from_response(response).await;
+        return Err(anyhow::Error::new(error).context("Batch request failed"));
+    }
+
+    let boundary = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split("boundary=").nth(1))
+        .map(|b| b.trim_matches('"').to_string())
+        .ok_or_else(|| anyhow!("Batch response has no multipart boundary"))?;
+
+    let body = response
+        .text()
+        .await
+        .context("Failed to read batch response")?;
+
+    let mut replies: Vec<Option<BatchReply>> = (0..requests.len()).map(|_| None).collect();
+
+    for (index, status, json) in parse_batch_response(&body, &boundary) {
+        if (200..300).contains(&status)
+            && let Some(slot) = replies.get_mut(index)
+        {
+            *slot = Some(serde_json::from_str(json));
+        }
+    }
+
+    Ok(replies)
+}
+
+fn batch_body(requests: &[&BatchRequest]) -> String {
+    let mut body = String::new();
+
+    for (i, request) in requests.iter().enumerate() {
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <item{i}>\r\n\
+             \r\n\
+             {} {}\r\n\
+             Content-Type: application/json\r\n\
+             \r\n\
+             {}\r\n",
+            request.method, request.path, request.body,
+        ));
+    }
+
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+    body
+}
+
+/// Split a `multipart/mixed` batch response into `(item index, HTTP status, body)`.
+/// Parts can come back in any order; `Content-ID: <response-itemN>` says which is which.
+fn parse_batch_response<'a>(body: &'a str, boundary: &str) -> Vec<(usize, u16, &'a str)> {
+    let delimiter = format!("--{boundary}");
+
+    body.split(delimiter.as_str())
+        .filter_map(|part| {
+            let (part_headers, http) = split_headers(part)?;
+
+            let index = part_headers.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                if !name.trim().eq_ignore_ascii_case("content-id") {
+                    return None;
+                }
+                value
+                    .trim()
+                    .trim_start_matches("<response-item")
+                    .trim_end_matches('>')
+                    .parse()
+                    .ok()
+            })?;
+
+            let (http_headers, json) = split_headers(http)?;
+            let status = http_headers.lines().next()?.split_whitespace().nth(1)?;
+
+            Some((index, status.parse().ok()?, json.trim()))
+        })
+        .collect()
+}
+
+/// Split at the first blank line (CRLF or LF).
+fn split_headers(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start_matches(['\r', '\n']);
+
+    text.split_once("\r\n\r\n")
+        .or_else(|| text.split_once("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_out_of_order_parts_with_statuses() {
+        let body = "--batch_abc\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item1>\r\n\
+            \r\n\
+            HTTP/1.1 404 Not Found\r\n\
+            Content-Type: application/json\r\n\
+            \r\n\
+            {\"error\":{}}\r\n\
+            --batch_abc\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item0>\r\n\
+            \r\n\
+            HTTP/1.1 200 OK\r\n\
+            Content-Type: application/json; charset=UTF-8\r\n\
+            \r\n\
+            {\"id\":\"evt1\"}\r\n\
+            --batch_abc--\r\n";
+
+        let parts = parse_batch_response(body, "batch_abc");
+
+        assert_eq!(
+            parts,
+            vec![(1, 404, "{\"error\":{}}"), (0, 200, "{\"id\":\"evt1\"}")]
+        );
+    }
+
+    #[test]
+    fn batch_body_numbers_items_in_order() {
+        let create = BatchRequest {
+            method: "POST",
+            path: "/calendar/v3/calendars/primary/events".to_string(),
+            body: serde_json::json!({"summary": "A"}),
+        };
+
+        let body = batch_body(&[&create, &create]);
+
+        assert!(body.contains("Content-ID: <item0>"));
+        assert!(body.contains("Content-ID: <item1>"));
+        assert!(body.contains("POST /calendar/v3/calendars/primary/events\r\n"));
+        assert!(body.ends_with("--caldir_batch--\r\n"));
+    }
+
+    #[test]
+    fn recurring_overrides_skip_the_batch() {
+        let mut event = Event::new(
+            "Standup",
+            caldir_core::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()),
+        );
+        event.recurrence_id = Some(caldir_core::RecurrenceId::from_event_time(
+            event.start.clone(),
+        ));
+
+        let request = batch_request(
+            &EventWrite::Create(event),
+            "me@example.com",
+            "/calendar/v3/calendars/primary/events",
+            Notify::None,
+        );
+
+        assert!(request.is_none());
+    }
+
+    #[test]
+    fn overrides_with_their_masters_id_skip_the_batch() {
+        use caldir_core::XProperty;
+
+        let mut event = Event::new(
+            "Standup",
+            caldir_core::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()),
+        );
+        event.recurrence_id = Some(caldir_core::RecurrenceId::from_event_time(
+            event.start.clone(),
+        ));
+        let path = "/calendar/v3/calendars/primary/events";
+
+        event.x_properties = vec![XProperty::new(PROVIDER_EVENT_ID_PROPERTY, "abc123")];
+        let update = EventWrite::Update(event.clone());
+        assert!(batch_request(&update, "me@example.com", path, Notify::None).is_none());
+
+        event.x_properties = vec![XProperty::new(
+            PROVIDER_EVENT_ID_PROPERTY,
+            "abc123_20260302",
+        )];
+        let update = EventWrite::Update(event);
+        let request = batch_request(&update, "me@example.com", path, Notify::None).unwrap();
+        assert_eq!(
+            request.path,
+            format!("{path}/abc123_20260302?sendUpdates=none&conferenceDataVersion=1")
+        );
+    }
+
+    #[test]
+    fn asks_google_to_email_only_external_guests() {
+        let event = Event::new(
+            "Standup",
+            caldir_core::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()),
+        );
+
+        let request = batch_request(
+            &EventWrite::Create(event),
+            "me@example.com",
+            "/calendar/v3/calendars/primary/events",
+            Notify::External,
+        )
+        .unwrap();
+
+        assert!(request.path.contains("sendUpdates=externalOnly"));
+    }
+}
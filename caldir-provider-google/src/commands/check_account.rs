@@ -0,0 +1,22 @@
+use anyhow::Result;
+use caldir_core::provider::ProviderStorage;
+use caldir_core::rpc::{AccountStatus, CheckAccount};
+
+use crate::constants::PROVIDER_NAME;
+use crate::session::SessionStore;
+
+/// The stored OAuth session's expiry. An account without one isn't signed in.
+pub async fn handle(cmd: CheckAccount) -> Result<AccountStatus> {
+    let storage = ProviderStorage::for_provider(PROVIDER_NAME)?;
+    let session_store = SessionStore::new(storage);
+
+    let Ok(session) = session_store.load(&cmd.account_identifier) else {
+        return Ok(AccountStatus::default());
+    };
+
+    Ok(AccountStatus {
+        signed_in: true,
+        expires_at: Some(session.data.expires_at),
+        refreshable: !session.data.refresh_token.is_empty(),
+    })
+}
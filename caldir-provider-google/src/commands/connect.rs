@@ -25,6 +25,10 @@ pub const SCOPES: &[&str] = &[
     "https://www.googleapis.com/auth/calendar.events",
 ];
 
+/// For `caldir schedule`. Asked for on interactive sign-in only, so service
+/// accounts keep working with the scopes their delegation already allows.
+const FREE_BUSY_SCOPE: &str = "https://www.googleapis.com/auth/calendar.freebusy";
+
 pub async fn handle(cmd: Connect) -> Result<ConnectResponse> {
     let storage = ProviderStorage::for_provider(PROVIDER_NAME)?;
     let session_store = SessionStore::new(storage.clone());
@@ -143,6 +147,12 @@ To connect to Google Calendar, you need to create OAuth credentials:\n\
     // Self-hosted path: user has their own OAuth credentials
     let app_config = app_config_store.load()?;
 
+    let scopes: Vec<String> = SCOPES
+        .iter()
+        .chain([&FREE_BUSY_SCOPE])
+        .map(|s| s.to_string())
+        .collect();
+
     let client = Client::new(
         app_config.client_id.clone(),
         app_config.client_secret.clone(),
@@ -151,8 +161,6 @@ To connect to Google Calendar, you need to create OAuth credentials:\n\
         String::new(),
     );
 
-    let scopes: Vec<String> = SCOPES.iter().map(|s| s.to_string()).collect();
-
     let authorization_url = client.user_consent_url(&scopes);
 
     let parsed_url = Url::parse(&authorization_url)?;
@@ -1,17 +1,21 @@
 use anyhow::{Context, Result, anyhow};
+use caldir_core::Event;
 use caldir_core::provider::ProviderStorage;
 use caldir_core::rpc::CreateEvent;
-use caldir_core::{Event, EventTime};
-use google_calendar::types::SendUpdates;
 
 use crate::app_config::AppConfigStore;
+use crate::commands::instance::{instance_id, instance_patch_body};
 use crate::commands::invite::patch_invite_status;
+use crate::commands::update_event::patch_event;
+use crate::commands::{ensure_writable, send_updates};
 use crate::constants::{PROVIDER_EVENT_ID_PROPERTY, PROVIDER_NAME};
 use crate::google_event::{FromGoogle, ToGoogle};
 use crate::remote_config::GoogleRemoteConfig;
 use crate::session::SessionStore;
 
 pub async fn handle(cmd: CreateEvent) -> Result<Event> {
+    ensure_writable(&cmd.event)?;
+
     let config = GoogleRemoteConfig::try_from(&cmd.remote)?;
     let account_email = &config.google_account;
     let calendar_id = &config.google_calendar_id;
@@ -21,14 +25,14 @@ pub async fn handle(cmd: CreateEvent) -> Result<Event> {
     let app_config_store = AppConfigStore::new(storage);
 
     let session = session_store
-        .load_valid(account_email, &app_config_store)
+        .load_for_remote(&config, &app_config_store)
         .await?;
     let client = session_store.client(&session, &app_config_store)?;
 
     // Recurring instance override:
     // Shares the master's iCalUID, so creating via events().insert() trips Google's "duplicate identifier" check.
-    // Google's data model treats an override as a modification of an existing auto-expanded instance.
-    // PUT the synthetic instance id `{master_id}_{rid}` instead.
+    // Google's data model treats an override as a modification of an existing auto-expanded instance,
+    // so find that instance via events.instances and PATCH it.
     if let Some(rid) = cmd.event.recurrence_id.as_ref() {
         let master_id = cmd
             .event
@@ -40,54 +44,31 @@ pub async fn handle(cmd: CreateEvent) -> Result<Event> {
                 )
             })?;
 
-        let instance_id = format!(
-            "{}_{}",
-            master_id,
-            google_instance_suffix(rid.as_event_time())
-        );
+        let instance_id = instance_id(session.access_token(), calendar_id, master_id, rid).await?;
 
-        // If it's just an RSVP status update, use PATCH instead of PUT:
-        if cmd.event.is_invite_for(account_email) {
-            let google_event = patch_invite_status(
+        // If it's just an RSVP status update, only patch our own status
+        let google_event = if cmd.event.is_invite_for(account_email) {
+            patch_invite_status(
                 &session,
                 calendar_id,
                 &instance_id,
                 &cmd.event,
                 account_email,
             )
-            .await?;
-
-            return Event::from_google(google_event);
+            .await?
         } else {
-            let mut google_event = cmd.event.to_google();
-
-            google_event.id = instance_id.clone();
-
-            // Overrides never carry their own RRULE in Google's model.
-            google_event.recurrence = Vec::new();
+            patch_event(
+                session.access_token(),
+                calendar_id,
+                &instance_id,
+                &instance_patch_body(&cmd.event)?,
+                cmd.notify_attendees,
+            )
+            .await
+            .context("Failed to update recurring instance")?
+        };
 
-            let response = client
-                .events()
-                .update(
-                    calendar_id,
-                    &instance_id,
-                    0,
-                    0,
-                    false,
-                    SendUpdates::All,
-                    false,
-                    &google_event,
-                )
-                .await
-                .with_context(|| {
-                    format!(
-                        "Failed to update recurring instance: {}",
-                        google_event.summary
-                    )
-                })?;
-
-            return Event::from_google(response.body);
-        }
+        return Event::from_google(google_event);
     }
 
     // Let google change the ID
@@ -102,7 +83,7 @@ pub async fn handle(cmd: CreateEvent) -> Result<Event> {
             1,
             0,
             false,
-            SendUpdates::All,
+            send_updates(cmd.notify_attendees),
             false,
             &google_event,
         )
@@ -125,7 +106,7 @@ pub async fn handle(cmd: CreateEvent) -> Result<Event> {
                     1,
                     0,
                     false,
-                    SendUpdates::All,
+                    send_updates(cmd.notify_attendees),
                     false,
                     &google_event,
                 )
@@ -149,33 +130,6 @@ fn is_conference_data_error(error: &google_calendar::ClientError) -> bool {
     }
 }
 
-/// Format a `recurrence_id` as the suffix Google appends to a recurring
-/// event's id to identify a single instance:
-/// - all-day:    `YYYYMMDD`
-/// - timed:      `YYYYMMDDTHHMMSSZ` (UTC)
-///
-/// Zoned instances get their wallclock resolved to a UTC instant so the
-/// suffix matches what Google's API auto-expansion uses.
-fn google_instance_suffix(rid: &EventTime) -> String {
-    match rid {
-        EventTime::Date(d) => d.format("%Y%m%d").to_string(),
-        EventTime::DateTimeUtc(dt) => dt.format("%Y%m%dT%H%M%SZ").to_string(),
-        EventTime::DateTimeFloating(dt) => dt.format("%Y%m%dT%H%M%SZ").to_string(),
-        EventTime::DateTimeZoned { datetime, tzid } => {
-            let utc = if let Ok(tz) = tzid.parse::<chrono_tz::Tz>() {
-                datetime
-                    .and_local_timezone(tz)
-                    .single()
-                    .map(|zoned| zoned.with_timezone(&chrono::Utc))
-                    .unwrap_or_else(|| datetime.and_utc())
-            } else {
-                datetime.and_utc()
-            };
-            utc.format("%Y%m%dT%H%M%SZ").to_string()
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,16 +1,16 @@
 use anyhow::{Context, Result};
 use caldir_core::provider::ProviderStorage;
 use caldir_core::rpc::DeleteEvent;
-use google_calendar::types::SendUpdates;
 
 use crate::app_config::AppConfigStore;
+use crate::commands::instance::{instance_id, is_instance_id};
+use crate::commands::send_updates;
 use crate::constants::{PROVIDER_EVENT_ID_PROPERTY, PROVIDER_NAME};
 use crate::remote_config::GoogleRemoteConfig;
 use crate::session::SessionStore;
 
 pub async fn handle(cmd: DeleteEvent) -> Result<()> {
     let config = GoogleRemoteConfig::try_from(&cmd.remote)?;
-    let account_email = &config.google_account;
     let calendar_id = &config.google_calendar_id;
 
     let google_event_id = cmd
@@ -25,13 +25,27 @@ pub async fn handle(cmd: DeleteEvent) -> Result<()> {
     let app_config_store = AppConfigStore::new(storage);
 
     let session = session_store
-        .load_valid(account_email, &app_config_store)
+        .load_for_remote(&config, &app_config_store)
         .await?;
     let client = session_store.client(&session, &app_config_store)?;
 
+    // Deleting an occurrence cancels just that instance. Overrides that
+    // still carry their master's ID must not take the whole series along.
+    let google_event_id = match cmd.event.recurrence_id.as_ref() {
+        Some(rid) if !is_instance_id(google_event_id) => {
+            instance_id(session.access_token(), calendar_id, google_event_id, rid).await?
+        }
+        _ => google_event_id.to_string(),
+    };
+
     client
         .events()
-        .delete(calendar_id, google_event_id, false, SendUpdates::All)
+        .delete(
+            calendar_id,
+            &google_event_id,
+            false,
+            send_updates(cmd.notify_attendees),
+        )
         .await
         .context("Failed to delete event")?;
 
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use caldir_core::provider::ProviderStorage;
+use caldir_core::rpc::Disconnect;
+
+use crate::constants::PROVIDER_NAME;
+use crate::session::SessionStore;
+
+const REVOKE_URL: &str = "https://oauth2.googleapis.com/revoke";
+
+/// Revoke the account's refresh token, which also revokes caldir's access
+/// to it, then remove the stored session.
+pub async fn handle(cmd: Disconnect) -> Result<()> {
+    let storage = ProviderStorage::for_provider(PROVIDER_NAME)?;
+    let session_store = SessionStore::new(storage);
+
+    if let Ok(session) = session_store.load(&cmd.account_identifier) {
+        // Best effort: a token Google already revoked is fine to forget
+        let _ = reqwest::Client::new()
+            .post(REVOKE_URL)
+            .form(&[("token", &session.data.refresh_token)])
+            .send()
+            .await;
+    }
+
+    session_store.delete(&cmd.account_identifier)
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use caldir_core::provider::ProviderStorage;
+use caldir_core::rpc::{BusySchedule, FreeBusy, TimePeriod};
+use serde::Deserialize;
+
+use crate::api_error::GoogleApiError;
+use crate::app_config::AppConfigStore;
+use crate::constants::PROVIDER_NAME;
+use crate::remote_config::GoogleRemoteConfig;
+use crate::session::SessionStore;
+
+const FREE_BUSY_URL: &str = "https://www.googleapis.com/calendar/v3/freeBusy";
+
+#[derive(Deserialize)]
+struct FreeBusyResponse {
+    #[serde(default)]
+    calendars: HashMap<String, CalendarBusy>,
+}
+
+#[derive(Deserialize)]
+struct CalendarBusy {
+    #[serde(default)]
+    busy: Vec<TimePeriod>,
+    #[serde(default)]
+    errors: Vec<CalendarBusyError>,
+}
+
+#[derive(Deserialize)]
+struct CalendarBusyError {
+    reason: String,
+}
+
+/// `freebusy.query`, with each email as a calendar id (a person's primary
+/// calendar).
+pub async fn handle(cmd: FreeBusy) -> Result<Vec<BusySchedule>> {
+    let config = GoogleRemoteConfig::try_from(&cmd.remote)?;
+
+    let storage = ProviderStorage::for_provider(PROVIDER_NAME)?;
+    let session_store = SessionStore::new(storage.clone());
+    let app_config_store = AppConfigStore::new(storage);
+
+    let session = session_store
+        .load_for_remote(&config, &app_config_store)
+        .await?;
+
+    let body = serde_json::json!({
+        "timeMin": cmd.from,
+        "timeMax": cmd.to,
+        "items": cmd.emails.iter().map(|email| serde_json::json!({ "id": email })).collect::<Vec<_>>(),
+    });
+
+    let response = reqwest::Client::new()
+        .post(FREE_BUSY_URL)
+        .bearer_auth(session.access_token())
+        .json(&body)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let error = GoogleApiError::from_response(response).await;
+        return Err(anyhow::Error::new(error).context("Failed to query free/busy"));
+    }
+
+    let mut response: FreeBusyResponse = response.json().await?;
+
+    let schedules = cmd
+        .emails
+        .into_iter()
+        .map(|email| match response.calendars.remove(&email) {
+            Some(calendar) => BusySchedule {
+                error: calendar.errors.first().map(|e| e.reason.clone()),
+                busy: calendar.busy,
+                email,
+            },
+            None => BusySchedule {
+                email,
+                busy: Vec::new(),
+                error: Some("notFound".to_string()),
+            },
+        })
+        .collect();
+
+    Ok(schedules)
+}
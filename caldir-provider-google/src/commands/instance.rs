@@ -0,0 +1,209 @@
+//! Single occurrences of recurring events.
+//!
+//! Google gives each occurrence of a series its own event ID. Editing one
+//! occurrence means patching that instance, whose ID is looked up with
+//! `events.instances` rather than guessed.
+
+use anyhow::{Context, Result};
+use caldir_core::{Event, EventTime, RecurrenceId};
+use chrono::Duration;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::api_error::GoogleApiError;
+use crate::commands::list_events::events_url;
+use crate::google_event::{ToGoogle, google_dt_to_event_time};
+
+/// One page of `events.instances`.
+#[derive(Deserialize)]
+struct InstancesPage {
+    #[serde(default)]
+    items: Vec<google_calendar::types::Event>,
+}
+
+/// Whether `event_id` is an occurrence's (`{master_id}_{suffix}`). Google's
+/// own IDs are base32hex, so they never contain `_`.
+pub(crate) fn is_instance_id(event_id: &str) -> bool {
+    event_id.contains('_')
+}
+
+/// The ID of the occurrence of `master_id` originally starting at
+/// `recurrence_id`, falling back to the ID Google would give it.
+pub(crate) async fn instance_id(
+    access_token: &str,
+    calendar_id: &str,
+    master_id: &str,
+    recurrence_id: &RecurrenceId,
+) -> Result<String> {
+    let found = find_instance(access_token, calendar_id, master_id, recurrence_id).await?;
+
+    Ok(found.unwrap_or_else(|| {
+        format!(
+            "{master_id}_{}",
+            google_instance_suffix(recurrence_id.as_event_time())
+        )
+    }))
+}
+
+async fn find_instance(
+    access_token: &str,
+    calendar_id: &str,
+    master_id: &str,
+    recurrence_id: &RecurrenceId,
+) -> Result<Option<String>> {
+    let mut url = events_url(calendar_id)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Invalid Google Calendar API base URL"))?
+        .push(master_id)
+        .push("instances");
+
+    // A day either side covers all-day occurrences in any timezone
+    let original_start = recurrence_id.as_event_time().to_utc();
+    let query = [
+        ("timeMin", (original_start - Duration::days(1)).to_rfc3339()),
+        ("timeMax", (original_start + Duration::days(2)).to_rfc3339()),
+        ("showDeleted", "true".to_string()),
+    ];
+
+    let response = reqwest::Client::new()
+        .get(url)
+        .bearer_auth(access_token)
+        .query(&query)
+        .send()
+        .await
+        .context("Failed to look up the occurrence")?;
+
+    if !response.status().is_success() {
+        let error = GoogleApiError::from_response(response).await;
+        return Err(anyhow::Error::new(error).context("Failed to look up the occurrence"));
+    }
+
+    let page: InstancesPage = response
+        .json()
+        .await
+        .context("Failed to parse occurrences")?;
+
+    Ok(page
+        .items
+        .into_iter()
+        .find(|instance| starts_originally_at(instance, recurrence_id.as_event_time()))
+        .map(|instance| instance.id))
+}
+
+fn starts_originally_at(instance: &google_calendar::types::Event, original: &EventTime) -> bool {
+    let Some(start) = google_dt_to_event_time(instance.original_start_time.as_ref()) else {
+        return false;
+    };
+
+    match (&start, original) {
+        (EventTime::Date(a), EventTime::Date(b)) => a == b,
+        (EventTime::Date(_), _) | (_, EventTime::Date(_)) => false,
+        _ => start.to_utc() == original.to_utc(),
+    }
+}
+
+/// The body for patching an occurrence with `event`. Unlike the series,
+/// an occurrence's own attendees are sent.
+pub(crate) fn instance_patch_body(event: &Event) -> Result<Value> {
+    let mut body = serde_json::to_value(event.to_google())?;
+
+    if let Value::Object(fields) = &mut body {
+        // Occurrences never carry their own RRULE in Google's model, and
+        // keep the series' ID, iCalUID and type.
+        for field in ["id", "iCalUID", "recurrence", "eventType"] {
+            fields.remove(field);
+        }
+    }
+
+    Ok(body)
+}
+
+/// Format a `recurrence_id` as the suffix Google appends to a recurring
+/// event's id to identify a single instance:
+/// - all-day:    `YYYYMMDD`
+/// - timed:      `YYYYMMDDTHHMMSSZ` (UTC)
+///
+/// Zoned instances get their wallclock resolved to a UTC instant so the
+/// suffix matches what Google's API auto-expansion uses.
+fn google_instance_suffix(rid: &EventTime) -> String {
+    match rid {
+        EventTime::Date(d) => d.format("%Y%m%d").to_string(),
+        EventTime::DateTimeUtc(dt) => dt.format("%Y%m%dT%H%M%SZ").to_string(),
+        EventTime::DateTimeFloating(dt) => dt.format("%Y%m%dT%H%M%SZ").to_string(),
+        EventTime::DateTimeZoned { .. } => rid.to_utc().format("%Y%m%dT%H%M%SZ").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use google_calendar::types as g;
+
+    fn instance(original_start: serde_json::Value) -> g::Event {
+        serde_json::from_value(serde_json::json!({
+            "id": "abc_20260302T090000Z",
+            "originalStartTime": original_start,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn matches_the_occurrence_by_its_original_start() {
+        let zoned = EventTime::DateTimeZoned {
+            datetime: NaiveDate::from_ymd_opt(2026, 3, 2)
+                .unwrap()
+                .and_hms_opt(10, 0, 0)
+                .unwrap(),
+            tzid: "Europe/Oslo".to_string(),
+        };
+        let timed = instance(serde_json::json!({
+            "dateTime": "2026-03-02T09:00:00Z",
+            "timeZone": "Europe/Oslo",
+        }));
+
+        assert!(starts_originally_at(&timed, &zoned));
+        assert!(!starts_originally_at(
+            &timed,
+            &EventTime::Date(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap())
+        ));
+    }
+
+    #[test]
+    fn matches_all_day_occurrences_by_date() {
+        let all_day = instance(serde_json::json!({ "date": "2026-03-02" }));
+
+        assert!(starts_originally_at(
+            &all_day,
+            &EventTime::Date(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap())
+        ));
+    }
+
+    #[test]
+    fn instance_ids_have_a_suffix() {
+        assert!(is_instance_id("abc123_20260302T090000Z"));
+        assert!(!is_instance_id("abc123"));
+        assert_eq!(
+            google_instance_suffix(&EventTime::Date(
+                NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+            )),
+            "20260302"
+        );
+    }
+
+    #[test]
+    fn patch_body_keeps_attendees_but_not_the_rrule() {
+        let mut event = Event::new(
+            "Standup",
+            EventTime::Date(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()),
+        );
+        event.recurrence = Some(caldir_core::Recurrence::new("FREQ=DAILY"));
+        event.attendees = vec![caldir_core::Attendee::new("alice@example.com")];
+
+        let body = instance_patch_body(&event).unwrap();
+
+        assert!(body.get("recurrence").is_none());
+        assert!(body.get("iCalUID").is_none());
+        assert!(body.get("attendees").is_some());
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use caldir_core::Event;
 
+use crate::api_error::GoogleApiError;
 use crate::google_event::to_google::participation_status_to_google;
 use crate::session::Session;
 
@@ -43,8 +44,8 @@ pub(crate) async fn patch_invite_status(
         .await?;
 
     if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-        anyhow::bail!("Error handling request: {}", error_text);
+        let error = GoogleApiError::from_response(response).await;
+        return Err(anyhow::Error::new(error).context("Error handling request"));
     }
 
     Ok(response.json().await?)
@@ -1,11 +1,13 @@
 use std::collections::HashMap;
 
 use anyhow::{Context, Result};
-use caldir_core::provider::ProviderStorage;
+use caldir_core::provider::{EventSink, ProviderStorage};
 use caldir_core::rpc::ListEvents;
 use caldir_core::{Availability, Event, EventTime, EventUid, RecurrenceId, Status, XProperty};
-use google_calendar::types::OrderBy;
+use serde::Deserialize;
+use url::Url;
 
+use crate::api_error::GoogleApiError;
 use crate::app_config::AppConfigStore;
 use crate::constants::{PROVIDER_EVENT_ID_PROPERTY, PROVIDER_NAME};
 use crate::google_event::{FromGoogle, google_dt_to_event_time};
@@ -13,8 +15,18 @@ use crate::remote_config::GoogleRemoteConfig;
 use crate::session::SessionStore;
 
 pub async fn handle(cmd: ListEvents) -> Result<Vec<Event>> {
+    let mut events = Vec::new();
+    fetch_events(cmd, |batch| events.extend(batch)).await?;
+    Ok(events)
+}
+
+/// Send each page to the CLI as soon as it's fetched.
+pub async fn stream(cmd: ListEvents, sink: &EventSink) -> Result<()> {
+    fetch_events(cmd, |batch| sink.send(batch)).await
+}
+
+async fn fetch_events(cmd: ListEvents, mut on_events: impl FnMut(Vec<Event>)) -> Result<()> {
     let config = GoogleRemoteConfig::try_from(&cmd.remote)?;
-    let account_email = &config.google_account;
     let calendar_id = &config.google_calendar_id;
 
     let storage = ProviderStorage::for_provider(PROVIDER_NAME)?;
@@ -22,37 +34,108 @@ pub async fn handle(cmd: ListEvents) -> Result<Vec<Event>> {
     let app_config_store = AppConfigStore::new(storage);
 
     let session = session_store
-        .load_valid(account_email, &app_config_store)
+        .load_for_remote(&config, &app_config_store)
         .await?;
-    let client = session_store.client(&session, &app_config_store)?;
-
-    let google_events = client
-        .events()
-        .list_all(
-            calendar_id,
-            "",
-            0,
-            OrderBy::default(),
-            &[],
-            "", // search query
-            &[],
-            true, // show_deleted: include cancelled events so they surface as
-            // STATUS:CANCELLED locally rather than disappearing silently.
-            false,
-            false,
-            &cmd.to,
-            &cmd.from,
-            "",
-            "",
+
+    let http = reqwest::Client::new();
+    let url = events_url(calendar_id)?;
+
+    let mut converter = EventConverter::default();
+    let mut page_token: Option<String> = None;
+
+    loop {
+        let page = fetch_page(
+            &http,
+            &url,
+            session.access_token(),
+            &cmd,
+            config.page_size(),
+            page_token.as_deref(),
         )
+        .await?;
+
+        on_events(converter.convert_page(page.items)?);
+
+        match page.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    on_events(converter.finish());
+
+    Ok(())
+}
+
+/// One page of `events.list`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventsPage {
+    #[serde(default)]
+    items: Vec<google_calendar::types::Event>,
+    next_page_token: Option<String>,
+}
+
+/// `calendars/{calendar_id}/events`, with the id escaped (ids contain `@` and `#`).
+pub(crate) fn events_url(calendar_id: &str) -> Result<Url> {
+    let mut url = Url::parse("https://www.googleapis.com/calendar/v3/calendars")?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Invalid Google Calendar API base URL"))?
+        .push(calendar_id)
+        .push("events");
+    Ok(url)
+}
+
+async fn fetch_page(
+    http: &reqwest::Client,
+    url: &Url,
+    access_token: &str,
+    cmd: &ListEvents,
+    page_size: u32,
+    page_token: Option<&str>,
+) -> Result<EventsPage> {
+    let mut query = vec![
+        ("maxResults", page_size.to_string()),
+        // Include cancelled events so they surface as STATUS:CANCELLED
+        // locally rather than disappearing silently.
+        ("showDeleted", "true".to_string()),
+    ];
+    if !cmd.from.is_empty() {
+        query.push(("timeMin", cmd.from.clone()));
+    }
+    if !cmd.to.is_empty() {
+        query.push(("timeMax", cmd.to.clone()));
+    }
+    if let Some(token) = page_token {
+        query.push(("pageToken", token.to_string()));
+    }
+
+    let response = http
+        .get(url.clone())
+        .bearer_auth(access_token)
+        .query(&query)
+        .send()
         .await
-        .context("Failed to fetch events")?
-        .body;
+        .context("Failed to fetch events")?;
+
+    if !response.status().is_success() {
+        let error = GoogleApiError::from_response(response).await;
+        return Err(anyhow::Error::new(error).context("Failed to fetch events"));
+    }
+
+    response.json().await.context("Failed to parse events page")
+}
 
-    process_google_events(google_events)
+/// Convert a whole events list at once.
+#[cfg(test)]
+fn process_google_events(google_events: Vec<google_calendar::types::Event>) -> Result<Vec<Event>> {
+    let mut converter = EventConverter::default();
+    let mut events = converter.convert_page(google_events)?;
+    events.extend(converter.finish());
+    Ok(events)
 }
 
-/// Convert Google's raw events list into caldir Events.
+/// Converts Google's raw events into caldir Events, one page at a time.
 ///
 /// Google returns cancelled instances of recurring events as bare tombstones:
 /// status="cancelled" with no start/end, but with recurringEventId +
@@ -62,38 +145,51 @@ pub async fn handle(cmd: ListEvents) -> Result<Vec<Event>> {
 ///
 /// Standalone deleted events arrive as tombstones too (no recurringEventId);
 /// those are dropped — there's nothing local to attach them to.
-fn process_google_events(google_events: Vec<google_calendar::types::Event>) -> Result<Vec<Event>> {
-    let mut master_info_by_google_id: HashMap<String, (String, String)> = HashMap::new();
-    let mut cancellations: Vec<google_calendar::types::Event> = Vec::new();
-    let mut events: Vec<Event> = Vec::with_capacity(google_events.len());
-
-    for ge in google_events {
-        let has_start = ge
-            .start
-            .as_ref()
-            .is_some_and(|s| s.date_time.is_some() || s.date.is_some());
-
-        if !has_start && ge.status == "cancelled" {
-            if !ge.recurring_event_id.is_empty() {
-                cancellations.push(ge);
+///
+/// Cancellations are held back until `finish`, since their master may arrive
+/// on a later page.
+#[derive(Default)]
+struct EventConverter {
+    master_info_by_google_id: HashMap<String, (String, String)>,
+    cancellations: Vec<google_calendar::types::Event>,
+}
+
+impl EventConverter {
+    fn convert_page(
+        &mut self,
+        google_events: Vec<google_calendar::types::Event>,
+    ) -> Result<Vec<Event>> {
+        let mut events: Vec<Event> = Vec::with_capacity(google_events.len());
+
+        for ge in google_events {
+            let has_start = ge
+                .start
+                .as_ref()
+                .is_some_and(|s| s.date_time.is_some() || s.date.is_some());
+
+            if !has_start && ge.status == "cancelled" {
+                if !ge.recurring_event_id.is_empty() {
+                    self.cancellations.push(ge);
+                }
+                continue;
             }
-            continue;
-        }
 
-        if !ge.i_cal_uid.is_empty() {
-            master_info_by_google_id
-                .insert(ge.id.clone(), (ge.i_cal_uid.clone(), ge.summary.clone()));
+            if !ge.i_cal_uid.is_empty() {
+                self.master_info_by_google_id
+                    .insert(ge.id.clone(), (ge.i_cal_uid.clone(), ge.summary.clone()));
+            }
+            events.push(Event::from_google(ge)?);
         }
-        events.push(Event::from_google(ge)?);
-    }
 
-    for ge in cancellations {
-        if let Some(event) = cancellation_to_event(&ge, &master_info_by_google_id) {
-            events.push(event);
-        }
+        Ok(events)
     }
 
-    Ok(events)
+    fn finish(self) -> Vec<Event> {
+        self.cancellations
+            .iter()
+            .filter_map(|ge| cancellation_to_event(ge, &self.master_info_by_google_id))
+            .collect()
+    }
 }
 
 fn cancellation_to_event(
@@ -136,6 +232,7 @@ fn cancellation_to_event(
         status: Status::Cancelled,
         availability: Availability::Busy,
         visibility: Default::default(),
+        event_type: Default::default(),
         recurrence: None,
         recurrence_id: Some(RecurrenceId::from_event_time(recurrence_id)),
         last_modified: ge.updated,
@@ -143,7 +240,11 @@ fn cancellation_to_event(
         organizer: None,
         attendees: Vec::new(),
         reminders: Vec::new(),
+        reminders_default: false,
         url: None,
+        categories: Vec::new(),
+        color: None,
+        conferences: Vec::new(),
         attachments: Vec::new(),
         x_properties: vec![XProperty::new(PROVIDER_EVENT_ID_PROPERTY, &ge.id)],
     })
@@ -162,6 +263,33 @@ mod tests {
         serde_json::from_value(serde_json::json!({})).unwrap()
     }
 
+    #[test]
+    fn events_url_escapes_calendar_id() {
+        let url = events_url("en.usa#holiday@group.v.calendar.google.com").unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://www.googleapis.com/calendar/v3/calendars/\
+             en.usa%23holiday@group.v.calendar.google.com/events"
+        );
+    }
+
+    #[test]
+    fn events_page_reads_next_page_token() {
+        let page: EventsPage = serde_json::from_value(serde_json::json!({
+            "items": [{ "id": "abc" }],
+            "nextPageToken": "page-2",
+        }))
+        .unwrap();
+
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.next_page_token.as_deref(), Some("page-2"));
+
+        let last: EventsPage = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(last.items.is_empty());
+        assert!(last.next_page_token.is_none());
+    }
+
     fn master(id: &str, uid: &str, summary: &str) -> g::Event {
         g::Event {
             id: id.into(),
@@ -238,6 +366,28 @@ mod tests {
         assert_eq!(result[0].status, Status::Cancelled);
     }
 
+    #[test]
+    fn cancellation_waits_for_master_on_later_page() {
+        let mut converter = EventConverter::default();
+
+        let first = converter
+            .convert_page(vec![cancelled_instance(
+                "master_id_20260213T150000Z",
+                "master_id",
+                "2026-02-13T15:00:00Z",
+            )])
+            .unwrap();
+        let second = converter
+            .convert_page(vec![master("master_id", "uid@google.com", "Weekly retro")])
+            .unwrap();
+        let cancellations = converter.finish();
+
+        assert!(first.is_empty());
+        assert_eq!(second.len(), 1);
+        assert_eq!(cancellations.len(), 1);
+        assert_eq!(cancellations[0].uid.as_str(), "uid@google.com");
+    }
+
     #[test]
     fn standalone_cancelled_tombstone_is_dropped() {
         // Status=cancelled, no recurringEventId — a deleted standalone event.
@@ -1,7 +1,46 @@
+pub mod batch_write;
+pub mod check_account;
 pub mod connect;
 pub mod create_event;
 pub mod delete_event;
+pub mod disconnect;
+pub mod free_busy;
+pub(crate) mod instance;
 pub(crate) mod invite;
 pub mod list_calendars;
 pub mod list_events;
 pub mod update_event;
+
+use anyhow::{Result, bail};
+use caldir_core::rpc::Notify;
+use caldir_core::{Event, EventType};
+use google_calendar::types::SendUpdates;
+
+/// Google only lets working location events be set up in its own apps.
+pub(crate) fn ensure_writable(event: &Event) -> Result<()> {
+    if event.event_type == EventType::WorkingLocation {
+        bail!(
+            "Working location events can't be created or changed through the API; edit them in Google Calendar"
+        );
+    }
+
+    Ok(())
+}
+
+/// `sendUpdates` for a write: email attendees only when the user asked to.
+pub(crate) fn send_updates(notify: Notify) -> SendUpdates {
+    match notify {
+        Notify::All => SendUpdates::All,
+        Notify::External => SendUpdates::ExternalOnly,
+        Notify::None => SendUpdates::None,
+    }
+}
+
+/// [`send_updates`] as the query value, for requests made without the client.
+pub(crate) fn send_updates_param(notify: Notify) -> &'static str {
+    match notify {
+        Notify::All => "all",
+        Notify::External => "externalOnly",
+        Notify::None => "none",
+    }
+}
@@ -1,17 +1,23 @@
-use anyhow::{Result, anyhow, bail};
+use anyhow::{Result, anyhow};
 use caldir_core::Event;
 use caldir_core::provider::ProviderStorage;
-use caldir_core::rpc::UpdateEvent;
+use caldir_core::rpc::{Notify, UpdateEvent};
 use serde_json::Value;
 
+use crate::api_error::GoogleApiError;
 use crate::app_config::AppConfigStore;
+use crate::commands::instance::{instance_id, instance_patch_body, is_instance_id};
 use crate::commands::invite::patch_invite_status;
+use crate::commands::{ensure_writable, send_updates_param};
 use crate::constants::{PROVIDER_EVENT_ID_PROPERTY, PROVIDER_NAME};
+use crate::google_event::event_type::unpatchable_fields;
 use crate::google_event::{FromGoogle, ToGoogle};
 use crate::remote_config::GoogleRemoteConfig;
 use crate::session::SessionStore;
 
 pub async fn handle(cmd: UpdateEvent) -> Result<Event> {
+    ensure_writable(&cmd.event)?;
+
     let config = GoogleRemoteConfig::try_from(&cmd.remote)?;
     let account_email = &config.google_account;
     let calendar_id = &config.google_calendar_id;
@@ -21,7 +27,7 @@ pub async fn handle(cmd: UpdateEvent) -> Result<Event> {
     let app_config_store = AppConfigStore::new(storage);
 
     let session = session_store
-        .load_valid(account_email, &app_config_store)
+        .load_for_remote(&config, &app_config_store)
         .await?;
 
     // Get Google's event ID from custom properties
@@ -41,16 +47,37 @@ pub async fn handle(cmd: UpdateEvent) -> Result<Event> {
         )
         .await?;
 
+        Ok(Event::from_google(google_event)?)
+    } else if let Some(rid) = cmd.event.recurrence_id.as_ref() {
+        // A single occurrence: patch that instance, attendees included.
+        // Overrides pushed before their instance ID was known carry the
+        // master's ID, so look the instance up.
+        let instance_id = if is_instance_id(google_event_id) {
+            google_event_id.to_string()
+        } else {
+            instance_id(session.access_token(), calendar_id, google_event_id, rid).await?
+        };
+
+        let google_event = patch_event(
+            session.access_token(),
+            calendar_id,
+            &instance_id,
+            &instance_patch_body(&cmd.event)?,
+            cmd.notify_attendees,
+        )
+        .await?;
+
         Ok(Event::from_google(google_event)?)
     } else {
         // Organizer or own event: PATCH event fields, but never send attendees.
         // A single EXDATE edit is a master update; sending a full attendee list
         // here can rewrite invite state across the whole series.
-        let google_event = patch_event_without_attendees(
+        let google_event = patch_event(
             session.access_token(),
             calendar_id,
             google_event_id,
-            &cmd.event,
+            &patch_body_without_attendees(&cmd.event)?,
+            cmd.notify_attendees,
         )
         .await?;
 
@@ -58,40 +85,46 @@ pub async fn handle(cmd: UpdateEvent) -> Result<Event> {
     }
 }
 
-async fn patch_event_without_attendees(
+pub(crate) async fn patch_event(
     access_token: &str,
     calendar_id: &str,
     event_id: &str,
-    event: &Event,
+    body: &Value,
+    notify_attendees: Notify,
 ) -> Result<google_calendar::types::Event> {
-    let body = patch_body_without_attendees(event)?;
-
     let url = format!(
         "https://www.googleapis.com/calendar/v3/calendars/{}/events/{}?\
-         sendUpdates=all&conferenceDataVersion=1",
-        calendar_id, event_id,
+         sendUpdates={}&conferenceDataVersion=1",
+        calendar_id,
+        event_id,
+        send_updates_param(notify_attendees),
     );
 
     let response = reqwest::Client::new()
         .patch(&url)
         .bearer_auth(access_token)
-        .json(&body)
+        .json(body)
         .send()
         .await?;
 
     if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-        bail!("Error handling request: {}", error_text);
+        let error = GoogleApiError::from_response(response).await;
+        return Err(anyhow::Error::new(error).context("Error handling request"));
     }
 
     Ok(response.json().await?)
 }
 
-fn patch_body_without_attendees(event: &Event) -> Result<Value> {
+pub(crate) fn patch_body_without_attendees(event: &Event) -> Result<Value> {
     let mut body = serde_json::to_value(event.to_google())?;
 
     if let Value::Object(fields) = &mut body {
         fields.remove("attendees");
+        // An event's type can't change once it's created
+        fields.remove("eventType");
+        for field in unpatchable_fields(event.event_type) {
+            fields.remove(*field);
+        }
     }
 
     Ok(body)
@@ -100,7 +133,7 @@ fn patch_body_without_attendees(event: &Event) -> Result<Value> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use caldir_core::{Attendee, EventTime, Recurrence};
+    use caldir_core::{Attendee, EventTime, EventType, Recurrence};
     use chrono::NaiveDate;
 
     #[test]
@@ -122,4 +155,23 @@ mod tests {
         );
         assert!(body.get("recurrence").is_some());
     }
+
+    #[test]
+    fn patch_body_leaves_out_of_office_settings_alone() {
+        let mut event = Event::new(
+            "Vacation",
+            EventTime::Date(NaiveDate::from_ymd_opt(2026, 7, 10).unwrap()),
+        );
+        event.event_type = EventType::OutOfOffice;
+
+        let body = patch_body_without_attendees(&event).unwrap();
+
+        assert!(body.get("eventType").is_none());
+        assert!(body.get("reminders").is_none());
+        assert!(body.get("transparency").is_none());
+        assert_eq!(
+            body.get("summary").and_then(Value::as_str),
+            Some("Vacation")
+        );
+    }
 }
@@ -0,0 +1,55 @@
+//! Mapping between Google's fixed event palette (`colorId` "1"–"11") and
+//! the CSS3 color names RFC 7986 expects in `COLOR`.
+
+/// (Google colorId, CSS3 name closest to Google's swatch)
+const PALETTE: [(&str, &str); 11] = [
+    ("1", "lavender"),         // Lavender
+    ("2", "mediumaquamarine"), // Sage
+    ("3", "plum"),             // Grape
+    ("4", "salmon"),           // Flamingo
+    ("5", "gold"),             // Banana
+    ("6", "sandybrown"),       // Tangerine
+    ("7", "turquoise"),        // Peacock
+    ("8", "gray"),             // Graphite
+    ("9", "royalblue"),        // Blueberry
+    ("10", "mediumseagreen"),  // Basil
+    ("11", "tomato"),          // Tomato
+];
+
+pub fn color_name(color_id: &str) -> Option<&'static str> {
+    PALETTE
+        .iter()
+        .find(|(id, _)| *id == color_id)
+        .map(|(_, name)| *name)
+}
+
+/// Colors outside Google's palette have no colorId.
+pub fn color_id(color_name: &str) -> Option<&'static str> {
+    PALETTE
+        .iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case(color_name))
+        .map(|(id, _)| *id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_color_id_round_trips() {
+        for (id, _) in PALETTE {
+            assert_eq!(color_id(color_name(id).unwrap()), Some(id));
+        }
+    }
+
+    #[test]
+    fn unknown_values_map_to_none() {
+        assert_eq!(color_name("42"), None);
+        assert_eq!(color_id("chartreuse"), None);
+    }
+
+    #[test]
+    fn color_id_is_case_insensitive() {
+        assert_eq!(color_id("Tomato"), Some("11"));
+    }
+}
@@ -0,0 +1,50 @@
+//! Mapping between Google's `eventType` and caldir's [`EventType`].
+//!
+//! Types caldir doesn't know (e.g. `birthday`, `fromGmail`) are read as
+//! regular events.
+
+use caldir_core::EventType;
+
+pub fn event_type(google_event_type: &str) -> EventType {
+    match google_event_type {
+        "outOfOffice" => EventType::OutOfOffice,
+        "focusTime" => EventType::FocusTime,
+        "workingLocation" => EventType::WorkingLocation,
+        _ => EventType::Default,
+    }
+}
+
+/// Empty for regular events, so the field is left out of requests.
+pub fn google_event_type(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::Default => "",
+        EventType::OutOfOffice => "outOfOffice",
+        EventType::FocusTime => "focusTime",
+        EventType::WorkingLocation => "workingLocation",
+    }
+}
+
+/// Fields Google rejects, or that caldir would clobber, when patching an
+/// event of this type. Out-of-office and focus-time events have no
+/// reminders, conference or free/busy setting of their own.
+pub fn unpatchable_fields(event_type: EventType) -> &'static [&'static str] {
+    match event_type {
+        EventType::Default => &[],
+        EventType::OutOfOffice | EventType::FocusTime | EventType::WorkingLocation => {
+            &["reminders", "conferenceData", "transparency", "visibility"]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_types_are_regular_events() {
+        assert_eq!(event_type("outOfOffice"), EventType::OutOfOffice);
+        assert_eq!(event_type("birthday"), EventType::Default);
+        assert_eq!(event_type(""), EventType::Default);
+        assert_eq!(google_event_type(EventType::FocusTime), "focusTime");
+    }
+}
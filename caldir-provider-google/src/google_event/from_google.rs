@@ -1,9 +1,11 @@
 use anyhow::Result;
 use caldir_core::{
-    Attendee, Availability, Event, EventTime, EventUid, Organizer, ParticipationStatus, Recurrence,
-    RecurrenceId, Reminder, Status, Visibility, XProperty,
+    Attendee, Availability, Conference, Event, EventTime, EventUid, Organizer, ParticipationStatus,
+    Recurrence, RecurrenceId, Reminder, Status, Visibility, XProperty,
 };
 
+use super::color::color_name;
+use super::event_type::event_type;
 use crate::constants::{PROVIDER_COLOR_ID_PROPERTY, PROVIDER_EVENT_ID_PROPERTY};
 
 pub trait FromGoogle {
@@ -33,9 +35,9 @@ impl FromGoogle for Event {
             .map(RecurrenceId::from_event_time);
 
         // Note: when `reminders.useDefault: true` and overrides is empty, we
-        // intentionally leave reminders empty here — the local file has no
-        // VALARM, and `to_google` reads "no VALARM" as "inherit Google's
-        // calendar default reminders" on push.
+        // intentionally leave reminders empty here and set `reminders_default`
+        // instead — `to_google` reads it as "inherit Google's calendar default
+        // reminders" on push.
         let reminders: Vec<Reminder> = if let Some(ref rem) = event.reminders {
             rem.overrides
                 .iter()
@@ -46,6 +48,7 @@ impl FromGoogle for Event {
         } else {
             Vec::new()
         };
+        let reminders_default = event.reminders.as_ref().is_some_and(|rem| rem.use_default);
 
         let availability = if event.transparency == "transparent" {
             Availability::Free
@@ -85,6 +88,12 @@ impl FromGoogle for Event {
             })
             .collect();
 
+        let conferences = event
+            .conference_data
+            .as_ref()
+            .map(conferences_from_google)
+            .unwrap_or_default();
+
         let conference_url = event.conference_data.as_ref().and_then(|cd| {
             cd.entry_points
                 .iter()
@@ -98,6 +107,8 @@ impl FromGoogle for Event {
         if let Some(ref url) = conference_url {
             x_properties.push(XProperty::new("X-GOOGLE-CONFERENCE", url));
         }
+        let event_type = event_type(&event.event_type);
+        let color = color_name(&event.color_id).map(ToString::to_string);
         if !event.color_id.is_empty() {
             x_properties.push(XProperty::new(PROVIDER_COLOR_ID_PROPERTY, event.color_id));
         }
@@ -124,6 +135,7 @@ impl FromGoogle for Event {
             status,
             availability,
             visibility,
+            event_type,
             recurrence,
             recurrence_id,
             last_modified: event.updated,
@@ -131,15 +143,50 @@ impl FromGoogle for Event {
             organizer,
             attendees,
             reminders,
-            // Also mirrored in X-GOOGLE-CONFERENCE — kept here so local files
-            // round-trip stably (Google's API has no writable URL field).
-            url: conference_url,
+            reminders_default,
+            // Google's API has no writable URL field; join links live in
+            // `conferences` instead.
+            url: None,
+            // Google has no native tags; categories are local-only.
+            categories: Vec::new(),
+            color,
+            conferences,
             attachments: Vec::new(),
             x_properties,
         })
     }
 }
 
+/// Map Google's conference entry points to RFC 7986 CONFERENCE entries.
+fn conferences_from_google(data: &google_calendar::types::ConferenceData) -> Vec<Conference> {
+    let solution_name = data
+        .conference_solution
+        .as_ref()
+        .map(|s| s.name.as_str())
+        .filter(|n| !n.is_empty());
+
+    data.entry_points
+        .iter()
+        .filter(|ep| !ep.uri.is_empty())
+        .map(|ep| {
+            let mut conference = Conference::new(&ep.uri);
+            match ep.entry_point_type.as_str() {
+                "video" => conference = conference.with_feature("AUDIO").with_feature("VIDEO"),
+                "phone" => conference = conference.with_feature("PHONE"),
+                "sip" => conference = conference.with_feature("AUDIO"),
+                _ => {}
+            }
+            let label = Some(ep.label.as_str())
+                .filter(|l| !l.is_empty())
+                .or(solution_name.filter(|_| ep.entry_point_type == "video"));
+            if let Some(label) = label {
+                conference = conference.with_label(label);
+            }
+            conference
+        })
+        .collect()
+}
+
 /// Parse Google's recurrence Vec<String> into a typed Recurrence.
 ///
 /// Google returns entries like:
@@ -254,6 +301,8 @@ fn google_to_participation_status(google_status: &str) -> Option<ParticipationSt
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::google_event::ToGoogle;
+    use caldir_core::EventType;
     use google_calendar::types as g;
 
     fn empty_event() -> g::Event {
@@ -282,6 +331,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keeps_the_event_type() {
+        let mut ge = minimal_event();
+        ge.event_type = "focusTime".into();
+
+        let event = Event::from_google(ge).unwrap();
+
+        assert_eq!(event.event_type, EventType::FocusTime);
+        assert_eq!(event.to_google().event_type, "focusTime");
+    }
+
+    #[test]
+    fn color_id_maps_to_css_color_name() {
+        let mut ge = minimal_event();
+        ge.color_id = "11".into();
+
+        let event = Event::from_google(ge).unwrap();
+
+        assert_eq!(event.color.as_deref(), Some("tomato"));
+        assert_eq!(event.x_property(PROVIDER_COLOR_ID_PROPERTY), Some("11"));
+    }
+
+    #[test]
+    fn missing_color_id_leaves_color_unset() {
+        let event = Event::from_google(minimal_event()).unwrap();
+
+        assert_eq!(event.color, None);
+    }
+
     #[test]
     fn confirmed_status_maps_to_confirmed() {
         let mut ge = minimal_event();
@@ -400,9 +478,7 @@ mod tests {
     }
 
     #[test]
-    fn conference_url_populates_url_and_x_google_conference() {
-        // Google has no writable URL field, so we mirror the conference URL
-        // into Event.url for round-trip stability with legacy local files.
+    fn conference_data_populates_conferences_and_x_google_conference() {
         let mut ge = minimal_event();
         ge.conference_data = Some(
             serde_json::from_value(serde_json::json!({
@@ -415,8 +491,9 @@ mod tests {
 
         let event = Event::from_google(ge).unwrap();
 
+        assert_eq!(event.url, None);
         assert_eq!(
-            event.url.as_deref(),
+            event.conference_url(),
             Some("https://meet.google.com/abc-def-ghi")
         );
         assert_eq!(
@@ -426,18 +503,43 @@ mod tests {
     }
 
     #[test]
-    fn no_conference_data_leaves_url_none() {
+    fn phone_entry_points_become_phone_conferences() {
+        let mut ge = minimal_event();
+        ge.conference_data = Some(
+            serde_json::from_value(serde_json::json!({
+                "conferenceSolution": {"name": "Google Meet"},
+                "entryPoints": [
+                    {"entryPointType": "video", "uri": "https://meet.google.com/abc-def-ghi"},
+                    {"entryPointType": "phone", "uri": "tel:+1-555-0100", "label": "+1 555-0100"}
+                ]
+            }))
+            .unwrap(),
+        );
+
+        let event = Event::from_google(ge).unwrap();
+
+        assert_eq!(event.conferences.len(), 2);
+        assert_eq!(event.conferences[0].label.as_deref(), Some("Google Meet"));
+        assert!(event.conferences[0].has_feature("VIDEO"));
+        assert_eq!(event.conferences[1].uri, "tel:+1-555-0100");
+        assert!(event.conferences[1].has_feature("PHONE"));
+        assert_eq!(event.conferences[1].label.as_deref(), Some("+1 555-0100"));
+    }
+
+    #[test]
+    fn no_conference_data_leaves_url_and_conferences_empty() {
         let event = Event::from_google(minimal_event()).unwrap();
 
         assert_eq!(event.url, None);
+        assert!(event.conferences.is_empty());
     }
 
     // `useDefault: true` means "inherit the calendar's default reminders". We
     // deliberately don't expand those into explicit VALARMs locally: round-
     // tripping back via `to_google` would then send them as overrides and
     // pin the event to today's defaults, even if the calendar default later
-    // changes. The empty-reminders state on disk is what makes the next push
-    // emit `useDefault: true` again.
+    // changes. `reminders_default` is what makes the next push emit
+    // `useDefault: true` again.
     #[test]
     fn use_default_reminders_produces_empty_reminders() {
         let mut ge = minimal_event();
@@ -449,6 +551,8 @@ mod tests {
         let event = Event::from_google(ge).unwrap();
 
         assert!(event.reminders.is_empty());
+        assert!(event.reminders_default);
+        assert!(event.to_google().reminders.unwrap().use_default);
     }
 
     #[test]
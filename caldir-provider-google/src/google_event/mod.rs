@@ -1,3 +1,5 @@
+pub mod color;
+pub mod event_type;
 pub mod from_google;
 pub mod to_google;
 
@@ -3,6 +3,8 @@ use caldir_core::{
     Status, Visibility,
 };
 
+use super::color;
+use super::event_type::google_event_type;
 use crate::constants::{PROVIDER_COLOR_ID_PROPERTY, PROVIDER_EVENT_ID_PROPERTY};
 
 pub trait ToGoogle {
@@ -47,9 +49,12 @@ impl ToGoogle for Event {
             })
             .collect();
 
-        // "No VALARM locally" = "inherit Google's calendar defaults"
-        // Sending `None` here would otherwise clear the calendar-level default
-        let reminders = if valid_reminders.is_empty() {
+        // `reminders_default` = "inherit Google's calendar defaults". Without
+        // it, no VALARMs locally means no reminders at all. Reminders that
+        // were all stripped above fall back to the defaults rather than none.
+        let use_default =
+            self.reminders_default || (valid_reminders.is_empty() && !self.reminders.is_empty());
+        let reminders = if use_default {
             Some(google_calendar::types::Reminders {
                 overrides: vec![],
                 use_default: true,
@@ -81,12 +86,21 @@ impl ToGoogle for Event {
             .unwrap_or_default()
             .to_string();
 
+        // A local COLOR edit wins; otherwise keep whatever colorId we pulled.
         let color_id = self
-            .x_property(PROVIDER_COLOR_ID_PROPERTY)
+            .color
+            .as_deref()
+            .and_then(color::color_id)
+            .or_else(|| self.x_property(PROVIDER_COLOR_ID_PROPERTY))
             .unwrap_or_default()
             .to_string();
 
-        let conference_data = self.x_property("X-GOOGLE-CONFERENCE").and_then(|url| {
+        // X-GOOGLE-CONFERENCE (possibly empty, to request a new Meet) wins;
+        // otherwise a Meet link added as CONFERENCE is attached as-is.
+        let conference_url = self
+            .x_property("X-GOOGLE-CONFERENCE")
+            .or_else(|| self.conference_url());
+        let conference_data = conference_url.and_then(|url| {
             google_meet_conference_data(url, &format!("{}-{}", self.uid.as_str(), self.sequence))
         });
 
@@ -101,6 +115,7 @@ impl ToGoogle for Event {
             status,
             transparency,
             visibility,
+            event_type: google_event_type(self.event_type).to_string(),
             reminders,
             attendees,
             recurrence,
@@ -212,23 +227,12 @@ fn event_time_to_google(time: &EventTime) -> google_calendar::types::EventDateTi
             date_time: Some(dt.and_utc()),
             time_zone: String::new(),
         },
-        EventTime::DateTimeZoned { datetime, tzid } => {
-            // Convert wall clock time in the given timezone to actual UTC instant
-            let utc_dt = if let Ok(tz) = tzid.parse::<chrono_tz::Tz>() {
-                datetime
-                    .and_local_timezone(tz)
-                    .single()
-                    .map(|zoned| zoned.with_timezone(&chrono::Utc))
-                    .unwrap_or_else(|| datetime.and_utc())
-            } else {
-                datetime.and_utc()
-            };
-            google_calendar::types::EventDateTime {
-                date: None,
-                date_time: Some(utc_dt),
-                time_zone: tzid.clone(),
-            }
-        }
+        EventTime::DateTimeZoned { tzid, .. } => google_calendar::types::EventDateTime {
+            date: None,
+            // The wall clock's instant in `tzid`, even around DST changes
+            date_time: Some(time.to_utc()),
+            time_zone: tzid.clone(),
+        },
     }
 }
 
@@ -280,6 +284,24 @@ mod tests {
         )
     }
 
+    #[test]
+    fn local_color_takes_precedence_over_pulled_color_id() {
+        let mut event = sample_event();
+        event.color = Some("royalblue".to_string());
+        event.x_properties = vec![XProperty::new(PROVIDER_COLOR_ID_PROPERTY, "11")];
+
+        assert_eq!(event.to_google().color_id, "9");
+    }
+
+    #[test]
+    fn color_outside_palette_keeps_pulled_color_id() {
+        let mut event = sample_event();
+        event.color = Some("chartreuse".to_string());
+        event.x_properties = vec![XProperty::new(PROVIDER_COLOR_ID_PROPERTY, "11")];
+
+        assert_eq!(event.to_google().color_id, "11");
+    }
+
     // Google's API rejects reminder overrides with `minutes: 0` ("Missing
     // override reminder minutes"), because the `google-calendar` crate strips
     // zero-valued integers from the serialized JSON. A reminder that fires at
@@ -340,17 +362,32 @@ mod tests {
         assert!(!reminders.use_default);
     }
 
-    // Local files without VALARMs must push as `useDefault: true` so that
-    // we don't silently strip Google's calendar-level default reminders on push.
+    // Events created without reminders (e.g. by `caldir new`) must push as
+    // `useDefault: true` so that we don't strip Google's calendar-level defaults.
     #[test]
     fn empty_reminders_sends_use_default_true() {
+        let mut event = sample_event();
+        event.reminders_default = true;
+
+        let google = event.to_google();
+        let reminders = google.reminders.expect("reminders always set");
+
+        assert!(reminders.use_default);
+        assert!(reminders.overrides.is_empty());
+    }
+
+    // Removing every reminder from an event has to turn them off on Google,
+    // not bring back the calendar's defaults.
+    #[test]
+    fn empty_reminders_without_default_sends_no_reminders() {
         let event = sample_event();
         assert!(event.reminders.is_empty());
+        assert!(!event.reminders_default);
 
         let google = event.to_google();
         let reminders = google.reminders.expect("reminders always set");
 
-        assert!(reminders.use_default);
+        assert!(!reminders.use_default);
         assert!(reminders.overrides.is_empty());
     }
 
@@ -431,6 +468,20 @@ mod tests {
         assert!(event.to_google().conference_data.is_none());
     }
 
+    #[test]
+    fn google_meet_conference_populates_conference_data() {
+        let mut event = sample_event();
+        event.conferences = vec![
+            caldir_core::Conference::new("https://meet.google.com/abc-defg-hij")
+                .with_feature("VIDEO"),
+        ];
+
+        let google = event.to_google();
+
+        let conference = google.conference_data.expect("conference data");
+        assert_eq!(conference.conference_id, "abc-defg-hij");
+    }
+
     #[test]
     fn event_without_conference_x_property_has_no_conference_data() {
         assert!(sample_event().to_google().conference_data.is_none());
@@ -1,15 +1,19 @@
 //! Google Calendar provider for caldir.
 
+mod api_error;
 mod app_config;
 mod commands;
 mod constants;
 mod google_event;
 mod remote_config;
+mod service_account;
 mod session;
 
 use async_trait::async_trait;
 use caldir_core::rpc::{
-    Connect, ConnectResponse, CreateEvent, DeleteEvent, ListCalendars, ListEvents, UpdateEvent,
+    AccountStatus, AuthStyle, BatchWrite, BusySchedule, Capabilities, CheckAccount, Connect,
+    ConnectResponse, CreateEvent, DeleteEvent, Describe, Disconnect, FreeBusy, ListCalendars,
+    ListEvents, ProviderDescription, Response, UpdateEvent,
 };
 use caldir_core::{CalendarConfig, Event, provider};
 
@@ -18,27 +22,88 @@ struct GoogleProvider;
 #[async_trait]
 impl provider::Handler for GoogleProvider {
     async fn connect(&self, cmd: Connect) -> provider::Result<ConnectResponse> {
-        Ok(commands::connect::handle(cmd).await?)
+        commands::connect::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
+    }
+
+    async fn describe(&self, _cmd: Describe) -> provider::Result<ProviderDescription> {
+        Ok(ProviderDescription::new(
+            AuthStyle::OAuth,
+            Capabilities {
+                urls: false,
+                default_reminders: true,
+                free_busy: true,
+                floating_times: false,
+                cancel_occurrences: true,
+                ..Capabilities::default()
+            },
+        )
+        .with_version(env!("CARGO_PKG_VERSION")))
     }
 
     async fn list_calendars(&self, cmd: ListCalendars) -> provider::Result<Vec<CalendarConfig>> {
-        Ok(commands::list_calendars::handle(cmd).await?)
+        commands::list_calendars::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
     }
 
     async fn list_events(&self, cmd: ListEvents) -> provider::Result<Vec<Event>> {
-        Ok(commands::list_events::handle(cmd).await?)
+        commands::list_events::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
+    }
+
+    async fn stream_events(
+        &self,
+        cmd: ListEvents,
+        sink: &provider::EventSink,
+    ) -> provider::Result<()> {
+        commands::list_events::stream(cmd, sink)
+            .await
+            .map_err(api_error::into_provider_error)
     }
 
     async fn create_event(&self, cmd: CreateEvent) -> provider::Result<Event> {
-        Ok(commands::create_event::handle(cmd).await?)
+        commands::create_event::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
     }
 
     async fn update_event(&self, cmd: UpdateEvent) -> provider::Result<Event> {
-        Ok(commands::update_event::handle(cmd).await?)
+        commands::update_event::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
     }
 
     async fn delete_event(&self, cmd: DeleteEvent) -> provider::Result<()> {
-        Ok(commands::delete_event::handle(cmd).await?)
+        commands::delete_event::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
+    }
+
+    async fn free_busy(&self, cmd: FreeBusy) -> provider::Result<Vec<BusySchedule>> {
+        commands::free_busy::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
+    }
+
+    async fn batch_write(&self, cmd: BatchWrite) -> provider::Result<Vec<Response<Event>>> {
+        commands::batch_write::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
+    }
+
+    async fn check_account(&self, cmd: CheckAccount) -> provider::Result<AccountStatus> {
+        commands::check_account::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
+    }
+
+    async fn disconnect(&self, cmd: Disconnect) -> provider::Result<()> {
+        commands::disconnect::handle(cmd)
+            .await
+            .map_err(api_error::into_provider_error)
     }
 }
 
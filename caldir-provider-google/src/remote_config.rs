@@ -7,11 +7,33 @@ use anyhow::Result;
 use caldir_core::RemoteConfigParams;
 use serde::{Deserialize, Serialize};
 
+const DEFAULT_PAGE_SIZE: u32 = 250;
+const MAX_PAGE_SIZE: u32 = 2500;
+
+/// How the provider authenticates for a calendar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoogleAuth {
+    /// Interactive OAuth session from `caldir connect google`.
+    #[default]
+    Oauth,
+    /// Service account key with domain-wide delegation.
+    ServiceAccount,
+}
+
 /// Strongly-typed remote configuration for Google Calendar.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoogleRemoteConfig {
     pub google_account: String,
     pub google_calendar_id: String,
+    #[serde(default)]
+    pub google_auth: GoogleAuth,
+    /// User the service account acts as. Defaults to `google_account`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonate: Option<String>,
+    /// Events fetched per API request when listing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub google_page_size: Option<u32>,
 }
 
 impl GoogleRemoteConfig {
@@ -19,9 +41,24 @@ impl GoogleRemoteConfig {
         Self {
             google_account: account.into(),
             google_calendar_id: calendar_id.into(),
+            google_auth: GoogleAuth::default(),
+            impersonate: None,
+            google_page_size: None,
         }
     }
 
+    /// `google_page_size`, clamped to what the Calendar API accepts.
+    pub fn page_size(&self) -> u32 {
+        self.google_page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+
+    /// The user whose calendar data a service account acts on.
+    pub fn impersonated_user(&self) -> &str {
+        self.impersonate.as_deref().unwrap_or(&self.google_account)
+    }
+
     pub fn into_remote_config_params(self) -> RemoteConfigParams {
         let mut params = RemoteConfigParams::new();
         params.insert(
@@ -32,6 +69,21 @@ impl GoogleRemoteConfig {
             "google_calendar_id".to_string(),
             toml::Value::String(self.google_calendar_id),
         );
+        if self.google_auth == GoogleAuth::ServiceAccount {
+            params.insert(
+                "google_auth".to_string(),
+                toml::Value::String("service_account".to_string()),
+            );
+        }
+        if let Some(impersonate) = self.impersonate {
+            params.insert("impersonate".to_string(), toml::Value::String(impersonate));
+        }
+        if let Some(page_size) = self.google_page_size {
+            params.insert(
+                "google_page_size".to_string(),
+                toml::Value::Integer(page_size.into()),
+            );
+        }
         params
     }
 }
@@ -52,9 +104,94 @@ impl TryFrom<&RemoteConfigParams> for GoogleRemoteConfig {
             .ok_or_else(|| anyhow::anyhow!("Missing required field: google_calendar_id"))?
             .to_string();
 
+        let google_auth = match params.get("google_auth").and_then(|v| v.as_str()) {
+            None | Some("oauth") => GoogleAuth::Oauth,
+            Some("service_account") => GoogleAuth::ServiceAccount,
+            Some(other) => anyhow::bail!(
+                "Unknown google_auth \"{other}\" (expected \"oauth\" or \"service_account\")"
+            ),
+        };
+
+        let impersonate = params
+            .get("impersonate")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        let google_page_size = params
+            .get("google_page_size")
+            .and_then(|v| v.as_integer())
+            .map(|n| u32::try_from(n.max(1)).unwrap_or(MAX_PAGE_SIZE));
+
         Ok(Self {
             google_account,
             google_calendar_id,
+            google_auth,
+            impersonate,
+            google_page_size,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(toml_str: &str) -> RemoteConfigParams {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn defaults_to_oauth() {
+        let config = GoogleRemoteConfig::try_from(&params(
+            r#"
+            google_account = "me@gmail.com"
+            google_calendar_id = "primary"
+            "#,
+        ))
+        .unwrap();
+
+        assert_eq!(config.google_auth, GoogleAuth::Oauth);
+        assert_eq!(config.impersonated_user(), "me@gmail.com");
+    }
+
+    #[test]
+    fn parses_service_account_with_impersonation() {
+        let config = GoogleRemoteConfig::try_from(&params(
+            r#"
+            google_account = "room@corp.com"
+            google_calendar_id = "room@corp.com"
+            google_auth = "service_account"
+            impersonate = "admin@corp.com"
+            "#,
+        ))
+        .unwrap();
+
+        assert_eq!(config.google_auth, GoogleAuth::ServiceAccount);
+        assert_eq!(config.impersonated_user(), "admin@corp.com");
+
+        let round_trip = GoogleRemoteConfig::try_from(&config.into_remote_config_params()).unwrap();
+        assert_eq!(round_trip.google_auth, GoogleAuth::ServiceAccount);
+    }
+
+    #[test]
+    fn page_size_defaults_and_clamps() {
+        let mut config = GoogleRemoteConfig::new("me@gmail.com", "primary");
+        assert_eq!(config.page_size(), 250);
+
+        config.google_page_size = Some(10_000);
+        assert_eq!(config.page_size(), 2500);
+    }
+
+    #[test]
+    fn rejects_unknown_auth() {
+        let result = GoogleRemoteConfig::try_from(&params(
+            r#"
+            google_account = "me@gmail.com"
+            google_calendar_id = "primary"
+            google_auth = "password"
+            "#,
+        ));
+
+        assert!(result.is_err());
+    }
+}
@@ -1,13 +1,17 @@
 //! Filesystem-backed storage for [`Session`] credentials + OAuth refresh.
 
 use anyhow::{Context, Result};
-use caldir_core::provider::ProviderStorage;
+use caldir_core::provider::{CodedError, KeyringToken, ProviderStorage, TokenStorage};
+use caldir_core::rpc::ErrorCode;
 use chrono::{Duration, Utc};
 use google_calendar::Client;
 use serde::Deserialize;
 use std::path::PathBuf;
 
 use crate::app_config::AppConfigStore;
+use crate::constants::PROVIDER_NAME;
+use crate::remote_config::{GoogleAuth, GoogleRemoteConfig};
+use crate::service_account::ServiceAccountKey;
 
 use super::types::{AuthMode, Session, SessionData};
 
@@ -17,18 +21,64 @@ const HOSTED_REFRESH_URL: &str = "https://caldir.org/auth/google/refresh";
 ///
 /// Layout: `{storage.root()}/session/{slug}.toml`, slug forward-deterministic
 /// from the account email. Files contain OAuth tokens; on Unix they're
-/// chmod'd to `0600`.
+/// chmod'd to `0600`. With `token_storage = "keyring"` the same TOML is kept
+/// in the OS keychain instead, keyed by provider + account email.
 pub struct SessionStore {
     storage: ProviderStorage,
+    token_storage: TokenStorage,
 }
 
 impl SessionStore {
     pub fn new(storage: ProviderStorage) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            token_storage: TokenStorage::from_env(),
+        }
     }
 
     pub fn save(&self, session: &Session) -> Result<()> {
-        let path = self.path_for(&session.account_email);
+        let contents =
+            toml::to_string_pretty(&session.data).context("Failed to serialize session")?;
+
+        match self.token_storage {
+            TokenStorage::File => self.save_file(&session.account_email, &contents),
+            TokenStorage::Keyring => {
+                KeyringToken::new(PROVIDER_NAME, &session.account_email)
+                    .and_then(|token| token.save(&contents))
+                    .context("Failed to save session to the OS keychain")?;
+
+                // Now in the keychain — don't leave a plaintext copy behind.
+                let path = self.path_for(&session.account_email);
+                if path.exists() {
+                    std::fs::remove_file(&path)
+                        .with_context(|| format!("Failed to remove {}", path.display()))?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Remove the account's session from the OS keychain and disk.
+    /// Succeeds if there was none.
+    pub fn delete(&self, account_email: &str) -> Result<()> {
+        if self.token_storage == TokenStorage::Keyring {
+            KeyringToken::new(PROVIDER_NAME, account_email)
+                .and_then(|token| token.delete())
+                .context("Failed to remove session from the OS keychain")?;
+        }
+
+        let path = self.path_for(account_email);
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    fn save_file(&self, account_email: &str, contents: &str) -> Result<()> {
+        let path = self.path_for(account_email);
 
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).with_context(|| {
@@ -36,20 +86,23 @@ impl SessionStore {
             })?;
         }
 
-        let contents =
-            toml::to_string_pretty(&session.data).context("Failed to serialize session")?;
+        // Write-then-rename so a concurrent reader never sees a half-written file.
+        let tmp_path = path.with_extension("toml.tmp");
 
-        std::fs::write(&path, contents)
-            .with_context(|| format!("Failed to write session to {}", path.display()))?;
+        std::fs::write(&tmp_path, contents)
+            .with_context(|| format!("Failed to write session to {}", tmp_path.display()))?;
 
         // Plaintext OAuth tokens — owner-only.
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
-                .with_context(|| format!("Failed to set permissions on {}", path.display()))?;
+            std::fs::set_permissions(&tmp_path, std::fs::Permissions::from_mode(0o600))
+                .with_context(|| format!("Failed to set permissions on {}", tmp_path.display()))?;
         }
 
+        std::fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to write session to {}", path.display()))?;
+
         Ok(())
     }
 
@@ -58,6 +111,21 @@ impl SessionStore {
     /// The slug is forward-deterministic from the email, so we compute the
     /// path directly rather than scanning the directory.
     pub fn load(&self, account_email: &str) -> Result<Session> {
+        if self.token_storage == TokenStorage::Keyring
+            && let Some(contents) = KeyringToken::new(PROVIDER_NAME, account_email)
+                .and_then(|token| token.load())
+                .context("Failed to read session from the OS keychain")?
+        {
+            let data: SessionData = toml::from_str(&contents)
+                .context("Failed to parse Google OAuth session from the OS keychain")?;
+
+            return Ok(Session {
+                account_email: account_email.to_string(),
+                data,
+            });
+        }
+
+        // File storage, or a session saved before switching to the keychain
         let path = self.path_for(account_email);
 
         if !path.exists() {
@@ -91,6 +159,20 @@ impl SessionStore {
         account_email: &str,
         app_config_store: &AppConfigStore,
     ) -> Result<Session> {
+        let session = self.load(account_email)?;
+
+        if !session.is_expired() {
+            return Ok(session);
+        }
+
+        // Concurrent caldir runs (cron + manual) must not refresh the same
+        // token at once: one would end up holding a revoked refresh token.
+        // Re-read under the lock, another run may have refreshed meanwhile.
+        let _lock = self
+            .storage
+            .lock(&self.lock_name(account_email))
+            .context("Failed to lock session for refresh")?;
+
         let mut session = self.load(account_email)?;
 
         if session.is_expired() {
@@ -100,11 +182,32 @@ impl SessionStore {
         Ok(session)
     }
 
+    /// Session for a calendar's remote: the stored OAuth session for its
+    /// account, or a fresh service-account token when `google_auth = "service_account"`.
+    pub async fn load_for_remote(
+        &self,
+        config: &GoogleRemoteConfig,
+        app_config_store: &AppConfigStore,
+    ) -> Result<Session> {
+        match config.google_auth {
+            GoogleAuth::Oauth => {
+                self.load_valid(&config.google_account, app_config_store)
+                    .await
+            }
+            GoogleAuth::ServiceAccount => {
+                ServiceAccountKey::load(&self.storage)?
+                    .session(&config.google_account, config.impersonated_user())
+                    .await
+            }
+        }
+    }
+
     /// Build a `google_calendar::Client` for an existing session.
-    /// `AuthMode::Hosted` doesn't need app_config_store but takes it for symmetry.
+    /// `AuthMode::Hosted` and `AuthMode::ServiceAccount` don't need app_config_store
+    /// but take it for symmetry.
     pub fn client(&self, session: &Session, app_config_store: &AppConfigStore) -> Result<Client> {
         match session.auth_mode() {
-            AuthMode::Hosted => Ok(Client::new(
+            AuthMode::Hosted | AuthMode::ServiceAccount => Ok(Client::new(
                 String::new(),
                 String::new(),
                 String::new(),
@@ -132,6 +235,9 @@ impl SessionStore {
         match session.auth_mode() {
             AuthMode::Hosted => self.refresh_hosted(session).await,
             AuthMode::Local => self.refresh_local(session, app_config_store).await,
+            AuthMode::ServiceAccount => {
+                anyhow::bail!("Service account sessions are minted per call, not refreshed")
+            }
         }
     }
 
@@ -148,8 +254,15 @@ impl SessionStore {
             .context("Failed to send refresh request to caldir.org")?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to refresh token via caldir.org: {}", error_text);
+            let message = format!("Failed to refresh token via caldir.org: {error_text}");
+
+            // The refresh token was revoked or expired: only reconnecting helps
+            if status == reqwest::StatusCode::UNAUTHORIZED || error_text.contains("invalid_grant") {
+                return Err(CodedError::new(ErrorCode::AuthExpired, message).into());
+            }
+            anyhow::bail!(message);
         }
 
         #[derive(Deserialize)]
@@ -211,6 +324,11 @@ impl SessionStore {
         self.session_dir()
             .join(format!("{}.toml", Session::slug(account_email)))
     }
+
+    /// Lock file next to the session, relative to the storage root.
+    fn lock_name(&self, account_email: &str) -> String {
+        format!("session/{}.lock", Session::slug(account_email))
+    }
 }
 
 #[cfg(test)]
@@ -254,6 +372,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn save_overwrites_without_leaving_temp_file() {
+        let (tmp, store) = store();
+        let mut session = sample_session();
+        store.save(&session).unwrap();
+
+        session.data.access_token = "access-new".to_string();
+        store.save(&session).unwrap();
+
+        let entries: Vec<_> = std::fs::read_dir(tmp.path().join("session"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(entries.len(), 1, "unexpected files: {entries:?}");
+
+        let loaded = store.load(&session.account_email).unwrap();
+        assert_eq!(loaded.data.access_token, "access-new");
+    }
+
     #[test]
     fn load_round_trips_by_account_email() {
         let (_tmp, store) = store();
@@ -275,6 +412,18 @@ mod tests {
         assert!(err.to_string().contains("ghost@gmail.com"));
     }
 
+    #[test]
+    fn delete_removes_the_session_and_tolerates_none() {
+        let (_tmp, store) = store();
+        let session = sample_session();
+        store.save(&session).unwrap();
+
+        store.delete(&session.account_email).unwrap();
+
+        assert!(store.load(&session.account_email).is_err());
+        store.delete(&session.account_email).unwrap();
+    }
+
     #[test]
     fn slug_preserves_pre_migration_email_layout() {
         // Google's slug replaces only / \ : — NOT @ or . — and existing
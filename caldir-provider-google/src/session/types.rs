@@ -4,13 +4,15 @@ use chrono::{DateTime, Duration, Utc};
 use google_calendar::AccessToken;
 use serde::{Deserialize, Serialize};
 
-/// Whether this session was created via hosted (caldir.org) or local (self-hosted) OAuth.
+/// Whether this session was created via hosted (caldir.org) or local (self-hosted) OAuth,
+/// or minted on the fly from a service account key (never stored).
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum AuthMode {
     #[default]
     Local,
     Hosted,
+    ServiceAccount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
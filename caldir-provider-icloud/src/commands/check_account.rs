@@ -0,0 +1,17 @@
+use anyhow::Result;
+use caldir_core::provider::ProviderStorage;
+use caldir_core::rpc::{AccountStatus, CheckAccount};
+
+use crate::constants::PROVIDER_NAME;
+use crate::session::SessionStore;
+
+/// Signed in while credentials are stored. They don't expire, but a revoked
+/// password only shows up on the next request.
+pub async fn handle(cmd: CheckAccount) -> Result<AccountStatus> {
+    let store = SessionStore::new(ProviderStorage::for_provider(PROVIDER_NAME)?);
+
+    Ok(AccountStatus {
+        signed_in: store.load(&cmd.account_identifier).is_ok(),
+        ..AccountStatus::default()
+    })
+}
@@ -7,7 +7,8 @@ mod session;
 
 use async_trait::async_trait;
 use caldir_core::rpc::{
-    Connect, ConnectResponse, CreateEvent, DeleteEvent, ListCalendars, ListEvents, UpdateEvent,
+    AccountStatus, AuthStyle, Capabilities, CheckAccount, Connect, ConnectResponse, CreateEvent,
+    DeleteEvent, Describe, Disconnect, ListCalendars, ListEvents, ProviderDescription, UpdateEvent,
 };
 use caldir_core::{CalendarConfig, Event, provider};
 
@@ -19,6 +20,13 @@ impl provider::Handler for ICloudProvider {
         Ok(commands::connect::handle(cmd).await?)
     }
 
+    async fn describe(&self, _cmd: Describe) -> provider::Result<ProviderDescription> {
+        Ok(
+            ProviderDescription::new(AuthStyle::Credentials, Capabilities::default())
+                .with_version(env!("CARGO_PKG_VERSION")),
+        )
+    }
+
     async fn list_calendars(&self, cmd: ListCalendars) -> provider::Result<Vec<CalendarConfig>> {
         Ok(commands::list_calendars::handle(cmd).await?)
     }
@@ -38,6 +46,14 @@ impl provider::Handler for ICloudProvider {
     async fn delete_event(&self, cmd: DeleteEvent) -> provider::Result<()> {
         Ok(commands::delete_event::handle(cmd).await?)
     }
+
+    async fn check_account(&self, cmd: CheckAccount) -> provider::Result<AccountStatus> {
+        Ok(commands::check_account::handle(cmd).await?)
+    }
+
+    async fn disconnect(&self, cmd: Disconnect) -> provider::Result<()> {
+        Ok(commands::disconnect::handle(cmd).await?)
+    }
 }
 
 #[tokio::main]
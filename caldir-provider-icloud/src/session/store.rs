@@ -65,6 +65,16 @@ impl SessionStore {
         Ok(session)
     }
 
+    /// Remove the account's session file. Succeeds if there was none.
+    pub fn delete(&self, account_identifier: &str) -> Result<()> {
+        let path = self.path_for(account_identifier);
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+
     fn session_dir(&self) -> PathBuf {
         self.storage.root().join("session")
     }
@@ -133,6 +143,18 @@ mod tests {
         assert!(err.to_string().contains("ghost@icloud.com"));
     }
 
+    #[test]
+    fn delete_removes_the_session_and_tolerates_none() {
+        let (_tmp, store) = store();
+        let session = sample_session();
+        store.save(&session).unwrap();
+
+        store.delete(&session.apple_id).unwrap();
+
+        assert!(store.load(&session.apple_id).is_err());
+        store.delete(&session.apple_id).unwrap();
+    }
+
     #[cfg(unix)]
     #[test]
     fn save_chmods_session_file_to_0600() {
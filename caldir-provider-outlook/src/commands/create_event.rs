@@ -193,6 +193,7 @@ mod tests {
             is_reminder_on: false,
             show_as: String::new(),
             sensitivity: String::new(),
+            categories: Vec::new(),
             last_modified_date_time: None,
             online_meeting: None,
             original_start: original_start.map(|s| s.to_string()),
@@ -0,0 +1,14 @@
+use anyhow::Result;
+use caldir_core::provider::ProviderStorage;
+use caldir_core::rpc::Disconnect;
+
+use crate::constants::PROVIDER_NAME;
+use crate::session::SessionStore;
+
+/// Remove the account's stored session. Microsoft has no endpoint to revoke
+/// a single refresh token, so caldir's access stays listed under the
+/// account's app permissions until removed there.
+pub async fn handle(cmd: Disconnect) -> Result<()> {
+    let storage = ProviderStorage::for_provider(PROVIDER_NAME)?;
+    SessionStore::new(storage).delete(&cmd.account_identifier)
+}
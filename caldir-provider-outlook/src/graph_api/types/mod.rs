@@ -88,6 +88,10 @@ pub struct GraphEvent {
     /// PRIVATE since CLASS has no equivalent middle ground.
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub sensitivity: String,
+    /// Outlook category names. Always serialized so clearing every tag
+    /// locally also clears them on PATCH.
+    #[serde(default)]
+    pub categories: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_modified_date_time: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -10,7 +10,8 @@ mod session;
 
 use async_trait::async_trait;
 use caldir_core::rpc::{
-    Connect, ConnectResponse, CreateEvent, DeleteEvent, ListCalendars, ListEvents, UpdateEvent,
+    AccountStatus, AuthStyle, Capabilities, CheckAccount, Connect, ConnectResponse, CreateEvent,
+    DeleteEvent, Describe, Disconnect, ListCalendars, ListEvents, ProviderDescription, UpdateEvent,
 };
 use caldir_core::{CalendarConfig, Event, provider};
 
@@ -22,6 +23,19 @@ impl provider::Handler for OutlookProvider {
         Ok(commands::connect::handle(cmd).await?)
     }
 
+    async fn describe(&self, _cmd: Describe) -> provider::Result<ProviderDescription> {
+        Ok(ProviderDescription::new(
+            AuthStyle::OAuth,
+            Capabilities {
+                urls: false,
+                floating_times: false,
+                cancel_occurrences: true,
+                ..Capabilities::default()
+            },
+        )
+        .with_version(env!("CARGO_PKG_VERSION")))
+    }
+
     async fn list_calendars(&self, cmd: ListCalendars) -> provider::Result<Vec<CalendarConfig>> {
         Ok(commands::list_calendars::handle(cmd).await?)
     }
@@ -41,6 +55,14 @@ impl provider::Handler for OutlookProvider {
     async fn delete_event(&self, cmd: DeleteEvent) -> provider::Result<()> {
         Ok(commands::delete_event::handle(cmd).await?)
     }
+
+    async fn check_account(&self, cmd: CheckAccount) -> provider::Result<AccountStatus> {
+        Ok(commands::check_account::handle(cmd).await?)
+    }
+
+    async fn disconnect(&self, cmd: Disconnect) -> provider::Result<()> {
+        Ok(commands::disconnect::handle(cmd).await?)
+    }
 }
 
 #[tokio::main]
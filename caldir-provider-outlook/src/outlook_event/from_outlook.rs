@@ -2,8 +2,8 @@
 
 use anyhow::Result;
 use caldir_core::{
-    Attendee, Availability, Event, EventTime, EventUid, Organizer, ParticipationStatus, Recurrence,
-    RecurrenceId, Reminder, Status, Visibility, XProperty, tz_normalize,
+    Attendee, Availability, Conference, Event, EventTime, EventUid, Organizer, ParticipationStatus,
+    Recurrence, RecurrenceId, Reminder, Status, Visibility, XProperty, tz_normalize,
 };
 use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use chrono_tz::Tz;
@@ -157,6 +157,15 @@ pub fn from_outlook(event: GraphEvent, account_email: &str) -> Result<Event> {
         .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
         .map(|dt| dt.with_timezone(&Utc));
 
+    let conferences = conference_url
+        .iter()
+        .map(|url| {
+            Conference::new(url)
+                .with_feature("AUDIO")
+                .with_feature("VIDEO")
+        })
+        .collect();
+
     let mut x_properties = vec![XProperty::new(PROVIDER_EVENT_ID_PROPERTY, event.id)];
     if let Some(url) = conference_url {
         x_properties.push(XProperty::new(PROVIDER_CONFERENCE_PROPERTY, url));
@@ -190,12 +199,19 @@ pub fn from_outlook(event: GraphEvent, account_email: &str) -> Result<Event> {
         status,
         availability,
         visibility,
+        event_type: Default::default(),
         recurrence,
         recurrence_id,
         organizer,
         attendees,
         reminders,
+        // Outlook has no calendar default reminders.
+        reminders_default: false,
         url: None,
+        categories: event.categories,
+        // Outlook colors categories, not events.
+        color: None,
+        conferences,
         attachments: Vec::new(),
         x_properties,
         last_modified,
@@ -478,6 +494,7 @@ mod tests {
             is_reminder_on: false,
             show_as: "busy".to_string(),
             sensitivity: String::new(),
+            categories: Vec::new(),
             last_modified_date_time: None,
             online_meeting: None,
             original_start: None,
@@ -562,6 +579,33 @@ mod tests {
         assert_eq!(event.reminders[0].minutes_before_start, 30);
     }
 
+    #[test]
+    fn categories_map_to_event_categories() {
+        let mut ge = minimal_graph_event();
+        ge.categories = vec!["Red category".into(), "Travel".into()];
+
+        let event = from_outlook(ge, "me@example.com").unwrap();
+
+        assert_eq!(event.categories, vec!["Red category", "Travel"]);
+    }
+
+    #[test]
+    fn online_meeting_maps_to_conference_not_url() {
+        let mut ge = minimal_graph_event();
+        ge.online_meeting = Some(OnlineMeeting {
+            join_url: Some("https://teams.microsoft.com/l/meetup-join/abc".into()),
+        });
+
+        let event = from_outlook(ge, "me@example.com").unwrap();
+
+        assert_eq!(event.url, None);
+        assert_eq!(
+            event.conference_url(),
+            Some("https://teams.microsoft.com/l/meetup-join/abc")
+        );
+        assert!(event.conferences[0].has_feature("VIDEO"));
+    }
+
     #[test]
     fn private_sensitivity_maps_to_private() {
         let mut ge = minimal_graph_event();
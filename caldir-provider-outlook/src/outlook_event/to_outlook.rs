@@ -80,6 +80,7 @@ pub fn to_outlook(event: &Event) -> GraphEvent {
         is_reminder_on,
         show_as,
         sensitivity,
+        categories: event.categories.clone(),
         last_modified_date_time: None,
         online_meeting: None,
         original_start: None,
@@ -406,12 +407,17 @@ mod tests {
             status: Status::Confirmed,
             availability: Availability::Busy,
             visibility: Default::default(),
+            event_type: Default::default(),
             recurrence: None,
             recurrence_id: None,
             organizer: None,
             attendees: vec![],
             reminders: vec![],
+            reminders_default: false,
             url: None,
+            categories: vec![],
+            color: None,
+            conferences: vec![],
             attachments: vec![],
             x_properties: vec![],
             last_modified: None,
@@ -449,6 +455,14 @@ mod tests {
         e
     }
 
+    #[test]
+    fn categories_are_sent_as_outlook_categories() {
+        let mut e = html_event();
+        e.categories = vec!["Travel".to_string()];
+
+        assert_eq!(to_outlook(&e).categories, vec!["Travel"]);
+    }
+
     #[test]
     fn untouched_html_event_pushes_html_body() {
         // Pulled an Outlook HTML event, didn't edit it locally — push must
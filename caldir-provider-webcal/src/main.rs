@@ -8,7 +8,9 @@ mod http;
 mod remote_config;
 
 use async_trait::async_trait;
-use caldir_core::rpc::{Connect, ConnectResponse, ListEvents};
+use caldir_core::rpc::{
+    AuthStyle, Capabilities, Connect, ConnectResponse, Describe, ListEvents, ProviderDescription,
+};
 use caldir_core::{Event, provider};
 
 struct WebcalProvider;
@@ -19,6 +21,13 @@ impl provider::Handler for WebcalProvider {
         Ok(commands::connect::handle(cmd).await?)
     }
 
+    async fn describe(&self, _cmd: Describe) -> provider::Result<ProviderDescription> {
+        Ok(
+            ProviderDescription::new(AuthStyle::None, Capabilities::read_only())
+                .with_version(env!("CARGO_PKG_VERSION")),
+        )
+    }
+
     async fn list_events(&self, cmd: ListEvents) -> provider::Result<Vec<Event>> {
         Ok(commands::list_events::handle(cmd).await?)
     }